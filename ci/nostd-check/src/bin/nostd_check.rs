@@ -11,15 +11,36 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-
 #![no_std]
 
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
 use core::panic::PanicInfo;
 
 use getrandom::{register_custom_getrandom, Error};
 use linked_list_allocator::LockedHeap;
-#[allow(unused_imports)]
-use {zenoh_buffers, zenoh_codec, zenoh_protocol};
+use zenoh_buffers::{
+    reader::{HasReader, Reader},
+    writer::HasWriter,
+    ZBuf,
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_protocol::{
+    core::{
+        Encoding, EntityGlobalIdProto, Locator, Reliability, Timestamp, WhatAmI, WireExpr,
+        ZenohIdProto, NTP64,
+    },
+    network::{
+        declare::{self, DeclareBody, DeclareSubscriber},
+        interest::{InterestMode, InterestOptions},
+        push, request, response, Declare, Interest, NetworkMessage, Push, Request, Response,
+        ResponseFinal,
+    },
+    scouting::{HelloProto, Scout, ScoutingMessage},
+    transport::{frame, Close, Frame, KeepAlive, TransportMessage},
+    zenoh::{put, query, ConsolidationMode, Del, Err, Put, Query, Reply},
+};
 
 #[panic_handler]
 fn dummy_panic_handler(_: &PanicInfo) -> ! {
@@ -33,6 +54,191 @@ fn dummy_get_rand(_: &mut [u8]) -> Result<(), Error> {
     Ok(())
 }
 
+/// Encodes and decodes a message, checking that it is unchanged.
+macro_rules! roundtrip {
+    ($type:ty, $msg:expr) => {{
+        let msg: $type = $msg;
+        let codec = Zenoh080::new();
+        let mut buffer = vec![];
+        let mut writer = buffer.writer();
+        codec.write(&mut writer, &msg).unwrap();
+        let mut reader = buffer.reader();
+        let decoded: $type = codec.read(&mut reader).unwrap();
+        assert!(decoded == msg && !reader.can_read());
+    }};
+}
+
+fn network_messages() -> Vec<NetworkMessage> {
+    let zid = ZenohIdProto::try_from([1u8, 2, 3, 4]).unwrap();
+    let timestamp = Timestamp::new(NTP64(42), zid.into());
+    let sinfo = put::ext::SourceInfoType {
+        id: EntityGlobalIdProto { zid, eid: 7 },
+        sn: 42,
+    };
+    let payload = ZBuf::from(vec![0u8; 64]);
+    let put = Put {
+        timestamp: Some(timestamp),
+        encoding: Encoding::empty(),
+        ext_sinfo: Some(sinfo.clone()),
+        ext_attachment: Some(put::ext::AttachmentType {
+            buffer: payload.clone(),
+        }),
+        ext_trace: None,
+        ext_signature: None,
+        ext_unknown: vec![],
+        payload: payload.clone(),
+    };
+    let push = |payload| Push {
+        wire_expr: WireExpr::from(String::from("demo/example")),
+        ext_qos: push::ext::QoSType::PUSH,
+        ext_tstamp: Some(push::ext::TimestampType { timestamp }),
+        ext_nodeid: push::ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
+        payload,
+    };
+    vec![
+        push(put.clone().into()).into(),
+        push(
+            Del {
+                timestamp: Some(timestamp),
+                ext_sinfo: Some(sinfo),
+                ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
+                ext_unknown: vec![],
+            }
+            .into(),
+        )
+        .into(),
+        Request {
+            id: 1,
+            wire_expr: WireExpr::from(String::from("demo/**")),
+            ext_qos: request::ext::QoSType::REQUEST,
+            ext_tstamp: None,
+            ext_nodeid: request::ext::NodeIdType::DEFAULT,
+            ext_target: request::ext::QueryTarget::All,
+            ext_budget: None,
+            ext_timeout: None,
+            payload: Query {
+                consolidation: ConsolidationMode::None,
+                parameters: String::from("a=1;b=2"),
+                ext_sinfo: None,
+                ext_body: Some(query::ext::QueryBodyType {
+                    encoding: Encoding::empty(),
+                    payload: payload.clone(),
+                }),
+                ext_attachment: None,
+                ext_trace: None,
+                ext_unknown: vec![],
+            }
+            .into(),
+        }
+        .into(),
+        Response {
+            rid: 1,
+            wire_expr: WireExpr::from(String::from("demo/example")),
+            payload: Reply {
+                consolidation: ConsolidationMode::None,
+                ext_unknown: vec![],
+                payload: put.into(),
+            }
+            .into(),
+            ext_qos: response::ext::QoSType::RESPONSE,
+            ext_tstamp: None,
+            ext_respid: None,
+        }
+        .into(),
+        Response {
+            rid: 2,
+            wire_expr: WireExpr::from(String::from("demo/example")),
+            payload: Err {
+                encoding: Encoding::empty(),
+                ext_sinfo: None,
+                ext_unknown: vec![],
+                payload,
+            }
+            .into(),
+            ext_qos: response::ext::QoSType::RESPONSE,
+            ext_tstamp: None,
+            ext_respid: None,
+        }
+        .into(),
+        ResponseFinal {
+            rid: 1,
+            ext_qos: response::ext::QoSType::RESPONSE_FINAL,
+            ext_tstamp: None,
+        }
+        .into(),
+        Declare {
+            interest_id: Some(3),
+            ext_qos: declare::ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+            body: DeclareBody::DeclareSubscriber(DeclareSubscriber {
+                id: 4,
+                wire_expr: WireExpr::from(String::from("demo/**")),
+            }),
+        }
+        .into(),
+        NetworkMessage::from(zenoh_protocol::network::NetworkBody::Interest(Interest {
+            id: 3,
+            mode: InterestMode::CurrentFuture,
+            options: InterestOptions::ALL,
+            wire_expr: Some(WireExpr::from(String::from("demo/**"))),
+            ext_qos: declare::ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+        })),
+    ]
+}
+
 fn main() {
     register_custom_getrandom!(dummy_get_rand);
+
+    // The whole message set is encoded and decoded without std
+    let messages = network_messages();
+    for msg in messages.iter() {
+        roundtrip!(NetworkMessage, msg.clone());
+    }
+    roundtrip!(
+        TransportMessage,
+        Frame {
+            reliability: Reliability::Reliable,
+            sn: 1,
+            ext_qos: frame::ext::QoSType::DEFAULT,
+            payload: messages,
+        }
+        .into()
+    );
+    roundtrip!(
+        TransportMessage,
+        Close {
+            reason: 0,
+            session: true,
+        }
+        .into()
+    );
+    roundtrip!(TransportMessage, KeepAlive.into());
+    let zid = ZenohIdProto::try_from([1u8, 2, 3, 4]).unwrap();
+    roundtrip!(
+        ScoutingMessage,
+        Scout {
+            version: 9,
+            what: WhatAmI::Router.into(),
+            zid: Some(zid),
+        }
+        .into()
+    );
+    roundtrip!(
+        ScoutingMessage,
+        HelloProto {
+            version: 9,
+            whatami: WhatAmI::Peer,
+            zid,
+            locators: vec!["tcp/127.0.0.1:7447".parse::<Locator>().unwrap()],
+        }
+        .into()
+    );
 }
@@ -354,6 +354,32 @@ impl io::Read for ZBufReader<'_> {
     }
 }
 
+#[cfg(feature = "std")]
+impl io::BufRead for ZBufReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Skip the consumed and the empty slices, so that an empty buffer means the end
+        while let Some(slice) = self.inner.slices.get(self.cursor.slice) {
+            if self.cursor.byte < slice.len() {
+                return Ok(&slice.as_slice()[self.cursor.byte..]);
+            }
+            self.cursor.slice += 1;
+            self.cursor.byte = 0;
+        }
+        Ok(&[])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes given by `fill_buf` are all in the current slice
+        if let Some(slice) = self.inner.slices.get(self.cursor.slice) {
+            self.cursor.byte = (self.cursor.byte + amt).min(slice.len());
+            if self.cursor.byte == slice.len() {
+                self.cursor.slice += 1;
+                self.cursor.byte = 0;
+            }
+        }
+    }
+}
+
 impl AdvanceableReader for ZBufReader<'_> {
     fn skip(&mut self, offset: usize) -> Result<(), DidntRead> {
         let mut remaining_offset = offset;
@@ -595,6 +621,18 @@ impl ZBuf {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ZBuf {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // The buffer may be made of several slices, as when it is received in fragments
+        let mut zbuf = ZBuf::empty();
+        for slice in u.arbitrary_iter::<ZSlice>()? {
+            zbuf.push_zslice(slice?);
+        }
+        Ok(zbuf)
+    }
+}
+
 mod tests {
     #[test]
     fn zbuf_eq() {
@@ -668,4 +706,39 @@ mod tests {
         assert_eq!(reader.seek(std::io::SeekFrom::Start(10)).unwrap(), 10);
         reader.seek(std::io::SeekFrom::Current(-100)).unwrap_err();
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn zbuf_buf_read() {
+        use std::io::{BufRead, Read};
+
+        use super::{HasReader, ZBuf};
+
+        let mut buf = ZBuf::empty();
+        buf.push_zslice(b"first\nsec".to_vec().into());
+        buf.push_zslice(Vec::<u8>::new().into());
+        buf.push_zslice(b"ond\nthird".to_vec().into());
+
+        let mut reader = buf.reader();
+        assert_eq!(reader.fill_buf().unwrap(), b"first\nsec");
+        reader.consume(3);
+        assert_eq!(reader.fill_buf().unwrap(), b"st\nsec");
+        reader.consume(6);
+        // The empty slice is skipped
+        assert_eq!(reader.fill_buf().unwrap(), b"ond\nthird");
+        reader.consume(10);
+        assert_eq!(reader.fill_buf().unwrap(), b"");
+
+        // The lines span the slices
+        let lines: Vec<String> = buf.reader().lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["first", "second", "third"]);
+
+        // Reading and buffered reading can be mixed
+        let mut reader = buf.reader();
+        let mut first = [0u8; 2];
+        reader.read_exact(&mut first).unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!((&first, line.as_str()), (b"fi", "rst\n"));
+    }
 }
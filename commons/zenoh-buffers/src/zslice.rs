@@ -427,6 +427,13 @@ impl ZSlice {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ZSlice {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(u.arbitrary::<Vec<u8>>()?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
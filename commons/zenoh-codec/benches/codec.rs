@@ -85,6 +85,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         ext_qos: ext::QoSType::DEFAULT,
         ext_tstamp: None,
         ext_nodeid: ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: PushBody::Put(Put {
             timestamp: None,
             encoding: Encoding::empty(),
@@ -92,6 +95,8 @@ fn criterion_benchmark(c: &mut Criterion) {
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
             payload: ZBuf::from(vec![0u8; 8]),
         }),
@@ -131,6 +136,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         ext_qos: ext::QoSType::DEFAULT,
         ext_tstamp: None,
         ext_nodeid: ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: PushBody::Put(Put {
             timestamp: None,
             encoding: Encoding::empty(),
@@ -138,6 +146,8 @@ fn criterion_benchmark(c: &mut Criterion) {
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
             payload: ZBuf::from(vec![0u8; 8]),
         }),
@@ -172,6 +182,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         ext_qos: ext::QoSType::DEFAULT,
         ext_tstamp: None,
         ext_nodeid: ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: PushBody::Put(Put {
             timestamp: None,
             encoding: Encoding::empty(),
@@ -179,6 +192,8 @@ fn criterion_benchmark(c: &mut Criterion) {
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
             payload: ZBuf::from(vec![0u8; 8]),
         }),
@@ -213,6 +228,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         ext_qos: ext::QoSType::DEFAULT,
         ext_tstamp: None,
         ext_nodeid: ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: PushBody::Put(Put {
             timestamp: None,
             encoding: Encoding::empty(),
@@ -220,6 +238,8 @@ fn criterion_benchmark(c: &mut Criterion) {
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
             payload: ZBuf::from(vec![0u8; 1_000_000]),
         }),
@@ -241,6 +261,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         ext_qos: ext::QoSType::DEFAULT,
         ext_tstamp: None,
         ext_nodeid: ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: PushBody::Put(Put {
             timestamp: None,
             encoding: Encoding::empty(),
@@ -248,6 +271,8 @@ fn criterion_benchmark(c: &mut Criterion) {
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
             payload: ZBuf::from(vec![0u8; 1_000_000]),
         }),
@@ -280,6 +305,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         ext_qos: ext::QoSType::DEFAULT,
         ext_tstamp: None,
         ext_nodeid: ext::NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: PushBody::Put(Put {
             timestamp: None,
             encoding: Encoding::empty(),
@@ -287,6 +315,8 @@ fn criterion_benchmark(c: &mut Criterion) {
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
             payload: ZBuf::from(vec![0u8; 1_000_000]),
         }),
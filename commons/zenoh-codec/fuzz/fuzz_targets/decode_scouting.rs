@@ -0,0 +1,45 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Decodes arbitrary bytes as a scouting message: a successfully decoded message must be encoded
+//! and then decoded back to the same message.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zenoh_buffers::{
+    reader::{HasReader, Reader},
+    writer::HasWriter,
+    ZSlice,
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_protocol::scouting::ScoutingMessage;
+
+fuzz_target!(|data: &[u8]| {
+    let codec = Zenoh080::new();
+
+    let mut zslice = ZSlice::from(data.to_vec());
+    let mut reader = zslice.reader();
+    let Ok(x): Result<ScoutingMessage, _> = codec.read(&mut reader) else {
+        return;
+    };
+
+    let mut buffer = vec![];
+    let mut writer = buffer.writer();
+    codec.write(&mut writer, &x).unwrap();
+
+    let mut reader = buffer.reader();
+    let y: ScoutingMessage = codec.read(&mut reader).unwrap();
+    assert_eq!(x, y);
+    assert!(!reader.can_read());
+});
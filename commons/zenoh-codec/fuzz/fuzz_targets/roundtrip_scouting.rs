@@ -0,0 +1,40 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Encodes an arbitrary scouting message: it must be decoded back to the same message.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zenoh_buffers::{
+    reader::{HasReader, Reader},
+    writer::HasWriter,
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_protocol::scouting::ScoutingMessage;
+
+fuzz_target!(|x: ScoutingMessage| {
+    let codec = Zenoh080::new();
+
+    let mut buffer = vec![];
+    let mut writer = buffer.writer();
+    // A message with a field exceeding its encoding bounds is not encoded
+    if codec.write(&mut writer, &x).is_err() {
+        return;
+    }
+
+    let mut reader = buffer.reader();
+    let y: ScoutingMessage = codec.read(&mut reader).unwrap();
+    assert_eq!(x, y);
+    assert!(!reader.can_read());
+});
@@ -30,6 +30,16 @@ where
 {
     let codec = Zenoh080Header::new(header);
     let (u, has_ext): (ZExtUnknown, bool) = codec.read(&mut *reader)?;
+    #[cfg(feature = "std")]
+    if let Some(valid) = vendor::validate(_s, &u) {
+        if !valid {
+            tracing::debug!("Malformed {_s} vendor ext: {u:?}");
+            if u.is_mandatory() {
+                return Err(DidntRead);
+            }
+        }
+        return Ok((u, has_ext));
+    }
     if u.is_mandatory() {
         #[cfg(feature = "std")]
         tracing::error!("Unknown {_s} ext: {u:?}");
@@ -82,6 +92,83 @@ where
     Ok(())
 }
 
+/// The registry of the vendor extensions known by the codec.
+///
+/// A registered vendor extension is accepted by the codec even if it is mandatory, unless its
+/// body can not be decoded. The messages are designated by their name, i.e. `"Put"`, `"Del"`,
+/// `"Query"`, `"Reply"` or `"Err"`.
+#[cfg(feature = "std")]
+pub mod vendor {
+    use std::sync::RwLock;
+
+    use zenoh_protocol::common::{iext, ZExtBody, ZExtUnknown, ZExtVendor};
+
+    struct Entry {
+        message: &'static str,
+        id: u8,
+        validate: fn(&ZExtBody) -> bool,
+    }
+
+    static REGISTRY: RwLock<Vec<Entry>> = RwLock::new(Vec::new());
+
+    fn validate_body<E: ZExtVendor>(body: &ZExtBody) -> bool {
+        E::decode(body).is_some()
+    }
+
+    /// Registers the vendor extension `E` for the `message` messages.
+    ///
+    /// Returns false if the id of `E` is invalid or already registered for `message`.
+    pub fn register<E: ZExtVendor>(message: &'static str) -> bool {
+        if E::ID > iext::ID_MASK {
+            return false;
+        }
+        let mut registry = REGISTRY.write().unwrap();
+        if registry
+            .iter()
+            .any(|e| e.message == message && e.id == E::ID)
+        {
+            return false;
+        }
+        registry.push(Entry {
+            message,
+            id: E::ID,
+            validate: validate_body::<E>,
+        });
+        true
+    }
+
+    /// Unregisters the vendor extension `E` for the `message` messages.
+    ///
+    /// Returns false if `E` was not registered for `message`.
+    pub fn unregister<E: ZExtVendor>(message: &str) -> bool {
+        let mut registry = REGISTRY.write().unwrap();
+        let len = registry.len();
+        registry.retain(|e| !(e.message == message && e.id == E::ID));
+        registry.len() != len
+    }
+
+    /// Returns whether the extension `id` is registered for the `message` messages.
+    pub fn is_registered(message: &str, id: u8) -> bool {
+        REGISTRY
+            .read()
+            .unwrap()
+            .iter()
+            .any(|e| e.message == message && e.id == iext::mid(id))
+    }
+
+    // Returns whether the body of `ext` is valid, or None if it is not a registered extension.
+    pub(super) fn validate(message: &str, ext: &ZExtUnknown) -> Option<bool> {
+        let registry = REGISTRY.read().unwrap();
+        if registry.is_empty() {
+            return None;
+        }
+        registry
+            .iter()
+            .find(|e| e.message == message && e.id == iext::mid(ext.id))
+            .map(|e| (e.validate)(&ext.body))
+    }
+}
+
 // ZExtUnit
 impl<const ID: u8, W> WCodec<(&ZExtUnit<{ ID }>, bool), &mut W> for Zenoh080
 where
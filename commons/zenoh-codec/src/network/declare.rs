@@ -553,7 +553,7 @@ where
         if x.complete {
             flags |= queryable::ext::flag::C;
         }
-        let v: u64 = (flags as u64) | ((x.distance as u64) << 8);
+        let v: u64 = (flags as u64) | ((x.distance as u64) << 8) | ((x.load as u64) << 24);
         let ext = queryable::ext::QueryableInfo::new(v);
 
         self.write(&mut *writer, (&ext, more))
@@ -574,9 +574,14 @@ where
 
         let complete = imsg::has_flag(ext.value as u8, queryable::ext::flag::C);
         let distance = (ext.value >> 8) as u16;
+        let load = (ext.value >> 24) as u8;
 
         Ok((
-            queryable::ext::QueryableInfoType { complete, distance },
+            queryable::ext::QueryableInfoType {
+                complete,
+                distance,
+                load,
+            },
             more,
         ))
     }
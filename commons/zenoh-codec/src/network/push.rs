@@ -17,7 +17,7 @@ use zenoh_buffers::{
 };
 use zenoh_protocol::{
     common::{iext, imsg},
-    core::WireExpr,
+    core::{WireExpr, NTP64},
     network::{
         id,
         push::{ext, flag},
@@ -40,14 +40,26 @@ where
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_hops,
+            ext_hop_limit,
+            ext_deadline,
             payload,
         } = x;
+        // The hop count is only carried for the nodes collecting the sample metadata
+        let ext_hops = if cfg!(feature = "sample_metadata") {
+            *ext_hops
+        } else {
+            0
+        };
 
         // Header
         let mut header = id::PUSH;
         let mut n_exts = ((ext_qos != &ext::QoSType::DEFAULT) as u8)
             + (ext_tstamp.is_some() as u8)
-            + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8);
+            + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8)
+            + ((ext_hops != 0) as u8)
+            + (ext_hop_limit.is_some() as u8)
+            + (ext_deadline.is_some() as u8);
         if n_exts != 0 {
             header |= flag::Z;
         }
@@ -75,6 +87,21 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (*ext_nodeid, n_exts != 0))?;
         }
+        if ext_hops != 0 {
+            n_exts -= 1;
+            let e = ext::Hops::new(ext_hops as u64);
+            self.write(&mut *writer, (&e, n_exts != 0))?;
+        }
+        if let Some(hop_limit) = ext_hop_limit {
+            n_exts -= 1;
+            let e = ext::HopLimit::new(*hop_limit as u64);
+            self.write(&mut *writer, (&e, n_exts != 0))?;
+        }
+        if let Some(deadline) = ext_deadline {
+            n_exts -= 1;
+            let e = ext::Deadline::new(deadline.as_u64());
+            self.write(&mut *writer, (&e, n_exts != 0))?;
+        }
 
         // Payload
         self.write(&mut *writer, payload)?;
@@ -120,6 +147,9 @@ where
         let mut ext_qos = ext::QoSType::DEFAULT;
         let mut ext_tstamp = None;
         let mut ext_nodeid = ext::NodeIdType::DEFAULT;
+        let mut ext_hops = 0;
+        let mut ext_hop_limit = None;
+        let mut ext_deadline = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -141,6 +171,22 @@ where
                     ext_nodeid = nid;
                     has_ext = ext;
                 }
+                ext::Hops::ID => {
+                    let (h, ext): (ext::Hops, bool) = eodec.read(&mut *reader)?;
+                    ext_hops = h.value.min(ext::HopsType::MAX as u64) as ext::HopsType;
+                    has_ext = ext;
+                }
+                ext::HopLimit::ID => {
+                    let (l, ext): (ext::HopLimit, bool) = eodec.read(&mut *reader)?;
+                    ext_hop_limit =
+                        Some(l.value.min(ext::HopLimitType::MAX as u64) as ext::HopLimitType);
+                    has_ext = ext;
+                }
+                ext::Deadline::ID => {
+                    let (d, ext): (ext::Deadline, bool) = eodec.read(&mut *reader)?;
+                    ext_deadline = Some(NTP64(d.value));
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Push", ext)?;
                 }
@@ -156,6 +202,9 @@ where
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_hops,
+            ext_hop_limit,
+            ext_deadline,
         })
     }
 }
@@ -15,6 +15,8 @@ use zenoh_buffers::{
     reader::{DidntRead, Reader},
     writer::{DidntWrite, Writer},
 };
+#[cfg(feature = "unstable")]
+use zenoh_protocol::{common::ZExtZBufHeader, core::ZenohIdProto};
 use zenoh_protocol::{
     common::{iext, imsg},
     core::WireExpr,
@@ -29,6 +31,8 @@ use zenoh_protocol::{
 use crate::{
     common::extension, RCodec, WCodec, Zenoh080, Zenoh080Bounded, Zenoh080Condition, Zenoh080Header,
 };
+#[cfg(feature = "unstable")]
+use crate::{LCodec, Zenoh080Length};
 
 // Target
 impl<W> WCodec<(&ext::QueryTarget, bool), &mut W> for Zenoh080
@@ -44,6 +48,18 @@ where
             ext::QueryTarget::BestMatching => 0,
             ext::QueryTarget::All => 1,
             ext::QueryTarget::AllComplete => 2,
+            #[cfg(feature = "unstable")]
+            ext::QueryTarget::Specific(zid) => {
+                let header: ZExtZBufHeader<{ ext::TargetZid::ID }> =
+                    ZExtZBufHeader::new(1 + self.w_len(zid));
+                self.write(&mut *writer, (&header, more))?;
+
+                let flags: u8 = (zid.size() as u8 - 1) << 4;
+                self.write(&mut *writer, flags)?;
+
+                let lodec = Zenoh080Length::new(zid.size());
+                return lodec.write(&mut *writer, zid);
+            }
         };
         let ext = ext::Target::new(v);
         self.write(&mut *writer, (&ext, more))
@@ -57,6 +73,19 @@ where
     type Error = DidntRead;
 
     fn read(self, reader: &mut R) -> Result<(ext::QueryTarget, bool), Self::Error> {
+        #[cfg(feature = "unstable")]
+        if iext::eid(self.header) == ext::TargetZid::ID {
+            let (_, more): (ZExtZBufHeader<{ ext::TargetZid::ID }>, bool) =
+                self.read(&mut *reader)?;
+
+            let flags: u8 = self.codec.read(&mut *reader)?;
+            let length = 1 + ((flags >> 4) as usize);
+
+            let lodec = Zenoh080Length::new(length);
+            let zid: ZenohIdProto = lodec.read(&mut *reader)?;
+            return Ok((ext::QueryTarget::Specific(zid), more));
+        }
+
         let (ext, more): (ext::Target, bool) = self.read(&mut *reader)?;
         let rt = match ext.value {
             0 => ext::QueryTarget::BestMatching,
@@ -213,6 +242,12 @@ where
                     ext_target = rt;
                     has_ext = ext;
                 }
+                #[cfg(feature = "unstable")]
+                ext::TargetZid::ID => {
+                    let (rt, ext): (ext::QueryTarget, bool) = eodec.read(&mut *reader)?;
+                    ext_target = rt;
+                    has_ext = ext;
+                }
                 ext::Budget::ID => {
                     let (l, ext): (ext::Budget, bool) = eodec.read(&mut *reader)?;
                     ext_limit = ext::BudgetType::new(l.value as u32);
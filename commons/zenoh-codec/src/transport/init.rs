@@ -52,6 +52,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         } = x;
 
@@ -66,6 +67,7 @@ where
             + (ext_mlink.is_some() as u8)
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
+            + (ext_noise.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8;
 
         #[cfg(feature = "shared-memory")]
@@ -127,6 +129,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (compression, n_exts != 0))?;
         }
+        if let Some(noise) = ext_noise.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (noise, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
@@ -192,6 +198,7 @@ where
         let mut ext_mlink = None;
         let mut ext_lowlatency = None;
         let mut ext_compression = None;
+        let mut ext_noise = None;
         let mut ext_patch = ext::PatchType::NONE;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -235,6 +242,11 @@ where
                     ext_compression = Some(q);
                     has_ext = ext;
                 }
+                ext::Noise::ID => {
+                    let (n, ext): (ext::Noise, bool) = eodec.read(&mut *reader)?;
+                    ext_noise = Some(n);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
@@ -260,6 +272,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         })
     }
@@ -288,6 +301,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         } = x;
 
@@ -302,6 +316,7 @@ where
             + (ext_mlink.is_some() as u8)
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
+            + (ext_noise.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8;
 
         #[cfg(feature = "shared-memory")]
@@ -366,6 +381,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (compression, n_exts != 0))?;
         }
+        if let Some(noise) = ext_noise.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (noise, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
@@ -434,6 +453,7 @@ where
         let mut ext_mlink = None;
         let mut ext_lowlatency = None;
         let mut ext_compression = None;
+        let mut ext_noise = None;
         let mut ext_patch = ext::PatchType::NONE;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -477,6 +497,11 @@ where
                     ext_compression = Some(q);
                     has_ext = ext;
                 }
+                ext::Noise::ID => {
+                    let (n, ext): (ext::Noise, bool) = eodec.read(&mut *reader)?;
+                    ext_noise = Some(n);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
@@ -503,6 +528,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         })
     }
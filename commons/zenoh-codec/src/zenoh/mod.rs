@@ -410,3 +410,106 @@ where
         Ok((ext::AttachmentType { buffer }, more))
     }
 }
+
+// Extension: TraceContext
+impl<W, const ID: u8> WCodec<(&ext::TraceContextType<{ ID }>, bool), &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: (&ext::TraceContextType<{ ID }>, bool)) -> Self::Output {
+        let (x, more) = x;
+        let ext::TraceContextType {
+            trace_id,
+            span_id,
+            flags,
+        } = x;
+
+        let header: ZExtZBufHeader<{ ID }> =
+            ZExtZBufHeader::new(ext::TraceContextType::<{ ID }>::LEN);
+        self.write(&mut *writer, (&header, more))?;
+        writer.write_exact(trace_id)?;
+        writer.write_exact(span_id)?;
+        writer.write_u8(*flags)?;
+        Ok(())
+    }
+}
+
+impl<R, const ID: u8> RCodec<(ext::TraceContextType<{ ID }>, bool), &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(ext::TraceContextType<{ ID }>, bool), Self::Error> {
+        let (h, more): (ZExtZBufHeader<{ ID }>, bool) = self.read(&mut *reader)?;
+        if h.len != ext::TraceContextType::<{ ID }>::LEN {
+            return Err(DidntRead);
+        }
+
+        let mut trace_id = [0u8; 16];
+        reader.read_exact(&mut trace_id)?;
+        let mut span_id = [0u8; 8];
+        reader.read_exact(&mut span_id)?;
+        let flags = reader.read_u8()?;
+
+        Ok((
+            ext::TraceContextType {
+                trace_id,
+                span_id,
+                flags,
+            },
+            more,
+        ))
+    }
+}
+
+// Extension: Signature
+impl<W, const ID: u8> WCodec<(&ext::SignatureType<{ ID }>, bool), &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: (&ext::SignatureType<{ ID }>, bool)) -> Self::Output {
+        let (x, more) = x;
+        let ext::SignatureType {
+            public_key,
+            signature,
+        } = x;
+
+        let header: ZExtZBufHeader<{ ID }> = ZExtZBufHeader::new(ext::SignatureType::<{ ID }>::LEN);
+        self.write(&mut *writer, (&header, more))?;
+        writer.write_exact(public_key)?;
+        writer.write_exact(signature)?;
+        Ok(())
+    }
+}
+
+impl<R, const ID: u8> RCodec<(ext::SignatureType<{ ID }>, bool), &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(ext::SignatureType<{ ID }>, bool), Self::Error> {
+        let (h, more): (ZExtZBufHeader<{ ID }>, bool) = self.read(&mut *reader)?;
+        if h.len != ext::SignatureType::<{ ID }>::LEN {
+            return Err(DidntRead);
+        }
+
+        let mut public_key = [0u8; 32];
+        reader.read_exact(&mut public_key)?;
+        let mut signature = [0u8; 64];
+        reader.read_exact(&mut signature)?;
+
+        Ok((
+            ext::SignatureType {
+                public_key,
+                signature,
+            },
+            more,
+        ))
+    }
+}
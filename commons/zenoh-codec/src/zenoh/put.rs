@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 use zenoh_buffers::{
     reader::{DidntRead, Reader},
@@ -45,6 +45,8 @@ where
             encoding,
             ext_sinfo,
             ext_attachment,
+            ext_trace,
+            ext_signature,
             #[cfg(feature = "shared-memory")]
             ext_shm,
             ext_unknown,
@@ -61,6 +63,8 @@ where
         }
         let mut n_exts = (ext_sinfo.is_some()) as u8
             + (ext_attachment.is_some()) as u8
+            + (ext_trace.is_some()) as u8
+            + (ext_signature.is_some()) as u8
             + (ext_unknown.len() as u8);
         #[cfg(feature = "shared-memory")]
         {
@@ -93,6 +97,14 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (att, n_exts != 0))?;
         }
+        if let Some(trace) = ext_trace.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (trace, n_exts != 0))?;
+        }
+        if let Some(signature) = ext_signature.as_deref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (signature, n_exts != 0))?;
+        }
         for u in ext_unknown.iter() {
             n_exts -= 1;
             self.write(&mut *writer, (u, n_exts != 0))?;
@@ -155,6 +167,8 @@ where
         #[cfg(feature = "shared-memory")]
         let mut ext_shm: Option<ext::ShmType> = None;
         let mut ext_attachment: Option<ext::AttachmentType> = None;
+        let mut ext_trace: Option<ext::TraceContextType> = None;
+        let mut ext_signature: Option<Box<ext::SignatureType>> = None;
         let mut ext_unknown = Vec::new();
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -178,6 +192,17 @@ where
                     ext_attachment = Some(a);
                     has_ext = ext;
                 }
+                ext::TraceContext::ID => {
+                    let (t, ext): (ext::TraceContextType, bool) = eodec.read(&mut *reader)?;
+                    // An invalid trace context is ignored, not the message carrying it
+                    ext_trace = t.is_valid().then_some(t);
+                    has_ext = ext;
+                }
+                ext::Signature::ID => {
+                    let (s, ext): (ext::SignatureType, bool) = eodec.read(&mut *reader)?;
+                    ext_signature = Some(Box::new(s));
+                    has_ext = ext;
+                }
                 _ => {
                     let (u, ext) = extension::read(reader, "Put", ext)?;
                     ext_unknown.push(u);
@@ -208,6 +233,8 @@ where
             #[cfg(feature = "shared-memory")]
             ext_shm,
             ext_attachment,
+            ext_trace,
+            ext_signature,
             ext_unknown,
             payload,
         })
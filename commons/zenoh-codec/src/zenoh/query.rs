@@ -77,6 +77,7 @@ where
             ext_sinfo,
             ext_body,
             ext_attachment,
+            ext_trace,
             ext_unknown,
         } = x;
 
@@ -91,6 +92,7 @@ where
         let mut n_exts = (ext_sinfo.is_some() as u8)
             + (ext_body.is_some() as u8)
             + (ext_attachment.is_some() as u8)
+            + (ext_trace.is_some() as u8)
             + (ext_unknown.len() as u8);
         if n_exts != 0 {
             header |= flag::Z;
@@ -118,6 +120,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (att, n_exts != 0))?;
         }
+        if let Some(trace) = ext_trace.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (trace, n_exts != 0))?;
+        }
         for u in ext_unknown.iter() {
             n_exts -= 1;
             self.write(&mut *writer, (u, n_exts != 0))?;
@@ -166,6 +172,7 @@ where
         let mut ext_sinfo: Option<ext::SourceInfoType> = None;
         let mut ext_body: Option<ext::QueryBodyType> = None;
         let mut ext_attachment: Option<ext::AttachmentType> = None;
+        let mut ext_trace: Option<ext::TraceContextType> = None;
         let mut ext_unknown = Vec::new();
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -188,6 +195,12 @@ where
                     ext_attachment = Some(a);
                     has_ext = ext;
                 }
+                ext::TraceContext::ID => {
+                    let (t, ext): (ext::TraceContextType, bool) = eodec.read(&mut *reader)?;
+                    // An invalid trace context is ignored, not the message carrying it
+                    ext_trace = t.is_valid().then_some(t);
+                    has_ext = ext;
+                }
                 _ => {
                     let (u, ext) = extension::read(reader, "Query", ext)?;
                     ext_unknown.push(u);
@@ -202,6 +215,7 @@ where
             ext_sinfo,
             ext_body,
             ext_attachment,
+            ext_trace,
             ext_unknown,
         })
     }
@@ -632,3 +632,84 @@ fn codec_reply() {
 fn codec_err() {
     run!(zenoh::Err, zenoh::Err::rand());
 }
+
+#[test]
+fn codec_trace_context() {
+    let codec = Zenoh080::new();
+    let mut x = zenoh::Put::rand();
+    x.ext_trace = Some(zenoh::put::ext::TraceContextType::rand());
+    let mut buffer = vec![];
+    codec.write(&mut buffer.writer(), &x).unwrap();
+    let y: zenoh::Put = codec.read(&mut buffer.reader()).unwrap();
+    assert_eq!(x, y);
+
+    // Trace contexts with an all-zeros trace id or span id are dropped from the message
+    for trace in [
+        zenoh::put::ext::TraceContextType {
+            trace_id: [0; 16],
+            ..zenoh::put::ext::TraceContextType::rand()
+        },
+        zenoh::put::ext::TraceContextType {
+            span_id: [0; 8],
+            ..zenoh::put::ext::TraceContextType::rand()
+        },
+    ] {
+        x.ext_trace = Some(trace);
+        buffer.clear();
+        codec.write(&mut buffer.writer(), &x).unwrap();
+        let y: zenoh::Put = codec.read(&mut buffer.reader()).unwrap();
+        assert_eq!(y.ext_trace, None);
+        assert_eq!(y.payload, x.payload);
+    }
+}
+
+#[test]
+fn codec_vendor_extension() {
+    use zenoh_codec::common::extension::vendor;
+
+    #[derive(Debug, PartialEq)]
+    struct Deadline(u64);
+
+    impl ZExtVendor for Deadline {
+        const ID: u8 = 0xe;
+        const MANDATORY: bool = true;
+
+        fn encode(&self) -> ZExtBody {
+            ZExtBody::Z64(self.0)
+        }
+
+        fn decode(body: &ZExtBody) -> Option<Self> {
+            match body {
+                ZExtBody::Z64(value) => Some(Self(*value)),
+                _ => None,
+            }
+        }
+    }
+
+    let codec = Zenoh080::new();
+    let mut x = zenoh::Put::rand();
+    x.ext_unknown = vec![Deadline(42).to_unknown()];
+    let mut buffer = vec![];
+    codec.write(&mut buffer.writer(), &x).unwrap();
+
+    // The mandatory extension is rejected as long as it is not registered
+    let y: Result<zenoh::Put, _> = codec.read(&mut buffer.reader());
+    assert!(y.is_err());
+
+    assert!(vendor::register::<Deadline>("Put"));
+    assert!(!vendor::register::<Deadline>("Put"));
+    assert!(vendor::is_registered("Put", Deadline(0).to_unknown().id));
+    let y: zenoh::Put = codec.read(&mut buffer.reader()).unwrap();
+    assert_eq!(x, y);
+    assert_eq!(Deadline::from_unknowns(&y.ext_unknown), Some(Deadline(42)));
+
+    // A malformed mandatory extension is rejected even if it is registered
+    x.ext_unknown = vec![ZExtUnknown::new(Deadline::ID, true, ZExtBody::Unit)];
+    buffer.clear();
+    codec.write(&mut buffer.writer(), &x).unwrap();
+    let y: Result<zenoh::Put, _> = codec.read(&mut buffer.reader());
+    assert!(y.is_err());
+
+    assert!(vendor::unregister::<Deadline>("Put"));
+    assert!(!vendor::is_registered("Put", Deadline::ID));
+}
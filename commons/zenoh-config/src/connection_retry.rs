@@ -18,7 +18,7 @@ use zenoh_protocol::core::{EndPoint, WhatAmI};
 
 use crate::{defaults, mode_dependent::*, Config};
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, schemars::JsonSchema)]
 pub struct ConnectionRetryModeDependentConf {
     // initial wait timeout until next try
     pub period_init_ms: Option<ModeDependentValue<i64>>,
@@ -66,6 +66,12 @@ pub mod open {
     }
 }
 
+#[allow(non_upper_case_globals)]
+#[allow(dead_code)]
+pub mod config_watch {
+    pub const period: u64 = 1000;
+}
+
 #[allow(non_upper_case_globals)]
 #[allow(dead_code)]
 pub mod scouting {
@@ -92,6 +98,25 @@ pub mod scouting {
             mode_accessor!(bool);
         }
     }
+    pub mod mdns {
+        pub const enabled: bool = false;
+        pub const interface: &str = "auto";
+        pub mod autoconnect {
+            pub const router: &crate::WhatAmIMatcher = // ""
+                &crate::WhatAmIMatcher::empty();
+            pub const peer: &crate::WhatAmIMatcher = // "router|peer"
+                &crate::WhatAmIMatcher::empty().router().peer();
+            pub const client: &crate::WhatAmIMatcher = // "router|peer"
+                &crate::WhatAmIMatcher::empty().router().peer();
+            mode_accessor!(crate::WhatAmIMatcher);
+        }
+        pub mod listen {
+            pub const router: &bool = &true;
+            pub const peer: &bool = &true;
+            pub const client: &bool = &false;
+            mode_accessor!(bool);
+        }
+    }
     pub mod gossip {
         pub const enabled: bool = true;
         pub const multihop: bool = false;
@@ -141,6 +166,17 @@ pub mod routing {
     pub mod peer {
         pub const mode: &str = "peer_to_peer";
     }
+    pub mod static_topology {
+        pub const enabled: bool = false;
+    }
+    pub mod dampening {
+        pub const enabled: bool = false;
+        pub const penalty: u32 = 1000;
+        pub const suppress_threshold: u32 = 2000;
+        pub const reuse_threshold: u32 = 750;
+        pub const half_life: u64 = 15000;
+        pub const max_suppress_time: u64 = 60000;
+    }
 }
 
 impl Default for ListenConfig {
@@ -183,11 +219,14 @@ impl Default for TransportUnicastConf {
             open_timeout: 10_000,
             accept_timeout: 10_000,
             accept_pending: 100,
+            accept_pending_per_source: 10,
+            cookie_lifetime: 10_000,
             max_sessions: 1_000,
             max_links: 1,
             lowlatency: false,
             qos: QoSUnicastConf::default(),
             compression: CompressionUnicastConf::default(),
+            noise: NoiseUnicastConf::default(),
         }
     }
 }
@@ -223,6 +262,16 @@ impl Default for CompressionUnicastConf {
     }
 }
 
+#[allow(clippy::derivable_impls)]
+impl Default for NoiseUnicastConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            psk: None,
+        }
+    }
+}
+
 #[allow(clippy::derivable_impls)]
 impl Default for CompressionMulticastConf {
     fn default() -> Self {
@@ -265,6 +314,24 @@ impl Default for QueueSizeConf {
     }
 }
 
+impl QueueWeightsConf {
+    pub const MIN: usize = 1;
+    pub const MAX: usize = 255;
+}
+
+impl Default for QueueWeightsConf {
+    fn default() -> Self {
+        Self {
+            interactive_high: 32,
+            interactive_low: 16,
+            data_high: 8,
+            data: 4,
+            data_low: 2,
+            background: 1,
+        }
+    }
+}
+
 impl Default for CongestionControlDropConf {
     fn default() -> Self {
         Self {
@@ -287,6 +354,7 @@ impl Default for BatchingConf {
         BatchingConf {
             enabled: true,
             time_limit: 1,
+            target_latency: None,
         }
     }
 }
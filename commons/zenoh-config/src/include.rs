@@ -41,7 +41,7 @@ where
                     Ok(mut d) => T::deserialize(&mut d).map_err(|e| zerror!("JSON5 error: {}", e).into()),
                     Err(e) => Err(zerror!("JSON5 error: {}", e).into()),
                 },
-                Some("yaml") => {
+                Some("yaml") | Some("yml") => {
                     let d = serde_yaml::Deserializer::from_str(&content);
                     T::deserialize(d).map_err(|e| zerror!("YAML error: {}", e).into())
                 },
@@ -175,3 +175,106 @@ where
 
     Ok(())
 }
+
+/// The name of the top level property listing the files included by a configuration file.
+pub(crate) const INCLUDE_PROPERTY_NAME: &str = "include";
+
+/// Returns `true` if the configuration file at `path` includes other files.
+pub(crate) fn has_includes<P: AsRef<Path>>(path: P) -> bool {
+    deserialize_from_file::<Value, _>(path)
+        .is_ok_and(|value| value.get(INCLUDE_PROPERTY_NAME).is_some())
+}
+
+/// Reads the configuration file at `path` along with the files it includes.
+///
+/// The top level `include` property lists the included files (a path or an array of paths,
+/// relative to the including file). They are merged in order, each one overriding the previous
+/// ones, and the including file overrides them all, see [`merge`]. Included files may include
+/// other files, as long as no file includes itself.
+pub(crate) fn read_with_includes<P: AsRef<Path>>(path: P) -> ZResult<Value> {
+    read_with_sources(path.as_ref(), &mut vec![])
+}
+
+/// Reads the configuration file at `path` like [`read_with_includes`], adding the canonical path
+/// of every file read to `sources`.
+pub(crate) fn read_with_sources(path: &Path, sources: &mut Vec<PathBuf>) -> ZResult<Value> {
+    read_with_includes_rec(path, &mut vec![], sources)
+}
+
+fn read_with_includes_rec(
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+    sources: &mut Vec<PathBuf>,
+) -> ZResult<Value> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| zerror!("failed to canonicalize path '{}' - {}", path.display(), e))?;
+    if chain.contains(&canonical) {
+        bail!("loop detected while including file '{}'", path.display());
+    }
+    if !sources.contains(&canonical) {
+        sources.push(canonical.clone());
+    }
+    let mut value: Value = deserialize_from_file(path)?;
+    let includes = match value
+        .as_object_mut()
+        .and_then(|values| values.remove(INCLUDE_PROPERTY_NAME))
+    {
+        None => return Ok(value),
+        Some(Value::String(include)) => vec![include],
+        Some(Value::Array(includes)) => includes
+            .into_iter()
+            .map(|include| match include {
+                Value::String(include) => Ok(include),
+                _ => bail!(
+                    "{}.{} : property must contain strings",
+                    path.display(),
+                    INCLUDE_PROPERTY_NAME
+                ),
+            })
+            .collect::<ZResult<_>>()?,
+        Some(_) => bail!(
+            "{}.{} : property must have string or array type",
+            path.display(),
+            INCLUDE_PROPERTY_NAME
+        ),
+    };
+
+    let local_path = path.parent().unwrap_or(Path::new("."));
+    chain.push(canonical);
+    let mut merged = Value::Object(Map::new());
+    for include in includes {
+        let included =
+            read_with_includes_rec(&local_path.join(&include), chain, sources).map_err(|e| {
+                zerror!(
+                    "{}.{} : failed to include file '{}' - {}",
+                    path.display(),
+                    INCLUDE_PROPERTY_NAME,
+                    include,
+                    e
+                )
+            })?;
+        merge(&mut merged, included);
+    }
+    chain.pop();
+    merge(&mut merged, value);
+    Ok(merged)
+}
+
+/// Merges `overlay` into `base`: objects are merged recursively, any other value of `overlay`
+/// (including arrays and `null`) replaces the one of `base`.
+pub(crate) fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (k, v) in overlay {
+                match base.get_mut(&k) {
+                    Some(base) => merge(base, v),
+                    None => {
+                        base.insert(k, v);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
@@ -22,12 +22,20 @@
 pub mod defaults;
 mod include;
 pub mod qos;
+pub mod secret;
 pub mod wrappers;
 
 #[allow(unused_imports)]
 use std::convert::TryFrom; // This is a false positive from the rust analyser
 use std::{
-    any::Any, collections::HashSet, fmt, io::Read, net::SocketAddr, ops, path::Path, sync::Weak,
+    any::Any,
+    collections::{HashMap, HashSet},
+    fmt,
+    io::Read,
+    net::SocketAddr,
+    ops,
+    path::{Path, PathBuf},
+    sync::Weak,
 };
 
 use include::recursive_include;
@@ -77,14 +85,14 @@ impl Zeroize for SecretString {
 
 pub type SecretValue = Secret<SecretString>;
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum InterceptorFlow {
     Egress,
     Ingress,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, schemars::JsonSchema)]
 pub struct DownsamplingRuleConf {
     /// A list of key-expressions to which the downsampling will be applied.
     /// Downsampling will be applied for all key extensions if the parameter is None
@@ -93,7 +101,7 @@ pub struct DownsamplingRuleConf {
     pub freq: f64,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, schemars::JsonSchema)]
 pub struct DownsamplingItemConf {
     /// A list of interfaces to which the downsampling will be applied
     /// Downsampling will be applied for all interfaces if the parameter is None
@@ -104,7 +112,29 @@ pub struct DownsamplingItemConf {
     pub flow: InterceptorFlow,
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RegionNeighbourConf {
+    /// The name of the region the matching remote nodes belong to.
+    pub region: String,
+    /// The zenoh ids of the remote nodes belonging to this region.
+    pub zids: Option<Vec<ZenohId>>,
+    /// The network interfaces through which the remote nodes of this region are reached.
+    pub interfaces: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StaticNeighbourConf {
+    /// The zenoh id of the neighbour.
+    pub zid: ZenohId,
+    /// The key expressions allowed to flow from the neighbour. All key expressions are allowed if not set.
+    pub ingress: Option<Vec<OwnedKeyExpr>>,
+    /// The key expressions allowed to flow to the neighbour. All key expressions are allowed if not set.
+    pub egress: Option<Vec<OwnedKeyExpr>>,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, schemars::JsonSchema)]
 pub struct AclConfigRule {
     pub id: String,
     pub key_exprs: Vec<String>,
@@ -113,7 +143,7 @@ pub struct AclConfigRule {
     pub permission: Permission,
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone)]
+#[derive(Serialize, Debug, Deserialize, Clone, schemars::JsonSchema)]
 pub struct AclConfigSubjects {
     pub id: String,
     pub interfaces: Option<Vec<Interface>>,
@@ -121,7 +151,7 @@ pub struct AclConfigSubjects {
     pub usernames: Option<Vec<Username>>,
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash, schemars::JsonSchema)]
 pub struct Interface(pub String);
 
 impl std::fmt::Display for Interface {
@@ -130,7 +160,7 @@ impl std::fmt::Display for Interface {
     }
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash, schemars::JsonSchema)]
 pub struct CertCommonName(pub String);
 
 impl std::fmt::Display for CertCommonName {
@@ -139,7 +169,7 @@ impl std::fmt::Display for CertCommonName {
     }
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash, schemars::JsonSchema)]
 pub struct Username(pub String);
 
 impl std::fmt::Display for Username {
@@ -148,7 +178,7 @@ impl std::fmt::Display for Username {
     }
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash, schemars::JsonSchema)]
 pub struct AclConfigPolicyEntry {
     pub rules: Vec<String>,
     pub subjects: Vec<String>,
@@ -161,9 +191,10 @@ pub struct PolicyRule {
     pub message: AclMessage,
     pub permission: Permission,
     pub flow: InterceptorFlow,
+    pub rule_id: String,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AclMessage {
     Put,
@@ -177,13 +208,38 @@ pub enum AclMessage {
     LivelinessQuery,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Permission {
     Allow,
     Deny,
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueScheduling {
+    #[default]
+    Strict,
+    WeightedRoundRobin,
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryLoadBalancing {
+    /// The queries are routed to the first nearest complete queryable.
+    #[default]
+    None,
+    /// The queries are routed in turn to each nearest complete queryable.
+    RoundRobin,
+    /// The queries are routed to the nearest complete queryable with the lowest reported load,
+    /// then with the fewest pending queries.
+    LeastLoaded,
+}
+
 pub trait ConfigValidator: Send + Sync {
     fn check_config(
         &self,
@@ -235,16 +291,17 @@ fn config_keys() {
 validated_struct::validator! {
     #[derive(Default)]
     #[recursive_attrs]
-    #[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+    #[derive(serde::Deserialize, serde::Serialize, schemars::JsonSchema, Clone, Debug)]
     #[serde(default)]
     #[serde(deny_unknown_fields)]
     #[doc(hidden)]
     Config {
         /// The Zenoh ID of the instance. This ID MUST be unique throughout your Zenoh infrastructure and cannot exceed 16 bytes of length. If left unset, a random u128 will be generated.
-        id: ZenohId,
+        id: Option<ZenohId>,
         /// The metadata of the instance. Arbitrary json data available from the admin space
         metadata: Value,
         /// The node's mode ("router" (default value in `zenohd`), "peer" or "client").
+        #[schemars(with = "Option<String>")]
         mode: Option<whatami::WhatAmI>,
         /// Which zenoh nodes to connect to.
         pub connect:
@@ -252,6 +309,7 @@ validated_struct::validator! {
             /// global timeout for full connect cycle
             pub timeout_ms: Option<ModeDependentValue<i64>>,
             /// The list of endpoints to connect to
+            #[schemars(with = "ModeDependentValue<Vec<String>>")]
             pub endpoints: ModeDependentValue<Vec<EndPoint>>,
             /// if connection timeout exceed, exit from application
             pub exit_on_failure: Option<ModeDependentValue<bool>>,
@@ -263,6 +321,7 @@ validated_struct::validator! {
             /// global timeout for full listen cycle
             pub timeout_ms: Option<ModeDependentValue<i64>>,
             /// The list of endpoints to listen on
+            #[schemars(with = "ModeDependentValue<Vec<String>>")]
             pub endpoints: ModeDependentValue<Vec<EndPoint>>,
             /// if connection timeout exceed, exit from application
             pub exit_on_failure: Option<ModeDependentValue<bool>>,
@@ -300,10 +359,24 @@ validated_struct::validator! {
                 /// The time-to-live on multicast scouting packets. (default: 1)
                 pub ttl: Option<u32>,
                 /// Which type of Zenoh instances to automatically establish sessions with upon discovery through UDP multicast.
+                #[schemars(with = "Option<ModeDependentValue<String>>")]
                 autoconnect: Option<ModeDependentValue<WhatAmIMatcher>>,
                 /// Whether or not to listen for scout messages on UDP multicast and reply to them.
                 listen: Option<ModeDependentValue<bool>>,
             },
+            /// The DNS-SD over mDNS scouting configuration.
+            pub mdns: #[derive(Default)]
+            ScoutingMdnsConf {
+                /// Whether mDNS scouting is enabled or not.
+                enabled: Option<bool>,
+                /// The network interface which should be used for mDNS scouting. `zenohd` will automatically select an interface if none is provided.
+                interface: Option<String>,
+                /// Which type of Zenoh instances to automatically establish sessions with upon discovery through mDNS.
+                #[schemars(with = "Option<ModeDependentValue<String>>")]
+                autoconnect: Option<ModeDependentValue<WhatAmIMatcher>>,
+                /// Whether or not to advertise this Zenoh instance through mDNS by answering mDNS queries.
+                listen: Option<ModeDependentValue<bool>>,
+            },
             /// The gossip scouting configuration.
             pub gossip: #[derive(Default)]
             GossipConf {
@@ -316,9 +389,22 @@ validated_struct::validator! {
                 /// direct connectivity with each other.
                 multihop: Option<bool>,
                 /// Which type of Zenoh instances to send gossip messages to.
+                #[schemars(with = "Option<ModeDependentValue<String>>")]
                 target: Option<ModeDependentValue<WhatAmIMatcher>>,
                 /// Which type of Zenoh instances to automatically establish sessions with upon discovery through gossip.
+                #[schemars(with = "Option<ModeDependentValue<String>>")]
                 autoconnect: Option<ModeDependentValue<WhatAmIMatcher>>,
+                /// Labels advertised to the other Zenoh instances through gossip.
+                labels: Option<HashMap<String, String>>,
+                /// Restricts which of the Zenoh instances discovered through gossip are automatically connected.
+                pub autoconnect_filter: #[derive(Default)]
+                GossipAutoconnectFilterConf {
+                    /// Labels that discovered Zenoh instances must advertise (with the same values) to be connected.
+                    labels: Option<HashMap<String, String>>,
+                    /// IP subnets (e.g. "192.168.1.0/24") in which discovered Zenoh instances must have a locator to be connected.
+                    /// Only the locators in those subnets are used to connect.
+                    subnets: Option<Vec<String>>,
+                },
             },
         },
 
@@ -331,6 +417,9 @@ validated_struct::validator! {
             /// If set to false (default), messages with timestamps in the future are retimestamped.
             /// Timestamps are ignored if timestamping is disabled.
             drop_future_timestamp: Option<bool>,
+            /// The maximum delta in milliseconds accepted between the received timestamps and
+            /// the local HLC. Defaults to the UHLC_MAX_DELTA_MS environment variable, or 500.
+            max_delta_ms: Option<u64>,
         },
 
         /// The default timeout to apply to queries in milliseconds.
@@ -347,6 +436,15 @@ validated_struct::validator! {
                 /// connected to each other.
                 /// The failover brokering only works if gossip discovery is enabled.
                 peers_failover_brokering: Option<bool>,
+                /// The last sample of each key matching the configured key expressions is cached
+                /// by the router and served to newly declared subscribers and to queries.
+                pub last_value_cache: #[derive(Default)]
+                LastValueCacheConf {
+                    /// The key expressions for which the last sample of each key is cached.
+                    key_exprs: Vec<OwnedKeyExpr>,
+                    /// The maximum number of cached keys. Unlimited if not set.
+                    max_keys: Option<usize>,
+                },
             },
             /// The routing strategy to use in peers and it's configuration.
             pub peer: #[derive(Default)]
@@ -354,6 +452,45 @@ validated_struct::validator! {
                 /// The routing strategy to use in peers. ("peer_to_peer" or "linkstate").
                 mode: Option<String>,
             },
+            /// The routing of the queries.
+            pub queries: #[derive(Default)]
+            QueriesRoutingConf {
+                /// How the queries targeting the best matching queryables are distributed when
+                /// several complete queryables at the same distance serve them.
+                /// ("none", "round_robin" or "least_loaded").
+                load_balancing: QueryLoadBalancing,
+            },
+            /// The static topology configuration.
+            /// When enabled, dynamic discovery (multicast, mDNS and gossip scouting) is disabled,
+            /// sessions are only established with the configured neighbours
+            /// and only the configured key expressions flow to and from them.
+            pub static_topology: #[derive(Default)]
+            StaticTopologyConf {
+                /// Whether the static topology mode is enabled or not.
+                enabled: Option<bool>,
+                /// The only remote nodes sessions can be established with.
+                neighbours: Vec<StaticNeighbourConf>,
+            },
+            /// The dampening of flapping routers and peers.
+            /// Each time the session with a router or a peer closes, its penalty is increased.
+            /// The penalty decays exponentially over time. When it exceeds the suppress threshold,
+            /// sessions with the router or peer are refused until it decays below the reuse threshold,
+            /// so that its declarations are not propagated network-wide on every flap.
+            pub dampening: #[derive(Default)]
+            DampeningConf {
+                /// Whether the dampening is enabled or not.
+                enabled: Option<bool>,
+                /// The penalty added on each flap.
+                penalty: Option<u32>,
+                /// The penalty above which a router or peer gets suppressed.
+                suppress_threshold: Option<u32>,
+                /// The penalty below which a suppressed router or peer is reused.
+                reuse_threshold: Option<u32>,
+                /// The time in milliseconds after which the penalty is halved.
+                half_life: Option<u64>,
+                /// The maximum time in milliseconds a router or peer can be suppressed.
+                max_suppress_time: Option<u64>,
+            },
         },
 
         /// The declarations aggregation strategy.
@@ -365,11 +502,15 @@ validated_struct::validator! {
             publishers: Vec<OwnedKeyExpr>,
         },
 
-        /// Overwrite QoS options for Zenoh messages by key expression (ignores Zenoh API QoS config)
+        /// QoS options for Zenoh messages by key expression
         pub qos: #[derive(Default)]
         QoSConfig {
-            /// A list of QoS configurations for PUT and DELETE messages by key expressions
+            /// A list of QoS configurations for PUT and DELETE messages by key expressions,
+            /// overwriting the QoS options given through the Zenoh API
             publication: PublisherQoSConfList,
+            /// A list of default QoS configurations for publishers, PUT and DELETE messages by key
+            /// expressions, which may still be changed through the Zenoh API
+            defaults: PublisherQoSConfList,
         },
 
         pub transport: #[derive(Default)]
@@ -381,6 +522,10 @@ validated_struct::validator! {
                 accept_timeout: u64,
                 /// Number of links that may stay pending during accept phase (default: 100).
                 accept_pending: usize,
+                /// Number of links coming from the same IP address that may stay pending during accept phase, bounded by `accept_pending` (default: 10).
+                accept_pending_per_source: usize,
+                /// Lifetime in milliseconds of the cookie sent in InitAck, an OpenSyn carrying an older cookie is refused (default: 10000).
+                cookie_lifetime: u64,
                 /// Maximum number of unicast sessions (default: 1000)
                 max_sessions: usize,
                 /// Maximum number of unicast incoming links per transport session (default: 1)
@@ -399,6 +544,15 @@ validated_struct::validator! {
                     /// When enabled is true, batches will be sent compressed. (default `false`).
                     enabled: bool,
                 },
+                pub noise: NoiseUnicastConf {
+                    /// You must compile zenoh with "transport_noise" feature to be able to enable Noise encryption.
+                    /// When enabled is true, the links not secured by TLS or QUIC are encrypted and the sessions
+                    /// with nodes not supporting it are refused. (default `false`).
+                    enabled: bool,
+                    /// The secret shared by the nodes to authenticate each other during the Noise handshake,
+                    /// required when enabled.
+                    psk: Option<String>,
+                },
             },
             pub multicast: TransportMulticastConf {
                 /// Link join interval duration in milliseconds (default: 2500)
@@ -425,6 +579,7 @@ validated_struct::validator! {
                     /// The resolution in bits to be used for the message sequence numbers.
                     /// When establishing a session with another Zenoh instance, the lowest value of the two instances will be used.
                     /// Accepted values: 8bit, 16bit, 32bit, 64bit.
+                    #[schemars(with = "String")]
                     sequence_number_resolution: Bits where (sequence_number_resolution_validator),
                     /// Link lease duration in milliseconds (default: 10000)
                     lease: u64,
@@ -477,6 +632,30 @@ validated_struct::validator! {
                             enabled: bool,
                             /// The maximum time limit (in ms) a message should be retained for batching when back-pressure happens.
                             time_limit: u64,
+                            /// The target latency (in µs) of the batching. When set, a batch is transmitted either when it is full
+                            /// or when its oldest message has been retained for the target latency, regardless of the back-pressure.
+                            target_latency: Option<u64>,
+                        },
+                        /// The occupancy, in percent of its size, above which a priority queue raises a head-of-line blocking alarm.
+                        /// The current occupancy of each queue and the number of alarms it raised are reported in the admin space
+                        /// along with the transport stats and in the session metrics.
+                        pub occupancy_alarm: Option<u8>,
+                        /// The scheduling of the transmission of the priority queues.
+                        pub scheduling: #[derive(Default)]
+                        QueueSchedulingConf {
+                            /// Using QueueScheduling::Strict a queue is served only when the queues of higher priority are empty.
+                            /// Using QueueScheduling::WeightedRoundRobin the control and real-time queues are still served first,
+                            /// while the other queues are served in turn, each up to its weight in consecutive batches.
+                            mode: QueueScheduling,
+                            /// The weights of the queues below the real-time priority, between 1 and 255.
+                            pub weights: QueueWeightsConf {
+                                interactive_high: usize,
+                                interactive_low: usize,
+                                data_high: usize,
+                                data: usize,
+                                data_low: usize,
+                                background: usize,
+                            } where (queue_weights_validator),
                         },
                     },
                     // Number of threads used for TX
@@ -509,14 +688,19 @@ validated_struct::validator! {
                     pub so_rcvbuf: Option<u32>,
                     // Skip serializing field because they contain secrets
                     #[serde(skip_serializing)]
+                    #[schemars(with = "Option<String>")]
                     root_ca_certificate_base64: Option<SecretValue>,
                     #[serde(skip_serializing)]
+                    #[schemars(with = "Option<String>")]
                     listen_private_key_base64:  Option<SecretValue>,
                     #[serde(skip_serializing)]
+                    #[schemars(with = "Option<String>")]
                     listen_certificate_base64: Option<SecretValue>,
                     #[serde(skip_serializing)]
+                    #[schemars(with = "Option<String>")]
                     connect_private_key_base64 :  Option<SecretValue>,
                     #[serde(skip_serializing)]
+                    #[schemars(with = "Option<String>")]
                     connect_certificate_base64 :  Option<SecretValue>,
                 },
                 pub tcp: #[derive(Default)]
@@ -558,6 +742,21 @@ validated_struct::validator! {
                     key_size: Option<usize>,
                     known_keys_file: Option<String>,
                 },
+                pub jwt: #[derive(Default)]
+                JwtConf {
+                    /// The JSON Web Token presented when opening a session.
+                    token: Option<String>,
+                    /// The path to a file containing the JSON Web Key Set validating the tokens of the accepted sessions.
+                    jwks_file: Option<String>,
+                    /// The interval in seconds at which the JSON Web Key Set file is reloaded (default: 300).
+                    jwks_refresh_interval: Option<u64>,
+                    /// The issuer (`iss` claim) the tokens are expected to have.
+                    issuer: Option<String>,
+                    /// The audience (`aud` claim) the tokens are expected to have.
+                    audience: Option<String>,
+                    /// The claim of the tokens holding the username matched by the access control (default: "sub").
+                    username_claim: Option<String>,
+                },
             },
 
         },
@@ -585,6 +784,66 @@ validated_struct::validator! {
 
         },
 
+        /// Redirection of the samples dropped by this node to its sessions.
+        pub dead_letters: #[derive(Default)]
+        DeadLettersConf {
+            /// Deliver the samples dropped because of congestion, missing links, access control
+            /// denial or serialization failure to the subscribers of
+            /// `@/<zid>/<whatami>/dropped/<reason>/<key_expr>` (false by default).
+            #[serde(default = "set_false")]
+            pub enabled: bool,
+        },
+
+        /// The ring of the recent significant events of this node.
+        pub flight_recorder: #[derive(Default)]
+        FlightRecorderConf {
+            /// Print the recorded events on the standard error when the process panics (false by
+            /// default). The panic hook is global to the process.
+            #[serde(default = "set_false")]
+            pub panic_hook: bool,
+        },
+
+        /// Capture of the network messages exchanged with the remote nodes.
+        pub capture: #[derive(Default)]
+        CaptureConf {
+            /// The file the messages are appended to. Messages are not captured if not set.
+            pub file: Option<String>,
+        },
+
+        /// Persistence of the identity of the instance across restarts, in an encrypted file.
+        pub identity: #[derive(Default)]
+        /// <div class="stab unstable">
+        ///   <span class="emoji">🔬</span>
+        ///   This API has been marked as unstable: it works as advertised, but we may change it in a future release.
+        ///   To use it, you must enable zenoh's <code>unstable</code> feature flag.
+        /// </div>
+        IdentityConf {
+            /// The file storing the Zenoh ID and the link credentials of the instance, created with
+            /// the current ones if missing. The identity is not persisted if not set.
+            pub file: Option<String>,
+            /// The secret encrypting the file, which may be a reference, see [`secret`].
+            #[serde(skip_serializing)]
+            #[schemars(with = "Option<String>")]
+            pub secret: Option<SecretValue>,
+        },
+
+        /// Watch of a configuration file whose changes are re-applied live where supported.
+        pub config_watch: #[derive(Default)]
+        ConfigWatchConf {
+            /// The configuration file to watch. The file is not watched if not set.
+            pub file: Option<String>,
+            /// The period in milliseconds at which the file is checked for changes.
+            period: Option<u64>,
+        },
+
+        /// Configuration of the logs of the instance.
+        pub logging: #[derive(Default)]
+        LoggingConf {
+            /// The filtering directives of the logs, e.g. `z=info`, overriding `RUST_LOG`. They
+            /// are re-applied live when changed in a watched configuration file.
+            pub filter: Option<String>,
+        },
+
         /// Configuration of the downsampling.
         downsampling: Vec<DownsamplingItemConf>,
 
@@ -597,11 +856,24 @@ validated_struct::validator! {
             pub policies: Option<Vec<AclConfigPolicyEntry>>,
         },
 
+        /// Configuration of the routing regions.
+        pub regions: #[derive(Default)]
+        RegionsConf {
+            /// The name of the region this node belongs to. Regions are disabled when not set.
+            pub name: Option<String>,
+            /// The remote nodes belonging to other regions.
+            /// Remote nodes not matching any entry are considered part of this node's region.
+            pub neighbours: Vec<RegionNeighbourConf>,
+            /// The key expressions allowed to cross the boundaries of this node's region.
+            pub exports: Vec<OwnedKeyExpr>,
+        },
+
         /// A list of directories where plugins may be searched for if no `__path__` was specified for them.
         /// The executable's current directory will be added to the search paths.
         pub plugins_loading: #[derive(Default)]
         PluginsLoading {
             pub enabled: bool,
+            #[schemars(with = "Vec<Value>")]
             pub search_dirs: LibSearchDirs,
         },
         #[validated(recursive_accessors)]
@@ -776,6 +1048,56 @@ impl Config {
     ) -> ConnectionRetryConf {
         get_retry_config(self, endpoint, listen)
     }
+
+    /// The prefix of the environment variables overriding configuration fields.
+    pub const ENV_PREFIX: &'static str = "ZENOH_";
+
+    /// Returns the name of the environment variable overriding the configuration field at `key`,
+    /// e.g. `ZENOH_CONNECT_ENDPOINTS` for `connect/endpoints`.
+    pub fn env_var_name(key: &str) -> String {
+        format!(
+            "{}{}",
+            Self::ENV_PREFIX,
+            key.replace('/', "_").to_uppercase()
+        )
+    }
+
+    /// Overrides configuration fields with the value of their environment variable, as named by
+    /// [`Config::env_var_name`].
+    ///
+    /// Values are parsed as JSON5. Values which are not valid JSON5 are taken as strings, or as
+    /// comma-separated lists of strings, e.g. `ZENOH_MODE=router` or
+    /// `ZENOH_CONNECT_ENDPOINTS=tcp/10.0.0.1:7447,tcp/10.0.0.2:7447`.
+    /// Variables overriding a whole section are applied before those overriding its fields.
+    pub fn apply_env_overlay(&mut self) -> ZResult<()> {
+        self.apply_overlay(|var| std::env::var(var).ok())
+    }
+
+    /// Overrides configuration fields like [`Config::apply_env_overlay`], with the variables
+    /// returned by `vars`.
+    fn apply_overlay(&mut self, vars: impl Fn(&str) -> Option<String>) -> ZResult<()> {
+        let mut keys: Vec<String> = self.keys().collect();
+        keys.sort_by_key(|key| key.matches('/').count());
+        for key in keys {
+            let var = Self::env_var_name(&key);
+            let Some(value) = vars(&var) else {
+                continue;
+            };
+            let list: Vec<&str> = value.split(',').map(str::trim).collect();
+            let inserted = self.insert_json5(&key, &value).is_ok()
+                || self
+                    .insert_json5(&key, &serde_json::to_string(&value)?)
+                    .is_ok()
+                || self
+                    .insert_json5(&key, &serde_json::to_string(&list)?)
+                    .is_ok();
+            if !inserted {
+                bail!("Invalid value for {} ({}): {}", var, key, value);
+            }
+            tracing::debug!("Config {} overridden by {}", key, var);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -801,11 +1123,85 @@ impl std::error::Error for ConfigOpenErr {}
 impl Config {
     pub fn from_file<P: AsRef<Path>>(path: P) -> ZResult<Self> {
         let path = path.as_ref();
-        let mut config = Self::_from_file(path)?;
+        let mut config = if include::has_includes(path) {
+            Self::from_value(include::read_with_includes(path)?)?
+        } else {
+            Self::_from_file(path)?
+        };
         config.plugins.load_external_configs()?;
         Ok(config)
     }
 
+    /// Loads the configuration file at `path` like [`Config::from_file`], also returning the files
+    /// it was read from: `path` and the files it includes, directly or not. Unless set in the
+    /// files, the id of the configuration is `id` (or a random one).
+    pub fn from_file_with_sources<P: AsRef<Path>>(
+        path: P,
+        id: Option<ZenohId>,
+    ) -> ZResult<(Self, Vec<PathBuf>)> {
+        let mut sources = vec![];
+        let mut value = include::read_with_sources(path.as_ref(), &mut sources)?;
+        if let (Some(id), Some(values)) = (id, value.as_object_mut()) {
+            values
+                .entry("id")
+                .or_insert_with(|| Value::String(id.to_string()));
+        }
+        let mut config = Self::from_value(value)?;
+        config.plugins.load_external_configs()?;
+        Ok((config, sources))
+    }
+
+    /// Loads the configuration file at `path` like [`Config::from_file`], but rejects the fields
+    /// which are unknown or would be ignored, and reports the path of every invalid field.
+    pub fn from_file_strict<P: AsRef<Path>>(path: P) -> ZResult<Self> {
+        let path = path.as_ref();
+        let raw = include::read_with_includes(path)?;
+        let config = match Self::from_file(path) {
+            Ok(config) => config,
+            Err(e) => {
+                let mut errors = vec![];
+                invalid_fields(&mut Config::default(), &raw, "", &mut errors);
+                if errors.is_empty() {
+                    return Err(e);
+                }
+                bail!(
+                    "Invalid configuration {}:\n  {}",
+                    path.display(),
+                    errors.join("\n  ")
+                )
+            }
+        };
+        let mut unknown = vec![];
+        let keys: Vec<String> = config.keys().collect();
+        let parsed = serde_json::to_value(&config)?;
+        unknown_fields(&raw, &parsed, &keys, "", &mut unknown);
+        if !unknown.is_empty() {
+            bail!(
+                "Invalid configuration {}:\n  {}",
+                path.display(),
+                unknown
+                    .iter()
+                    .map(|path| format!("{path}: unknown field"))
+                    .collect::<Vec<_>>()
+                    .join("\n  ")
+            )
+        }
+        Ok(config)
+    }
+
+    /// Returns the JSON schema of the configuration.
+    pub fn json_schema() -> Value {
+        serde_json::to_value(schemars::schema_for!(Config))
+            .expect("the configuration schema should serialize to JSON")
+    }
+
+    fn from_value(value: Value) -> ZResult<Config> {
+        Config::from_deserializer(value).map_err(|e| match e {
+            Ok(c) => zerror!("Invalid configuration: {}", c).into(),
+            Err(e) => zerror!("JSON error: {}", e).into(),
+        })
+    }
+
     fn _from_file(path: &Path) -> ZResult<Config> {
         match std::fs::File::open(path) {
             Ok(mut f) => {
@@ -845,6 +1241,72 @@ impl Config {
     }
 }
 
+fn field_path(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{prefix}/{field}")
+    }
+}
+
+/// Inserts the fields of `raw` one by one into `config`, collecting the errors of the fields
+/// which cannot be inserted along with their path.
+fn invalid_fields(config: &mut Config, raw: &Value, prefix: &str, errors: &mut Vec<String>) {
+    let Value::Object(fields) = raw else {
+        return;
+    };
+    let keys: Vec<String> = config.keys().collect();
+    for (field, value) in fields {
+        let path = field_path(prefix, field);
+        if !keys.contains(&path) {
+            errors.push(format!("{path}: unknown field"));
+            continue;
+        }
+        if let Err(e) = config.insert_json5(&path, &value.to_string()) {
+            let section = format!("{path}/");
+            if value.is_object() && keys.iter().any(|k| k.starts_with(&section)) {
+                invalid_fields(config, value, &path, errors);
+            } else {
+                errors.push(format!("{path}: {e}"));
+            }
+        }
+    }
+}
+
+/// Collects the path of the fields of `raw` which are missing from `parsed`, i.e. the fields
+/// ignored while parsing `raw`. Fields which are known `keys` but not serialized are skipped.
+fn unknown_fields(
+    raw: &Value,
+    parsed: &Value,
+    keys: &[String],
+    prefix: &str,
+    unknown: &mut Vec<String>,
+) {
+    match (raw, parsed) {
+        (Value::Object(raw), Value::Object(parsed)) => {
+            for (field, value) in raw {
+                // Plugins configurations are validated by the plugins themselves
+                if value.is_null() || (prefix.is_empty() && field == "plugins") {
+                    continue;
+                }
+                let path = field_path(prefix, field);
+                match parsed.get(field) {
+                    Some(parsed) => unknown_fields(value, parsed, keys, &path, unknown),
+                    None if keys.contains(&path) => {}
+                    None => unknown.push(path),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(parsed)) => {
+            for (i, (value, parsed)) in raw.iter().zip(parsed).enumerate() {
+                let path = field_path(prefix, &i.to_string());
+                unknown_fields(value, parsed, keys, &path, unknown);
+            }
+        }
+        _ => {}
+    }
+}
+
 impl std::fmt::Display for Config {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         serde_json::to_value(self)
@@ -870,6 +1332,129 @@ fn config_from_json() {
     println!("{}", serde_json::to_string_pretty(&config).unwrap());
 }
 
+#[test]
+fn config_env_overlay() {
+    assert_eq!(
+        Config::env_var_name("transport/link/tx/lease"),
+        "ZENOH_TRANSPORT_LINK_TX_LEASE"
+    );
+    let vars = HashMap::from([
+        ("ZENOH_MODE", "client"),
+        (
+            "ZENOH_CONNECT_ENDPOINTS",
+            "tcp/10.0.0.1:7447, tcp/10.0.0.2:7447",
+        ),
+        ("ZENOH_SCOUTING", "{ timeout: 1234 }"),
+        ("ZENOH_SCOUTING_DELAY", "42"),
+    ]);
+    let mut config = Config::default();
+    config
+        .apply_overlay(|var| vars.get(var).map(|value| value.to_string()))
+        .unwrap();
+    assert_eq!(*config.mode(), Some(WhatAmI::Client));
+    assert_eq!(
+        config
+            .connect()
+            .endpoints()
+            .get(WhatAmI::Client)
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(*config.scouting().timeout(), Some(1234));
+    assert_eq!(*config.scouting().delay(), Some(42));
+
+    let res = Config::default().apply_overlay(|var| {
+        (var == "ZENOH_TRANSPORT_LINK_TX_LEASE").then(|| "not a number".to_string())
+    });
+    assert!(res.is_err());
+}
+
+#[test]
+fn config_strict() {
+    let schema = Config::json_schema();
+    assert!(schema["properties"]["transport"].is_object());
+    assert!(schema["definitions"]["DownsamplingItemConf"].is_object());
+    Config::from_file_strict("../../DEFAULT_CONFIG.json5").unwrap();
+
+    let path =
+        std::env::temp_dir().join(format!("zenoh-config-strict-{}.json5", std::process::id()));
+    let load = |content: &str| {
+        std::fs::write(&path, content).unwrap();
+        let res = Config::from_file_strict(&path);
+        let _ = std::fs::remove_file(&path);
+        res
+    };
+    // Known fields which are not serialized
+    load(r#"{ transport: { link: { tls: { root_ca_certificate_base64: "Zm9v" } } } }"#).unwrap();
+    // Ignored by a lenient parsing
+    let err = load(
+        r#"{ downsampling: [{ flow: "egress", rules: [{ key_expr: "a/b", freq: 1, burst: 2 }] }] }"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        err.contains("downsampling/0/rules/0/burst: unknown field"),
+        "{err}"
+    );
+    // Rejected by a lenient parsing
+    let err =
+        load(r#"{ scouting: { timeout: "soon", multicast: { enabled: false, unknown: 1 } } }"#)
+            .unwrap_err()
+            .to_string();
+    assert!(err.contains("scouting/timeout:"), "{err}");
+    assert!(
+        err.contains("scouting/multicast/unknown: unknown field"),
+        "{err}"
+    );
+}
+
+#[test]
+fn config_include() {
+    let dir = std::env::temp_dir().join(format!("zenoh-config-include-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("site")).unwrap();
+    let write = |name: &str, content: &str| std::fs::write(dir.join(name), content).unwrap();
+    write(
+        "common.json5",
+        r#"{ scouting: { delay: 1, timeout: 2 }, listen: { endpoints: ["tcp/127.0.0.1:7447"] } }"#,
+    );
+    write("site/site.json5", r#"{ scouting: { delay: 3 } }"#);
+    write(
+        "device.json5",
+        r#"{ include: ["common.json5", "site/site.json5"], scouting: { timeout: 4 } }"#,
+    );
+    write("loop.json5", r#"{ include: "site/loop.json5" }"#);
+    write("site/loop.json5", r#"{ include: "../loop.json5" }"#);
+    let device = Config::from_file(dir.join("device.json5"));
+    let looping = Config::from_file(dir.join("loop.json5"));
+    let id = ZenohId::default();
+    let sourced = Config::from_file_with_sources(dir.join("device.json5"), Some(id));
+    let expected_sources: Vec<PathBuf> = ["device.json5", "common.json5", "site/site.json5"]
+        .iter()
+        .map(|name| dir.join(name).canonicalize().unwrap())
+        .collect();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let device = device.unwrap();
+    assert_eq!(*device.scouting().delay(), Some(3));
+    assert_eq!(*device.scouting().timeout(), Some(4));
+    assert_eq!(
+        device
+            .listen()
+            .endpoints()
+            .get(WhatAmI::Peer)
+            .unwrap()
+            .len(),
+        1
+    );
+    let err = looping.unwrap_err().to_string();
+    assert!(err.contains("loop detected"), "{err}");
+    let (sourced, sources) = sourced.unwrap();
+    assert_eq!(*sourced.id(), Some(id));
+    assert_eq!(*sourced.scouting().delay(), Some(3));
+    assert_eq!(sources, expected_sources);
+}
+
 fn sequence_number_resolution_validator(b: &Bits) -> bool {
     b <= &Bits::from(TransportSn::MAX)
 }
@@ -899,6 +1484,27 @@ fn queue_size_validator(q: &QueueSizeConf) -> bool {
         && check(background)
 }
 
+fn queue_weights_validator(w: &QueueWeightsConf) -> bool {
+    fn check(weight: &usize) -> bool {
+        (QueueWeightsConf::MIN..=QueueWeightsConf::MAX).contains(weight)
+    }
+
+    let QueueWeightsConf {
+        interactive_high,
+        interactive_low,
+        data_high,
+        data,
+        data_low,
+        background,
+    } = w;
+    check(interactive_high)
+        && check(interactive_low)
+        && check(data_high)
+        && check(data)
+        && check(data_low)
+        && check(background)
+}
+
 fn user_conf_validator(u: &UsrPwdConf) -> bool {
     (u.password().is_none() && u.user().is_none()) || (u.password().is_some() && u.user().is_some())
 }
@@ -1074,6 +1680,17 @@ impl serde::Serialize for PluginsConfig {
         value.serialize(serializer)
     }
 }
+impl schemars::JsonSchema for PluginsConfig {
+    fn schema_name() -> String {
+        "PluginsConfig".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        // Each plugin validates its own configuration
+        Map::<String, Value>::json_schema(gen)
+    }
+}
+
 impl Default for PluginsConfig {
     fn default() -> Self {
         Self {
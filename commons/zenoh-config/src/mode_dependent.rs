@@ -35,7 +35,7 @@ pub trait ModeDependent<T> {
     fn get_mut(&mut self, whatami: WhatAmI) -> Option<&mut T>;
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ModeValues<T> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub router: Option<T>,
@@ -86,6 +86,26 @@ impl<T> ModeDependentValue<T> {
     }
 }
 
+impl<T: schemars::JsonSchema> schemars::JsonSchema for ModeDependentValue<T> {
+    fn schema_name() -> String {
+        format!("ModeDependentValue_{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            subschemas: Some(Box::new(schemars::schema::SubschemaValidation {
+                any_of: Some(vec![
+                    gen.subschema_for::<T>(),
+                    gen.subschema_for::<ModeValues<T>>(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 impl<T> ModeDependent<T> for ModeDependentValue<T> {
     #[inline]
     fn router(&self) -> Option<&T> {
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use zenoh_keyexpr::keyexpr_tree::{IKeyExprTreeMut, KeBoxTree};
 use zenoh_protocol::core::{key_expr::OwnedKeyExpr, CongestionControl, Reliability};
 
-#[derive(Debug, Deserialize, Default, Serialize, Clone)]
+#[derive(Debug, Deserialize, Default, Serialize, Clone, schemars::JsonSchema)]
 pub struct PublisherQoSConfList(pub(crate) Vec<PublisherQoSConf>);
 
 impl From<PublisherQoSConfList> for KeBoxTree<PublisherQoSConfig> {
@@ -31,13 +31,13 @@ impl From<PublisherQoSConfList> for KeBoxTree<PublisherQoSConfig> {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, schemars::JsonSchema)]
 pub(crate) struct PublisherQoSConf {
     pub key_exprs: Vec<OwnedKeyExpr>,
     pub config: PublisherQoSConfig,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, schemars::JsonSchema)]
 pub struct PublisherQoSConfig {
     pub congestion_control: Option<PublisherCongestionControlConf>,
     pub priority: Option<PublisherPriorityConf>,
@@ -46,9 +46,11 @@ pub struct PublisherQoSConfig {
     pub reliability: Option<PublisherReliabilityConf>,
     #[cfg(feature = "unstable")]
     pub allowed_destination: Option<PublisherLocalityConf>,
+    #[cfg(feature = "unstable")]
+    pub hop_limit: Option<u8>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum PublisherCongestionControlConf {
     Drop,
@@ -73,7 +75,7 @@ impl From<CongestionControl> for PublisherCongestionControlConf {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PublisherPriorityConf {
     RealTime = 1,
@@ -85,7 +87,7 @@ pub enum PublisherPriorityConf {
     Background = 7,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PublisherReliabilityConf {
     BestEffort,
@@ -110,7 +112,7 @@ impl From<Reliability> for PublisherReliabilityConf {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PublisherLocalityConf {
     SessionLocal,
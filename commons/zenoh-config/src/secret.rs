@@ -0,0 +1,137 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! References to secrets stored outside of the configuration.
+//!
+//! Secret fields of the configuration (TLS keys and certificates, user passwords, ...) may be set
+//! to a reference instead of a literal value:
+//! - `env:<VAR>` is replaced by the value of the environment variable `<VAR>`,
+//! - `file:<PATH>` is replaced by the content of the file at `<PATH>`,
+//! - `literal:<VALUE>` is replaced by `<VALUE>` as is, e.g. `literal:env:x` for the value `env:x`.
+//!
+//! References are kept as is in the configuration and resolved by the components using them,
+//! so the secrets never appear in the configuration file, the logs or the admin space.
+use std::borrow::Cow;
+
+use secrecy::ExposeSecret;
+use zenoh_result::{zerror, ZResult};
+
+use crate::{SecretValue, TLSConf};
+
+/// The prefix of a reference to an environment variable.
+pub const ENV_PREFIX: &str = "env:";
+/// The prefix of a reference to a file.
+pub const FILE_PREFIX: &str = "file:";
+/// The prefix of a literal value, that is not resolved.
+pub const LITERAL_PREFIX: &str = "literal:";
+
+/// Returns `true` if `value` is a reference to a secret.
+pub fn is_reference(value: &str) -> bool {
+    [ENV_PREFIX, FILE_PREFIX]
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+}
+
+/// Returns the secret referenced by `value`, or `value` itself if it is not a reference.
+///
+/// Trailing newlines of files are removed.
+pub fn resolve(value: &str) -> ZResult<Cow<'_, str>> {
+    if let Some(var) = value.strip_prefix(ENV_PREFIX) {
+        let secret = std::env::var(var)
+            .map_err(|e| zerror!("Unable to resolve secret '{}': {}", value, e))?;
+        Ok(Cow::Owned(secret))
+    } else if let Some(path) = value.strip_prefix(FILE_PREFIX) {
+        let secret = std::fs::read_to_string(path)
+            .map_err(|e| zerror!("Unable to resolve secret '{}': {}", value, e))?;
+        Ok(Cow::Owned(trim_newlines(secret)))
+    } else if let Some(literal) = value.strip_prefix(LITERAL_PREFIX) {
+        Ok(Cow::Borrowed(literal))
+    } else {
+        Ok(Cow::Borrowed(value))
+    }
+}
+
+/// Resolves the optional value `value`, see [`resolve`].
+pub fn resolve_option(value: Option<&str>) -> ZResult<Option<Cow<'_, str>>> {
+    value.map(resolve).transpose()
+}
+
+fn trim_newlines(mut secret: String) -> String {
+    let len = secret.trim_end_matches(['\n', '\r']).len();
+    secret.truncate(len);
+    secret
+}
+
+/// The resolved keys and certificates of a TLS configuration.
+#[derive(Default)]
+pub struct TlsSecrets<'a> {
+    pub root_ca_certificate: Option<Cow<'a, str>>,
+    pub root_ca_certificate_base64: Option<Cow<'a, str>>,
+    pub listen_private_key: Option<Cow<'a, str>>,
+    pub listen_private_key_base64: Option<Cow<'a, str>>,
+    pub listen_certificate: Option<Cow<'a, str>>,
+    pub listen_certificate_base64: Option<Cow<'a, str>>,
+    pub connect_private_key: Option<Cow<'a, str>>,
+    pub connect_private_key_base64: Option<Cow<'a, str>>,
+    pub connect_certificate: Option<Cow<'a, str>>,
+    pub connect_certificate_base64: Option<Cow<'a, str>>,
+}
+
+impl<'a> TlsSecrets<'a> {
+    /// Resolves the keys and certificates of `conf`, see [`resolve`].
+    pub fn resolve(conf: &'a TLSConf) -> ZResult<Self> {
+        fn base64(value: &Option<SecretValue>) -> ZResult<Option<Cow<'_, str>>> {
+            resolve_option(value.as_ref().map(|s| s.expose_secret().as_str()))
+        }
+        Ok(Self {
+            root_ca_certificate: resolve_option(conf.root_ca_certificate().as_deref())?,
+            root_ca_certificate_base64: base64(conf.root_ca_certificate_base64())?,
+            listen_private_key: resolve_option(conf.listen_private_key().as_deref())?,
+            listen_private_key_base64: base64(conf.listen_private_key_base64())?,
+            listen_certificate: resolve_option(conf.listen_certificate().as_deref())?,
+            listen_certificate_base64: base64(conf.listen_certificate_base64())?,
+            connect_private_key: resolve_option(conf.connect_private_key().as_deref())?,
+            connect_private_key_base64: base64(conf.connect_private_key_base64())?,
+            connect_certificate: resolve_option(conf.connect_certificate().as_deref())?,
+            connect_certificate_base64: base64(conf.connect_certificate_base64())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_resolve() {
+        assert_eq!(resolve("literal").unwrap(), "literal");
+        assert_eq!(resolve("literal:env:HOME").unwrap(), "env:HOME");
+        assert!(!is_reference("C:\\keys\\key.pem"));
+        assert!(!is_reference("exec:echo"));
+
+        // Set by cargo when running the tests
+        assert_eq!(
+            resolve("env:CARGO_PKG_NAME").unwrap(),
+            env!("CARGO_PKG_NAME")
+        );
+        assert!(resolve("env:ZENOH_TEST_SECRET_UNSET").is_err());
+
+        let path = std::env::temp_dir().join(format!("zenoh-secret-{}", std::process::id()));
+        std::fs::write(&path, "from file\n").unwrap();
+        let res = resolve(&format!("file:{}", path.display())).map(Cow::into_owned);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(res.unwrap(), "from file");
+        assert!(resolve(&format!("file:{}", path.display())).is_err());
+    }
+}
@@ -16,7 +16,7 @@
 //! These wrappers are used to avoid exposing the the API necessary only for zenoh internals into the public API.
 
 use core::fmt;
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use zenoh_protocol::{
@@ -41,6 +41,16 @@ impl ZenohId {
     }
 }
 
+impl schemars::JsonSchema for ZenohId {
+    fn schema_name() -> String {
+        "ZenohId".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
 impl fmt::Debug for ZenohId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
@@ -101,35 +111,64 @@ impl FromStr for ZenohId {
 
 /// A zenoh Hello message.
 #[derive(Clone)]
-#[repr(transparent)]
-pub struct Hello(HelloProto);
+pub struct Hello {
+    inner: HelloProto,
+    #[cfg(feature = "unstable")]
+    latency: Option<Duration>,
+}
 
 impl Hello {
     /// Get the locators of this Hello message.
     pub fn locators(&self) -> &[Locator] {
-        &self.0.locators
+        &self.inner.locators
     }
 
     /// Get the zenoh id of this Hello message.
     pub fn zid(&self) -> ZenohId {
-        self.0.zid.into()
+        self.inner.zid.into()
     }
 
     /// Get the whatami of this Hello message.
     pub fn whatami(&self) -> WhatAmI {
-        self.0.whatami
+        self.inner.whatami
+    }
+
+    /// Get the estimated latency to the first locator of this Hello message.
+    ///
+    /// Only set when the locators were probed for reachability, in which case the locators are
+    /// the reachable ones sorted by increasing latency.
+    #[zenoh_macros::unstable]
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
     }
 }
 
 impl From<HelloProto> for Hello {
     fn from(inner: HelloProto) -> Self {
-        Hello(inner)
+        Hello {
+            inner,
+            #[cfg(feature = "unstable")]
+            latency: None,
+        }
+    }
+}
+
+impl From<(HelloProto, Duration)> for Hello {
+    /// Builds a Hello whose locators were probed, the latency being only exposed by the unstable
+    /// API.
+    #[cfg_attr(not(feature = "unstable"), allow(unused_variables))]
+    fn from((inner, latency): (HelloProto, Duration)) -> Self {
+        Hello {
+            inner,
+            #[cfg(feature = "unstable")]
+            latency: Some(latency),
+        }
     }
 }
 
 impl fmt::Debug for Hello {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(&self.0, f)
+        fmt::Debug::fmt(&self.inner, f)
     }
 }
 
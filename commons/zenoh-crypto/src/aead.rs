@@ -0,0 +1,147 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use aes_gcm::{
+    aead::{Aead, AeadInPlace, Payload},
+    Aes256Gcm, KeyInit, Nonce, Tag,
+};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use zenoh_result::{bail, zerror, ZResult};
+
+/// An authenticated cipher, taking the nonce from a counter: the caller must never use the same
+/// counter twice with the same key.
+pub struct AeadCipher {
+    inner: Aes256Gcm,
+}
+
+impl AeadCipher {
+    pub const KEY_SIZE: usize = 32;
+    pub const NONCE_SIZE: usize = 12;
+    pub const TAG_SIZE: usize = 16;
+
+    pub fn new(key: [u8; Self::KEY_SIZE]) -> AeadCipher {
+        AeadCipher {
+            inner: Aes256Gcm::new(&key.into()),
+        }
+    }
+
+    /// Encrypts the bytes in place with the nonce derived from `counter`, returning the tag.
+    pub fn seal_in_place(&self, counter: u64, bytes: &mut [u8]) -> ZResult<[u8; Self::TAG_SIZE]> {
+        let tag = self
+            .inner
+            .encrypt_in_place_detached(Nonce::from_slice(&Self::nonce(counter)), &[], bytes)
+            .map_err(|e| zerror!("Encryption failed: {}", e))?;
+        Ok(tag.into())
+    }
+
+    /// Decrypts in place the bytes sealed by [`AeadCipher::seal_in_place`] with the same counter.
+    pub fn open_in_place(&self, counter: u64, bytes: &mut [u8], tag: &[u8]) -> ZResult<()> {
+        if tag.len() != Self::TAG_SIZE {
+            bail!("Invalid tag length to decrypt: {}", tag.len());
+        }
+        self.inner
+            .decrypt_in_place_detached(
+                Nonce::from_slice(&Self::nonce(counter)),
+                &[],
+                bytes,
+                Tag::from_slice(tag),
+            )
+            .map_err(|e| zerror!("Decryption failed: {}", e))?;
+        Ok(())
+    }
+
+    fn nonce(counter: u64) -> [u8; Self::NONCE_SIZE] {
+        let mut nonce = [0u8; Self::NONCE_SIZE];
+        nonce[Self::NONCE_SIZE - 8..].copy_from_slice(&counter.to_be_bytes());
+        nonce
+    }
+}
+
+/// An authenticated cipher, sealing the bytes along with a random nonce.
+///
+/// Its 192-bit nonces are large enough to be drawn at random for any number of messages sealed
+/// with the same key, unlike the 96-bit nonces of [`AeadCipher`].
+pub struct XAeadCipher {
+    inner: XChaCha20Poly1305,
+}
+
+impl XAeadCipher {
+    pub const KEY_SIZE: usize = 32;
+    pub const NONCE_SIZE: usize = 24;
+
+    pub fn new(key: [u8; Self::KEY_SIZE]) -> XAeadCipher {
+        XAeadCipher {
+            inner: XChaCha20Poly1305::new(&key.into()),
+        }
+    }
+
+    /// Encrypts the bytes, authenticating them along with the associated data.
+    pub fn seal(&self, bytes: &[u8], aad: &[u8]) -> ZResult<Vec<u8>> {
+        let mut nonce = [0u8; Self::NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let sealed = self
+            .inner
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: bytes, aad })
+            .map_err(|e| zerror!("Encryption failed: {}", e))?;
+        let mut res = Vec::with_capacity(Self::NONCE_SIZE + sealed.len());
+        res.extend_from_slice(&nonce);
+        res.extend_from_slice(&sealed);
+        Ok(res)
+    }
+
+    /// Decrypts the bytes sealed with the same key and associated data.
+    pub fn open(&self, bytes: &[u8], aad: &[u8]) -> ZResult<Vec<u8>> {
+        if bytes.len() < Self::NONCE_SIZE {
+            bail!("Invalid bytes length to decrypt: {}", bytes.len());
+        }
+        let (nonce, sealed) = bytes.split_at(Self::NONCE_SIZE);
+        let res = self
+            .inner
+            .decrypt(XNonce::from_slice(nonce), Payload { msg: sealed, aad })
+            .map_err(|e| zerror!("Decryption failed: {}", e))?;
+        Ok(res)
+    }
+}
+
+mod tests {
+    #[test]
+    fn aead_cipher() {
+        use super::XAeadCipher;
+
+        let cipher = XAeadCipher::new([1; XAeadCipher::KEY_SIZE]);
+        let sealed = cipher.seal(b"payload", b"key/expr").unwrap();
+        assert_ne!(&sealed[XAeadCipher::NONCE_SIZE..], b"payload");
+        assert_eq!(cipher.open(&sealed, b"key/expr").unwrap(), b"payload");
+        // The associated data and the key are authenticated
+        assert!(cipher.open(&sealed, b"other/expr").is_err());
+        let other = XAeadCipher::new([2; XAeadCipher::KEY_SIZE]);
+        assert!(other.open(&sealed, b"key/expr").is_err());
+        assert!(cipher.open(&sealed[..4], b"key/expr").is_err());
+    }
+
+    #[test]
+    fn aead_cipher_in_place() {
+        use super::AeadCipher;
+
+        let cipher = AeadCipher::new([1; AeadCipher::KEY_SIZE]);
+        let mut bytes = *b"payload";
+        let tag = cipher.seal_in_place(7, &mut bytes).unwrap();
+        assert_ne!(&bytes, b"payload");
+        // The counter is authenticated
+        let mut other = bytes;
+        assert!(cipher.open_in_place(8, &mut other, &tag).is_err());
+        cipher.open_in_place(7, &mut bytes, &tag).unwrap();
+        assert_eq!(&bytes, b"payload");
+    }
+}
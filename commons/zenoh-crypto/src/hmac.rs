@@ -24,3 +24,10 @@ pub fn sign(key: &[u8], data: &[u8]) -> ZResult<Vec<u8>> {
 pub fn digest(data: &[u8]) -> Vec<u8> {
     Sha3_256::digest(data).as_slice().to_vec()
 }
+
+/// Derives a key from a password and a salt with PBKDF2-HMAC-SHA3-256.
+pub fn derive_key<const N: usize>(password: &[u8], salt: &[u8], rounds: u32) -> [u8; N] {
+    let mut key = [0u8; N];
+    pbkdf2::pbkdf2_hmac::<Sha3_256>(password, salt, rounds, &mut key);
+    key
+}
@@ -17,9 +17,12 @@
 //! This crate is intended for Zenoh's internal use.
 //!
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+mod aead;
 mod cipher;
 pub mod hmac;
 mod prng;
+pub mod signature;
 
+pub use aead::*;
 pub use cipher::*;
 pub use prng::*;
@@ -0,0 +1,78 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier, VerifyingKey};
+use zenoh_result::{zerror, ZResult};
+
+/// An Ed25519 signer.
+#[derive(Clone)]
+pub struct Signer {
+    inner: SigningKey,
+}
+
+impl Signer {
+    pub const SECRET_KEY_SIZE: usize = 32;
+    pub const PUBLIC_KEY_SIZE: usize = 32;
+    pub const SIGNATURE_SIZE: usize = 64;
+
+    pub fn generate() -> Signer {
+        Signer {
+            inner: SigningKey::generate(&mut rand::thread_rng()),
+        }
+    }
+
+    pub fn from_bytes(secret_key: &[u8; Self::SECRET_KEY_SIZE]) -> Signer {
+        Signer {
+            inner: SigningKey::from_bytes(secret_key),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SECRET_KEY_SIZE] {
+        self.inner.to_bytes()
+    }
+
+    pub fn public_key(&self) -> [u8; Self::PUBLIC_KEY_SIZE] {
+        self.inner.verifying_key().to_bytes()
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> [u8; Self::SIGNATURE_SIZE] {
+        self.inner.sign(msg).to_bytes()
+    }
+}
+
+/// Verifies the Ed25519 signature of the message by the given public key.
+pub fn verify(
+    public_key: &[u8; Signer::PUBLIC_KEY_SIZE],
+    msg: &[u8],
+    signature: &[u8; Signer::SIGNATURE_SIZE],
+) -> ZResult<()> {
+    let key = VerifyingKey::from_bytes(public_key).map_err(|e| zerror!("Invalid key: {}", e))?;
+    key.verify(msg, &Signature::from_bytes(signature))
+        .map_err(|e| zerror!("Invalid signature: {}", e).into())
+}
+
+mod tests {
+    #[test]
+    fn signer() {
+        use super::{verify, Signer};
+
+        let signer = Signer::generate();
+        let signature = signer.sign(b"message");
+        assert!(verify(&signer.public_key(), b"message", &signature).is_ok());
+        assert!(verify(&signer.public_key(), b"other", &signature).is_err());
+        let other = Signer::generate();
+        assert!(verify(&other.public_key(), b"message", &signature).is_err());
+        let restored = Signer::from_bytes(&signer.to_bytes());
+        assert_eq!(restored.public_key(), signer.public_key());
+    }
+}
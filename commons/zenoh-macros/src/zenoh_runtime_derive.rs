@@ -137,7 +137,7 @@ fn generate_declare_param(
         )*
 
         // An internal helper struct for parsing the RuntimeParam
-        #[derive(Deserialize, Debug, Clone, Copy)]
+        #[derive(Deserialize, Debug, Clone)]
         #[serde(deny_unknown_fields)]
         struct AbstractRuntimeParam {
             #(
@@ -291,7 +291,7 @@ pub(crate) fn derive_generic_runtime_param(input: DeriveInput) -> Result<TokenSt
         use std::marker::PhantomData;
 
         // Declare a helper struct to be generic over any T implementing DefaultParam
-        #[derive(Deserialize, Debug, Clone, Copy)]
+        #[derive(Deserialize, Debug, Clone)]
         #[serde(deny_unknown_fields, default)]
         struct #helper_name<T>
         where
@@ -109,6 +109,7 @@ pub struct DidntConvert;
 
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ZExtUnit<const ID: u8>;
 
 impl<const ID: u8> Default for ZExtUnit<{ ID }> {
@@ -166,6 +167,7 @@ impl<const ID: u8> TryFrom<ZExtUnknown> for ZExtUnit<{ ID }> {
 
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ZExtZ64<const ID: u8> {
     pub value: u64,
 }
@@ -223,6 +225,7 @@ impl<const ID: u8> TryFrom<ZExtUnknown> for ZExtZ64<{ ID }> {
 
 #[repr(transparent)]
 #[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ZExtZBuf<const ID: u8> {
     pub value: ZBuf,
 }
@@ -308,6 +311,7 @@ impl<const ID: u8> Debug for ZExtZBufHeader<{ ID }> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ZExtBody {
     Unit,
     Z64(u64),
@@ -371,6 +375,29 @@ impl ZExtUnknown {
         let body = ZExtBody::rand();
         Self::new(id, mandatory, body)
     }
+
+    /// Builds an arbitrary extension with an id not lower than `start`, so that it does not
+    /// collide with the extensions known by the message.
+    #[cfg(feature = "arbitrary")]
+    pub fn arbitrary2(
+        u: &mut arbitrary::Unstructured<'_>,
+        start: u8,
+        mandatory: bool,
+    ) -> arbitrary::Result<Self> {
+        let id: u8 = u.int_in_range(start..=iext::ID_MASK)?;
+        let body: ZExtBody = u.arbitrary()?;
+        Ok(Self::new(id, mandatory, body))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ZExtUnknown {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let id: u8 = u.int_in_range(0x00..=iext::ID_MASK)?;
+        let mandatory: bool = u.arbitrary()?;
+        let body: ZExtBody = u.arbitrary()?;
+        Ok(Self::new(id, mandatory, body))
+    }
 }
 
 impl Debug for ZExtUnknown {
@@ -417,6 +444,42 @@ impl<const ID: u8> From<ZExtZBuf<{ ID }>> for ZExtUnknown {
     }
 }
 
+/// # Vendor extensions
+///
+/// A vendor extension is a message extension defined by a downstream product rather than by the
+/// zenoh protocol. It is carried as a [`ZExtUnknown`] by the messages that keep their unknown
+/// extensions (i.e. `Put`, `Del`, `Query`, `Reply` and `Err`), and it is forwarded unchanged by the
+/// nodes that do not know it.
+///
+/// A mandatory vendor extension makes the nodes that do not know it drop the message, so it
+/// should only be used once the extension has been registered in the codec of every node that
+/// may receive it.
+pub trait ZExtVendor: Sized {
+    /// The id of the extension, which must not collide with the ids of the extensions defined
+    /// by the zenoh protocol for the messages it is attached to.
+    const ID: u8;
+    /// Whether the nodes that do not know the extension must drop the message.
+    const MANDATORY: bool;
+
+    /// Encodes the extension into its body.
+    fn encode(&self) -> ZExtBody;
+
+    /// Decodes the extension from its body, returning `None` if the body is malformed.
+    fn decode(body: &ZExtBody) -> Option<Self>;
+
+    /// Returns the extension as it is carried by the messages.
+    fn to_unknown(&self) -> ZExtUnknown {
+        ZExtUnknown::new(Self::ID, Self::MANDATORY, self.encode())
+    }
+
+    /// Returns the first extension of `exts` that is this extension, if any, decoded.
+    fn from_unknowns(exts: &[ZExtUnknown]) -> Option<Self> {
+        exts.iter()
+            .find(|e| iext::mid(e.id) == Self::ID & iext::ID_MASK)
+            .and_then(|e| Self::decode(&e.body))
+    }
+}
+
 // Macros
 #[macro_export]
 macro_rules! zextunit {
@@ -77,3 +77,18 @@ impl Encoding {
         Encoding { id, schema }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Encoding {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let id: EncodingId = u.arbitrary()?;
+        // The schema length is encoded on a single byte
+        let schema = if u.arbitrary()? {
+            let len = u.int_in_range(0..=u8::MAX as usize)?;
+            Some(u.bytes(len)?.to_vec().into())
+        } else {
+            None
+        };
+        Ok(Encoding { id, schema })
+    }
+}
@@ -689,6 +689,28 @@ impl EndPoint {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for EndPoint {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let proto: &str = u.arbitrary()?;
+        let address: &str = u.arbitrary()?;
+        let mut endpoint = format!("{proto}{PROTO_SEPARATOR}{address}");
+
+        if u.arbitrary()? {
+            endpoint.push(METADATA_SEPARATOR);
+            endpoint.push_str(u.arbitrary()?);
+        }
+        if u.arbitrary()? {
+            endpoint.push(CONFIG_SEPARATOR);
+            endpoint.push_str(u.arbitrary()?);
+        }
+
+        endpoint
+            .parse()
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
 #[test]
 fn endpoints() {
     assert!(EndPoint::from_str("/").is_err());
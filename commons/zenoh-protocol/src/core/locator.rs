@@ -128,3 +128,10 @@ impl Locator {
         EndPoint::rand().into()
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Locator {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(u.arbitrary::<EndPoint>()?.into())
+    }
+}
@@ -91,6 +91,25 @@ impl Default for ZenohIdProto {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ZenohIdProto {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(1..=Self::MAX_SIZE)?;
+        // An all-zero id is invalid
+        Self::try_from(u.bytes(len)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+/// Builds an arbitrary [`Timestamp`], which `uhlc` does not implement [`arbitrary::Arbitrary`] for.
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_timestamp(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<Timestamp> {
+    let time = NTP64(u.arbitrary()?);
+    let id: ZenohIdProto = u.arbitrary()?;
+    Ok(Timestamp::new(time, (&id).into()))
+}
+
 // Mimics uhlc::SizeError,
 #[derive(Debug, Clone, Copy)]
 pub struct SizeError(usize);
@@ -280,6 +299,7 @@ pub type EntityId = u32;
 
 /// The global unique id of a zenoh entity.
 #[derive(Debug, Default, Copy, Clone, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntityGlobalIdProto {
     pub zid: ZenohIdProto,
     pub eid: EntityId,
@@ -298,6 +318,7 @@ impl EntityGlobalIdProto {
 
 #[repr(u8)]
 #[derive(Debug, Default, Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Priority {
     Control = 0,
     RealTime = 1,
@@ -351,6 +372,18 @@ impl PriorityRange {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PriorityRange {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let start = u.int_in_range(Priority::MAX as u8..=Priority::MIN as u8 - 1)?;
+        let end = u.int_in_range((start + 1)..=Priority::MIN as u8)?;
+
+        Ok(Self(
+            Priority::try_from(start).unwrap()..=Priority::try_from(end).unwrap(),
+        ))
+    }
+}
+
 impl Display for PriorityRange {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}-{}", *self.start() as u8, *self.end() as u8)
@@ -455,6 +488,7 @@ impl TryFrom<u8> for Priority {
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 pub enum Reliability {
     BestEffort = 0,
@@ -561,6 +595,7 @@ impl Channel {
 
 /// Congestion control strategy.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 pub enum CongestionControl {
     #[default]
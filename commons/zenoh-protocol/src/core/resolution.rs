@@ -149,6 +149,14 @@ impl Resolution {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Resolution {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let v: u8 = u.arbitrary()?;
+        Ok(Self(v & 0b00001111))
+    }
+}
+
 impl Default for Resolution {
     fn default() -> Self {
         let frame_sn = Bits::from(TransportSn::MAX) as u8;
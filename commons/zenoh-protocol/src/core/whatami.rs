@@ -19,6 +19,7 @@ use zenoh_result::{bail, ZError};
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum WhatAmI {
     Router = 0b001,
     #[default]
@@ -170,6 +171,23 @@ impl WhatAmIMatcher {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for WhatAmIMatcher {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut waim = WhatAmIMatcher::empty();
+        if u.arbitrary()? {
+            waim = waim.router();
+        }
+        if u.arbitrary()? {
+            waim = waim.peer();
+        }
+        if u.arbitrary()? {
+            waim = waim.client();
+        }
+        Ok(waim)
+    }
+}
+
 impl TryFrom<u8> for WhatAmIMatcher {
     type Error = ();
 
@@ -256,3 +256,17 @@ impl WireExpr<'_> {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for WireExpr<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let scope: ExprId = u.arbitrary()?;
+        let suffix: String = u.arbitrary()?;
+
+        Ok(WireExpr {
+            scope,
+            suffix: suffix.into(),
+            mapping: Mapping::DEFAULT,
+        })
+    }
+}
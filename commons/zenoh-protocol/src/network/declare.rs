@@ -50,6 +50,7 @@ pub mod flag {
 /// +---------------+
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Declare {
     pub interest_id: Option<super::interest::InterestId>,
     pub ext_qos: ext::QoSType,
@@ -91,6 +92,7 @@ pub mod id {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DeclareBody {
     DeclareKeyExpr(DeclareKeyExpr),
     UndeclareKeyExpr(UndeclareKeyExpr),
@@ -167,6 +169,7 @@ pub mod common {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct DeclareFinal;
 
     impl DeclareFinal {
@@ -195,6 +198,7 @@ pub mod common {
         /// ```
         pub type WireExprExt = zextzbuf!(0x0f, true);
         #[derive(Debug, Clone, PartialEq, Eq)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub struct WireExprType {
             pub wire_expr: WireExpr<'static>,
         }
@@ -253,6 +257,7 @@ pub mod keyexpr {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct DeclareKeyExpr {
         pub id: ExprId,
         pub wire_expr: WireExpr<'static>,
@@ -287,6 +292,7 @@ pub mod keyexpr {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct UndeclareKeyExpr {
         pub id: ExprId,
     }
@@ -338,6 +344,7 @@ pub mod subscriber {
     /// - if R==1 then the subscription is reliable, else it is best effort    ///
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct DeclareSubscriber {
         pub id: SubscriberId,
         pub wire_expr: WireExpr<'static>,
@@ -372,6 +379,7 @@ pub mod subscriber {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct UndeclareSubscriber {
         pub id: SubscriberId,
         pub ext_wire_expr: common::ext::WireExprType,
@@ -428,6 +436,7 @@ pub mod queryable {
     /// - if D==1 then the queryable distance is present
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct DeclareQueryable {
         pub id: QueryableId,
         pub wire_expr: WireExpr<'static>,
@@ -452,17 +461,25 @@ pub mod queryable {
         /// +---------------+
         /// ~ distance <z16>~
         /// +---------------+
+        /// ~  load  <u8>   ~
+        /// +---------------+
         /// ```
+        ///
+        /// The load is encoded in bits 24 to 31 of the extension value, so that it is ignored
+        /// by the implementations which don't support it.
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub struct QueryableInfoType {
             pub complete: bool, // Default false: incomplete
             pub distance: u16,  // Default 0: no distance
+            pub load: u8,       // Default 0: unloaded
         }
 
         impl QueryableInfoType {
             pub const DEFAULT: Self = Self {
                 complete: false,
                 distance: 0,
+                load: 0,
             };
 
             #[cfg(feature = "test")]
@@ -471,8 +488,13 @@ pub mod queryable {
                 let mut rng = rand::thread_rng();
                 let complete: bool = rng.gen_bool(0.5);
                 let distance: u16 = rng.gen();
+                let load: u8 = rng.gen();
 
-                Self { complete, distance }
+                Self {
+                    complete,
+                    distance,
+                    load,
+                }
             }
         }
 
@@ -517,6 +539,7 @@ pub mod queryable {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct UndeclareQueryable {
         pub id: QueryableId,
         pub ext_wire_expr: common::ext::WireExprType,
@@ -568,6 +591,7 @@ pub mod token {
     ///
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct DeclareToken {
         pub id: TokenId,
         pub wire_expr: WireExpr<'static>,
@@ -602,6 +626,7 @@ pub mod token {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct UndeclareToken {
         pub id: TokenId,
         pub ext_wire_expr: common::ext::WireExprType,
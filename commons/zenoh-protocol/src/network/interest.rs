@@ -154,6 +154,7 @@ pub type DeclareRequestId = u32;
 pub type AtomicDeclareRequestId = AtomicU32;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum InterestMode {
     Final,
     Current,
@@ -238,6 +239,33 @@ impl Interest {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Interest {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let id: InterestId = u.arbitrary()?;
+        let mode: InterestMode = u.arbitrary()?;
+        // The options and the key expression are not encoded for a final interest
+        let (options, wire_expr) = if mode == InterestMode::Final {
+            (InterestOptions::empty(), None)
+        } else {
+            (u.arbitrary()?, u.arbitrary()?)
+        };
+        let ext_qos: ext::QoSType = u.arbitrary()?;
+        let ext_tstamp: Option<ext::TimestampType> = u.arbitrary()?;
+        let ext_nodeid: ext::NodeIdType = u.arbitrary()?;
+
+        Ok(Self {
+            id,
+            mode,
+            wire_expr,
+            options,
+            ext_qos,
+            ext_tstamp,
+            ext_nodeid,
+        })
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone, Copy)]
 pub struct InterestOptions {
@@ -323,6 +351,26 @@ impl InterestOptions {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for InterestOptions {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // The restricted, named and mapping options are derived from the key expression
+        let mut s = Self::empty();
+        for o in [
+            InterestOptions::KEYEXPRS,
+            InterestOptions::SUBSCRIBERS,
+            InterestOptions::QUERYABLES,
+            InterestOptions::TOKENS,
+            InterestOptions::AGGREGATE,
+        ] {
+            if u.arbitrary()? {
+                s += o;
+            }
+        }
+        Ok(s)
+    }
+}
+
 impl PartialEq for InterestOptions {
     fn eq(&self, other: &Self) -> bool {
         self.keyexprs() == other.keyexprs()
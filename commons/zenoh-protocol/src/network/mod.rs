@@ -46,6 +46,7 @@ pub mod id {
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Mapping {
     #[default]
     Receiver = 0,
@@ -70,6 +71,7 @@ impl Mapping {
 
 // Zenoh messages at zenoh-network level
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum NetworkBody {
     Push(Push),
     Request(Request),
@@ -157,6 +159,15 @@ impl NetworkMessage {
             NetworkBody::OAM(msg) => msg.ext_qos.get_priority(),
         }
     }
+
+    /// The time after which the message is stale and must not be transmitted, if any.
+    #[inline]
+    pub fn deadline(&self) -> Option<push::ext::DeadlineType> {
+        match &self.body {
+            NetworkBody::Push(msg) => msg.ext_deadline,
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for NetworkMessage {
@@ -186,6 +197,14 @@ impl From<NetworkBody> for NetworkMessage {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for NetworkMessage {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // The reliability is not part of the message encoding
+        Ok(u.arbitrary::<NetworkBody>()?.into())
+    }
+}
+
 impl From<Declare> for NetworkMessage {
     fn from(declare: Declare) -> Self {
         NetworkBody::Declare(declare).into()
@@ -240,6 +259,7 @@ pub mod ext {
     /// ```
     #[repr(transparent)]
     #[derive(Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct QoSType<const ID: u8> {
         inner: u8,
     }
@@ -373,6 +393,14 @@ pub mod ext {
         }
     }
 
+    #[cfg(feature = "arbitrary")]
+    impl<'a, const ID: u8> arbitrary::Arbitrary<'a> for TimestampType<{ ID }> {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            let timestamp = crate::core::arbitrary_timestamp(u)?;
+            Ok(Self { timestamp })
+        }
+    }
+
     /// ```text
     ///  7 6 5 4 3 2 1 0
     /// +-+-+-+-+-+-+-+-+
@@ -382,6 +410,7 @@ pub mod ext {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct NodeIdType<const ID: u8> {
         pub node_id: u16,
     }
@@ -430,6 +459,7 @@ pub mod ext {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct EntityGlobalIdType<const ID: u8> {
         pub zid: ZenohIdProto,
         pub eid: EntityId,
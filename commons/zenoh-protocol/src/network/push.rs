@@ -44,6 +44,9 @@ pub struct Push {
     pub ext_qos: ext::QoSType,
     pub ext_tstamp: Option<ext::TimestampType>,
     pub ext_nodeid: ext::NodeIdType,
+    pub ext_hops: ext::HopsType,
+    pub ext_hop_limit: Option<ext::HopLimitType>,
+    pub ext_deadline: Option<ext::DeadlineType>,
     pub payload: PushBody,
 }
 
@@ -61,6 +64,23 @@ pub mod ext {
 
     pub type NodeId = zextz64!(0x3, true);
     pub type NodeIdType = crate::network::ext::NodeIdType<{ NodeId::ID }>;
+
+    /// The number of links the message traversed, incremented by each node receiving it.
+    /// Not encoded when 0, i.e. when the message has been generated by the node itself, nor
+    /// without the `sample_metadata` feature.
+    pub type Hops = zextz64!(0x4, false);
+    pub type HopsType = u8;
+
+    /// The number of links the message may still traverse, decremented by each node forwarding
+    /// it on a link. A message with a hop limit of 0 is only delivered within the node.
+    /// Not encoded when there is no limit.
+    pub type HopLimit = zextz64!(0x5, false);
+    pub type HopLimitType = u8;
+
+    /// The time after which the message is stale and is dropped instead of being transmitted,
+    /// as an NTP64 time since the UNIX epoch. Not encoded when there is no deadline.
+    pub type Deadline = zextz64!(0x6, false);
+    pub type DeadlineType = crate::core::NTP64;
 }
 
 impl Push {
@@ -74,6 +94,13 @@ impl Push {
         let ext_qos = ext::QoSType::rand();
         let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
         let ext_nodeid = ext::NodeIdType::rand();
+        let ext_hops = if cfg!(feature = "sample_metadata") {
+            rng.gen()
+        } else {
+            0
+        };
+        let ext_hop_limit = rng.gen_bool(0.5).then(|| rng.gen());
+        let ext_deadline = rng.gen_bool(0.5).then(|| uhlc::NTP64(rng.gen()));
 
         Self {
             wire_expr,
@@ -81,6 +108,38 @@ impl Push {
             ext_tstamp,
             ext_qos,
             ext_nodeid,
+            ext_hops,
+            ext_hop_limit,
+            ext_deadline,
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Push {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let wire_expr: WireExpr<'static> = u.arbitrary()?;
+        let payload: PushBody = u.arbitrary()?;
+        let ext_qos: ext::QoSType = u.arbitrary()?;
+        let ext_tstamp: Option<ext::TimestampType> = u.arbitrary()?;
+        let ext_nodeid: ext::NodeIdType = u.arbitrary()?;
+        let ext_hops: ext::HopsType = if cfg!(feature = "sample_metadata") {
+            u.arbitrary()?
+        } else {
+            0
+        };
+        let ext_hop_limit: Option<ext::HopLimitType> = u.arbitrary()?;
+        let ext_deadline = u.arbitrary::<Option<u64>>()?.map(uhlc::NTP64);
+
+        Ok(Self {
+            wire_expr,
+            payload,
+            ext_tstamp,
+            ext_qos,
+            ext_nodeid,
+            ext_hops,
+            ext_hop_limit,
+            ext_deadline,
+        })
+    }
+}
@@ -67,6 +67,8 @@ pub struct Request {
 pub mod ext {
     use core::{num::NonZeroU32, time::Duration};
 
+    #[cfg(feature = "unstable")]
+    use crate::core::ZenohIdProto;
     use crate::{
         common::{ZExtZ64, ZExtZBuf},
         zextz64, zextzbuf,
@@ -92,6 +94,7 @@ pub mod ext {
     // The `zenoh::queryable::Queryable`s that should be target of a `zenoh::Session::get()`.
     #[repr(u8)]
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub enum QueryTarget {
         /// Let Zenoh find the BestMatching queryable capabale of serving the query.
         #[default]
@@ -100,8 +103,24 @@ pub mod ext {
         All,
         /// Deliver the query to all queryables matching the query's key expression that are declared as complete.
         AllComplete,
+        /// Deliver the query only to the queryables matching the query's key expression declared by the
+        /// zenoh node with the given id.
+        #[cfg(feature = "unstable")]
+        Specific(ZenohIdProto),
     }
 
+    pub type TargetZid = zextzbuf!(0x7, false);
+    // ```text
+    // - TargetZid (0x07), replaces the Target extension for a QueryTarget::Specific.
+    //   Only sent on transports whose negotiated patch supports it.
+    //  7 6 5 4 3 2 1 0
+    // +-+-+-+-+-+-+-+-+
+    // |zid_len|X|X|X|X|
+    // +-------+-+-+---+
+    // ~      zid      ~
+    // +---------------+
+    // ```
+
     impl QueryTarget {
         pub const DEFAULT: Self = Self::BestMatching;
 
@@ -114,6 +133,8 @@ pub mod ext {
                 QueryTarget::All,
                 QueryTarget::AllComplete,
                 QueryTarget::BestMatching,
+                #[cfg(feature = "unstable")]
+                QueryTarget::Specific(ZenohIdProto::rand()),
             ]
             .choose(&mut rng)
             .unwrap()
@@ -168,3 +189,33 @@ impl Request {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Request {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let wire_expr: WireExpr<'static> = u.arbitrary()?;
+        let id: RequestId = u.arbitrary()?;
+        let payload: RequestBody = u.arbitrary()?;
+        let ext_qos: ext::QoSType = u.arbitrary()?;
+        let ext_tstamp: Option<ext::TimestampType> = u.arbitrary()?;
+        let ext_nodeid: ext::NodeIdType = u.arbitrary()?;
+        let ext_target: ext::QueryTarget = u.arbitrary()?;
+        let ext_budget: Option<ext::BudgetType> = u.arbitrary()?;
+        // The timeout is encoded in milliseconds
+        let ext_timeout = u
+            .arbitrary::<Option<u64>>()?
+            .map(ext::TimeoutType::from_millis);
+
+        Ok(Self {
+            wire_expr,
+            id,
+            payload,
+            ext_qos,
+            ext_tstamp,
+            ext_nodeid,
+            ext_target,
+            ext_budget,
+            ext_timeout,
+        })
+    }
+}
@@ -25,6 +25,7 @@ pub mod id {
 
 // Zenoh messages at scouting level
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ScoutingBody {
     Scout(Scout),
     Hello(HelloProto),
@@ -63,6 +64,13 @@ impl From<ScoutingBody> for ScoutingMessage {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ScoutingMessage {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(u.arbitrary::<ScoutingBody>()?.into())
+    }
+}
+
 impl From<Scout> for ScoutingMessage {
     fn from(scout: Scout) -> Self {
         ScoutingBody::Scout(scout).into()
@@ -72,6 +72,7 @@ pub mod flag {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Scout {
     pub version: u8,
     pub what: WhatAmIMatcher,
@@ -109,8 +109,32 @@ impl Frame {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Frame {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let reliability: Reliability = u.arbitrary()?;
+        let sn: TransportSn = u.arbitrary()?;
+        let ext_qos: ext::QoSType = u.arbitrary()?;
+        // The messages take the reliability of the frame they are decoded from
+        let mut payload = Vec::new();
+        for _ in 0..u.int_in_range(1..=3)? {
+            let mut m: NetworkMessage = u.arbitrary()?;
+            m.reliability = reliability;
+            payload.push(m);
+        }
+
+        Ok(Frame {
+            reliability,
+            sn,
+            ext_qos,
+            payload,
+        })
+    }
+}
+
 // FrameHeader
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FrameHeader {
     pub reliability: Reliability,
     pub sn: TransportSn,
@@ -117,6 +117,7 @@ pub mod flag {
 /// ZExtUnit
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct InitSyn {
     pub version: u8,
     pub whatami: WhatAmI,
@@ -131,6 +132,7 @@ pub struct InitSyn {
     pub ext_mlink: Option<ext::MultiLink>,
     pub ext_lowlatency: Option<ext::LowLatency>,
     pub ext_compression: Option<ext::Compression>,
+    pub ext_noise: Option<ext::Noise>,
     pub ext_patch: ext::PatchType,
 }
 
@@ -173,6 +175,10 @@ pub mod ext {
     /// if >= 1, then fragmentation first/drop markers
     pub type Patch = zextz64!(0x7, false);
     pub type PatchType = crate::transport::ext::PatchType<{ Patch::ID }>;
+
+    /// # Noise extension
+    /// Used to carry the Noise handshake messages establishing the keys of an encrypted link
+    pub type Noise = zextzbuf!(0x8, false);
 }
 
 impl InitSyn {
@@ -197,6 +203,7 @@ impl InitSyn {
         let ext_mlink = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_lowlatency = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
+        let ext_noise = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_patch = ext::PatchType::rand();
 
         Self {
@@ -213,6 +220,7 @@ impl InitSyn {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         }
     }
@@ -229,6 +237,7 @@ impl InitSyn {
 /// ZExtZ64
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct InitAck {
     pub version: u8,
     pub whatami: WhatAmI,
@@ -244,6 +253,7 @@ pub struct InitAck {
     pub ext_mlink: Option<ext::MultiLink>,
     pub ext_lowlatency: Option<ext::LowLatency>,
     pub ext_compression: Option<ext::Compression>,
+    pub ext_noise: Option<ext::Noise>,
     pub ext_patch: ext::PatchType,
 }
 
@@ -274,6 +284,7 @@ impl InitAck {
         let ext_mlink = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_lowlatency = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
+        let ext_noise = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_patch = ext::PatchType::rand();
 
         Self {
@@ -291,6 +302,7 @@ impl InitAck {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         }
     }
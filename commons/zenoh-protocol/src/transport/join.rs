@@ -178,3 +178,36 @@ impl Join {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Join {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let version: u8 = u.arbitrary()?;
+        let whatami: WhatAmI = u.arbitrary()?;
+        let zid: ZenohIdProto = u.arbitrary()?;
+        let resolution: Resolution = u.arbitrary()?;
+        let batch_size: BatchSize = u.arbitrary()?;
+        let lease = if u.arbitrary()? {
+            Duration::from_secs(u.arbitrary()?)
+        } else {
+            Duration::from_millis(u.arbitrary()?)
+        };
+        let next_sn: PrioritySn = u.arbitrary()?;
+        let ext_qos: Option<ext::QoSType> = u.arbitrary()?;
+        let ext_shm: Option<ext::Shm> = u.arbitrary()?;
+        let ext_patch: ext::PatchType = u.arbitrary()?;
+
+        Ok(Self {
+            version,
+            whatami,
+            zid,
+            resolution,
+            batch_size,
+            lease,
+            next_sn,
+            ext_qos,
+            ext_shm,
+            ext_patch,
+        })
+    }
+}
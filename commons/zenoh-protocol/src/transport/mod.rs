@@ -62,6 +62,7 @@ pub mod id {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TransportMessageLowLatency {
     pub body: TransportBodyLowLatency,
 }
@@ -77,6 +78,7 @@ impl TryFrom<NetworkMessage> for TransportMessageLowLatency {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TransportBodyLowLatency {
     Close(Close),
     KeepAlive(KeepAlive),
@@ -86,6 +88,7 @@ pub enum TransportBodyLowLatency {
 pub type TransportSn = u32;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PrioritySn {
     pub reliable: TransportSn,
     pub best_effort: TransportSn,
@@ -111,6 +114,7 @@ impl PrioritySn {
 
 // Zenoh messages at zenoh-transport level
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TransportBody {
     InitSyn(InitSyn),
     InitAck(InitAck),
@@ -170,6 +174,13 @@ impl From<TransportBody> for TransportMessage {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for TransportMessage {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(u.arbitrary::<TransportBody>()?.into())
+    }
+}
+
 impl From<InitSyn> for TransportMessage {
     fn from(init_syn: InitSyn) -> Self {
         TransportBody::InitSyn(init_syn).into()
@@ -264,6 +275,7 @@ pub mod ext {
     /// ```
     #[repr(transparent)]
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct QoSType<const ID: u8> {
         inner: u8,
     }
@@ -313,11 +325,15 @@ pub mod ext {
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct PatchType<const ID: u8>(u8);
 
     impl<const ID: u8> PatchType<ID> {
         pub const NONE: Self = Self(0);
+        #[cfg(not(feature = "unstable"))]
         pub const CURRENT: Self = Self(1);
+        #[cfg(feature = "unstable")]
+        pub const CURRENT: Self = Self(2);
 
         pub fn new(int: u8) -> Self {
             Self(int)
@@ -331,6 +347,10 @@ pub mod ext {
             self.0 >= 1
         }
 
+        pub fn has_query_target_zid(&self) -> bool {
+            self.0 >= 2
+        }
+
         #[cfg(feature = "test")]
         pub fn rand() -> Self {
             use rand::Rng;
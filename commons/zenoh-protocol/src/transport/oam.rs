@@ -48,6 +48,7 @@ pub mod flag {
 /// - 0b11: Reserved
 ///
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Oam {
     pub id: OamId,
     pub body: ZExtBody,
@@ -179,6 +179,39 @@ impl OpenSyn {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for OpenSyn {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let lease = if u.arbitrary()? {
+            Duration::from_secs(u.arbitrary()?)
+        } else {
+            Duration::from_millis(u.arbitrary()?)
+        };
+        let initial_sn: TransportSn = u.arbitrary()?;
+        let cookie: ZSlice = u.arbitrary()?;
+        let ext_qos: Option<ext::QoS> = u.arbitrary()?;
+        #[cfg(feature = "shared-memory")]
+        let ext_shm: Option<ext::Shm> = u.arbitrary()?;
+        let ext_auth: Option<ext::Auth> = u.arbitrary()?;
+        let ext_mlink: Option<ext::MultiLinkSyn> = u.arbitrary()?;
+        let ext_lowlatency: Option<ext::LowLatency> = u.arbitrary()?;
+        let ext_compression: Option<ext::Compression> = u.arbitrary()?;
+
+        Ok(Self {
+            lease,
+            initial_sn,
+            cookie,
+            ext_qos,
+            #[cfg(feature = "shared-memory")]
+            ext_shm,
+            ext_auth,
+            ext_mlink,
+            ext_lowlatency,
+            ext_compression,
+        })
+    }
+}
+
 /// # OpenAck message
 ///
 /// ```text
@@ -239,3 +272,34 @@ impl OpenAck {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for OpenAck {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let lease = if u.arbitrary()? {
+            Duration::from_secs(u.arbitrary()?)
+        } else {
+            Duration::from_millis(u.arbitrary()?)
+        };
+        let initial_sn: TransportSn = u.arbitrary()?;
+        let ext_qos: Option<ext::QoS> = u.arbitrary()?;
+        #[cfg(feature = "shared-memory")]
+        let ext_shm: Option<ext::Shm> = u.arbitrary()?;
+        let ext_auth: Option<ext::Auth> = u.arbitrary()?;
+        let ext_mlink: Option<ext::MultiLinkAck> = u.arbitrary()?;
+        let ext_lowlatency: Option<ext::LowLatency> = u.arbitrary()?;
+        let ext_compression: Option<ext::Compression> = u.arbitrary()?;
+
+        Ok(Self {
+            lease,
+            initial_sn,
+            ext_qos,
+            #[cfg(feature = "shared-memory")]
+            ext_shm,
+            ext_auth,
+            ext_mlink,
+            ext_lowlatency,
+            ext_compression,
+        })
+    }
+}
@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 use uhlc::Timestamp;
 
@@ -45,6 +45,8 @@ pub struct Del {
     pub timestamp: Option<Timestamp>,
     pub ext_sinfo: Option<ext::SourceInfoType>,
     pub ext_attachment: Option<ext::AttachmentType>,
+    pub ext_trace: Option<ext::TraceContextType>,
+    pub ext_signature: Option<Box<ext::SignatureType>>,
     pub ext_unknown: Vec<ZExtUnknown>,
 }
 
@@ -59,6 +61,16 @@ pub mod ext {
     /// # User attachment
     pub type Attachment = zextzbuf!(0x2, false);
     pub type AttachmentType = crate::zenoh::ext::AttachmentType<{ Attachment::ID }>;
+
+    /// # Trace context extension
+    /// Used to propagate a W3C trace context along with the data
+    pub type TraceContext = zextzbuf!(0x3, false);
+    pub type TraceContextType = crate::zenoh::ext::TraceContextType<{ TraceContext::ID }>;
+
+    /// # Signature extension
+    /// Used to authenticate the data and its publisher
+    pub type Signature = zextzbuf!(0x4, false);
+    pub type SignatureType = crate::zenoh::ext::SignatureType<{ Signature::ID }>;
 }
 
 impl Del {
@@ -76,19 +88,56 @@ impl Del {
         });
         let ext_sinfo = rng.gen_bool(0.5).then_some(ext::SourceInfoType::rand());
         let ext_attachment = rng.gen_bool(0.5).then_some(ext::AttachmentType::rand());
+        let ext_trace = rng.gen_bool(0.5).then_some(ext::TraceContextType::rand());
+        let ext_signature = rng
+            .gen_bool(0.5)
+            .then(|| Box::new(ext::SignatureType::rand()));
         let mut ext_unknown = Vec::new();
         for _ in 0..rng.gen_range(0..4) {
-            ext_unknown.push(ZExtUnknown::rand2(
-                iext::mid(ext::Attachment::ID) + 1,
-                false,
-            ));
+            ext_unknown.push(ZExtUnknown::rand2(iext::mid(ext::Signature::ID) + 1, false));
         }
 
         Self {
             timestamp,
             ext_sinfo,
             ext_attachment,
+            ext_trace,
+            ext_signature,
             ext_unknown,
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Del {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use crate::{common::iext, core::arbitrary_timestamp};
+
+        let timestamp = if u.arbitrary()? {
+            Some(arbitrary_timestamp(u)?)
+        } else {
+            None
+        };
+        let ext_sinfo: Option<ext::SourceInfoType> = u.arbitrary()?;
+        let ext_attachment: Option<ext::AttachmentType> = u.arbitrary()?;
+        let ext_trace: Option<ext::TraceContextType> = u.arbitrary()?;
+        let ext_signature: Option<Box<ext::SignatureType>> = u.arbitrary()?;
+        let mut ext_unknown = Vec::new();
+        for _ in 0..u.int_in_range(0..=3)? {
+            ext_unknown.push(ZExtUnknown::arbitrary2(
+                u,
+                iext::mid(ext::Signature::ID) + 1,
+                false,
+            )?);
+        }
+
+        Ok(Self {
+            timestamp,
+            ext_sinfo,
+            ext_attachment,
+            ext_trace,
+            ext_signature,
+            ext_unknown,
+        })
+    }
+}
@@ -101,3 +101,33 @@ impl Err {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Err {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use crate::common::iext;
+
+        let encoding: Encoding = u.arbitrary()?;
+        let ext_sinfo: Option<ext::SourceInfoType> = u.arbitrary()?;
+        #[cfg(feature = "shared-memory")]
+        let ext_shm: Option<ext::ShmType> = u.arbitrary()?;
+        let mut ext_unknown = Vec::new();
+        for _ in 0..u.int_in_range(0..=3)? {
+            ext_unknown.push(ZExtUnknown::arbitrary2(
+                u,
+                iext::mid(ext::SourceInfo::ID) + 1,
+                false,
+            )?);
+        }
+        let payload: ZBuf = u.arbitrary()?;
+
+        Ok(Self {
+            encoding,
+            ext_sinfo,
+            #[cfg(feature = "shared-memory")]
+            ext_shm,
+            ext_unknown,
+            payload,
+        })
+    }
+}
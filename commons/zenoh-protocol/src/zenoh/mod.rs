@@ -42,6 +42,7 @@ pub struct DataInfo {
 
 // Push
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum PushBody {
     Put(Put),
     Del(Del),
@@ -76,6 +77,7 @@ impl From<Del> for PushBody {
 
 // Request
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum RequestBody {
     Query(Query),
 }
@@ -102,6 +104,7 @@ impl From<Query> for RequestBody {
 
 // Response
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ResponseBody {
     Reply(Reply),
     Err(Err),
@@ -151,6 +154,7 @@ pub mod ext {
     /// +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct SourceInfoType<const ID: u8> {
         pub id: EntityGlobalIdProto,
         pub sn: u32,
@@ -173,6 +177,7 @@ pub mod ext {
     /// +-+-+-+-+-+-+-+-+
     #[cfg(feature = "shared-memory")]
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct ShmType<const ID: u8>;
 
     #[cfg(feature = "shared-memory")]
@@ -202,6 +207,7 @@ pub mod ext {
     ///  +---------------+
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct ValueType<const VID: u8, const SID: u8> {
         #[cfg(feature = "shared-memory")]
         pub ext_shm: Option<ShmType<{ SID }>>,
@@ -232,6 +238,95 @@ pub mod ext {
         }
     }
 
+    /// ```text
+    ///  7 6 5 4 3 2 1 0
+    /// +-+-+-+-+-+-+-+-+
+    /// ~  public_key   ~  -- 32 bytes
+    /// +---------------+
+    /// ~   signature   ~  -- 64 bytes
+    /// +---------------+
+    /// ```
+    ///
+    /// Carries the Ed25519 signature of the data by its publisher, along with the public key
+    /// to verify it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct SignatureType<const ID: u8> {
+        pub public_key: [u8; 32],
+        pub signature: [u8; 64],
+    }
+
+    impl<const ID: u8> SignatureType<{ ID }> {
+        pub const LEN: usize = 32 + 64;
+
+        #[cfg(feature = "test")]
+        pub fn rand() -> Self {
+            use rand::Rng;
+            let mut rng = rand::thread_rng();
+
+            let mut signature = [0u8; 64];
+            rng.fill(&mut signature[..]);
+            Self {
+                public_key: rng.gen(),
+                signature,
+            }
+        }
+    }
+
+    /// ```text
+    ///  7 6 5 4 3 2 1 0
+    /// +-+-+-+-+-+-+-+-+
+    /// ~   trace_id    ~  -- 16 bytes
+    /// +---------------+
+    /// ~    span_id    ~  -- 8 bytes
+    /// +---------------+
+    /// |     flags     |
+    /// +---------------+
+    /// ```
+    ///
+    /// Carries a W3C trace context (`traceparent`) along with the data,
+    /// allowing end-to-end tracing across the zenoh infrastructure.
+    /// Neither the trace id nor the span id may be made of zeros only.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct TraceContextType<const ID: u8> {
+        pub trace_id: [u8; 16],
+        pub span_id: [u8; 8],
+        pub flags: u8,
+    }
+
+    impl<const ID: u8> TraceContextType<{ ID }> {
+        pub const LEN: usize = 16 + 8 + 1;
+
+        /// Whether the trace context is valid, i.e. whether neither its trace id nor its span id
+        /// is made of zeros only, as mandated by the W3C specification.
+        pub fn is_valid(&self) -> bool {
+            self.trace_id != [0; 16] && self.span_id != [0; 8]
+        }
+
+        #[cfg(feature = "test")]
+        pub fn rand() -> Self {
+            use rand::Rng;
+            let mut rng = rand::thread_rng();
+
+            Self {
+                trace_id: rng.gen_range(1..=u128::MAX).to_be_bytes(),
+                span_id: rng.gen_range(1..=u64::MAX).to_be_bytes(),
+                flags: rng.gen(),
+            }
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    impl<'a, const ID: u8> arbitrary::Arbitrary<'a> for TraceContextType<{ ID }> {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(Self {
+                trace_id: u.int_in_range(1..=u128::MAX)?.to_be_bytes(),
+                span_id: u.int_in_range(1..=u64::MAX)?.to_be_bytes(),
+                flags: u.arbitrary()?,
+            })
+        }
+    }
+
     /// ```text
     /// 7 6 5 4 3 2 1 0
     /// +-+-+-+-+-+-+-+-+
@@ -244,6 +339,7 @@ pub mod ext {
     ///       ...         -- N times (key, value) tuples
     /// ```
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     pub struct AttachmentType<const ID: u8> {
         pub buffer: ZBuf,
     }
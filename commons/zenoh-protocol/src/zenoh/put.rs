@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 use uhlc::Timestamp;
 use zenoh_buffers::ZBuf;
@@ -51,6 +51,8 @@ pub struct Put {
     pub encoding: Encoding,
     pub ext_sinfo: Option<ext::SourceInfoType>,
     pub ext_attachment: Option<ext::AttachmentType>,
+    pub ext_trace: Option<ext::TraceContextType>,
+    pub ext_signature: Option<Box<ext::SignatureType>>,
     #[cfg(feature = "shared-memory")]
     pub ext_shm: Option<ext::ShmType>,
     pub ext_unknown: Vec<ZExtUnknown>,
@@ -77,6 +79,16 @@ pub mod ext {
     /// # User attachment
     pub type Attachment = zextzbuf!(0x3, false);
     pub type AttachmentType = crate::zenoh::ext::AttachmentType<{ Attachment::ID }>;
+
+    /// # Trace context extension
+    /// Used to propagate a W3C trace context along with the data
+    pub type TraceContext = zextzbuf!(0x4, false);
+    pub type TraceContextType = crate::zenoh::ext::TraceContextType<{ TraceContext::ID }>;
+
+    /// # Signature extension
+    /// Used to authenticate the data and its publisher
+    pub type Signature = zextzbuf!(0x5, false);
+    pub type SignatureType = crate::zenoh::ext::SignatureType<{ Signature::ID }>;
 }
 
 impl Put {
@@ -97,12 +109,13 @@ impl Put {
         #[cfg(feature = "shared-memory")]
         let ext_shm = rng.gen_bool(0.5).then_some(ext::ShmType::rand());
         let ext_attachment = rng.gen_bool(0.5).then_some(ext::AttachmentType::rand());
+        let ext_trace = rng.gen_bool(0.5).then_some(ext::TraceContextType::rand());
+        let ext_signature = rng
+            .gen_bool(0.5)
+            .then(|| Box::new(ext::SignatureType::rand()));
         let mut ext_unknown = Vec::new();
         for _ in 0..rng.gen_range(0..4) {
-            ext_unknown.push(ZExtUnknown::rand2(
-                iext::mid(ext::Attachment::ID) + 1,
-                false,
-            ));
+            ext_unknown.push(ZExtUnknown::rand2(iext::mid(ext::Signature::ID) + 1, false));
         }
         let payload = ZBuf::rand(rng.gen_range(1..=64));
 
@@ -113,8 +126,52 @@ impl Put {
             #[cfg(feature = "shared-memory")]
             ext_shm,
             ext_attachment,
+            ext_trace,
+            ext_signature,
             ext_unknown,
             payload,
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Put {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use crate::{common::iext, core::arbitrary_timestamp};
+
+        let timestamp = if u.arbitrary()? {
+            Some(arbitrary_timestamp(u)?)
+        } else {
+            None
+        };
+        let encoding: Encoding = u.arbitrary()?;
+        let ext_sinfo: Option<ext::SourceInfoType> = u.arbitrary()?;
+        #[cfg(feature = "shared-memory")]
+        let ext_shm: Option<ext::ShmType> = u.arbitrary()?;
+        let ext_attachment: Option<ext::AttachmentType> = u.arbitrary()?;
+        let ext_trace: Option<ext::TraceContextType> = u.arbitrary()?;
+        let ext_signature: Option<Box<ext::SignatureType>> = u.arbitrary()?;
+        let mut ext_unknown = Vec::new();
+        for _ in 0..u.int_in_range(0..=3)? {
+            ext_unknown.push(ZExtUnknown::arbitrary2(
+                u,
+                iext::mid(ext::Signature::ID) + 1,
+                false,
+            )?);
+        }
+        let payload: ZBuf = u.arbitrary()?;
+
+        Ok(Self {
+            timestamp,
+            encoding,
+            ext_sinfo,
+            #[cfg(feature = "shared-memory")]
+            ext_shm,
+            ext_attachment,
+            ext_trace,
+            ext_signature,
+            ext_unknown,
+            payload,
+        })
+    }
+}
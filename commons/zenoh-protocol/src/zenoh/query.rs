@@ -18,6 +18,7 @@ use crate::common::ZExtUnknown;
 /// The kind of consolidation to apply to a query.
 #[repr(u8)]
 #[derive(Debug, Default, Clone, PartialEq, Eq, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ConsolidationMode {
     /// Apply automatic consolidation based on queryable's preferences
     #[default]
@@ -84,6 +85,7 @@ pub struct Query {
     pub ext_sinfo: Option<ext::SourceInfoType>,
     pub ext_body: Option<ext::QueryBodyType>,
     pub ext_attachment: Option<ext::AttachmentType>,
+    pub ext_trace: Option<ext::TraceContextType>,
     pub ext_unknown: Vec<ZExtUnknown>,
 }
 
@@ -104,6 +106,11 @@ pub mod ext {
     /// # User attachment
     pub type Attachment = zextzbuf!(0x5, false);
     pub type AttachmentType = crate::zenoh::ext::AttachmentType<{ Attachment::ID }>;
+
+    /// # Trace context extension
+    /// Used to propagate a W3C trace context along with the query
+    pub type TraceContext = zextzbuf!(0x6, false);
+    pub type TraceContextType = crate::zenoh::ext::TraceContextType<{ TraceContext::ID }>;
 }
 
 impl Query {
@@ -130,10 +137,11 @@ impl Query {
         let ext_sinfo = rng.gen_bool(0.5).then_some(ext::SourceInfoType::rand());
         let ext_body = rng.gen_bool(0.5).then_some(ext::QueryBodyType::rand());
         let ext_attachment = rng.gen_bool(0.5).then_some(ext::AttachmentType::rand());
+        let ext_trace = rng.gen_bool(0.5).then_some(ext::TraceContextType::rand());
         let mut ext_unknown = Vec::new();
         for _ in 0..rng.gen_range(0..4) {
             ext_unknown.push(ZExtUnknown::rand2(
-                iext::mid(ext::Attachment::ID) + 1,
+                iext::mid(ext::TraceContext::ID) + 1,
                 false,
             ));
         }
@@ -144,7 +152,40 @@ impl Query {
             ext_sinfo,
             ext_body,
             ext_attachment,
+            ext_trace,
             ext_unknown,
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Query {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use crate::common::iext;
+
+        let consolidation: ConsolidationMode = u.arbitrary()?;
+        let parameters: String = u.arbitrary()?;
+        let ext_sinfo: Option<ext::SourceInfoType> = u.arbitrary()?;
+        let ext_body: Option<ext::QueryBodyType> = u.arbitrary()?;
+        let ext_attachment: Option<ext::AttachmentType> = u.arbitrary()?;
+        let ext_trace: Option<ext::TraceContextType> = u.arbitrary()?;
+        let mut ext_unknown = Vec::new();
+        for _ in 0..u.int_in_range(0..=3)? {
+            ext_unknown.push(ZExtUnknown::arbitrary2(
+                u,
+                iext::mid(ext::TraceContext::ID) + 1,
+                false,
+            )?);
+        }
+
+        Ok(Self {
+            consolidation,
+            parameters,
+            ext_sinfo,
+            ext_body,
+            ext_attachment,
+            ext_trace,
+            ext_unknown,
+        })
+    }
+}
@@ -72,3 +72,21 @@ impl Reply {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Reply {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let payload: ReplyBody = u.arbitrary()?;
+        let consolidation: ConsolidationMode = u.arbitrary()?;
+        let mut ext_unknown = Vec::new();
+        for _ in 0..u.int_in_range(0..=3)? {
+            ext_unknown.push(ZExtUnknown::arbitrary2(u, 1, false)?);
+        }
+
+        Ok(Self {
+            consolidation,
+            ext_unknown,
+            payload,
+        })
+    }
+}
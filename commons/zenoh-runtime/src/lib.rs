@@ -35,7 +35,7 @@ use lazy_static::lazy_static;
 use serde::Deserialize;
 use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
 use zenoh_macros::{GenericRuntimeParam, RegisterParam};
-use zenoh_result::ZResult as Result;
+use zenoh_result::{bail, ZResult as Result};
 
 pub const ZENOH_RUNTIME_ENV: &str = "ZENOH_RUNTIME";
 
@@ -49,6 +49,11 @@ pub struct RuntimeParam {
     pub max_blocking_threads: usize,
     /// Hand over one ZRuntime to another one.
     pub handover: Option<ZRuntime>,
+    /// Prefix of the names of the threads, followed by their index. Defaults to the name of the
+    /// ZRuntime.
+    pub thread_name: Option<String>,
+    /// CPU cores the threads are pinned to, in turn. The threads are not pinned if empty.
+    pub cpu_affinity: Vec<usize>,
 }
 
 impl Default for RuntimeParam {
@@ -57,13 +62,17 @@ impl Default for RuntimeParam {
             worker_threads: 1,
             max_blocking_threads: 50,
             handover: None,
+            thread_name: None,
+            cpu_affinity: vec![],
         }
     }
 }
 
 impl RuntimeParam {
     pub fn build(&self, zrt: ZRuntime) -> Result<Runtime> {
-        let rt = tokio::runtime::Builder::new_multi_thread()
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        let thread_name = self.thread_name.clone().unwrap_or_else(|| zrt.to_string());
+        builder
             .worker_threads(self.worker_threads)
             .max_blocking_threads(self.max_blocking_threads)
             .enable_io()
@@ -73,10 +82,26 @@ impl RuntimeParam {
                     .get(&zrt)
                     .unwrap()
                     .fetch_add(1, Ordering::SeqCst);
-                format!("{}-{}", zrt, id)
-            })
-            .build()?;
-        Ok(rt)
+                format!("{}-{}", thread_name, id)
+            });
+        if !self.cpu_affinity.is_empty() {
+            let available = core_affinity::get_core_ids().unwrap_or_default();
+            let mut cores = Vec::with_capacity(self.cpu_affinity.len());
+            for id in self.cpu_affinity.iter() {
+                match available.iter().find(|core| core.id == *id) {
+                    Some(core) => cores.push(*core),
+                    None => bail!("CPU core {id} of {zrt} is not available"),
+                }
+            }
+            let next = AtomicUsize::new(0);
+            builder.on_thread_start(move || {
+                let core = cores[next.fetch_add(1, Ordering::SeqCst) % cores.len()];
+                if !core_affinity::set_for_current(core) {
+                    tracing::warn!("Failed to pin a thread of {zrt} to CPU core {}", core.id);
+                }
+            });
+        }
+        Ok(builder.build()?)
     }
 }
 
@@ -90,7 +115,7 @@ impl RuntimeParam {
 ///   rx: (handover: app),
 ///   acc: (handover: app),
 ///   app: (worker_threads: 2),
-///   tx: (max_blocking_threads: 1)
+///   tx: (max_blocking_threads: 1, thread_name: "zenoh-tx", cpu_affinity: [2, 3])
 /// )'
 /// ```
 /// Note: The runtime parameter takes effect at the beginning of the zenoh process and no longer be
@@ -224,3 +249,25 @@ async fn block_in_place_fail_test() {
     use crate::ZRuntime;
     ZRuntime::TX.block_in_place(async { println!("Done") });
 }
+
+#[test]
+fn runtime_param_thread_test() {
+    let param = RuntimeParam {
+        thread_name: Some("zenoh-test".to_string()),
+        cpu_affinity: vec![core_affinity::get_core_ids().unwrap()[0].id],
+        ..Default::default()
+    };
+    let rt = param.build(ZRuntime::Net).unwrap();
+    let name = rt.block_on(async {
+        tokio::spawn(async { std::thread::current().name().map(String::from) })
+            .await
+            .unwrap()
+    });
+    assert!(name.unwrap().starts_with("zenoh-test-"));
+
+    let param = RuntimeParam {
+        cpu_affinity: vec![usize::MAX],
+        ..Default::default()
+    };
+    assert!(param.build(ZRuntime::Net).is_err());
+}
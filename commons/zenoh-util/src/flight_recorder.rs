@@ -0,0 +1,248 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! An always-on, in-memory ring of the recent significant events of a runtime.
+//!
+//! A [`FlightRecorder`] keeps the last [`CAPACITY`] declarations, link state changes, message
+//! drops and errors regardless of the logging configuration, so that they can be retrieved for
+//! post-mortem analysis, e.g. through the admin space or, once
+//! [installed](FlightRecorder::install_panic_hook), when the process panics.
+//!
+//! Recording an event takes no lock, and its message is only formatted when it is retrieved.
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+use arc_swap::ArcSwapOption;
+use serde::{Serialize, Serializer};
+
+/// The maximum number of events kept by a flight recorder.
+pub const CAPACITY: usize = 1024;
+
+/// The kind of an [`Event`] of the flight recorder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Declaration,
+    Link,
+    Drop,
+    Error,
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::Declaration => write!(f, "declaration"),
+            EventKind::Link => write!(f, "link"),
+            EventKind::Drop => write!(f, "drop"),
+            EventKind::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// An event of the flight recorder.
+#[derive(Clone, Debug, Serialize)]
+pub struct Event {
+    #[serde(serialize_with = "serialize_time")]
+    pub time: SystemTime,
+    pub kind: EventKind,
+    pub message: String,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}",
+            humantime::format_rfc3339_micros(self.time),
+            self.kind,
+            self.message
+        )
+    }
+}
+
+fn serialize_time<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&humantime::format_rfc3339_micros(*time))
+}
+
+type Message = Box<dyn Fn(&mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync>;
+
+/// A recorded event, whose message is not formatted yet.
+struct Record {
+    seq: u64,
+    time: SystemTime,
+    kind: EventKind,
+    message: Message,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.message)(f)
+    }
+}
+
+/// A fixed-size ring of the last [`CAPACITY`] events, overwriting the oldest one when full.
+pub struct FlightRecorder {
+    slots: Box<[ArcSwapOption<Record>]>,
+    next: AtomicU64,
+}
+
+impl FlightRecorder {
+    pub fn new() -> Self {
+        Self {
+            slots: (0..CAPACITY).map(|_| ArcSwapOption::empty()).collect(),
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// Records an event, whose message is written by `message` when the events are retrieved.
+    ///
+    /// See [`record_event!`](crate::record_event), which captures the arguments of the message.
+    pub fn record<F>(&self, kind: EventKind, message: F)
+    where
+        F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync + 'static,
+    {
+        let seq = self.next.fetch_add(1, Ordering::Relaxed);
+        let record = Record {
+            seq,
+            time: SystemTime::now(),
+            kind,
+            message: Box::new(message),
+        };
+        self.slots[(seq % CAPACITY as u64) as usize].store(Some(Arc::new(record)));
+    }
+
+    /// Returns the recorded events, from the oldest to the most recent.
+    pub fn events(&self) -> Vec<Event> {
+        let mut records = self
+            .slots
+            .iter()
+            .filter_map(|slot| slot.load_full())
+            .collect::<Vec<_>>();
+        records.sort_unstable_by_key(|record| record.seq);
+        records
+            .iter()
+            .map(|record| Event {
+                time: record.time,
+                kind: record.kind,
+                message: record.to_string(),
+            })
+            .collect()
+    }
+
+    /// Installs a panic hook printing the events of this recorder on the standard error after the
+    /// message of the panic, for as long as the recorder is alive. The previous panic hook is
+    /// still called.
+    ///
+    /// The panic hook is global to the process, hence it is up to the application to install it.
+    pub fn install_panic_hook(self: &Arc<Self>) {
+        let recorder = Arc::downgrade(self);
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            hook(info);
+            let Some(recorder) = recorder.upgrade() else {
+                return;
+            };
+            let events = recorder.events();
+            if !events.is_empty() {
+                eprintln!("Flight recorder, last {} events:", events.len());
+                for event in events {
+                    eprintln!("  {event}");
+                }
+            }
+        }));
+    }
+}
+
+impl Default for FlightRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for FlightRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlightRecorder")
+            .field("recorded", &self.next.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Records an event of the given [`EventKind`] variant in a [`FlightRecorder`], formatted like
+/// [`format!`] when the events are retrieved.
+///
+/// The arguments are named, and are owned by the recorder: a borrowed argument is cloned.
+///
+/// ```
+/// use zenoh_util::flight_recorder::FlightRecorder;
+///
+/// let recorder = FlightRecorder::new();
+/// let link = "tcp/127.0.0.1:7447";
+/// zenoh_util::record_event!(recorder, Link, "link {link} closed", link);
+/// zenoh_util::record_event!(recorder, Drop, "{count} messages dropped", count = 2 + 1);
+/// assert_eq!(recorder.events()[1].message, "3 messages dropped");
+/// ```
+#[macro_export]
+macro_rules! record_event {
+    (@arg $name:ident) => {
+        $name
+    };
+    (@arg $name:ident = $arg:expr) => {
+        $arg
+    };
+    ($recorder:expr, $kind:ident, $fmt:literal $(, $name:ident $(= $arg:expr)?)* $(,)?) => {{
+        $(
+            let $name = ::std::borrow::ToOwned::to_owned(
+                &$crate::record_event!(@arg $name $(= $arg)?),
+            );
+        )*
+        $recorder.record(
+            $crate::flight_recorder::EventKind::$kind,
+            move |f: &mut ::std::fmt::Formatter<'_>| ::std::write!(f, $fmt),
+        )
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flight_recorder() {
+        let recorder = FlightRecorder::new();
+        for i in 0..CAPACITY + 2 {
+            record_event!(recorder, Drop, "event {i}", i);
+        }
+        let events = recorder.events();
+        assert_eq!(events.len(), CAPACITY);
+        assert_eq!(events[0].message, "event 2");
+        assert_eq!(
+            events[CAPACITY - 1].message,
+            format!("event {}", CAPACITY + 1)
+        );
+        assert_eq!(events[CAPACITY - 1].kind, EventKind::Drop);
+
+        let json = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(json["kind"], "drop");
+        assert!(json["time"].as_str().unwrap().ends_with('Z'));
+
+        // Each recorder has its own events
+        assert!(FlightRecorder::new().events().is_empty());
+    }
+}
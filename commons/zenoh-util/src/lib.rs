@@ -20,6 +20,7 @@
 use lazy_static::lazy_static;
 
 pub mod ffi;
+pub mod flight_recorder;
 mod lib_loader;
 pub mod lib_search_dirs;
 pub mod net;
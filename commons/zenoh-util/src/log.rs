@@ -11,14 +11,20 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{fmt, thread, thread::ThreadId};
+use std::{fmt, sync::OnceLock, thread, thread::ThreadId};
 
 use tracing::{field::Field, span, Event, Metadata, Subscriber};
 use tracing_subscriber::{
     layer::{Context, SubscriberExt},
     registry::LookupSpan,
-    EnvFilter,
+    reload, EnvFilter,
 };
+use zenoh_result::{bail, zerror, ZResult};
+
+type FilterReload = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Replaces the filter of the subscriber installed by this module, if any.
+static FILTER_RELOAD: OnceLock<FilterReload> = OnceLock::new();
 
 /// A utility function to enable the tracing formatting subscriber.
 ///
@@ -55,16 +61,79 @@ where
     init_env_filter(env_filter);
 }
 
+/// A utility function to enable the tracing formatting subscriber along with an
+/// [OpenTelemetry](https://opentelemetry.io) layer exporting spans over OTLP.
+///
+/// Filtering follows the same rules as [`init_log_from_env_or`]. The OTLP exporter is configured
+/// from the standard `OTEL_EXPORTER_OTLP_*` environment variables and spans are reported under
+/// the given `service_name`.
+///
+/// This function must be called from within a Tokio runtime.
+#[cfg(feature = "opentelemetry")]
+pub fn init_log_with_opentelemetry<S>(service_name: &str, fallback: S) -> zenoh_result::ZResult<()>
+where
+    S: AsRef<str>,
+{
+    use opentelemetry::{trace::TracerProvider, KeyValue};
+    use opentelemetry_sdk::{runtime, trace::Config, Resource};
+
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(fallback));
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic())
+        .with_trace_config(
+            Config::default().with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                service_name.to_owned(),
+            )])),
+        )
+        .install_batch(runtime::Tokio)?;
+    let tracer = provider.tracer("zenoh");
+    opentelemetry::global::set_tracer_provider(provider);
+
+    let (env_filter, handle) = reload::Layer::new(env_filter);
+    let subscriber = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_thread_ids(true)
+                .with_thread_names(true)
+                .with_level(true)
+                .with_target(true),
+        )
+        .with(tracing_opentelemetry::layer().with_tracer(tracer));
+    tracing::subscriber::set_global_default(subscriber)?;
+    let _ = FILTER_RELOAD.set(Box::new(move |filter| handle.reload(filter)));
+    Ok(())
+}
+
 fn init_env_filter(env_filter: EnvFilter) {
     let subscriber = tracing_subscriber::fmt()
         .with_env_filter(env_filter)
+        .with_filter_reloading()
         .with_thread_ids(true)
         .with_thread_names(true)
         .with_level(true)
         .with_target(true);
+    let handle = subscriber.reload_handle();
 
     let subscriber = subscriber.finish();
-    let _ = tracing::subscriber::set_global_default(subscriber);
+    if tracing::subscriber::set_global_default(subscriber).is_ok() {
+        let _ = FILTER_RELOAD.set(Box::new(move |filter| handle.reload(filter)));
+    }
+}
+
+/// Replaces the filtering directives of the tracing subscriber, e.g. with `z=debug`.
+///
+/// Only the subscribers installed by the `init_log_*` functions of this module based on an
+/// [`EnvFilter`] can be updated: an error is returned if none was installed.
+pub fn set_log_filter(directives: &str) -> ZResult<()> {
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|e| zerror!("Invalid log filter '{}': {}", directives, e))?;
+    let Some(reload) = FILTER_RELOAD.get() else {
+        bail!("The log filter can't be updated: no reloadable tracing subscriber was installed");
+    };
+    reload(filter).map_err(|e| zerror!("Unable to update the log filter: {}", e).into())
 }
 
 pub struct LogRecord {
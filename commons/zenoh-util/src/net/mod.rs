@@ -11,13 +11,14 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::net::{IpAddr, Ipv6Addr};
+use std::{
+    net::{IpAddr, Ipv6Addr},
+    str::FromStr,
+};
 
 use tokio::net::{TcpSocket, UdpSocket};
 use zenoh_core::zconfigurable;
-#[cfg(unix)]
-use zenoh_result::zerror;
-use zenoh_result::{bail, ZResult};
+use zenoh_result::{bail, zerror, ZResult};
 
 zconfigurable! {
     static ref WINDOWS_GET_ADAPTERS_ADDRESSES_BUF_SIZE: u32 = 8192;
@@ -452,3 +453,81 @@ pub fn set_bind_to_device_udp_socket(socket: &UdpSocket, iface: &str) -> ZResult
     tracing::warn!("Binding the socket {socket:?} to the interface {iface} is not supported on macOS and Windows");
     Ok(())
 }
+
+/// An IP subnet in CIDR notation, e.g. `192.168.1.0/24` or `fd00::/8`. An address without prefix
+/// length is a subnet of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Subnet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Subnet {
+    /// Returns `true` if `ip` belongs to the subnet.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Subnet {
+    type Err = zenoh_result::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|e| zerror!("Invalid subnet '{}': {}", s, e))?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse()
+                .map_err(|e| zerror!("Invalid subnet '{}': {}", s, e))?,
+            None => max,
+        };
+        if prefix > max {
+            bail!("Invalid subnet '{}': prefix length exceeds {}", s, max);
+        }
+        Ok(Subnet { addr, prefix })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subnet_contains() {
+        let subnet: Subnet = "192.168.1.0/24".parse().unwrap();
+        assert!(subnet.contains(&"192.168.1.42".parse().unwrap()));
+        assert!(!subnet.contains(&"192.168.2.42".parse().unwrap()));
+        assert!(!subnet.contains(&"::1".parse().unwrap()));
+
+        let subnet: Subnet = "0.0.0.0/0".parse().unwrap();
+        assert!(subnet.contains(&"10.0.0.1".parse().unwrap()));
+
+        let subnet: Subnet = "10.0.0.1".parse().unwrap();
+        assert!(subnet.contains(&"10.0.0.1".parse().unwrap()));
+        assert!(!subnet.contains(&"10.0.0.2".parse().unwrap()));
+
+        let subnet: Subnet = "fd00::/8".parse().unwrap();
+        assert!(subnet.contains(&"fd12::1".parse().unwrap()));
+        assert!(!subnet.contains(&"fe80::1".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<Subnet>().is_err());
+        assert!("localhost/8".parse::<Subnet>().is_err());
+        assert!("not-a-subnet".parse::<Subnet>().is_err());
+    }
+}
@@ -47,6 +47,12 @@ use zenoh_result::ZResult;
 pub const BIND_INTERFACE: &str = "iface";
 pub const TCP_SO_SND_BUF: &str = "so_sndbuf";
 pub const TCP_SO_RCV_BUF: &str = "so_rcvbuf";
+/// The file descriptor of an already bound and listening TCP socket, e.g. passed by the service
+/// manager with socket activation, that a listener adopts instead of binding a new one.
+pub const TCP_LISTEN_FD: &str = "listen_fd";
+/// Whether the links of an endpoint are read in a busy-poll loop instead of parking on the
+/// reactor, trading CPU for a lower reception latency.
+pub const BUSY_POLL: &str = "busy_poll";
 
 #[derive(Clone, Debug, Serialize, Hash, PartialEq, Eq)]
 pub struct Link {
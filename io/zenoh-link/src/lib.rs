@@ -21,6 +21,10 @@ use std::collections::HashMap;
 
 use zenoh_config::Config;
 pub use zenoh_link_commons::*;
+#[cfg(feature = "transport_mem")]
+pub use zenoh_link_mem as mem;
+#[cfg(feature = "transport_mem")]
+use zenoh_link_mem::{LinkManagerUnicastMem, MemLocatorInspector, MEM_LOCATOR_PREFIX};
 #[cfg(feature = "transport_quic")]
 pub use zenoh_link_quic as quic;
 #[cfg(feature = "transport_quic")]
@@ -92,6 +96,8 @@ pub const PROTOCOLS: &[&str] = &[
     unixpipe::UNIXPIPE_LOCATOR_PREFIX,
     #[cfg(all(feature = "transport_vsock", target_os = "linux"))]
     vsock::VSOCK_LOCATOR_PREFIX,
+    #[cfg(feature = "transport_mem")]
+    mem::MEM_LOCATOR_PREFIX,
 ];
 
 #[derive(Default, Clone)]
@@ -114,6 +120,8 @@ pub struct LocatorInspector {
     unixpipe_inspector: UnixPipeLocatorInspector,
     #[cfg(all(feature = "transport_vsock", target_os = "linux"))]
     vsock_inspector: VsockLocatorInspector,
+    #[cfg(feature = "transport_mem")]
+    mem_inspector: MemLocatorInspector,
 }
 impl LocatorInspector {
     pub fn is_reliable(&self, locator: &Locator) -> ZResult<bool> {
@@ -139,6 +147,8 @@ impl LocatorInspector {
             UNIXPIPE_LOCATOR_PREFIX => self.unixpipe_inspector.is_reliable(locator),
             #[cfg(all(feature = "transport_vsock", target_os = "linux"))]
             VSOCK_LOCATOR_PREFIX => self.vsock_inspector.is_reliable(locator),
+            #[cfg(feature = "transport_mem")]
+            MEM_LOCATOR_PREFIX => self.mem_inspector.is_reliable(locator),
             _ => bail!("Unsupported protocol: {}.", protocol),
         }
     }
@@ -168,6 +178,8 @@ impl LocatorInspector {
             UNIXPIPE_LOCATOR_PREFIX => self.unixpipe_inspector.is_multicast(locator).await,
             #[cfg(all(feature = "transport_vsock", target_os = "linux"))]
             VSOCK_LOCATOR_PREFIX => self.vsock_inspector.is_multicast(locator).await,
+            #[cfg(feature = "transport_mem")]
+            MEM_LOCATOR_PREFIX => self.mem_inspector.is_multicast(locator).await,
             _ => bail!("Unsupported protocol: {}.", protocol),
         }
     }
@@ -268,6 +280,8 @@ impl LinkManagerBuilderUnicast {
             }
             #[cfg(all(feature = "transport_vsock", target_os = "linux"))]
             VSOCK_LOCATOR_PREFIX => Ok(std::sync::Arc::new(LinkManagerUnicastVsock::new(_manager))),
+            #[cfg(feature = "transport_mem")]
+            MEM_LOCATOR_PREFIX => Ok(std::sync::Arc::new(LinkManagerUnicastMem::new(_manager))),
             _ => bail!("Unicast not supported for {} protocol", protocol),
         }
     }
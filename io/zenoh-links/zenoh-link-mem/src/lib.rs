@@ -0,0 +1,75 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This crate is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+//!
+//! Implements in-memory links between the zenoh runtimes of a same process.
+//!
+//! A `mem/<name>` listener registers `<name>` in a registry shared by the process, and a
+//! `mem/<name>` link connects to it through a pair of in-memory pipes, without any system call.
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use zenoh_core::zconfigurable;
+use zenoh_link_commons::LocatorInspector;
+use zenoh_protocol::{
+    core::{Locator, Metadata, Reliability},
+    transport::BatchSize,
+};
+use zenoh_result::ZResult;
+
+mod unicast;
+pub use unicast::*;
+
+pub const MEM_LOCATOR_PREFIX: &str = "mem";
+
+const IS_RELIABLE: bool = true;
+
+#[derive(Default, Clone, Copy)]
+pub struct MemLocatorInspector;
+#[async_trait]
+impl LocatorInspector for MemLocatorInspector {
+    fn protocol(&self) -> &str {
+        MEM_LOCATOR_PREFIX
+    }
+
+    async fn is_multicast(&self, _locator: &Locator) -> ZResult<bool> {
+        Ok(false)
+    }
+
+    fn is_reliable(&self, locator: &Locator) -> ZResult<bool> {
+        if let Some(reliability) = locator
+            .metadata()
+            .get(Metadata::RELIABILITY)
+            .map(Reliability::from_str)
+            .transpose()?
+        {
+            Ok(reliability == Reliability::Reliable)
+        } else {
+            Ok(IS_RELIABLE)
+        }
+    }
+}
+
+zconfigurable! {
+    // Default MTU in bytes.
+    static ref MEM_DEFAULT_MTU: BatchSize = BatchSize::MAX;
+    // Size in bytes of the pipe buffering each direction of a link.
+    // Default set to fit two batches of the maximum size.
+    static ref MEM_PIPE_SIZE: usize = 2 * (BatchSize::MAX as usize + 2);
+}
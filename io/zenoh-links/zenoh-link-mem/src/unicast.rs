@@ -0,0 +1,264 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt, DuplexStream, ReadHalf, WriteHalf},
+    sync::Mutex as AsyncMutex,
+};
+use zenoh_core::zlock;
+use zenoh_link_commons::{
+    LinkAuthId, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait, NewLinkChannelSender,
+};
+use zenoh_protocol::{
+    core::{EndPoint, Locator},
+    transport::BatchSize,
+};
+use zenoh_result::{bail, zerror, ZResult};
+
+use super::{MEM_DEFAULT_MTU, MEM_LOCATOR_PREFIX, MEM_PIPE_SIZE};
+
+lazy_static::lazy_static! {
+    // The listeners of all the link managers of the process, by name
+    static ref LISTENERS: Mutex<HashMap<String, NewLinkChannelSender>> = Mutex::new(HashMap::new());
+}
+
+// The identifier of the next link, making the source locators of the links unique
+static NEXT_LINK_ID: AtomicU64 = AtomicU64::new(0);
+
+pub struct LinkUnicastMem {
+    // The reading and writing halves of the pipe, each only used by one task at a time
+    reader: AsyncMutex<ReadHalf<DuplexStream>>,
+    writer: AsyncMutex<WriteHalf<DuplexStream>>,
+    src_locator: Locator,
+    dst_locator: Locator,
+}
+
+impl LinkUnicastMem {
+    fn new(pipe: DuplexStream, src_locator: Locator, dst_locator: Locator) -> Self {
+        let (reader, writer) = tokio::io::split(pipe);
+        Self {
+            reader: AsyncMutex::new(reader),
+            writer: AsyncMutex::new(writer),
+            src_locator,
+            dst_locator,
+        }
+    }
+}
+
+#[async_trait]
+impl LinkUnicastTrait for LinkUnicastMem {
+    async fn close(&self) -> ZResult<()> {
+        tracing::trace!("Closing mem link: {}", self);
+        self.writer.lock().await.shutdown().await.map_err(|e| {
+            let e = zerror!("mem link shutdown {}: {:?}", self, e);
+            tracing::trace!("{}", e);
+            e.into()
+        })
+    }
+
+    async fn write(&self, buffer: &[u8]) -> ZResult<usize> {
+        self.writer.lock().await.write(buffer).await.map_err(|e| {
+            let e = zerror!("Write error on mem link {}: {}", self, e);
+            tracing::trace!("{}", e);
+            e.into()
+        })
+    }
+
+    async fn write_all(&self, buffer: &[u8]) -> ZResult<()> {
+        self.writer
+            .lock()
+            .await
+            .write_all(buffer)
+            .await
+            .map_err(|e| {
+                let e = zerror!("Write error on mem link {}: {}", self, e);
+                tracing::trace!("{}", e);
+                e.into()
+            })
+    }
+
+    async fn read(&self, buffer: &mut [u8]) -> ZResult<usize> {
+        self.reader.lock().await.read(buffer).await.map_err(|e| {
+            let e = zerror!("Read error on mem link {}: {}", self, e);
+            tracing::trace!("{}", e);
+            e.into()
+        })
+    }
+
+    async fn read_exact(&self, buffer: &mut [u8]) -> ZResult<()> {
+        let _ = self
+            .reader
+            .lock()
+            .await
+            .read_exact(buffer)
+            .await
+            .map_err(|e| {
+                let e = zerror!("Read error on mem link {}: {}", self, e);
+                tracing::trace!("{}", e);
+                e
+            })?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn get_src(&self) -> &Locator {
+        &self.src_locator
+    }
+
+    #[inline(always)]
+    fn get_dst(&self) -> &Locator {
+        &self.dst_locator
+    }
+
+    #[inline(always)]
+    fn get_mtu(&self) -> BatchSize {
+        *MEM_DEFAULT_MTU
+    }
+
+    #[inline(always)]
+    fn get_interface_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    #[inline(always)]
+    fn is_reliable(&self) -> bool {
+        super::IS_RELIABLE
+    }
+
+    #[inline(always)]
+    fn is_streamed(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn get_auth_id(&self) -> &LinkAuthId {
+        &LinkAuthId::NONE
+    }
+}
+
+impl fmt::Display for LinkUnicastMem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} => {}", self.src_locator, self.dst_locator)?;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for LinkUnicastMem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mem")
+            .field("src", &self.src_locator)
+            .field("dst", &self.dst_locator)
+            .finish()
+    }
+}
+
+pub struct LinkManagerUnicastMem {
+    manager: NewLinkChannelSender,
+    listeners: Mutex<HashSet<EndPoint>>,
+}
+
+impl LinkManagerUnicastMem {
+    pub fn new(manager: NewLinkChannelSender) -> Self {
+        Self {
+            manager,
+            listeners: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl Drop for LinkManagerUnicastMem {
+    fn drop(&mut self) {
+        let mut listeners = zlock!(LISTENERS);
+        for endpoint in zlock!(self.listeners).iter() {
+            listeners.remove(endpoint.address().as_str());
+        }
+    }
+}
+
+#[async_trait]
+impl LinkManagerUnicastTrait for LinkManagerUnicastMem {
+    async fn new_link(&self, endpoint: EndPoint) -> ZResult<LinkUnicast> {
+        let name = endpoint.address();
+        let Some(listener) = zlock!(LISTENERS).get(name.as_str()).cloned() else {
+            bail!("Can not create a new mem link: no listener named {}", name);
+        };
+        let id = NEXT_LINK_ID.fetch_add(1, Ordering::Relaxed);
+        let src_locator = Locator::new(MEM_LOCATOR_PREFIX, format!("{name}.{id}"), "")?;
+        let dst_locator = Locator::new(MEM_LOCATOR_PREFIX, name.as_str(), "")?;
+
+        let (local, remote) = tokio::io::duplex(*MEM_PIPE_SIZE);
+        let remote = LinkUnicastMem::new(remote, dst_locator.clone(), src_locator.clone());
+        listener
+            .send_async(LinkUnicast(Arc::new(remote)))
+            .await
+            .map_err(|_| zerror!("Can not create a new mem link: listener {} is closed", name))?;
+        tracing::debug!("Connected mem link to: {}", name);
+        let link = Arc::new(LinkUnicastMem::new(local, src_locator, dst_locator));
+        Ok(LinkUnicast(link))
+    }
+
+    async fn new_listener(&self, endpoint: EndPoint) -> ZResult<Locator> {
+        let name = endpoint.address().as_str().to_string();
+        if name.is_empty() {
+            bail!(
+                "Can not create a new mem listener without a name: {}",
+                endpoint
+            );
+        }
+        {
+            let mut listeners = zlock!(LISTENERS);
+            if listeners.contains_key(&name) {
+                bail!(
+                    "Can not create a new mem listener: {} is already in use",
+                    name
+                );
+            }
+            listeners.insert(name, self.manager.clone());
+        }
+        let locator = endpoint.to_locator();
+        zlock!(self.listeners).insert(endpoint);
+        Ok(locator)
+    }
+
+    async fn del_listener(&self, endpoint: &EndPoint) -> ZResult<()> {
+        if !zlock!(self.listeners).remove(endpoint) {
+            bail!(
+                "Can not delete the listener because it has not been found: {}",
+                endpoint
+            );
+        }
+        zlock!(LISTENERS).remove(endpoint.address().as_str());
+        Ok(())
+    }
+
+    async fn get_listeners(&self) -> Vec<EndPoint> {
+        zlock!(self.listeners).iter().cloned().collect()
+    }
+
+    async fn get_locators(&self) -> Vec<Locator> {
+        zlock!(self.listeners)
+            .iter()
+            .map(|x| x.to_locator())
+            .collect()
+    }
+}
@@ -25,9 +25,8 @@ use rustls::{
     version::TLS13,
     ClientConfig, RootCertStore, ServerConfig,
 };
-use secrecy::ExposeSecret;
 use webpki::anchor_from_trusted_cert;
-use zenoh_config::Config as ZenohConfig;
+use zenoh_config::{secret, Config as ZenohConfig};
 use zenoh_link_commons::{
     tls::WebPkiVerifierAnyServerName, ConfigurationInspector, BIND_INTERFACE,
 };
@@ -44,11 +43,16 @@ pub struct TlsConfigurator;
 
 impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
     fn inspect_config(&self, config: &ZenohConfig) -> ZResult<String> {
-        let mut ps: Vec<(&str, &str)> = vec![];
-
         let c = config.transport().link().tls();
+        // Secret references are kept in the configuration and only resolved here
+        let secrets = secret::TlsSecrets::resolve(c)?;
+
+        let mut ps: Vec<(&str, &str)> = vec![];
 
-        match (c.root_ca_certificate(), c.root_ca_certificate_base64()) {
+        match (
+            secrets.root_ca_certificate.as_deref(),
+            secrets.root_ca_certificate_base64.as_deref(),
+        ) {
             (Some(_), Some(_)) => {
                 bail!("Only one between 'root_ca_certificate' and 'root_ca_certificate_base64' can be present!")
             }
@@ -56,15 +60,15 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
                 ps.push((TLS_ROOT_CA_CERTIFICATE_FILE, ca_certificate));
             }
             (None, Some(ca_certificate)) => {
-                ps.push((
-                    TLS_ROOT_CA_CERTIFICATE_BASE64,
-                    ca_certificate.expose_secret(),
-                ));
+                ps.push((TLS_ROOT_CA_CERTIFICATE_BASE64, ca_certificate));
             }
             _ => {}
         }
 
-        match (c.listen_private_key(), c.listen_private_key_base64()) {
+        match (
+            secrets.listen_private_key.as_deref(),
+            secrets.listen_private_key_base64.as_deref(),
+        ) {
             (Some(_), Some(_)) => {
                 bail!("Only one between 'listen_private_key' and 'listen_private_key_base64' can be present!")
             }
@@ -72,15 +76,15 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
                 ps.push((TLS_LISTEN_PRIVATE_KEY_FILE, server_private_key));
             }
             (None, Some(server_private_key)) => {
-                ps.push((
-                    TLS_LISTEN_PRIVATE_KEY_BASE64,
-                    server_private_key.expose_secret(),
-                ));
+                ps.push((TLS_LISTEN_PRIVATE_KEY_BASE64, server_private_key));
             }
             _ => {}
         }
 
-        match (c.listen_certificate(), c.listen_certificate_base64()) {
+        match (
+            secrets.listen_certificate.as_deref(),
+            secrets.listen_certificate_base64.as_deref(),
+        ) {
             (Some(_), Some(_)) => {
                 bail!("Only one between 'listen_certificate' and 'listen_certificate_base64' can be present!")
             }
@@ -88,10 +92,7 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
                 ps.push((TLS_LISTEN_CERTIFICATE_FILE, server_certificate));
             }
             (None, Some(server_certificate)) => {
-                ps.push((
-                    TLS_LISTEN_CERTIFICATE_BASE64,
-                    server_certificate.expose_secret(),
-                ));
+                ps.push((TLS_LISTEN_CERTIFICATE_BASE64, server_certificate));
             }
             _ => {}
         }
@@ -101,7 +102,10 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
             false => ps.push((TLS_ENABLE_MTLS, "false")),
         }
 
-        match (c.connect_private_key(), c.connect_private_key_base64()) {
+        match (
+            secrets.connect_private_key.as_deref(),
+            secrets.connect_private_key_base64.as_deref(),
+        ) {
             (Some(_), Some(_)) => {
                 bail!("Only one between 'connect_private_key' and 'connect_private_key_base64' can be present!")
             }
@@ -109,15 +113,15 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
                 ps.push((TLS_CONNECT_PRIVATE_KEY_FILE, client_private_key));
             }
             (None, Some(client_private_key)) => {
-                ps.push((
-                    TLS_CONNECT_PRIVATE_KEY_BASE64,
-                    client_private_key.expose_secret(),
-                ));
+                ps.push((TLS_CONNECT_PRIVATE_KEY_BASE64, client_private_key));
             }
             _ => {}
         }
 
-        match (c.connect_certificate(), c.connect_certificate_base64()) {
+        match (
+            secrets.connect_certificate.as_deref(),
+            secrets.connect_certificate_base64.as_deref(),
+        ) {
             (Some(_), Some(_)) => {
                 bail!("Only one between 'connect_certificate' and 'connect_certificate_base64' can be present!")
             }
@@ -125,10 +129,7 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
                 ps.push((TLS_CONNECT_CERTIFICATE_FILE, client_certificate));
             }
             (None, Some(client_certificate)) => {
-                ps.push((
-                    TLS_CONNECT_CERTIFICATE_BASE64,
-                    client_certificate.expose_secret(),
-                ));
+                ps.push((TLS_CONNECT_CERTIFICATE_BASE64, client_certificate));
             }
             _ => {}
         }
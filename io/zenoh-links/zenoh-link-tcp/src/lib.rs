@@ -82,6 +82,8 @@ zconfigurable! {
     // Amount of time in microseconds to throttle the accept loop upon an error.
     // Default set to 100 ms.
     static ref TCP_ACCEPT_THROTTLE_TIME: u64 = 100_000;
+    // Number of unsuccessful reads of a busy-polled link before yielding to the runtime.
+    static ref TCP_BUSY_POLL_SPINS: u32 = 1_000;
 }
 
 pub async fn get_tcp_addrs(address: Address<'_>) -> ZResult<impl Iterator<Item = SocketAddr>> {
@@ -11,7 +11,9 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{cell::UnsafeCell, convert::TryInto, fmt, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    cell::UnsafeCell, convert::TryInto, fmt, io::Read, net::SocketAddr, sync::Arc, time::Duration,
+};
 
 use async_trait::async_trait;
 use tokio::{
@@ -30,8 +32,8 @@ use zenoh_protocol::{
 use zenoh_result::{bail, zerror, Error as ZError, ZResult};
 
 use crate::{
-    get_tcp_addrs, utils::TcpLinkConfig, TCP_ACCEPT_THROTTLE_TIME, TCP_DEFAULT_MTU,
-    TCP_LINGER_TIMEOUT, TCP_LOCATOR_PREFIX,
+    get_tcp_addrs, utils::TcpLinkConfig, TCP_ACCEPT_THROTTLE_TIME, TCP_BUSY_POLL_SPINS,
+    TCP_DEFAULT_MTU, TCP_LINGER_TIMEOUT, TCP_LOCATOR_PREFIX,
 };
 
 pub struct LinkUnicastTcp {
@@ -45,12 +47,19 @@ pub struct LinkUnicastTcp {
     dst_locator: Locator,
     // The computed mtu
     mtu: BatchSize,
+    // Whether the socket is read in a busy-poll loop
+    busy_poll: bool,
 }
 
 unsafe impl Sync for LinkUnicastTcp {}
 
 impl LinkUnicastTcp {
-    fn new(socket: TcpStream, src_addr: SocketAddr, dst_addr: SocketAddr) -> LinkUnicastTcp {
+    fn new(
+        socket: TcpStream,
+        src_addr: SocketAddr,
+        dst_addr: SocketAddr,
+        busy_poll: bool,
+    ) -> LinkUnicastTcp {
         // Set the TCP nodelay option
         if let Err(err) = socket.set_nodelay(true) {
             tracing::warn!(
@@ -104,9 +113,42 @@ impl LinkUnicastTcp {
             dst_addr,
             dst_locator: Locator::new(TCP_LOCATOR_PREFIX, dst_addr.to_string(), "").unwrap(),
             mtu,
+            busy_poll,
         }
     }
 
+    // Reads the socket without parking on the reactor: the non-blocking socket is polled in a
+    // spin loop, yielding to the runtime every TCP_BUSY_POLL_SPINS attempts so that the read can
+    // still be timed out or cancelled.
+    async fn busy_read(&self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let mut spins = 0;
+        loop {
+            match (&*socket2::SockRef::from(&*self.get_mut_socket())).read(buffer) {
+                Ok(n) => return Ok(n),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+            spins += 1;
+            if spins < *TCP_BUSY_POLL_SPINS {
+                std::hint::spin_loop();
+            } else {
+                spins = 0;
+                tokio::task::yield_now().await;
+            }
+        }
+    }
+
+    async fn busy_read_exact(&self, mut buffer: &mut [u8]) -> std::io::Result<()> {
+        while !buffer.is_empty() {
+            match self.busy_read(buffer).await? {
+                0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+                n => buffer = &mut buffer[n..],
+            }
+        }
+        Ok(())
+    }
+
     #[allow(clippy::mut_from_ref)]
     fn get_mut_socket(&self) -> &mut TcpStream {
         unsafe { &mut *self.socket.get() }
@@ -142,7 +184,12 @@ impl LinkUnicastTrait for LinkUnicastTcp {
     }
 
     async fn read(&self, buffer: &mut [u8]) -> ZResult<usize> {
-        self.get_mut_socket().read(buffer).await.map_err(|e| {
+        let res = if self.busy_poll {
+            self.busy_read(buffer).await
+        } else {
+            self.get_mut_socket().read(buffer).await
+        };
+        res.map_err(|e| {
             let e = zerror!("Read error on TCP link {}: {}", self, e);
             tracing::trace!("{}", e);
             e.into()
@@ -150,16 +197,16 @@ impl LinkUnicastTrait for LinkUnicastTcp {
     }
 
     async fn read_exact(&self, buffer: &mut [u8]) -> ZResult<()> {
-        let _ = self
-            .get_mut_socket()
-            .read_exact(buffer)
-            .await
-            .map_err(|e| {
-                let e = zerror!("Read error on TCP link {}: {}", self, e);
-                tracing::trace!("{}", e);
-                e
-            })?;
-        Ok(())
+        let res = if self.busy_poll {
+            self.busy_read_exact(buffer).await
+        } else {
+            self.get_mut_socket().read_exact(buffer).await.map(|_| ())
+        };
+        res.map_err(|e| {
+            let e = zerror!("Read error on TCP link {}: {}", self, e);
+            tracing::trace!("{}", e);
+            e.into()
+        })
     }
 
     #[inline(always)]
@@ -223,6 +270,7 @@ impl fmt::Debug for LinkUnicastTcp {
             .field("src", &self.src_addr)
             .field("dst", &self.dst_addr)
             .field("mtu", &self.get_mtu())
+            .field("busy_poll", &self.busy_poll)
             .finish()
     }
 }
@@ -239,6 +287,38 @@ impl LinkManagerUnicastTcp {
             listeners: ListenersUnicastIP::new(),
         }
     }
+
+    async fn add_listener(
+        &self,
+        endpoint: EndPoint,
+        socket: TcpListener,
+        local_addr: SocketAddr,
+        busy_poll: bool,
+    ) -> ZResult<Locator> {
+        // Update the endpoint locator address
+        let endpoint = EndPoint::new(
+            endpoint.protocol(),
+            format!("{local_addr}"),
+            endpoint.metadata(),
+            endpoint.config(),
+        )?;
+
+        let token = self.listeners.token.child_token();
+
+        let task = {
+            let token = token.clone();
+            let manager = self.manager.clone();
+
+            async move { accept_task(socket, token, manager, busy_poll).await }
+        };
+
+        let locator = endpoint.to_locator();
+        self.listeners
+            .add_listener(endpoint, local_addr, task, token)
+            .await?;
+
+        Ok(locator)
+    }
 }
 
 #[async_trait]
@@ -258,7 +338,12 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTcp {
         for da in dst_addrs {
             match socket_config.new_link(&da).await {
                 Ok((stream, src_addr, dst_addr)) => {
-                    let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr));
+                    let link = Arc::new(LinkUnicastTcp::new(
+                        stream,
+                        src_addr,
+                        dst_addr,
+                        link_config.busy_poll,
+                    ));
                     return Ok(LinkUnicast(link));
                 }
                 Err(e) => {
@@ -278,40 +363,34 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTcp {
         )
     }
 
-    async fn new_listener(&self, mut endpoint: EndPoint) -> ZResult<Locator> {
-        let addrs = get_tcp_addrs(endpoint.address()).await?;
+    async fn new_listener(&self, endpoint: EndPoint) -> ZResult<Locator> {
         let config = endpoint.config();
 
         let link_config = TcpLinkConfig::new(&config)?;
+        let busy_poll = link_config.busy_poll;
+        #[cfg(unix)]
+        if let Some(fd) = link_config.listen_fd {
+            let (socket, local_addr) = crate::utils::adopt_listener(fd).map_err(|e| {
+                zerror!(
+                    "Can not create a new TCP listener bound to {}: {}",
+                    endpoint,
+                    e
+                )
+            })?;
+            return self
+                .add_listener(endpoint, socket, local_addr, busy_poll)
+                .await;
+        }
+        let addrs = get_tcp_addrs(endpoint.address()).await?;
         let socket_config: TcpSocketConfig<'_> = link_config.into();
 
         let mut errs: Vec<ZError> = vec![];
         for da in addrs {
             match socket_config.new_listener(&da) {
                 Ok((socket, local_addr)) => {
-                    // Update the endpoint locator address
-                    endpoint = EndPoint::new(
-                        endpoint.protocol(),
-                        format!("{local_addr}"),
-                        endpoint.metadata(),
-                        endpoint.config(),
-                    )?;
-
-                    let token = self.listeners.token.child_token();
-
-                    let task = {
-                        let token = token.clone();
-                        let manager = self.manager.clone();
-
-                        async move { accept_task(socket, token, manager).await }
-                    };
-
-                    let locator = endpoint.to_locator();
-                    self.listeners
-                        .add_listener(endpoint, local_addr, task, token)
-                        .await?;
-
-                    return Ok(locator);
+                    return self
+                        .add_listener(endpoint, socket, local_addr, busy_poll)
+                        .await;
                 }
                 Err(e) => {
                     errs.push(e);
@@ -371,6 +450,7 @@ async fn accept_task(
     socket: TcpListener,
     token: CancellationToken,
     manager: NewLinkChannelSender,
+    busy_poll: bool,
 ) -> ZResult<()> {
     async fn accept(socket: &TcpListener) -> ZResult<(TcpStream, SocketAddr)> {
         let res = socket.accept().await.map_err(|e| zerror!(e))?;
@@ -401,7 +481,7 @@ async fn accept_task(
 
                         tracing::debug!("Accepted TCP connection on {:?}: {:?}", src_addr, dst_addr);
                         // Create the new link object
-                        let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr));
+                        let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr, busy_poll));
 
                         // Communicate the new link to the initial transport manager
                         if let Err(e) = manager.send_async(LinkUnicast(link)).await {
@@ -11,9 +11,17 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(unix)]
+use std::{net::SocketAddr, os::unix::io::RawFd, sync::Mutex};
+
+#[cfg(unix)]
+use tokio::net::TcpListener;
 use zenoh_config::Config as ZenohConfig;
+#[cfg(unix)]
+use zenoh_link_commons::TCP_LISTEN_FD;
 use zenoh_link_commons::{
-    tcp::TcpSocketConfig, ConfigurationInspector, BIND_INTERFACE, TCP_SO_RCV_BUF, TCP_SO_SND_BUF,
+    tcp::TcpSocketConfig, ConfigurationInspector, BIND_INTERFACE, BUSY_POLL, TCP_SO_RCV_BUF,
+    TCP_SO_SND_BUF,
 };
 use zenoh_protocol::core::{parameters, Config};
 use zenoh_result::{zerror, ZResult};
@@ -45,6 +53,9 @@ pub(crate) struct TcpLinkConfig<'a> {
     pub(crate) rx_buffer_size: Option<u32>,
     pub(crate) tx_buffer_size: Option<u32>,
     pub(crate) bind_iface: Option<&'a str>,
+    pub(crate) busy_poll: bool,
+    #[cfg(unix)]
+    pub(crate) listen_fd: Option<RawFd>,
 }
 
 impl<'a> TcpLinkConfig<'a> {
@@ -53,6 +64,9 @@ impl<'a> TcpLinkConfig<'a> {
             rx_buffer_size: None,
             tx_buffer_size: None,
             bind_iface: config.get(BIND_INTERFACE),
+            busy_poll: false,
+            #[cfg(unix)]
+            listen_fd: None,
         };
 
         if let Some(size) = config.get(TCP_SO_RCV_BUF) {
@@ -68,6 +82,20 @@ impl<'a> TcpLinkConfig<'a> {
             );
         };
 
+        if let Some(busy_poll) = config.get(BUSY_POLL) {
+            tcp_config.busy_poll = busy_poll
+                .parse()
+                .map_err(|_| zerror!("Unknown TCP busy poll argument: {}", busy_poll))?;
+        };
+
+        #[cfg(unix)]
+        if let Some(fd) = config.get(TCP_LISTEN_FD) {
+            tcp_config.listen_fd = Some(
+                fd.parse()
+                    .map_err(|_| zerror!("Unknown TCP listen file descriptor argument: {}", fd))?,
+            );
+        };
+
         Ok(tcp_config)
     }
 }
@@ -77,3 +105,33 @@ impl<'a> From<TcpLinkConfig<'a>> for TcpSocketConfig<'a> {
         Self::new(value.tx_buffer_size, value.rx_buffer_size, value.bind_iface)
     }
 }
+
+// The file descriptors adopted by a listener, which are closed once the listener is deleted
+#[cfg(unix)]
+static ADOPTED_FDS: Mutex<Vec<RawFd>> = Mutex::new(Vec::new());
+
+/// Adopts the already bound and listening TCP socket `fd`.
+#[cfg(unix)]
+pub(crate) fn adopt_listener(fd: RawFd) -> ZResult<(TcpListener, SocketAddr)> {
+    use std::os::unix::io::{BorrowedFd, FromRawFd};
+
+    let mut adopted = ADOPTED_FDS.lock().unwrap();
+    if adopted.contains(&fd) {
+        return Err(zerror!("file descriptor {} has already been adopted", fd).into());
+    }
+    // SAFETY: the file descriptor is only borrowed to check that it is a TCP socket
+    let socket = unsafe { BorrowedFd::borrow_raw(fd) };
+    let socket = socket2::SockRef::from(&socket);
+    let local_addr = match (socket.r#type(), socket.local_addr()) {
+        (Ok(socket2::Type::STREAM), Ok(addr)) => addr.as_socket(),
+        _ => None,
+    }
+    .ok_or_else(|| zerror!("file descriptor {} is not a TCP socket", fd))?;
+
+    // SAFETY: the socket is owned by the listener from now on, and never adopted again
+    let listener = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+    adopted.push(fd);
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    Ok((listener, local_addr))
+}
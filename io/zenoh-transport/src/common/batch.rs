@@ -23,6 +23,8 @@ use zenoh_codec::{
     transport::batch::{BatchError, Zenoh080Batch},
     RCodec, WCodec,
 };
+#[cfg(feature = "trace_spans")]
+use zenoh_protocol::transport::TransportSn;
 use zenoh_protocol::{
     network::NetworkMessage,
     transport::{fragment::FragmentHeader, frame::FrameHeader, BatchSize, TransportMessage},
@@ -31,7 +33,7 @@ use zenoh_result::{zerror, ZResult};
 #[cfg(feature = "transport_compression")]
 use {std::sync::Arc, zenoh_protocol::common::imsg};
 
-const L_LEN: usize = (BatchSize::BITS / 8) as usize;
+pub(crate) const L_LEN: usize = (BatchSize::BITS / 8) as usize;
 const H_LEN: usize = BatchHeader::SIZE;
 
 // Split the inner buffer into (length, header, payload) immutable slices
@@ -164,6 +166,11 @@ impl WBatchStats {
     }
 }
 
+// The range of sequence numbers of the frames and fragments serialized on a batch, used to
+// correlate the spans of the messages with the span of the link write of the batch
+#[cfg(feature = "trace_spans")]
+pub type WBatchSns = Option<(TransportSn, TransportSn)>;
+
 #[repr(u8)]
 #[derive(Debug)]
 pub enum Finalize {
@@ -201,6 +208,9 @@ pub struct WBatch {
     // Statistics related to this batch
     #[cfg(feature = "stats")]
     pub stats: WBatchStats,
+    // The sequence numbers of the messages serialized on this batch
+    #[cfg(feature = "trace_spans")]
+    pub sns: WBatchSns,
     // an ephemeral batch will not be recycled in the pipeline
     // it can be used to push a stop fragment when no batch are available
     pub ephemeral: bool,
@@ -215,6 +225,8 @@ impl WBatch {
             ephemeral: false,
             #[cfg(feature = "stats")]
             stats: WBatchStats::default(),
+            #[cfg(feature = "trace_spans")]
+            sns: None,
         };
 
         // Bring the batch in a clear state
@@ -256,9 +268,20 @@ impl WBatch {
         {
             self.stats.clear();
         }
+        #[cfg(feature = "trace_spans")]
+        {
+            self.sns = None;
+        }
         Self::init(&mut self.buffer, &self.config);
     }
 
+    /// Record the sequence number of a message serialized on the [`WBatch`].
+    #[cfg(feature = "trace_spans")]
+    #[inline(always)]
+    pub fn trace_sn(&mut self, sn: TransportSn) {
+        self.sns = Some(self.sns.map_or((sn, sn), |(first, _)| (first, sn)));
+    }
+
     /// Get a `&[u8]` to access the internal memory buffer, usually for transmitting it on the network.
     #[inline(always)]
     pub fn as_slice(&self) -> &[u8] {
@@ -611,6 +634,9 @@ mod tests {
             ext_qos: ext::QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType::DEFAULT,
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -618,6 +644,8 @@ mod tests {
                 #[cfg(feature = "shared-memory")]
                 ext_shm: None,
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
                 ext_unknown: vec![],
                 payload: ZBuf::from(vec![0u8; 8]),
             }),
@@ -0,0 +1,167 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The congestion met by the messages pushed on the transmission pipelines by a thread.
+//!
+//! Pushing a message on a transmission pipeline is synchronous: the message is either
+//! serialized on a batch, possibly after waiting for a batch to be freed, or dropped. The
+//! outcome of the pushes made while running [`observe`] is reported to its caller.
+use std::cell::Cell;
+
+use crate::QueueOccupancy;
+
+/// The congestion met by the messages pushed on the transmission pipelines, see [`observe`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CongestionReport {
+    /// The number of messages which waited for a free batch before being pushed.
+    pub blocked: usize,
+    /// The number of messages dropped because their queue was congested.
+    pub dropped: usize,
+    /// The number of messages dropped because their deadline expired.
+    pub expired: usize,
+    /// The most occupied queue the messages were pushed on, if any.
+    pub queue: Option<QueueOccupancy>,
+}
+
+impl CongestionReport {
+    fn merge(&mut self, other: &CongestionReport) {
+        self.blocked += other.blocked;
+        self.dropped += other.dropped;
+        self.expired += other.expired;
+        if let Some(queue) = other.queue {
+            self.add_queue(queue);
+        }
+    }
+
+    fn add_queue(&mut self, queue: QueueOccupancy) {
+        match self.queue {
+            // Compare the occupancy ratios without dividing
+            Some(q) if q.occupied * queue.capacity >= queue.occupied * q.capacity => {}
+            _ => self.queue = Some(queue),
+        }
+    }
+}
+
+thread_local! {
+    static REPORT: Cell<Option<CongestionReport>> = const { Cell::new(None) };
+}
+
+// Restores the report of an enclosing `observe`, even if the observed function panics
+struct Scope {
+    outer: Option<CongestionReport>,
+}
+
+impl Scope {
+    fn enter() -> Self {
+        Scope {
+            outer: REPORT.with(|r| r.replace(Some(CongestionReport::default()))),
+        }
+    }
+
+    fn exit(mut self) -> CongestionReport {
+        let report = REPORT.with(|r| r.get()).unwrap_or_default();
+        if let Some(outer) = &mut self.outer {
+            outer.merge(&report);
+        }
+        report
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        REPORT.with(|r| r.set(self.outer));
+    }
+}
+
+/// Runs `f` and returns the congestion met by the messages it pushed on the transmission
+/// pipelines from the current thread.
+///
+/// The pushes made in a nested call to `observe` are also reported to the enclosing one.
+pub fn observe<R>(f: impl FnOnce() -> R) -> (R, CongestionReport) {
+    let scope = Scope::enter();
+    let res = f();
+    (res, scope.exit())
+}
+
+/// Records the outcome of a push if the current thread is observed, `queue` being only
+/// evaluated in that case.
+#[inline]
+pub(crate) fn record(blocked: bool, dropped: bool, queue: impl FnOnce() -> QueueOccupancy) {
+    REPORT.with(|r| {
+        if let Some(mut report) = r.get() {
+            report.blocked += blocked as usize;
+            report.dropped += dropped as usize;
+            report.add_queue(queue());
+            r.set(Some(report));
+        }
+    });
+}
+
+/// Records a message dropped because its deadline expired if the current thread is observed,
+/// `queue` being only evaluated in that case.
+#[inline]
+pub(crate) fn record_expiry(queue: impl FnOnce() -> QueueOccupancy) {
+    REPORT.with(|r| {
+        if let Some(mut report) = r.get() {
+            report.expired += 1;
+            report.add_queue(queue());
+            r.set(Some(report));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use zenoh_protocol::core::Priority;
+
+    use super::*;
+
+    fn queue(occupied: usize, capacity: usize) -> QueueOccupancy {
+        QueueOccupancy {
+            priority: Priority::DEFAULT,
+            occupied,
+            capacity,
+            alarm: false,
+            alarms: 0,
+            served: 0,
+            dropped: 0,
+            expired: 0,
+        }
+    }
+
+    #[test]
+    fn congestion_observe() {
+        record(true, true, || unreachable!());
+
+        let ((), report) = observe(|| {
+            record(false, false, || queue(1, 4));
+            let ((), inner) = observe(|| {
+                record(true, false, || queue(3, 8));
+                record(false, true, || queue(2, 16));
+                record_expiry(|| queue(1, 16));
+            });
+            assert_eq!(inner.blocked, 1);
+            assert_eq!(inner.dropped, 1);
+            assert_eq!(inner.expired, 1);
+            assert_eq!(inner.queue, Some(queue(3, 8)));
+        });
+        assert_eq!(report.blocked, 1);
+        assert_eq!(report.dropped, 1);
+        assert_eq!(report.expired, 1);
+        assert_eq!(report.queue, Some(queue(3, 8)));
+
+        let ((), report) = observe(|| {});
+        assert_eq!(report, CongestionReport::default());
+    }
+}
@@ -0,0 +1,159 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Injection of faults on the batches sent on the unicast links, for chaos testing.
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+use zenoh_link::Locator;
+use zenoh_result::{bail, ZResult};
+
+/// The faults injected on the batches sent on a link.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinkFaults {
+    /// The delay in milliseconds before sending each batch.
+    pub delay_ms: u64,
+    /// The probability of dropping a batch.
+    pub drop: f64,
+    /// The probability of sending a batch after the next one.
+    pub reorder: f64,
+    /// The probability of flipping a random bit of a batch.
+    pub corrupt: f64,
+}
+
+impl LinkFaults {
+    fn validate(&self) -> ZResult<()> {
+        for (name, p) in [
+            ("drop", self.drop),
+            ("reorder", self.reorder),
+            ("corrupt", self.corrupt),
+        ] {
+            if !(0.0..=1.0).contains(&p) {
+                bail!("The {name} probability of a link fault must be between 0 and 1: {p}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The faults injected on the unicast links of a [`TransportManager`](crate::TransportManager).
+///
+/// The faults of a link are selected by its destination locator, and default to the faults
+/// injected on all the links. They apply to the batches sent after they are set, on the links
+/// already established as on the new ones.
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    is_active: AtomicBool,
+    faults: RwLock<HashMap<Option<Locator>, LinkFaults>>,
+}
+
+impl FaultInjector {
+    /// Inject faults on the links to `link`, or on all the links if `None`.
+    pub fn set(&self, link: Option<Locator>, faults: LinkFaults) -> ZResult<()> {
+        faults.validate()?;
+        let mut guard = self.faults.write().unwrap();
+        guard.insert(link, faults);
+        self.is_active.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Stop injecting the faults set for `link`, or for all the links if `None`.
+    pub fn remove(&self, link: Option<&Locator>) {
+        let mut guard = self.faults.write().unwrap();
+        guard.retain(|l, _| l.as_ref() != link);
+        self.is_active.store(!guard.is_empty(), Ordering::Release);
+    }
+
+    /// Stop injecting faults.
+    pub fn clear(&self) {
+        let mut guard = self.faults.write().unwrap();
+        guard.clear();
+        self.is_active.store(false, Ordering::Release);
+    }
+
+    /// The injected faults, by destination locator.
+    pub fn get(&self) -> Vec<(Option<Locator>, LinkFaults)> {
+        self.faults
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(l, f)| (l.clone(), *f))
+            .collect()
+    }
+
+    /// The faults to inject on a link to `dst`, if any.
+    pub(crate) fn faults(&self, dst: &Locator) -> Option<LinkFaults> {
+        if !self.is_active.load(Ordering::Acquire) {
+            return None;
+        }
+        let guard = self.faults.read().unwrap();
+        guard
+            .iter()
+            .find_map(|(l, f)| (l.as_ref() == Some(dst)).then_some(*f))
+            .or_else(|| guard.get(&None).copied())
+    }
+}
+
+// The same fault injector is shared by the links of a manager
+impl PartialEq for FaultInjector {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for FaultInjector {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fault_injector_lookup() {
+        let injector = FaultInjector::default();
+        let tcp: Locator = "tcp/127.0.0.1:7447".parse().unwrap();
+        let udp: Locator = "udp/127.0.0.1:7447".parse().unwrap();
+        assert_eq!(injector.faults(&tcp), None);
+
+        let all = LinkFaults {
+            drop: 0.5,
+            ..Default::default()
+        };
+        let one = LinkFaults {
+            delay_ms: 10,
+            ..Default::default()
+        };
+        injector.set(None, all).unwrap();
+        injector.set(Some(tcp.clone()), one).unwrap();
+        assert_eq!(injector.faults(&tcp), Some(one));
+        assert_eq!(injector.faults(&udp), Some(all));
+
+        injector.remove(None);
+        assert_eq!(injector.faults(&udp), None);
+        injector.clear();
+        assert_eq!(injector.faults(&tcp), None);
+
+        let invalid = LinkFaults {
+            corrupt: 2.0,
+            ..Default::default()
+        };
+        assert!(injector.set(None, invalid).is_err());
+    }
+}
@@ -12,9 +12,13 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 pub mod batch;
+pub mod congestion;
 pub(crate) mod defragmentation;
+#[cfg(feature = "fault_injection")]
+pub mod faults;
 pub(crate) mod pipeline;
 pub(crate) mod priority;
+pub mod reception;
 pub(crate) mod seq_num;
 #[cfg(feature = "stats")]
 pub mod stats;
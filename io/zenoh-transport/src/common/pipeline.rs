@@ -15,14 +15,15 @@ use std::{
     fmt,
     ops::Add,
     sync::{
-        atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicUsize, Ordering},
         Arc, Mutex, MutexGuard,
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use crossbeam_utils::CachePadded;
 use ringbuffer_spsc::{RingBuffer, RingBufferReader, RingBufferWriter};
+use serde::Serialize;
 use zenoh_buffers::{
     reader::{HasReader, Reader},
     writer::HasWriter,
@@ -31,8 +32,10 @@ use zenoh_buffers::{
 use zenoh_codec::{transport::batch::BatchError, WCodec, Zenoh080};
 use zenoh_config::QueueSizeConf;
 use zenoh_core::zlock;
+#[cfg(feature = "trace_spans")]
+use zenoh_protocol::transport::TransportSn;
 use zenoh_protocol::{
-    core::Priority,
+    core::{Priority, NTP64},
     network::NetworkMessage,
     transport::{
         fragment,
@@ -42,12 +45,13 @@ use zenoh_protocol::{
     },
 };
 use zenoh_sync::{event, Notifier, WaitDeadlineError, Waiter};
+use zenoh_util::flight_recorder::FlightRecorder;
 
 use super::{
     batch::{Encode, WBatch},
     priority::{TransportChannelTx, TransportPriorityTx},
 };
-use crate::common::batch::BatchConfig;
+use crate::common::{batch::BatchConfig, congestion};
 
 const RBLEN: usize = QueueSizeConf::MAX;
 
@@ -55,6 +59,112 @@ const RBLEN: usize = QueueSizeConf::MAX;
 struct StageInRefill {
     n_ref_r: Waiter,
     s_ref_r: RingBufferReader<WBatch, RBLEN>,
+    gauge: Arc<QueueGauge>,
+}
+
+/// The occupancy of a priority queue of a transmission pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct QueueOccupancy {
+    pub priority: Priority,
+    /// The number of batches being filled or waiting to be transmitted.
+    pub occupied: usize,
+    /// The total number of batches of the queue.
+    pub capacity: usize,
+    /// Whether the occupancy is above the alarm threshold.
+    pub alarm: bool,
+    /// The number of times the occupancy rose above the alarm threshold.
+    pub alarms: usize,
+    /// The number of batches transmitted from the queue.
+    pub served: usize,
+    /// The number of messages dropped because the queue was congested.
+    pub dropped: usize,
+    /// The number of messages dropped because their deadline expired before being transmitted.
+    pub expired: usize,
+}
+
+// Inner structure to track the number of batches taken out of the refill ring buffer
+struct QueueGauge {
+    priority: Priority,
+    capacity: usize,
+    alarm_threshold: Option<usize>,
+    occupied: CachePadded<AtomicUsize>,
+    alarm: AtomicBool,
+    alarms: AtomicUsize,
+    served: AtomicUsize,
+    dropped: AtomicUsize,
+    expired: AtomicUsize,
+}
+
+impl QueueGauge {
+    fn new(priority: Priority, capacity: usize, alarm_threshold: Option<u8>) -> Self {
+        Self {
+            priority,
+            capacity,
+            // The threshold is a percentage of the capacity, rounded up to at least one batch
+            alarm_threshold: alarm_threshold
+                .map(|pct| (capacity * pct.min(100) as usize).div_ceil(100).max(1)),
+            occupied: CachePadded::new(AtomicUsize::new(0)),
+            alarm: AtomicBool::new(false),
+            alarms: AtomicUsize::new(0),
+            served: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+            expired: AtomicUsize::new(0),
+        }
+    }
+
+    fn inc(&self) {
+        let occupied = self.occupied.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(threshold) = self.alarm_threshold {
+            if occupied >= threshold && !self.alarm.swap(true, Ordering::Relaxed) {
+                self.alarms.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    "Transmission queue {:?} is {}/{} full: head-of-line blocking may occur",
+                    self.priority,
+                    occupied,
+                    self.capacity
+                );
+            }
+        }
+    }
+
+    fn dec(&self) {
+        let occupied = self.occupied.fetch_sub(1, Ordering::Relaxed) - 1;
+        if let Some(threshold) = self.alarm_threshold {
+            if occupied < threshold && self.alarm.swap(false, Ordering::Relaxed) {
+                tracing::info!(
+                    "Transmission queue {:?} is back to {}/{}",
+                    self.priority,
+                    occupied,
+                    self.capacity
+                );
+            }
+        }
+    }
+
+    fn serve(&self) {
+        self.served.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_drop(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_expiry(&self) {
+        self.expired.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn occupancy(&self) -> QueueOccupancy {
+        QueueOccupancy {
+            priority: self.priority,
+            occupied: self.occupied.load(Ordering::Relaxed),
+            capacity: self.capacity,
+            alarm: self.alarm.load(Ordering::Relaxed),
+            alarms: self.alarms.load(Ordering::Relaxed),
+            served: self.served.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -68,7 +178,11 @@ impl std::error::Error for TransportClosed {}
 
 impl StageInRefill {
     fn pull(&mut self) -> Option<WBatch> {
-        self.s_ref_r.pull()
+        let batch = self.s_ref_r.pull();
+        if batch.is_some() {
+            self.gauge.inc();
+        }
+        batch
     }
 
     fn wait(&self) -> bool {
@@ -216,14 +330,33 @@ impl LazyDeadline {
     }
 }
 
+// The instant at which a message deadline expires, or `None` if it has already expired
+fn expiry(deadline: NTP64) -> Option<Instant> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    deadline
+        .to_duration()
+        .checked_sub(now)
+        .map(|left| Instant::now() + left)
+}
+
+/// Whether the deadline of a message has expired.
+pub(crate) fn has_expired(deadline: NTP64) -> bool {
+    expiry(deadline).is_none()
+}
+
 struct Deadline {
     lazy_deadline: LazyDeadline,
+    // The instant at which the message itself expires, if it has a deadline
+    expiry: Option<Instant>,
 }
 
 impl Deadline {
-    fn new(wait_time: Duration, max_wait_time: Option<Duration>) -> Self {
+    fn new(wait_time: Duration, max_wait_time: Option<Duration>, expiry: Option<Instant>) -> Self {
         Self {
             lazy_deadline: LazyDeadline::new(WaitTime::new(wait_time, max_wait_time)),
+            expiry,
         }
     }
 
@@ -231,13 +364,38 @@ impl Deadline {
     fn wait(&mut self, s_ref: &StageInRefill) -> Result<bool, TransportClosed> {
         match self.lazy_deadline.deadline() {
             DeadlineSetting::Immediate => Ok(false),
-            DeadlineSetting::Finite(instant) => s_ref.wait_deadline(*instant),
+            // A message is never waited for past its own deadline
+            DeadlineSetting::Finite(instant) => match self.expiry {
+                Some(expiry) if expiry < *instant => s_ref.wait_deadline(expiry),
+                _ => s_ref.wait_deadline(*instant),
+            },
         }
     }
 
+    fn has_expired(&self) -> bool {
+        self.expiry.is_some_and(|expiry| expiry <= Instant::now())
+    }
+
     fn on_next_fragment(&mut self) {
         self.lazy_deadline.advance();
     }
+
+    // The deadline is only set when waiting for a free batch
+    fn has_waited(&self) -> bool {
+        matches!(
+            self.lazy_deadline.deadline,
+            Some(DeadlineSetting::Finite(_))
+        )
+    }
+}
+
+// Record the sequence number of a message serialized on a batch, both on the batch and on the
+// span of the message
+#[cfg(feature = "trace_spans")]
+#[inline]
+fn trace_sn(batch: &mut WBatch, sn: TransportSn) {
+    batch.trace_sn(sn);
+    tracing::Span::current().record("sn", sn);
 }
 
 // This is the initial stage of the pipeline where messages are serliazed on
@@ -316,7 +474,15 @@ impl StageIn {
         let mut batch = zgetbatch_rets!();
         // Attempt the serialization on the current batch
         let e = match batch.encode(&*msg) {
-            Ok(_) => zretok!(batch, msg),
+            Ok(_) => {
+                // The message has been appended to the current frame of its channel
+                #[cfg(feature = "trace_spans")]
+                {
+                    let sn = self.mutex.channel(msg.is_reliable()).sn.last();
+                    trace_sn(&mut batch, sn);
+                }
+                zretok!(batch, msg)
+            }
             Err(e) => e,
         };
 
@@ -336,6 +502,8 @@ impl StageIn {
         if let BatchError::NewFrame = e {
             // Attempt a serialization with a new frame
             if batch.encode((&*msg, &frame)).is_ok() {
+                #[cfg(feature = "trace_spans")]
+                trace_sn(&mut batch, sn);
                 zretok!(batch, msg);
             }
         }
@@ -348,6 +516,8 @@ impl StageIn {
 
         // Attempt a second serialization on fully empty batch
         if batch.encode((&*msg, &frame)).is_ok() {
+            #[cfg(feature = "trace_spans")]
+            trace_sn(&mut batch, sn);
             zretok!(batch, msg);
         }
 
@@ -392,6 +562,8 @@ impl StageIn {
             // Serialize the message fragment
             match batch.encode((&mut reader, &mut fragment)) {
                 Ok(_) => {
+                    #[cfg(feature = "trace_spans")]
+                    trace_sn(&mut batch, fragment.sn);
                     // Update the SN
                     fragment.sn = tch.sn.get();
                     fragment.ext_first = None;
@@ -499,15 +671,21 @@ enum Pull {
 #[derive(Clone)]
 struct Backoff {
     threshold: MicroSeconds,
+    target_latency: Option<MicroSeconds>,
     last_bytes: BatchSize,
     atomic: Arc<AtomicBackoff>,
     // active: bool,
 }
 
 impl Backoff {
-    fn new(threshold: Duration, atomic: Arc<AtomicBackoff>) -> Self {
+    fn new(
+        threshold: Duration,
+        target_latency: Option<Duration>,
+        atomic: Arc<AtomicBackoff>,
+    ) -> Self {
         Self {
             threshold: threshold.as_micros() as MicroSeconds,
+            target_latency: target_latency.map(|t| t.as_micros() as MicroSeconds),
             last_bytes: 0,
             atomic,
             // active: false,
@@ -534,31 +712,43 @@ impl StageOutIn {
     }
 
     fn try_pull_deep(&mut self) -> Pull {
-        // Verify first backoff is not active
-        let mut pull = !self.backoff.atomic.active.load(Ordering::Relaxed);
+        let mut backoff = 0;
+        let pull = match self.backoff.target_latency {
+            // The current batch is retained until its oldest message reaches the target latency
+            Some(target_latency) => {
+                let age = (LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds)
+                    .wrapping_sub(self.backoff.atomic.first_write.load(Ordering::Relaxed));
+                backoff = target_latency.saturating_sub(age);
+                backoff == 0
+            }
+            None => {
+                // Verify first backoff is not active
+                let mut pull = !self.backoff.atomic.active.load(Ordering::Relaxed);
 
-        // If backoff is active, verify the current number of bytes is equal to the old number
-        // of bytes seen in the previous backoff iteration
-        if !pull {
-            let new_bytes = self.backoff.atomic.bytes.load(Ordering::Relaxed);
-            let old_bytes = self.backoff.last_bytes;
-            self.backoff.last_bytes = new_bytes;
+                // If backoff is active, verify the current number of bytes is equal to the old number
+                // of bytes seen in the previous backoff iteration
+                if !pull {
+                    let new_bytes = self.backoff.atomic.bytes.load(Ordering::Relaxed);
+                    let old_bytes = self.backoff.last_bytes;
+                    self.backoff.last_bytes = new_bytes;
 
-            pull = new_bytes == old_bytes;
-        }
+                    pull = new_bytes == old_bytes;
+                }
 
-        // Verify that we have not been doing backoff for too long
-        let mut backoff = 0;
-        if !pull {
-            let diff = (LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds)
-                .saturating_sub(self.backoff.atomic.first_write.load(Ordering::Relaxed));
+                // Verify that we have not been doing backoff for too long
+                if !pull {
+                    let diff = (LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds)
+                        .saturating_sub(self.backoff.atomic.first_write.load(Ordering::Relaxed));
 
-            if diff >= self.backoff.threshold {
-                pull = true;
-            } else {
-                backoff = self.backoff.threshold - diff;
+                    if diff >= self.backoff.threshold {
+                        pull = true;
+                    } else {
+                        backoff = self.backoff.threshold - diff;
+                    }
+                }
+                pull
             }
-        }
+        };
 
         if pull {
             // It seems no new bytes have been written on the batch, try to pull
@@ -633,7 +823,7 @@ impl StageOut {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub(crate) struct TransmissionPipelineConf {
     pub(crate) batch: BatchConfig,
     pub(crate) queue_size: [usize; Priority::NUM],
@@ -641,6 +831,10 @@ pub(crate) struct TransmissionPipelineConf {
     pub(crate) wait_before_close: Duration,
     pub(crate) batching_enabled: bool,
     pub(crate) batching_time_limit: Duration,
+    pub(crate) batching_target_latency: Option<Duration>,
+    pub(crate) queue_occupancy_alarm: Option<u8>,
+    pub(crate) queue_weights: Option<[usize; Priority::NUM]>,
+    pub(crate) flight_recorder: Option<Arc<FlightRecorder>>,
 }
 
 // A 2-stage transmission pipeline
@@ -665,6 +859,7 @@ impl TransmissionPipeline {
         // This is a MPSC channel
         let (n_out_w, n_out_r) = event::new();
 
+        let mut gauges = vec![];
         for (prio, num) in size_iter.enumerate() {
             assert!(*num != 0 && *num <= RBLEN);
 
@@ -680,6 +875,18 @@ impl TransmissionPipeline {
             // This is a SPSC channel
             let (n_ref_w, n_ref_r) = event::new();
 
+            let priority_id = if priority.len() == 1 {
+                Priority::DEFAULT
+            } else {
+                Priority::try_from(prio as u8).unwrap()
+            };
+            let gauge = Arc::new(QueueGauge::new(
+                priority_id,
+                *num,
+                config.queue_occupancy_alarm,
+            ));
+            gauges.push(gauge.clone());
+
             // Create the refill ring buffer
             // This is a SPSC ring buffer
             let (s_out_w, s_out_r) = RingBuffer::<WBatch, RBLEN>::init();
@@ -693,7 +900,11 @@ impl TransmissionPipeline {
             });
 
             stage_in.push(Mutex::new(StageIn {
-                s_ref: StageInRefill { n_ref_r, s_ref_r },
+                s_ref: StageInRefill {
+                    n_ref_r,
+                    s_ref_r,
+                    gauge,
+                },
                 s_out: StageInOut {
                     n_out_w: n_out_w.clone(),
                     s_out_w,
@@ -713,7 +924,11 @@ impl TransmissionPipeline {
                 s_in: StageOutIn {
                     s_out_r,
                     current,
-                    backoff: Backoff::new(config.batching_time_limit, bytes),
+                    backoff: Backoff::new(
+                        config.batching_time_limit,
+                        config.batching_target_latency,
+                        bytes,
+                    ),
                 },
                 s_ref: StageOutRefill { n_ref_w, s_ref_w },
             });
@@ -722,17 +937,21 @@ impl TransmissionPipeline {
         let active = Arc::new(TransmissionPipelineStatus {
             disabled: AtomicBool::new(false),
             congested: AtomicU8::new(0),
+            gauges: gauges.into_boxed_slice(),
         });
         let producer = TransmissionPipelineProducer {
             stage_in: stage_in.into_boxed_slice().into(),
             status: active.clone(),
             wait_before_drop: config.wait_before_drop,
             wait_before_close: config.wait_before_close,
+            flight_recorder: config.flight_recorder,
         };
+        let scheduler = Scheduler::new(stage_out.len(), config.queue_weights);
         let consumer = TransmissionPipelineConsumer {
             stage_out: stage_out.into_boxed_slice(),
             n_out_r,
             status: active,
+            scheduler,
         };
 
         (producer, consumer)
@@ -744,6 +963,8 @@ struct TransmissionPipelineStatus {
     disabled: AtomicBool,
     // Bitflags to indicate the given priority queue is congested
     congested: AtomicU8,
+    // The occupancy of each priority queue
+    gauges: Box<[Arc<QueueGauge>]>,
 }
 
 impl TransmissionPipelineStatus {
@@ -777,6 +998,7 @@ pub(crate) struct TransmissionPipelineProducer {
     status: Arc<TransmissionPipelineStatus>,
     wait_before_drop: (Duration, Duration),
     wait_before_close: Duration,
+    flight_recorder: Option<Arc<FlightRecorder>>,
 }
 
 impl TransmissionPipelineProducer {
@@ -793,26 +1015,76 @@ impl TransmissionPipelineProducer {
             (0, Priority::DEFAULT)
         };
 
+        // A message whose deadline expired is stale and dropped instead of being transmitted
+        let expiry = match msg.deadline() {
+            Some(deadline) => match expiry(deadline) {
+                Some(expiry) => Some(expiry),
+                None => {
+                    self.expire(idx);
+                    return Ok(false);
+                }
+            },
+            None => None,
+        };
+
         // If message is droppable, compute a deadline after which the sample could be dropped
         let (wait_time, max_wait_time) = if msg.is_droppable() {
             // Checked if we are blocked on the priority queue and we drop directly the message
             if self.status.is_congested(priority) {
+                self.status.gauges[idx].record_drop();
+                congestion::record(false, true, || self.status.gauges[idx].occupancy());
                 return Ok(false);
             }
             (self.wait_before_drop.0, Some(self.wait_before_drop.1))
         } else {
             (self.wait_before_close, None)
         };
-        let mut deadline = Deadline::new(wait_time, max_wait_time);
+        let mut deadline = Deadline::new(wait_time, max_wait_time, expiry);
+        #[cfg(feature = "trace_spans")]
+        let _span = tracing::trace_span!(
+            "zenoh::batch",
+            ?priority,
+            reliability = ?msg.reliability,
+            sn = tracing::field::Empty,
+        )
+        .entered();
         // Lock the channel. We are the only one that will be writing on it.
         let mut queue = zlock!(self.stage_in[idx]);
         let sent = queue.push_network_message(&mut msg, priority, &mut deadline)?;
+        // The message expired while waiting for a batch, which says nothing of the congestion
+        if !sent && deadline.has_expired() {
+            self.expire(idx);
+            return Ok(false);
+        }
+        congestion::record(sent && deadline.has_waited(), !sent, || {
+            self.status.gauges[idx].occupancy()
+        });
         if !sent {
+            self.status.gauges[idx].record_drop();
+            // Only the first drop is recorded, the following ones being dropped upfront
+            // until the queue is no longer congested
+            if let (false, Some(recorder)) =
+                (self.status.is_congested(priority), &self.flight_recorder)
+            {
+                zenoh_util::record_event!(
+                    recorder,
+                    Drop,
+                    "Message dropped because the {priority:?} queue is congested",
+                    priority
+                );
+            }
             self.status.set_congested(priority, true);
         }
         Ok(sent)
     }
 
+    fn expire(&self, idx: usize) {
+        let gauge = &self.status.gauges[idx];
+        gauge.record_expiry();
+        congestion::record_expiry(|| gauge.occupancy());
+        tracing::trace!("Message dropped because its deadline expired");
+    }
+
     #[inline]
     pub(crate) fn push_transport_message(&self, msg: TransportMessage, priority: Priority) -> bool {
         // If the queue is not QoS, it means that we only have one priority with index 0.
@@ -826,6 +1098,10 @@ impl TransmissionPipelineProducer {
         queue.push_transport_message(msg)
     }
 
+    pub(crate) fn occupancy(&self) -> Vec<QueueOccupancy> {
+        self.status.gauges.iter().map(|g| g.occupancy()).collect()
+    }
+
     pub(crate) fn disable(&self) {
         self.status.set_disabled(true);
 
@@ -841,31 +1117,88 @@ impl TransmissionPipelineProducer {
     }
 }
 
+// Inner structure to select the priority queue to pull a batch from
+struct Scheduler {
+    // The number of queues served in strict priority order, before the weighted ones
+    strict: usize,
+    weights: [usize; Priority::NUM],
+    // The weighted queue being served and the number of batches it has been served in a row
+    current: usize,
+    served: usize,
+}
+
+impl Scheduler {
+    fn new(num: usize, weights: Option<[usize; Priority::NUM]>) -> Self {
+        let (strict, weights) = match weights {
+            Some(weights) if num == Priority::NUM => (Priority::InteractiveHigh as usize, weights),
+            _ => (num, [0; Priority::NUM]),
+        };
+        Self {
+            strict,
+            weights,
+            current: strict,
+            served: 0,
+        }
+    }
+
+    // Try to pull a batch, returning the backoff to wait for otherwise
+    fn try_pull(&mut self, stage_out: &mut [StageOut]) -> Result<(WBatch, usize), MicroSeconds> {
+        // The queues of highest priority are served first, waiting for the backoff of a
+        // partial batch rather than serving the queues of lower priority
+        for (prio, queue) in stage_out.iter_mut().enumerate().take(self.strict) {
+            match queue.try_pull() {
+                Pull::Some(batch) => return Ok((batch, prio)),
+                Pull::Backoff(deadline) => return Err(deadline),
+                Pull::None => {}
+            }
+        }
+
+        // The weighted queues are served in turn, starting from the current one
+        let mut backoff = MicroSeconds::MAX;
+        let num = stage_out.len() - self.strict;
+        for i in 0..num {
+            let prio = self.strict + (self.current - self.strict + i) % num;
+            match stage_out[prio].try_pull() {
+                Pull::Some(batch) => {
+                    if prio != self.current {
+                        self.current = prio;
+                        self.served = 0;
+                    }
+                    self.served += 1;
+                    if self.served >= self.weights[prio] {
+                        self.current = self.strict + (prio + 1 - self.strict) % num;
+                        self.served = 0;
+                    }
+                    return Ok((batch, prio));
+                }
+                Pull::Backoff(deadline) => backoff = backoff.min(deadline),
+                Pull::None => {}
+            }
+        }
+        Err(backoff)
+    }
+}
+
 pub(crate) struct TransmissionPipelineConsumer {
     // A single Mutex for all the priority queues
     stage_out: Box<[StageOut]>,
     n_out_r: Waiter,
     status: Arc<TransmissionPipelineStatus>,
+    scheduler: Scheduler,
 }
 
 impl TransmissionPipelineConsumer {
     pub(crate) async fn pull(&mut self) -> Option<(WBatch, Priority)> {
         while !self.status.is_disabled() {
-            let mut backoff = MicroSeconds::MAX;
             // Calculate the backoff maximum
-            for (prio, queue) in self.stage_out.iter_mut().enumerate() {
-                match queue.try_pull() {
-                    Pull::Some(batch) => {
-                        let prio = Priority::try_from(prio as u8).unwrap();
-                        return Some((batch, prio));
-                    }
-                    Pull::Backoff(deadline) => {
-                        backoff = deadline;
-                        break;
-                    }
-                    Pull::None => {}
+            let backoff = match self.scheduler.try_pull(&mut self.stage_out) {
+                Ok((batch, prio)) => {
+                    self.status.gauges[prio].serve();
+                    let prio = Priority::try_from(prio as u8).unwrap();
+                    return Some((batch, prio));
                 }
-            }
+                Err(backoff) => backoff,
+            };
 
             // In case of writing many small messages, `recv_async()` will most likely return immedietaly.
             // While trying to pull from the queue, the stage_in `lock()` will most likely taken, leading to
@@ -899,6 +1232,7 @@ impl TransmissionPipelineConsumer {
     pub(crate) fn refill(&mut self, batch: WBatch, priority: Priority) {
         if !batch.is_ephemeral() {
             self.stage_out[priority as usize].refill(batch);
+            self.status.gauges[priority as usize].dec();
             self.status.set_congested(priority, false);
         }
     }
@@ -970,6 +1304,10 @@ mod tests {
         wait_before_drop: (Duration::from_millis(1), Duration::from_millis(1024)),
         wait_before_close: Duration::from_secs(5),
         batching_time_limit: Duration::from_micros(1),
+        batching_target_latency: None,
+        queue_occupancy_alarm: None,
+        queue_weights: None,
+        flight_recorder: None,
     };
 
     const CONFIG_NOT_STREAMED: TransmissionPipelineConf = TransmissionPipelineConf {
@@ -984,6 +1322,10 @@ mod tests {
         wait_before_drop: (Duration::from_millis(1), Duration::from_millis(1024)),
         wait_before_close: Duration::from_secs(5),
         batching_time_limit: Duration::from_micros(1),
+        batching_target_latency: None,
+        queue_occupancy_alarm: None,
+        queue_weights: None,
+        flight_recorder: None,
     };
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
@@ -998,6 +1340,9 @@ mod tests {
                 ext_qos: ext::QoSType::new(Priority::Control, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: ext::NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: None,
                 payload: PushBody::Put(Put {
                     timestamp: None,
                     encoding: Encoding::empty(),
@@ -1005,6 +1350,8 @@ mod tests {
                     #[cfg(feature = "shared-memory")]
                     ext_shm: None,
                     ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
                     ext_unknown: vec![],
                     payload,
                 }),
@@ -1109,6 +1456,244 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn tx_pipeline_occupancy() -> ZResult<()> {
+        let config = TransmissionPipelineConf {
+            queue_size: [4; Priority::NUM],
+            queue_occupancy_alarm: Some(50),
+            ..CONFIG_NOT_STREAMED
+        };
+        let tct = TransportPriorityTx::make(Bits::from(TransportSn::MAX))?;
+        let priorities = vec![tct];
+        let (producer, mut consumer) = TransmissionPipeline::make(config, priorities.as_slice());
+
+        // Express messages are moved out of the serialization batch right away
+        let message: NetworkMessage = Push {
+            wire_expr: "test".into(),
+            ext_qos: ext::QoSType::new(Priority::Data, CongestionControl::Block, true),
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType::DEFAULT,
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
+            payload: PushBody::Put(Put {
+                timestamp: None,
+                encoding: Encoding::empty(),
+                ext_sinfo: None,
+                #[cfg(feature = "shared-memory")]
+                ext_shm: None,
+                ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
+                ext_unknown: vec![],
+                payload: ZBuf::from(vec![0_u8; 8]),
+            }),
+        }
+        .into();
+
+        let occupancy = |producer: &TransmissionPipelineProducer| {
+            let queues = producer.occupancy();
+            assert_eq!(queues.len(), 1);
+            assert_eq!(queues[0].priority, Priority::DEFAULT);
+            assert_eq!(queues[0].capacity, 4);
+            (queues[0].occupied, queues[0].alarm, queues[0].alarms)
+        };
+        assert_eq!(occupancy(&producer), (0, false, 0));
+
+        producer.push_network_message(message.clone()).unwrap();
+        assert_eq!(occupancy(&producer), (1, false, 0));
+        producer.push_network_message(message.clone()).unwrap();
+        producer.push_network_message(message.clone()).unwrap();
+        assert_eq!(occupancy(&producer), (3, true, 1));
+
+        for _ in 0..3 {
+            let (batch, priority) = timeout(TIMEOUT, consumer.pull()).await?.unwrap();
+            consumer.refill(batch, priority);
+        }
+        assert_eq!(occupancy(&producer), (0, false, 1));
+
+        // The alarm is counted each time the occupancy rises above the threshold
+        producer.push_network_message(message.clone()).unwrap();
+        producer.push_network_message(message).unwrap();
+        assert_eq!(occupancy(&producer), (2, true, 2));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn tx_pipeline_deadline() -> ZResult<()> {
+        let config = TransmissionPipelineConf {
+            queue_size: [1; Priority::NUM],
+            ..CONFIG_NOT_STREAMED
+        };
+        let tct = TransportPriorityTx::make(Bits::from(TransportSn::MAX))?;
+        let priorities = vec![tct];
+        let (producer, mut consumer) = TransmissionPipeline::make(config, priorities.as_slice());
+
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let message = |deadline: Option<Duration>| -> NetworkMessage {
+            Push {
+                wire_expr: "test".into(),
+                ext_qos: ext::QoSType::new(Priority::Data, CongestionControl::Block, true),
+                ext_tstamp: None,
+                ext_nodeid: ext::NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: deadline.map(NTP64::from),
+                payload: PushBody::Put(Put {
+                    timestamp: None,
+                    encoding: Encoding::empty(),
+                    ext_sinfo: None,
+                    #[cfg(feature = "shared-memory")]
+                    ext_shm: None,
+                    ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
+                    ext_unknown: vec![],
+                    payload: ZBuf::from(vec![0_u8; 8]),
+                }),
+            }
+            .into()
+        };
+        let expired = |producer: &TransmissionPipelineProducer| producer.occupancy()[0].expired;
+
+        // A stale message is dropped right away
+        assert!(!producer.push_network_message(message(Some(now() - SLEEP)))?);
+        assert_eq!(expired(&producer), 1);
+
+        // The only batch is taken, the message is dropped when its deadline expires instead of
+        // waiting for the batch until the transport is closed
+        assert!(producer.push_network_message(message(None))?);
+        let start = Instant::now();
+        assert!(!producer.push_network_message(message(Some(now() + SLEEP)))?);
+        assert!(start.elapsed() < CONFIG_NOT_STREAMED.wait_before_close);
+        assert_eq!(expired(&producer), 2);
+        assert_eq!(producer.occupancy()[0].dropped, 0);
+
+        // The message is transmitted before its deadline
+        let (batch, priority) = timeout(TIMEOUT, consumer.pull()).await?.unwrap();
+        consumer.refill(batch, priority);
+        assert!(producer.push_network_message(message(Some(now() + TIMEOUT)))?);
+        assert_eq!(expired(&producer), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn tx_pipeline_target_latency() -> ZResult<()> {
+        let target_latency = Duration::from_millis(50);
+        let config = TransmissionPipelineConf {
+            batching_target_latency: Some(target_latency),
+            ..CONFIG_NOT_STREAMED
+        };
+        let tct = TransportPriorityTx::make(Bits::from(TransportSn::MAX))?;
+        let priorities = vec![tct];
+        let (producer, mut consumer) = TransmissionPipeline::make(config, priorities.as_slice());
+
+        let message: NetworkMessage = Push {
+            wire_expr: "test".into(),
+            ext_qos: ext::QoSType::new(Priority::Data, CongestionControl::Block, false),
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType::DEFAULT,
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
+            payload: PushBody::Put(Put {
+                timestamp: None,
+                encoding: Encoding::empty(),
+                ext_sinfo: None,
+                #[cfg(feature = "shared-memory")]
+                ext_shm: None,
+                ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
+                ext_unknown: vec![],
+                payload: ZBuf::from(vec![0_u8; 8]),
+            }),
+        }
+        .into();
+
+        // The messages are retained in a single batch until the first one reaches the target
+        // latency, even though the consumer is ready to transmit
+        let now = Instant::now();
+        for _ in 0..10 {
+            producer.push_network_message(message.clone())?;
+        }
+        let (batch, priority) = timeout(TIMEOUT, consumer.pull()).await?.unwrap();
+        assert!(now.elapsed() >= target_latency);
+        consumer.refill(batch, priority);
+        assert!(timeout(SLEEP, consumer.pull()).await.is_err());
+        assert_eq!(producer.occupancy()[0].served, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn tx_pipeline_weighted_round_robin() -> ZResult<()> {
+        let mut queue_weights = [1; Priority::NUM];
+        queue_weights[Priority::Data as usize] = 2;
+        let config = TransmissionPipelineConf {
+            queue_size: [4; Priority::NUM],
+            queue_weights: Some(queue_weights),
+            ..CONFIG_NOT_STREAMED
+        };
+        let priorities = (0..Priority::NUM)
+            .map(|_| TransportPriorityTx::make(Bits::from(TransportSn::MAX)))
+            .collect::<ZResult<Vec<_>>>()?;
+        let (producer, mut consumer) = TransmissionPipeline::make(config, priorities.as_slice());
+
+        // Express messages are moved out of the serialization batch right away
+        let message = |priority| -> NetworkMessage {
+            Push {
+                wire_expr: "test".into(),
+                ext_qos: ext::QoSType::new(priority, CongestionControl::Block, true),
+                ext_tstamp: None,
+                ext_nodeid: ext::NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: None,
+                payload: PushBody::Put(Put {
+                    timestamp: None,
+                    encoding: Encoding::empty(),
+                    ext_sinfo: None,
+                    #[cfg(feature = "shared-memory")]
+                    ext_shm: None,
+                    ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
+                    ext_unknown: vec![],
+                    payload: ZBuf::from(vec![0_u8; 8]),
+                }),
+            }
+            .into()
+        };
+        for _ in 0..4 {
+            producer.push_network_message(message(Priority::Data))?;
+            producer.push_network_message(message(Priority::Background))?;
+        }
+        producer.push_network_message(message(Priority::RealTime))?;
+
+        // The real-time queue is served first, then the data queue is served twice as much as
+        // the background one instead of starving it
+        let mut order = vec![];
+        for _ in 0..9 {
+            let (batch, priority) = timeout(TIMEOUT, consumer.pull()).await?.unwrap();
+            order.push(priority);
+            consumer.refill(batch, priority);
+        }
+        use Priority::{Background as B, Data as D, RealTime as R};
+        assert_eq!(order, vec![R, D, D, B, D, D, B, B, B]);
+
+        let served = producer
+            .occupancy()
+            .iter()
+            .map(|queue| queue.served)
+            .collect::<Vec<_>>();
+        assert_eq!(served, vec![0, 1, 0, 0, 0, 4, 0, 4]);
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn tx_pipeline_blocking() -> ZResult<()> {
         fn schedule(queue: TransmissionPipelineProducer, counter: Arc<AtomicUsize>, id: usize) {
@@ -1126,6 +1711,9 @@ mod tests {
                 ext_qos: ext::QoSType::new(Priority::Control, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: ext::NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: None,
                 payload: PushBody::Put(Put {
                     timestamp: None,
                     encoding: Encoding::empty(),
@@ -1133,6 +1721,8 @@ mod tests {
                     #[cfg(feature = "shared-memory")]
                     ext_shm: None,
                     ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
                     ext_unknown: vec![],
                     payload,
                 }),
@@ -1244,6 +1834,9 @@ mod tests {
                         ),
                         ext_tstamp: None,
                         ext_nodeid: ext::NodeIdType::DEFAULT,
+                        ext_hops: 0,
+                        ext_hop_limit: None,
+                        ext_deadline: None,
                         payload: PushBody::Put(Put {
                             timestamp: None,
                             encoding: Encoding::empty(),
@@ -1251,6 +1844,8 @@ mod tests {
                             #[cfg(feature = "shared-memory")]
                             ext_shm: None,
                             ext_attachment: None,
+                            ext_trace: None,
+                            ext_signature: None,
                             ext_unknown: vec![],
                             payload,
                         }),
@@ -1303,6 +1898,9 @@ mod tests {
             ext_qos: ext::QoSType::new(Priority::Control, CongestionControl::Block, true),
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType::DEFAULT,
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -1310,6 +1908,8 @@ mod tests {
                 #[cfg(feature = "shared-memory")]
                 ext_shm: None,
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
                 ext_unknown: vec![],
                 payload: vec![42u8].into(),
             }),
@@ -0,0 +1,119 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The reception of the network messages from the links.
+//!
+//! Handing a received message to the transport callback is synchronous: the message is routed,
+//! and possibly delivered to the local subscribers, before the next one is handled. With the
+//! `sample_metadata` feature, the link and the time the message being handled by a thread was
+//! received at are available from [`ingress`], and the links traversed by the publications are
+//! counted.
+#[cfg(feature = "sample_metadata")]
+use zenoh_protocol::network::NetworkBody;
+use zenoh_protocol::{core::Locator, network::NetworkMessage};
+
+#[cfg(feature = "sample_metadata")]
+pub use self::ingress::{ingress, Ingress};
+
+/// Counts the link the message traversed to be received, with the `sample_metadata` feature.
+#[inline]
+pub(crate) fn count_hop(msg: &mut NetworkMessage) {
+    #[cfg(feature = "sample_metadata")]
+    if let NetworkBody::Push(push) = &mut msg.body {
+        push.ext_hops = push.ext_hops.saturating_add(1);
+    }
+    #[cfg(not(feature = "sample_metadata"))]
+    let _ = msg;
+}
+
+/// Runs `f`, which handles the network messages received now from `src` on `dst`.
+#[inline]
+pub(crate) fn receive<R>(src: &Locator, dst: &Locator, f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "sample_metadata")]
+    return ingress::receive(src, dst, f);
+    #[cfg(not(feature = "sample_metadata"))]
+    {
+        let _ = (src, dst);
+        f()
+    }
+}
+
+#[cfg(feature = "sample_metadata")]
+mod ingress {
+    use std::{cell::RefCell, time::SystemTime};
+
+    use zenoh_protocol::core::Locator;
+
+    /// The link and the time a network message was received at.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Ingress {
+        /// The locator of the remote end of the link.
+        pub src: Locator,
+        /// The locator of the local end of the link, or the multicast group.
+        pub dst: Locator,
+        /// The time the batch of the message was received at.
+        pub time: SystemTime,
+    }
+
+    thread_local! {
+        static INGRESS: RefCell<Option<Ingress>> = const { RefCell::new(None) };
+    }
+
+    // Restores the ingress of an enclosing `receive`, even if the handling function panics
+    struct Scope {
+        outer: Option<Ingress>,
+    }
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            INGRESS.with(|i| *i.borrow_mut() = self.outer.take());
+        }
+    }
+
+    pub(super) fn receive<R>(src: &Locator, dst: &Locator, f: impl FnOnce() -> R) -> R {
+        let ingress = Ingress {
+            src: src.clone(),
+            dst: dst.clone(),
+            time: SystemTime::now(),
+        };
+        let _scope = Scope {
+            outer: INGRESS.with(|i| i.borrow_mut().replace(ingress)),
+        };
+        f()
+    }
+
+    /// Returns the ingress of the network message handled by the current thread, if it was
+    /// received from a link.
+    pub fn ingress() -> Option<Ingress> {
+        INGRESS.with(|i| i.borrow().clone())
+    }
+}
+
+#[cfg(all(test, feature = "sample_metadata"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reception_ingress() {
+        let a = "tcp/127.0.0.1:7447".parse().unwrap();
+        let b = "tcp/127.0.0.1:7448".parse().unwrap();
+        assert!(ingress().is_none());
+        receive(&a, &b, || {
+            assert_eq!(ingress().unwrap().src, a);
+            receive(&b, &a, || assert_eq!(ingress().unwrap().src, b));
+            assert_eq!(ingress().unwrap().dst, b);
+        });
+        assert!(ingress().is_none());
+    }
+}
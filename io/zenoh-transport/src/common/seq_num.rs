@@ -191,6 +191,12 @@ impl SeqNumGenerator {
         self.0.get()
     }
 
+    /// Returns the last generated sequence number
+    #[cfg(feature = "trace_spans")]
+    pub(crate) fn last(&self) -> TransportSn {
+        self.0.get().wrapping_sub(1) & self.0.resolution()
+    }
+
     /// Generates the next sequence number
     pub(crate) fn get(&mut self) -> TransportSn {
         let now = self.now();
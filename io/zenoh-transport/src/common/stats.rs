@@ -276,5 +276,21 @@ stats_struct! {
         # HELP "Counter of received bytes in zenoh reply message payloads."
         # TYPE "counter"
         pub rx_z_reply_pl_bytes DiscriminatedStats,
+
+        # HELP "Counter of incoming links admitted to the handshake."
+        # TYPE "counter"
+        pub handshake_accepted,
+
+        # HELP "Counter of incoming links closed because of the pending handshake limits."
+        # TYPE "counter"
+        pub handshake_rejected,
+
+        # HELP "Counter of handshakes that failed or timed out."
+        # TYPE "counter"
+        pub handshake_failed,
+
+        # HELP "Counter of handshakes that established a transport."
+        # TYPE "counter"
+        pub handshake_completed,
     }
 }
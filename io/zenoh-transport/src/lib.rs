@@ -22,14 +22,17 @@ pub mod manager;
 pub mod multicast;
 pub mod unicast;
 
+#[cfg(feature = "fault_injection")]
+pub use common::faults;
 #[cfg(feature = "stats")]
 pub use common::stats;
 
 #[cfg(feature = "shared-memory")]
 mod shm;
 
-use std::{any::Any, sync::Arc};
+use std::{any::Any, fmt, sync::Arc};
 
+pub use common::pipeline::QueueOccupancy;
 pub use manager::*;
 use serde::Serialize;
 use zenoh_link::Link;
@@ -121,6 +124,36 @@ pub trait TransportPeerEventHandler: Send + Sync {
     fn del_link(&self, link: Link);
     fn closed(&self);
     fn as_any(&self) -> &dyn Any;
+    /// Called with the data messages dropped on transmission when
+    /// [`TransportManagerConfig::dead_letters`] is enabled.
+    fn dropped(&self, _msg: NetworkMessage, _reason: DropReason) {}
+}
+
+/// The reason why a message was dropped instead of being transmitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// The transmission queue was congested.
+    Congestion,
+    /// The transport had no link matching the reliability and the priority of the message.
+    NoLink,
+    /// The access control denied the message.
+    AccessDenied,
+    /// The message could not be serialized.
+    Serialization,
+    /// The deadline of the message expired before it could be transmitted.
+    Expired,
+}
+
+impl fmt::Display for DropReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DropReason::Congestion => "congestion",
+            DropReason::NoLink => "no_link",
+            DropReason::AccessDenied => "access_denied",
+            DropReason::Serialization => "serialization",
+            DropReason::Expired => "expired",
+        })
+    }
 }
 
 // Define an empty TransportCallback for the listener transport
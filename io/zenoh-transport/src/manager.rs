@@ -15,7 +15,9 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use rand::{RngCore, SeedableRng};
 use tokio::sync::Mutex as AsyncMutex;
-use zenoh_config::{Config, LinkRxConf, QueueConf, QueueSizeConf};
+use zenoh_config::{
+    Config, LinkRxConf, QueueConf, QueueScheduling, QueueSchedulingConf, QueueSizeConf,
+};
 use zenoh_crypto::{BlockCipher, PseudoRng};
 use zenoh_link::NewLinkChannelSender;
 use zenoh_protocol::{
@@ -29,6 +31,7 @@ use zenoh_shm::api::client_storage::GLOBAL_CLIENT_STORAGE;
 #[cfg(feature = "shared-memory")]
 use zenoh_shm::reader::ShmReader;
 use zenoh_task::TaskController;
+use zenoh_util::flight_recorder::FlightRecorder;
 
 use super::{
     unicast::manager::{
@@ -112,6 +115,10 @@ pub struct TransportManagerConfig {
     pub wait_before_close: Duration,
     pub queue_size: [usize; Priority::NUM],
     pub queue_backoff: Duration,
+    pub queue_target_latency: Option<Duration>,
+    pub queue_occupancy_alarm: Option<u8>,
+    pub queue_weights: Option<[usize; Priority::NUM]>,
+    pub dead_letters: bool,
     pub defrag_buff_size: usize,
     pub link_rx_buffer_size: usize,
     pub unicast: TransportManagerConfigUnicast,
@@ -120,6 +127,7 @@ pub struct TransportManagerConfig {
     pub handler: Arc<dyn TransportEventHandler>,
     pub tx_threads: usize,
     pub protocols: Vec<String>,
+    pub flight_recorder: Arc<FlightRecorder>,
 }
 
 pub struct TransportManagerState {
@@ -140,9 +148,13 @@ pub struct TransportManagerBuilder {
     batch_size: BatchSize,
     batching_enabled: bool,
     batching_time_limit: Duration,
+    batching_target_latency: Option<Duration>,
     wait_before_drop: (Duration, Duration),
     wait_before_close: Duration,
     queue_size: QueueSizeConf,
+    queue_occupancy_alarm: Option<u8>,
+    queue_scheduling: QueueSchedulingConf,
+    dead_letters: bool,
     defrag_buff_size: usize,
     link_rx_buffer_size: usize,
     unicast: TransportManagerBuilderUnicast,
@@ -150,6 +162,7 @@ pub struct TransportManagerBuilder {
     endpoints: HashMap<String, String>, // (protocol, config)
     tx_threads: usize,
     protocols: Option<Vec<String>>,
+    flight_recorder: Option<Arc<FlightRecorder>>,
     #[cfg(feature = "shared-memory")]
     shm_reader: Option<ShmReader>,
 }
@@ -191,6 +204,11 @@ impl TransportManagerBuilder {
         self
     }
 
+    pub fn batching_target_latency(mut self, batching_target_latency: Option<Duration>) -> Self {
+        self.batching_target_latency = batching_target_latency;
+        self
+    }
+
     pub fn wait_before_drop(mut self, wait_before_drop: (Duration, Duration)) -> Self {
         self.wait_before_drop = wait_before_drop;
         self
@@ -206,6 +224,26 @@ impl TransportManagerBuilder {
         self
     }
 
+    pub fn queue_occupancy_alarm(mut self, queue_occupancy_alarm: Option<u8>) -> Self {
+        self.queue_occupancy_alarm = queue_occupancy_alarm;
+        self
+    }
+
+    pub fn queue_scheduling(mut self, queue_scheduling: QueueSchedulingConf) -> Self {
+        self.queue_scheduling = queue_scheduling;
+        self
+    }
+
+    pub fn dead_letters(mut self, dead_letters: bool) -> Self {
+        self.dead_letters = dead_letters;
+        self
+    }
+
+    pub fn flight_recorder(mut self, flight_recorder: Arc<FlightRecorder>) -> Self {
+        self.flight_recorder = Some(flight_recorder);
+        self
+    }
+
     pub fn defrag_buff_size(mut self, defrag_buff_size: usize) -> Self {
         self.defrag_buff_size = defrag_buff_size;
         self
@@ -226,6 +264,13 @@ impl TransportManagerBuilder {
         self
     }
 
+    /// Sets the user-password credentials to open transports with, unless some are configured.
+    #[cfg(feature = "auth_usrpwd")]
+    pub fn default_usrpwd(mut self, user: Vec<u8>, password: Vec<u8>) -> Self {
+        self.unicast = self.unicast.default_usrpwd(user, password);
+        self
+    }
+
     pub fn multicast(mut self, multicast: TransportManagerBuilderMulticast) -> Self {
         self.multicast = multicast;
         self
@@ -242,7 +287,9 @@ impl TransportManagerBuilder {
     }
 
     pub async fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilder> {
-        self = self.zid((*config.id()).into());
+        if let Some(zid) = config.id() {
+            self = self.zid((*zid).into());
+        }
         if let Some(v) = config.mode() {
             self = self.whatami(*v);
         }
@@ -258,6 +305,13 @@ impl TransportManagerBuilder {
         self = self.batching_time_limit(Duration::from_millis(
             *link.tx().queue().batching().time_limit(),
         ));
+        self = self.batching_target_latency(
+            link.tx()
+                .queue()
+                .batching()
+                .target_latency()
+                .map(Duration::from_micros),
+        );
         self = self.defrag_buff_size(*link.rx().max_message_size());
         self = self.link_rx_buffer_size(*link.rx().buffer_size());
         self = self.wait_before_drop((
@@ -266,6 +320,9 @@ impl TransportManagerBuilder {
         ));
         self = self.wait_before_close(duration_from_i64us(*cc_block.wait_before_close()));
         self = self.queue_size(link.tx().queue().size().clone());
+        self = self.queue_occupancy_alarm(*link.tx().queue().occupancy_alarm());
+        self = self.queue_scheduling(link.tx().queue().scheduling().clone());
+        self = self.dead_letters(*config.dead_letters().enabled());
         self = self.tx_threads(*link.tx().threads());
         self = self.protocols(link.protocols().clone());
 
@@ -315,6 +372,22 @@ impl TransportManagerBuilder {
         queue_size[Priority::DataLow as usize] = *self.queue_size.data_low();
         queue_size[Priority::Background as usize] = *self.queue_size.background();
 
+        // The control and real-time queues are always served first
+        let queue_weights = match self.queue_scheduling.mode() {
+            QueueScheduling::Strict => None,
+            QueueScheduling::WeightedRoundRobin => {
+                let weights = self.queue_scheduling.weights();
+                let mut queue_weights = [0; Priority::NUM];
+                queue_weights[Priority::InteractiveHigh as usize] = *weights.interactive_high();
+                queue_weights[Priority::InteractiveLow as usize] = *weights.interactive_low();
+                queue_weights[Priority::DataHigh as usize] = *weights.data_high();
+                queue_weights[Priority::Data as usize] = *weights.data();
+                queue_weights[Priority::DataLow as usize] = *weights.data_low();
+                queue_weights[Priority::Background as usize] = *weights.background();
+                Some(queue_weights)
+            }
+        };
+
         let config = TransportManagerConfig {
             version: self.version,
             zid: self.zid,
@@ -326,6 +399,10 @@ impl TransportManagerBuilder {
             wait_before_close: self.wait_before_close,
             queue_size,
             queue_backoff: self.batching_time_limit,
+            queue_target_latency: self.batching_target_latency,
+            queue_occupancy_alarm: self.queue_occupancy_alarm,
+            queue_weights,
+            dead_letters: self.dead_letters,
             defrag_buff_size: self.defrag_buff_size,
             link_rx_buffer_size: self.link_rx_buffer_size,
             unicast: unicast.config,
@@ -339,6 +416,7 @@ impl TransportManagerBuilder {
                     .map(|x| x.to_string())
                     .collect()
             }),
+            flight_recorder: self.flight_recorder.unwrap_or_default(),
         };
 
         let state = TransportManagerState {
@@ -377,7 +455,11 @@ impl Default for TransportManagerBuilder {
             ),
             wait_before_close: duration_from_i64us(*cc_block.wait_before_close()),
             queue_size: queue.size,
+            queue_occupancy_alarm: queue.occupancy_alarm,
+            queue_scheduling: queue.scheduling,
+            dead_letters: false,
             batching_time_limit: Duration::from_millis(backoff),
+            batching_target_latency: None,
             defrag_buff_size: *link_rx.max_message_size(),
             link_rx_buffer_size: *link_rx.buffer_size(),
             endpoints: HashMap::new(),
@@ -385,6 +467,7 @@ impl Default for TransportManagerBuilder {
             multicast: TransportManagerBuilderMulticast::default(),
             tx_threads: 1,
             protocols: None,
+            flight_recorder: None,
             #[cfg(feature = "shared-memory")]
             shm_reader: None,
         }
@@ -403,6 +486,8 @@ pub struct TransportManager {
     pub(crate) shmr: ShmReader,
     #[cfg(feature = "stats")]
     pub(crate) stats: Arc<crate::stats::TransportStats>,
+    #[cfg(feature = "fault_injection")]
+    pub(crate) faults: Arc<crate::faults::FaultInjector>,
     pub(crate) task_controller: TaskController,
 }
 
@@ -429,6 +514,8 @@ impl TransportManager {
             new_unicast_link_sender,
             #[cfg(feature = "stats")]
             stats: std::sync::Arc::new(crate::stats::TransportStats::default()),
+            #[cfg(feature = "fault_injection")]
+            faults: Arc::new(crate::faults::FaultInjector::default()),
             #[cfg(feature = "shared-memory")]
             shmr,
             task_controller: TaskController::default(),
@@ -469,6 +556,11 @@ impl TransportManager {
         self.stats.clone()
     }
 
+    #[cfg(feature = "fault_injection")]
+    pub fn faults(&self) -> &crate::faults::FaultInjector {
+        &self.faults
+    }
+
     pub async fn close(&self) {
         self.close_unicast().await;
         self.task_controller.terminate_all_async().await;
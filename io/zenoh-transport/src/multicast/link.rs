@@ -314,6 +314,10 @@ impl TransportLinkMulticastUniversal {
                 wait_before_close: self.transport.manager.config.wait_before_close,
                 batching_enabled: self.transport.manager.config.batching,
                 batching_time_limit: self.transport.manager.config.queue_backoff,
+                batching_target_latency: self.transport.manager.config.queue_target_latency,
+                queue_occupancy_alarm: self.transport.manager.config.queue_occupancy_alarm,
+                queue_weights: self.transport.manager.config.queue_weights,
+                flight_recorder: Some(self.transport.manager.config.flight_recorder.clone()),
             };
             // The pipeline
             let (producer, consumer) = TransmissionPipeline::make(tpc, &priority_tx);
@@ -28,6 +28,7 @@ use super::transport::{TransportMulticastInner, TransportMulticastPeer};
 use crate::common::{
     batch::{Decode, RBatch},
     priority::TransportChannelRx,
+    reception,
 };
 
 /*************************************/
@@ -37,10 +38,10 @@ use crate::common::{
 impl TransportMulticastInner {
     fn trigger_callback(
         &self,
-        #[allow(unused_mut)] // shared-memory feature requires mut
         mut msg: NetworkMessage,
         peer: &TransportMulticastPeer,
     ) -> ZResult<()> {
+        reception::count_hop(&mut msg);
         #[cfg(feature = "shared-memory")]
         {
             if self.manager.config.multicast.is_shm {
@@ -278,10 +279,28 @@ impl TransportMulticastInner {
 
     pub(super) fn read_messages(
         &self,
-        mut batch: RBatch,
+        batch: RBatch,
         locator: Locator,
         batch_size: BatchSize,
         #[cfg(feature = "stats")] transport: &TransportMulticastInner,
+    ) -> ZResult<()> {
+        reception::receive(&locator, &self.locator, || {
+            self.read_batch(
+                batch,
+                &locator,
+                batch_size,
+                #[cfg(feature = "stats")]
+                transport,
+            )
+        })
+    }
+
+    fn read_batch(
+        &self,
+        mut batch: RBatch,
+        locator: &Locator,
+        batch_size: BatchSize,
+        #[cfg(feature = "stats")] transport: &TransportMulticastInner,
     ) -> ZResult<()> {
         while !batch.is_empty() {
             let msg: TransportMessage = batch
@@ -296,7 +315,7 @@ impl TransportMulticastInner {
             }
 
             let r_guard = zread!(self.peers);
-            match r_guard.get(&locator) {
+            match r_guard.get(locator) {
                 Some(peer) => {
                     peer.set_active();
                     match msg.body {
@@ -308,7 +327,7 @@ impl TransportMulticastInner {
                         TransportBody::KeepAlive(KeepAlive { .. }) => {}
                         TransportBody::Close(Close { reason, .. }) => {
                             drop(r_guard);
-                            self.del_peer(&locator, reason)?;
+                            self.del_peer(locator, reason)?;
                         }
                         _ => {
                             tracing::debug!(
@@ -322,7 +341,7 @@ impl TransportMulticastInner {
                 None => {
                     drop(r_guard);
                     if let TransportBody::Join(join) = msg.body {
-                        self.handle_join_from_unknown(join, &locator, batch_size)?;
+                        self.handle_join_from_unknown(join, locator, batch_size)?;
                     }
                 }
             }
@@ -13,6 +13,8 @@
 //
 use zenoh_link::{LinkAuthId, LinkAuthType};
 
+#[cfg(feature = "auth_jwt")]
+use super::establishment::ext::auth::JwtId;
 #[cfg(feature = "auth_usrpwd")]
 use super::establishment::ext::auth::UsrPwdId;
 
@@ -52,3 +54,13 @@ impl From<UsrPwdId> for AuthId {
         }
     }
 }
+
+#[cfg(feature = "auth_jwt")]
+impl From<JwtId> for AuthId {
+    fn from(jwt_id: JwtId) -> Self {
+        match jwt_id.0 {
+            Some(username) => AuthId::Username(username),
+            None => AuthId::None,
+        }
+    }
+}
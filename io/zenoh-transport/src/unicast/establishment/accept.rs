@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use rand::Rng;
@@ -31,6 +31,8 @@ use zenoh_protocol::{
 };
 use zenoh_result::ZResult;
 
+#[cfg(feature = "auth_jwt")]
+use super::ext::auth::JwtId;
 #[cfg(feature = "auth_usrpwd")]
 use super::ext::auth::UsrPwdId;
 #[cfg(feature = "shared-memory")]
@@ -64,17 +66,27 @@ struct StateTransport {
     ext_patch: ext::patch::StateAccept,
 }
 
-#[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+#[cfg(any(
+    feature = "transport_auth",
+    feature = "transport_compression",
+    feature = "transport_noise"
+))]
 struct StateLink {
     #[cfg(feature = "transport_auth")]
     ext_auth: ext::auth::StateAccept,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::StateAccept,
+    #[cfg(feature = "transport_noise")]
+    ext_noise: ext::noise::StateAccept,
 }
 
 struct State {
     transport: StateTransport,
-    #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+    #[cfg(any(
+        feature = "transport_auth",
+        feature = "transport_compression",
+        feature = "transport_noise"
+    ))]
     link: StateLink,
 }
 
@@ -116,6 +128,8 @@ struct RecvOpenSynOut {
     other_initial_sn: TransportSn,
     #[cfg(feature = "auth_usrpwd")]
     other_auth_id: UsrPwdId,
+    #[cfg(feature = "auth_jwt")]
+    other_jwt_id: JwtId,
 }
 
 // OpenAck
@@ -133,6 +147,7 @@ struct AcceptLink<'a> {
     link: &'a mut TransportLinkUnicast,
     prng: &'a Mutex<PseudoRng>,
     cipher: &'a BlockCipher,
+    cookie_lifetime: Duration,
     ext_qos: ext::qos::QoSFsm<'a>,
     #[cfg(feature = "transport_multilink")]
     ext_mlink: ext::multilink::MultiLinkFsm<'a>,
@@ -144,6 +159,8 @@ struct AcceptLink<'a> {
     ext_lowlatency: ext::lowlatency::LowLatencyFsm<'a>,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::CompressionFsm<'a>,
+    #[cfg(feature = "transport_noise")]
+    ext_noise: ext::noise::NoiseFsm<'a>,
     ext_patch: ext::patch::PatchFsm<'a>,
 }
 
@@ -270,6 +287,13 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Noise
+        #[cfg(feature = "transport_noise")]
+        self.ext_noise
+            .recv_init_syn((&mut state.link.ext_noise, init_syn.ext_noise))
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         // Extension Patch
         self.ext_patch
             .recv_init_syn((&mut state.transport.ext_patch, init_syn.ext_patch))
@@ -348,6 +372,16 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             None
         );
 
+        // Extension Noise
+        let ext_noise = zcondfeat!(
+            "transport_noise",
+            self.ext_noise
+                .send_init_ack(&state.link.ext_noise)
+                .await
+                .map_err(|e| (e, Some(close::reason::GENERIC)))?,
+            None
+        );
+
         // Extension Patch
         let ext_patch = self
             .ext_patch
@@ -360,12 +394,15 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             let mut prng = zasynclock!(self.prng);
 
             let nonce: u64 = prng.gen();
+            let expiry = (now() + self.cookie_lifetime).as_millis() as u64;
             let cookie = Cookie {
                 zid: input.other_zid,
                 whatami: input.other_whatami,
                 resolution: state.transport.resolution,
                 batch_size: state.transport.batch_size,
                 nonce,
+                expiry,
+                source: self.link.link.get_dst().address().as_str().to_string(),
                 ext_qos: state.transport.ext_qos,
                 #[cfg(feature = "transport_multilink")]
                 ext_mlink: state.transport.ext_mlink,
@@ -376,6 +413,8 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
                 ext_lowlatency: state.transport.ext_lowlatency,
                 #[cfg(feature = "transport_compression")]
                 ext_compression: state.link.ext_compression,
+                #[cfg(feature = "transport_noise")]
+                ext_noise: state.link.ext_noise,
                 ext_patch: state.transport.ext_patch,
             };
 
@@ -411,6 +450,7 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         }
         .into();
@@ -502,6 +542,21 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             return Err((e.into(), Some(close::reason::INVALID)));
         }
 
+        // The cookie is self-contained: verify that it is still valid and that it
+        // has been issued to the host it comes back from
+        if now().as_millis() as u64 > cookie.expiry {
+            let e = zerror!("Rejecting OpenSyn on: {}. Expired cookie.", self.link);
+            return Err((e.into(), Some(close::reason::EXPIRED)));
+        }
+        if cookie.source != self.link.link.get_dst().address().as_str() {
+            let e = zerror!(
+                "Rejecting OpenSyn on: {}. Cookie issued to {}.",
+                self.link,
+                cookie.source
+            );
+            return Err((e.into(), Some(close::reason::INVALID)));
+        }
+
         // Rebuild the state from the cookie
         let mut state = State {
             transport: StateTransport {
@@ -515,12 +570,18 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
                 ext_lowlatency: cookie.ext_lowlatency,
                 ext_patch: cookie.ext_patch,
             },
-            #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+            #[cfg(any(
+                feature = "transport_auth",
+                feature = "transport_compression",
+                feature = "transport_noise"
+            ))]
             link: StateLink {
                 #[cfg(feature = "transport_auth")]
                 ext_auth: cookie.ext_auth,
                 #[cfg(feature = "transport_compression")]
                 ext_compression: cookie.ext_compression,
+                #[cfg(feature = "transport_noise")]
+                ext_noise: cookie.ext_noise,
             },
         };
 
@@ -540,13 +601,16 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
         }
 
         // Extension Auth
-        #[cfg(feature = "auth_usrpwd")]
-        let user_password_id = self
+        #[cfg(feature = "transport_auth")]
+        #[cfg_attr(
+            not(any(feature = "auth_usrpwd", feature = "auth_jwt")),
+            allow(unused_variables)
+        )]
+        let ext_auth_out = self
             .ext_auth
             .recv_open_syn((&mut state.link.ext_auth, open_syn.ext_auth))
             .await
-            .map_err(|e| (e, Some(close::reason::GENERIC)))?
-            .auth_id;
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension MultiLink
         #[cfg(feature = "transport_multilink")]
@@ -574,7 +638,9 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             other_lease: open_syn.lease,
             other_initial_sn: open_syn.initial_sn,
             #[cfg(feature = "auth_usrpwd")]
-            other_auth_id: user_password_id,
+            other_auth_id: ext_auth_out.auth_id,
+            #[cfg(feature = "auth_jwt")]
+            other_jwt_id: ext_auth_out.jwt_id,
         };
         Ok((state, output))
     }
@@ -668,6 +734,12 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
     }
 }
 
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
 pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -> ZResult<()> {
     let endpoint = link.get_src().to_endpoint();
     let direction = TransportLinkUnicastDirection::Inbound;
@@ -689,6 +761,7 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         link: &mut link,
         prng: &manager.prng,
         cipher: &manager.cipher,
+        cookie_lifetime: manager.config.unicast.cookie_lifetime,
         ext_qos: ext::qos::QoSFsm::new(),
         #[cfg(feature = "shared-memory")]
         ext_shm: manager
@@ -704,6 +777,8 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
         ext_compression: ext::compression::CompressionFsm::new(),
+        #[cfg(feature = "transport_noise")]
+        ext_noise: ext::noise::NoiseFsm::new(manager.config.unicast.noise_psk.as_ref()),
         ext_patch: ext::patch::PatchFsm::new(),
     };
 
@@ -749,7 +824,11 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
                     ),
                     ext_patch: ext::patch::StateAccept::new(),
                 },
-                #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+                #[cfg(any(
+                    feature = "transport_auth",
+                    feature = "transport_compression",
+                    feature = "transport_noise"
+                ))]
                 link: StateLink {
                     #[cfg(feature = "transport_auth")]
                     ext_auth: manager.state.unicast.authenticator.accept(&mut *prng),
@@ -757,6 +836,11 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
                     ext_compression: ext::compression::StateAccept::new(
                         manager.config.unicast.is_compression,
                     ),
+                    #[cfg(feature = "transport_noise")]
+                    ext_noise: ext::noise::StateAccept::new(
+                        manager.config.unicast.is_noise,
+                        &fsm.link.link,
+                    ),
                 },
             }
         };
@@ -812,6 +896,8 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         is_lowlatency: state.transport.ext_lowlatency.is_lowlatency(),
         #[cfg(feature = "auth_usrpwd")]
         auth_id: osyn_out.other_auth_id,
+        #[cfg(feature = "auth_jwt")]
+        jwt_id: osyn_out.other_jwt_id,
         patch: state.transport.ext_patch.get(),
     };
 
@@ -827,6 +913,10 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         reliability: state.transport.ext_qos.reliability(),
     };
     let a_link = link.reconfigure(a_config);
+    #[cfg(feature = "transport_noise")]
+    let a_link = a_link.encrypt(state.link.ext_noise.cipher());
+    #[cfg(feature = "fault_injection")]
+    let a_link = a_link.inject(manager.faults.clone());
     let s_link = format!("{:?}", a_link);
     let a_link = LinkUnicastWithOpenAck::new(a_link, Some(oack_out.open_ack));
     let _transport = manager
@@ -33,6 +33,10 @@ pub(crate) struct Cookie {
     pub(crate) resolution: Resolution,
     pub(crate) batch_size: BatchSize,
     pub(crate) nonce: u64,
+    // Milliseconds since UNIX epoch after which the cookie is refused
+    pub(crate) expiry: u64,
+    // Address of the remote host the cookie has been issued to
+    pub(crate) source: String,
     // Extensions
     pub(crate) ext_qos: ext::qos::StateAccept,
     #[cfg(feature = "transport_multilink")]
@@ -44,6 +48,8 @@ pub(crate) struct Cookie {
     pub(crate) ext_lowlatency: ext::lowlatency::StateAccept,
     #[cfg(feature = "transport_compression")]
     pub(crate) ext_compression: ext::compression::StateAccept,
+    #[cfg(feature = "transport_noise")]
+    pub(crate) ext_noise: ext::noise::StateAccept,
     pub(crate) ext_patch: ext::patch::StateAccept,
 }
 
@@ -60,6 +66,8 @@ where
         self.write(&mut *writer, x.resolution.as_u8())?;
         self.write(&mut *writer, x.batch_size)?;
         self.write(&mut *writer, x.nonce)?;
+        self.write(&mut *writer, x.expiry)?;
+        self.write(&mut *writer, &x.source)?;
         // Extensions
         self.write(&mut *writer, &x.ext_qos)?;
         #[cfg(feature = "transport_multilink")]
@@ -71,6 +79,8 @@ where
         self.write(&mut *writer, &x.ext_lowlatency)?;
         #[cfg(feature = "transport_compression")]
         self.write(&mut *writer, &x.ext_compression)?;
+        #[cfg(feature = "transport_noise")]
+        self.write(&mut *writer, &x.ext_noise)?;
         self.write(&mut *writer, &x.ext_patch)?;
 
         Ok(())
@@ -91,6 +101,8 @@ where
         let resolution = Resolution::from(resolution);
         let batch_size: BatchSize = self.read(&mut *reader)?;
         let nonce: u64 = self.read(&mut *reader)?;
+        let expiry: u64 = self.read(&mut *reader)?;
+        let source: String = self.read(&mut *reader)?;
         // Extensions
         let ext_qos: ext::qos::StateAccept = self.read(&mut *reader)?;
         #[cfg(feature = "transport_multilink")]
@@ -102,6 +114,8 @@ where
         let ext_lowlatency: ext::lowlatency::StateAccept = self.read(&mut *reader)?;
         #[cfg(feature = "transport_compression")]
         let ext_compression: ext::compression::StateAccept = self.read(&mut *reader)?;
+        #[cfg(feature = "transport_noise")]
+        let ext_noise: ext::noise::StateAccept = self.read(&mut *reader)?;
         let ext_patch: ext::patch::StateAccept = self.read(&mut *reader)?;
 
         let cookie = Cookie {
@@ -110,6 +124,8 @@ where
             resolution,
             batch_size,
             nonce,
+            expiry,
+            source,
             ext_qos,
             #[cfg(feature = "transport_multilink")]
             ext_mlink,
@@ -120,6 +136,8 @@ where
             ext_lowlatency,
             #[cfg(feature = "transport_compression")]
             ext_compression,
+            #[cfg(feature = "transport_noise")]
+            ext_noise,
             ext_patch,
         };
 
@@ -182,6 +200,15 @@ impl Cookie {
             resolution: Resolution::rand(),
             batch_size: rng.gen(),
             nonce: rng.gen(),
+            expiry: rng.gen(),
+            source: format!(
+                "{}.{}.{}.{}:{}",
+                rng.gen::<u8>(),
+                rng.gen::<u8>(),
+                rng.gen::<u8>(),
+                rng.gen::<u8>(),
+                rng.gen::<u16>()
+            ),
             ext_qos: ext::qos::StateAccept::rand(),
             #[cfg(feature = "transport_multilink")]
             ext_mlink: ext::multilink::StateAccept::rand(),
@@ -192,6 +219,8 @@ impl Cookie {
             ext_lowlatency: ext::lowlatency::StateAccept::rand(),
             #[cfg(feature = "transport_compression")]
             ext_compression: ext::compression::StateAccept::rand(),
+            #[cfg(feature = "transport_noise")]
+            ext_noise: ext::noise::StateAccept::rand(),
             ext_patch: ext::patch::StateAccept::rand(),
         }
     }
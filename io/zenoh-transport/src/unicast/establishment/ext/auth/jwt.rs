@@ -0,0 +1,402 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use tokio::sync::RwLock;
+use zenoh_buffers::{
+    buffer::SplitBuffer,
+    reader::{DidntRead, Reader},
+    writer::{DidntWrite, Writer},
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_config::{secret, JwtConf};
+use zenoh_core::{bail, zasyncread, zasyncwrite, zerror, Error as ZError, Result as ZResult};
+use zenoh_protocol::common::{ZExtUnit, ZExtZBuf};
+
+use crate::unicast::establishment::{ext::auth::id, AcceptFsm, OpenFsm};
+
+mod ext {
+    use zenoh_protocol::{zextunit, zextzbuf};
+
+    use super::{id::JWT, ZExtUnit, ZExtZBuf};
+
+    pub(super) type InitSyn = zextzbuf!(JWT, false);
+    pub(super) type InitAck = zextunit!(JWT, false);
+    pub(super) type OpenSyn = zextunit!(JWT, false);
+    pub(super) type OpenAck = zextunit!(JWT, false);
+}
+
+const DEFAULT_JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+const DEFAULT_USERNAME_CLAIM: &str = "sub";
+
+// Authenticator
+struct Jwks {
+    file: String,
+    refresh_interval: Duration,
+    keys: JwkSet,
+    loaded: Instant,
+}
+
+impl Jwks {
+    async fn load(file: &str) -> ZResult<JwkSet> {
+        let content = tokio::fs::read_to_string(file)
+            .await
+            .map_err(|e| zerror!("Invalid JSON Web Key Set file '{}': {}.", file, e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| zerror!("Invalid JSON Web Key Set file '{}': {}.", file, e).into())
+    }
+
+    fn is_stale(&self) -> bool {
+        self.loaded.elapsed() >= self.refresh_interval
+    }
+}
+
+pub struct AuthJwt {
+    token: Option<String>,
+    jwks: Option<Jwks>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    username_claim: String,
+}
+
+impl AuthJwt {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token,
+            jwks: None,
+            issuer: None,
+            audience: None,
+            username_claim: DEFAULT_USERNAME_CLAIM.to_owned(),
+        }
+    }
+
+    pub async fn from_config(config: &JwtConf) -> ZResult<Option<Self>> {
+        const S: &str = "JWT extension - From config.";
+
+        let mut jwks = None;
+        if let Some(file) = config.jwks_file() {
+            let keys = Jwks::load(file).await.map_err(|e| zerror!("{S} {e}"))?;
+            jwks = Some(Jwks {
+                file: file.to_owned(),
+                refresh_interval: config
+                    .jwks_refresh_interval()
+                    .map_or(DEFAULT_JWKS_REFRESH_INTERVAL, Duration::from_secs),
+                keys,
+                loaded: Instant::now(),
+            });
+            tracing::debug!("{S} JSON Web Key Set has been configured.");
+        }
+
+        let token = config.token().clone();
+        if token.is_some() {
+            tracing::debug!("{S} JSON Web Token has been configured.");
+        }
+
+        if jwks.is_some() || token.is_some() {
+            tracing::debug!("{S} JWT authentication is enabled.");
+            Ok(Some(Self {
+                token,
+                jwks,
+                issuer: config.issuer().clone(),
+                audience: config.audience().clone(),
+                username_claim: config
+                    .username_claim()
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_USERNAME_CLAIM.to_owned()),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reloads the JSON Web Key Set file, keeping the previous keys if it is invalid.
+    async fn refresh(&mut self) {
+        let Some(jwks) = self.jwks.as_mut() else {
+            return;
+        };
+        match Jwks::load(&jwks.file).await {
+            Ok(keys) => jwks.keys = keys,
+            Err(e) => tracing::warn!("JWT extension - {e} Keeping the previous keys."),
+        }
+        jwks.loaded = Instant::now();
+    }
+
+    /// Validates the signature and the claims of `token`, returning its username.
+    fn validate(&self, token: &str) -> ZResult<String> {
+        let Some(jwks) = self.jwks.as_ref() else {
+            bail!("JSON Web Tokens are not accepted.");
+        };
+        let header = jsonwebtoken::decode_header(token)?;
+        let jwk = match header.kid.as_deref() {
+            Some(kid) => jwks.keys.find(kid),
+            None if jwks.keys.keys.len() == 1 => jwks.keys.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| zerror!("Unknown JSON Web Token key."))?;
+        // The algorithm of the token must be the one of its key, if the key specifies one
+        if let Some(alg) = jwk.common.key_algorithm {
+            if Algorithm::from_str(&alg.to_string()).ok() != Some(header.alg) {
+                bail!("Invalid JSON Web Token algorithm '{:?}'.", header.alg);
+            }
+        }
+        let key = DecodingKey::from_jwk(jwk)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.validate_nbf = true;
+        if let Some(issuer) = self.issuer.as_ref() {
+            validation.set_issuer(&[issuer]);
+        }
+        match self.audience.as_ref() {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        let claims = jsonwebtoken::decode::<serde_json::Value>(token, &key, &validation)?.claims;
+        match claims[&self.username_claim].as_str() {
+            Some(username) if !username.is_empty() => Ok(username.to_owned()),
+            _ => bail!("JSON Web Token without a '{}' claim.", self.username_claim),
+        }
+    }
+}
+
+impl fmt::Debug for AuthJwt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.token.as_ref() {
+            Some(_) => write!(f, "Token: '***', ")?,
+            None => write!(f, "Token: '', ")?,
+        }
+        match self.jwks.as_ref() {
+            Some(jwks) => write!(f, "JWKS: '{}'", jwks.file),
+            None => write!(f, "JWKS: ''"),
+        }
+    }
+}
+
+// OpenFsm / AcceptFsm
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct StateOpen;
+
+impl StateOpen {
+    pub(crate) const fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct StateAccept {
+    username: Vec<u8>,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct JwtId(pub Option<String>);
+
+impl StateAccept {
+    pub(crate) const fn new() -> Self {
+        Self { username: vec![] }
+    }
+
+    #[cfg(all(test, feature = "test"))]
+    pub(crate) fn rand() -> Self {
+        use rand::{distributions::Alphanumeric, Rng};
+
+        let rng = rand::thread_rng();
+        Self {
+            username: rng.sample_iter(Alphanumeric).take(16).collect(),
+        }
+    }
+}
+
+// Codec
+impl<W> WCodec<&StateAccept, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
+        self.write(&mut *writer, x.username.as_slice())
+    }
+}
+
+impl<R> RCodec<StateAccept, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
+        let username: Vec<u8> = self.read(&mut *reader)?;
+        Ok(StateAccept { username })
+    }
+}
+
+pub(crate) struct AuthJwtFsm<'a> {
+    inner: &'a RwLock<AuthJwt>,
+}
+
+impl<'a> AuthJwtFsm<'a> {
+    pub(super) const fn new(inner: &'a RwLock<AuthJwt>) -> Self {
+        Self { inner }
+    }
+}
+
+/*************************************/
+/*             InitSyn               */
+/*************************************/
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// ~     token     ~
+/// +---------------+
+///
+/// ZExtZBuf
+/// ```
+
+#[async_trait]
+impl<'a> OpenFsm for &'a AuthJwtFsm<'a> {
+    type Error = ZError;
+
+    type SendInitSynIn = &'a StateOpen;
+    type SendInitSynOut = Option<ext::InitSyn>;
+    async fn send_init_syn(
+        self,
+        _input: Self::SendInitSynIn,
+    ) -> Result<Self::SendInitSynOut, Self::Error> {
+        const S: &str = "JWT extension - Send InitSyn.";
+
+        // The token is resolved at each establishment to pick up renewed tokens
+        let r_inner = zasyncread!(self.inner);
+        let Some(token) = r_inner.token.as_ref() else {
+            return Ok(None);
+        };
+        let token = secret::resolve(token).map_err(|e| zerror!("{S} {e}"))?;
+
+        let output = Some(ZExtZBuf::new(token.as_bytes().to_vec().into()));
+        Ok(output)
+    }
+
+    type RecvInitAckIn = (&'a mut StateOpen, Option<ext::InitAck>);
+    type RecvInitAckOut = ();
+    async fn recv_init_ack(
+        self,
+        input: Self::RecvInitAckIn,
+    ) -> Result<Self::RecvInitAckOut, Self::Error> {
+        const S: &str = "JWT extension - Recv InitAck.";
+
+        let (_, ext) = input;
+        if zasyncread!(self.inner).token.is_some() && ext.is_none() {
+            bail!("{S} Expected extension.");
+        }
+
+        Ok(())
+    }
+
+    type SendOpenSynIn = &'a StateOpen;
+    type SendOpenSynOut = Option<ext::OpenSyn>;
+    async fn send_open_syn(
+        self,
+        _input: Self::SendOpenSynIn,
+    ) -> Result<Self::SendOpenSynOut, Self::Error> {
+        Ok(None)
+    }
+
+    type RecvOpenAckIn = (&'a mut StateOpen, Option<ext::OpenAck>);
+    type RecvOpenAckOut = ();
+    async fn recv_open_ack(
+        self,
+        _input: Self::RecvOpenAckIn,
+    ) -> Result<Self::RecvOpenAckOut, Self::Error> {
+        Ok(())
+    }
+}
+
+/*************************************/
+/*            ACCEPT                 */
+/*************************************/
+#[async_trait]
+impl<'a> AcceptFsm for &'a AuthJwtFsm<'a> {
+    type Error = ZError;
+
+    type RecvInitSynIn = (&'a mut StateAccept, Option<ext::InitSyn>);
+    type RecvInitSynOut = ();
+    async fn recv_init_syn(
+        self,
+        input: Self::RecvInitSynIn,
+    ) -> Result<Self::RecvInitSynOut, Self::Error> {
+        const S: &str = "JWT extension - Recv InitSyn.";
+
+        // If no JSON Web Key Set is configured, the tokens are not validated
+        let is_stale = match zasyncread!(self.inner).jwks.as_ref() {
+            Some(jwks) => jwks.is_stale(),
+            None => return Ok(()),
+        };
+        if is_stale {
+            zasyncwrite!(self.inner).refresh().await;
+        }
+
+        let (state, mut ext_jwt) = input;
+        let ext_jwt = ext_jwt
+            .take()
+            .ok_or_else(|| zerror!("{S} Expected extension."))?;
+        let token = String::from_utf8(ext_jwt.value.contiguous().into_owned())
+            .map_err(|_| zerror!("{S} Decoding error."))?;
+
+        let username = zasyncread!(self.inner)
+            .validate(&token)
+            .map_err(|e| zerror!("{S} {e}"))?;
+        state.username = username.into_bytes();
+
+        Ok(())
+    }
+
+    type SendInitAckIn = &'a StateAccept;
+    type SendInitAckOut = Option<ext::InitAck>;
+    async fn send_init_ack(
+        self,
+        _input: Self::SendInitAckIn,
+    ) -> Result<Self::SendInitAckOut, Self::Error> {
+        Ok(Some(ZExtUnit::new()))
+    }
+
+    type RecvOpenSynIn = (&'a mut StateAccept, Option<ext::OpenSyn>);
+    type RecvOpenSynOut = JwtId; // username of the validated token, if any
+    async fn recv_open_syn(
+        self,
+        input: Self::RecvOpenSynIn,
+    ) -> Result<Self::RecvOpenSynOut, Self::Error> {
+        const S: &str = "JWT extension - Recv OpenSyn.";
+
+        // The username was validated at InitSyn and carried by the cookie
+        let (state, _) = input;
+        if state.username.is_empty() {
+            return Ok(JwtId(None));
+        }
+        let username = String::from_utf8(state.username.clone())
+            .map_err(|_| zerror!("{S} Decoding error."))?;
+        Ok(JwtId(Some(username)))
+    }
+
+    type SendOpenAckIn = &'a StateAccept;
+    type SendOpenAckOut = Option<ext::OpenAck>;
+    async fn send_open_ack(
+        self,
+        _input: Self::SendOpenAckIn,
+    ) -> Result<Self::SendOpenAckOut, Self::Error> {
+        Ok(None)
+    }
+}
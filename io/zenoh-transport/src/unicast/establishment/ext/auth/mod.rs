@@ -11,6 +11,8 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(feature = "auth_jwt")]
+pub(crate) mod jwt;
 #[cfg(feature = "auth_pubkey")]
 pub(crate) mod pubkey;
 #[cfg(feature = "auth_usrpwd")]
@@ -19,6 +21,8 @@ pub(crate) mod usrpwd;
 use std::{convert::TryInto, marker::PhantomData};
 
 use async_trait::async_trait;
+#[cfg(feature = "auth_jwt")]
+pub use jwt::*;
 #[cfg(feature = "auth_pubkey")]
 pub use pubkey::*;
 use rand::{CryptoRng, Rng};
@@ -46,6 +50,8 @@ pub(crate) mod id {
     pub(crate) const PUBKEY: u8 = 0x1;
     #[cfg(feature = "auth_usrpwd")]
     pub(crate) const USRPWD: u8 = 0x2;
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) const JWT: u8 = 0x3;
 }
 
 #[derive(Debug, Default)]
@@ -54,9 +60,20 @@ pub struct Auth {
     pubkey: Option<RwLock<AuthPubKey>>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<RwLock<AuthUsrPwd>>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<RwLock<AuthJwt>>,
 }
 
 impl Auth {
+    /// Sets the user-password credentials to open transports with, unless some are configured.
+    #[cfg(feature = "auth_usrpwd")]
+    pub(crate) fn set_default_usrpwd(&mut self, user: Vec<u8>, password: Vec<u8>) {
+        match self.usrpwd.as_mut() {
+            Some(usrpwd) => usrpwd.get_mut().set_default_credentials(user, password),
+            None => self.usrpwd = Some(RwLock::new(AuthUsrPwd::new(Some((user, password))))),
+        }
+    }
+
     pub(crate) async fn from_config(config: &Config) -> ZResult<Self> {
         let auth = config.transport().auth();
 
@@ -67,6 +84,8 @@ impl Auth {
             usrpwd: AuthUsrPwd::from_config(auth.usrpwd())
                 .await?
                 .map(RwLock::new),
+            #[cfg(feature = "auth_jwt")]
+            jwt: AuthJwt::from_config(auth.jwt()).await?.map(RwLock::new),
         })
     }
 
@@ -82,6 +101,8 @@ impl Auth {
                 .usrpwd
                 .is_some()
                 .then_some(usrpwd::StateOpen::new(prng)),
+            #[cfg(feature = "auth_jwt")]
+            jwt: self.jwt.is_some().then_some(jwt::StateOpen::new()),
         }
     }
 
@@ -97,6 +118,8 @@ impl Auth {
                 .usrpwd
                 .is_some()
                 .then_some(usrpwd::StateAccept::new(prng)),
+            #[cfg(feature = "auth_jwt")]
+            jwt: self.jwt.is_some().then_some(jwt::StateAccept::new()),
         }
     }
 
@@ -106,6 +129,8 @@ impl Auth {
             pubkey: self.pubkey.as_ref().map(|x| AuthPubKeyFsm::new(x, prng)),
             #[cfg(feature = "auth_usrpwd")]
             usrpwd: self.usrpwd.as_ref().map(AuthUsrPwdFsm::new),
+            #[cfg(feature = "auth_jwt")]
+            jwt: self.jwt.as_ref().map(AuthJwtFsm::new),
             _a: PhantomData,
         }
     }
@@ -119,6 +144,8 @@ impl Auth {
             pubkey: None,
             #[cfg(feature = "auth_usrpwd")]
             usrpwd: None,
+            #[cfg(feature = "auth_jwt")]
+            jwt: None,
         }
     }
 
@@ -141,6 +168,16 @@ impl Auth {
     pub fn get_usrpwd(&self) -> Option<&RwLock<AuthUsrPwd>> {
         self.usrpwd.as_ref()
     }
+
+    #[cfg(feature = "auth_jwt")]
+    pub fn set_jwt(&mut self, jwt: Option<AuthJwt>) {
+        self.jwt = jwt.map(RwLock::new);
+    }
+
+    #[cfg(feature = "auth_jwt")]
+    pub fn get_jwt(&self) -> Option<&RwLock<AuthJwt>> {
+        self.jwt.as_ref()
+    }
 }
 
 pub(crate) struct AuthFsm<'a> {
@@ -148,6 +185,8 @@ pub(crate) struct AuthFsm<'a> {
     pubkey: Option<AuthPubKeyFsm<'a>>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<AuthUsrPwdFsm<'a>>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<AuthJwtFsm<'a>>,
     _a: PhantomData<&'a ()>, // Required only when all auth features are disabled
 }
 
@@ -157,6 +196,8 @@ pub(crate) struct StateOpen {
     pubkey: Option<pubkey::StateOpen>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<usrpwd::StateOpen>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<jwt::StateOpen>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -165,6 +206,8 @@ pub(crate) struct StateAccept {
     pubkey: Option<pubkey::StateAccept>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<usrpwd::StateAccept>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<jwt::StateAccept>,
 }
 
 impl StateAccept {
@@ -176,6 +219,8 @@ impl StateAccept {
             pubkey: rng.gen_bool(0.5).then_some(pubkey::StateAccept::rand()),
             #[cfg(feature = "auth_usrpwd")]
             usrpwd: rng.gen_bool(0.5).then_some(usrpwd::StateAccept::rand()),
+            #[cfg(feature = "auth_jwt")]
+            jwt: rng.gen_bool(0.5).then_some(jwt::StateAccept::rand()),
         }
     }
 }
@@ -210,6 +255,15 @@ where
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            if let Some(jwt) = x.jwt.as_ref() {
+                self.write(&mut wbuf, id::JWT)?;
+                self.write(&mut wbuf, jwt)?;
+                count += 1;
+            }
+        }
+
         self.write(&mut *writer, count)?;
         if !buff.is_empty() {
             let mut rbuf = buff.reader();
@@ -233,6 +287,8 @@ where
         let mut pubkey: Option<pubkey::StateAccept> = None;
         #[cfg(feature = "auth_usrpwd")]
         let mut usrpwd: Option<usrpwd::StateAccept> = None;
+        #[cfg(feature = "auth_jwt")]
+        let mut jwt: Option<jwt::StateAccept> = None;
 
         while count > 0 {
             let e: u8 = self.read(&mut *reader)?;
@@ -245,6 +301,10 @@ where
                 id::USRPWD => {
                     usrpwd = Some(self.read(&mut *reader)?);
                 }
+                #[cfg(feature = "auth_jwt")]
+                id::JWT => {
+                    jwt = Some(self.read(&mut *reader)?);
+                }
                 _ => return Err(DidntRead),
             }
 
@@ -256,6 +316,8 @@ where
             pubkey,
             #[cfg(feature = "auth_usrpwd")]
             usrpwd,
+            #[cfg(feature = "auth_jwt")]
+            jwt,
         };
         Ok(state)
     }
@@ -325,6 +387,19 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_init_syn(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -377,6 +452,18 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    e.recv_init_ack((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         Ok(())
     }
 
@@ -416,6 +503,19 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_open_syn(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -468,6 +568,18 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    e.recv_open_ack((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         Ok(())
     }
 }
@@ -478,6 +590,8 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
 pub(crate) struct RecvOpenSynOut {
     #[cfg(feature = "auth_usrpwd")]
     pub(crate) auth_id: UsrPwdId,
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) jwt_id: JwtId,
 }
 
 #[async_trait]
@@ -525,6 +639,18 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    e.recv_init_syn((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         Ok(())
     }
 
@@ -564,6 +690,19 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_init_ack(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -623,9 +762,28 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
                 _ => bail!("{S} Invalid UsrPwd configuration."),
             }
         }
+
+        #[cfg(feature = "auth_jwt")]
+        let jwt_id: JwtId;
+
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    jwt_id = e.recv_open_syn((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {
+                    jwt_id = JwtId(None);
+                }
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
         Ok(RecvOpenSynOut {
             #[cfg(feature = "auth_usrpwd")]
             auth_id,
+            #[cfg(feature = "auth_jwt")]
+            jwt_id,
         })
     }
 
@@ -665,6 +823,19 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_open_ack(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid JWT configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -26,7 +26,7 @@ use zenoh_buffers::{
     writer::{DidntWrite, HasWriter, Writer},
 };
 use zenoh_codec::{RCodec, WCodec, Zenoh080};
-use zenoh_config::PubKeyConf;
+use zenoh_config::{secret, PubKeyConf};
 use zenoh_core::{bail, zasynclock, zasyncread, zerror, Error as ZError, Result as ZResult};
 use zenoh_crypto::PseudoRng;
 use zenoh_protocol::common::{ZExtUnit, ZExtZBuf};
@@ -85,9 +85,9 @@ impl AuthPubKey {
         // First, check if PEM keys are provided
         match (config.public_key_pem(), config.private_key_pem()) {
             (Some(public), Some(private)) => {
-                let pub_key = RsaPublicKey::from_pkcs1_pem(public)
+                let pub_key = RsaPublicKey::from_pkcs1_pem(&secret::resolve(public)?)
                     .map_err(|e| zerror!("{} Rsa Public Key: {}.", S, e))?;
-                let pri_key = RsaPrivateKey::from_pkcs1_pem(private)
+                let pri_key = RsaPrivateKey::from_pkcs1_pem(&secret::resolve(private)?)
                     .map_err(|e| zerror!("{} Rsa Private Key: {}.", S, e))?;
                 return Ok(Some(Self::new(pub_key.into(), pri_key.into())));
             }
@@ -103,10 +103,11 @@ impl AuthPubKey {
         // Second, check if PEM files are provided
         match (config.public_key_file(), config.private_key_file()) {
             (Some(public), Some(private)) => {
-                let path = Path::new(public);
-                let pub_key = RsaPublicKey::read_pkcs1_pem_file(path)
+                let public = secret::resolve(public)?;
+                let pub_key = RsaPublicKey::read_pkcs1_pem_file(Path::new(public.as_ref()))
                     .map_err(|e| zerror!("{} Rsa Public Key: {}.", S, e))?;
-                let path = Path::new(private);
+                let private = secret::resolve(private)?;
+                let path = Path::new(private.as_ref());
                 let pri_key = RsaPrivateKey::read_pkcs1_pem_file(path)
                     .map_err(|e| zerror!("{} Rsa Private Key: {}.", S, e))?;
                 return Ok(Some(Self::new(pub_key.into(), pri_key.into())));
@@ -21,7 +21,7 @@ use zenoh_buffers::{
     writer::{DidntWrite, HasWriter, Writer},
 };
 use zenoh_codec::{RCodec, WCodec, Zenoh080};
-use zenoh_config::UsrPwdConf;
+use zenoh_config::{secret, UsrPwdConf};
 use zenoh_core::{bail, zasyncread, zerror, Error as ZError, Result as ZResult};
 use zenoh_crypto::hmac;
 use zenoh_protocol::common::{ZExtUnit, ZExtZ64, ZExtZBuf};
@@ -66,12 +66,18 @@ impl AuthUsrPwd {
         Ok(())
     }
 
+    /// Sets the credentials to open transports with, unless some are configured.
+    pub(crate) fn set_default_credentials(&mut self, user: User, password: Password) {
+        self.credentials.get_or_insert((user, password));
+    }
+
     pub async fn from_config(config: &UsrPwdConf) -> ZResult<Option<Self>> {
         const S: &str = "UsrPwd extension - From config.";
 
         let mut lookup: HashMap<User, Password> = HashMap::new();
         if let Some(dict) = config.dictionary_file() {
-            let content = tokio::fs::read_to_string(dict)
+            let dict = secret::resolve(dict)?;
+            let content = tokio::fs::read_to_string(dict.as_ref())
                 .await
                 .map_err(|e| zerror!("{S} Invalid user-password dictionary file: {}.", e))?;
 
@@ -106,7 +112,10 @@ impl AuthUsrPwd {
         if let Some(user) = config.user() {
             if let Some(password) = config.password() {
                 tracing::debug!("{S} User-password has been configured.");
-                credentials = Some((user.as_bytes().to_owned(), password.as_bytes().to_owned()));
+                credentials = Some((
+                    secret::resolve(user)?.as_bytes().to_owned(),
+                    secret::resolve(password)?.as_bytes().to_owned(),
+                ));
             }
         }
 
@@ -18,6 +18,8 @@ pub(crate) mod compression;
 pub(crate) mod lowlatency;
 #[cfg(feature = "transport_multilink")]
 pub(crate) mod multilink;
+#[cfg(feature = "transport_noise")]
+pub(crate) mod noise;
 pub(crate) mod patch;
 pub(crate) mod qos;
 #[cfg(feature = "shared-memory")]
@@ -0,0 +1,433 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use sha3::{Digest, Sha3_256};
+use snow::{Builder, HandshakeState};
+use zenoh_buffers::{
+    buffer::SplitBuffer,
+    reader::{DidntRead, Reader},
+    writer::{DidntWrite, Writer},
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_core::{bail, zerror};
+use zenoh_crypto::AeadCipher;
+use zenoh_link::{LinkAuthType, LinkUnicast};
+use zenoh_protocol::transport::init;
+use zenoh_result::{Error as ZError, ZResult};
+
+use crate::unicast::establishment::{AcceptFsm, OpenFsm};
+
+// The nodes authenticate each other with the pre-shared key, the NN pattern alone being open to
+// man-in-the-middle attacks
+const PATTERN: &str = "Noise_NNpsk0_25519_AESGCM_SHA256";
+const PROLOGUE: &[u8] = b"zenoh";
+// The maximum length of a NN handshake message: an ephemeral key and an empty payload tag
+const MAX_MESSAGE_LEN: usize = 64;
+
+pub(crate) const PSK_SIZE: usize = 32;
+
+type Keys = ([u8; AeadCipher::KEY_SIZE], [u8; AeadCipher::KEY_SIZE]);
+
+/// Derives the pre-shared key of the Noise handshake from the configured secret.
+pub(crate) fn psk(secret: &str) -> [u8; PSK_SIZE] {
+    Sha3_256::digest(secret.as_bytes()).into()
+}
+
+// Links already encrypted by TLS or QUIC do not need a second layer of encryption
+fn is_encrypted(link: &LinkUnicast) -> bool {
+    !matches!(link.get_auth_id().get_type(), LinkAuthType::None)
+}
+
+/// The cipher of a link encrypted with the keys established by the Noise handshake.
+///
+/// Each batch is sent as the little-endian counter used as nonce, followed by the encrypted batch
+/// and its authentication tag. Batches received with a counter not greater than the last accepted
+/// one are rejected as replayed.
+pub(crate) struct LinkCipher {
+    tx: AeadCipher,
+    rx: AeadCipher,
+    tx_counter: AtomicU64,
+    rx_counter: AtomicU64,
+}
+
+impl LinkCipher {
+    pub(crate) const COUNTER_SIZE: usize = 8;
+    pub(crate) const OVERHEAD: usize = Self::COUNTER_SIZE + AeadCipher::TAG_SIZE;
+
+    fn new((tx, rx): Keys) -> Self {
+        Self {
+            tx: AeadCipher::new(tx),
+            rx: AeadCipher::new(rx),
+            tx_counter: AtomicU64::new(0),
+            rx_counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Appends the sealed `bytes` to `into`.
+    pub(crate) fn seal(&self, bytes: &[u8], into: &mut Vec<u8>) -> ZResult<()> {
+        let counter = self.tx_counter.fetch_add(1, Ordering::Relaxed);
+        if counter == u64::MAX {
+            bail!("Noise cipher exhausted");
+        }
+        into.extend_from_slice(&counter.to_le_bytes());
+        let start = into.len();
+        into.extend_from_slice(bytes);
+        let tag = self.tx.seal_in_place(counter, &mut into[start..])?;
+        into.extend_from_slice(&tag);
+        Ok(())
+    }
+
+    /// Opens the `sealed` bytes into `into`, returning the length of the decrypted bytes.
+    pub(crate) fn open(&self, sealed: &[u8], into: &mut [u8]) -> ZResult<usize> {
+        if sealed.len() < Self::OVERHEAD {
+            bail!("Invalid sealed batch length: {}", sealed.len());
+        }
+        let (counter, sealed) = sealed.split_at(Self::COUNTER_SIZE);
+        let counter = u64::from_le_bytes(counter.try_into().unwrap());
+        if counter < self.rx_counter.load(Ordering::Relaxed) {
+            bail!("Replayed batch with counter {}", counter);
+        }
+        let (bytes, tag) = sealed.split_at(sealed.len() - AeadCipher::TAG_SIZE);
+        let into = into
+            .get_mut(..bytes.len())
+            .ok_or_else(|| zerror!("Sealed batch larger than the buffer: {}", bytes.len()))?;
+        into.copy_from_slice(bytes);
+        self.rx.open_in_place(counter, into, tag)?;
+        self.rx_counter.store(counter + 1, Ordering::Relaxed);
+        Ok(bytes.len())
+    }
+}
+
+// The same cipher is shared by the clones of a link
+impl PartialEq for LinkCipher {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for LinkCipher {}
+
+// Extension Fsm
+pub(crate) struct NoiseFsm<'a> {
+    psk: Option<&'a [u8; PSK_SIZE]>,
+}
+
+impl<'a> NoiseFsm<'a> {
+    pub(crate) const fn new(psk: Option<&'a [u8; PSK_SIZE]>) -> Self {
+        Self { psk }
+    }
+
+    fn builder(&self) -> ZResult<Builder<'a>> {
+        let Some(psk) = self.psk else {
+            bail!("Noise encryption requires a pre-shared key");
+        };
+        let params = PATTERN
+            .parse()
+            .map_err(|e| zerror!("Invalid Noise pattern {}: {}", PATTERN, e))?;
+        Ok(Builder::new(params).prologue(PROLOGUE).psk(0, psk))
+    }
+}
+
+/*************************************/
+/*              OPEN                 */
+/*************************************/
+#[derive(Debug)]
+pub(crate) struct StateOpen {
+    is_noise: bool,
+    handshake: Option<HandshakeState>,
+    keys: Option<Keys>,
+}
+
+impl StateOpen {
+    pub(crate) fn new(is_noise: bool, link: &LinkUnicast) -> Self {
+        Self {
+            is_noise: is_noise && !is_encrypted(link),
+            handshake: None,
+            keys: None,
+        }
+    }
+
+    pub(crate) fn cipher(&self) -> Option<LinkCipher> {
+        self.keys.map(LinkCipher::new)
+    }
+}
+
+#[async_trait]
+impl<'a> OpenFsm for &'a NoiseFsm<'a> {
+    type Error = ZError;
+
+    type SendInitSynIn = &'a mut StateOpen;
+    type SendInitSynOut = Option<init::ext::Noise>;
+    async fn send_init_syn(
+        self,
+        state: Self::SendInitSynIn,
+    ) -> Result<Self::SendInitSynOut, Self::Error> {
+        const S: &str = "Noise extension - Send InitSyn.";
+
+        if !state.is_noise {
+            return Ok(None);
+        }
+
+        let mut handshake = self
+            .builder()?
+            .build_initiator()
+            .map_err(|e| zerror!("{S} {e}"))?;
+        let mut message = vec![0u8; MAX_MESSAGE_LEN];
+        let len = handshake
+            .write_message(&[], &mut message)
+            .map_err(|e| zerror!("{S} {e}"))?;
+        message.truncate(len);
+        state.handshake = Some(handshake);
+
+        Ok(Some(init::ext::Noise::new(message.into())))
+    }
+
+    type RecvInitAckIn = (&'a mut StateOpen, Option<init::ext::Noise>);
+    type RecvInitAckOut = ();
+    async fn recv_init_ack(
+        self,
+        input: Self::RecvInitAckIn,
+    ) -> Result<Self::RecvInitAckOut, Self::Error> {
+        const S: &str = "Noise extension - Recv InitAck.";
+
+        let (state, other_ext) = input;
+        let Some(mut handshake) = state.handshake.take() else {
+            return Ok(());
+        };
+        let Some(ext) = other_ext else {
+            bail!("{S} Encryption refused by the peer.");
+        };
+
+        let mut payload = [0u8; MAX_MESSAGE_LEN];
+        handshake
+            .read_message(&ext.value.contiguous(), &mut payload)
+            .map_err(|e| zerror!("{S} {e}"))?;
+        if !handshake.is_handshake_finished() {
+            bail!("{S} Handshake not finished.");
+        }
+        // The first key encrypts from the initiator to the responder
+        state.keys = Some(handshake.dangerously_get_raw_split());
+
+        Ok(())
+    }
+
+    type SendOpenSynIn = &'a StateOpen;
+    type SendOpenSynOut = ();
+    async fn send_open_syn(
+        self,
+        _state: Self::SendOpenSynIn,
+    ) -> Result<Self::SendOpenSynOut, Self::Error> {
+        Ok(())
+    }
+
+    type RecvOpenAckIn = &'a mut StateOpen;
+    type RecvOpenAckOut = ();
+    async fn recv_open_ack(
+        self,
+        _state: Self::RecvOpenAckIn,
+    ) -> Result<Self::RecvOpenAckOut, Self::Error> {
+        Ok(())
+    }
+}
+
+/*************************************/
+/*            ACCEPT                 */
+/*************************************/
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct StateAccept {
+    is_noise: bool,
+    // The keys are carried by the encrypted cookie until the link is established
+    keys: Option<Keys>,
+    message: Option<Vec<u8>>,
+}
+
+impl StateAccept {
+    pub(crate) fn new(is_noise: bool, link: &LinkUnicast) -> Self {
+        Self {
+            is_noise: is_noise && !is_encrypted(link),
+            keys: None,
+            message: None,
+        }
+    }
+
+    pub(crate) fn cipher(&self) -> Option<LinkCipher> {
+        // The second key encrypts from the responder to the initiator
+        self.keys.map(|(k1, k2)| LinkCipher::new((k2, k1)))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rand() -> Self {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        Self {
+            is_noise: rng.gen_bool(0.5),
+            keys: rng.gen_bool(0.5).then(|| (rng.gen(), rng.gen())),
+            message: None,
+        }
+    }
+}
+
+// Codec
+impl<W> WCodec<&StateAccept, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
+        let is_noise = u8::from(x.is_noise);
+        self.write(&mut *writer, is_noise)?;
+        match x.keys.as_ref() {
+            Some((k1, k2)) => {
+                self.write(&mut *writer, 1u8)?;
+                writer.write_exact(k1)?;
+                writer.write_exact(k2)?;
+            }
+            None => self.write(&mut *writer, 0u8)?,
+        }
+        Ok(())
+    }
+}
+
+impl<R> RCodec<StateAccept, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
+        let is_noise: u8 = self.read(&mut *reader)?;
+        let is_noise = is_noise == 1;
+        let has_keys: u8 = self.read(&mut *reader)?;
+        let keys = if has_keys == 1 {
+            let mut k1 = [0u8; AeadCipher::KEY_SIZE];
+            reader.read_exact(&mut k1)?;
+            let mut k2 = [0u8; AeadCipher::KEY_SIZE];
+            reader.read_exact(&mut k2)?;
+            Some((k1, k2))
+        } else {
+            None
+        };
+        Ok(StateAccept {
+            is_noise,
+            keys,
+            message: None,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> AcceptFsm for &'a NoiseFsm<'a> {
+    type Error = ZError;
+
+    type RecvInitSynIn = (&'a mut StateAccept, Option<init::ext::Noise>);
+    type RecvInitSynOut = ();
+    async fn recv_init_syn(
+        self,
+        input: Self::RecvInitSynIn,
+    ) -> Result<Self::RecvInitSynOut, Self::Error> {
+        const S: &str = "Noise extension - Recv InitSyn.";
+
+        let (state, other_ext) = input;
+        if !state.is_noise {
+            return Ok(());
+        }
+        let Some(ext) = other_ext else {
+            bail!("{S} Encryption required but not requested by the peer.");
+        };
+
+        let mut handshake = self
+            .builder()?
+            .build_responder()
+            .map_err(|e| zerror!("{S} {e}"))?;
+        let mut payload = [0u8; MAX_MESSAGE_LEN];
+        handshake
+            .read_message(&ext.value.contiguous(), &mut payload)
+            .map_err(|e| zerror!("{S} {e}"))?;
+        let mut message = vec![0u8; MAX_MESSAGE_LEN];
+        let len = handshake
+            .write_message(&[], &mut message)
+            .map_err(|e| zerror!("{S} {e}"))?;
+        message.truncate(len);
+        if !handshake.is_handshake_finished() {
+            bail!("{S} Handshake not finished.");
+        }
+        state.keys = Some(handshake.dangerously_get_raw_split());
+        state.message = Some(message);
+
+        Ok(())
+    }
+
+    type SendInitAckIn = &'a StateAccept;
+    type SendInitAckOut = Option<init::ext::Noise>;
+    async fn send_init_ack(
+        self,
+        state: Self::SendInitAckIn,
+    ) -> Result<Self::SendInitAckOut, Self::Error> {
+        let output = state
+            .message
+            .as_ref()
+            .map(|message| init::ext::Noise::new(message.clone().into()));
+        Ok(output)
+    }
+
+    type RecvOpenSynIn = &'a mut StateAccept;
+    type RecvOpenSynOut = ();
+    async fn recv_open_syn(
+        self,
+        _state: Self::RecvOpenSynIn,
+    ) -> Result<Self::RecvOpenSynOut, Self::Error> {
+        Ok(())
+    }
+
+    type SendOpenAckIn = &'a StateAccept;
+    type SendOpenAckOut = ();
+    async fn send_open_ack(
+        self,
+        _state: Self::SendOpenAckIn,
+    ) -> Result<Self::SendOpenAckOut, Self::Error> {
+        Ok(())
+    }
+}
+
+mod tests {
+    #[test]
+    fn noise_link_cipher() {
+        use super::LinkCipher;
+
+        let alice = LinkCipher::new(([1; 32], [2; 32]));
+        let bob = LinkCipher::new(([2; 32], [1; 32]));
+
+        let mut sealed = vec![];
+        alice.seal(b"batch", &mut sealed).unwrap();
+        assert_eq!(sealed.len(), b"batch".len() + LinkCipher::OVERHEAD);
+
+        let mut into = [0u8; 16];
+        let len = bob.open(&sealed, &mut into).unwrap();
+        assert_eq!(&into[..len], b"batch");
+        // Replayed and tampered batches are rejected
+        assert!(bob.open(&sealed, &mut into).is_err());
+        let mut other = vec![];
+        alice.seal(b"batch", &mut other).unwrap();
+        *other.last_mut().unwrap() ^= 1;
+        assert!(bob.open(&other, &mut into).is_err());
+        // The keys are directional
+        let mut other = vec![];
+        alice.seal(b"batch", &mut other).unwrap();
+        assert!(alice.open(&other, &mut into).is_err());
+        assert!(bob.open(&other, &mut into).is_ok());
+    }
+}
@@ -32,6 +32,8 @@ use zenoh_result::ZResult;
 use super::ext::shm::AuthSegment;
 #[cfg(feature = "shared-memory")]
 use crate::shm::TransportShmConfig;
+#[cfg(feature = "auth_jwt")]
+use crate::unicast::establishment::ext::auth::JwtId;
 #[cfg(feature = "auth_usrpwd")]
 use crate::unicast::establishment::ext::auth::UsrPwdId;
 use crate::{
@@ -61,17 +63,27 @@ struct StateTransport {
     ext_patch: ext::patch::StateOpen,
 }
 
-#[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+#[cfg(any(
+    feature = "transport_auth",
+    feature = "transport_compression",
+    feature = "transport_noise"
+))]
 struct StateLink {
     #[cfg(feature = "transport_auth")]
     ext_auth: ext::auth::StateOpen,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::StateOpen,
+    #[cfg(feature = "transport_noise")]
+    ext_noise: ext::noise::StateOpen,
 }
 
 struct State {
     transport: StateTransport,
-    #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+    #[cfg(any(
+        feature = "transport_auth",
+        feature = "transport_compression",
+        feature = "transport_noise"
+    ))]
     link: StateLink,
 }
 
@@ -125,6 +137,8 @@ struct OpenLink<'a> {
     ext_lowlatency: ext::lowlatency::LowLatencyFsm<'a>,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::CompressionFsm<'a>,
+    #[cfg(feature = "transport_noise")]
+    ext_noise: ext::noise::NoiseFsm<'a>,
     ext_patch: ext::patch::PatchFsm<'a>,
 }
 
@@ -194,6 +208,16 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             None
         );
 
+        // Extension Noise
+        let ext_noise = zcondfeat!(
+            "transport_noise",
+            self.ext_noise
+                .send_init_syn(&mut state.link.ext_noise)
+                .await
+                .map_err(|e| (e, Some(close::reason::GENERIC)))?,
+            None
+        );
+
         // Extension Patch
         let ext_patch = self
             .ext_patch
@@ -215,6 +239,7 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_noise,
             ext_patch,
         }
         .into();
@@ -357,6 +382,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Noise
+        #[cfg(feature = "transport_noise")]
+        self.ext_noise
+            .recv_init_ack((&mut state.link.ext_noise, init_ack.ext_noise))
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         // Extension Patch
         self.ext_patch
             .recv_init_ack((&mut state.transport.ext_patch, init_ack.ext_patch))
@@ -591,6 +623,8 @@ pub(crate) async fn open_link(
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
         ext_compression: ext::compression::CompressionFsm::new(),
+        #[cfg(feature = "transport_noise")]
+        ext_noise: ext::noise::NoiseFsm::new(manager.config.unicast.noise_psk.as_ref()),
         ext_patch: ext::patch::PatchFsm::new(),
     };
 
@@ -625,7 +659,11 @@ pub(crate) async fn open_link(
                 ),
                 ext_patch: ext::patch::StateOpen::new(),
             },
-            #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
+            #[cfg(any(
+                feature = "transport_auth",
+                feature = "transport_compression",
+                feature = "transport_noise"
+            ))]
             link: StateLink {
                 #[cfg(feature = "transport_auth")]
                 ext_auth: manager.state.unicast.authenticator.open(&mut *prng),
@@ -633,6 +671,8 @@ pub(crate) async fn open_link(
                 ext_compression: ext::compression::StateOpen::new(
                     manager.config.unicast.is_compression,
                 ),
+                #[cfg(feature = "transport_noise")]
+                ext_noise: ext::noise::StateOpen::new(manager.config.unicast.is_noise, &link.link),
             },
         }
     };
@@ -689,6 +729,8 @@ pub(crate) async fn open_link(
         is_lowlatency: state.transport.ext_lowlatency.is_lowlatency(),
         #[cfg(feature = "auth_usrpwd")]
         auth_id: UsrPwdId(None),
+        #[cfg(feature = "auth_jwt")]
+        jwt_id: JwtId(None),
         patch: state.transport.ext_patch.get(),
     };
 
@@ -704,6 +746,10 @@ pub(crate) async fn open_link(
         reliability: state.transport.ext_qos.reliability(),
     };
     let o_link = link.reconfigure(o_config);
+    #[cfg(feature = "transport_noise")]
+    let o_link = o_link.encrypt(state.link.ext_noise.cipher());
+    #[cfg(feature = "fault_injection")]
+    let o_link = o_link.inject(manager.faults.clone());
     let s_link = format!("{:?}", o_link);
     let o_link = LinkUnicastWithOpenAck::new(o_link, None);
     let transport = manager
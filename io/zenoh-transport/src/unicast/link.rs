@@ -22,7 +22,13 @@ use zenoh_protocol::{
 };
 use zenoh_result::{zerror, ZResult};
 
+#[cfg(any(feature = "transport_noise", feature = "fault_injection"))]
+use crate::common::batch::L_LEN;
 use crate::common::batch::{BatchConfig, Decode, Encode, Finalize, RBatch, WBatch};
+#[cfg(feature = "fault_injection")]
+use crate::common::faults::{FaultInjector, LinkFaults};
+#[cfg(feature = "transport_noise")]
+use crate::unicast::establishment::ext::noise::LinkCipher;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub(crate) enum TransportLinkUnicastDirection {
@@ -43,6 +49,10 @@ pub(crate) struct TransportLinkUnicastConfig {
 pub(crate) struct TransportLinkUnicast {
     pub(crate) link: LinkUnicast,
     pub(crate) config: TransportLinkUnicastConfig,
+    #[cfg(feature = "transport_noise")]
+    pub(crate) cipher: Option<Arc<LinkCipher>>,
+    #[cfg(feature = "fault_injection")]
+    pub(crate) faults: Option<Arc<FaultInjector>>,
 }
 
 impl TransportLinkUnicast {
@@ -56,7 +66,34 @@ impl TransportLinkUnicast {
 
     fn init(link: LinkUnicast, mut config: TransportLinkUnicastConfig) -> Self {
         config.batch.mtu = link.get_mtu().min(config.batch.mtu);
-        Self { link, config }
+        Self {
+            link,
+            config,
+            #[cfg(feature = "transport_noise")]
+            cipher: None,
+            #[cfg(feature = "fault_injection")]
+            faults: None,
+        }
+    }
+
+    #[cfg(feature = "fault_injection")]
+    pub(crate) fn inject(mut self, faults: Arc<FaultInjector>) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    #[cfg(feature = "transport_noise")]
+    pub(crate) fn encrypt(mut self, cipher: Option<LinkCipher>) -> Self {
+        if let Some(cipher) = cipher {
+            // Leave room for the counter and the tag of the sealed batches
+            self.config.batch.mtu = self
+                .config
+                .batch
+                .mtu
+                .saturating_sub(LinkCipher::OVERHEAD as BatchSize);
+            self.cipher = Some(Arc::new(cipher));
+        }
+        self
     }
 
     pub(crate) fn link(&self) -> Link {
@@ -80,6 +117,10 @@ impl TransportLinkUnicast {
                     )),
                 None
             ),
+            #[cfg(feature = "transport_noise")]
+            sealed: vec![],
+            #[cfg(feature = "fault_injection")]
+            held: None,
         }
     }
 
@@ -87,6 +128,13 @@ impl TransportLinkUnicast {
         TransportLinkUnicastRx {
             link: self.link.clone(),
             config: self.config.clone(),
+            #[cfg(feature = "transport_noise")]
+            cipher: self.cipher.clone(),
+            #[cfg(feature = "transport_noise")]
+            sealed: match self.cipher {
+                Some(_) => vec![0; L_LEN + self.config.batch.mtu as usize + LinkCipher::OVERHEAD],
+                None => vec![],
+            },
         }
     }
 
@@ -139,6 +187,11 @@ impl PartialEq<Link> for TransportLinkUnicast {
 pub(crate) struct TransportLinkUnicastTx {
     pub(crate) inner: TransportLinkUnicast,
     pub(crate) buffer: Option<BBuf>,
+    #[cfg(feature = "transport_noise")]
+    sealed: Vec<u8>,
+    // A batch held back to be sent after the next one
+    #[cfg(feature = "fault_injection")]
+    held: Option<Vec<u8>>,
 }
 
 impl TransportLinkUnicastTx {
@@ -160,11 +213,88 @@ impl TransportLinkUnicastTx {
                 .as_slice(),
         };
 
+        #[cfg(feature = "transport_noise")]
+        let bytes = match self.inner.cipher.as_ref() {
+            Some(cipher) => seal(
+                cipher,
+                self.inner.link.is_streamed(),
+                bytes,
+                &mut self.sealed,
+            )
+            .map_err(|e| zerror!("{ERR}{}. {e}", self.inner))?,
+            None => bytes,
+        };
+
         // tracing::trace!("WBytes: {:02x?}", bytes);
 
+        #[cfg(feature = "fault_injection")]
+        if let Some(faults) = self
+            .inner
+            .faults
+            .as_ref()
+            .and_then(|f| f.faults(self.inner.link.get_dst()))
+        {
+            let bytes = bytes.to_vec();
+            return self.send_faulty(bytes, faults).await;
+        }
+
         // Send the message on the link
         self.inner.link.write_all(bytes).await?;
 
+        #[cfg(feature = "fault_injection")]
+        if let Some(held) = self.held.take() {
+            self.inner.link.write_all(&held).await?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "fault_injection")]
+    async fn send_faulty(&mut self, mut bytes: Vec<u8>, faults: LinkFaults) -> ZResult<()> {
+        use rand::Rng;
+
+        if faults.delay_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(faults.delay_ms)).await;
+        }
+        // The random generator is not held across the await points
+        let (drop, corrupt, reorder) = {
+            let mut rng = rand::thread_rng();
+            // The length of the batches is preserved on streamed links
+            let start = if self.inner.link.is_streamed() {
+                L_LEN
+            } else {
+                0
+            };
+            let corrupt = (bytes.len() > start && rng.gen_bool(faults.corrupt)).then(|| {
+                (
+                    rng.gen_range(start..bytes.len()),
+                    1u8 << rng.gen_range(0..8),
+                )
+            });
+            (
+                rng.gen_bool(faults.drop),
+                corrupt,
+                rng.gen_bool(faults.reorder),
+            )
+        };
+        if drop {
+            tracing::trace!("Dropped batch on link {}", self.inner);
+            return Ok(());
+        }
+        if let Some((index, bit)) = corrupt {
+            tracing::trace!("Corrupted batch on link {}", self.inner);
+            bytes[index] ^= bit;
+        }
+        if reorder && self.held.is_none() {
+            tracing::trace!("Reordered batch on link {}", self.inner);
+            self.held = Some(bytes);
+            return Ok(());
+        }
+
+        self.inner.link.write_all(&bytes).await?;
+        if let Some(held) = self.held.take() {
+            self.inner.link.write_all(&held).await?;
+        }
         Ok(())
     }
 
@@ -180,6 +310,28 @@ impl TransportLinkUnicastTx {
     }
 }
 
+#[cfg(feature = "transport_noise")]
+fn seal<'a>(
+    cipher: &LinkCipher,
+    is_streamed: bool,
+    bytes: &[u8],
+    into: &'a mut Vec<u8>,
+) -> ZResult<&'a [u8]> {
+    into.clear();
+    if !is_streamed {
+        cipher.seal(bytes, into)?;
+        return Ok(into.as_slice());
+    }
+
+    // The length of the batch is replaced by the length of the sealed batch
+    into.extend_from_slice(&BatchSize::MIN.to_le_bytes());
+    cipher.seal(&bytes[L_LEN..], into)?;
+    let len = BatchSize::try_from(into.len() - L_LEN)
+        .map_err(|_| zerror!("Sealed batch too large: {}", into.len() - L_LEN))?;
+    into[..L_LEN].copy_from_slice(&len.to_le_bytes());
+    Ok(into.as_slice())
+}
+
 impl fmt::Display for TransportLinkUnicastTx {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.inner)
@@ -199,18 +351,17 @@ impl fmt::Debug for TransportLinkUnicastTx {
 pub(crate) struct TransportLinkUnicastRx {
     pub(crate) link: LinkUnicast,
     pub(crate) config: TransportLinkUnicastConfig,
+    #[cfg(feature = "transport_noise")]
+    cipher: Option<Arc<LinkCipher>>,
+    #[cfg(feature = "transport_noise")]
+    sealed: Vec<u8>,
 }
 
 impl TransportLinkUnicastRx {
-    pub async fn recv_batch<C, T>(&mut self, buff: C) -> ZResult<RBatch>
-    where
-        C: Fn() -> T + Copy,
-        T: AsMut<[u8]> + ZSliceBuffer + 'static,
-    {
+    async fn read(&self, into: &mut [u8]) -> ZResult<usize> {
         const ERR: &str = "Read error from link: ";
 
-        let mut into = (buff)();
-        let end = if self.link.is_streamed() {
+        if self.link.is_streamed() {
             // Read and decode the message length
             let mut len = BatchSize::MIN.to_le_bytes();
             self.link.read_exact(&mut len).await?;
@@ -218,15 +369,64 @@ impl TransportLinkUnicastRx {
 
             // Read the bytes
             let slice = into
-                .as_mut()
                 .get_mut(len.len()..len.len() + l)
                 .ok_or_else(|| zerror!("{ERR}{self}. Invalid batch length or buffer size."))?;
             self.link.read_exact(slice).await?;
-            len.len() + l
+            Ok(len.len() + l)
         } else {
             // Read the bytes
-            self.link.read(into.as_mut()).await?
+            self.link.read(into).await
+        }
+    }
+
+    #[cfg(feature = "transport_noise")]
+    async fn read_sealed(&mut self, cipher: &LinkCipher, into: &mut [u8]) -> ZResult<usize> {
+        const ERR: &str = "Read error from link: ";
+
+        let is_streamed = self.link.is_streamed();
+        let offset = if is_streamed { L_LEN } else { 0 };
+        let mut sealed = std::mem::take(&mut self.sealed);
+        let res = loop {
+            let end = match self.read(&mut sealed).await {
+                Ok(end) => end,
+                Err(e) => break Err(e),
+            };
+            let len = match into.get_mut(offset..) {
+                Some(into) => cipher.open(&sealed[offset..end], into),
+                None => Err(zerror!("Invalid buffer size").into()),
+            };
+            match len {
+                Ok(len) => {
+                    if is_streamed {
+                        into[..L_LEN].copy_from_slice(&(len as BatchSize).to_le_bytes());
+                    }
+                    break Ok(offset + len);
+                }
+                // Datagrams may be lost, reordered or forged: drop them and keep the link
+                Err(e) if !is_streamed => tracing::debug!("{ERR}{self}. Dropped datagram: {e}"),
+                Err(e) => break Err(zerror!("{ERR}{self}. {e}").into()),
+            }
         };
+        self.sealed = sealed;
+        res
+    }
+
+    pub async fn recv_batch<C, T>(&mut self, buff: C) -> ZResult<RBatch>
+    where
+        C: Fn() -> T + Copy,
+        T: AsMut<[u8]> + ZSliceBuffer + 'static,
+    {
+        const ERR: &str = "Read error from link: ";
+
+        let mut into = (buff)();
+        let end = zcondfeat!(
+            "transport_noise",
+            match self.cipher.clone() {
+                Some(cipher) => self.read_sealed(&cipher, into.as_mut()).await?,
+                None => self.read(into.as_mut()).await?,
+            },
+            self.read(into.as_mut()).await?
+        );
 
         // tracing::trace!("RBytes: {:02x?}", &into.as_slice()[0..end]);
 
@@ -283,7 +483,7 @@ impl MaybeOpenAck {
     }
 
     pub(crate) async fn send_open_ack(mut self) -> ZResult<()> {
-        if let Some(msg) = self.open_ack {
+        if let Some(msg) = self.open_ack.take() {
             zcondfeat!(
                 "transport_compression",
                 {
@@ -292,17 +492,29 @@ impl MaybeOpenAck {
                     // Then then we re-enable it, in case it was enabled, after the OpenAck has been sent.
                     let compression = self.link.inner.config.batch.is_compression;
                     self.link.inner.config.batch.is_compression = false;
-                    self.link.send(&msg.into()).await?;
+                    self.send(msg).await?;
                     self.link.inner.config.batch.is_compression = compression;
                 },
                 {
-                    self.link.send(&msg.into()).await?;
+                    self.send(msg).await?;
                 }
             )
         }
         Ok(())
     }
 
+    async fn send(&mut self, msg: OpenAck) -> ZResult<()> {
+        // Likewise, the OpenAck is not encrypted as the peer installs the cipher once received
+        #[cfg(feature = "transport_noise")]
+        let cipher = self.link.inner.cipher.take();
+        let res = self.link.send(&msg.into()).await;
+        #[cfg(feature = "transport_noise")]
+        {
+            self.link.inner.cipher = cipher;
+        }
+        res.map(|_| ())
+    }
+
     pub(crate) fn link(&self) -> Link {
         self.link.inner.link()
     }
@@ -22,16 +22,14 @@ use zenoh_protocol::{network::NetworkMessage, transport::TransportMessageLowLate
 use zenoh_result::{zerror, ZResult};
 
 use super::transport::TransportUnicastLowlatency;
+use crate::common::reception;
 
 /*************************************/
 /*            TRANSPORT RX           */
 /*************************************/
 impl TransportUnicastLowlatency {
-    fn trigger_callback(
-        &self,
-        #[allow(unused_mut)] // shared-memory feature requires mut
-        mut msg: NetworkMessage,
-    ) -> ZResult<()> {
+    fn trigger_callback(&self, mut msg: NetworkMessage) -> ZResult<()> {
+        reception::count_hop(&mut msg);
         let callback = zread!(self.callback).clone();
         if let Some(callback) = callback.as_ref() {
             #[cfg(feature = "shared-memory")]
@@ -79,7 +77,9 @@ impl TransportUnicastLowlatency {
                 }
                 zenoh_protocol::transport::TransportBodyLowLatency::KeepAlive(_) => {}
                 zenoh_protocol::transport::TransportBodyLowLatency::Network(msg) => {
-                    let _ = self.trigger_callback(msg);
+                    let _ = reception::receive(link.get_dst(), link.get_src(), || {
+                        self.trigger_callback(msg)
+                    });
                 }
             }
         }
@@ -37,7 +37,7 @@ use crate::{
         transport_unicast_inner::{AddLinkResult, TransportUnicastTrait},
         TransportConfigUnicast,
     },
-    TransportManager, TransportPeerEventHandler,
+    QueueOccupancy, TransportManager, TransportPeerEventHandler,
 };
 
 /*************************************/
@@ -179,6 +179,11 @@ impl TransportUnicastTrait for TransportUnicastLowlatency {
         vec![]
     }
 
+    fn get_queues(&self) -> Vec<(Link, Vec<QueueOccupancy>)> {
+        // The lowlatency transport writes directly on the link without queueing
+        vec![]
+    }
+
     fn get_zid(&self) -> ZenohIdProto {
         self.config.zid
     }
@@ -195,6 +200,8 @@ impl TransportUnicastTrait for TransportUnicastLowlatency {
         // Convert usrpwd auth id to AuthId
         #[cfg(feature = "auth_usrpwd")]
         auth_ids.push(self.config.auth_id.clone().into());
+        #[cfg(feature = "auth_jwt")]
+        auth_ids.push(self.config.jwt_id.clone().into());
         auth_ids
     }
 
@@ -13,9 +13,10 @@
 //
 use std::{
     collections::HashMap,
+    net::{IpAddr, SocketAddr},
     sync::{
         atomic::{AtomicUsize, Ordering::SeqCst},
-        Arc,
+        Arc, Mutex,
     },
     time::Duration,
 };
@@ -25,8 +26,10 @@ use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 use zenoh_config::CompressionUnicastConf;
 #[cfg(feature = "shared-memory")]
 use zenoh_config::ShmConf;
+#[cfg(feature = "transport_noise")]
+use zenoh_config::{secret, NoiseUnicastConf};
 use zenoh_config::{Config, LinkTxConf, QoSUnicastConf, TransportUnicastConf};
-use zenoh_core::{zasynclock, zcondfeat};
+use zenoh_core::{zasynclock, zcondfeat, zlock};
 use zenoh_crypto::PseudoRng;
 use zenoh_link::*;
 use zenoh_protocol::{
@@ -44,6 +47,8 @@ use super::{link::LinkUnicastWithOpenAck, transport_unicast_inner::InitTransport
 use crate::unicast::establishment::ext::auth::Auth;
 #[cfg(feature = "transport_multilink")]
 use crate::unicast::establishment::ext::multilink::MultiLink;
+#[cfg(feature = "transport_noise")]
+use crate::unicast::establishment::ext::noise;
 use crate::{
     unicast::{
         lowlatency::transport::TransportUnicastLowlatency,
@@ -63,6 +68,8 @@ pub struct TransportManagerConfigUnicast {
     pub open_timeout: Duration,
     pub accept_timeout: Duration,
     pub accept_pending: usize,
+    pub accept_pending_per_source: usize,
+    pub cookie_lifetime: Duration,
     pub max_sessions: usize,
     pub is_qos: bool,
     pub is_lowlatency: bool,
@@ -72,11 +79,17 @@ pub struct TransportManagerConfigUnicast {
     pub is_shm: bool,
     #[cfg(feature = "transport_compression")]
     pub is_compression: bool,
+    #[cfg(feature = "transport_noise")]
+    pub is_noise: bool,
+    #[cfg(feature = "transport_noise")]
+    pub noise_psk: Option<[u8; noise::PSK_SIZE]>,
 }
 
 pub struct TransportManagerStateUnicast {
     // Incoming uninitialized transports
     pub(super) incoming: Arc<AtomicUsize>,
+    // Incoming uninitialized transports per remote IP address
+    pub(super) incoming_per_source: Arc<Mutex<HashMap<IpAddr, usize>>>,
     // Established listeners
     pub(super) protocols: Arc<AsyncMutex<HashMap<String, LinkManagerUnicast>>>,
     // Established transports
@@ -109,6 +122,8 @@ pub struct TransportManagerBuilderUnicast {
     pub(super) open_timeout: Duration,
     pub(super) accept_timeout: Duration,
     pub(super) accept_pending: usize,
+    pub(super) accept_pending_per_source: usize,
+    pub(super) cookie_lifetime: Duration,
     pub(super) max_sessions: usize,
     pub(super) is_qos: bool,
     #[cfg(feature = "transport_multilink")]
@@ -120,6 +135,10 @@ pub struct TransportManagerBuilderUnicast {
     pub(super) is_lowlatency: bool,
     #[cfg(feature = "transport_compression")]
     pub(super) is_compression: bool,
+    #[cfg(feature = "transport_noise")]
+    pub(super) is_noise: bool,
+    #[cfg(feature = "transport_noise")]
+    pub(super) noise_psk: Option<[u8; noise::PSK_SIZE]>,
 }
 
 impl TransportManagerBuilderUnicast {
@@ -148,6 +167,16 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    pub fn accept_pending_per_source(mut self, accept_pending_per_source: usize) -> Self {
+        self.accept_pending_per_source = accept_pending_per_source;
+        self
+    }
+
+    pub fn cookie_lifetime(mut self, cookie_lifetime: Duration) -> Self {
+        self.cookie_lifetime = cookie_lifetime;
+        self
+    }
+
     pub fn max_sessions(mut self, max_sessions: usize) -> Self {
         self.max_sessions = max_sessions;
         self
@@ -175,6 +204,13 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    /// Sets the user-password credentials to open transports with, unless some are configured.
+    #[cfg(feature = "auth_usrpwd")]
+    pub fn default_usrpwd(mut self, user: Vec<u8>, password: Vec<u8>) -> Self {
+        self.authenticator.set_default_usrpwd(user, password);
+        self
+    }
+
     #[cfg(feature = "shared-memory")]
     pub fn shm(mut self, is_shm: bool) -> Self {
         self.is_shm = is_shm;
@@ -187,6 +223,19 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    #[cfg(feature = "transport_noise")]
+    pub fn noise(mut self, is_noise: bool) -> Self {
+        self.is_noise = is_noise;
+        self
+    }
+
+    /// Sets the secret from which the pre-shared key of the Noise handshake is derived.
+    #[cfg(feature = "transport_noise")]
+    pub fn noise_psk(mut self, secret: Option<&str>) -> Self {
+        self.noise_psk = secret.map(noise::psk);
+        self
+    }
+
     pub async fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilderUnicast> {
         self = self.lease(Duration::from_millis(
             *config.transport().link().tx().lease(),
@@ -199,6 +248,11 @@ impl TransportManagerBuilderUnicast {
             *config.transport().unicast().accept_timeout(),
         ));
         self = self.accept_pending(*config.transport().unicast().accept_pending());
+        self = self
+            .accept_pending_per_source(*config.transport().unicast().accept_pending_per_source());
+        self = self.cookie_lifetime(Duration::from_millis(
+            *config.transport().unicast().cookie_lifetime(),
+        ));
         self = self.max_sessions(*config.transport().unicast().max_sessions());
         self = self.qos(*config.transport().unicast().qos().enabled());
         self = self.lowlatency(*config.transport().unicast().lowlatency());
@@ -219,6 +273,12 @@ impl TransportManagerBuilderUnicast {
         {
             self = self.compression(*config.transport().unicast().compression().enabled());
         }
+        #[cfg(feature = "transport_noise")]
+        {
+            let noise = config.transport().unicast().noise();
+            let psk = secret::resolve_option(noise.psk().as_deref())?;
+            self = self.noise(*noise.enabled()).noise_psk(psk.as_deref());
+        }
 
         Ok(self)
     }
@@ -231,6 +291,14 @@ impl TransportManagerBuilderUnicast {
         if self.is_qos && self.is_lowlatency {
             bail!("'qos' and 'lowlatency' options are incompatible");
         }
+        #[cfg(feature = "transport_noise")]
+        if self.is_noise && self.is_lowlatency {
+            bail!("'noise' and 'lowlatency' options are incompatible");
+        }
+        #[cfg(feature = "transport_noise")]
+        if self.is_noise && self.noise_psk.is_none() {
+            bail!("'noise' requires a 'psk' for the nodes to authenticate each other");
+        }
 
         let config = TransportManagerConfigUnicast {
             lease: self.lease,
@@ -238,6 +306,8 @@ impl TransportManagerBuilderUnicast {
             open_timeout: self.open_timeout,
             accept_timeout: self.accept_timeout,
             accept_pending: self.accept_pending,
+            accept_pending_per_source: self.accept_pending_per_source,
+            cookie_lifetime: self.cookie_lifetime,
             max_sessions: self.max_sessions,
             is_qos: self.is_qos,
             #[cfg(feature = "transport_multilink")]
@@ -247,10 +317,15 @@ impl TransportManagerBuilderUnicast {
             is_lowlatency: self.is_lowlatency,
             #[cfg(feature = "transport_compression")]
             is_compression: self.is_compression,
+            #[cfg(feature = "transport_noise")]
+            is_noise: self.is_noise,
+            #[cfg(feature = "transport_noise")]
+            noise_psk: self.noise_psk,
         };
 
         let state = TransportManagerStateUnicast {
             incoming: Arc::new(AtomicUsize::new(0)),
+            incoming_per_source: Arc::new(Mutex::new(HashMap::new())),
             protocols: Arc::new(AsyncMutex::new(HashMap::new())),
             transports: Arc::new(AsyncMutex::new(HashMap::new())),
             #[cfg(feature = "transport_multilink")]
@@ -281,6 +356,8 @@ impl Default for TransportManagerBuilderUnicast {
         let shm = ShmConf::default();
         #[cfg(feature = "transport_compression")]
         let compression = CompressionUnicastConf::default();
+        #[cfg(feature = "transport_noise")]
+        let noise = NoiseUnicastConf::default();
 
         Self {
             lease: Duration::from_millis(*link_tx.lease()),
@@ -288,6 +365,8 @@ impl Default for TransportManagerBuilderUnicast {
             open_timeout: Duration::from_millis(*transport.open_timeout()),
             accept_timeout: Duration::from_millis(*transport.accept_timeout()),
             accept_pending: *transport.accept_pending(),
+            accept_pending_per_source: *transport.accept_pending_per_source(),
+            cookie_lifetime: Duration::from_millis(*transport.cookie_lifetime()),
             max_sessions: *transport.max_sessions(),
             is_qos: *qos.enabled(),
             #[cfg(feature = "transport_multilink")]
@@ -299,6 +378,10 @@ impl Default for TransportManagerBuilderUnicast {
             is_lowlatency: *transport.lowlatency(),
             #[cfg(feature = "transport_compression")]
             is_compression: *compression.enabled(),
+            #[cfg(feature = "transport_noise")]
+            is_noise: *noise.enabled(),
+            #[cfg(feature = "transport_noise")]
+            noise_psk: None,
         }
     }
 }
@@ -778,31 +861,85 @@ impl TransportManager {
             // - there is a tentative of DoS attack.
             // In both cases, let's close the link straight away with no additional notification
             tracing::trace!("Closing link for preventing potential DoS: {}", link);
+            #[cfg(feature = "stats")]
+            self.stats.inc_handshake_rejected(1);
             let _ = link.close().await;
             return;
         }
 
+        // Links coming from a single host may not take all the pending slots
+        let source = link
+            .get_dst()
+            .address()
+            .as_str()
+            .parse::<SocketAddr>()
+            .ok()
+            .map(|addr| addr.ip());
+        if let Some(ip) = source {
+            let is_admitted = {
+                let mut guard = zlock!(self.state.unicast.incoming_per_source);
+                let pending = guard.get(&ip).copied().unwrap_or(0);
+                if pending < self.config.unicast.accept_pending_per_source {
+                    guard.insert(ip, pending + 1);
+                    true
+                } else {
+                    false
+                }
+            };
+            if !is_admitted {
+                tracing::trace!(
+                    "Closing link for preventing potential DoS from {}: {}",
+                    ip,
+                    link
+                );
+                #[cfg(feature = "stats")]
+                self.stats.inc_handshake_rejected(1);
+                let _ = link.close().await;
+                return;
+            }
+        }
+
         // A new link is available
         tracing::trace!("Accepting link... {}", link);
         self.state.unicast.incoming.fetch_add(1, SeqCst);
+        #[cfg(feature = "stats")]
+        self.stats.inc_handshake_accepted(1);
 
         // Spawn a task to accept the link
         let c_manager = self.clone();
         self.task_controller
             .spawn_with_rt(zenoh_runtime::ZRuntime::Acceptor, async move {
-                if tokio::time::timeout(
+                let _is_accepted = match tokio::time::timeout(
                     c_manager.config.unicast.accept_timeout,
                     super::establishment::accept::accept_link(link, &c_manager),
                 )
                 .await
-                .is_err()
                 {
-                    tracing::debug!(
-                        "Failed to accept link before deadline ({}ms)",
-                        c_manager.config.unicast.accept_timeout.as_millis()
-                    );
+                    Ok(res) => res.is_ok(),
+                    Err(_) => {
+                        tracing::debug!(
+                            "Failed to accept link before deadline ({}ms)",
+                            c_manager.config.unicast.accept_timeout.as_millis()
+                        );
+                        false
+                    }
+                };
+                #[cfg(feature = "stats")]
+                if _is_accepted {
+                    c_manager.stats.inc_handshake_completed(1);
+                } else {
+                    c_manager.stats.inc_handshake_failed(1);
                 }
                 incoming_counter.fetch_sub(1, SeqCst);
+                if let Some(ip) = source {
+                    let mut guard = zlock!(c_manager.state.unicast.incoming_per_source);
+                    if let Some(pending) = guard.get_mut(&ip) {
+                        *pending -= 1;
+                        if *pending == 0 {
+                            guard.remove(&ip);
+                        }
+                    }
+                }
             });
     }
 }
@@ -39,10 +39,12 @@ use zenoh_protocol::{
 use zenoh_result::{zerror, ZResult};
 
 use self::transport_unicast_inner::TransportUnicastTrait;
-use super::{TransportPeer, TransportPeerEventHandler};
+use super::{QueueOccupancy, TransportPeer, TransportPeerEventHandler};
 #[cfg(feature = "shared-memory")]
 use crate::shm::TransportShmConfig;
 use crate::unicast::authentication::AuthId;
+#[cfg(feature = "auth_jwt")]
+use crate::unicast::establishment::ext::auth::JwtId;
 #[cfg(feature = "auth_usrpwd")]
 use crate::unicast::establishment::ext::auth::UsrPwdId;
 
@@ -63,6 +65,8 @@ pub(crate) struct TransportConfigUnicast {
     pub(crate) is_lowlatency: bool,
     #[cfg(feature = "auth_usrpwd")]
     pub(crate) auth_id: UsrPwdId,
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) jwt_id: JwtId,
     pub(crate) patch: PatchType,
 }
 
@@ -91,6 +95,13 @@ impl TransportUnicast {
         Ok(transport.get_whatami())
     }
 
+    /// Returns the protocol patch negotiated with the remote peer.
+    #[inline(always)]
+    pub fn get_patch(&self) -> ZResult<PatchType> {
+        let transport = self.get_inner()?;
+        Ok(transport.get_config().patch)
+    }
+
     #[cfg(feature = "shared-memory")]
     #[inline(always)]
     pub fn is_shm(&self) -> ZResult<bool> {
@@ -123,6 +134,12 @@ impl TransportUnicast {
         Ok(transport.get_links())
     }
 
+    /// Returns the current occupancy of the transmission queues of each link.
+    pub fn get_queues(&self) -> ZResult<Vec<(Link, Vec<QueueOccupancy>)>> {
+        let transport = self.get_inner()?;
+        Ok(transport.get_queues())
+    }
+
     pub fn get_auth_ids(&self) -> ZResult<Vec<AuthId>> {
         let transport = self.get_inner()?;
         Ok(transport.get_auth_ids())
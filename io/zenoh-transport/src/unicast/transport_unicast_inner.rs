@@ -27,7 +27,7 @@ use zenoh_result::ZResult;
 use super::link::{LinkUnicastWithOpenAck, MaybeOpenAck};
 use crate::{
     unicast::{link::TransportLinkUnicast, TransportConfigUnicast},
-    TransportPeerEventHandler,
+    QueueOccupancy, TransportPeerEventHandler,
 };
 
 pub(crate) type LinkError = (zenoh_result::Error, TransportLinkUnicast, u8);
@@ -63,6 +63,7 @@ pub(crate) trait TransportUnicastTrait: Send + Sync {
     fn get_whatami(&self) -> WhatAmI;
     fn get_callback(&self) -> Option<Arc<dyn TransportPeerEventHandler>>;
     fn get_links(&self) -> Vec<Link>;
+    fn get_queues(&self) -> Vec<(Link, Vec<QueueOccupancy>)>;
     fn get_auth_ids(&self) -> Vec<super::authentication::AuthId>;
     #[cfg(feature = "shared-memory")]
     fn is_shm(&self) -> bool;
@@ -19,6 +19,8 @@ use zenoh_link::Link;
 use zenoh_protocol::transport::{KeepAlive, TransportMessage};
 use zenoh_result::{zerror, ZResult};
 use zenoh_sync::{RecyclingObject, RecyclingObjectPool};
+#[cfg(feature = "trace_spans")]
+use {crate::common::batch::WBatch, zenoh_protocol::core::Priority};
 #[cfg(feature = "stats")]
 use {crate::common::stats::TransportStats, std::sync::Arc};
 
@@ -66,6 +68,10 @@ impl TransportLinkUnicastUniversal {
             wait_before_close: transport.manager.config.wait_before_close,
             batching_enabled: transport.manager.config.batching,
             batching_time_limit: transport.manager.config.queue_backoff,
+            batching_target_latency: transport.manager.config.queue_target_latency,
+            queue_occupancy_alarm: transport.manager.config.queue_occupancy_alarm,
+            queue_weights: transport.manager.config.queue_weights,
+            flight_recorder: Some(transport.manager.config.flight_recorder.clone()),
         };
 
         // The pipeline
@@ -103,6 +109,13 @@ impl TransportLinkUnicastUniversal {
 
             if let Err(e) = res {
                 tracing::debug!("TX task failed: {}", e);
+                zenoh_util::record_event!(
+                    transport.manager.config.flight_recorder,
+                    Error,
+                    "TX task failed on link {link}: {error}",
+                    link = tx.inner.link,
+                    error = e.to_string()
+                );
                 // Spawn a task to avoid a deadlock waiting for this same task
                 // to finish in the close() joining its handle
                 // TODO(yuyuan): do more study to check which ZRuntime should be used or refine the
@@ -133,6 +146,13 @@ impl TransportLinkUnicastUniversal {
             // TODO(yuyuan): improve this callback
             if let Err(e) = res {
                 tracing::debug!("RX task failed: {}", e);
+                zenoh_util::record_event!(
+                    transport.manager.config.flight_recorder,
+                    Error,
+                    "RX task failed on link {link}: {error}",
+                    link = rx.link,
+                    error = e.to_string()
+                );
 
                 // Spawn a task to avoid a deadlock waiting for this same task
                 // to finish in the close() joining its handle
@@ -171,6 +191,25 @@ impl TransportLinkUnicastUniversal {
 /*************************************/
 /*              TASKS                */
 /*************************************/
+// The span of the write of a batch on a link, carrying the range of sequence numbers of the
+// messages of the batch
+#[cfg(feature = "trace_spans")]
+fn link_write_span(
+    link: &TransportLinkUnicastTx,
+    priority: Priority,
+    batch: &WBatch,
+) -> tracing::Span {
+    let (first_sn, last_sn) = batch.sns.unzip();
+    tracing::trace_span!(
+        "zenoh::link_write",
+        %link,
+        ?priority,
+        bytes = batch.len(),
+        first_sn,
+        last_sn,
+    )
+}
+
 async fn tx_task(
     mut pipeline: TransmissionPipelineConsumer,
     link: &mut TransportLinkUnicastTx,
@@ -183,6 +222,14 @@ async fn tx_task(
             res = tokio::time::timeout(keep_alive, pipeline.pull()) => {
                 match res {
                     Ok(Some((mut batch, priority))) => {
+                        #[cfg(feature = "trace_spans")]
+                        {
+                            use tracing::Instrument;
+
+                            let span = link_write_span(link, priority, &batch);
+                            link.send_batch(&mut batch).instrument(span).await?;
+                        }
+                        #[cfg(not(feature = "trace_spans"))]
                         link.send_batch(&mut batch).await?;
 
                         #[cfg(feature = "stats")]
@@ -27,6 +27,7 @@ use crate::{
     common::{
         batch::{Decode, RBatch},
         priority::TransportChannelRx,
+        reception,
     },
     unicast::transport_unicast_inner::TransportUnicastTrait,
     TransportPeerEventHandler,
@@ -39,9 +40,9 @@ impl TransportUnicastUniversal {
     fn trigger_callback(
         &self,
         callback: &dyn TransportPeerEventHandler,
-        #[allow(unused_mut)] // shared-memory feature requires mut
         mut msg: NetworkMessage,
     ) -> ZResult<()> {
+        reception::count_hop(&mut msg);
         #[cfg(feature = "shared-memory")]
         {
             if self.config.shm.is_some() {
@@ -80,6 +81,9 @@ impl TransportUnicastUniversal {
         } = frame;
 
         let priority = ext_qos.priority();
+        #[cfg(feature = "trace_spans")]
+        let _span =
+            tracing::trace_span!("zenoh::frame_read", ?reliability, ?priority, sn).entered();
         let c = if self.is_qos() {
             &self.priority_rx[priority as usize]
         } else if priority == Priority::DEFAULT {
@@ -128,6 +132,15 @@ impl TransportUnicastUniversal {
             payload,
         } = fragment;
 
+        #[cfg(feature = "trace_spans")]
+        let _span = tracing::trace_span!(
+            "zenoh::fragment_read",
+            ?reliability,
+            priority = ?qos.priority(),
+            sn,
+            more,
+        )
+        .entered();
         let c = if self.is_qos() {
             &self.priority_rx[qos.priority() as usize]
         } else if qos.priority() == Priority::DEFAULT {
@@ -214,7 +227,11 @@ impl TransportUnicastUniversal {
         Ok(true)
     }
 
-    pub(super) fn read_messages(&self, mut batch: RBatch, link: &Link) -> ZResult<()> {
+    pub(super) fn read_messages(&self, batch: RBatch, link: &Link) -> ZResult<()> {
+        reception::receive(&link.dst, &link.src, || self.read_batch(batch, link))
+    }
+
+    fn read_batch(&self, mut batch: RBatch, link: &Link) -> ZResult<()> {
         while !batch.is_empty() {
             let msg: TransportMessage = batch
                 .decode()
@@ -39,7 +39,7 @@ use crate::{
         universal::link::TransportLinkUnicastUniversal,
         TransportConfigUnicast,
     },
-    TransportManager, TransportPeerEventHandler,
+    QueueOccupancy, TransportManager, TransportPeerEventHandler,
 };
 
 /*************************************/
@@ -382,6 +382,13 @@ impl TransportUnicastTrait for TransportUnicastUniversal {
         zread!(self.links).iter().map(|l| l.link.link()).collect()
     }
 
+    fn get_queues(&self) -> Vec<(Link, Vec<QueueOccupancy>)> {
+        zread!(self.links)
+            .iter()
+            .map(|l| (l.link.link(), l.pipeline.occupancy()))
+            .collect()
+    }
+
     fn get_auth_ids(&self) -> Vec<AuthId> {
         // Convert LinkUnicast auth ids to AuthId
         #[allow(unused_mut)]
@@ -392,6 +399,8 @@ impl TransportUnicastTrait for TransportUnicastUniversal {
         // Convert usrpwd auth id to AuthId
         #[cfg(feature = "auth_usrpwd")]
         auth_ids.push(self.config.auth_id.clone().into());
+        #[cfg(feature = "auth_jwt")]
+        auth_ids.push(self.config.jwt_id.clone().into());
         auth_ids
     }
 
@@ -13,7 +13,7 @@
 //
 use zenoh_protocol::{
     core::{Priority, PriorityRange, Reliability},
-    network::NetworkMessage,
+    network::{NetworkBody, NetworkMessage},
     transport::close,
 };
 use zenoh_result::ZResult;
@@ -21,7 +21,10 @@ use zenoh_result::ZResult;
 use super::transport::TransportUnicastUniversal;
 #[cfg(feature = "shared-memory")]
 use crate::shm::map_zmsg_to_partner;
-use crate::unicast::transport_unicast_inner::TransportUnicastTrait;
+use crate::{
+    common::pipeline::has_expired, unicast::transport_unicast_inner::TransportUnicastTrait,
+    DropReason,
+};
 
 impl TransportUnicastUniversal {
     /// Returns the index of the best matching [`Reliability`]-[`PriorityRange`] pair.
@@ -69,6 +72,21 @@ impl TransportUnicastUniversal {
         match_.full.or(match_.partial).or(match_.any)
     }
 
+    fn is_dead_letter(&self, msg: &NetworkMessage) -> bool {
+        self.manager.config.dead_letters && matches!(msg.body, NetworkBody::Push(_))
+    }
+
+    fn dead_letter(&self, msg: NetworkMessage, reason: DropReason) {
+        let callback = self
+            .callback
+            .read()
+            .expect("reading `TransportUnicastUniversal::callback` should not fail")
+            .clone();
+        if let Some(callback) = callback {
+            callback.dropped(msg, reason);
+        }
+    }
+
     fn schedule_on_link(&self, msg: NetworkMessage) -> ZResult<bool> {
         let transport_links = self
             .links
@@ -92,6 +110,15 @@ impl TransportUnicastUniversal {
                 "Message dropped because the transport has no links: {}",
                 msg
             );
+            zenoh_util::record_event!(
+                self.manager.config.flight_recorder,
+                Drop,
+                "Message dropped because the transport with {zid} has no links",
+                zid = self.config.zid
+            );
+            if self.is_dead_letter(&msg) {
+                self.dead_letter(msg, DropReason::NoLink);
+            }
 
             // No Link found
             return Ok(false);
@@ -113,12 +140,30 @@ impl TransportUnicastUniversal {
         // block for fairly long time
         drop(transport_links);
         let droppable = msg.is_droppable();
+        let deadline = msg.deadline();
+        let dead_letter = self.is_dead_letter(&msg).then(|| msg.clone());
         let push = pipeline.push_network_message(msg)?;
-        if !push && !droppable {
+        // A message dropped because it expired does not tell that the link is unresponsive
+        let expired = !push && deadline.is_some_and(has_expired);
+        if let (false, Some(msg)) = (push, dead_letter) {
+            let reason = if expired {
+                DropReason::Expired
+            } else {
+                DropReason::Congestion
+            };
+            self.dead_letter(msg, reason);
+        }
+        if !push && !droppable && !expired {
             tracing::error!(
                 "Unable to push non droppable network message to {}. Closing transport!",
                 self.config.zid
             );
+            zenoh_util::record_event!(
+                self.manager.config.flight_recorder,
+                Error,
+                "Unable to push non droppable network message to {zid}. Closing transport!",
+                zid = self.config.zid
+            );
             zenoh_runtime::ZRuntime::RX.spawn({
                 let transport = self.clone();
                 async move {
@@ -143,6 +188,9 @@ impl TransportUnicastUniversal {
         {
             if let Err(e) = map_zmsg_to_partner(&mut msg, &self.config.shm) {
                 tracing::trace!("Failed SHM conversion: {}", e);
+                if self.is_dead_letter(&msg) {
+                    self.dead_letter(msg, DropReason::Serialization);
+                }
                 return Ok(false);
             }
         }
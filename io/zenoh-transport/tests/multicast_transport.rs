@@ -262,6 +262,9 @@ mod tests {
             ext_qos: QoSType::new(channel.priority, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: NodeIdType::DEFAULT,
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: Put {
                 payload: vec![0u8; msg_size].into(),
                 timestamp: None,
@@ -270,6 +273,8 @@ mod tests {
                 #[cfg(feature = "shared-memory")]
                 ext_shm: None,
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
                 ext_unknown: vec![],
             }
             .into(),
@@ -0,0 +1,104 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "transport_tcp")]
+use std::{convert::TryFrom, sync::Arc, time::Duration};
+
+use tokio::net::TcpStream;
+use zenoh_core::ztimeout;
+use zenoh_link::EndPoint;
+use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
+use zenoh_result::ZResult;
+use zenoh_transport::{
+    multicast::TransportMulticast, unicast::TransportUnicast, DummyTransportPeerEventHandler,
+    TransportEventHandler, TransportManager, TransportMulticastEventHandler, TransportPeer,
+    TransportPeerEventHandler,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+struct SHHandshake;
+
+impl TransportEventHandler for SHHandshake {
+    fn new_unicast(
+        &self,
+        _peer: TransportPeer,
+        _transport: TransportUnicast,
+    ) -> ZResult<Arc<dyn TransportPeerEventHandler>> {
+        Ok(Arc::new(DummyTransportPeerEventHandler))
+    }
+
+    fn new_multicast(
+        &self,
+        _transport: TransportMulticast,
+    ) -> ZResult<Arc<dyn TransportMulticastEventHandler>> {
+        panic!();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn handshake_pending_per_source() {
+    zenoh_util::init_log_from_env_or("error");
+    let endpoint: EndPoint = format!("tcp/127.0.0.1:{}", 19200).parse().unwrap();
+
+    // The router admits at most 2 pending handshakes per source
+    let unicast = TransportManager::config_unicast()
+        .accept_timeout(TIMEOUT)
+        .accept_pending_per_source(2);
+    let router_manager = TransportManager::builder()
+        .whatami(WhatAmI::Router)
+        .zid(ZenohIdProto::try_from([1]).unwrap())
+        .unicast(unicast)
+        .build(Arc::new(SHHandshake))
+        .unwrap();
+    ztimeout!(router_manager.add_listener_unicast(endpoint.clone())).unwrap();
+
+    let client_manager = TransportManager::builder()
+        .whatami(WhatAmI::Client)
+        .zid(ZenohIdProto::try_from([2]).unwrap())
+        .build(Arc::new(SHHandshake))
+        .unwrap();
+
+    // Connections that never start the handshake take the slots of the source
+    let mut idle = vec![];
+    for _ in 0..2 {
+        idle.push(ztimeout!(TcpStream::connect(endpoint.address().as_str())).unwrap());
+    }
+    tokio::time::sleep(SLEEP).await;
+    let res = ztimeout!(client_manager.open_transport_unicast(endpoint.clone()));
+    assert!(res.is_err());
+    #[cfg(feature = "stats")]
+    {
+        let stats = router_manager.get_stats().report();
+        assert_eq!(stats.handshake_accepted, 2);
+        assert_eq!(stats.handshake_rejected, 1);
+    }
+
+    // Closing them frees the slots
+    drop(idle);
+    tokio::time::sleep(SLEEP).await;
+    let res = ztimeout!(client_manager.open_transport_unicast(endpoint.clone()));
+    assert!(res.is_ok());
+    #[cfg(feature = "stats")]
+    {
+        let stats = router_manager.get_stats().report();
+        assert_eq!(stats.handshake_failed, 2);
+        assert_eq!(stats.handshake_completed, 1);
+    }
+
+    ztimeout!(client_manager.close());
+    ztimeout!(router_manager.del_listener_unicast(&endpoint)).unwrap();
+    ztimeout!(router_manager.close());
+}
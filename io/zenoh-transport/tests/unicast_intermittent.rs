@@ -307,6 +307,9 @@ async fn transport_intermittent(endpoint: &EndPoint, lowlatency_transport: bool)
             ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: NodeIdType::DEFAULT,
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: Put {
                 payload: vec![0u8; MSG_SIZE].into(),
                 timestamp: None,
@@ -315,6 +318,8 @@ async fn transport_intermittent(endpoint: &EndPoint, lowlatency_transport: bool)
                 #[cfg(feature = "shared-memory")]
                 ext_shm: None,
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
                 ext_unknown: vec![],
             }
             .into(),
@@ -523,6 +523,14 @@ async fn openclose_ws_only_with_lowlatency_transport() {
     openclose_lowlatency_transport(&endpoint).await;
 }
 
+#[cfg(feature = "transport_mem")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn openclose_mem_only() {
+    zenoh_util::init_log_from_env_or("error");
+    let endpoint: EndPoint = "mem/openclose_mem_only".parse().unwrap();
+    openclose_universal_transport(&endpoint).await;
+}
+
 #[cfg(feature = "transport_unixpipe")]
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 #[ignore]
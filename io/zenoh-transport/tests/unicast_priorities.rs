@@ -289,6 +289,9 @@ async fn single_run(router_handler: Arc<SHRouter>, client_transport: TransportUn
                 ext_qos: QoSType::new(*p, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: None,
                 payload: Put {
                     payload: vec![0u8; *ms].into(),
                     timestamp: None,
@@ -297,6 +300,8 @@ async fn single_run(router_handler: Arc<SHRouter>, client_transport: TransportUn
                     #[cfg(feature = "shared-memory")]
                     ext_shm: None,
                     ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
                     ext_unknown: vec![],
                 }
                 .into(),
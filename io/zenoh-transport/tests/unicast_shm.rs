@@ -252,6 +252,9 @@ mod tests {
                 ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: None,
                 payload: Put {
                     payload: sbuf.into(),
                     timestamp: None,
@@ -259,6 +262,8 @@ mod tests {
                     ext_sinfo: None,
                     ext_shm: None,
                     ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
                     ext_unknown: vec![],
                 }
                 .into(),
@@ -293,6 +298,9 @@ mod tests {
                 ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
+                ext_hops: 0,
+                ext_hop_limit: None,
+                ext_deadline: None,
                 payload: Put {
                     payload: sbuf.into(),
                     timestamp: None,
@@ -300,6 +308,8 @@ mod tests {
                     ext_sinfo: None,
                     ext_shm: None,
                     ext_attachment: None,
+                    ext_trace: None,
+                    ext_signature: None,
                     ext_unknown: vec![],
                 }
                 .into(),
@@ -236,6 +236,7 @@ const MSG_SIZE_ALL: [usize; 2] = [1_024, 131_072];
     feature = "transport_tcp",
     feature = "transport_udp",
     feature = "transport_unixsock-stream",
+    feature = "transport_mem",
 ))]
 const MSG_SIZE_NOFRAG: [usize; 1] = [1_024];
 const MSG_SIZE_LOWLATENCY: [usize; 1] = MSG_SIZE_NOFRAG;
@@ -474,6 +475,9 @@ async fn test_transport(
         ext_qos: QoSType::new(channel.priority, cctrl, false),
         ext_tstamp: None,
         ext_nodeid: NodeIdType::DEFAULT,
+        ext_hops: 0,
+        ext_hop_limit: None,
+        ext_deadline: None,
         payload: Put {
             payload: vec![0u8; msg_size].into(),
             timestamp: None,
@@ -482,6 +486,8 @@ async fn test_transport(
             #[cfg(feature = "shared-memory")]
             ext_shm: None,
             ext_attachment: None,
+            ext_trace: None,
+            ext_signature: None,
             ext_unknown: vec![],
         }
         .into(),
@@ -861,6 +867,55 @@ async fn transport_unicast_unixpipe_only_with_lowlatency_transport() {
     run_with_lowlatency_transport(&endpoints, &endpoints, &channel, &MSG_SIZE_LOWLATENCY).await;
 }
 
+#[cfg(feature = "transport_mem")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_mem_only() {
+    zenoh_util::init_log_from_env_or("error");
+
+    // Define the locator
+    let endpoints: Vec<EndPoint> = vec![
+        "mem/transport_unicast_mem_only".parse().unwrap(),
+        "mem/transport_unicast_mem_only2".parse().unwrap(),
+    ];
+    // Define the reliability and congestion control
+    let channel = [
+        Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        },
+        Channel {
+            priority: Priority::RealTime,
+            reliability: Reliability::Reliable,
+        },
+    ];
+    // Run
+    run_with_universal_transport(&endpoints, &endpoints, &channel, &MSG_SIZE_ALL).await;
+}
+
+#[cfg(feature = "transport_mem")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_mem_only_with_lowlatency_transport() {
+    zenoh_util::init_log_from_env_or("error");
+
+    // Define the locator
+    let endpoints: Vec<EndPoint> = vec!["mem/transport_unicast_mem_only_with_lowlatency_transport"
+        .parse()
+        .unwrap()];
+    // Define the reliability and congestion control
+    let channel = [
+        Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        },
+        Channel {
+            priority: Priority::RealTime,
+            reliability: Reliability::Reliable,
+        },
+    ];
+    // Run
+    run_with_lowlatency_transport(&endpoints, &endpoints, &channel, &MSG_SIZE_LOWLATENCY).await;
+}
+
 #[cfg(all(feature = "transport_tcp", feature = "transport_udp"))]
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn transport_unicast_tcp_udp() {
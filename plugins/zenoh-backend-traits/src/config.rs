@@ -61,12 +61,25 @@ pub struct StorageConfig {
     pub name: String,
     pub key_expr: OwnedKeyExpr,
     pub complete: bool,
+    // A read-only storage does not subscribe to its key expression: it only serves queries
+    pub read_only: bool,
+    // A write-only storage does not declare a queryable: it only ingests publications
+    pub write_only: bool,
     pub strip_prefix: Option<OwnedKeyExpr>,
     pub volume_id: String,
     pub volume_cfg: Value,
     pub garbage_collection_config: GarbageCollectionConfig,
+    pub wildcard_updates: WildcardUpdatesMode,
     // Note: ReplicaConfig is optional. Alignment will be performed only if it is a replica
     pub replication: Option<ReplicaConfig>,
+    // Note: TimeSeriesConfig is optional. The history of the keys is kept only if it is set
+    pub time_series: Option<TimeSeriesConfig>,
+    // Note: ExpirationConfig is optional. The entries never expire if it is not set
+    pub expiration: Option<ExpirationConfig>,
+    // Note: QuotaConfig is optional. The storage is not limited if it is not set
+    pub quota: Option<QuotaConfig>,
+    // Note: MigrationConfig is optional. The payloads are never migrated if it is not set
+    pub migration: Option<MigrationConfig>,
 }
 // Note: All parameters should be same for replicas, else will result on huge overhead
 #[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
@@ -76,6 +89,7 @@ pub struct ReplicaConfig {
     pub hot: u64,
     pub warm: u64,
     pub propagation_delay: Duration,
+    pub alignment_bandwidth: Option<u64>,
 }
 
 impl StructVersion for VolumeConfig {
@@ -146,6 +160,13 @@ impl Default for ReplicaConfig {
             //
             // ⚠️ THIS VALUE SHOULD BE THE SAME FOR ALL REPLICAS.
             propagation_delay: Duration::from_millis(250),
+            // The maximum rate, expressed in BYTES per second, at which this replica sends
+            // alignment data to the other replicas.
+            //
+            // This value only affects the local replica and can thus differ between replicas.
+            //
+            // By default, the alignment traffic is not limited.
+            alignment_bandwidth: None,
         }
     }
 }
@@ -158,6 +179,9 @@ pub struct GarbageCollectionConfig {
     pub period: Duration,
     // The metadata older than this parameter will be garbage collected
     pub lifespan: Duration,
+    // The maximum number of wildcard puts, and of wildcard deletes, remembered by the storage:
+    // the oldest ones are garbage collected first
+    pub max_wildcard_updates: Option<usize>,
 }
 
 impl Default for GarbageCollectionConfig {
@@ -165,10 +189,113 @@ impl Default for GarbageCollectionConfig {
         Self {
             period: Duration::from_secs(30),
             lifespan: Duration::from_secs(86400),
+            max_wildcard_updates: None,
         }
     }
 }
 
+// How a storage handles the puts and deletes received on a wildcard key expression
+#[derive(JsonSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WildcardUpdatesMode {
+    // The update is applied to the existing matching keys, and remembered until garbage collected
+    // to be applied to the matching keys that are later received with an older timestamp
+    #[default]
+    Remember,
+    // The update is only applied to the matching keys existing when it is received
+    ExistingOnly,
+}
+
+// The configuration of the history kept by the storage manager for each key of a storage, to
+// answer the queries with a time range
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct TimeSeriesConfig {
+    // The maximum number of samples kept per key
+    pub max_samples: Option<usize>,
+    // The samples older than this duration are discarded
+    pub max_age: Option<Duration>,
+}
+
+// The configuration of the expiration of the entries of a storage: an entry is deleted once the
+// TTL of the first rule matching its key has elapsed since its last put
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct ExpirationConfig {
+    pub rules: Vec<ExpirationRule>,
+    // The duration between two checks of the expired entries
+    pub period: Duration,
+    // Whether a Delete sample is published when an entry expires
+    pub publish_deletes: bool,
+}
+
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct ExpirationRule {
+    pub key_expr: OwnedKeyExpr,
+    pub ttl: Duration,
+}
+
+impl ExpirationConfig {
+    /// Returns the TTL of the first rule including `key`, if any.
+    pub fn ttl(&self, key: &keyexpr) -> Option<Duration> {
+        self.rules
+            .iter()
+            .find(|rule| rule.key_expr.includes(key))
+            .map(|rule| rule.ttl)
+    }
+}
+
+// The limits on the content of a storage, and how they are enforced
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct QuotaConfig {
+    // The maximum number of entries (i.e. keys) of the storage
+    pub max_entries: Option<usize>,
+    // The maximum total size, in bytes, of the payloads of the storage
+    pub max_bytes: Option<u64>,
+    pub eviction: EvictionPolicy,
+}
+
+// What a storage does when a put would exceed its quota
+#[derive(JsonSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    // The put is rejected
+    #[default]
+    Reject,
+    // The put is stored, and the least recently used entries (put or queried) are deleted
+    Lru,
+    // The put is stored, and the entries with the oldest timestamps are deleted
+    OldestFirst,
+}
+
+// The migrations of the payloads of a storage from an old schema to a new one: the entries whose
+// key is included in the key expression of a rule are migrated by the migrator it names, which
+// must be registered with the storage manager
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct MigrationConfig {
+    pub rules: Vec<MigrationRule>,
+}
+
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct MigrationRule {
+    pub key_expr: OwnedKeyExpr,
+    pub migrator: String,
+    pub mode: MigrationMode,
+}
+
+// When the entries of a storage are migrated
+#[derive(JsonSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MigrationMode {
+    // The entries are migrated when they are read by a query
+    #[default]
+    Lazy,
+    // The entries are migrated when the storage is loaded, and when they are read by a query
+    Eager,
+}
+
+impl MigrationConfig {
+    /// Returns the first rule including `key`, if any.
+    pub fn rule(&self, key: &keyexpr) -> Option<&MigrationRule> {
+        self.rules.iter().find(|rule| rule.key_expr.includes(key))
+    }
+}
+
 #[derive(Debug)]
 pub enum ConfigDiff {
     DeleteVolume(VolumeConfig),
@@ -452,6 +579,30 @@ impl StorageConfig {
             },
             None => false,
         };
+        let read_only = match config.get("read_only") {
+            Some(Value::Bool(b)) => *b,
+            None => false,
+            _ => bail!(
+                "Invalid type for field `read_only` of storage `{}`. Only boolean values are \
+                 accepted.",
+                storage_name
+            ),
+        };
+        let write_only = match config.get("write_only") {
+            Some(Value::Bool(b)) => *b,
+            None => false,
+            _ => bail!(
+                "Invalid type for field `write_only` of storage `{}`. Only boolean values are \
+                 accepted.",
+                storage_name
+            ),
+        };
+        if read_only && write_only {
+            bail!(
+                "Storage `{}` cannot be both `read_only` and `write_only`",
+                storage_name
+            )
+        }
         let strip_prefix: Option<OwnedKeyExpr> = match config.get("strip_prefix") {
             Some(Value::String(s)) => {
                 if !key_expr.starts_with(s) {
@@ -543,6 +694,17 @@ impl StorageConfig {
                         )
                     }
                 }
+                if let Some(n) = s.get("max_wildcard_updates") {
+                    match n.to_string().parse::<usize>() {
+                        Ok(n) => garbage_collection_config.max_wildcard_updates = Some(n),
+                        _ => bail!(
+                            "Invalid value for field `max_wildcard_updates` in \
+                             `garbage_collection` of storage `{}`. Only integer values are \
+                             accepted.",
+                            storage_name
+                        ),
+                    }
+                }
                 garbage_collection_config
             }
             None => GarbageCollectionConfig::default(),
@@ -620,19 +782,286 @@ impl StorageConfig {
                         )
                     }
                 }
+                if let Some(b) = s.get("alignment_bandwidth") {
+                    match b.to_string().parse::<u64>() {
+                        Ok(b) if b > 0 => replication.alignment_bandwidth = Some(b),
+                        _ => bail!(
+                            "Invalid value for field `alignment_bandwidth` in `replica_config` of \
+                             storage `{}`. Only positive integer values are accepted.",
+                            plugin_name
+                        ),
+                    }
+                }
                 Some(replication)
             }
             None => None,
         };
+        let wildcard_updates = match config.get("wildcard_updates") {
+            Some(Value::String(s)) if s == "remember" => WildcardUpdatesMode::Remember,
+            Some(Value::String(s)) if s == "existing_only" => WildcardUpdatesMode::ExistingOnly,
+            None => WildcardUpdatesMode::Remember,
+            Some(v) => bail!(
+                "wildcard_updates='{}' of storage `{}` is not a valid value. Accepted values: \
+                 ['remember', 'existing_only']",
+                v,
+                storage_name
+            ),
+        };
+        // The alignment of the replicas relies on the remembered wildcard updates
+        if wildcard_updates == WildcardUpdatesMode::ExistingOnly && replication.is_some() {
+            bail!(
+                "Storage `{}` cannot be replicated with wildcard_updates='existing_only'",
+                storage_name
+            )
+        }
+        let time_series = match config.get("time_series") {
+            Some(s) => {
+                let mut time_series = TimeSeriesConfig {
+                    max_samples: None,
+                    max_age: None,
+                };
+                if let Some(n) = s.get("max_samples") {
+                    match n.to_string().parse::<usize>() {
+                        Ok(n) if n > 0 => time_series.max_samples = Some(n),
+                        _ => bail!(
+                            "Invalid value for field `max_samples` in `time_series` of storage \
+                             `{}`. Only positive integer values are accepted.",
+                            storage_name
+                        ),
+                    }
+                }
+                if let Some(age) = s.get("max_age") {
+                    match age.to_string().parse::<f64>() {
+                        Ok(age) if age > 0.0 => {
+                            time_series.max_age = Some(Duration::from_secs_f64(age))
+                        }
+                        _ => bail!(
+                            "Invalid value for field `max_age` in `time_series` of storage `{}`. \
+                             Expecting a positive integer or floating point number.",
+                            storage_name
+                        ),
+                    }
+                }
+                if time_series.max_samples.is_none() && time_series.max_age.is_none() {
+                    bail!(
+                        "`time_series` of storage `{}` must set at least one of `max_samples` \
+                         or `max_age`",
+                        storage_name
+                    )
+                }
+                Some(time_series)
+            }
+            None => None,
+        };
+        let expiration = match config.get("expiration") {
+            Some(s) => {
+                let mut expiration = ExpirationConfig {
+                    rules: vec![],
+                    period: Duration::from_secs(1),
+                    publish_deletes: false,
+                };
+                let Some(rules) = s.get("rules").and_then(|r| r.as_array()) else {
+                    bail!(
+                        "`expiration` of storage `{}` must have a `rules` array",
+                        storage_name
+                    )
+                };
+                for rule in rules {
+                    let rule_key_expr = match rule.get("key_expr").and_then(|k| k.as_str()) {
+                        Some(k) => match keyexpr::new(k) {
+                            Ok(k) => k.to_owned(),
+                            Err(e) => bail!(
+                                "key_expr='{}' of an `expiration` rule of storage `{}` is not a \
+                                 valid key-expression: {}",
+                                k,
+                                storage_name,
+                                e
+                            ),
+                        },
+                        None => bail!(
+                            "The `expiration` rules of storage `{}` must have a `key_expr` \
+                             string-typed field",
+                            storage_name
+                        ),
+                    };
+                    if !rule_key_expr.intersects(&key_expr) {
+                        bail!(
+                            "key_expr='{}' of an `expiration` rule of storage `{}` does not \
+                             intersect the storage's key_expr='{}'",
+                            rule_key_expr,
+                            storage_name,
+                            key_expr
+                        )
+                    }
+                    let ttl = match rule.get("ttl").map(|t| t.to_string().parse::<f64>()) {
+                        Some(Ok(ttl)) if ttl > 0.0 => Duration::from_secs_f64(ttl),
+                        _ => bail!(
+                            "Invalid value for field `ttl` of an `expiration` rule of storage \
+                             `{}`. Expecting a positive integer or floating point number.",
+                            storage_name
+                        ),
+                    };
+                    expiration.rules.push(ExpirationRule {
+                        key_expr: rule_key_expr,
+                        ttl,
+                    });
+                }
+                if let Some(period) = s.get("period") {
+                    match period.to_string().parse::<f64>() {
+                        Ok(period) if period > 0.0 => {
+                            expiration.period = Duration::from_secs_f64(period)
+                        }
+                        _ => bail!(
+                            "Invalid value for field `period` in `expiration` of storage `{}`. \
+                             Expecting a positive integer or floating point number.",
+                            storage_name
+                        ),
+                    }
+                }
+                if let Some(publish_deletes) = s.get("publish_deletes") {
+                    match publish_deletes.as_bool() {
+                        Some(publish_deletes) => expiration.publish_deletes = publish_deletes,
+                        None => bail!(
+                            "Invalid type for field `publish_deletes` in `expiration` of storage \
+                             `{}`. Only boolean values are accepted.",
+                            storage_name
+                        ),
+                    }
+                }
+                Some(expiration)
+            }
+            None => None,
+        };
+        let quota = match config.get("quota") {
+            Some(s) => {
+                let mut quota = QuotaConfig {
+                    max_entries: None,
+                    max_bytes: None,
+                    eviction: EvictionPolicy::default(),
+                };
+                if let Some(n) = s.get("max_entries") {
+                    match n.to_string().parse::<usize>() {
+                        Ok(n) if n > 0 => quota.max_entries = Some(n),
+                        _ => bail!(
+                            "Invalid value for field `max_entries` in `quota` of storage `{}`. \
+                             Only positive integer values are accepted.",
+                            storage_name
+                        ),
+                    }
+                }
+                if let Some(n) = s.get("max_bytes") {
+                    match n.to_string().parse::<u64>() {
+                        Ok(n) if n > 0 => quota.max_bytes = Some(n),
+                        _ => bail!(
+                            "Invalid value for field `max_bytes` in `quota` of storage `{}`. \
+                             Only positive integer values are accepted.",
+                            storage_name
+                        ),
+                    }
+                }
+                if quota.max_entries.is_none() && quota.max_bytes.is_none() {
+                    bail!(
+                        "`quota` of storage `{}` must set at least one of `max_entries` or \
+                         `max_bytes`",
+                        storage_name
+                    )
+                }
+                quota.eviction = match s.get("eviction") {
+                    Some(Value::String(e)) if e == "reject" => EvictionPolicy::Reject,
+                    Some(Value::String(e)) if e == "lru" => EvictionPolicy::Lru,
+                    Some(Value::String(e)) if e == "oldest_first" => EvictionPolicy::OldestFirst,
+                    None => EvictionPolicy::default(),
+                    Some(e) => bail!(
+                        "eviction='{}' in `quota` of storage `{}` is not a valid value. Accepted \
+                         values: ['reject', 'lru', 'oldest_first']",
+                        e,
+                        storage_name
+                    ),
+                };
+                Some(quota)
+            }
+            None => None,
+        };
+        let migration = match config.get("migration") {
+            Some(s) => {
+                let Some(rules) = s.get("rules").and_then(|r| r.as_array()) else {
+                    bail!(
+                        "`migration` of storage `{}` must have a `rules` array",
+                        storage_name
+                    )
+                };
+                let mut migration = MigrationConfig { rules: vec![] };
+                for rule in rules {
+                    let rule_key_expr = match rule.get("key_expr").and_then(|k| k.as_str()) {
+                        Some(k) => match keyexpr::new(k) {
+                            Ok(k) => k.to_owned(),
+                            Err(e) => bail!(
+                                "key_expr='{}' of a `migration` rule of storage `{}` is not a \
+                                 valid key-expression: {}",
+                                k,
+                                storage_name,
+                                e
+                            ),
+                        },
+                        None => bail!(
+                            "The `migration` rules of storage `{}` must have a `key_expr` \
+                             string-typed field",
+                            storage_name
+                        ),
+                    };
+                    if !rule_key_expr.intersects(&key_expr) {
+                        bail!(
+                            "key_expr='{}' of a `migration` rule of storage `{}` does not \
+                             intersect the storage's key_expr='{}'",
+                            rule_key_expr,
+                            storage_name,
+                            key_expr
+                        )
+                    }
+                    let Some(migrator) = rule.get("migrator").and_then(|m| m.as_str()) else {
+                        bail!(
+                            "The `migration` rules of storage `{}` must have a `migrator` \
+                             string-typed field",
+                            storage_name
+                        )
+                    };
+                    let mode = match rule.get("mode") {
+                        Some(Value::String(m)) if m == "lazy" => MigrationMode::Lazy,
+                        Some(Value::String(m)) if m == "eager" => MigrationMode::Eager,
+                        None => MigrationMode::default(),
+                        Some(m) => bail!(
+                            "mode='{}' of a `migration` rule of storage `{}` is not a valid \
+                             value. Accepted values: ['lazy', 'eager']",
+                            m,
+                            storage_name
+                        ),
+                    };
+                    migration.rules.push(MigrationRule {
+                        key_expr: rule_key_expr,
+                        migrator: migrator.to_string(),
+                        mode,
+                    });
+                }
+                Some(migration)
+            }
+            None => None,
+        };
         Ok(StorageConfig {
             name: storage_name.into(),
             key_expr,
             complete,
+            read_only,
+            write_only,
             strip_prefix,
             volume_id,
             volume_cfg,
             garbage_collection_config,
+            wildcard_updates,
             replication,
+            time_series,
+            expiration,
+            quota,
+            migration,
         })
     }
 }
@@ -15,9 +15,13 @@
 use std::time::Duration;
 
 use serde_json::json;
+use zenoh::key_expr::keyexpr;
 
 use super::StorageConfig;
-use crate::config::ReplicaConfig;
+use crate::config::{
+    EvictionPolicy, ExpirationConfig, ExpirationRule, MigrationMode, QuotaConfig, ReplicaConfig,
+    TimeSeriesConfig, WildcardUpdatesMode,
+};
 
 #[test]
 fn test_replica_config() {
@@ -61,6 +65,7 @@ Actual message: {err}",
             "hot": 6,
             "warm": 60,
             "propagation_delay": 250,
+            "alignment_bandwidth": 1000000,
         }
     });
     let storage_config =
@@ -72,7 +77,295 @@ Actual message: {err}",
             sub_intervals: 4,
             hot: 6,
             warm: 60,
-            propagation_delay: Duration::from_millis(250)
+            propagation_delay: Duration::from_millis(250),
+            alignment_bandwidth: Some(1_000_000),
         })
     );
 }
+
+#[test]
+fn test_time_series_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(storage_config.time_series, None);
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "time_series": {
+            "max_samples": 100,
+            "max_age": 3600,
+        }
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(
+        storage_config.time_series,
+        Some(TimeSeriesConfig {
+            max_samples: Some(100),
+            max_age: Some(Duration::from_secs(3600)),
+        })
+    );
+
+    let empty_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "time_series": {}
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &empty_config).is_err());
+
+    let invalid_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "time_series": {
+            "max_samples": 0,
+        }
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &invalid_config).is_err());
+}
+
+#[test]
+fn test_expiration_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "expiration": {
+            "rules": [
+                { "key_expr": "test/presence/**", "ttl": 30 },
+                { "key_expr": "test/**", "ttl": 0.5 },
+            ],
+            "publish_deletes": true,
+        }
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    let expiration = storage_config.expiration.unwrap();
+    assert_eq!(
+        expiration,
+        ExpirationConfig {
+            rules: vec![
+                ExpirationRule {
+                    key_expr: "test/presence/**".try_into().unwrap(),
+                    ttl: Duration::from_secs(30),
+                },
+                ExpirationRule {
+                    key_expr: "test/**".try_into().unwrap(),
+                    ttl: Duration::from_millis(500),
+                },
+            ],
+            period: Duration::from_secs(1),
+            publish_deletes: true,
+        }
+    );
+    assert_eq!(
+        expiration.ttl("test/presence/a".try_into().unwrap()),
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(
+        expiration.ttl("test/other".try_into().unwrap()),
+        Some(Duration::from_millis(500))
+    );
+
+    let not_intersecting_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "expiration": {
+            "rules": [{ "key_expr": "other/**", "ttl": 30 }],
+        }
+    });
+    assert!(
+        StorageConfig::try_from("test-plugin", "test-storage", &not_intersecting_config).is_err()
+    );
+
+    let invalid_ttl_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "expiration": {
+            "rules": [{ "key_expr": "test/**", "ttl": -1 }],
+        }
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &invalid_ttl_config).is_err());
+}
+
+#[test]
+fn test_access_mode_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert!(!storage_config.read_only);
+    assert!(!storage_config.write_only);
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "read_only": true,
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert!(storage_config.read_only);
+    assert!(!storage_config.write_only);
+
+    let both_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "read_only": true,
+        "write_only": true,
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &both_config).is_err());
+
+    let invalid_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "write_only": "yes",
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &invalid_config).is_err());
+}
+
+#[test]
+fn test_wildcard_updates_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(
+        storage_config.wildcard_updates,
+        WildcardUpdatesMode::Remember
+    );
+    assert_eq!(
+        storage_config
+            .garbage_collection_config
+            .max_wildcard_updates,
+        None
+    );
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "wildcard_updates": "existing_only",
+        "garbage_collection": {
+            "max_wildcard_updates": 100,
+        },
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(
+        storage_config.wildcard_updates,
+        WildcardUpdatesMode::ExistingOnly
+    );
+    assert_eq!(
+        storage_config
+            .garbage_collection_config
+            .max_wildcard_updates,
+        Some(100)
+    );
+
+    let replicated_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "wildcard_updates": "existing_only",
+        "replication": {},
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &replicated_config).is_err());
+
+    let invalid_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "wildcard_updates": "forget",
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &invalid_config).is_err());
+}
+
+#[test]
+fn test_quota_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "quota": {
+            "max_entries": 1000,
+            "max_bytes": 1048576,
+            "eviction": "lru",
+        }
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(
+        storage_config.quota,
+        Some(QuotaConfig {
+            max_entries: Some(1000),
+            max_bytes: Some(1048576),
+            eviction: EvictionPolicy::Lru,
+        })
+    );
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "quota": { "max_entries": 10 }
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(
+        storage_config.quota.unwrap().eviction,
+        EvictionPolicy::Reject
+    );
+
+    let empty_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "quota": {}
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &empty_config).is_err());
+
+    let invalid_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "quota": { "max_bytes": 100, "eviction": "random" }
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &invalid_config).is_err());
+}
+
+#[test]
+fn test_migration_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "migration": {
+            "rules": [
+                { "key_expr": "test/a/**", "migrator": "a_v2", "mode": "eager" },
+                { "key_expr": "test/**", "migrator": "v2" },
+            ]
+        }
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    let migration = storage_config.migration.unwrap();
+    let rule = migration.rule(keyexpr::new("test/a/b").unwrap()).unwrap();
+    assert_eq!(rule.migrator, "a_v2");
+    assert_eq!(rule.mode, MigrationMode::Eager);
+    let rule = migration.rule(keyexpr::new("test/b").unwrap()).unwrap();
+    assert_eq!(rule.migrator, "v2");
+    assert_eq!(rule.mode, MigrationMode::Lazy);
+    assert!(migration.rule(keyexpr::new("other").unwrap()).is_none());
+
+    let missing_migrator_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "migration": { "rules": [{ "key_expr": "test/**" }] }
+    });
+    assert!(
+        StorageConfig::try_from("test-plugin", "test-storage", &missing_migrator_config).is_err()
+    );
+
+    let invalid_mode_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "migration": { "rules": [{ "key_expr": "test/**", "migrator": "v2", "mode": "never" }] }
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &invalid_mode_config).is_err());
+
+    let disjoint_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "migration": { "rules": [{ "key_expr": "other/**", "migrator": "v2" }] }
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &disjoint_config).is_err());
+}
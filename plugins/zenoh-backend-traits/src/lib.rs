@@ -191,6 +191,13 @@ pub trait Volume: Send + Sync {
     /// Returns the capability of this backend
     fn get_capability(&self) -> Capability;
 
+    /// Returns the capability of a storage of this backend created with the given properties.
+    ///
+    /// Defaults to the capability of the backend, for backends whose storages all behave alike.
+    fn get_storage_capability(&self, _props: &StorageConfig) -> Capability {
+        self.get_capability()
+    }
+
     /// Creates a storage configured with some properties.
     async fn create_storage(&self, props: StorageConfig) -> ZResult<Box<dyn Storage>>;
 }
@@ -257,4 +264,23 @@ pub trait Storage: Send + Sync {
     /// The latest Timestamp corresponding to each key is either the timestamp of the delete or put whichever is the latest.
     /// Remember to fetch the entry corresponding to the `None` key
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>>;
+
+    /// Function called when a compaction of this storage is requested on the administration space.
+    /// The default implementation returns an error, for the backends not supporting compaction.
+    async fn compact(&mut self) -> ZResult<()> {
+        Err("Compaction is not supported by this storage".into())
+    }
+}
+
+/// Trait to be implemented by a migration of the payloads of a storage, from an old schema to a
+/// new one.
+///
+/// A migrator is registered with the storage manager under a name, which the `migration` rules
+/// of the storages configuration refer to. The entries matching a rule are migrated lazily, when
+/// they are read by a query, or eagerly, when the storage is loaded. If the storage only keeps
+/// the latest value of each key, the migrated data replaces the stored one, with its timestamp.
+pub trait Migrator: Send + Sync {
+    /// Returns the migrated `data` stored for `key_expr`, or `None` if it already has the new
+    /// schema. An error leaves the stored data unchanged.
+    fn migrate(&self, key_expr: &keyexpr, data: &StoredData) -> ZResult<Option<StoredData>>;
 }
@@ -42,12 +42,25 @@ const WORKER_THREAD_NUM: usize = 2;
 const MAX_BLOCK_THREAD_NUM: usize = 50;
 lazy_static::lazy_static! {
     // The global runtime is used in the dynamic plugins, which we can't get the current runtime
-    static ref TOKIO_RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_multi_thread()
-               .worker_threads(WORKER_THREAD_NUM)
-               .max_blocking_threads(MAX_BLOCK_THREAD_NUM)
-               .enable_all()
-               .build()
-               .expect("Unable to create runtime");
+    // It is created on first use and shut down when the plugin is stopped
+    static ref TOKIO_RUNTIME: Mutex<Option<tokio::runtime::Runtime>> = Mutex::new(None);
+}
+
+/// Returns a handle on the global runtime, creating it if needed.
+fn global_runtime() -> tokio::runtime::Handle {
+    TOKIO_RUNTIME
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(WORKER_THREAD_NUM)
+                .max_blocking_threads(MAX_BLOCK_THREAD_NUM)
+                .enable_all()
+                .build()
+                .expect("Unable to create runtime")
+        })
+        .handle()
+        .clone()
 }
 #[inline(always)]
 fn spawn_runtime(task: impl Future<Output = ()> + Send + 'static) {
@@ -59,7 +72,7 @@ fn spawn_runtime(task: impl Future<Output = ()> + Send + 'static) {
         }
         Err(_) => {
             // Unable to get the current runtime (dynamic plugins), spawn on the global runtime
-            TOKIO_RUNTIME.spawn(task);
+            global_runtime().spawn(task);
         }
     }
 }
@@ -113,6 +126,14 @@ impl Plugin for ExamplePlugin {
             },
         )))))
     }
+
+    // Called by zenohd when the plugin is unloaded, after its instance is dropped
+    fn stop() -> ZResult<()> {
+        // The runtime is dropped out of the lock, as it waits for its tasks and threads
+        let runtime = TOKIO_RUNTIME.lock().unwrap().take();
+        drop(runtime);
+        Ok(())
+    }
 }
 
 // An inner-state for the RunningPlugin
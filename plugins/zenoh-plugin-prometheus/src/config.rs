@@ -0,0 +1,110 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::time::Duration;
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+const DEFAULT_HTTP_INTERFACE: &str = "[::]";
+const DEFAULT_PATH: &str = "/metrics";
+const DEFAULT_SCRAPE_TIMEOUT_MS: u64 = 5000;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The port, or `<local_ip>:<port>` address, of the HTTP server.
+    #[serde(deserialize_with = "deserialize_http_port")]
+    pub http_port: String,
+    /// The path of the metrics on the HTTP server.
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// The timeout of the queries on the admin space made for each scrape.
+    #[serde(default = "default_scrape_timeout_ms")]
+    pub scrape_timeout_ms: u64,
+    #[serde(default)]
+    __path__: Option<serde_json::Value>,
+    __required__: Option<bool>,
+    __config__: Option<String>,
+    __plugin__: Option<String>,
+}
+
+impl Config {
+    pub fn scrape_timeout(&self) -> Duration {
+        Duration::from_millis(self.scrape_timeout_ms)
+    }
+}
+
+impl From<&Config> for serde_json::Value {
+    fn from(c: &Config) -> Self {
+        serde_json::to_value(c).unwrap()
+    }
+}
+
+fn default_path() -> String {
+    DEFAULT_PATH.into()
+}
+
+fn default_scrape_timeout_ms() -> u64 {
+    DEFAULT_SCRAPE_TIMEOUT_MS
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HttpPort {
+    Port(u16),
+    Address(String),
+}
+
+fn deserialize_http_port<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let address = match HttpPort::deserialize(deserializer)? {
+        HttpPort::Port(port) => return Ok(format!("{DEFAULT_HTTP_INTERFACE}:{port}")),
+        HttpPort::Address(address) => address,
+    };
+    let (interface, port) = address
+        .rsplit_once(':')
+        .unwrap_or((DEFAULT_HTTP_INTERFACE, &address));
+    if port.parse::<u16>().is_err() {
+        return Err(de::Error::invalid_value(
+            de::Unexpected::Str(&address),
+            &r#"a port number, or a string with format "<local_ip>:<port_number>""#,
+        ));
+    }
+    Ok(format!("{interface}:{port}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config() {
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 9464}"#).unwrap();
+        assert_eq!(config.http_port, format!("{DEFAULT_HTTP_INTERFACE}:9464"));
+        assert_eq!(config.path, DEFAULT_PATH);
+        assert_eq!(config.scrape_timeout(), Duration::from_secs(5));
+
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": "127.0.0.1:9464", "path": "/zenoh", "__path__": ["/lib"]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.http_port, "127.0.0.1:9464");
+        assert_eq!(config.path, "/zenoh");
+
+        assert!(serde_json::from_str::<Config>(r#"{"http_port": "localhost:port"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"http_port": 9464, "unknown": 1}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{}"#).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This crate is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tide::{Request, Response, Server, StatusCode};
+use tokio::{task::JoinHandle, time::timeout};
+use zenoh::{
+    internal::{
+        bail,
+        plugins::{RunningPluginTrait, ZenohPlugin},
+        runtime::Runtime,
+        zerror,
+    },
+    session::Session,
+    Result as ZResult,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin, PluginControl};
+
+mod config;
+pub use config::Config;
+mod metrics;
+use metrics::Metrics;
+
+const WORKER_THREAD_NUM: usize = 2;
+const MAX_BLOCK_THREAD_NUM: usize = 50;
+lazy_static::lazy_static! {
+    // The global runtime is used in the dynamic plugins, which we can't get the current runtime
+    // It is created on first use and shut down when the plugin is stopped
+    static ref TOKIO_RUNTIME: Mutex<Option<tokio::runtime::Runtime>> = Mutex::new(None);
+}
+
+/// Returns a handle on the global runtime, creating it if needed.
+fn global_runtime() -> tokio::runtime::Handle {
+    TOKIO_RUNTIME
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(WORKER_THREAD_NUM)
+                .max_blocking_threads(MAX_BLOCK_THREAD_NUM)
+                .enable_all()
+                .build()
+                .expect("Unable to create runtime")
+        })
+        .handle()
+        .clone()
+}
+
+#[inline(always)]
+fn blockon_runtime<F: Future>(task: F) -> F::Output {
+    // Check whether able to get the current runtime
+    match tokio::runtime::Handle::try_current() {
+        Ok(rt) => {
+            // Able to get the current runtime (standalone binary), use the current runtime
+            tokio::task::block_in_place(|| rt.block_on(task))
+        }
+        Err(_) => {
+            // Unable to get the current runtime (dynamic plugins), reuse the global runtime
+            tokio::task::block_in_place(|| global_runtime().block_on(task))
+        }
+    }
+}
+
+fn spawn_runtime<F>(task: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    // Check whether able to get the current runtime
+    match tokio::runtime::Handle::try_current() {
+        Ok(rt) => {
+            // Able to get the current runtime (standalone binary), spawn on the current runtime
+            rt.spawn(task)
+        }
+        Err(_) => {
+            // Unable to get the current runtime (dynamic plugins), spawn on the global runtime
+            global_runtime().spawn(task)
+        }
+    }
+}
+
+#[cfg(feature = "dynamic_plugin")]
+zenoh_plugin_trait::declare_plugin!(PrometheusPlugin);
+
+pub struct PrometheusPlugin {}
+
+impl ZenohPlugin for PrometheusPlugin {}
+
+impl Plugin for PrometheusPlugin {
+    type StartArgs = Runtime;
+    type Instance = zenoh::internal::plugins::RunningPlugin;
+    const DEFAULT_NAME: &'static str = "prometheus";
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(
+        name: &str,
+        runtime: &Self::StartArgs,
+    ) -> ZResult<zenoh::internal::plugins::RunningPlugin> {
+        // Try to initiate login.
+        // Required in case of dynamic lib, otherwise no logs.
+        // But cannot be done twice in case of static link.
+        zenoh::init_log_from_env_or("error");
+        tracing::debug!("Prometheus plugin {}", Self::PLUGIN_LONG_VERSION);
+
+        let runtime_conf = runtime.config().lock();
+        let plugin_conf = runtime_conf
+            .plugin(name)
+            .ok_or_else(|| zerror!("Plugin `{}`: missing config", name))?;
+        let conf: Config = serde_json::from_value(plugin_conf.clone())
+            .map_err(|e| zerror!("Plugin `{}` configuration error: {}", name, e))?;
+        if !*runtime_conf.adminspace.enabled() {
+            tracing::warn!(
+                "Plugin `{}`: the admin space is disabled, only the `zenoh_info` metric is available",
+                name
+            );
+        }
+        drop(runtime_conf);
+
+        let task = run(runtime.clone(), conf.clone());
+        let task =
+            blockon_runtime(async { timeout(Duration::from_millis(1), spawn_runtime(task)).await });
+
+        // The server task should block inside: if it returns immediately (for example, address
+        // already in use), the error is inside Ok
+        if let Ok(Ok(Err(e))) = task {
+            bail!("Prometheus server failed within 1ms: {e}")
+        }
+
+        Ok(Box::new(RunningPlugin))
+    }
+
+    fn stop() -> ZResult<()> {
+        // The runtime is dropped out of the lock, as it waits for its tasks and threads
+        let runtime = TOKIO_RUNTIME.lock().unwrap().take();
+        drop(runtime);
+        Ok(())
+    }
+}
+
+struct RunningPlugin;
+
+impl PluginControl for RunningPlugin {}
+
+impl RunningPluginTrait for RunningPlugin {}
+
+struct State {
+    session: Session,
+    admin_key: String,
+    whatami: String,
+    timeout: Duration,
+}
+
+impl State {
+    /// Collects the metrics of the node and of its storages from the admin space.
+    async fn scrape(&self) -> Metrics {
+        let mut metrics = Metrics::default();
+        let mut node = false;
+        for (_, status) in self.get(&format!("{}?_stats=true", self.admin_key)).await {
+            metrics.add_node(&self.whatami, &status);
+            node = true;
+        }
+        if !node {
+            // The admin space is disabled: the node is only known by its runtime
+            let zid = self.session.zid().to_string();
+            metrics.add(
+                "zenoh_info",
+                metrics::MetricType::Gauge,
+                &[("zid", &zid), ("whatami", &self.whatami)],
+                1.0,
+            );
+        }
+        let storages = format!("{}/status/plugins/*/storages/*", self.admin_key);
+        for (key, status) in self.get(&storages).await {
+            let storage = key.rsplit('/').next().unwrap_or_default();
+            metrics.add_storage(storage, &status);
+        }
+        metrics
+    }
+
+    async fn get(&self, selector: &str) -> Vec<(String, serde_json::Value)> {
+        let replies = match self.session.get(selector).timeout(self.timeout).await {
+            Ok(replies) => replies,
+            Err(e) => {
+                tracing::warn!("Prometheus plugin failed to query {}: {}", selector, e);
+                return vec![];
+            }
+        };
+        let mut results = vec![];
+        while let Ok(reply) = replies.recv_async().await {
+            let Ok(sample) = reply.result() else {
+                continue;
+            };
+            match serde_json::from_slice(&sample.payload().to_bytes()) {
+                Ok(value) => results.push((sample.key_expr().to_string(), value)),
+                Err(e) => tracing::debug!(
+                    "Prometheus plugin ignoring invalid status < {} >: {}",
+                    sample.key_expr(),
+                    e
+                ),
+            }
+        }
+        results
+    }
+}
+
+async fn scrape(req: Request<Arc<State>>) -> tide::Result<Response> {
+    let metrics = req.state().scrape().await;
+    Ok(Response::builder(StatusCode::Ok)
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.to_string())
+        .build())
+}
+
+pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
+    // Try to initiate login.
+    // Required in case of dynamic lib, otherwise no logs.
+    // But cannot be done twice in case of static link.
+    zenoh::init_log_from_env_or("error");
+
+    let state = State {
+        admin_key: format!("@/{}/{}", runtime.zid(), runtime.whatami()),
+        whatami: runtime.whatami().to_string(),
+        session: zenoh::session::init(runtime).await?,
+        timeout: conf.scrape_timeout(),
+    };
+    let mut app = Server::with_state(Arc::new(state));
+    app.at(&conf.path).get(scrape);
+
+    if let Err(e) = app.listen(conf.http_port).await {
+        tracing::error!("Unable to start http server for Prometheus: {:?}", e);
+        return Err(e.into());
+    }
+    Ok(())
+}
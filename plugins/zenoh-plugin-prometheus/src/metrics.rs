@@ -0,0 +1,286 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The conversion of the admin space statuses into Prometheus metrics.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde_json::Value;
+
+/// The sections of a storage status holding its statistics.
+const STORAGE_SECTIONS: [&str; 3] = ["garbage_collection", "quota", "replication"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MetricType {
+    Counter,
+    Gauge,
+}
+
+impl fmt::Display for MetricType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricType::Counter => write!(f, "counter"),
+            MetricType::Gauge => write!(f, "gauge"),
+        }
+    }
+}
+
+struct Family {
+    kind: MetricType,
+    samples: Vec<(String, f64)>,
+}
+
+/// A set of metrics, formatted in the Prometheus text exposition format.
+///
+/// The samples are grouped by metric name, as the format requires.
+#[derive(Default)]
+pub(crate) struct Metrics {
+    families: BTreeMap<String, Family>,
+}
+
+impl Metrics {
+    pub(crate) fn add(
+        &mut self,
+        name: &str,
+        kind: MetricType,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        let labels = labels
+            .iter()
+            .map(|(name, value)| format!("{name}=\"{}\"", escape(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.families
+            .entry(sanitize(name))
+            .or_insert_with(|| Family {
+                kind,
+                samples: vec![],
+            })
+            .samples
+            .push((labels, value));
+    }
+
+    /// Adds the metrics of the status of a zenoh node, as replied on `@/<zid>/<whatami>?_stats`.
+    pub(crate) fn add_node(&mut self, whatami: &str, status: &Value) {
+        let zid = status["zid"].as_str().unwrap_or_default();
+        let version = status["version"].as_str().unwrap_or_default();
+        self.add(
+            "zenoh_info",
+            MetricType::Gauge,
+            &[("zid", zid), ("whatami", whatami), ("version", version)],
+            1.0,
+        );
+        self.add_stats("zenoh_transport", &[], &status["stats"]);
+
+        let sessions = status["sessions"].as_array().map_or(&[][..], Vec::as_slice);
+        let mut count: BTreeMap<&str, usize> = BTreeMap::new();
+        for session in sessions {
+            let peer = session["peer"].as_str().unwrap_or_default();
+            let whatami = session["whatami"].as_str().unwrap_or_default();
+            *count.entry(whatami).or_default() += 1;
+            let labels = [("peer", peer), ("whatami", whatami)];
+            let links = session["links"].as_array().map_or(0, Vec::len);
+            self.add(
+                "zenoh_session_links",
+                MetricType::Gauge,
+                &labels,
+                links as f64,
+            );
+            self.add_stats("zenoh_session", &labels, &session["stats"]);
+
+            let Some(queues) = session["queues"].as_object() else {
+                continue;
+            };
+            for (link, queues) in queues {
+                for queue in queues.as_array().map_or(&[][..], Vec::as_slice) {
+                    let priority = match &queue["priority"] {
+                        Value::String(priority) => priority.clone(),
+                        priority => priority.to_string(),
+                    };
+                    let labels = [("peer", peer), ("link", link), ("priority", &priority)];
+                    for (name, field) in [
+                        ("zenoh_link_queue_occupied_batches", "occupied"),
+                        ("zenoh_link_queue_capacity_batches", "capacity"),
+                        ("zenoh_link_queue_alarm", "alarm"),
+                    ] {
+                        if let Some(value) = to_f64(&queue[field]) {
+                            self.add(name, MetricType::Gauge, &labels, value);
+                        }
+                    }
+                }
+            }
+        }
+        for (whatami, count) in count {
+            self.add(
+                "zenoh_sessions",
+                MetricType::Gauge,
+                &[("whatami", whatami)],
+                count as f64,
+            );
+        }
+    }
+
+    /// Adds the counters of a transport statistics report, the counters discriminating the user
+    /// and admin messages being labelled with their `space`.
+    fn add_stats(&mut self, prefix: &str, labels: &[(&str, &str)], stats: &Value) {
+        let Some(stats) = stats.as_object() else {
+            return;
+        };
+        for (field, value) in stats {
+            let name = format!("{prefix}_{field}_total");
+            match value {
+                Value::Object(spaces) => {
+                    for (space, value) in spaces {
+                        if let Some(value) = value.as_f64() {
+                            let mut labels = labels.to_vec();
+                            labels.push(("space", space));
+                            self.add(&name, MetricType::Counter, &labels, value);
+                        }
+                    }
+                }
+                value => {
+                    if let Some(value) = value.as_f64() {
+                        self.add(&name, MetricType::Counter, labels, value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds the metrics of the status of a storage of the storage manager, i.e. the numeric
+    /// fields of its statistics sections.
+    pub(crate) fn add_storage(&mut self, storage: &str, status: &Value) {
+        for section in STORAGE_SECTIONS {
+            let Some(fields) = status[section].as_object() else {
+                continue;
+            };
+            for (field, value) in fields {
+                if let Some(value) = to_f64(value) {
+                    self.add(
+                        &format!("zenoh_storage_{section}_{field}"),
+                        MetricType::Gauge,
+                        &[("storage", storage)],
+                        value,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, family) in &self.families {
+            writeln!(f, "# TYPE {name} {}", family.kind)?;
+            for (labels, value) in &family.samples {
+                match labels.is_empty() {
+                    true => writeln!(f, "{name} {value}")?,
+                    false => writeln!(f, "{name}{{{labels}}} {value}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        value => value.as_f64(),
+    }
+}
+
+/// Replaces the characters not allowed in a metric name by `_`.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | ':' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_node_metrics() {
+        let status = json!({
+            "zid": "a1b2",
+            "version": "v1.0.0",
+            "sessions": [
+                {
+                    "peer": "c3d4",
+                    "whatami": "peer",
+                    "links": ["tcp/127.0.0.1:7447"],
+                    "stats": {"tx_bytes": 10, "tx_z_put_msgs": {"user": 2, "admin": 1}},
+                    "queues": {
+                        "tcp/127.0.0.1:7447": [
+                            {"priority": "Data", "occupied": 1, "capacity": 4, "alarm": false}
+                        ]
+                    }
+                },
+                {"peer": "e5f6", "whatami": "peer", "links": []}
+            ],
+            "stats": {"tx_bytes": 30}
+        });
+        let mut metrics = Metrics::default();
+        metrics.add_node("router", &status);
+        let text = metrics.to_string();
+        for line in [
+            r#"zenoh_info{zid="a1b2",whatami="router",version="v1.0.0"} 1"#,
+            "# TYPE zenoh_transport_tx_bytes_total counter\nzenoh_transport_tx_bytes_total 30",
+            r#"zenoh_session_tx_bytes_total{peer="c3d4",whatami="peer"} 10"#,
+            r#"zenoh_session_tx_z_put_msgs_total{peer="c3d4",whatami="peer",space="user"} 2"#,
+            r#"zenoh_session_links{peer="e5f6",whatami="peer"} 0"#,
+            r#"zenoh_link_queue_occupied_batches{peer="c3d4",link="tcp/127.0.0.1:7447",priority="Data"} 1"#,
+            r#"zenoh_link_queue_alarm{peer="c3d4",link="tcp/127.0.0.1:7447",priority="Data"} 0"#,
+            r#"zenoh_sessions{whatami="peer"} 2"#,
+        ] {
+            assert!(text.contains(line), "missing `{line}` in:\n{text}");
+        }
+        // The samples of a metric are grouped under a single TYPE line
+        assert_eq!(text.matches("# TYPE zenoh_session_links gauge").count(), 1);
+    }
+
+    #[test]
+    fn test_storage_metrics() {
+        let status = json!({
+            "key_expr": "demo/**",
+            "volume": {"id": "memory", "timeout": 5},
+            "garbage_collection": {"tombstones": 3, "collected": 7},
+            "quota": {"entries": 2, "max_entries": 10, "max_bytes": null}
+        });
+        let mut metrics = Metrics::default();
+        metrics.add_storage("demo\"1", &status);
+        let text = metrics.to_string();
+        assert!(
+            text.contains(r#"zenoh_storage_garbage_collection_tombstones{storage="demo\"1"} 3"#)
+        );
+        assert!(text.contains(r#"zenoh_storage_quota_max_entries{storage="demo\"1"} 10"#));
+        assert!(!text.contains("max_bytes"));
+        assert!(!text.contains("timeout"));
+    }
+}
@@ -0,0 +1,82 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the scrape of the metrics of a runtime with its admin space enabled.
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread::sleep,
+    time::Duration,
+};
+
+use tokio::runtime::Runtime;
+use zenoh::Config;
+use zenoh_plugin_trait::Plugin;
+
+fn scrape(path: &str) -> String {
+    let mut stream = TcpStream::connect("127.0.0.1:47464").unwrap();
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+async fn test_scrape() {
+    let mut config = Config::default();
+    config
+        .insert_json5("plugins/prometheus", r#"{ http_port: "127.0.0.1:47464" }"#)
+        .unwrap();
+    config
+        .insert_json5("adminspace", r#"{ enabled: true }"#)
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let zid = runtime.zid().to_string();
+    let plugin = zenoh_plugin_prometheus::PrometheusPlugin::start("prometheus", &runtime).unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    let response = tokio::task::spawn_blocking(|| scrape("/metrics"))
+        .await
+        .unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.contains("# TYPE zenoh_info gauge"), "{response}");
+    assert!(
+        response.contains(&format!(
+            r#"zenoh_info{{zid="{zid}",whatami="peer",version="#
+        )),
+        "{response}"
+    );
+
+    let response = tokio::task::spawn_blocking(|| scrape("/unknown"))
+        .await
+        .unwrap();
+    assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+
+    drop(plugin);
+}
+
+#[test]
+fn scrape_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_scrape().await });
+}
@@ -0,0 +1,407 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The authentication and authorization of the REST clients.
+//!
+//! A client authenticates with the `Authorization` header of its requests, using either:
+//! - the `Basic` scheme, with a user and password of the `auth.users` configuration,
+//! - the `Bearer` scheme, with a token of the `auth.tokens` configuration, or with a JSON Web Token
+//!   signed with HS256 by the `auth.jwt.secret`. The username is then read from its
+//!   `auth.jwt.username_claim`. Its `exp` claim is required, and verified along with its `nbf`
+//!   claim and, if `auth.jwt.issuer` and `auth.jwt.audience` are configured, its `iss` and `aud`
+//!   claims.
+//!
+//! An authenticated request is then authorized by the `access_control` configuration of the
+//! router, as the `ingress` message it results in on the key expression of its path:
+//! `put` for PUT and PATCH, `delete` for DELETE, `declare_subscriber` for Server-Sent Events and
+//! `query` otherwise. The decision is the one of the router for a remote node authenticated with
+//! the username of the client: the rules apply to the subjects whose `usernames` contain it and
+//! which do not restrict the interfaces or certificate common names.
+//!
+//! The operations of a WebSocket connection are authorized in the same way, one by one.
+
+use std::sync::Arc;
+
+use base64::{engine::general_purpose, Engine};
+use http_types::Method;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use tide::{Middleware, Next, Request, StatusCode};
+use zenoh::{
+    config::{AclMessage, Permission},
+    internal::{bail, zerror},
+    session::Session,
+    Result as ZResult, Wait,
+};
+
+use crate::{config::AuthConfig, path_to_key_expr};
+
+pub(crate) struct Auth {
+    config: AuthConfig,
+}
+
+impl Auth {
+    pub(crate) fn new(config: AuthConfig) -> ZResult<Self> {
+        if config.users.is_empty() && config.tokens.is_empty() && config.jwt.is_none() {
+            bail!("The REST plugin `auth` configuration requires `users`, `tokens` or `jwt`");
+        }
+        Ok(Auth { config })
+    }
+
+    /// Returns the username of the client with the given `Authorization` header value.
+    fn authenticate(&self, authorization: &str) -> ZResult<String> {
+        let (scheme, credentials) = authorization
+            .split_once(' ')
+            .ok_or_else(|| zerror!("Invalid Authorization header"))?;
+        let credentials = credentials.trim();
+        if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = general_purpose::STANDARD
+                .decode(credentials)
+                .map_err(|e| zerror!("Invalid Basic credentials: {e}"))?;
+            let decoded = String::from_utf8(decoded)?;
+            let (user, password) = decoded
+                .split_once(':')
+                .ok_or_else(|| zerror!("Invalid Basic credentials"))?;
+            match self.config.users.get(user) {
+                Some(expected) if constant_time_eq(expected.as_bytes(), password.as_bytes()) => {
+                    Ok(user.to_string())
+                }
+                _ => bail!("Invalid user or password"),
+            }
+        } else if scheme.eq_ignore_ascii_case("bearer") {
+            let token = self
+                .config
+                .tokens
+                .iter()
+                .find(|(token, _)| constant_time_eq(token.as_bytes(), credentials.as_bytes()));
+            match (token, &self.config.jwt) {
+                (Some((_, username)), _) => Ok(username.clone()),
+                (None, Some(_)) if credentials.contains('.') => self.verify_jwt(credentials),
+                (None, _) => bail!("Invalid token"),
+            }
+        } else {
+            bail!("Unsupported authentication scheme '{}'", scheme)
+        }
+    }
+
+    fn verify_jwt(&self, token: &str) -> ZResult<String> {
+        let Some(jwt) = &self.config.jwt else {
+            bail!("JSON Web Tokens are not accepted");
+        };
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_nbf = true;
+        let mut required = vec!["exp"];
+        if let Some(issuer) = jwt.issuer.as_ref() {
+            validation.set_issuer(&[issuer]);
+            required.push("iss");
+        }
+        match jwt.audience.as_ref() {
+            Some(audience) => {
+                validation.set_audience(&[audience]);
+                required.push("aud");
+            }
+            None => validation.validate_aud = false,
+        }
+        validation.set_required_spec_claims(&required);
+        let key = DecodingKey::from_secret(jwt.secret.as_bytes());
+        let claims = jsonwebtoken::decode::<serde_json::Value>(token, &key, &validation)
+            .map_err(|e| zerror!("Invalid JSON Web Token: {e}"))?
+            .claims;
+        match claims[&jwt.username_claim].as_str() {
+            Some(username) if !username.is_empty() => Ok(username.to_string()),
+            _ => bail!("JSON Web Token without a '{}' claim", jwt.username_claim),
+        }
+    }
+}
+
+/// An authenticated client, set as extension of its requests.
+#[derive(Clone)]
+pub(crate) struct Principal {
+    username: String,
+}
+
+impl Principal {
+    /// Checks that the access control of `session` allows the client to send `message` on the
+    /// key expression of `path`.
+    pub(crate) fn authorize(
+        &self,
+        session: &Session,
+        message: AclMessage,
+        path: &str,
+        zid: &str,
+    ) -> ZResult<()> {
+        let key_expr = path_to_key_expr(path, zid)?;
+        let decision = session
+            .check_acl(message, &key_expr)
+            .username(self.username.as_str())
+            .wait()?;
+        if decision.permission == Permission::Deny {
+            bail!(
+                "User '{}' is not authorized to {:?} on {}",
+                self.username,
+                message,
+                key_expr
+            );
+        }
+        Ok(())
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn request_message(req: &Request<(Arc<Session>, String)>) -> AclMessage {
+    match req.method() {
+        Method::Put | Method::Patch => AclMessage::Put,
+        Method::Delete => AclMessage::Delete,
+        _ if req
+            .header("accept")
+            .is_some_and(|accept| accept[0].as_str().starts_with("text/event-stream")) =>
+        {
+            AclMessage::DeclareSubscriber
+        }
+        _ => AclMessage::Query,
+    }
+}
+
+pub(crate) struct AuthMiddleware(pub(crate) Arc<Auth>);
+
+#[tide::utils::async_trait]
+impl Middleware<(Arc<Session>, String)> for AuthMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request<(Arc<Session>, String)>,
+        next: Next<'_, (Arc<Session>, String)>,
+    ) -> tide::Result {
+        let authorization = req.header("authorization").map(|h| h.last().to_string());
+        let username = match authorization.map(|a| self.0.authenticate(&a)) {
+            Some(Ok(username)) => username,
+            result => {
+                if let Some(Err(e)) = result {
+                    tracing::debug!("REST authentication failed: {}", e);
+                }
+                let mut res = crate::response(
+                    StatusCode::Unauthorized,
+                    "text/plain",
+                    "Authentication required",
+                );
+                res.insert_header("www-authenticate", r#"Basic realm="zenoh", Bearer"#);
+                return Ok(res);
+            }
+        };
+        let principal = Principal { username };
+
+        // The operations of a WebSocket connection are authorized one by one
+        if !crate::ws::is_websocket(&req) {
+            let message = request_message(&req);
+            let (session, zid) = req.state();
+            if let Err(e) = principal.authorize(session, message, req.url().path(), zid) {
+                tracing::debug!("REST authorization failed: {}", e);
+                return Ok(crate::response(
+                    StatusCode::Forbidden,
+                    "text/plain",
+                    &e.to_string(),
+                ));
+            }
+        }
+        req.set_ext(principal);
+        Ok(next.run(req).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use jsonwebtoken::{EncodingKey, Header};
+
+    use super::*;
+
+    fn auth(jwt: serde_json::Value) -> Auth {
+        let config = serde_json::from_value(serde_json::json!({
+            "users": {"alice": "secret"},
+            "tokens": {"t0k3n": "bob"},
+            "jwt": jwt
+        }))
+        .unwrap();
+        Auth::new(config).unwrap()
+    }
+
+    fn jwt(alg: Algorithm, secret: &str, claims: serde_json::Value) -> String {
+        let key = EncodingKey::from_secret(secret.as_bytes());
+        jsonwebtoken::encode(&Header::new(alg), &claims, &key).unwrap()
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let auth = auth(serde_json::json!({"secret": "jwt-secret"}));
+        let basic = |credentials: &str| {
+            auth.authenticate(&format!(
+                "Basic {}",
+                general_purpose::STANDARD.encode(credentials)
+            ))
+        };
+        assert_eq!(basic("alice:secret").unwrap(), "alice");
+        assert!(basic("alice:wrong").is_err());
+        assert!(basic("eve:secret").is_err());
+        assert_eq!(auth.authenticate("Bearer t0k3n").unwrap(), "bob");
+        assert!(auth.authenticate("Bearer other").is_err());
+        assert!(auth.authenticate("Digest t0k3n").is_err());
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let bearer = |alg, secret, claims| {
+            auth.authenticate(&format!("Bearer {}", jwt(alg, secret, claims)))
+        };
+        let hs256 = |claims| bearer(Algorithm::HS256, "jwt-secret", claims);
+        assert_eq!(
+            hs256(serde_json::json!({"sub": "carol", "exp": now + 600})).unwrap(),
+            "carol"
+        );
+        assert!(hs256(serde_json::json!({"sub": "carol", "exp": now - 600})).is_err());
+        assert!(hs256(serde_json::json!({"sub": "carol"})).is_err());
+        assert!(
+            hs256(serde_json::json!({"sub": "carol", "exp": now + 600, "nbf": now + 300})).is_err()
+        );
+        assert!(hs256(serde_json::json!({"name": "carol", "exp": now + 600})).is_err());
+        let claims = serde_json::json!({"sub": "carol", "exp": now + 600});
+        assert!(bearer(Algorithm::HS256, "other-secret", claims.clone()).is_err());
+        // The algorithm is pinned
+        assert!(bearer(Algorithm::HS384, "jwt-secret", claims).is_err());
+
+        // The issuer and audience are verified if configured
+        let auth = self::auth(serde_json::json!({
+            "secret": "jwt-secret",
+            "issuer": "https://issuer.example.com",
+            "audience": "zenoh"
+        }));
+        let bearer = |claims| {
+            auth.authenticate(&format!(
+                "Bearer {}",
+                jwt(Algorithm::HS256, "jwt-secret", claims)
+            ))
+        };
+        assert_eq!(
+            bearer(serde_json::json!({
+                "sub": "carol",
+                "exp": now + 600,
+                "iss": "https://issuer.example.com",
+                "aud": "zenoh"
+            }))
+            .unwrap(),
+            "carol"
+        );
+        assert!(bearer(serde_json::json!({
+            "sub": "carol",
+            "exp": now + 600,
+            "iss": "https://other.example.com",
+            "aud": "zenoh"
+        }))
+        .is_err());
+        assert!(bearer(serde_json::json!({
+            "sub": "carol",
+            "exp": now + 600,
+            "iss": "https://issuer.example.com"
+        }))
+        .is_err());
+    }
+
+    fn session(acl: serde_json::Value) -> Session {
+        let mut config = zenoh::Config::default();
+        config
+            .insert_json5("scouting/multicast/enabled", "false")
+            .unwrap();
+        config
+            .insert_json5("access_control", &acl.to_string())
+            .unwrap();
+        zenoh::open(config).wait().unwrap()
+    }
+
+    #[test]
+    fn test_authorize() {
+        let session = session(serde_json::json!({
+            "enabled": true,
+            "default_permission": "deny",
+            "rules": [
+                {
+                    "id": "read",
+                    "key_exprs": ["demo/**"],
+                    "messages": ["query", "declare_subscriber", "reply"],
+                    "flows": ["ingress", "egress"],
+                    "permission": "allow"
+                },
+                {
+                    "id": "write",
+                    "key_exprs": ["demo/**"],
+                    "messages": ["put", "delete"],
+                    "flows": ["ingress"],
+                    "permission": "allow"
+                },
+                {
+                    "id": "private",
+                    "key_exprs": ["demo/private/**"],
+                    "messages": ["put", "query"],
+                    "flows": ["ingress"],
+                    "permission": "deny"
+                },
+                {
+                    "id": "lan",
+                    "key_exprs": ["lan/**"],
+                    "messages": ["put"],
+                    "flows": ["ingress"],
+                    "permission": "allow"
+                }
+            ],
+            "subjects": [
+                {"id": "readers", "usernames": ["alice", "bob"]},
+                {"id": "writers", "usernames": ["alice"]},
+                {"id": "lan", "usernames": ["alice"], "interfaces": ["lo"]}
+            ],
+            "policies": [
+                {"rules": ["read", "private"], "subjects": ["readers"]},
+                {"rules": ["write"], "subjects": ["writers"]},
+                {"rules": ["lan"], "subjects": ["lan"]}
+            ]
+        }));
+        let check = |username: &str, message, path| {
+            Principal {
+                username: username.to_string(),
+            }
+            .authorize(&session, message, path, "zid")
+            .is_ok()
+        };
+        assert!(check("alice", AclMessage::Put, "demo/a"));
+        assert!(check("alice", AclMessage::Query, "demo/a"));
+        assert!(!check("alice", AclMessage::Put, "demo/private/a"));
+        assert!(check("alice", AclMessage::Delete, "demo/private/a"));
+        assert!(!check("alice", AclMessage::Put, "other/a"));
+        assert!(check("bob", AclMessage::DeclareSubscriber, "demo/a"));
+        assert!(!check("bob", AclMessage::Put, "demo/a"));
+        assert!(!check("carol", AclMessage::Query, "demo/a"));
+        // The subjects restricted to some interfaces do not apply to the REST clients
+        assert!(!check("alice", AclMessage::Put, "lan/a"));
+        session.close().wait().unwrap();
+
+        // Without access control, all the authenticated clients are authorized
+        let session = self::session(serde_json::json!({"enabled": false}));
+        assert!(Principal {
+            username: "carol".into()
+        }
+        .authorize(&session, AclMessage::Put, "a", "zid")
+        .is_ok());
+        session.close().wait().unwrap();
+    }
+}
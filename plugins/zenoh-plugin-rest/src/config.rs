@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 use schemars::JsonSchema;
 use serde::{
@@ -33,6 +33,12 @@ pub struct Config {
     pub work_thread_num: usize,
     #[serde(default = "default_max_block_thread_num")]
     pub max_block_thread_num: usize,
+    #[serde(default, skip_serializing)]
+    pub auth: Option<AuthConfig>,
+    /// The origins, besides the origin of the REST server itself, from which the browsers may open
+    /// WebSocket connections on `/ws`.
+    #[serde(default)]
+    pub ws_allowed_origins: Vec<String>,
     #[serde(default, deserialize_with = "deserialize_path")]
     __path__: Option<Vec<String>>,
     __required__: Option<bool>,
@@ -40,6 +46,39 @@ pub struct Config {
     __plugin__: Option<String>,
 }
 
+/// The authentication of the REST clients. The requests are authorized according to the
+/// `access_control` configuration of the router, the username of a client being matched against
+/// the `usernames` of its subjects.
+#[derive(JsonSchema, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// The password of each user allowed to authenticate with the `Basic` scheme.
+    #[serde(default)]
+    pub users: HashMap<String, String>,
+    /// The username associated to each token allowed to authenticate with the `Bearer` scheme.
+    #[serde(default)]
+    pub tokens: HashMap<String, String>,
+    /// The validation of JSON Web Tokens, accepted with the `Bearer` scheme.
+    #[serde(default)]
+    pub jwt: Option<JwtConfig>,
+}
+
+#[derive(JsonSchema, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JwtConfig {
+    /// The secret of the HS256 signature of the tokens.
+    pub secret: String,
+    /// The issuer (`iss` claim) the tokens are expected to have.
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// The audience (`aud` claim) the tokens are expected to have.
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// The claim holding the username.
+    #[serde(default = "default_username_claim")]
+    pub username_claim: String,
+}
+
 impl From<&Config> for serde_json::Value {
     fn from(c: &Config) -> Self {
         serde_json::to_value(c).unwrap()
@@ -61,6 +100,10 @@ fn default_max_block_thread_num() -> usize {
     DEFAULT_MAX_BLOCK_THREAD_NUM
 }
 
+fn default_username_claim() -> String {
+    "sub".into()
+}
+
 struct HttpPortVisitor;
 
 impl Visitor<'_> for HttpPortVisitor {
@@ -217,6 +260,21 @@ mod tests {
         assert_eq!(__required__, Some(true));
     }
 
+    #[test]
+    fn test_auth_field() {
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "auth": {"users": {"alice": "pwd"}, "jwt": {"secret": "s"}}}"#,
+        )
+        .unwrap();
+        let auth = config.auth.as_ref().unwrap();
+        assert_eq!(auth.users.get("alice").unwrap(), "pwd");
+        assert!(auth.tokens.is_empty());
+        assert_eq!(auth.jwt.as_ref().unwrap().username_claim, "sub");
+
+        // The secrets are not exposed in the admin space
+        assert!(serde_json::Value::from(&config).get("auth").is_none());
+    }
+
     #[test]
     fn test_no_path_field_and_no_required_field() {
         // See: https://github.com/eclipse-zenoh/zenoh-plugin-webserver/issues/19
@@ -24,7 +24,7 @@ use std::{
     str::FromStr,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     time::Duration,
 };
@@ -51,9 +51,11 @@ use zenoh::{
 };
 use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin, PluginControl};
 
+mod auth;
 mod config;
 pub use config::Config;
 use zenoh::query::ReplyError;
+mod ws;
 
 const GIT_VERSION: &str = git_version::git_version!(prefix = "v", cargo_prefix = "v");
 lazy_static::lazy_static! {
@@ -65,12 +67,25 @@ lazy_static::lazy_static! {
     static ref WORKER_THREAD_NUM: AtomicUsize = AtomicUsize::new(config::DEFAULT_WORK_THREAD_NUM);
     static ref MAX_BLOCK_THREAD_NUM: AtomicUsize = AtomicUsize::new(config::DEFAULT_MAX_BLOCK_THREAD_NUM);
     // The global runtime is used in the dynamic plugins, which we can't get the current runtime
-    static ref TOKIO_RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_multi_thread()
-               .worker_threads(WORKER_THREAD_NUM.load(Ordering::SeqCst))
-               .max_blocking_threads(MAX_BLOCK_THREAD_NUM.load(Ordering::SeqCst))
-               .enable_all()
-               .build()
-               .expect("Unable to create runtime");
+    // It is created on first use and shut down when the plugin is stopped
+    static ref TOKIO_RUNTIME: Mutex<Option<tokio::runtime::Runtime>> = Mutex::new(None);
+}
+
+/// Returns a handle on the global runtime, creating it if needed.
+fn global_runtime() -> tokio::runtime::Handle {
+    TOKIO_RUNTIME
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(WORKER_THREAD_NUM.load(Ordering::SeqCst))
+                .max_blocking_threads(MAX_BLOCK_THREAD_NUM.load(Ordering::SeqCst))
+                .enable_all()
+                .build()
+                .expect("Unable to create runtime")
+        })
+        .handle()
+        .clone()
 }
 
 #[inline(always)]
@@ -83,7 +98,7 @@ pub(crate) fn blockon_runtime<F: Future>(task: F) -> F::Output {
         }
         Err(_) => {
             // Unable to get the current runtime (dynamic plugins), reuse the global runtime
-            tokio::task::block_in_place(|| TOKIO_RUNTIME.block_on(task))
+            tokio::task::block_in_place(|| global_runtime().block_on(task))
         }
     }
 }
@@ -101,7 +116,7 @@ where
         }
         Err(_) => {
             // Unable to get the current runtime (dynamic plugins), spawn on the global runtime
-            TOKIO_RUNTIME.spawn(task)
+            global_runtime().spawn(task)
         }
     }
 }
@@ -304,6 +319,13 @@ impl Plugin for RestPlugin {
 
         Ok(Box::new(RunningPlugin(conf)))
     }
+
+    fn stop() -> ZResult<()> {
+        // The runtime is dropped out of the lock, as it waits for its tasks and threads
+        let runtime = TOKIO_RUNTIME.lock().unwrap().take();
+        drop(runtime);
+        Ok(())
+    }
 }
 
 struct RunningPlugin(Config);
@@ -522,6 +544,10 @@ pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
     zenoh::init_log_from_env_or("error");
 
     let zid = runtime.zid().to_string();
+    let auth = match conf.auth {
+        Some(auth) => Some(Arc::new(auth::Auth::new(auth)?)),
+        None => None,
+    };
     let session = zenoh::session::init(runtime).await.unwrap();
 
     let mut app = Server::with_state((Arc::new(session), zid));
@@ -535,6 +561,9 @@ pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
             .allow_origin(tide::security::Origin::from("*"))
             .allow_credentials(false),
     );
+    if let Some(auth) = auth {
+        app.with(auth::AuthMiddleware(auth));
+    }
 
     app.at("/")
         .get(query)
@@ -542,6 +571,13 @@ pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
         .put(write)
         .patch(write)
         .delete(write);
+    let ws_allowed_origins = Arc::new(conf.ws_allowed_origins);
+    app.at("/ws")
+        .get(move |req| ws::websocket(req, ws_allowed_origins.clone()))
+        .post(query)
+        .put(write)
+        .patch(write)
+        .delete(write);
     app.at("*")
         .get(query)
         .post(query)
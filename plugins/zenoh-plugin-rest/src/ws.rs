@@ -0,0 +1,527 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The `/ws` WebSocket endpoint of the REST plugin.
+//!
+//! A client sends JSON text messages with an `op` field, and a client-chosen `id` echoed in the
+//! messages replying to it:
+//!
+//! - `{"op": "subscribe", "id": 1, "key_expr": "demo/**"}`: the received samples are sent as
+//!   `{"type": "sample", "id": 1, "kind": "PUT", "sample": {...}}`, until
+//! - `{"op": "unsubscribe", "id": 1}`, replied with `{"type": "done", "id": 1}`.
+//! - `{"op": "get", "id": 2, "selector": "demo/**?_time=[now(-1m)..]"}`, with optional `payload`
+//!   and `encoding` fields: the replies are sent as `{"type": "reply", "id": 2, "reply": {...}}`,
+//!   followed by `{"type": "done", "id": 2}`.
+//! - `{"op": "put", "id": 3, "key_expr": "demo/a", "payload": "value"}`, with an optional
+//!   `encoding` field, and `{"op": "delete", "id": 4, "key_expr": "demo/a"}`: replied with
+//!   `{"type": "done", "id": ...}`.
+//!
+//! The samples and replies are formatted as in the JSON responses of the REST API. Failures are
+//! reported as `{"type": "error", "id": ..., "message": "..."}`.
+//!
+//! If the plugin is configured with `auth`, each operation is authorized for the authenticated
+//! client of the connection, and rejected with an error message otherwise.
+//!
+//! A GET request on `/ws` that is not a WebSocket upgrade is processed as a regular query on the
+//! `ws` key expression.
+//!
+//! The upgrades sent by browsers from another origin than the server's one are refused, unless the
+//! origin is listed in `ws_allowed_origins`. The messages are queued to a client up to
+//! [`QUEUE_SIZE`], the connection of a client not keeping up with them being closed.
+
+use std::{collections::HashMap, sync::Arc};
+
+use flume::TrySendError;
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tide::{Request, Response, StatusCode};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    task::JoinHandle,
+};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+use tokio_util::{compat::FuturesAsyncReadCompatExt, sync::CancellationToken};
+use zenoh::{
+    bytes::Encoding,
+    config::AclMessage,
+    key_expr::KeyExpr,
+    query::{ConsolidationMode, Parameters, QueryConsolidation, Selector, ZenohParameters},
+    session::Session,
+    Result as ZResult,
+};
+
+use crate::{
+    auth::Principal, path_to_key_expr, result_to_json, sample_to_json, spawn_runtime, JSONSample,
+};
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+enum WsRequest {
+    Subscribe {
+        id: u64,
+        key_expr: String,
+    },
+    Unsubscribe {
+        id: u64,
+    },
+    Get {
+        id: u64,
+        selector: String,
+        #[serde(default)]
+        payload: Option<String>,
+        #[serde(default)]
+        encoding: Option<String>,
+    },
+    Put {
+        id: u64,
+        key_expr: String,
+        payload: String,
+        #[serde(default)]
+        encoding: Option<String>,
+    },
+    Delete {
+        id: u64,
+        key_expr: String,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WsResponse {
+    Sample {
+        id: u64,
+        kind: String,
+        sample: JSONSample,
+    },
+    Reply {
+        id: u64,
+        reply: JSONSample,
+    },
+    Done {
+        id: u64,
+    },
+    Error {
+        id: Option<u64>,
+        message: String,
+    },
+}
+
+impl WsRequest {
+    /// Returns the id, the ACL message and the key expression of the operation, if it accesses
+    /// the key space.
+    fn access(&self) -> Option<(u64, AclMessage, &str)> {
+        match self {
+            WsRequest::Subscribe { id, key_expr } => {
+                Some((*id, AclMessage::DeclareSubscriber, key_expr))
+            }
+            WsRequest::Unsubscribe { .. } => None,
+            WsRequest::Get { id, selector, .. } => {
+                let key_expr = selector
+                    .split_once('?')
+                    .map_or(selector.as_str(), |(k, _)| k);
+                Some((*id, AclMessage::Query, key_expr))
+            }
+            WsRequest::Put { id, key_expr, .. } => Some((*id, AclMessage::Put, key_expr)),
+            WsRequest::Delete { id, key_expr } => Some((*id, AclMessage::Delete, key_expr)),
+        }
+    }
+}
+
+impl WsResponse {
+    fn result(id: u64, result: ZResult<()>) -> Self {
+        match result {
+            Ok(()) => WsResponse::Done { id },
+            Err(e) => WsResponse::Error {
+                id: Some(id),
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+/// The number of messages queued to a client before its connection is closed.
+pub(crate) const QUEUE_SIZE: usize = 1024;
+
+/// The queue of the messages sent to a client, closing its connection when full.
+#[derive(Clone)]
+struct Responses {
+    tx: flume::Sender<WsResponse>,
+    closed: CancellationToken,
+}
+
+impl Responses {
+    /// Queues the response, returning false if the connection is closed.
+    fn send(&self, response: WsResponse) -> bool {
+        match self.tx.try_send(response) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                tracing::warn!("WebSocket client not keeping up with its messages! Terminate");
+                self.closed.cancel();
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Whether the `origin` of a request to `host`, if any, is the origin of the server or one of
+/// `allowed`.
+fn is_allowed_origin(origin: Option<&str>, host: Option<&str>, allowed: &[String]) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    if allowed.iter().any(|allowed| allowed == origin) {
+        return true;
+    }
+    let origin_host = origin.split_once("://").map_or(origin, |(_, host)| host);
+    host.is_some_and(|host| host.eq_ignore_ascii_case(origin_host))
+}
+
+/// Whether `req` is a WebSocket upgrade of the `/ws` endpoint.
+pub(crate) fn is_websocket(req: &Request<(Arc<Session>, String)>) -> bool {
+    req.url().path() == "/ws"
+        && req.method() == http_types::Method::Get
+        && req
+            .header("upgrade")
+            .is_some_and(|upgrade| upgrade.last().as_str().eq_ignore_ascii_case("websocket"))
+}
+
+pub(crate) async fn websocket(
+    req: Request<(Arc<Session>, String)>,
+    allowed_origins: Arc<Vec<String>>,
+) -> tide::Result<Response> {
+    // The other requests have been authorized as regular queries
+    if !is_websocket(&req) {
+        return crate::query(req).await;
+    }
+    tracing::trace!("Incoming WebSocket request: {:?}", req);
+    let origin = req.header("origin").map(|o| o.last().as_str());
+    let host = req.header("host").map(|h| h.last().as_str());
+    if !is_allowed_origin(origin, host, &allowed_origins) {
+        return Ok(crate::response(
+            StatusCode::Forbidden,
+            "text/plain",
+            "Origin not allowed",
+        ));
+    }
+    let Some(key) = req.header("sec-websocket-key") else {
+        return Ok(crate::response(
+            StatusCode::BadRequest,
+            "text/plain",
+            "Missing Sec-WebSocket-Key header",
+        ));
+    };
+
+    let mut res = Response::new(StatusCode::SwitchingProtocols);
+    res.insert_header("upgrade", "websocket");
+    res.insert_header("connection", "Upgrade");
+    res.insert_header(
+        "sec-websocket-accept",
+        derive_accept_key(key.last().as_str().as_bytes()),
+    );
+    let upgrade = AsMut::<http_types::Response>::as_mut(&mut res)
+        .recv_upgrade()
+        .await;
+    let (session, zid) = req.state().clone();
+    let principal = req.ext::<Principal>().cloned();
+    spawn_runtime(async move {
+        if let Some(connection) = upgrade.await {
+            let stream =
+                WebSocketStream::from_raw_socket(connection.compat(), Role::Server, None).await;
+            serve(stream, session, zid, principal).await;
+        }
+    });
+    Ok(res)
+}
+
+async fn serve<S>(
+    stream: WebSocketStream<S>,
+    session: Arc<Session>,
+    zid: String,
+    principal: Option<Principal>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (mut sink, mut stream) = stream.split();
+    let (tx, rx) = flume::bounded::<WsResponse>(QUEUE_SIZE);
+    let tx = Responses {
+        tx,
+        closed: CancellationToken::new(),
+    };
+    let closed = tx.closed.clone();
+    let writer = spawn_runtime(async move {
+        loop {
+            let response = tokio::select! {
+                response = rx.recv_async() => response,
+                _ = closed.cancelled() => break,
+            };
+            let Ok(response) = response else {
+                break;
+            };
+            let text = serde_json::to_string(&response).unwrap_or("{}".into());
+            if let Err(e) = sink.send(Message::Text(text)).await {
+                tracing::debug!("WebSocket error ({})! Terminate", e);
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    let mut subscriptions: HashMap<u64, JoinHandle<()>> = HashMap::new();
+    loop {
+        let message = tokio::select! {
+            message = stream.next() => message,
+            _ = tx.closed.cancelled() => break,
+        };
+        let Some(message) = message else {
+            break;
+        };
+        let text = match message {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::debug!("WebSocket error ({})! Terminate", e);
+                break;
+            }
+        };
+        let request = match serde_json::from_str::<WsRequest>(&text) {
+            Ok(request) => request,
+            Err(e) => {
+                tx.send(WsResponse::Error {
+                    id: None,
+                    message: format!("Invalid request: {e}"),
+                });
+                continue;
+            }
+        };
+        tracing::trace!("Incoming WebSocket message: {:?}", request);
+        if let (Some(principal), Some((id, message, key_expr))) = (&principal, request.access()) {
+            if let Err(e) = principal.authorize(&session, message, key_expr, &zid) {
+                tx.send(WsResponse::result(id, Err(e)));
+                continue;
+            }
+        }
+        match request {
+            WsRequest::Subscribe { id, key_expr } => {
+                let task = spawn_runtime(subscribe(
+                    session.clone(),
+                    zid.clone(),
+                    id,
+                    key_expr,
+                    tx.clone(),
+                ));
+                if let Some(previous) = subscriptions.insert(id, task) {
+                    previous.abort();
+                }
+            }
+            WsRequest::Unsubscribe { id } => {
+                if let Some(task) = subscriptions.remove(&id) {
+                    task.abort();
+                }
+                tx.send(WsResponse::Done { id });
+            }
+            WsRequest::Get {
+                id,
+                selector,
+                payload,
+                encoding,
+            } => {
+                let (session, zid, tx) = (session.clone(), zid.clone(), tx.clone());
+                spawn_runtime(async move {
+                    if let Err(e) = get(&session, &zid, id, &selector, payload, encoding, &tx).await
+                    {
+                        tx.send(WsResponse::result(id, Err(e)));
+                    }
+                });
+            }
+            WsRequest::Put {
+                id,
+                key_expr,
+                payload,
+                encoding,
+            } => {
+                let result = async {
+                    let key_expr = path_to_key_expr(&key_expr, &zid)?;
+                    let encoding = encoding.map(Encoding::from).unwrap_or_default();
+                    session.put(&key_expr, payload).encoding(encoding).await
+                }
+                .await;
+                tx.send(WsResponse::result(id, result));
+            }
+            WsRequest::Delete { id, key_expr } => {
+                let result = async {
+                    let key_expr = path_to_key_expr(&key_expr, &zid)?;
+                    session.delete(&key_expr).await
+                }
+                .await;
+                tx.send(WsResponse::result(id, result));
+            }
+        }
+    }
+
+    // The subscribers are undeclared when their task is aborted
+    for task in subscriptions.into_values() {
+        task.abort();
+    }
+    drop(tx);
+    let _ = writer.await;
+}
+
+async fn subscribe(session: Arc<Session>, zid: String, id: u64, key_expr: String, tx: Responses) {
+    let subscriber = match path_to_key_expr(&key_expr, &zid) {
+        Ok(key_expr) => session.declare_subscriber(key_expr).await,
+        Err(e) => Err(e),
+    };
+    let subscriber = match subscriber {
+        Ok(subscriber) => subscriber,
+        Err(e) => {
+            tx.send(WsResponse::result(id, Err(e)));
+            return;
+        }
+    };
+    tracing::debug!("Subscribe to {} for WebSocket", subscriber.key_expr());
+    while let Ok(sample) = subscriber.recv_async().await {
+        let response = WsResponse::Sample {
+            id,
+            kind: sample.kind().to_string(),
+            sample: sample_to_json(&sample),
+        };
+        if !tx.send(response) {
+            break;
+        }
+    }
+}
+
+async fn get(
+    session: &Session,
+    zid: &str,
+    id: u64,
+    selector: &str,
+    payload: Option<String>,
+    encoding: Option<String>,
+    tx: &Responses,
+) -> ZResult<()> {
+    let (key_expr, parameters) = selector.split_once('?').unwrap_or((selector, ""));
+    let key_expr: KeyExpr = path_to_key_expr(key_expr, zid)?;
+    let parameters = Parameters::from(parameters);
+    let consolidation = if parameters.time_range().is_some() {
+        QueryConsolidation::from(ConsolidationMode::None)
+    } else {
+        QueryConsolidation::from(ConsolidationMode::Latest)
+    };
+    let mut query = session
+        .get(Selector::borrowed(&key_expr, &parameters))
+        .consolidation(consolidation);
+    if let Some(payload) = payload {
+        query = query
+            .payload(payload)
+            .encoding(encoding.map(Encoding::from).unwrap_or_default());
+    }
+    let replies = query.await?;
+    while let Ok(reply) = replies.recv_async().await {
+        let reply = WsResponse::Reply {
+            id,
+            reply: result_to_json(reply.result()),
+        };
+        if !tx.send(reply) {
+            return Ok(());
+        }
+    }
+    tx.send(WsResponse::Done { id });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ws_request() {
+        assert_eq!(
+            serde_json::from_str::<WsRequest>(
+                r#"{"op": "subscribe", "id": 1, "key_expr": "demo/**"}"#
+            )
+            .unwrap(),
+            WsRequest::Subscribe {
+                id: 1,
+                key_expr: "demo/**".into()
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<WsRequest>(r#"{"op": "get", "id": 2, "selector": "demo/a"}"#)
+                .unwrap(),
+            WsRequest::Get {
+                id: 2,
+                selector: "demo/a".into(),
+                payload: None,
+                encoding: None
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<WsRequest>(
+                r#"{"op": "put", "id": 3, "key_expr": "demo/a", "payload": "1", "encoding": "text/plain"}"#
+            )
+            .unwrap(),
+            WsRequest::Put {
+                id: 3,
+                key_expr: "demo/a".into(),
+                payload: "1".into(),
+                encoding: Some("text/plain".into())
+            }
+        );
+        assert!(serde_json::from_str::<WsRequest>(r#"{"op": "put", "id": 3}"#).is_err());
+        assert!(serde_json::from_str::<WsRequest>(r#"{"op": "watch", "id": 4}"#).is_err());
+    }
+
+    #[test]
+    fn test_ws_origin() {
+        let allowed = vec!["https://example.com".to_string()];
+        assert!(is_allowed_origin(None, Some("localhost:8000"), &[]));
+        assert!(is_allowed_origin(
+            Some("http://localhost:8000"),
+            Some("localhost:8000"),
+            &[]
+        ));
+        assert!(!is_allowed_origin(
+            Some("https://attacker.com"),
+            Some("localhost:8000"),
+            &allowed
+        ));
+        assert!(is_allowed_origin(
+            Some("https://example.com"),
+            Some("localhost:8000"),
+            &allowed
+        ));
+    }
+
+    #[test]
+    fn test_ws_response() {
+        let response = serde_json::to_value(WsResponse::result(1, Ok(()))).unwrap();
+        assert_eq!(response, serde_json::json!({"type": "done", "id": 1}));
+        let response = serde_json::to_value(WsResponse::Error {
+            id: None,
+            message: "Invalid request".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            response,
+            serde_json::json!({"type": "error", "id": null, "message": "Invalid request"})
+        );
+    }
+}
@@ -0,0 +1,666 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! A volume writing the samples as line protocol to InfluxDB 3.x, or to any line-protocol
+//! endpoint, and reading them back with SQL queries.
+//!
+//! Each storage writes to a measurement of a database, with one point per sample: the key is the
+//! `key` tag (absent for the storage prefix itself), and the `kind`, `payload` (in base64),
+//! `encoding` and `timestamp` fields hold the sample. Deletions are written as `DEL` points, so
+//! that the history of the keys can still be queried by time range.
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine};
+use http_types::{mime, Method, Mime, Request, Url};
+use serde_json::{Map, Value};
+use tokio::{net::TcpStream, sync::Mutex};
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    query::{Parameters, TimeBound, TimeRange, ZenohParameters},
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{
+    config::{PrivacyGetResult, PrivacyTransparentGet, StorageConfig, VolumeConfig},
+    *,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
+
+use crate::INFLUXDB3_BACKEND_NAME;
+
+// Volume configuration
+const PROP_URL: &str = "url";
+const PROP_TOKEN: &str = "token";
+const PROP_WRITE_PATH: &str = "write_path";
+const PROP_BATCH_SIZE: &str = "batch_size";
+const PROP_BATCH_TIMEOUT: &str = "batch_timeout_ms";
+
+// Storage configuration
+const PROP_STORAGE_DB: &str = "db";
+const PROP_STORAGE_MEASUREMENT: &str = "measurement";
+
+/// The InfluxDB 3.x write endpoint. The InfluxDB 1.x compatible `/write` endpoint, which also
+/// takes a `db` parameter, can be configured instead.
+const DEFAULT_WRITE_PATH: &str = "/api/v3/write_lp";
+const QUERY_PATH: &str = "/api/v3/query_sql";
+const DEFAULT_BATCH_SIZE: usize = 1000;
+const DEFAULT_BATCH_TIMEOUT: Duration = Duration::from_millis(100);
+
+const TAG_KEY: &str = "key";
+const FIELD_KIND: &str = "kind";
+const FIELD_PAYLOAD: &str = "payload";
+const FIELD_ENCODING: &str = "encoding";
+const FIELD_TIMESTAMP: &str = "timestamp";
+const KIND_PUT: &str = "PUT";
+const KIND_DEL: &str = "DEL";
+
+pub struct InfluxDb3Backend {
+    config: VolumeConfig,
+    client: Client,
+    write_path: String,
+    batch_size: usize,
+    batch_timeout: Duration,
+}
+
+impl Plugin for InfluxDb3Backend {
+    type StartArgs = VolumeConfig;
+    type Instance = VolumeInstance;
+
+    const DEFAULT_NAME: &'static str = INFLUXDB3_BACKEND_NAME;
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let url = match args.rest.get(PROP_URL) {
+            Some(Value::String(url)) => Url::parse(url)?,
+            _ => bail!(
+                "`{}` field of volume `{}` is missing or not a string",
+                PROP_URL,
+                args.name()
+            ),
+        };
+        if url.scheme() != "http" {
+            bail!(
+                "{}='{}' of volume `{}` is not supported: only `http` URLs are",
+                PROP_URL,
+                url,
+                args.name()
+            );
+        }
+        let token = match args.rest.get_private(PROP_TOKEN) {
+            PrivacyGetResult::NotFound => None,
+            PrivacyGetResult::Private(Value::String(token))
+            | PrivacyGetResult::Public(Value::String(token))
+            | PrivacyGetResult::Both {
+                private: Value::String(token),
+                ..
+            } => Some(token.clone()),
+            _ => bail!(
+                "`{}` field of volume `{}` must be a string",
+                PROP_TOKEN,
+                args.name()
+            ),
+        };
+        let write_path = match args.rest.get(PROP_WRITE_PATH) {
+            None => DEFAULT_WRITE_PATH.to_string(),
+            Some(Value::String(path)) => path.clone(),
+            Some(_) => bail!(
+                "`{}` field of volume `{}` must be a string",
+                PROP_WRITE_PATH,
+                args.name()
+            ),
+        };
+        let positive = |key: &str| -> ZResult<Option<u64>> {
+            match args.rest.get(key) {
+                None => Ok(None),
+                Some(v) => match v.as_u64() {
+                    Some(n) if n > 0 => Ok(Some(n)),
+                    _ => bail!(
+                        "`{}` field of volume `{}` must be a positive integer",
+                        key,
+                        args.name()
+                    ),
+                },
+            }
+        };
+        let batch_size = positive(PROP_BATCH_SIZE)?.map_or(DEFAULT_BATCH_SIZE, |n| n as usize);
+        let batch_timeout =
+            positive(PROP_BATCH_TIMEOUT)?.map_or(DEFAULT_BATCH_TIMEOUT, Duration::from_millis);
+        tracing::debug!("InfluxDB 3 volume '{}' using {}", args.name(), url);
+        Ok(Box::new(InfluxDb3Backend {
+            config: args.clone(),
+            client: Client { url, token },
+            write_path,
+            batch_size,
+            batch_timeout,
+        }))
+    }
+}
+
+#[async_trait]
+impl Volume for InfluxDb3Backend {
+    fn get_admin_status(&self) -> serde_json::Value {
+        self.config.to_json_value()
+    }
+
+    fn get_capability(&self) -> Capability {
+        Capability {
+            persistence: Persistence::Durable,
+            history: History::All,
+        }
+    }
+
+    async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
+        tracing::debug!(
+            "Create InfluxDB 3 Storage with configuration: {:?}",
+            properties
+        );
+        let volume_cfg = properties.volume_cfg.as_object().ok_or_else(|| {
+            zerror!(
+                "Storage `{}` must set the `{}` of its InfluxDB database in its `volume` object",
+                properties.name,
+                PROP_STORAGE_DB
+            )
+        })?;
+        let db = match volume_cfg.get(PROP_STORAGE_DB) {
+            Some(Value::String(db)) => db.clone(),
+            _ => bail!(
+                "`volume.{}` field of storage `{}` is missing or not a string",
+                PROP_STORAGE_DB,
+                properties.name
+            ),
+        };
+        let measurement = match volume_cfg.get(PROP_STORAGE_MEASUREMENT) {
+            None => properties.name.clone(),
+            Some(Value::String(measurement)) => measurement.clone(),
+            Some(_) => bail!(
+                "`volume.{}` field of storage `{}` must be a string",
+                PROP_STORAGE_MEASUREMENT,
+                properties.name
+            ),
+        };
+
+        let writer = Arc::new(Writer {
+            client: self.client.clone(),
+            write_path: self.write_path.clone(),
+            db: db.clone(),
+            batch_size: self.batch_size,
+            lines: Mutex::new(vec![]),
+            written: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        });
+        spawn_flusher(Arc::downgrade(&writer), self.batch_timeout);
+
+        Ok(Box::new(InfluxDb3Storage {
+            config: properties,
+            client: self.client.clone(),
+            db,
+            measurement,
+            writer,
+        }))
+    }
+}
+
+impl Drop for InfluxDb3Backend {
+    fn drop(&mut self) {
+        tracing::trace!("InfluxDb3Backend::drop()");
+    }
+}
+
+/// A minimal HTTP client, opening a connection per request.
+#[derive(Clone)]
+struct Client {
+    url: Url,
+    token: Option<String>,
+}
+
+impl Client {
+    async fn post(&self, url: Url, content_type: Mime, body: Vec<u8>) -> ZResult<String> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| zerror!("No host in URL {}", url))?;
+        let port = url.port_or_known_default().unwrap_or(80);
+        let stream = TcpStream::connect((host, port)).await?;
+
+        let mut request = Request::new(Method::Post, url.clone());
+        if let Some(token) = &self.token {
+            request.insert_header("Authorization", format!("Bearer {token}").as_str());
+        }
+        request.set_content_type(content_type);
+        request.set_body(body);
+        let mut response = async_h1::connect(stream.compat(), request)
+            .await
+            .map_err(|e| zerror!("{}", e))?;
+        let body = response.body_string().await.map_err(|e| zerror!("{}", e))?;
+        if !response.status().is_success() {
+            bail!("HTTP status {}: {}", response.status(), body);
+        }
+        Ok(body)
+    }
+}
+
+/// The lines written by a storage, sent in batches of `batch_size` lines, or periodically.
+struct Writer {
+    client: Client,
+    write_path: String,
+    db: String,
+    batch_size: usize,
+    lines: Mutex<Vec<String>>,
+    written: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl Writer {
+    async fn push(&self, line: String) -> ZResult<()> {
+        let batch = {
+            let mut lines = self.lines.lock().await;
+            lines.push(line);
+            if lines.len() < self.batch_size {
+                return Ok(());
+            }
+            std::mem::take(&mut *lines)
+        };
+        self.write(batch).await
+    }
+
+    async fn flush(&self) -> ZResult<()> {
+        let batch = std::mem::take(&mut *self.lines.lock().await);
+        if batch.is_empty() {
+            return Ok(());
+        }
+        self.write(batch).await
+    }
+
+    async fn write(&self, batch: Vec<String>) -> ZResult<()> {
+        // The precision is left to its default, which is the nanosecond or inferred from the
+        // timestamps depending on the endpoint
+        let mut url = self.client.url.join(&self.write_path)?;
+        url.query_pairs_mut().append_pair("db", &self.db);
+        match self
+            .client
+            .post(url, mime::PLAIN, batch.join("\n").into_bytes())
+            .await
+        {
+            Ok(_) => {
+                self.written
+                    .fetch_add(batch.len() as u64, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(e) => {
+                self.failed.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                bail!(
+                    "Failed to write {} lines to InfluxDB database '{}': {}",
+                    batch.len(),
+                    self.db,
+                    e
+                )
+            }
+        }
+    }
+}
+
+/// Flushes the lines of `writer` every `period`, until it is dropped.
+fn spawn_flusher(writer: Weak<Writer>, period: Duration) {
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let Some(writer) = writer.upgrade() else {
+                break;
+            };
+            if let Err(e) = writer.flush().await {
+                tracing::warn!("{}", e);
+            }
+        }
+    });
+}
+
+struct InfluxDb3Storage {
+    config: StorageConfig,
+    client: Client,
+    db: String,
+    measurement: String,
+    writer: Arc<Writer>,
+}
+
+impl InfluxDb3Storage {
+    async fn query(&self, sql: String) -> ZResult<Vec<Map<String, Value>>> {
+        tracing::trace!("query {}", sql);
+        let url = self.client.url.join(QUERY_PATH)?;
+        let body = serde_json::to_vec(&serde_json::json!({
+            "db": self.db,
+            "q": sql,
+            "format": "json",
+        }))?;
+        match self.client.post(url, mime::JSON, body).await {
+            Ok(body) if body.trim().is_empty() => Ok(vec![]),
+            Ok(body) => Ok(serde_json::from_str(&body)?),
+            // Nothing was written in the database or the measurement yet
+            Err(e) if e.to_string().contains("not found") => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn select(&self, key: &Option<OwnedKeyExpr>) -> String {
+        let mut sql = format!(
+            "SELECT {FIELD_KIND}, {FIELD_PAYLOAD}, {FIELD_ENCODING}, {FIELD_TIMESTAMP} FROM {} \
+             WHERE {TAG_KEY} ",
+            quote_identifier(&self.measurement)
+        );
+        match key {
+            Some(key) => {
+                let _ = write!(sql, "= {}", quote_literal(key.as_str()));
+            }
+            None => sql.push_str("IS NULL"),
+        }
+        sql
+    }
+}
+
+/// Returns the line protocol point of a sample.
+fn line(
+    measurement: &str,
+    key: &Option<OwnedKeyExpr>,
+    kind: &str,
+    payload: &ZBytes,
+    encoding: &Encoding,
+    timestamp: &Timestamp,
+) -> String {
+    let mut line = String::new();
+    escape(&mut line, measurement, &[',', ' ']);
+    if let Some(key) = key {
+        let _ = write!(line, ",{TAG_KEY}=");
+        escape(&mut line, key.as_str(), &[',', '=', ' ']);
+    }
+    let _ = write!(
+        line,
+        " {FIELD_KIND}=\"{kind}\",{FIELD_PAYLOAD}=\"{}\",{FIELD_ENCODING}=\"",
+        general_purpose::STANDARD.encode(payload.to_bytes())
+    );
+    escape(&mut line, &encoding.to_string(), &['"', '\\']);
+    let _ = write!(
+        line,
+        "\",{FIELD_TIMESTAMP}=\"{}\" {}",
+        timestamp,
+        nanos(timestamp.get_time().to_system_time())
+    );
+    line
+}
+
+fn escape(out: &mut String, value: &str, special: &[char]) {
+    for c in value.chars() {
+        if special.contains(&c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
+
+fn nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Appends the conditions on the `time` column selecting `time_range` to a `WHERE` clause.
+fn time_conditions(sql: &mut String, time_range: &TimeRange<SystemTime>) {
+    match time_range.start {
+        TimeBound::Inclusive(t) => {
+            let _ = write!(sql, " AND time >= to_timestamp_nanos({})", nanos(t));
+        }
+        TimeBound::Exclusive(t) => {
+            let _ = write!(sql, " AND time > to_timestamp_nanos({})", nanos(t));
+        }
+        TimeBound::Unbounded => {}
+    }
+    match time_range.end {
+        TimeBound::Inclusive(t) => {
+            let _ = write!(sql, " AND time <= to_timestamp_nanos({})", nanos(t));
+        }
+        TimeBound::Exclusive(t) => {
+            let _ = write!(sql, " AND time < to_timestamp_nanos({})", nanos(t));
+        }
+        TimeBound::Unbounded => {}
+    }
+}
+
+fn column<'a>(row: &'a Map<String, Value>, name: &str) -> ZResult<&'a str> {
+    row.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| zerror!("Missing `{}` column in InfluxDB row", name).into())
+}
+
+fn parse_timestamp(timestamp: &str) -> ZResult<Timestamp> {
+    Timestamp::from_str(timestamp)
+        .map_err(|e| zerror!("Invalid timestamp '{}' in InfluxDB row: {:?}", timestamp, e).into())
+}
+
+/// Returns the data of a row, or `None` if it is a deletion.
+fn stored_data(row: &Map<String, Value>) -> ZResult<Option<StoredData>> {
+    if column(row, FIELD_KIND)? != KIND_PUT {
+        return Ok(None);
+    }
+    Ok(Some(StoredData {
+        payload: general_purpose::STANDARD
+            .decode(column(row, FIELD_PAYLOAD)?)?
+            .into(),
+        encoding: Encoding::from(column(row, FIELD_ENCODING)?.to_string()),
+        timestamp: parse_timestamp(column(row, FIELD_TIMESTAMP)?)?,
+    }))
+}
+
+#[async_trait]
+impl Storage for InfluxDb3Storage {
+    fn get_admin_status(&self) -> serde_json::Value {
+        let mut status = self.config.to_json_value();
+        if let Some(obj) = status.as_object_mut() {
+            obj.insert(
+                "written".into(),
+                self.writer.written.load(Ordering::Relaxed).into(),
+            );
+            obj.insert(
+                "failed".into(),
+                self.writer.failed.load(Ordering::Relaxed).into(),
+            );
+        }
+        status
+    }
+
+    async fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?}", key);
+        self.writer
+            .push(line(
+                &self.measurement,
+                &key,
+                KIND_PUT,
+                &payload,
+                &encoding,
+                &timestamp,
+            ))
+            .await?;
+        Ok(StorageInsertionResult::Inserted)
+    }
+
+    async fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("delete for {:?}", key);
+        self.writer
+            .push(line(
+                &self.measurement,
+                &key,
+                KIND_DEL,
+                &ZBytes::default(),
+                &Encoding::default(),
+                &timestamp,
+            ))
+            .await?;
+        Ok(StorageInsertionResult::Deleted)
+    }
+
+    async fn get(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        // The pending lines are written first, for the reads to be consistent with the writes
+        self.writer.flush().await?;
+        let mut sql = self.select(&key);
+        match Parameters::from(parameters).time_range() {
+            Some(time_range) => {
+                time_conditions(&mut sql, &time_range?.resolve());
+                sql.push_str(" ORDER BY time");
+                let rows = self.query(sql).await?;
+                rows.iter()
+                    .filter_map(|row| stored_data(row).transpose())
+                    .collect()
+            }
+            None => {
+                sql.push_str(" ORDER BY time DESC LIMIT 1");
+                let rows = self.query(sql).await?;
+                match rows.first().map(stored_data).transpose()?.flatten() {
+                    Some(data) => Ok(vec![data]),
+                    None => Err(format!("Key {:?} is not present", key).into()),
+                }
+            }
+        }
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        self.writer.flush().await?;
+        let sql = format!(
+            "SELECT {TAG_KEY}, {FIELD_KIND}, {FIELD_TIMESTAMP} FROM {} ORDER BY time",
+            quote_identifier(&self.measurement)
+        );
+        // The latest point of each key, which is `None` if it is a deletion
+        let mut latest = HashMap::new();
+        for row in self.query(sql).await? {
+            let key = match row.get(TAG_KEY).and_then(Value::as_str) {
+                Some(key) => Some(OwnedKeyExpr::try_from(key.to_string())?),
+                None => None,
+            };
+            let timestamp = match column(&row, FIELD_KIND)? {
+                KIND_PUT => Some(parse_timestamp(column(&row, FIELD_TIMESTAMP)?)?),
+                _ => None,
+            };
+            latest.insert(key, timestamp);
+        }
+        Ok(latest
+            .into_iter()
+            .filter_map(|(key, timestamp)| Some((key, timestamp?)))
+            .collect())
+    }
+}
+
+impl Drop for InfluxDb3Storage {
+    fn drop(&mut self) {
+        tracing::trace!("InfluxDb3Storage::drop()");
+        // The pending lines are still written
+        let writer = self.writer.clone();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                if let Err(e) = writer.flush().await {
+                    tracing::warn!("{}", e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zenoh::time::{TimestampId, NTP64};
+
+    use super::*;
+
+    #[test]
+    fn test_line_round_trip() {
+        let key = Some(OwnedKeyExpr::new("a b/c,d=e").unwrap());
+        let timestamp = Timestamp::new(NTP64::from(Duration::from_secs(1)), TimestampId::rand());
+        let line = line(
+            "my measurement",
+            &key,
+            KIND_PUT,
+            &ZBytes::from("value"),
+            &Encoding::TEXT_PLAIN,
+            &timestamp,
+        );
+        assert!(line.starts_with(r"my\ measurement,key=a\ b/c\,d\=e kind="));
+        assert!(line.ends_with(" 1000000000"));
+
+        let mut row = Map::new();
+        row.insert(FIELD_KIND.into(), KIND_PUT.into());
+        row.insert(
+            FIELD_PAYLOAD.into(),
+            general_purpose::STANDARD.encode("value").into(),
+        );
+        row.insert(
+            FIELD_ENCODING.into(),
+            Encoding::TEXT_PLAIN.to_string().into(),
+        );
+        row.insert(FIELD_TIMESTAMP.into(), timestamp.to_string().into());
+        let data = stored_data(&row).unwrap().unwrap();
+        assert_eq!(data.payload.try_to_string().unwrap(), "value");
+        assert_eq!(data.encoding, Encoding::TEXT_PLAIN);
+        assert_eq!(data.timestamp, timestamp);
+
+        row.insert(FIELD_KIND.into(), KIND_DEL.into());
+        assert!(stored_data(&row).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_time_conditions() {
+        let mut sql = String::new();
+        time_conditions(
+            &mut sql,
+            &TimeRange {
+                start: TimeBound::Inclusive(UNIX_EPOCH + Duration::from_secs(1)),
+                end: TimeBound::Unbounded,
+            },
+        );
+        assert_eq!(sql, " AND time >= to_timestamp_nanos(1000000000)");
+        assert_eq!(quote_literal("it's"), "'it''s'");
+    }
+}
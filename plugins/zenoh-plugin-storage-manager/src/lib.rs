@@ -48,21 +48,42 @@ use zenoh_plugin_trait::{
     plugin_long_version, plugin_version, Plugin, PluginControl, PluginReport, PluginStatusRec,
 };
 
+#[cfg(feature = "influxdb3")]
+mod influxdb3_backend;
 mod memory_backend;
 mod replication;
+pub use replication::{AlignmentStatus, ReplicaAlignment};
+#[cfg(feature = "rocksdb")]
+mod rocksdb_backend;
+#[cfg(feature = "sqlite")]
+mod sqlite_backend;
 mod storages_mgt;
+pub use storages_mgt::register_migrator;
 use storages_mgt::*;
 
 const WORKER_THREAD_NUM: usize = 2;
 const MAX_BLOCK_THREAD_NUM: usize = 50;
 lazy_static::lazy_static! {
     // The global runtime is used in the zenohd case, which we can't get the current runtime
-    static ref TOKIO_RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_multi_thread()
-               .worker_threads(WORKER_THREAD_NUM)
-               .max_blocking_threads(MAX_BLOCK_THREAD_NUM)
-               .enable_all()
-               .build()
-               .expect("Unable to create runtime");
+    // It is created on first use and shut down when the plugin is stopped
+    static ref TOKIO_RUNTIME: Mutex<Option<tokio::runtime::Runtime>> = Mutex::new(None);
+}
+
+/// Returns a handle on the global runtime, creating it if needed.
+fn global_runtime() -> tokio::runtime::Handle {
+    TOKIO_RUNTIME
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(WORKER_THREAD_NUM)
+                .max_blocking_threads(MAX_BLOCK_THREAD_NUM)
+                .enable_all()
+                .build()
+                .expect("Unable to create runtime")
+        })
+        .handle()
+        .clone()
 }
 
 #[cfg(feature = "dynamic_plugin")]
@@ -88,6 +109,13 @@ impl Plugin for StoragesPlugin {
             config,
         )?)))
     }
+
+    fn stop() -> ZResult<()> {
+        // The runtime is dropped out of the lock, as it waits for its tasks and threads
+        let runtime = TOKIO_RUNTIME.lock().unwrap().take();
+        drop(runtime);
+        Ok(())
+    }
 }
 
 type PluginsManager = zenoh_plugin_trait::PluginsManager<VolumeConfig, VolumeInstance>;
@@ -125,6 +153,21 @@ impl StorageRuntimeInner {
 
         let mut plugins_manager = PluginsManager::dynamic(lib_loader.clone(), BACKEND_LIB_PREFIX);
         plugins_manager.declare_static_plugin::<MemoryBackend, &str>(MEMORY_BACKEND_NAME, true);
+        #[cfg(feature = "rocksdb")]
+        plugins_manager.declare_static_plugin::<rocksdb_backend::RocksDbBackend, &str>(
+            ROCKSDB_BACKEND_NAME,
+            true,
+        );
+        #[cfg(feature = "sqlite")]
+        plugins_manager.declare_static_plugin::<sqlite_backend::SqliteBackend, &str>(
+            SQLITE_BACKEND_NAME,
+            true,
+        );
+        #[cfg(feature = "influxdb3")]
+        plugins_manager.declare_static_plugin::<influxdb3_backend::InfluxDb3Backend, &str>(
+            INFLUXDB3_BACKEND_NAME,
+            true,
+        );
 
         let session = Arc::new(zenoh::session::init(runtime.clone()).wait()?);
 
@@ -204,7 +247,7 @@ impl StorageRuntimeInner {
         tracing::info!("Killing volume '{}'", name);
         if let Some(storages) = self.storages.remove(name) {
             tokio::task::block_in_place(|| {
-                TOKIO_RUNTIME.block_on(futures::future::join_all(
+                global_runtime().block_on(futures::future::join_all(
                     storages
                         .into_values()
                         .map(|s| async move { s.send(StorageMessage::Stop) }),
@@ -278,7 +321,7 @@ impl StorageRuntimeInner {
             backend.name()
         );
         let stopper = tokio::task::block_in_place(|| {
-            TOKIO_RUNTIME.block_on(create_and_start_storage(
+            global_runtime().block_on(create_and_start_storage(
                 admin_key,
                 storage.clone(),
                 backend.instance(),
@@ -373,7 +416,7 @@ impl RunningPluginTrait for StorageRuntime {
                     with_extended_string(key, &[storage], |key| {
                         if keyexpr::new(key.as_str()).unwrap().intersects(key_expr) {
                             if let Some(value) = tokio::task::block_in_place(|| {
-                                TOKIO_RUNTIME.block_on(async {
+                                global_runtime().block_on(async {
                                     let (tx, mut rx) = tokio::sync::mpsc::channel(1);
                                     let _ = handle.send(StorageMessage::GetStatus(tx));
                                     rx.recv().await
@@ -392,6 +435,12 @@ impl RunningPluginTrait for StorageRuntime {
 
 const BACKEND_LIB_PREFIX: &str = "zenoh_backend_";
 const MEMORY_BACKEND_NAME: &str = "memory";
+#[cfg(feature = "rocksdb")]
+const ROCKSDB_BACKEND_NAME: &str = "rocksdb";
+#[cfg(feature = "sqlite")]
+const SQLITE_BACKEND_NAME: &str = "sqlite";
+#[cfg(feature = "influxdb3")]
+const INFLUXDB3_BACKEND_NAME: &str = "influxdb3";
 
 fn with_extended_string<R, F: FnMut(&mut String) -> R>(
     prefix: &mut String,
@@ -154,6 +154,11 @@ impl Storage for MemoryStorage {
         }
         Ok(result)
     }
+
+    async fn compact(&mut self) -> ZResult<()> {
+        // nothing to compact in memory
+        Ok(())
+    }
 }
 
 impl Drop for MemoryStorage {
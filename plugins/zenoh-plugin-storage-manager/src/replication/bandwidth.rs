@@ -0,0 +1,69 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+/// The [BandwidthLimiter] caps the rate at which a Replica sends alignment data.
+///
+/// Each emission of `n` bytes reserves `n / bytes_per_second` seconds of the link: an emission
+/// has to wait until all the previous reservations have elapsed.
+#[derive(Debug)]
+pub(crate) struct BandwidthLimiter {
+    bytes_per_second: u64,
+    next_available: Mutex<Instant>,
+}
+
+impl BandwidthLimiter {
+    pub(crate) fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+            next_available: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until `bytes` can be sent without exceeding the configured bandwidth.
+    pub(crate) async fn acquire(&self, bytes: usize) {
+        let start = {
+            let mut next_available = match self.next_available.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let start = (*next_available).max(Instant::now());
+            *next_available =
+                start + Duration::from_secs_f64(bytes as f64 / self.bytes_per_second as f64);
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bandwidth_limiter() {
+        let limiter = BandwidthLimiter::new(10_000);
+        let start = Instant::now();
+
+        limiter.acquire(500).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        limiter.acquire(1_000).await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        limiter.acquire(10).await;
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+}
@@ -36,7 +36,10 @@ use zenoh::{
 };
 
 use self::aligner_reply::AlignmentReply;
-use super::{digest::Digest, log::LogLatest, Action, Event, LogLatestKey};
+use super::{
+    bandwidth::BandwidthLimiter, digest::Digest, log::LogLatest, Action, Event, LogLatestKey,
+    ReplicationMetrics,
+};
 use crate::{
     replication::core::aligner_query::AlignmentQuery,
     storages_mgt::{LatestUpdates, StorageService},
@@ -54,6 +57,8 @@ pub(crate) struct Replication {
     pub(crate) storage_key_expr: OwnedKeyExpr,
     pub(crate) latest_updates: Arc<RwLock<LatestUpdates>>,
     pub(crate) storage_service: Arc<StorageService>,
+    pub(crate) metrics: Arc<ReplicationMetrics>,
+    pub(crate) bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
 }
 
 impl Replication {
@@ -255,7 +260,10 @@ impl Replication {
                     )
                     .await
                 {
-                    Ok(_) => tracing::trace!("Published Digest: {digest:?}"),
+                    Ok(_) => {
+                        tracing::trace!("Published Digest: {digest:?}");
+                        replication.metrics.digest_published();
+                    }
                     Err(e) => tracing::error!("Failed to publish the replication Digest: {e:?}"),
                 }
 
@@ -367,6 +375,7 @@ impl Replication {
                             };
 
                         tracing::debug!("Replication digest received");
+                        replication.metrics.digest_received();
 
                         let digest = match replication.replication_log.read().await.digest() {
                             Ok(digest) => digest,
@@ -378,8 +387,14 @@ impl Replication {
                             }
                         };
 
-                        if let Some(digest_diff) = digest.diff(other_digest) {
+                        let digest_diff = digest.diff(other_digest);
+                        replication
+                            .metrics
+                            .digest_compared(source_zid.as_str(), digest_diff.is_none());
+
+                        if let Some(digest_diff) = digest_diff {
                             tracing::debug!("Potential misalignment detected: {digest_diff:?}");
+                            replication.metrics.divergence_detected();
 
                             let replica_aligner_ke = match keformat!(
                                 aligner_key_expr_formatter::formatter(),
@@ -491,6 +506,14 @@ impl Replication {
     ) -> JoinHandle<()> {
         let replication = self.clone();
         tokio::task::spawn(async move {
+            let _pending_alignment = replication.metrics.alignment_started();
+            let _pending_retrieval = match &alignment_query {
+                AlignmentQuery::Events(events) => {
+                    Some(replication.metrics.retrieval_started(events.len()))
+                }
+                _ => None,
+            };
+
             let attachment = match bincode::serialize(&alignment_query) {
                 Ok(attachment) => attachment,
                 Err(e) => {
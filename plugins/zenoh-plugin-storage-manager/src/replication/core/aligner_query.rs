@@ -83,7 +83,7 @@ impl Replication {
         match alignment_query {
             AlignmentQuery::Discovery => {
                 tracing::trace!("Processing `AlignmentQuery::Discovery`");
-                reply_to_query(
+                self.reply_to_query(
                     &query,
                     AlignmentReply::Discovery(self.zenoh_session.zid()),
                     None,
@@ -197,7 +197,7 @@ impl Replication {
                 .collect::<HashMap<_, _>>()
         });
 
-        reply_to_query(query, reply, None).await;
+        self.reply_to_query(query, reply, None).await;
     }
 
     /// Replies to the [Query] with a structure containing, for each interval index present in the
@@ -222,7 +222,7 @@ impl Replication {
         }
 
         let reply = AlignmentReply::SubIntervals(sub_intervals_fingerprints);
-        reply_to_query(query, reply, None).await;
+        self.reply_to_query(query, reply, None).await;
     }
 
     /// Replies to the [Query] with all the [EventMetadata] of the [Event]s present in the
@@ -270,7 +270,7 @@ impl Replication {
         }
 
         let reply = AlignmentReply::EventsMetadata(events);
-        reply_to_query(query, reply, None).await;
+        self.reply_to_query(query, reply, None).await;
     }
 
     /// Replies to the [Query] with the [EventMetadata] and [Value] identified as missing.
@@ -340,33 +340,48 @@ impl Replication {
             }
         };
 
-        reply_to_query(query, AlignmentReply::Retrieval(event_to_retrieve), value).await;
+        self.reply_to_query(query, AlignmentReply::Retrieval(event_to_retrieve), value)
+            .await;
     }
-}
 
-/// Replies to a Query, adding the [AlignmentReply] as an attachment and, if provided, the payload
-/// with the corresponding [zenoh::bytes::Encoding].
-async fn reply_to_query(query: &Query, reply: AlignmentReply, value: Option<(ZBytes, Encoding)>) {
-    let attachment = match bincode::serialize(&reply) {
-        Ok(attachment) => attachment,
-        Err(e) => {
-            tracing::error!("Failed to serialize AlignmentReply: {e:?}");
-            return;
+    /// Replies to a Query, adding the [AlignmentReply] as an attachment and, if provided, the
+    /// payload with the corresponding [zenoh::bytes::Encoding].
+    ///
+    /// If an `alignment_bandwidth` is configured, this method waits until the reply can be sent
+    /// without exceeding it.
+    async fn reply_to_query(
+        &self,
+        query: &Query,
+        reply: AlignmentReply,
+        value: Option<(ZBytes, Encoding)>,
+    ) {
+        let attachment = match bincode::serialize(&reply) {
+            Ok(attachment) => attachment,
+            Err(e) => {
+                tracing::error!("Failed to serialize AlignmentReply: {e:?}");
+                return;
+            }
+        };
+
+        let reply_size = attachment.len() + value.as_ref().map_or(0, |(payload, _)| payload.len());
+        if let Some(bandwidth_limiter) = &self.bandwidth_limiter {
+            bandwidth_limiter.acquire(reply_size).await;
         }
-    };
 
-    let reply_fut = if let Some(value) = value {
-        query
-            .reply(query.key_expr(), value.0)
-            .encoding(value.1)
-            .attachment(attachment)
-    } else {
-        query
-            .reply(query.key_expr(), ZBytes::new())
-            .attachment(attachment)
-    };
+        let reply_fut = if let Some(value) = value {
+            query
+                .reply(query.key_expr(), value.0)
+                .encoding(value.1)
+                .attachment(attachment)
+        } else {
+            query
+                .reply(query.key_expr(), ZBytes::new())
+                .attachment(attachment)
+        };
 
-    if let Err(e) = reply_fut.await {
-        tracing::error!("Failed to reply to Query: {e:?}");
+        match reply_fut.await {
+            Ok(()) => self.metrics.alignment_bytes_sent(reply_size),
+            Err(e) => tracing::error!("Failed to reply to Query: {e:?}"),
+        }
     }
 }
@@ -286,6 +286,13 @@ impl Replication {
                                 .await
                                 .delete(replica_event.stripped_key.clone(), replica_event.timestamp)
                                 .await;
+                            self.storage_service
+                                .record_aligned_update(
+                                    &replica_event.stripped_key,
+                                    None,
+                                    replica_event.timestamp,
+                                )
+                                .await;
                         }
                     }
                 }
@@ -302,6 +309,7 @@ impl Replication {
             }
         }
 
+        self.metrics.event_aligned();
         replication_log_guard.insert_event_unchecked(replica_event.clone().into());
         None
     }
@@ -368,6 +376,7 @@ impl Replication {
                 let SampleFields {
                     payload, encoding, ..
                 } = sample.into();
+                let size = payload.len() as u64;
                 if matches!(
                     self.storage_service
                         .storage
@@ -389,6 +398,13 @@ impl Replication {
                     // In that scenario the Storage should either return an error or `Outdated`.
                     return;
                 }
+                self.storage_service
+                    .record_aligned_update(
+                        &replica_event.stripped_key,
+                        Some(size),
+                        replica_event.timestamp,
+                    )
+                    .await;
             }
             Action::WildcardPut(_) => {
                 let SampleFields {
@@ -404,6 +420,7 @@ impl Replication {
             }
         }
 
+        self.metrics.event_aligned();
         replication_log_guard.insert_event_unchecked(replica_event.into());
     }
 
@@ -490,6 +507,13 @@ impl Replication {
                         .await
                         .delete(replica_event.stripped_key.clone(), *log_event.timestamp())
                         .await;
+                    self.storage_service
+                        .record_aligned_update(
+                            &replica_event.stripped_key,
+                            None,
+                            *log_event.timestamp(),
+                        )
+                        .await;
                 }
 
                 let log_event_metadata = log_event.into();
@@ -627,6 +651,13 @@ impl Replication {
                     ) {
                         continue;
                     }
+                    self.storage_service
+                        .record_aligned_update(
+                            overridden_event.key_expr(),
+                            Some(payload.len() as u64),
+                            replica_event.timestamp,
+                        )
+                        .await;
                 }
                 (Action::Put, SampleKind::Delete) => {
                     if matches!(
@@ -643,6 +674,13 @@ impl Replication {
                     ) {
                         continue;
                     }
+                    self.storage_service
+                        .record_aligned_update(
+                            overridden_event.key_expr(),
+                            None,
+                            *overridden_event.timestamp(),
+                        )
+                        .await;
                 }
 
                 // We are overriding a Wildcard Update with another Wildcard Update, there is no
@@ -768,6 +806,7 @@ impl Replication {
             SampleKind::Delete => Action::WildcardDelete(wildcard_ke),
         };
 
+        let size = payload.len() as u64;
         if kind == SampleKind::Put
             && matches!(
                 self.storage_service
@@ -790,6 +829,11 @@ impl Replication {
             );
             return;
         }
+        if kind == SampleKind::Put {
+            self.storage_service
+                .record_aligned_update(&replica_event.stripped_key, Some(size), timestamp)
+                .await;
+        }
 
         // First create an Event with the metadata sent by the Replica: we want to keep the
         // `timestamp_last_non_wildcard_update`.
@@ -0,0 +1,273 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use zenoh::internal::zlock;
+
+/// The number of intervals after which a Replica that did not publish its Digest is no longer
+/// considered in the [AlignmentStatus].
+const REPLICA_TIMEOUT_INTERVALS: u32 = 3;
+
+/// The alignment status of a replicated Storage with the other Replicas.
+///
+/// It is the reply to a query on `<storage admin key>/alignment`, e.g.
+/// `@/<zid>/router/status/plugins/storage_manager/storages/<storage>/alignment`, and is also
+/// included in the status of the Storage, under the `alignment` field. It allows deployment
+/// scripts to wait until a Storage is aligned:
+///
+/// ```no_run
+/// # async fn wait_aligned(session: &zenoh::Session, admin_key: &str) {
+/// use zenoh_plugin_storage_manager::AlignmentStatus;
+///
+/// loop {
+///     let reply = session.get(format!("{admin_key}/alignment")).await.unwrap();
+///     let status: AlignmentStatus = match reply.recv_async().await {
+///         Ok(reply) => serde_json::from_slice(&reply.result().unwrap().payload().to_bytes())
+///             .unwrap(),
+///         Err(_) => continue,
+///     };
+///     if status.is_aligned() {
+///         break;
+///     }
+///     tokio::time::sleep(std::time::Duration::from_millis(status.estimated_convergence_ms))
+///         .await;
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlignmentStatus {
+    /// The time of the last Digest published by this Replica, in milliseconds since the UNIX
+    /// epoch.
+    pub last_digest_published_ms: Option<u64>,
+    /// The time of the last Digest received from another Replica, in milliseconds since the UNIX
+    /// epoch.
+    pub last_digest_received_ms: Option<u64>,
+    /// The Replicas which published their Digest recently, by Zenoh id.
+    pub replicas: BTreeMap<String, ReplicaAlignment>,
+    /// The number of keys being retrieved from the other Replicas.
+    pub diverging_keys: u64,
+    /// The number of alignment queries in progress.
+    pub pending_alignments: u64,
+    /// The estimated time until this Replica converges with the other Replicas, in milliseconds,
+    /// 0 if it is aligned.
+    ///
+    /// It accounts for the retrieval of the diverging keys, at the pace of the previous
+    /// retrievals, and for the next exchange of Digests confirming the alignment.
+    pub estimated_convergence_ms: u64,
+}
+
+impl AlignmentStatus {
+    /// Returns `true` if at least one other Replica was discovered, if the last Digest of each
+    /// one matched the content of this Replica and if no alignment is in progress.
+    pub fn is_aligned(&self) -> bool {
+        !self.replicas.is_empty()
+            && self.replicas.values().all(|replica| replica.aligned)
+            && self.diverging_keys == 0
+            && self.pending_alignments == 0
+    }
+}
+
+/// The alignment of a Replica with another one, as of the last Digest it received from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaAlignment {
+    /// The time of the last Digest received from the Replica, in milliseconds since the UNIX
+    /// epoch.
+    pub last_digest_ms: u64,
+    /// Whether the last Digest received from the Replica matched the content of this Replica.
+    pub aligned: bool,
+}
+
+/// The [ReplicationMetrics] track the divergence of a Replica with the other Replicas and the
+/// progress of their alignment.
+///
+/// They are exposed in the admin space, in the status of the Storage, under the `replication`
+/// field, and summarised in its [AlignmentStatus].
+#[derive(Debug, Default)]
+pub(crate) struct ReplicationMetrics {
+    interval: Duration,
+    digests_received: AtomicU64,
+    divergences_detected: AtomicU64,
+    // Milliseconds since the UNIX epoch, 0 if no divergence was ever detected.
+    last_divergence: AtomicU64,
+    alignment_queries: AtomicU64,
+    pending_alignments: AtomicU64,
+    events_aligned: AtomicU64,
+    alignment_bytes_sent: AtomicU64,
+    // Milliseconds since the UNIX epoch, 0 if no Digest was ever published.
+    last_digest_published: AtomicU64,
+    replicas: Mutex<HashMap<String, ReplicaAlignment>>,
+    diverging_keys: AtomicU64,
+    keys_retrieved: AtomicU64,
+    retrieval_time_ms: AtomicU64,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl ReplicationMetrics {
+    /// Creates the metrics of a Replica publishing its Digest every `interval`.
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn digest_published(&self) {
+        self.last_digest_published
+            .store(now_millis(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn digest_received(&self) {
+        self.digests_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn divergence_detected(&self) {
+        self.divergences_detected.fetch_add(1, Ordering::Relaxed);
+        self.last_divergence.store(now_millis(), Ordering::Relaxed);
+    }
+
+    /// Registers the comparison of the Digest of `replica` with the local one.
+    pub(crate) fn digest_compared(&self, replica: &str, aligned: bool) {
+        zlock!(self.replicas).insert(
+            replica.to_string(),
+            ReplicaAlignment {
+                last_digest_ms: now_millis(),
+                aligned,
+            },
+        );
+    }
+
+    /// Registers the start of an alignment query, which is considered pending until the returned
+    /// guard is dropped.
+    pub(crate) fn alignment_started(self: &Arc<Self>) -> PendingAlignment {
+        self.alignment_queries.fetch_add(1, Ordering::Relaxed);
+        self.pending_alignments.fetch_add(1, Ordering::Relaxed);
+        PendingAlignment(self.clone())
+    }
+
+    /// Registers the start of the retrieval of `keys` diverging keys, which are considered
+    /// diverging until the returned guard is dropped.
+    pub(crate) fn retrieval_started(self: &Arc<Self>, keys: usize) -> PendingRetrieval {
+        self.diverging_keys
+            .fetch_add(keys as u64, Ordering::Relaxed);
+        PendingRetrieval {
+            metrics: self.clone(),
+            keys: keys as u64,
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn event_aligned(&self) {
+        self.events_aligned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn alignment_bytes_sent(&self, bytes: usize) {
+        self.alignment_bytes_sent
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let last_divergence = match self.last_divergence.load(Ordering::Relaxed) {
+            0 => Value::Null,
+            millis => millis.into(),
+        };
+        json!({
+            "digests_received": self.digests_received.load(Ordering::Relaxed),
+            "divergences_detected": self.divergences_detected.load(Ordering::Relaxed),
+            "last_divergence_ms": last_divergence,
+            "alignment_queries": self.alignment_queries.load(Ordering::Relaxed),
+            "pending_alignments": self.pending_alignments.load(Ordering::Relaxed),
+            "events_aligned": self.events_aligned.load(Ordering::Relaxed),
+            "alignment_bytes_sent": self.alignment_bytes_sent.load(Ordering::Relaxed),
+        })
+    }
+
+    pub(crate) fn alignment_status(&self) -> AlignmentStatus {
+        let now = now_millis();
+        let timeout = (self.interval * REPLICA_TIMEOUT_INTERVALS).as_millis() as u64;
+        let replicas: BTreeMap<_, _> = zlock!(self.replicas)
+            .iter()
+            .filter(|(_, replica)| now.saturating_sub(replica.last_digest_ms) <= timeout)
+            .map(|(zid, replica)| (zid.clone(), *replica))
+            .collect();
+        let mut status = AlignmentStatus {
+            last_digest_published_ms: match self.last_digest_published.load(Ordering::Relaxed) {
+                0 => None,
+                millis => Some(millis),
+            },
+            last_digest_received_ms: replicas.values().map(|r| r.last_digest_ms).max(),
+            replicas,
+            diverging_keys: self.diverging_keys.load(Ordering::Relaxed),
+            pending_alignments: self.pending_alignments.load(Ordering::Relaxed),
+            estimated_convergence_ms: 0,
+        };
+        if !status.is_aligned() {
+            let keys_retrieved = self.keys_retrieved.load(Ordering::Relaxed);
+            let retrieval_ms = match keys_retrieved {
+                0 => 0,
+                keys => {
+                    status.diverging_keys * self.retrieval_time_ms.load(Ordering::Relaxed) / keys
+                }
+            };
+            status.estimated_convergence_ms = retrieval_ms + self.interval.as_millis() as u64;
+        }
+        status
+    }
+}
+
+/// Guard decrementing the number of pending alignments of the [ReplicationMetrics] when dropped.
+pub(crate) struct PendingAlignment(Arc<ReplicationMetrics>);
+
+impl Drop for PendingAlignment {
+    fn drop(&mut self) {
+        self.0.pending_alignments.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Guard registering the retrieval of diverging keys in the [ReplicationMetrics] when dropped.
+pub(crate) struct PendingRetrieval {
+    metrics: Arc<ReplicationMetrics>,
+    keys: u64,
+    start: Instant,
+}
+
+impl Drop for PendingRetrieval {
+    fn drop(&mut self) {
+        let metrics = &self.metrics;
+        metrics
+            .diverging_keys
+            .fetch_sub(self.keys, Ordering::Relaxed);
+        metrics
+            .keys_retrieved
+            .fetch_add(self.keys, Ordering::Relaxed);
+        metrics
+            .retrieval_time_ms
+            .fetch_add(self.start.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+}
@@ -26,12 +26,16 @@
 //!
 //! [History::Latest]: zenoh_backend_traits::History::Latest
 
+mod bandwidth;
 mod classification;
 mod configuration;
 mod core;
 mod digest;
 mod log;
+mod metrics;
 mod service;
 
 pub(crate) use log::{Action, Event, LogLatest, LogLatestKey};
+pub(crate) use metrics::ReplicationMetrics;
+pub use metrics::{AlignmentStatus, ReplicaAlignment};
 pub(crate) use service::ReplicationService;
@@ -20,7 +20,7 @@ use tokio::{
 };
 use zenoh::{key_expr::OwnedKeyExpr, session::Session};
 
-use super::{core::Replication, LogLatest};
+use super::{bandwidth::BandwidthLimiter, core::Replication, LogLatest, ReplicationMetrics};
 use crate::storages_mgt::{LatestUpdates, StorageMessage, StorageService};
 
 pub(crate) struct ReplicationService {
@@ -53,14 +53,24 @@ impl ReplicationService {
         storage_key_expr: OwnedKeyExpr,
         replication_log: Arc<RwLock<LogLatest>>,
         latest_updates: Arc<RwLock<LatestUpdates>>,
+        metrics: Arc<ReplicationMetrics>,
         mut rx: Receiver<StorageMessage>,
     ) {
+        let bandwidth_limiter = replication_log
+            .read()
+            .await
+            .configuration
+            .alignment_bandwidth
+            .map(|bandwidth| Arc::new(BandwidthLimiter::new(bandwidth)));
+
         let replication = Replication {
             zenoh_session,
             replication_log,
             storage_key_expr,
             latest_updates,
             storage_service,
+            metrics,
+            bandwidth_limiter,
         };
 
         if replication
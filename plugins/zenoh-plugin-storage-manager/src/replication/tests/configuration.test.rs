@@ -29,6 +29,7 @@ fn test_lower_bounds() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            alignment_bandwidth: None,
         },
     );
 
@@ -50,6 +51,7 @@ fn test_difference() {
         hot: 1,
         warm: 5,
         propagation_delay: Duration::from_millis(250),
+        alignment_bandwidth: None,
     };
 
     let configuration_a = Configuration::new(
@@ -67,12 +69,24 @@ fn test_difference() {
     assert_ne!(configuration_a.fingerprint, configuration_b.fingerprint);
 
     let configuration_c = Configuration::new(
-        configuration_a.storage_key_expr,
+        configuration_a.storage_key_expr.clone(),
         Some(OwnedKeyExpr::from_str("replication/test").unwrap()),
-        identical_replica_config,
+        identical_replica_config.clone(),
     );
 
     assert_ne!(configuration_a.fingerprint, configuration_c.fingerprint);
+
+    // The alignment bandwidth only affects the local replica.
+    let configuration_d = Configuration::new(
+        configuration_a.storage_key_expr.clone(),
+        None,
+        ReplicaConfig {
+            alignment_bandwidth: Some(1_000),
+            ..identical_replica_config
+        },
+    );
+
+    assert_eq!(configuration_a.fingerprint, configuration_d.fingerprint);
 }
 
 #[test]
@@ -86,6 +100,7 @@ fn test_get_classification() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            alignment_bandwidth: None,
         },
     );
 
@@ -0,0 +1,704 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
+
+use async_trait::async_trait;
+use rocksdb::{
+    ColumnFamily, ColumnFamilyDescriptor, DBCompactionStyle, Direction, IteratorMode, Options,
+    WriteBatch, DB,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    query::{Parameters, TimeRange, ZenohParameters},
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{
+    config::{StorageConfig, VolumeConfig},
+    *,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
+
+use crate::ROCKSDB_BACKEND_NAME;
+
+/// The environment variable overriding the default root directory of the RocksDB databases.
+const ROOT_ENV: &str = "ZENOH_BACKEND_ROCKSDB_ROOT";
+const DEFAULT_ROOT_DIR: &str = "zenoh_backend_rocksdb";
+
+/// The column family holding the latest value of each key.
+const CF_DATA: &str = "data";
+/// The column family holding every value of each key, when history is enabled.
+const CF_HISTORY: &str = "history";
+
+// Volume configuration
+const PROP_ROOT_DIR: &str = "dir";
+const PROP_COMPACTION: &str = "compaction";
+const PROP_COMPACTION_STYLE: &str = "style";
+const PROP_COMPACTION_PERIODIC: &str = "periodic_seconds";
+const PROP_COMPACTION_BACKGROUND_JOBS: &str = "max_background_jobs";
+const PROP_COMPACTION_LEVEL0_TRIGGER: &str = "level0_file_num_trigger";
+
+// Storage configuration
+const PROP_STORAGE_DIR: &str = "dir";
+const PROP_STORAGE_CREATE_DB: &str = "create_db";
+const PROP_STORAGE_HISTORY: &str = "history";
+
+#[derive(Debug, Clone, Default)]
+struct CompactionConfig {
+    style: Option<DBCompactionStyle>,
+    periodic_seconds: Option<u64>,
+    max_background_jobs: Option<i32>,
+    level0_file_num_trigger: Option<i32>,
+}
+
+impl CompactionConfig {
+    fn from_config(volume: &str, value: Option<&Value>) -> ZResult<Self> {
+        let config = match value {
+            None => return Ok(CompactionConfig::default()),
+            Some(Value::Object(config)) => config,
+            Some(_) => bail!(
+                "`{}` field of volume `{}` must be an object",
+                PROP_COMPACTION,
+                volume
+            ),
+        };
+        let style = match config.get(PROP_COMPACTION_STYLE) {
+            None => None,
+            Some(Value::String(s)) => match s.as_str() {
+                "level" => Some(DBCompactionStyle::Level),
+                "universal" => Some(DBCompactionStyle::Universal),
+                "fifo" => Some(DBCompactionStyle::Fifo),
+                s => bail!(
+                    "{}.{}='{}' is not a valid value for volume `{}`. Accepted values: \
+                     ['level', 'universal', 'fifo']",
+                    PROP_COMPACTION,
+                    PROP_COMPACTION_STYLE,
+                    s,
+                    volume
+                ),
+            },
+            Some(_) => bail!(
+                "{}.{} of volume `{}` must be a string",
+                PROP_COMPACTION,
+                PROP_COMPACTION_STYLE,
+                volume
+            ),
+        };
+        let positive = |key: &str| -> ZResult<Option<u64>> {
+            match config.get(key) {
+                None => Ok(None),
+                Some(v) => match v.as_u64() {
+                    Some(n) if n > 0 => Ok(Some(n)),
+                    _ => bail!(
+                        "{}.{} of volume `{}` must be a positive integer",
+                        PROP_COMPACTION,
+                        key,
+                        volume
+                    ),
+                },
+            }
+        };
+        let small = |key: &str| -> ZResult<Option<i32>> {
+            positive(key)?
+                .map(|n| {
+                    i32::try_from(n).map_err(|_| {
+                        zerror!(
+                            "{}.{} of volume `{}` is too large",
+                            PROP_COMPACTION,
+                            key,
+                            volume
+                        )
+                        .into()
+                    })
+                })
+                .transpose()
+        };
+        Ok(CompactionConfig {
+            style,
+            periodic_seconds: positive(PROP_COMPACTION_PERIODIC)?,
+            max_background_jobs: small(PROP_COMPACTION_BACKGROUND_JOBS)?,
+            level0_file_num_trigger: small(PROP_COMPACTION_LEVEL0_TRIGGER)?,
+        })
+    }
+
+    fn apply(&self, opts: &mut Options) {
+        if let Some(style) = self.style {
+            opts.set_compaction_style(style);
+        }
+        if let Some(secs) = self.periodic_seconds {
+            opts.set_periodic_compaction_seconds(secs);
+        }
+        if let Some(jobs) = self.max_background_jobs {
+            opts.set_max_background_jobs(jobs);
+        }
+        if let Some(n) = self.level0_file_num_trigger {
+            opts.set_level_zero_file_num_compaction_trigger(n);
+        }
+    }
+}
+
+pub struct RocksDbBackend {
+    config: VolumeConfig,
+    root: PathBuf,
+    compaction: CompactionConfig,
+}
+
+impl Plugin for RocksDbBackend {
+    type StartArgs = VolumeConfig;
+    type Instance = VolumeInstance;
+
+    const DEFAULT_NAME: &'static str = ROCKSDB_BACKEND_NAME;
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let root = match args.rest.get(PROP_ROOT_DIR) {
+            Some(Value::String(dir)) => PathBuf::from(dir),
+            Some(_) => bail!(
+                "`{}` field of volume `{}` must be a string",
+                PROP_ROOT_DIR,
+                args.name()
+            ),
+            None => match std::env::var_os(ROOT_ENV) {
+                Some(dir) => PathBuf::from(dir),
+                None => match std::env::var_os("HOME") {
+                    Some(home) => PathBuf::from(home).join(".zenoh").join(DEFAULT_ROOT_DIR),
+                    None => PathBuf::from(DEFAULT_ROOT_DIR),
+                },
+            },
+        };
+        let compaction =
+            CompactionConfig::from_config(args.name(), args.rest.get(PROP_COMPACTION))?;
+        tracing::debug!(
+            "RocksDB volume '{}' using root directory {:?}",
+            args.name(),
+            root
+        );
+        Ok(Box::new(RocksDbBackend {
+            config: args.clone(),
+            root,
+            compaction,
+        }))
+    }
+}
+
+#[async_trait]
+impl Volume for RocksDbBackend {
+    fn get_admin_status(&self) -> serde_json::Value {
+        let mut status = self.config.to_json_value();
+        if let Some(obj) = status.as_object_mut() {
+            obj.insert(
+                "root_dir".into(),
+                self.root.to_string_lossy().into_owned().into(),
+            );
+        }
+        status
+    }
+
+    fn get_capability(&self) -> Capability {
+        Capability {
+            persistence: Persistence::Durable,
+            history: History::Latest,
+        }
+    }
+
+    fn get_storage_capability(&self, properties: &StorageConfig) -> Capability {
+        // Storages keeping the history of their keys serve the time range queries themselves
+        let history = properties
+            .volume_cfg
+            .get(PROP_STORAGE_HISTORY)
+            .and_then(|value| bool_property(properties, Some(value)).ok())
+            .unwrap_or(false);
+        Capability {
+            persistence: Persistence::Durable,
+            history: if history {
+                History::All
+            } else {
+                History::Latest
+            },
+        }
+    }
+
+    async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
+        tracing::debug!(
+            "Create RocksDB Storage with configuration: {:?}",
+            properties
+        );
+        let volume_cfg = properties.volume_cfg.as_object().ok_or_else(|| {
+            zerror!(
+                "Storage `{}` must set the `{}` of its RocksDB database in its `volume` object",
+                properties.name,
+                PROP_STORAGE_DIR
+            )
+        })?;
+        let dir = match volume_cfg.get(PROP_STORAGE_DIR) {
+            Some(Value::String(dir)) => self.root.join(dir),
+            _ => bail!(
+                "`volume.{}` field of storage `{}` is missing or not a string",
+                PROP_STORAGE_DIR,
+                properties.name
+            ),
+        };
+        let create_db = bool_property(&properties, volume_cfg.get(PROP_STORAGE_CREATE_DB))?;
+        let history = bool_property(&properties, volume_cfg.get(PROP_STORAGE_HISTORY))?;
+
+        let mut opts = Options::default();
+        opts.create_if_missing(create_db);
+        opts.create_missing_column_families(true);
+        self.compaction.apply(&mut opts);
+        // All the existing column families must be opened, e.g. the history one even if history
+        // was disabled since the database creation
+        let mut names = DB::list_cf(&opts, &dir).unwrap_or_default();
+        for name in [CF_DATA, CF_HISTORY] {
+            if (name == CF_DATA || history) && !names.iter().any(|n| n == name) {
+                names.push(name.into());
+            }
+        }
+        let cfs = names
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, opts.clone()));
+        let db = DB::open_cf_descriptors(&opts, &dir, cfs)
+            .map_err(|e| zerror!("Failed to open RocksDB database {:?}: {}", dir, e))?;
+        tracing::debug!("RocksDB database {:?} opened", dir);
+
+        Ok(Box::new(RocksDbStorage {
+            config: properties,
+            db: Arc::new(db),
+            history,
+        }))
+    }
+}
+
+fn bool_property(properties: &StorageConfig, value: Option<&Value>) -> ZResult<bool> {
+    match value {
+        None => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(Value::String(s)) if s == "true" => Ok(true),
+        Some(Value::String(s)) if s == "false" => Ok(false),
+        Some(v) => bail!(
+            "Invalid value {} in `volume` of storage `{}`: a boolean is expected",
+            v,
+            properties.name
+        ),
+    }
+}
+
+impl Drop for RocksDbBackend {
+    fn drop(&mut self) {
+        tracing::trace!("RocksDbBackend::drop()");
+    }
+}
+
+/// The value stored for a key, in both the data and history column families.
+#[derive(Serialize, Deserialize)]
+struct StoredValue {
+    payload: Vec<u8>,
+    encoding: String,
+    timestamp: Timestamp,
+}
+
+impl From<StoredValue> for StoredData {
+    fn from(value: StoredValue) -> Self {
+        StoredData {
+            payload: ZBytes::from(value.payload),
+            encoding: Encoding::from(value.encoding),
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+struct RocksDbStorage {
+    config: StorageConfig,
+    db: Arc<DB>,
+    history: bool,
+}
+
+/// The key of the data column family. The storage prefix itself, i.e. the `None` key, is stored
+/// as the empty key, which is not a valid key expression.
+fn data_key(key: &Option<OwnedKeyExpr>) -> Vec<u8> {
+    match key {
+        Some(key) => key.as_bytes().to_vec(),
+        None => vec![],
+    }
+}
+
+/// The key of the history column family: the data key, a `0` separator (which is not a valid key
+/// expression character) and the timestamp, so that the values of a key are sorted by time.
+fn history_key(key: &Option<OwnedKeyExpr>, timestamp: &Timestamp) -> Vec<u8> {
+    let mut bytes = history_prefix(key);
+    bytes.extend_from_slice(&timestamp.get_time().as_u64().to_be_bytes());
+    bytes.extend_from_slice(&timestamp.get_id().to_le_bytes());
+    bytes
+}
+
+fn history_prefix(key: &Option<OwnedKeyExpr>) -> Vec<u8> {
+    let mut bytes = data_key(key);
+    bytes.push(0);
+    bytes
+}
+
+fn decode_value(bytes: &[u8]) -> ZResult<StoredValue> {
+    bincode::deserialize(bytes).map_err(|e| zerror!("Corrupted RocksDB value: {}", e).into())
+}
+
+fn cf_handle<'a>(db: &'a DB, name: &str) -> ZResult<&'a ColumnFamily> {
+    db.cf_handle(name)
+        .ok_or_else(|| zerror!("Missing column family '{}'", name).into())
+}
+
+fn read(db: &DB, key: &Option<OwnedKeyExpr>) -> ZResult<Option<StoredValue>> {
+    match db.get_pinned_cf(cf_handle(db, CF_DATA)?, data_key(key))? {
+        Some(bytes) => Ok(Some(decode_value(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+fn read_history(
+    db: &DB,
+    key: &Option<OwnedKeyExpr>,
+    time_range: &TimeRange<SystemTime>,
+) -> ZResult<Vec<StoredData>> {
+    let prefix = history_prefix(key);
+    let mut result = vec![];
+    for entry in db.iterator_cf(
+        cf_handle(db, CF_HISTORY)?,
+        IteratorMode::From(&prefix, Direction::Forward),
+    ) {
+        let (k, v) = entry?;
+        if !k.starts_with(&prefix) {
+            break;
+        }
+        let value = decode_value(&v)?;
+        if time_range.contains(value.timestamp.get_time().to_system_time()) {
+            result.push(value.into());
+        }
+    }
+    Ok(result)
+}
+
+impl RocksDbStorage {
+    /// Runs a blocking operation on the database on the blocking thread pool, so that the
+    /// RocksDB calls don't stall the async tasks of the storage manager.
+    async fn blocking<T, F>(&self, f: F) -> ZResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&DB) -> ZResult<T> + Send + 'static,
+    {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || f(&db))
+            .await
+            .map_err(|e| zerror!("RocksDB operation failed: {}", e))?
+    }
+}
+
+#[async_trait]
+impl Storage for RocksDbStorage {
+    fn get_admin_status(&self) -> serde_json::Value {
+        self.config.to_json_value()
+    }
+
+    async fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?}", key);
+        let value = bincode::serialize(&StoredValue {
+            payload: payload.to_bytes().into_owned(),
+            encoding: encoding.to_string(),
+            timestamp,
+        })?;
+        let history = self.history;
+        self.blocking(move |db| {
+            let previous = read(db, &key)?;
+            let outdated = previous
+                .as_ref()
+                .is_some_and(|previous| previous.timestamp >= timestamp);
+            // An outdated value is still part of the history of the key
+            if outdated && !history {
+                return Ok(StorageInsertionResult::Outdated);
+            }
+            let mut batch = WriteBatch::default();
+            if !outdated {
+                batch.put_cf(cf_handle(db, CF_DATA)?, data_key(&key), &value);
+            }
+            if history {
+                batch.put_cf(
+                    cf_handle(db, CF_HISTORY)?,
+                    history_key(&key, &timestamp),
+                    &value,
+                );
+            }
+            db.write(batch)?;
+            Ok(match previous {
+                Some(_) if !outdated => StorageInsertionResult::Replaced,
+                _ => StorageInsertionResult::Inserted,
+            })
+        })
+        .await
+    }
+
+    async fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("delete for {:?}", key);
+        self.blocking(move |db| {
+            if read(db, &key)?.is_some_and(|previous| previous.timestamp >= timestamp) {
+                return Ok(StorageInsertionResult::Outdated);
+            }
+            // The history of the key is kept, so that it can still be queried by time range
+            db.delete_cf(cf_handle(db, CF_DATA)?, data_key(&key))?;
+            Ok(StorageInsertionResult::Deleted)
+        })
+        .await
+    }
+
+    async fn get(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        let time_range = match Parameters::from(parameters).time_range() {
+            Some(time_range) if self.history => Some(time_range?.resolve()),
+            _ => None,
+        };
+        self.blocking(move |db| {
+            if let Some(time_range) = time_range {
+                return read_history(db, &key, &time_range);
+            }
+            match read(db, &key)? {
+                Some(value) => Ok(vec![value.into()]),
+                None => Err(format!("Key {:?} is not present", key).into()),
+            }
+        })
+        .await
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        self.blocking(|db| {
+            let mut result = vec![];
+            for entry in db.iterator_cf(cf_handle(db, CF_DATA)?, IteratorMode::Start) {
+                let (k, v) = entry?;
+                let key = if k.is_empty() {
+                    None
+                } else {
+                    Some(OwnedKeyExpr::try_from(String::from_utf8(k.into_vec())?)?)
+                };
+                result.push((key, decode_value(&v)?.timestamp));
+            }
+            Ok(result)
+        })
+        .await
+    }
+
+    async fn compact(&mut self) -> ZResult<()> {
+        tracing::debug!("compacting {:?}", self.db.path());
+        self.blocking(|db| {
+            // The history column family only exists if the history is enabled
+            for cf in [CF_DATA, CF_HISTORY]
+                .into_iter()
+                .filter_map(|name| db.cf_handle(name))
+            {
+                db.compact_range_cf(cf, None::<&[u8]>, None::<&[u8]>);
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+impl Drop for RocksDbStorage {
+    fn drop(&mut self) {
+        // The database is closed once its last reference is dropped
+        tracing::trace!("RocksDbStorage::drop()");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+    use zenoh::time::{TimestampId, NTP64};
+
+    use super::*;
+
+    fn timestamp(id: TimestampId, secs: u64) -> Timestamp {
+        Timestamp::new(NTP64::from(Duration::from_secs(secs)), id)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rocksdb_storage() {
+        let root = std::env::temp_dir().join(format!("zenoh_rocksdb_test_{}", std::process::id()));
+        let volume = VolumeConfig {
+            name: ROCKSDB_BACKEND_NAME.into(),
+            backend: None,
+            paths: None,
+            required: false,
+            rest: json!({ "dir": root.to_string_lossy() })
+                .as_object()
+                .unwrap()
+                .clone(),
+        };
+        let backend = RocksDbBackend::start(ROCKSDB_BACKEND_NAME, &volume).unwrap();
+        let config = StorageConfig::try_from(
+            "test-plugin",
+            "test-storage",
+            &json!({
+                "key_expr": "test/**",
+                "volume": { "id": "rocksdb", "dir": "test", "create_db": true, "history": true },
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            backend.get_storage_capability(&config).history,
+            History::All
+        );
+        let mut storage = backend.create_storage(config).await.unwrap();
+        let id = TimestampId::rand();
+
+        let key = Some(OwnedKeyExpr::new("a/b").unwrap());
+        for (secs, value) in [(1, "v1"), (3, "v3")] {
+            storage
+                .put(
+                    key.clone(),
+                    value.into(),
+                    Encoding::TEXT_PLAIN,
+                    timestamp(id, secs),
+                )
+                .await
+                .unwrap();
+        }
+        // An older value is only added to the history
+        storage
+            .put(
+                key.clone(),
+                "v2".into(),
+                Encoding::TEXT_PLAIN,
+                timestamp(id, 2),
+            )
+            .await
+            .unwrap();
+        let latest = storage.get(key.clone(), "").await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].payload.try_to_string().unwrap(), "v3");
+        assert_eq!(latest[0].timestamp, timestamp(id, 3));
+        let history = storage.get(key.clone(), "_time=[..]").await.unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|data| data.payload.try_to_string().unwrap().into_owned())
+                .collect::<Vec<_>>(),
+            vec!["v1", "v2", "v3"]
+        );
+
+        storage
+            .put(None, "root".into(), Encoding::TEXT_PLAIN, timestamp(id, 4))
+            .await
+            .unwrap();
+        let mut entries = storage.get_all_entries().await.unwrap();
+        entries.sort_by_key(|(_, t)| *t);
+        assert_eq!(
+            entries,
+            vec![(key.clone(), timestamp(id, 3)), (None, timestamp(id, 4))]
+        );
+
+        assert!(matches!(
+            storage.delete(key.clone(), timestamp(id, 2)).await.unwrap(),
+            StorageInsertionResult::Outdated
+        ));
+        assert!(storage.get(key.clone(), "").await.is_ok());
+        assert!(matches!(
+            storage.delete(key.clone(), timestamp(id, 5)).await.unwrap(),
+            StorageInsertionResult::Deleted
+        ));
+        assert!(storage.get(key.clone(), "").await.is_err());
+        assert_eq!(storage.get(key, "_time=[..]").await.unwrap().len(), 3);
+        storage.compact().await.unwrap();
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rocksdb_storage_latest() {
+        let root =
+            std::env::temp_dir().join(format!("zenoh_rocksdb_latest_test_{}", std::process::id()));
+        let volume = VolumeConfig {
+            name: ROCKSDB_BACKEND_NAME.into(),
+            backend: None,
+            paths: None,
+            required: false,
+            rest: json!({ "dir": root.to_string_lossy() })
+                .as_object()
+                .unwrap()
+                .clone(),
+        };
+        let backend = RocksDbBackend::start(ROCKSDB_BACKEND_NAME, &volume).unwrap();
+        let config = StorageConfig::try_from(
+            "test-plugin",
+            "test-storage",
+            &json!({
+                "key_expr": "test/**",
+                "volume": { "id": "rocksdb", "dir": "test", "create_db": true },
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            backend.get_storage_capability(&config).history,
+            History::Latest
+        );
+        let mut storage = backend.create_storage(config).await.unwrap();
+        let id = TimestampId::rand();
+
+        let key = Some(OwnedKeyExpr::new("a/b").unwrap());
+        storage
+            .put(
+                key.clone(),
+                "v2".into(),
+                Encoding::TEXT_PLAIN,
+                timestamp(id, 2),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            storage
+                .put(
+                    key.clone(),
+                    "v1".into(),
+                    Encoding::TEXT_PLAIN,
+                    timestamp(id, 1)
+                )
+                .await
+                .unwrap(),
+            StorageInsertionResult::Outdated
+        ));
+        // Without history, a time range query is served from the latest value
+        let latest = storage.get(key.clone(), "_time=[..]").await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].payload.try_to_string().unwrap(), "v2");
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
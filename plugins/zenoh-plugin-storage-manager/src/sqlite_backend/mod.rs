@@ -0,0 +1,525 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    fmt::Write,
+    path::PathBuf,
+    str::FromStr,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Row};
+use serde_json::Value;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror, zlock},
+    key_expr::OwnedKeyExpr,
+    query::{Parameters, TimeBound, TimeRange, ZenohParameters},
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{
+    config::{StorageConfig, VolumeConfig},
+    *,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
+
+use crate::SQLITE_BACKEND_NAME;
+
+/// The environment variable overriding the default root directory of the SQLite databases.
+const ROOT_ENV: &str = "ZENOH_BACKEND_SQLITE_ROOT";
+const DEFAULT_ROOT_DIR: &str = "zenoh_backend_sqlite";
+
+// Volume configuration
+const PROP_ROOT_DIR: &str = "dir";
+
+// Storage configuration
+const PROP_STORAGE_FILE: &str = "file";
+const PROP_STORAGE_CREATE_DB: &str = "create_db";
+const PROP_STORAGE_HISTORY: &str = "history";
+const PROP_STORAGE_SYNCHRONOUS: &str = "synchronous";
+
+/// The table holding the latest value of each key. The storage prefix itself, i.e. the `None`
+/// key, is stored as the empty key, which is not a valid key expression.
+const CREATE_DATA_TABLE: &str = "CREATE TABLE IF NOT EXISTS data (
+    key TEXT PRIMARY KEY,
+    payload BLOB NOT NULL,
+    encoding TEXT NOT NULL,
+    time INTEGER NOT NULL,
+    timestamp TEXT NOT NULL
+)";
+/// The table holding every value of each key, when history is enabled. The `time` column is the
+/// time of the timestamp in nanoseconds since the UNIX epoch, for the time range queries.
+const CREATE_HISTORY_TABLE: &str = "CREATE TABLE IF NOT EXISTS history (
+    key TEXT NOT NULL,
+    payload BLOB NOT NULL,
+    encoding TEXT NOT NULL,
+    time INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    PRIMARY KEY (key, timestamp)
+)";
+
+pub struct SqliteBackend {
+    config: VolumeConfig,
+    root: PathBuf,
+}
+
+impl Plugin for SqliteBackend {
+    type StartArgs = VolumeConfig;
+    type Instance = VolumeInstance;
+
+    const DEFAULT_NAME: &'static str = SQLITE_BACKEND_NAME;
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let root = match args.rest.get(PROP_ROOT_DIR) {
+            Some(Value::String(dir)) => PathBuf::from(dir),
+            Some(_) => bail!(
+                "`{}` field of volume `{}` must be a string",
+                PROP_ROOT_DIR,
+                args.name()
+            ),
+            None => match std::env::var_os(ROOT_ENV) {
+                Some(dir) => PathBuf::from(dir),
+                None => match std::env::var_os("HOME") {
+                    Some(home) => PathBuf::from(home).join(".zenoh").join(DEFAULT_ROOT_DIR),
+                    None => PathBuf::from(DEFAULT_ROOT_DIR),
+                },
+            },
+        };
+        tracing::debug!(
+            "SQLite volume '{}' using root directory {:?}",
+            args.name(),
+            root
+        );
+        Ok(Box::new(SqliteBackend {
+            config: args.clone(),
+            root,
+        }))
+    }
+}
+
+#[async_trait]
+impl Volume for SqliteBackend {
+    fn get_admin_status(&self) -> serde_json::Value {
+        let mut status = self.config.to_json_value();
+        if let Some(obj) = status.as_object_mut() {
+            obj.insert(
+                "root_dir".into(),
+                self.root.to_string_lossy().into_owned().into(),
+            );
+        }
+        status
+    }
+
+    fn get_capability(&self) -> Capability {
+        Capability {
+            persistence: Persistence::Durable,
+            history: History::Latest,
+        }
+    }
+
+    async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
+        tracing::debug!("Create SQLite Storage with configuration: {:?}", properties);
+        let volume_cfg = properties.volume_cfg.as_object().ok_or_else(|| {
+            zerror!(
+                "Storage `{}` must set the `{}` of its SQLite database in its `volume` object",
+                properties.name,
+                PROP_STORAGE_FILE
+            )
+        })?;
+        let file = match volume_cfg.get(PROP_STORAGE_FILE) {
+            Some(Value::String(file)) => self.root.join(file),
+            _ => bail!(
+                "`volume.{}` field of storage `{}` is missing or not a string",
+                PROP_STORAGE_FILE,
+                properties.name
+            ),
+        };
+        let create_db = bool_property(&properties, volume_cfg.get(PROP_STORAGE_CREATE_DB))?;
+        let history = bool_property(&properties, volume_cfg.get(PROP_STORAGE_HISTORY))?;
+        // In WAL mode, "normal" only risks losing the last transactions on a power loss, and
+        // syncs much less often than "full"
+        let synchronous = match volume_cfg.get(PROP_STORAGE_SYNCHRONOUS) {
+            None => "NORMAL",
+            Some(Value::String(s)) => match s.as_str() {
+                "off" => "OFF",
+                "normal" => "NORMAL",
+                "full" => "FULL",
+                "extra" => "EXTRA",
+                s => bail!(
+                    "volume.{}='{}' is not a valid value for storage `{}`. Accepted values: \
+                     ['off', 'normal', 'full', 'extra']",
+                    PROP_STORAGE_SYNCHRONOUS,
+                    s,
+                    properties.name
+                ),
+            },
+            Some(_) => bail!(
+                "`volume.{}` field of storage `{}` must be a string",
+                PROP_STORAGE_SYNCHRONOUS,
+                properties.name
+            ),
+        };
+
+        let mut flags = OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        if create_db {
+            if let Some(dir) = file.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            flags |= OpenFlags::SQLITE_OPEN_CREATE;
+        }
+        let conn = Connection::open_with_flags(&file, flags)
+            .map_err(|e| zerror!("Failed to open SQLite database {:?}: {}", file, e))?;
+        let journal_mode: String =
+            conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+        if !journal_mode.eq_ignore_ascii_case("wal") {
+            bail!(
+                "Failed to enable the WAL mode of SQLite database {:?}: journal mode is '{}'",
+                file,
+                journal_mode
+            );
+        }
+        conn.pragma_update(None, "synchronous", synchronous)?;
+        conn.execute(CREATE_DATA_TABLE, [])?;
+        if history {
+            conn.execute(CREATE_HISTORY_TABLE, [])?;
+        }
+        tracing::debug!("SQLite database {:?} opened", file);
+
+        Ok(Box::new(SqliteStorage {
+            config: properties,
+            conn: Mutex::new(conn),
+            history,
+        }))
+    }
+}
+
+fn bool_property(properties: &StorageConfig, value: Option<&Value>) -> ZResult<bool> {
+    match value {
+        None => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(Value::String(s)) if s == "true" => Ok(true),
+        Some(Value::String(s)) if s == "false" => Ok(false),
+        Some(v) => bail!(
+            "Invalid value {} in `volume` of storage `{}`: a boolean is expected",
+            v,
+            properties.name
+        ),
+    }
+}
+
+impl Drop for SqliteBackend {
+    fn drop(&mut self) {
+        tracing::trace!("SqliteBackend::drop()");
+    }
+}
+
+struct SqliteStorage {
+    config: StorageConfig,
+    conn: Mutex<Connection>,
+    history: bool,
+}
+
+fn data_key(key: &Option<OwnedKeyExpr>) -> &str {
+    match key {
+        Some(key) => key.as_str(),
+        None => "",
+    }
+}
+
+fn nanos(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos().min(i64::MAX as u128) as i64)
+        .unwrap_or(0)
+}
+
+fn parse_timestamp(timestamp: &str) -> ZResult<Timestamp> {
+    Timestamp::from_str(timestamp)
+        .map_err(|e| zerror!("Corrupted SQLite timestamp '{}': {:?}", timestamp, e).into())
+}
+
+/// Reads a `payload, encoding, timestamp` row.
+fn read_row(row: &Row) -> rusqlite::Result<(Vec<u8>, String, String)> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+}
+
+fn stored_data((payload, encoding, timestamp): (Vec<u8>, String, String)) -> ZResult<StoredData> {
+    Ok(StoredData {
+        payload: ZBytes::from(payload),
+        encoding: Encoding::from(encoding),
+        timestamp: parse_timestamp(&timestamp)?,
+    })
+}
+
+/// Appends the conditions on the `time` column selecting `time_range` to a `WHERE` clause.
+fn time_conditions(sql: &mut String, time_range: &TimeRange<SystemTime>) {
+    match time_range.start {
+        TimeBound::Inclusive(t) => {
+            let _ = write!(sql, " AND time >= {}", nanos(t));
+        }
+        TimeBound::Exclusive(t) => {
+            let _ = write!(sql, " AND time > {}", nanos(t));
+        }
+        TimeBound::Unbounded => {}
+    }
+    match time_range.end {
+        TimeBound::Inclusive(t) => {
+            let _ = write!(sql, " AND time <= {}", nanos(t));
+        }
+        TimeBound::Exclusive(t) => {
+            let _ = write!(sql, " AND time < {}", nanos(t));
+        }
+        TimeBound::Unbounded => {}
+    }
+}
+
+impl SqliteStorage {
+    fn read_history(
+        &self,
+        key: &Option<OwnedKeyExpr>,
+        parameters: &str,
+    ) -> ZResult<Option<Vec<StoredData>>> {
+        if !self.history {
+            return Ok(None);
+        }
+        let time_range = match Parameters::from(parameters).time_range() {
+            Some(time_range) => time_range?.resolve(),
+            None => return Ok(None),
+        };
+        let mut sql = "SELECT payload, encoding, timestamp FROM history WHERE key = ?1".to_string();
+        time_conditions(&mut sql, &time_range);
+        sql.push_str(" ORDER BY time");
+        let conn = zlock!(self.conn);
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![data_key(key)], read_row)?;
+        let mut result = vec![];
+        for row in rows {
+            result.push(stored_data(row?)?);
+        }
+        Ok(Some(result))
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    fn get_admin_status(&self) -> serde_json::Value {
+        self.config.to_json_value()
+    }
+
+    async fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?}", key);
+        let mut conn = zlock!(self.conn);
+        let previous: Option<String> = conn
+            .query_row(
+                "SELECT timestamp FROM data WHERE key = ?1",
+                params![data_key(&key)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(previous) = &previous {
+            if parse_timestamp(previous)? >= timestamp {
+                return Ok(StorageInsertionResult::Outdated);
+            }
+        }
+        let payload = payload.to_bytes();
+        let encoding = encoding.to_string();
+        let time = nanos(timestamp.get_time().to_system_time());
+        let timestamp = timestamp.to_string();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO data (key, payload, encoding, time, timestamp) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![data_key(&key), payload.as_ref(), encoding, time, timestamp],
+        )?;
+        if self.history {
+            tx.execute(
+                "INSERT OR REPLACE INTO history (key, payload, encoding, time, timestamp) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![data_key(&key), payload.as_ref(), encoding, time, timestamp],
+            )?;
+        }
+        tx.commit()?;
+        Ok(match previous {
+            Some(_) => StorageInsertionResult::Replaced,
+            None => StorageInsertionResult::Inserted,
+        })
+    }
+
+    async fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        _timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("delete for {:?}", key);
+        // The history of the key is kept, so that it can still be queried by time range
+        zlock!(self.conn).execute("DELETE FROM data WHERE key = ?1", params![data_key(&key)])?;
+        Ok(StorageInsertionResult::Deleted)
+    }
+
+    async fn get(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        if let Some(history) = self.read_history(&key, parameters)? {
+            return Ok(history);
+        }
+        let row = zlock!(self.conn)
+            .query_row(
+                "SELECT payload, encoding, timestamp FROM data WHERE key = ?1",
+                params![data_key(&key)],
+                read_row,
+            )
+            .optional()?;
+        match row {
+            Some(row) => Ok(vec![stored_data(row)?]),
+            None => Err(format!("Key {:?} is not present", key).into()),
+        }
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        let conn = zlock!(self.conn);
+        let mut stmt = conn.prepare("SELECT key, timestamp FROM data")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut result = vec![];
+        for row in rows {
+            let (key, timestamp) = row?;
+            let key = if key.is_empty() {
+                None
+            } else {
+                Some(OwnedKeyExpr::try_from(key)?)
+            };
+            result.push((key, parse_timestamp(&timestamp)?));
+        }
+        Ok(result)
+    }
+
+    async fn compact(&mut self) -> ZResult<()> {
+        let conn = zlock!(self.conn);
+        tracing::debug!("compacting {:?}", conn.path());
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        conn.execute("VACUUM", [])?;
+        Ok(())
+    }
+}
+
+impl Drop for SqliteStorage {
+    fn drop(&mut self) {
+        // The database is closed, and its WAL checkpointed, with its connection
+        tracing::trace!("SqliteStorage::drop()");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+    use zenoh::time::{TimestampId, NTP64};
+
+    use super::*;
+
+    fn timestamp(id: TimestampId, secs: u64) -> Timestamp {
+        Timestamp::new(NTP64::from(Duration::from_secs(secs)), id)
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_storage() {
+        let root = std::env::temp_dir().join(format!("zenoh_sqlite_test_{}", std::process::id()));
+        let volume = VolumeConfig {
+            name: SQLITE_BACKEND_NAME.into(),
+            backend: None,
+            paths: None,
+            required: false,
+            rest: json!({ "dir": root.to_string_lossy() })
+                .as_object()
+                .unwrap()
+                .clone(),
+        };
+        let backend = SqliteBackend::start(SQLITE_BACKEND_NAME, &volume).unwrap();
+        let config = StorageConfig::try_from(
+            "test-plugin",
+            "test-storage",
+            &json!({
+                "key_expr": "test/**",
+                "volume": { "id": "sqlite", "file": "test.db", "create_db": true, "history": true },
+            }),
+        )
+        .unwrap();
+        let mut storage = backend.create_storage(config).await.unwrap();
+        let id = TimestampId::rand();
+
+        let key = Some(OwnedKeyExpr::new("a/b").unwrap());
+        for (secs, value) in [(1, "v1"), (2, "v2")] {
+            storage
+                .put(
+                    key.clone(),
+                    value.into(),
+                    Encoding::TEXT_PLAIN,
+                    timestamp(id, secs),
+                )
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            storage
+                .put(
+                    key.clone(),
+                    "v0".into(),
+                    Encoding::TEXT_PLAIN,
+                    timestamp(id, 1)
+                )
+                .await
+                .unwrap(),
+            StorageInsertionResult::Outdated
+        ));
+        let latest = storage.get(key.clone(), "").await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].payload.try_to_string().unwrap(), "v2");
+        assert_eq!(latest[0].timestamp, timestamp(id, 2));
+        let history = storage.get(key.clone(), "_time=[..]").await.unwrap();
+        assert_eq!(history.len(), 2);
+
+        storage
+            .put(None, "root".into(), Encoding::TEXT_PLAIN, timestamp(id, 3))
+            .await
+            .unwrap();
+        let mut entries = storage.get_all_entries().await.unwrap();
+        entries.sort_by_key(|(_, t)| *t);
+        assert_eq!(
+            entries,
+            vec![(key.clone(), timestamp(id, 2)), (None, timestamp(id, 3))]
+        );
+
+        storage.delete(key.clone(), timestamp(id, 4)).await.unwrap();
+        assert!(storage.get(key.clone(), "").await.is_err());
+        assert_eq!(storage.get(key, "_time=[..]").await.unwrap().len(), 2);
+        storage.compact().await.unwrap();
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
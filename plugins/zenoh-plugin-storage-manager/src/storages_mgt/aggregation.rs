@@ -0,0 +1,220 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::str::FromStr;
+
+use serde_json::Value;
+use zenoh::{
+    bytes::Encoding, internal::bail, query::Parameters, time::Timestamp, Result as ZResult,
+};
+use zenoh_backend_traits::StoredData;
+
+/// The selector parameter setting the aggregation function applied to the values.
+pub(crate) const AGGREGATION_PARAM: &str = "_agg";
+/// The selector parameter setting the field of the values to aggregate, as a `.` separated path.
+pub(crate) const FIELD_PARAM: &str = "_field";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Aggregation {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl FromStr for Aggregation {
+    type Err = zenoh::Error;
+
+    fn from_str(s: &str) -> ZResult<Self> {
+        match s {
+            "count" => Ok(Aggregation::Count),
+            "sum" => Ok(Aggregation::Sum),
+            "min" => Ok(Aggregation::Min),
+            "max" => Ok(Aggregation::Max),
+            "avg" => Ok(Aggregation::Avg),
+            s => bail!(
+                "Invalid value '{}' for `{}`. Accepted values: ['count', 'sum', 'min', 'max', 'avg']",
+                s,
+                AGGREGATION_PARAM
+            ),
+        }
+    }
+}
+
+/// An aggregation of values, requested with the `_agg` and `_field` selector parameters.
+///
+/// The payloads are decoded as CBOR if their encoding is `application/cbor`, and as JSON
+/// otherwise (a plain text number being a valid JSON document). If `_field` is set, the
+/// aggregation applies to this field of the decoded values, e.g. `_field=sensor.temperature`.
+///
+/// `count` counts the values that could be decoded and have the requested field, the other
+/// aggregations apply to the numeric ones. The values without a numeric result are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AggregationQuery {
+    aggregation: Aggregation,
+    field: Option<Vec<String>>,
+}
+
+impl AggregationQuery {
+    pub(crate) fn new(aggregation: Aggregation) -> Self {
+        AggregationQuery {
+            aggregation,
+            field: None,
+        }
+    }
+
+    pub(crate) fn from_parameters(parameters: &Parameters) -> ZResult<Option<Self>> {
+        let Some(aggregation) = parameters.get(AGGREGATION_PARAM) else {
+            if parameters.contains_key(FIELD_PARAM) {
+                bail!(
+                    "`{}` requires `{}` to be set",
+                    FIELD_PARAM,
+                    AGGREGATION_PARAM
+                );
+            }
+            return Ok(None);
+        };
+        let field = match parameters.get(FIELD_PARAM) {
+            Some(field) if field.split('.').any(str::is_empty) => bail!(
+                "Invalid value '{}' for `{}`: expecting a `.` separated path of field names",
+                field,
+                FIELD_PARAM
+            ),
+            Some(field) => Some(field.split('.').map(String::from).collect()),
+            None => None,
+        };
+        Ok(Some(AggregationQuery {
+            aggregation: aggregation.parse()?,
+            field,
+        }))
+    }
+
+    /// Aggregates `values`, returning the result with the given timestamp, or `None` if there is
+    /// no value to aggregate.
+    pub(crate) fn apply<'a>(
+        &self,
+        values: impl IntoIterator<Item = &'a StoredData>,
+        timestamp: Timestamp,
+    ) -> Option<StoredData> {
+        let values = values.into_iter().filter_map(|data| self.extract(data));
+        let result = match self.aggregation {
+            Aggregation::Count => values.count().to_string(),
+            aggregation => {
+                let numbers: Vec<f64> = values.filter_map(|v| v.as_f64()).collect();
+                if numbers.is_empty() {
+                    return None;
+                }
+                let value = match aggregation {
+                    Aggregation::Min => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+                    Aggregation::Max => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                    Aggregation::Sum => numbers.iter().sum(),
+                    _ => numbers.iter().sum::<f64>() / numbers.len() as f64,
+                };
+                value.to_string()
+            }
+        };
+        Some(StoredData {
+            payload: result.into(),
+            encoding: Encoding::APPLICATION_JSON,
+            timestamp,
+        })
+    }
+
+    /// Decodes the payload of `data` and returns its requested field, if any.
+    fn extract(&self, data: &StoredData) -> Option<Value> {
+        let bytes = data.payload.to_bytes();
+        let value: Value = if data.encoding == Encoding::APPLICATION_CBOR {
+            ciborium::from_reader(bytes.as_ref()).ok()?
+        } else {
+            serde_json::from_slice(&bytes).ok()?
+        };
+        match &self.field {
+            Some(path) => path.iter().try_fold(value, |mut value, name| {
+                value.get_mut(name).map(Value::take)
+            }),
+            None => Some(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zenoh::time::{TimestampId, NTP64};
+
+    use super::*;
+
+    fn data(payload: Vec<u8>, encoding: Encoding) -> StoredData {
+        StoredData {
+            payload: payload.into(),
+            encoding,
+            timestamp: Timestamp::new(NTP64::from(Duration::from_secs(1)), TimestampId::rand()),
+        }
+    }
+
+    fn cbor(value: &Value) -> Vec<u8> {
+        let mut bytes = vec![];
+        ciborium::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_aggregation() {
+        let values = vec![
+            data(
+                br#"{"sensor":{"temperature":20}}"#.to_vec(),
+                Encoding::APPLICATION_JSON,
+            ),
+            data(
+                cbor(&serde_json::json!({"sensor": {"temperature": 25.5}})),
+                Encoding::APPLICATION_CBOR,
+            ),
+            data(
+                br#"{"sensor":{"temperature":"hot"}}"#.to_vec(),
+                Encoding::APPLICATION_JSON,
+            ),
+            data(br#"{"sensor":{}}"#.to_vec(), Encoding::APPLICATION_JSON),
+            data(b"not json".to_vec(), Encoding::TEXT_PLAIN),
+            data(b"12".to_vec(), Encoding::TEXT_PLAIN),
+        ];
+        let aggregate = |params: &str| {
+            AggregationQuery::from_parameters(&Parameters::from(params))
+                .unwrap()
+                .unwrap()
+                .apply(&values, values[0].timestamp)
+                .map(|d| d.payload.try_to_string().unwrap().into_owned())
+        };
+        let field = "_field=sensor.temperature";
+        assert_eq!(aggregate(&format!("_agg=count;{field}")).unwrap(), "3");
+        assert_eq!(aggregate(&format!("_agg=sum;{field}")).unwrap(), "45.5");
+        assert_eq!(aggregate(&format!("_agg=min;{field}")).unwrap(), "20");
+        assert_eq!(aggregate(&format!("_agg=max;{field}")).unwrap(), "25.5");
+        assert_eq!(aggregate(&format!("_agg=avg;{field}")).unwrap(), "22.75");
+        assert_eq!(aggregate("_agg=count").unwrap(), "5");
+        assert_eq!(aggregate("_agg=sum").unwrap(), "12");
+        assert!(aggregate("_agg=avg;_field=unknown").is_none());
+        assert_eq!(aggregate("_agg=count;_field=unknown").unwrap(), "0");
+
+        assert!(AggregationQuery::from_parameters(&Parameters::from(""))
+            .unwrap()
+            .is_none());
+        assert!(AggregationQuery::from_parameters(&Parameters::from("_field=a")).is_err());
+        assert!(AggregationQuery::from_parameters(&Parameters::from("_agg=median")).is_err());
+        assert!(
+            AggregationQuery::from_parameters(&Parameters::from("_agg=sum;_field=a..b")).is_err()
+        );
+    }
+}
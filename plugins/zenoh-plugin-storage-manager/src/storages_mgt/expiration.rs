@@ -0,0 +1,171 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{collections::HashMap, time::SystemTime};
+
+use zenoh::{
+    key_expr::{keyexpr, OwnedKeyExpr},
+    sample::SampleKind,
+    time::Timestamp,
+};
+use zenoh_backend_traits::config::ExpirationConfig;
+
+/// The deadlines of the entries of a Storage having a TTL, according to its [`ExpirationConfig`].
+///
+/// The deadline of an entry is computed from the timestamp of its last put, so that all the
+/// replicas of a Storage expire the same entries.
+pub(crate) struct Expirations {
+    config: ExpirationConfig,
+    deadlines: HashMap<Option<OwnedKeyExpr>, (Timestamp, SystemTime)>,
+}
+
+impl Expirations {
+    pub(crate) fn new(config: ExpirationConfig) -> Self {
+        Self {
+            config,
+            deadlines: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &ExpirationConfig {
+        &self.config
+    }
+
+    /// Updates the deadline of the entry `stripped_key`, whose full key is `key`, after it was
+    /// put or deleted at `timestamp`.
+    pub(crate) fn update(
+        &mut self,
+        stripped_key: Option<OwnedKeyExpr>,
+        key: &keyexpr,
+        kind: SampleKind,
+        timestamp: Timestamp,
+    ) {
+        if self
+            .deadlines
+            .get(&stripped_key)
+            .is_some_and(|(ts, _)| *ts > timestamp)
+        {
+            return;
+        }
+        match (kind, self.config.ttl(key)) {
+            (SampleKind::Put, Some(ttl)) => {
+                let deadline = timestamp.get_time().to_system_time() + ttl;
+                self.deadlines.insert(stripped_key, (timestamp, deadline));
+            }
+            _ => {
+                self.deadlines.remove(&stripped_key);
+            }
+        }
+    }
+
+    /// Removes and returns the entries whose deadline is reached.
+    pub(crate) fn take_expired(&mut self, now: SystemTime) -> Vec<Option<OwnedKeyExpr>> {
+        let mut expired = vec![];
+        self.deadlines.retain(|key, (_, deadline)| {
+            if *deadline <= now {
+                expired.push(key.clone());
+                false
+            } else {
+                true
+            }
+        });
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use zenoh::time::{TimestampId, NTP64};
+    use zenoh_backend_traits::config::ExpirationRule;
+
+    use super::*;
+
+    fn timestamp(secs: u64) -> Timestamp {
+        Timestamp::new(NTP64::from(Duration::from_secs(secs)), TimestampId::rand())
+    }
+
+    #[test]
+    fn test_expirations() {
+        let mut expirations = Expirations::new(ExpirationConfig {
+            rules: vec![ExpirationRule {
+                key_expr: OwnedKeyExpr::new("test/presence/**").unwrap(),
+                ttl: Duration::from_secs(10),
+            }],
+            period: Duration::from_secs(1),
+            publish_deletes: false,
+        });
+        let key = |k: &str| OwnedKeyExpr::new(k).unwrap();
+        let presence = Some(key("presence/a"));
+
+        // Only the keys matching a rule expire
+        expirations.update(
+            Some(key("other")),
+            &key("test/other"),
+            SampleKind::Put,
+            timestamp(0),
+        );
+        expirations.update(
+            presence.clone(),
+            &key("test/presence/a"),
+            SampleKind::Put,
+            timestamp(0),
+        );
+        assert!(expirations
+            .take_expired(UNIX_EPOCH + Duration::from_secs(9))
+            .is_empty());
+
+        // A newer put postpones the deadline, an older one is ignored
+        expirations.update(
+            presence.clone(),
+            &key("test/presence/a"),
+            SampleKind::Put,
+            timestamp(5),
+        );
+        expirations.update(
+            presence.clone(),
+            &key("test/presence/a"),
+            SampleKind::Put,
+            timestamp(1),
+        );
+        assert!(expirations
+            .take_expired(UNIX_EPOCH + Duration::from_secs(14))
+            .is_empty());
+        assert_eq!(
+            expirations.take_expired(UNIX_EPOCH + Duration::from_secs(15)),
+            vec![presence.clone()]
+        );
+        assert!(expirations
+            .take_expired(UNIX_EPOCH + Duration::from_secs(100))
+            .is_empty());
+
+        // A delete cancels the expiration
+        expirations.update(
+            presence.clone(),
+            &key("test/presence/a"),
+            SampleKind::Put,
+            timestamp(20),
+        );
+        expirations.update(
+            presence,
+            &key("test/presence/a"),
+            SampleKind::Delete,
+            timestamp(21),
+        );
+        assert!(expirations
+            .take_expired(UNIX_EPOCH + Duration::from_secs(100))
+            .is_empty());
+    }
+}
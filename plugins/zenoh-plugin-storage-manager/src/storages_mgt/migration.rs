@@ -0,0 +1,206 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use zenoh::{internal::bail, key_expr::keyexpr, Result as ZResult};
+use zenoh_backend_traits::{
+    config::{MigrationConfig, MigrationMode},
+    Migrator, StoredData,
+};
+
+lazy_static::lazy_static! {
+    static ref MIGRATORS: RwLock<HashMap<String, Arc<dyn Migrator>>> = RwLock::new(HashMap::new());
+}
+
+/// Registers `migrator` under `name`, for the `migration` rules of the storages referring to it.
+///
+/// The migrators must be registered before the storages using them are created, the creation of
+/// a storage referring to an unknown migrator failing. Registering a migrator under an existing
+/// name replaces it for the storages created afterwards.
+pub fn register_migrator(name: impl Into<String>, migrator: Arc<dyn Migrator>) {
+    MIGRATORS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name.into(), migrator);
+}
+
+/// The migrations of the payloads of a Storage, according to its [`MigrationConfig`].
+pub(crate) struct Migrations {
+    config: MigrationConfig,
+    migrators: HashMap<String, Arc<dyn Migrator>>,
+    migrated: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl Migrations {
+    /// Resolves the migrators the rules of `config` refer to, failing if one is not registered.
+    pub(crate) fn new(config: MigrationConfig) -> ZResult<Self> {
+        let registered = MIGRATORS.read().unwrap_or_else(|e| e.into_inner());
+        let mut migrators = HashMap::new();
+        for rule in &config.rules {
+            match registered.get(&rule.migrator) {
+                Some(migrator) => {
+                    migrators.insert(rule.migrator.clone(), migrator.clone());
+                }
+                None => bail!("Unknown migrator '{}'", rule.migrator),
+            }
+        }
+        drop(registered);
+        Ok(Self {
+            config,
+            migrators,
+            migrated: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns `true` if some entries are migrated when the Storage is loaded.
+    pub(crate) fn has_eager_rules(&self) -> bool {
+        self.config
+            .rules
+            .iter()
+            .any(|rule| rule.mode == MigrationMode::Eager)
+    }
+
+    /// Returns `true` if the entries of `key_expr` are migrated when the Storage is loaded.
+    pub(crate) fn is_eager(&self, key_expr: &keyexpr) -> bool {
+        self.config
+            .rule(key_expr)
+            .is_some_and(|rule| rule.mode == MigrationMode::Eager)
+    }
+
+    /// Returns the migrated `data` stored for `key_expr`, with the same timestamp, or `None` if
+    /// no rule applies, if the data is up to date or if its migration failed.
+    pub(crate) fn migrate(&self, key_expr: &keyexpr, data: &StoredData) -> Option<StoredData> {
+        let rule = self.config.rule(key_expr)?;
+        let migrator = self.migrators.get(&rule.migrator)?;
+        match migrator.migrate(key_expr, data) {
+            Ok(Some(migrated)) => {
+                self.migrated.fetch_add(1, Ordering::Relaxed);
+                Some(StoredData {
+                    timestamp: data.timestamp,
+                    ..migrated
+                })
+            }
+            Ok(None) => None,
+            Err(e) => {
+                self.failed.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    "Migrator '{}' failed to migrate < {} >: {}",
+                    rule.migrator,
+                    key_expr,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "migrated": self.migrated.load(Ordering::Relaxed),
+            "failed": self.failed.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zenoh::{
+        bytes::Encoding,
+        key_expr::OwnedKeyExpr,
+        time::{Timestamp, TimestampId, NTP64},
+    };
+    use zenoh_backend_traits::config::MigrationRule;
+
+    use super::*;
+
+    // Prefixes the payloads with "v2:", failing on empty payloads
+    struct V2;
+
+    impl Migrator for V2 {
+        fn migrate(&self, _key_expr: &keyexpr, data: &StoredData) -> ZResult<Option<StoredData>> {
+            let payload = data.payload.try_to_string()?;
+            if payload.is_empty() {
+                bail!("empty payload");
+            }
+            if payload.starts_with("v2:") {
+                return Ok(None);
+            }
+            Ok(Some(StoredData {
+                payload: format!("v2:{payload}").into(),
+                encoding: data.encoding.clone(),
+                timestamp: data.timestamp,
+            }))
+        }
+    }
+
+    fn data(payload: &str) -> StoredData {
+        StoredData {
+            payload: payload.into(),
+            encoding: Encoding::TEXT_PLAIN,
+            timestamp: Timestamp::new(NTP64::from(Duration::from_secs(1)), TimestampId::rand()),
+        }
+    }
+
+    fn rule(key_expr: &str, migrator: &str, mode: MigrationMode) -> MigrationRule {
+        MigrationRule {
+            key_expr: OwnedKeyExpr::new(key_expr).unwrap(),
+            migrator: migrator.into(),
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_migrations() {
+        register_migrator("test_migrations_v2", Arc::new(V2));
+        let unknown = MigrationConfig {
+            rules: vec![rule("a/**", "test_migrations_unknown", MigrationMode::Lazy)],
+        };
+        assert!(Migrations::new(unknown).is_err());
+
+        let migrations = Migrations::new(MigrationConfig {
+            rules: vec![
+                rule("a/eager/**", "test_migrations_v2", MigrationMode::Eager),
+                rule("a/**", "test_migrations_v2", MigrationMode::Lazy),
+            ],
+        })
+        .unwrap();
+        assert!(migrations.has_eager_rules());
+        assert!(migrations.is_eager(keyexpr::new("a/eager/x").unwrap()));
+        assert!(!migrations.is_eager(keyexpr::new("a/x").unwrap()));
+
+        let key = keyexpr::new("a/x").unwrap();
+        let old = data("value");
+        let migrated = migrations.migrate(key, &old).unwrap();
+        assert_eq!(migrated.payload.try_to_string().unwrap(), "v2:value");
+        assert_eq!(migrated.timestamp, old.timestamp);
+        assert!(migrations.migrate(key, &migrated).is_none());
+        assert!(migrations.migrate(key, &data("")).is_none());
+        assert!(migrations
+            .migrate(keyexpr::new("b/x").unwrap(), &old)
+            .is_none());
+        assert_eq!(migrations.to_json()["migrated"], 1);
+        assert_eq!(migrations.to_json()["failed"], 1);
+    }
+}
@@ -15,13 +15,23 @@
 use std::{collections::HashMap, sync::Arc};
 
 use tokio::sync::{broadcast::Sender, Mutex, RwLock};
-use zenoh::{internal::bail, session::Session, Result as ZResult};
+use zenoh::{internal::bail, key_expr::OwnedKeyExpr, session::Session, Result as ZResult};
 use zenoh_backend_traits::{config::StorageConfig, History, VolumeInstance};
 
-use crate::replication::{Action, Event, LogLatest, LogLatestKey, ReplicationService};
-
+use crate::replication::{
+    Action, Event, LogLatest, LogLatestKey, ReplicationMetrics, ReplicationService,
+};
+
+mod aggregation;
+pub(crate) mod expiration;
+mod migration;
+pub use migration::register_migrator;
+use migration::Migrations;
+mod operations;
+mod quota;
 pub(crate) mod service;
 pub(crate) use service::StorageService;
+pub(crate) mod time_series;
 
 #[derive(Clone)]
 pub enum StorageMessage {
@@ -56,7 +66,11 @@ pub(crate) async fn create_and_start_storage(
     zenoh_session: Arc<Session>,
 ) -> ZResult<Sender<StorageMessage>> {
     tracing::trace!("Create storage '{}'", &admin_key);
-    let capability = backend.get_capability();
+    let capability = backend.get_storage_capability(&config);
+    let migrations = match config.migration.clone().map(Migrations::new).transpose() {
+        Ok(migrations) => migrations.map(Arc::new),
+        Err(e) => bail!("Invalid `migration` of storage '{}': {}", admin_key, e),
+    };
     let storage = backend.create_storage(config.clone()).await?;
 
     // Ex: @/390CEC11A1E34977A1C609A35BC015E6/router/status/plugins/storage_manager/storages/demo1
@@ -65,6 +79,7 @@ pub(crate) async fn create_and_start_storage(
     let uuid = parts[2];
     let storage_name = parts[7];
     let name = format!("{uuid}/{storage_name}");
+    let operations_key_expr = OwnedKeyExpr::try_from(format!("{admin_key}/*"))?;
 
     let (tx, rx_storage) = tokio::sync::broadcast::channel(1);
     let rx_replication = tx.subscribe();
@@ -104,6 +119,10 @@ pub(crate) async fn create_and_start_storage(
     }
 
     let latest_updates = Arc::new(RwLock::new(latest_updates));
+    let replication_metrics = config
+        .replication
+        .as_ref()
+        .map(|replica_config| Arc::new(ReplicationMetrics::new(replica_config.interval)));
 
     let storage = Arc::new(Mutex::new(storage));
 
@@ -126,13 +145,17 @@ pub(crate) async fn create_and_start_storage(
                 storage,
                 capability,
                 CacheLatest::new(latest_updates.clone(), replication_log.clone()),
+                replication_metrics.clone(),
+                migrations,
             )
             .await,
         );
 
         // Testing if the `replication_log` is set is equivalent to testing if the `replication` is
         // set: the `replication_log` is only set when the latter is.
-        if let Some(replication_log) = replication_log {
+        if let (Some(replication_log), Some(replication_metrics)) =
+            (replication_log, replication_metrics)
+        {
             tracing::debug!(
                 "Starting replication of storage '{}' on keyexpr '{}'",
                 name,
@@ -145,13 +168,14 @@ pub(crate) async fn create_and_start_storage(
                 config.key_expr,
                 replication_log,
                 latest_updates,
+                replication_metrics,
                 rx_replication,
             )
             .await;
         }
 
         storage_service
-            .start_storage_queryable_subscriber(rx_storage)
+            .start_storage_queryable_subscriber(operations_key_expr, rx_storage)
             .await;
     });
 
@@ -0,0 +1,252 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The administration operations of a Storage, triggered by a query on
+//! `<storage admin key>/<operation>`:
+//!
+//! - `compact`: compacts the Storage, if its backend supports it.
+//! - `export`: exports the latest value of each key of the Storage in a snapshot, sent as the
+//!   payload of the reply.
+//! - `import`: imports a snapshot, given as the payload of the query. The imported values are
+//!   processed as publications: they are only stored if they are more recent than the stored or
+//!   deleted ones.
+//!
+//! - `alignment`: replies with the [AlignmentStatus] of the Storage, if it is replicated.
+//!
+//! As they modify or expose the Storage, these operations, except `alignment`, are only
+//! available if `adminspace.permissions.write` is enabled.
+//!
+//! [AlignmentStatus]: crate::AlignmentStatus
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    query::Query,
+    sample::SampleBuilder,
+    time::Timestamp,
+    Result as ZResult,
+};
+
+use super::StorageService;
+
+const OPERATION_ALIGNMENT: &str = "alignment";
+const OPERATION_COMPACT: &str = "compact";
+const OPERATION_EXPORT: &str = "export";
+const OPERATION_IMPORT: &str = "import";
+
+/// The portable content of a Storage: the latest value of each of its keys.
+///
+/// The keys are not stripped, so that a snapshot can be imported in a Storage configured with a
+/// different `strip_prefix`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+    entries: Vec<SnapshotEntry>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SnapshotEntry {
+    key: String,
+    payload: Vec<u8>,
+    encoding: String,
+    timestamp: String,
+}
+
+impl Snapshot {
+    fn to_bytes(&self) -> ZResult<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> ZResult<Self> {
+        bincode::deserialize(bytes).map_err(|e| zerror!("Invalid snapshot: {e}").into())
+    }
+}
+
+impl StorageService {
+    /// Performs the operation requested by `query` and replies with its outcome.
+    ///
+    /// Queries with a wildcard key expression, e.g. on the whole administration space, are
+    /// ignored.
+    pub(crate) async fn reply_operation(&self, query: ZResult<Query>) {
+        let query = match query {
+            Ok(query) => query,
+            Err(e) => {
+                tracing::error!("Error in query: {}", e);
+                return;
+            }
+        };
+        if query.key_expr().is_wild() {
+            return;
+        }
+
+        let read_only = query
+            .key_expr()
+            .chunks()
+            .next_back()
+            .map(|chunk| chunk.as_str())
+            == Some(OPERATION_ALIGNMENT);
+        let write_permission = self.session.config().lock().adminspace.permissions().write;
+        let result = match read_only || write_permission {
+            true => self.perform_operation(&query).await,
+            false => Err(zerror!(
+                "Storage operations require `adminspace.permissions.write` to be enabled"
+            )
+            .into()),
+        };
+        let reply = match result {
+            Ok((payload, encoding)) => {
+                query
+                    .reply(query.key_expr(), payload)
+                    .encoding(encoding)
+                    .await
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Storage '{}' failed to perform operation < {} >: {e}",
+                    self.name,
+                    query.key_expr()
+                );
+                query.reply_err(e.to_string()).await
+            }
+        };
+        if let Err(e) = reply {
+            tracing::warn!(
+                "Storage '{}' raised an error replying a query: {}",
+                self.name,
+                e
+            )
+        }
+    }
+
+    async fn perform_operation(&self, query: &Query) -> ZResult<(ZBytes, Encoding)> {
+        match query
+            .key_expr()
+            .chunks()
+            .next_back()
+            .map(|chunk| chunk.as_str())
+        {
+            Some(OPERATION_ALIGNMENT) => match &self.replication_metrics {
+                Some(metrics) => Ok((
+                    serde_json::to_vec(&metrics.alignment_status())?.into(),
+                    Encoding::APPLICATION_JSON,
+                )),
+                None => bail!("Storage '{}' is not replicated", self.name),
+            },
+            Some(OPERATION_COMPACT) => {
+                self.storage.lock().await.compact().await?;
+                tracing::info!("Storage '{}' compacted", self.name);
+                Ok((r#"{"compacted":true}"#.into(), Encoding::APPLICATION_JSON))
+            }
+            Some(OPERATION_EXPORT) => {
+                let snapshot = self.export().await?;
+                tracing::info!(
+                    "Storage '{}' exported {} entries",
+                    self.name,
+                    snapshot.entries.len()
+                );
+                Ok((
+                    snapshot.to_bytes()?.into(),
+                    Encoding::APPLICATION_OCTET_STREAM,
+                ))
+            }
+            Some(OPERATION_IMPORT) => {
+                let Some(payload) = query.payload() else {
+                    bail!("The snapshot to import must be given as payload");
+                };
+                let count = self
+                    .import(Snapshot::from_bytes(&payload.to_bytes())?)
+                    .await?;
+                tracing::info!("Storage '{}' imported {count} entries", self.name);
+                Ok((
+                    format!(r#"{{"imported":{count}}}"#).into(),
+                    Encoding::APPLICATION_JSON,
+                ))
+            }
+            _ => bail!("Unknown storage operation < {} >", query.key_expr()),
+        }
+    }
+
+    async fn export(&self) -> ZResult<Snapshot> {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let mut storage = self.storage.lock().await;
+        let mut snapshot = Snapshot::default();
+        for (stripped_key, timestamp) in storage.get_all_entries().await? {
+            let key = crate::prefix(prefix, stripped_key.as_ref())?;
+            let data = match storage.get(stripped_key, "").await {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::debug!("Skipping < {key} > in export: {e:?}");
+                    continue;
+                }
+            };
+            if let Some(data) = data.into_iter().find(|d| d.timestamp == timestamp) {
+                snapshot.entries.push(SnapshotEntry {
+                    key: key.to_string(),
+                    payload: data.payload.to_bytes().into_owned(),
+                    encoding: data.encoding.to_string(),
+                    timestamp: data.timestamp.to_string(),
+                });
+            }
+        }
+        Ok(snapshot)
+    }
+
+    async fn import(&self, snapshot: Snapshot) -> ZResult<usize> {
+        let mut count = 0;
+        for entry in snapshot.entries {
+            let key = OwnedKeyExpr::try_from(entry.key)?;
+            if !self.configuration.key_expr.includes(&key) {
+                tracing::debug!(
+                    "Storage '{}' skipping imported key < {} >: not included in its key expression",
+                    self.name,
+                    key
+                );
+                continue;
+            }
+            let timestamp = Timestamp::from_str(&entry.timestamp)
+                .map_err(|e| zerror!("Invalid timestamp '{}': {e:?}", entry.timestamp))?;
+            let sample = SampleBuilder::put(key, entry.payload)
+                .encoding(Encoding::from(entry.encoding))
+                .timestamp(timestamp)
+                .into();
+            self.process_sample(sample).await?;
+            count += 1;
+        }
+        self.enforce_quota().await;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_serialization() {
+        let snapshot = Snapshot {
+            entries: vec![SnapshotEntry {
+                key: "demo/a".into(),
+                payload: b"value".to_vec(),
+                encoding: Encoding::TEXT_PLAIN.to_string(),
+                timestamp: "7054123566570568799/bc779a06d7e049bd88c3ff3db0c17fcc".into(),
+            }],
+        };
+        let bytes = snapshot.to_bytes().unwrap();
+        assert_eq!(Snapshot::from_bytes(&bytes).unwrap(), snapshot);
+        assert!(Snapshot::from_bytes(b"not a snapshot").is_err());
+    }
+}
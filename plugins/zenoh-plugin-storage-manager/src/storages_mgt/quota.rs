@@ -0,0 +1,205 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::collections::{BTreeMap, HashMap};
+
+use zenoh::{key_expr::OwnedKeyExpr, time::Timestamp};
+use zenoh_backend_traits::config::{EvictionPolicy, QuotaConfig};
+
+struct Usage {
+    size: u64,
+    rank: (u64, u64),
+}
+
+/// The usage of a Storage, i.e. its number of entries and the total size of their payloads, and
+/// its enforcement according to its [`QuotaConfig`].
+///
+/// The entries are ranked according to the eviction policy, so that the first one to evict can be
+/// found without going through all of them: by order of access for `lru`, by timestamp for
+/// `oldest_first`.
+pub(crate) struct Quota {
+    config: QuotaConfig,
+    entries: HashMap<Option<OwnedKeyExpr>, Usage>,
+    ranking: BTreeMap<(u64, u64), Option<OwnedKeyExpr>>,
+    bytes: u64,
+    access_counter: u64,
+    evicted: u64,
+    rejected: u64,
+}
+
+impl Quota {
+    pub(crate) fn new(config: QuotaConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            ranking: BTreeMap::new(),
+            bytes: 0,
+            access_counter: 0,
+            evicted: 0,
+            rejected: 0,
+        }
+    }
+
+    /// Returns `false` if the put of a payload of `size` bytes for `key` must be rejected, i.e. if
+    /// it would exceed the limits of the Storage and the eviction policy is `reject`.
+    pub(crate) fn admits(&mut self, key: &Option<OwnedKeyExpr>, size: u64) -> bool {
+        if self.config.eviction != EvictionPolicy::Reject {
+            return true;
+        }
+        let (entries, bytes) = match self.entries.get(key) {
+            Some(usage) => (self.entries.len(), self.bytes - usage.size + size),
+            None => (self.entries.len() + 1, self.bytes + size),
+        };
+        let admitted = self.within_limits(entries, bytes);
+        if !admitted {
+            self.rejected += 1;
+        }
+        admitted
+    }
+
+    /// Records that a payload of `size` bytes was stored for `key` at `timestamp`.
+    pub(crate) fn insert(&mut self, key: Option<OwnedKeyExpr>, size: u64, timestamp: Timestamp) {
+        self.remove(&key);
+        self.access_counter += 1;
+        let rank = match self.config.eviction {
+            EvictionPolicy::OldestFirst => (timestamp.get_time().as_u64(), self.access_counter),
+            EvictionPolicy::Reject | EvictionPolicy::Lru => (self.access_counter, 0),
+        };
+        self.bytes += size;
+        self.ranking.insert(rank, key.clone());
+        self.entries.insert(key, Usage { size, rank });
+    }
+
+    /// Records that `key` was deleted.
+    pub(crate) fn remove(&mut self, key: &Option<OwnedKeyExpr>) {
+        if let Some(usage) = self.entries.remove(key) {
+            self.bytes -= usage.size;
+            self.ranking.remove(&usage.rank);
+        }
+    }
+
+    /// Records that `key` was accessed, which postpones its eviction with the `lru` policy.
+    pub(crate) fn touch(&mut self, key: &Option<OwnedKeyExpr>) {
+        if self.config.eviction != EvictionPolicy::Lru {
+            return;
+        }
+        if let Some(usage) = self.entries.get_mut(key) {
+            self.access_counter += 1;
+            let rank = (self.access_counter, 0);
+            if let Some(key) = self.ranking.remove(&usage.rank) {
+                self.ranking.insert(rank, key);
+            }
+            usage.rank = rank;
+        }
+    }
+
+    /// Removes and returns the entries to evict to bring the Storage back within its limits.
+    pub(crate) fn take_evicted(&mut self) -> Vec<Option<OwnedKeyExpr>> {
+        let mut evicted = vec![];
+        if self.config.eviction == EvictionPolicy::Reject {
+            return evicted;
+        }
+        while !self.within_limits(self.entries.len(), self.bytes) {
+            let Some((_, key)) = self.ranking.pop_first() else {
+                break;
+            };
+            if let Some(usage) = self.entries.remove(&key) {
+                self.bytes -= usage.size;
+            }
+            evicted.push(key);
+        }
+        self.evicted += evicted.len() as u64;
+        evicted
+    }
+
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "entries": self.entries.len(),
+            "bytes": self.bytes,
+            "max_entries": self.config.max_entries,
+            "max_bytes": self.config.max_bytes,
+            "evicted": self.evicted,
+            "rejected": self.rejected,
+        })
+    }
+
+    fn within_limits(&self, entries: usize, bytes: u64) -> bool {
+        self.config.max_entries.map_or(true, |max| entries <= max)
+            && self.config.max_bytes.map_or(true, |max| bytes <= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zenoh::time::{TimestampId, NTP64};
+
+    use super::*;
+
+    fn timestamp(secs: u64) -> Timestamp {
+        Timestamp::new(NTP64::from(Duration::from_secs(secs)), TimestampId::rand())
+    }
+
+    fn key(k: &str) -> Option<OwnedKeyExpr> {
+        Some(OwnedKeyExpr::new(k).unwrap())
+    }
+
+    fn quota(eviction: EvictionPolicy) -> Quota {
+        Quota::new(QuotaConfig {
+            max_entries: Some(2),
+            max_bytes: Some(100),
+            eviction,
+        })
+    }
+
+    #[test]
+    fn test_quota_reject() {
+        let mut quota = quota(EvictionPolicy::Reject);
+        assert!(quota.admits(&key("a"), 10));
+        quota.insert(key("a"), 10, timestamp(1));
+        quota.insert(key("b"), 10, timestamp(2));
+
+        // A new key exceeds `max_entries`, an update of an existing key does not
+        assert!(!quota.admits(&key("c"), 10));
+        assert!(quota.admits(&key("a"), 90));
+        assert!(!quota.admits(&key("a"), 91));
+        assert!(quota.take_evicted().is_empty());
+
+        quota.remove(&key("a"));
+        assert!(quota.admits(&key("c"), 10));
+        assert_eq!(quota.to_json()["rejected"], 2);
+    }
+
+    #[test]
+    fn test_quota_eviction() {
+        let mut quota = quota(EvictionPolicy::OldestFirst);
+        quota.insert(key("a"), 10, timestamp(2));
+        quota.insert(key("b"), 10, timestamp(1));
+        quota.insert(key("c"), 10, timestamp(3));
+        assert_eq!(quota.take_evicted(), vec![key("b")]);
+        quota.insert(key("d"), 95, timestamp(4));
+        assert_eq!(quota.take_evicted(), vec![key("a"), key("c")]);
+        assert_eq!(quota.to_json()["bytes"], 95);
+
+        let mut quota = self::quota(EvictionPolicy::Lru);
+        assert!(quota.admits(&key("a"), 1000));
+        quota.insert(key("a"), 10, timestamp(2));
+        quota.insert(key("b"), 10, timestamp(1));
+        quota.touch(&key("a"));
+        quota.insert(key("c"), 10, timestamp(3));
+        assert_eq!(quota.take_evicted(), vec![key("b")]);
+        assert_eq!(quota.to_json()["evicted"], 1);
+    }
+}
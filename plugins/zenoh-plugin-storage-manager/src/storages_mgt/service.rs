@@ -13,13 +13,16 @@
 //
 
 use std::{
-    collections::HashSet,
     str::{self},
-    sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
+use futures::future::OptionFuture;
 use tokio::sync::{broadcast::Receiver, Mutex, RwLock, RwLockWriteGuard};
 use zenoh::{
     bytes::{Encoding, ZBytes},
@@ -31,19 +34,27 @@ use zenoh::{
         },
         OwnedKeyExpr,
     },
-    sample::{Sample, SampleBuilder, SampleFields, SampleKind},
+    query::{Query, ZenohParameters},
+    sample::{Locality, Sample, SampleBuilder, SampleFields, SampleKind},
     session::Session,
     time::{Timestamp, NTP64},
     Result as ZResult,
 };
 use zenoh_backend_traits::{
-    config::{GarbageCollectionConfig, StorageConfig},
+    config::{GarbageCollectionConfig, StorageConfig, WildcardUpdatesMode},
     Capability, History, StorageInsertionResult, StoredData,
 };
 
-use super::LatestUpdates;
+use super::{
+    aggregation::AggregationQuery,
+    expiration::Expirations,
+    migration::Migrations,
+    quota::Quota,
+    time_series::{Downsampling, TimeSeries},
+    LatestUpdates,
+};
 use crate::{
-    replication::{Action, Event},
+    replication::{Action, Event, ReplicationMetrics},
     storages_mgt::{CacheLatest, StorageMessage},
 };
 
@@ -79,14 +90,20 @@ impl From<Update> for StoredData {
 
 #[derive(Clone)]
 pub struct StorageService {
-    session: Arc<Session>,
+    pub(crate) session: Arc<Session>,
     pub(crate) configuration: StorageConfig,
-    name: String,
+    pub(crate) name: String,
     pub(crate) storage: Arc<Mutex<Box<dyn zenoh_backend_traits::Storage>>>,
     capability: Capability,
     pub(crate) wildcard_deletes: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     pub(crate) wildcard_puts: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     cache_latest: CacheLatest,
+    time_series: Option<Arc<RwLock<TimeSeries>>>,
+    expirations: Option<Arc<Mutex<Expirations>>>,
+    quota: Option<Arc<Mutex<Quota>>>,
+    migrations: Option<Arc<Migrations>>,
+    pub(crate) replication_metrics: Option<Arc<ReplicationMetrics>>,
+    gc_collected: Arc<AtomicU64>,
 }
 
 impl StorageService {
@@ -97,7 +114,21 @@ impl StorageService {
         storage: Arc<Mutex<Box<dyn zenoh_backend_traits::Storage>>>,
         capability: Capability,
         cache_latest: CacheLatest,
+        replication_metrics: Option<Arc<ReplicationMetrics>>,
+        migrations: Option<Arc<Migrations>>,
     ) -> Self {
+        let time_series = config
+            .time_series
+            .clone()
+            .map(|time_series| Arc::new(RwLock::new(TimeSeries::new(time_series))));
+        let expirations = config
+            .expiration
+            .clone()
+            .map(|expiration| Arc::new(Mutex::new(Expirations::new(expiration))));
+        let quota = config
+            .quota
+            .clone()
+            .map(|quota| Arc::new(Mutex::new(Quota::new(quota))));
         StorageService {
             session,
             configuration: config,
@@ -107,11 +138,18 @@ impl StorageService {
             wildcard_deletes: Arc::new(RwLock::new(KeBoxTree::default())),
             wildcard_puts: Arc::new(RwLock::new(KeBoxTree::default())),
             cache_latest,
+            time_series,
+            expirations,
+            quota,
+            migrations,
+            replication_metrics,
+            gc_collected: Arc::new(AtomicU64::new(0)),
         }
     }
 
     pub(crate) async fn start_storage_queryable_subscriber(
         self: Arc<Self>,
+        operations_key_expr: OwnedKeyExpr,
         mut rx: Receiver<StorageMessage>,
     ) {
         // start periodic GC event
@@ -132,29 +170,64 @@ impl StorageService {
                 wildcard_deletes: self.wildcard_deletes.clone(),
                 wildcard_puts: self.wildcard_puts.clone(),
                 latest_updates,
+                time_series: self.time_series.clone(),
+                collected: self.gc_collected.clone(),
             },
         );
         t.add_async(gc).await;
 
+        let expiration_period = match &self.expirations {
+            Some(expirations) => {
+                self.register_expirations().await;
+                expirations.lock().await.config().period
+            }
+            None => Duration::MAX,
+        };
+        let mut expiration_interval = tokio::time::interval(expiration_period);
+
+        // The usage is registered once the entries are migrated, which may change their size
+        self.migrate_entries().await;
+        if self.quota.is_some() {
+            self.register_quota_usage().await;
+        }
+
         let storage_key_expr = &self.configuration.key_expr;
 
-        // subscribe on key_expr
-        let storage_sub = match self.session.declare_subscriber(storage_key_expr).await {
-            Ok(storage_sub) => storage_sub,
-            Err(e) => {
-                tracing::error!("Error starting storage '{}': {}", self.name, e);
-                return;
+        // subscribe on key_expr, unless the storage is read-only
+        let storage_sub = if self.configuration.read_only {
+            None
+        } else {
+            match self.session.declare_subscriber(storage_key_expr).await {
+                Ok(storage_sub) => Some(storage_sub),
+                Err(e) => {
+                    tracing::error!("Error starting storage '{}': {}", self.name, e);
+                    return;
+                }
             }
         };
 
-        // answer to queries on key_expr
-        let storage_queryable = match self
-            .session
-            .declare_queryable(storage_key_expr)
-            .complete(self.configuration.complete)
-            .await
+        // answer to queries on key_expr, unless the storage is write-only
+        let storage_queryable = if self.configuration.write_only {
+            None
+        } else {
+            match self
+                .session
+                .declare_queryable(storage_key_expr)
+                .complete(self.configuration.complete)
+                .await
+            {
+                Ok(storage_queryable) => Some(storage_queryable),
+                Err(e) => {
+                    tracing::error!("Error starting storage '{}': {}", self.name, e);
+                    return;
+                }
+            }
+        };
+
+        // answer to administration operations on the storage
+        let operations_queryable = match self.session.declare_queryable(&operations_key_expr).await
         {
-            Ok(storage_queryable) => storage_queryable,
+            Ok(operations_queryable) => operations_queryable,
             Err(e) => {
                 tracing::error!("Error starting storage '{}': {}", self.name, e);
                 return;
@@ -170,8 +243,8 @@ impl StorageService {
         tokio::task::spawn(async move {
             loop {
                 tokio::select!(
-                    // on sample for key_expr
-                    sample = storage_sub.recv_async() => {
+                    // on sample for key_expr (the branch is disabled if there is no subscriber)
+                    Some(sample) = OptionFuture::from(storage_sub.as_ref().map(|sub| sub.recv_async())) => {
                         let sample = match sample {
                             Ok(sample) => sample,
                             Err(e) => {
@@ -184,11 +257,20 @@ impl StorageService {
                         if let Err(e) = self.process_sample(sample).await {
                             tracing::error!("{e:?}");
                         }
+                        self.enforce_quota().await;
                     },
                     // on query on key_expr
-                    query = storage_queryable.recv_async() => {
+                    Some(query) = OptionFuture::from(storage_queryable.as_ref().map(|queryable| queryable.recv_async())) => {
                         self.reply_query(query).await;
                     },
+                    // on administration operation
+                    operation = operations_queryable.recv_async() => {
+                        self.reply_operation(operation).await;
+                    },
+                    // on expiration check
+                    _ = expiration_interval.tick(), if self.expirations.is_some() => {
+                        self.expire_entries().await;
+                    },
                     // on storage handle drop
                     Ok(message) = rx.recv() => {
                         match message {
@@ -198,8 +280,29 @@ impl StorageService {
                             },
                             StorageMessage::GetStatus(tx) => {
                                 let storage = self.storage.lock().await;
-                                std::mem::drop(tx.send(storage.get_admin_status()).await);
+                                let mut status = storage.get_admin_status();
                                 drop(storage);
+                                if let serde_json::Value::Object(status) = &mut status {
+                                    status.insert(
+                                        "garbage_collection".into(),
+                                        self.garbage_collection_status().await,
+                                    );
+                                    if let Some(quota) = &self.quota {
+                                        status.insert("quota".into(), quota.lock().await.to_json());
+                                    }
+                                    if let Some(migrations) = &self.migrations {
+                                        status.insert("migration".into(), migrations.to_json());
+                                    }
+                                    if let Some(metrics) = &self.replication_metrics {
+                                        status.insert("replication".into(), metrics.to_json());
+                                        status.insert(
+                                            "alignment".into(),
+                                            serde_json::to_value(metrics.alignment_status())
+                                                .unwrap_or_default(),
+                                        );
+                                    }
+                                }
+                                std::mem::drop(tx.send(status).await);
                             }
                         };
                     },
@@ -328,6 +431,18 @@ impl StorageService {
                 }
             }
 
+            if let (Some(quota), SampleKind::Put) = (&self.quota, sample.kind()) {
+                let size = sample_to_store.payload().len() as u64;
+                if !quota.lock().await.admits(&stripped_key, size) {
+                    tracing::debug!(
+                        "Storage '{}' rejected < {} >: its quota would be exceeded",
+                        self.name,
+                        k
+                    );
+                    continue;
+                }
+            }
+
             let mut storage = self.storage.lock().await;
             let storage_result = match sample.kind() {
                 SampleKind::Put => {
@@ -357,6 +472,36 @@ impl StorageService {
                     if let Some(mut cache_guard) = cache_guard {
                         cache_guard.insert(new_event.log_key(), new_event);
                     }
+                    if let (Some(time_series), SampleKind::Put) = (&self.time_series, sample.kind())
+                    {
+                        time_series.write().await.insert(
+                            stripped_key.clone(),
+                            StoredData {
+                                payload: sample_to_store.payload().clone(),
+                                encoding: sample_to_store.encoding().clone(),
+                                timestamp: sample_to_store_timestamp,
+                            },
+                        );
+                    }
+                    if let Some(quota) = &self.quota {
+                        let mut quota = quota.lock().await;
+                        match sample.kind() {
+                            SampleKind::Put => quota.insert(
+                                stripped_key.clone(),
+                                sample_to_store.payload().len() as u64,
+                                sample_to_store_timestamp,
+                            ),
+                            SampleKind::Delete => quota.remove(&stripped_key),
+                        }
+                    }
+                    if let Some(expirations) = &self.expirations {
+                        expirations.lock().await.update(
+                            stripped_key.clone(),
+                            &k,
+                            sample.kind(),
+                            sample_to_store_timestamp,
+                        );
+                    }
                 }
                 Err(e) => {
                     // TODO In case of a wildcard update, multiple keys can be updated. What should
@@ -369,12 +514,316 @@ impl StorageService {
         Ok(())
     }
 
+    /// Registers the deadlines of the entries already present in the Storage, e.g. restored from
+    /// a persistent volume.
+    async fn register_expirations(&self) {
+        let Some(expirations) = &self.expirations else {
+            return;
+        };
+        let entries = match self.storage.lock().await.get_all_entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!(
+                    "Storage '{}' failed to retrieve its entries to expire: {e:?}",
+                    self.name
+                );
+                return;
+            }
+        };
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let mut expirations = expirations.lock().await;
+        for (stripped_key, timestamp) in entries {
+            match crate::prefix(prefix, stripped_key.as_ref()) {
+                Ok(key) => expirations.update(stripped_key, &key, SampleKind::Put, timestamp),
+                Err(e) => tracing::error!("{e:?}"),
+            }
+        }
+    }
+
+    /// Registers the usage of the entries already present in the Storage, e.g. restored from a
+    /// persistent volume.
+    async fn register_quota_usage(&self) {
+        let Some(quota) = &self.quota else {
+            return;
+        };
+        let mut storage = self.storage.lock().await;
+        let entries = match storage.get_all_entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!(
+                    "Storage '{}' failed to retrieve its entries to compute its usage: {e:?}",
+                    self.name
+                );
+                return;
+            }
+        };
+        let mut quota = quota.lock().await;
+        for (stripped_key, timestamp) in entries {
+            let Ok(data) = storage.get(stripped_key.clone(), "").await else {
+                continue;
+            };
+            if let Some(data) = data.into_iter().find(|d| d.timestamp == timestamp) {
+                quota.insert(stripped_key, data.payload.len() as u64, timestamp);
+            }
+        }
+    }
+
+    /// Migrates the entries already present in the Storage, e.g. restored from a persistent
+    /// volume, whose `migration` rule is eager.
+    async fn migrate_entries(&self) {
+        let Some(migrations) = &self.migrations else {
+            return;
+        };
+        if !migrations.has_eager_rules() {
+            return;
+        }
+        if self.capability.history != History::Latest {
+            tracing::warn!(
+                "Storage '{}' keeps the history of its keys: its entries are only migrated when \
+                 read",
+                self.name
+            );
+            return;
+        }
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let mut storage = self.storage.lock().await;
+        let entries = match storage.get_all_entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!(
+                    "Storage '{}' failed to retrieve its entries to migrate them: {e:?}",
+                    self.name
+                );
+                return;
+            }
+        };
+        for (stripped_key, _) in entries {
+            let key = match crate::prefix(prefix, stripped_key.as_ref()) {
+                Ok(key) => key,
+                Err(e) => {
+                    tracing::error!("{e:?}");
+                    continue;
+                }
+            };
+            if !migrations.is_eager(&key) {
+                continue;
+            }
+            match storage.get(stripped_key.clone(), "").await {
+                Ok(stored_data) => {
+                    self.migrate_stored(&mut storage, &key, &stripped_key, stored_data)
+                        .await;
+                }
+                Err(e) => tracing::warn!(
+                    "Storage '{}' failed to retrieve < {} > to migrate it: {e:?}",
+                    self.name,
+                    key
+                ),
+            }
+        }
+    }
+
+    /// Migrates the values read from the Storage for `key_expr`, according to its `migration`
+    /// rules.
+    ///
+    /// If the Storage only keeps the latest value of each key, the migrated value replaces the
+    /// stored one, so that it is only migrated once.
+    async fn migrate_stored(
+        &self,
+        storage: &mut Box<dyn zenoh_backend_traits::Storage>,
+        key_expr: &keyexpr,
+        stripped_key: &Option<OwnedKeyExpr>,
+        stored_data: Vec<StoredData>,
+    ) -> Vec<StoredData> {
+        let Some(migrations) = &self.migrations else {
+            return stored_data;
+        };
+        let mut result = Vec::with_capacity(stored_data.len());
+        for data in stored_data {
+            let Some(migrated) = migrations.migrate(key_expr, &data) else {
+                result.push(data);
+                continue;
+            };
+            if self.capability.history == History::Latest {
+                match storage
+                    .put(
+                        stripped_key.clone(),
+                        migrated.payload.clone(),
+                        migrated.encoding.clone(),
+                        migrated.timestamp,
+                    )
+                    .await
+                {
+                    Ok(_) => {
+                        if let Some(quota) = &self.quota {
+                            quota.lock().await.insert(
+                                stripped_key.clone(),
+                                migrated.payload.len() as u64,
+                                migrated.timestamp,
+                            );
+                        }
+                    }
+                    Err(e) => tracing::warn!(
+                        "Storage '{}' failed to store the migrated value of < {} >: {e:?}",
+                        self.name,
+                        key_expr
+                    ),
+                }
+            }
+            result.push(migrated);
+        }
+        result
+    }
+
+    /// Deletes the entries to evict to bring the Storage back within its quota, if any.
+    ///
+    /// An eviction is local to this Storage: it is not recorded in the Replication Log, so that
+    /// the replicas, which can have a different quota, keep their entries.
+    pub(crate) async fn enforce_quota(&self) {
+        let Some(quota) = &self.quota else {
+            return;
+        };
+        let evicted = quota.lock().await.take_evicted();
+        let prefix = self.configuration.strip_prefix.as_ref();
+        for stripped_key in evicted {
+            let key = match crate::prefix(prefix, stripped_key.as_ref()) {
+                Ok(key) => key,
+                Err(e) => {
+                    tracing::error!("{e:?}");
+                    continue;
+                }
+            };
+            tracing::trace!("Storage '{}': entry < {} > evicted", self.name, key);
+            let timestamp = self.session.new_timestamp();
+            if let Err(e) = self
+                .storage
+                .lock()
+                .await
+                .delete(stripped_key.clone(), timestamp)
+                .await
+            {
+                tracing::error!("Storage '{}' failed to evict < {} >: {e:?}", self.name, key);
+                continue;
+            }
+            if let Some(expirations) = &self.expirations {
+                expirations
+                    .lock()
+                    .await
+                    .update(stripped_key, &key, SampleKind::Delete, timestamp);
+            }
+        }
+    }
+
+    /// Records in the quota, if any, an update of `stripped_key` applied by the alignment with a
+    /// Replica: a put of `size` bytes, or a delete if `size` is `None`.
+    ///
+    /// The alignment is exempt from the `reject` policy, as the update was already admitted by
+    /// the Replica, but it still counts towards the usage of this Storage.
+    pub(crate) async fn record_aligned_update(
+        &self,
+        stripped_key: &Option<OwnedKeyExpr>,
+        size: Option<u64>,
+        timestamp: Timestamp,
+    ) {
+        let Some(quota) = &self.quota else {
+            return;
+        };
+        {
+            let mut quota = quota.lock().await;
+            match size {
+                Some(size) => quota.insert(stripped_key.clone(), size, timestamp),
+                None => quota.remove(stripped_key),
+            }
+        }
+        self.enforce_quota().await;
+    }
+
+    /// Records the access to `stripped_key` by a query, for the `lru` eviction policy.
+    async fn touch_quota(&self, stripped_key: &Option<OwnedKeyExpr>) {
+        if let Some(quota) = &self.quota {
+            quota.lock().await.touch(stripped_key);
+        }
+    }
+
+    /// Deletes the entries whose TTL has elapsed, publishing the corresponding Delete samples if
+    /// configured.
+    async fn expire_entries(&self) {
+        let Some(expirations) = &self.expirations else {
+            return;
+        };
+        let (expired, publish_deletes) = {
+            let mut expirations = expirations.lock().await;
+            (
+                expirations.take_expired(SystemTime::now()),
+                expirations.config().publish_deletes,
+            )
+        };
+        let prefix = self.configuration.strip_prefix.as_ref();
+        for stripped_key in expired {
+            let key = match crate::prefix(prefix, stripped_key.as_ref()) {
+                Ok(key) => key,
+                Err(e) => {
+                    tracing::error!("{e:?}");
+                    continue;
+                }
+            };
+            tracing::trace!("Storage '{}': entry < {} > expired", self.name, key);
+            let timestamp = self.session.new_timestamp();
+            let sample = SampleBuilder::delete(key.clone())
+                .timestamp(timestamp)
+                .into();
+            if let Err(e) = self.process_sample(sample).await {
+                tracing::error!("{e:?}");
+                continue;
+            }
+            if publish_deletes {
+                // The Storage already processed the deletion: it must not receive it again.
+                if let Err(e) = self
+                    .session
+                    .delete(&key)
+                    .timestamp(timestamp)
+                    .allowed_destination(Locality::Remote)
+                    .await
+                {
+                    tracing::warn!(
+                        "Storage '{}' failed to publish the expiration of < {} >: {e:?}",
+                        self.name,
+                        key
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the number of Wildcard Updates and of tombstones (i.e. cached deletes) currently
+    /// retained by the Storage, and the number of them removed by the garbage collection.
+    async fn garbage_collection_status(&self) -> serde_json::Value {
+        let wildcard_puts = self.wildcard_puts.read().await.key_value_pairs().count();
+        let wildcard_deletes = self.wildcard_deletes.read().await.key_value_pairs().count();
+        let tombstones = self
+            .cache_latest
+            .latest_updates
+            .read()
+            .await
+            .values()
+            .filter(|event| matches!(event.action(), Action::Delete | Action::WildcardDelete(_)))
+            .count();
+        serde_json::json!({
+            "wildcard_puts": wildcard_puts,
+            "wildcard_deletes": wildcard_deletes,
+            "tombstones": tombstones,
+            "collected": self.gc_collected.load(Ordering::Relaxed),
+        })
+    }
+
     /// Registers a Wildcard Update, storing it in a dedicated in-memory structure and on disk if
     /// the Storage persistence capability is set to `Durable`.
     ///
     /// The `key_expr` and `timestamp` cannot be extracted from the received Sample when aligning
     /// and hence must be manually passed.
     ///
+    /// If the Storage is configured with `wildcard_updates: "existing_only"`, the Wildcard Update
+    /// is not registered: it only applies to the keys existing when it is received.
+    ///
     /// # ⚠️ Cache with Replication
     ///
     /// It is the *responsibility of the caller* to insert a Wildcard Update event in the Cache. If
@@ -389,6 +838,10 @@ impl StorageService {
         payload: ZBytes,
         encoding: Encoding,
     ) {
+        if self.configuration.wildcard_updates == WildcardUpdatesMode::ExistingOnly {
+            return;
+        }
+
         let update = Update {
             kind,
             data: StoredData {
@@ -513,9 +966,8 @@ impl StorageService {
     ) -> Option<RwLockWriteGuard<'_, LatestUpdates>> {
         let cache_guard = self.cache_latest.latest_updates.write().await;
         if let Some(event) = cache_guard.get(&new_event.log_key()) {
-            if new_event.timestamp > event.timestamp {
-                return Some(cache_guard);
-            }
+            // The cached event may be a delete, that is not kept by the Storage
+            return (new_event.timestamp > event.timestamp).then_some(cache_guard);
         }
 
         if let Some(replication_log) = &self.cache_latest.replication_log {
@@ -553,6 +1005,25 @@ impl StorageService {
         };
         tracing::trace!("[STORAGE] Processing query on key_expr: {}", q.key_expr());
 
+        if let Some(time_series) = &self.time_series {
+            if q.parameters().time_range().is_some() {
+                self.reply_time_series_query(&q, time_series).await;
+                return;
+            }
+        }
+
+        match AggregationQuery::from_parameters(q.parameters()) {
+            Ok(Some(aggregation)) => {
+                self.reply_aggregation_query(&q, &aggregation).await;
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.reply_invalid_query(&q, e).await;
+                return;
+            }
+        }
+
         let prefix = self.configuration.strip_prefix.as_ref();
 
         if q.key_expr().is_wild() {
@@ -568,8 +1039,15 @@ impl StorageService {
                         return;
                     }
                 };
-                match storage.get(stripped_key, q.parameters().as_str()).await {
+                match storage
+                    .get(stripped_key.clone(), q.parameters().as_str())
+                    .await
+                {
                     Ok(stored_data) => {
+                        let stored_data = self
+                            .migrate_stored(&mut storage, &key, &stripped_key, stored_data)
+                            .await;
+                        self.touch_quota(&stripped_key).await;
                         for entry in stored_data {
                             if let Err(e) = q
                                 .reply(key.clone(), entry.payload.clone())
@@ -601,8 +1079,15 @@ impl StorageService {
                 }
             };
             let mut storage = self.storage.lock().await;
-            match storage.get(stripped_key, q.parameters().as_str()).await {
+            match storage
+                .get(stripped_key.clone(), q.parameters().as_str())
+                .await
+            {
                 Ok(stored_data) => {
+                    let stored_data = self
+                        .migrate_stored(&mut storage, q.key_expr(), &stripped_key, stored_data)
+                        .await;
+                    self.touch_quota(&stripped_key).await;
                     for entry in stored_data {
                         if let Err(e) = q
                             .reply(q.key_expr().clone(), entry.payload.clone())
@@ -625,6 +1110,124 @@ impl StorageService {
         }
     }
 
+    /// Replies to a query with an aggregation of the latest values of the matching keys, on the
+    /// key expression of the query and with the timestamp of the most recent of these values.
+    async fn reply_aggregation_query(&self, q: &Query, aggregation: &AggregationQuery) {
+        let keys = match q.key_expr().is_wild() {
+            true => self.get_matching_keys(q.key_expr()).await,
+            false => vec![q.key_expr().clone().into()],
+        };
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let mut values = vec![];
+        let mut storage = self.storage.lock().await;
+        for key in keys {
+            let stripped_key = match crate::strip_prefix(prefix, &key.clone().into()) {
+                Ok(k) => k,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return;
+                }
+            };
+            match storage
+                .get(stripped_key.clone(), q.parameters().as_str())
+                .await
+            {
+                Ok(stored_data) => values.extend(
+                    self.migrate_stored(&mut storage, &key, &stripped_key, stored_data)
+                        .await,
+                ),
+                Err(e) => {
+                    tracing::warn!("Storage '{}' raised an error on query: {e}", self.name)
+                }
+            }
+        }
+        drop(storage);
+
+        let Some(latest) = values.iter().map(|data| data.timestamp).max() else {
+            return;
+        };
+        if let Some(result) = aggregation.apply(&values, latest) {
+            if let Err(e) = q
+                .reply(q.key_expr().clone(), result.payload)
+                .encoding(result.encoding)
+                .timestamp(result.timestamp)
+                .await
+            {
+                tracing::warn!(
+                    "Storage '{}' raised an error replying a query: {}",
+                    self.name,
+                    e
+                )
+            }
+        }
+    }
+
+    /// Replies to a query with a time range with the history of the matching keys, downsampled
+    /// if requested.
+    /// Replies an error to a query whose parameters are invalid.
+    async fn reply_invalid_query(&self, q: &Query, e: impl std::fmt::Display) {
+        tracing::warn!("Storage '{}' received an invalid query: {e}", self.name);
+        if let Err(e) = q.reply_err(e.to_string()).await {
+            tracing::warn!(
+                "Storage '{}' raised an error replying a query: {}",
+                self.name,
+                e
+            )
+        }
+    }
+
+    async fn reply_time_series_query(&self, q: &Query, time_series: &RwLock<TimeSeries>) {
+        let time_range = match q.parameters().time_range() {
+            Some(Ok(time_range)) => time_range.resolve(),
+            Some(Err(e)) => {
+                self.reply_invalid_query(q, format!("invalid time range: {e}"))
+                    .await;
+                return;
+            }
+            None => return,
+        };
+        let downsampling = match Downsampling::from_parameters(q.parameters()) {
+            Ok(downsampling) => downsampling,
+            Err(e) => {
+                self.reply_invalid_query(q, e).await;
+                return;
+            }
+        };
+
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let mut replies = vec![];
+        for (key, values) in time_series.read().await.get(&time_range) {
+            let Ok(full_key) = crate::prefix(prefix, key.as_ref()) else {
+                tracing::error!("Internal error: empty key with no `strip_prefix` configured");
+                continue;
+            };
+            if q.key_expr().intersects(&full_key) {
+                let values = match &downsampling {
+                    Some(downsampling) => downsampling.apply(values),
+                    None => values,
+                };
+                replies.push((full_key, values));
+            }
+        }
+
+        for (key, values) in replies {
+            for entry in values {
+                if let Err(e) = q
+                    .reply(key.clone(), entry.payload)
+                    .encoding(entry.encoding)
+                    .timestamp(entry.timestamp)
+                    .await
+                {
+                    tracing::warn!(
+                        "Storage '{}' raised an error replying a query: {}",
+                        self.name,
+                        e
+                    )
+                }
+            }
+        }
+    }
+
     async fn get_matching_keys(&self, key_expr: &keyexpr) -> Vec<OwnedKeyExpr> {
         let mut result = Vec::new();
         // @TODO: if cache exists, use that to get the list
@@ -664,6 +1267,8 @@ struct GarbageCollectionEvent {
     wildcard_deletes: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     wildcard_puts: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     latest_updates: Option<Arc<RwLock<LatestUpdates>>>,
+    time_series: Option<Arc<RwLock<TimeSeries>>>,
+    collected: Arc<AtomicU64>,
 }
 
 #[async_trait]
@@ -673,41 +1278,101 @@ impl Timed for GarbageCollectionEvent {
         let time_limit = NTP64::from(SystemTime::now().duration_since(UNIX_EPOCH).unwrap())
             - NTP64::from(self.config.lifespan);
 
-        // Get lock on fields
-        let mut wildcard_deletes_guard = self.wildcard_deletes.write().await;
-        let mut wildcard_updates_guard = self.wildcard_puts.write().await;
+        let max_wildcard_updates = self.config.max_wildcard_updates;
+        let mut collected = collect_wildcard_updates(
+            &mut *self.wildcard_deletes.write().await,
+            &time_limit,
+            max_wildcard_updates,
+        );
+        collected += collect_wildcard_updates(
+            &mut *self.wildcard_puts.write().await,
+            &time_limit,
+            max_wildcard_updates,
+        );
 
-        let mut to_be_removed = HashSet::new();
-        for (k, update) in wildcard_deletes_guard.key_value_pairs() {
-            let ts = update.data.timestamp;
-            if ts.get_time() < &time_limit {
-                // mark key to be removed
-                to_be_removed.insert(k);
-            }
-        }
-        for k in to_be_removed {
-            wildcard_deletes_guard.remove(&k);
+        if let Some(latest_updates) = &self.latest_updates {
+            let mut latest_updates = latest_updates.write().await;
+            let len = latest_updates.len();
+            latest_updates.retain(|_, event| event.timestamp().get_time() >= &time_limit);
+            collected += len - latest_updates.len();
         }
+        self.collected
+            .fetch_add(collected as u64, Ordering::Relaxed);
 
-        let mut to_be_removed = HashSet::new();
-        for (k, update) in wildcard_updates_guard.key_value_pairs() {
-            let ts = update.data.timestamp;
-            if ts.get_time() < &time_limit {
-                // mark key to be removed
-                to_be_removed.insert(k);
-            }
-        }
-        for k in to_be_removed {
-            wildcard_updates_guard.remove(&k);
+        if let Some(time_series) = &self.time_series {
+            time_series.write().await.prune(SystemTime::now());
         }
 
-        if let Some(latest_updates) = &self.latest_updates {
-            latest_updates
-                .write()
-                .await
-                .retain(|_, event| event.timestamp().get_time() < &time_limit);
+        tracing::trace!("End garbage collection of obsolete data-infos");
+    }
+}
+
+/// Removes the Wildcard Updates older than `time_limit` and, if there are more than
+/// `max_wildcard_updates`, the oldest ones in excess. Returns the number of removed updates.
+fn collect_wildcard_updates(
+    wildcard_updates: &mut KeBoxTree<Update, UnknownWildness, KeyedSetProvider>,
+    time_limit: &NTP64,
+    max_wildcard_updates: Option<usize>,
+) -> usize {
+    let mut updates: Vec<(OwnedKeyExpr, Timestamp)> = wildcard_updates
+        .key_value_pairs()
+        .map(|(key_expr, update)| (key_expr, update.data.timestamp))
+        .collect();
+    updates.sort_unstable_by_key(|(_, timestamp)| *timestamp);
+    let expired = updates.partition_point(|(_, timestamp)| timestamp.get_time() < time_limit);
+    let in_excess = max_wildcard_updates.map_or(0, |max| updates.len().saturating_sub(max));
+    let to_remove = expired.max(in_excess);
+    for (key_expr, _) in &updates[..to_remove] {
+        wildcard_updates.remove(key_expr);
+    }
+    if to_remove > 0 {
+        wildcard_updates.prune();
+    }
+    to_remove
+}
+
+#[cfg(test)]
+mod tests {
+    use zenoh::time::TimestampId;
+
+    use super::*;
+
+    #[test]
+    fn test_collect_wildcard_updates() {
+        let mut wildcard_updates = KeBoxTree::default();
+        for (key_expr, secs) in [("a/*", 1), ("b/*", 2), ("c/*", 3), ("d/*", 4)] {
+            let update = Update {
+                kind: SampleKind::Delete,
+                data: StoredData {
+                    payload: ZBytes::default(),
+                    encoding: Encoding::default(),
+                    timestamp: Timestamp::new(
+                        NTP64::from(Duration::from_secs(secs)),
+                        TimestampId::rand(),
+                    ),
+                },
+            };
+            wildcard_updates.insert(keyexpr::new(key_expr).unwrap(), update);
         }
+        let keys = |tree: &KeBoxTree<Update, UnknownWildness, KeyedSetProvider>| {
+            let mut keys: Vec<String> = tree.key_value_pairs().map(|(k, _)| k.into()).collect();
+            keys.sort();
+            keys
+        };
 
-        tracing::trace!("End garbage collection of obsolete data-infos");
+        // Only the updates older than the time limit are removed
+        let time_limit = NTP64::from(Duration::from_secs(2));
+        assert_eq!(
+            collect_wildcard_updates(&mut wildcard_updates, &time_limit, None),
+            1
+        );
+        assert_eq!(keys(&wildcard_updates), vec!["b/*", "c/*", "d/*"]);
+
+        // The oldest updates in excess are removed
+        assert_eq!(
+            collect_wildcard_updates(&mut wildcard_updates, &time_limit, Some(1)),
+            2
+        );
+        assert_eq!(keys(&wildcard_updates), vec!["d/*"]);
     }
 }
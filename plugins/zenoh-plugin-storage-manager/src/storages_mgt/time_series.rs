@@ -0,0 +1,255 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, SystemTime},
+};
+
+use zenoh::{
+    internal::bail,
+    key_expr::OwnedKeyExpr,
+    query::{Parameters, TimeRange},
+    Result as ZResult,
+};
+use zenoh_backend_traits::{config::TimeSeriesConfig, StoredData};
+
+use super::aggregation::{Aggregation, AggregationQuery};
+
+/// The selector parameter setting the duration, in seconds, of the downsampling buckets.
+pub(crate) const BUCKET_PARAM: &str = "_bucket";
+
+/// The history of the values of each key of a Storage, bounded by its [`TimeSeriesConfig`].
+///
+/// The history of a key is kept when it is deleted, until its samples are discarded because of
+/// their age.
+pub(crate) struct TimeSeries {
+    config: TimeSeriesConfig,
+    series: HashMap<Option<OwnedKeyExpr>, VecDeque<StoredData>>,
+}
+
+impl TimeSeries {
+    pub(crate) fn new(config: TimeSeriesConfig) -> Self {
+        Self {
+            config,
+            series: HashMap::new(),
+        }
+    }
+
+    /// Inserts a value in the history of `key`, keeping it sorted by timestamp.
+    pub(crate) fn insert(&mut self, key: Option<OwnedKeyExpr>, data: StoredData) {
+        let series = self.series.entry(key).or_default();
+        let index = series.partition_point(|d| d.timestamp < data.timestamp);
+        if series
+            .get(index)
+            .is_some_and(|d| d.timestamp == data.timestamp)
+        {
+            return;
+        }
+        series.insert(index, data);
+        if let Some(max_samples) = self.config.max_samples {
+            while series.len() > max_samples {
+                series.pop_front();
+            }
+        }
+    }
+
+    /// Discards the samples older than the `max_age` of the configuration.
+    pub(crate) fn prune(&mut self, now: SystemTime) {
+        let Some(max_age) = self.config.max_age else {
+            return;
+        };
+        let Some(limit) = now.checked_sub(max_age) else {
+            return;
+        };
+        self.series.retain(|_, series| {
+            while series
+                .front()
+                .is_some_and(|d| d.timestamp.get_time().to_system_time() < limit)
+            {
+                series.pop_front();
+            }
+            !series.is_empty()
+        });
+    }
+
+    /// Returns the keys having a history, with their values within `range`.
+    pub(crate) fn get<'a>(
+        &'a self,
+        range: &'a TimeRange<SystemTime>,
+    ) -> impl Iterator<Item = (&'a Option<OwnedKeyExpr>, Vec<StoredData>)> + 'a {
+        self.series.iter().map(move |(key, series)| {
+            let values = series
+                .iter()
+                .filter(|d| range.contains(d.timestamp.get_time().to_system_time()))
+                .cloned()
+                .collect();
+            (key, values)
+        })
+    }
+}
+
+/// The downsampling of the values of a key, requested with the `_bucket` and `_agg` selector
+/// parameters.
+///
+/// The values are grouped in buckets of `_bucket` seconds, and each bucket is replaced by the
+/// [`AggregationQuery`] of its values (the average by default), with the timestamp of its most
+/// recent value. If only `_agg` is set, all the values are aggregated in a single one.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Downsampling {
+    bucket: Option<Duration>,
+    aggregation: AggregationQuery,
+}
+
+impl Downsampling {
+    pub(crate) fn from_parameters(parameters: &Parameters) -> ZResult<Option<Self>> {
+        let aggregation = AggregationQuery::from_parameters(parameters)?;
+        let bucket = match parameters.get(BUCKET_PARAM) {
+            Some(bucket) => match bucket.parse::<f64>() {
+                Ok(secs) if secs > 0.0 && secs.is_finite() => Some(Duration::from_secs_f64(secs)),
+                _ => bail!(
+                    "Invalid value '{}' for `{}`: expecting a positive number of seconds",
+                    bucket,
+                    BUCKET_PARAM
+                ),
+            },
+            None if aggregation.is_none() => return Ok(None),
+            None => None,
+        };
+        Ok(Some(Downsampling {
+            bucket,
+            aggregation: aggregation.unwrap_or_else(|| AggregationQuery::new(Aggregation::Avg)),
+        }))
+    }
+
+    /// Downsamples values sorted by timestamp.
+    pub(crate) fn apply(&self, values: Vec<StoredData>) -> Vec<StoredData> {
+        let Some(bucket) = self.bucket else {
+            return values
+                .last()
+                .and_then(|last| self.aggregation.apply(&values, last.timestamp))
+                .into_iter()
+                .collect();
+        };
+        let bucket_nanos = bucket.as_nanos().max(1);
+        let bucket_of =
+            |data: &StoredData| data.timestamp.get_time().to_duration().as_nanos() / bucket_nanos;
+        let mut result = vec![];
+        let mut start = 0;
+        for end in 1..=values.len() {
+            if end < values.len() && bucket_of(&values[end]) == bucket_of(&values[start]) {
+                continue;
+            }
+            let bucket = &values[start..end];
+            result.extend(
+                self.aggregation
+                    .apply(bucket, bucket[bucket.len() - 1].timestamp),
+            );
+            start = end;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use zenoh::{
+        bytes::Encoding,
+        time::{Timestamp, TimestampId, NTP64},
+    };
+
+    use super::*;
+
+    fn data(secs: u64, value: &str) -> StoredData {
+        StoredData {
+            payload: value.into(),
+            encoding: Encoding::TEXT_PLAIN,
+            timestamp: Timestamp::new(NTP64::from(Duration::from_secs(secs)), TimestampId::rand()),
+        }
+    }
+
+    fn values(data: &[StoredData]) -> Vec<String> {
+        data.iter()
+            .map(|d| d.payload.try_to_string().unwrap().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_time_series_retention() {
+        let mut time_series = TimeSeries::new(TimeSeriesConfig {
+            max_samples: Some(3),
+            max_age: Some(Duration::from_secs(10)),
+        });
+        let key = Some(OwnedKeyExpr::new("a").unwrap());
+        for (secs, value) in [(1, "1"), (4, "4"), (3, "3"), (2, "2"), (5, "5")] {
+            time_series.insert(key.clone(), data(secs, value));
+        }
+        let all: TimeRange<SystemTime> = TimeRange {
+            start: zenoh::query::TimeBound::Unbounded,
+            end: zenoh::query::TimeBound::Unbounded,
+        };
+        let (_, history) = time_series.get(&all).next().unwrap();
+        assert_eq!(values(&history), vec!["3", "4", "5"]);
+
+        time_series.prune(UNIX_EPOCH + Duration::from_secs(14));
+        let (_, history) = time_series.get(&all).next().unwrap();
+        assert_eq!(values(&history), vec!["4", "5"]);
+
+        time_series.prune(UNIX_EPOCH + Duration::from_secs(20));
+        assert!(time_series.get(&all).next().is_none());
+    }
+
+    #[test]
+    fn test_downsampling() {
+        let values_in = vec![
+            data(0, "1"),
+            data(1, "3"),
+            data(2, "not a number"),
+            data(10, "10"),
+            data(25, "4"),
+            data(29, "6"),
+        ];
+        let downsampling = |params: &str| {
+            Downsampling::from_parameters(&Parameters::from(params))
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(
+            values(&downsampling("_bucket=10").apply(values_in.clone())),
+            vec!["2", "10", "5"]
+        );
+        assert_eq!(
+            values(&downsampling("_bucket=10;_agg=min").apply(values_in.clone())),
+            vec!["1", "10", "4"]
+        );
+        let max = downsampling("_bucket=30;_agg=max").apply(values_in.clone());
+        assert_eq!(values(&max), vec!["10"]);
+        assert_eq!(
+            max[0].timestamp.get_time(),
+            &NTP64::from(Duration::from_secs(29))
+        );
+
+        assert!(Downsampling::from_parameters(&Parameters::from(""))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            values(&downsampling("_agg=count").apply(values_in.clone())),
+            vec!["5"]
+        );
+        assert!(Downsampling::from_parameters(&Parameters::from("_bucket=0")).is_err());
+        assert!(Downsampling::from_parameters(&Parameters::from("_bucket=1;_agg=median")).is_err());
+    }
+}
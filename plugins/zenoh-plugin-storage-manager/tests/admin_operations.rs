@@ -0,0 +1,163 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the administration operations of a storage: export its content and import it in another
+// storage, without restoring deleted values nor overriding more recent ones.
+
+use std::{thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    bytes::ZBytes,
+    internal::{plugins::RunningPlugin, zasync_executor_init},
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn get_data(session: &Session, key_expr: &str) -> Vec<Sample> {
+    session
+        .get(key_expr)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .collect()
+}
+
+/// Starts an isolated runtime with a memory storage on `admin/test/**`, returning a session on
+/// it and the admin key of the storage.
+async fn start_storage() -> (Session, String, RunningPlugin) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        admin_test: {
+                            key_expr: "admin/test/**",
+                            volume: {
+                                id: "memory"
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "adminspace",
+            r#"{ permissions: { read: true, write: true } }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let admin_key = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/admin_test",
+        runtime.zid()
+    );
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+    (session, admin_key, storage)
+}
+
+async fn import(session: &Session, admin_key: &str, snapshot: ZBytes) {
+    let replies: Vec<_> = session
+        .get(format!("{admin_key}/import"))
+        .payload(snapshot)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_ok());
+    sleep(Duration::from_millis(10));
+}
+
+async fn test_export_import() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let (session, admin_key, storage) = start_storage().await;
+
+    session.put("admin/test/a", "1").await.unwrap();
+    sleep(Duration::from_millis(10));
+
+    let replies = get_data(&session, &format!("{admin_key}/export")).await;
+    assert_eq!(replies.len(), 1);
+    let snapshot = replies[0].payload().clone();
+
+    // The snapshot restores the content in another storage
+    let (other_session, other_admin_key, other_storage) = start_storage().await;
+    assert!(get_data(&other_session, "admin/test/a").await.is_empty());
+    import(&other_session, &other_admin_key, snapshot.clone()).await;
+    let data = get_data(&other_session, "admin/test/a").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "1");
+
+    // A value deleted after the export is not restored
+    session.delete("admin/test/a").await.unwrap();
+    sleep(Duration::from_millis(10));
+    import(&session, &admin_key, snapshot.clone()).await;
+    assert!(get_data(&session, "admin/test/a").await.is_empty());
+
+    // A value more recent than the imported one is kept
+    session.put("admin/test/a", "2").await.unwrap();
+    sleep(Duration::from_millis(10));
+    import(&session, &admin_key, snapshot).await;
+    let data = get_data(&session, "admin/test/a").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "2");
+
+    // An unknown operation is an error
+    let replies: Vec<_> = session
+        .get(format!("{admin_key}/unknown"))
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_err());
+
+    drop(other_storage);
+    drop(storage);
+}
+
+#[test]
+fn export_import_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_export_import().await });
+}
@@ -0,0 +1,148 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the alignment status of two replicas of a storage: the second replica, started after a
+// value was stored by the first one, reports that it is aligned once it retrieved it.
+
+use std::time::{Duration, Instant};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::{plugins::RunningPlugin, zasync_executor_init},
+    Config, Session,
+};
+use zenoh_plugin_storage_manager::AlignmentStatus;
+use zenoh_plugin_trait::Plugin;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+async fn start_replica(endpoint: &str, listen: bool) -> (Session, RunningPlugin, String) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        replica: {
+                            key_expr: "alignment/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            replication: {
+                                interval: 1,
+                                sub_intervals: 2,
+                                hot: 2,
+                                warm: 2,
+                                propagation_delay: 100
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{ enabled: { router: true, peer: true, client: true } }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    let endpoints = format!(r#"["{endpoint}"]"#);
+    match listen {
+        true => config.insert_json5("listen/endpoints", &endpoints).unwrap(),
+        false => config
+            .insert_json5("connect/endpoints", &endpoints)
+            .unwrap(),
+    }
+
+    let mut runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    runtime.start().await.unwrap();
+    let admin_key = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/replica",
+        runtime.zid()
+    );
+    let session = zenoh::session::init(runtime).await.unwrap();
+    (session, storage, admin_key)
+}
+
+async fn alignment_status(session: &Session, admin_key: &str) -> Option<AlignmentStatus> {
+    let reply = session
+        .get(format!("{admin_key}/alignment"))
+        .await
+        .unwrap()
+        .recv_async()
+        .await
+        .ok()?;
+    let payload = reply.result().ok()?.payload().to_bytes();
+    Some(serde_json::from_slice(&payload).unwrap())
+}
+
+async fn test_alignment_status() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let endpoint = "tcp/127.0.0.1:38291";
+    let (session1, storage1, admin_key1) = start_replica(endpoint, true).await;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    session1.put("alignment/test/a", "1").await.unwrap();
+
+    // Without any other replica, the storage is not aligned
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let status = alignment_status(&session1, &admin_key1).await.unwrap();
+    assert!(status.replicas.is_empty());
+    assert!(status.last_digest_published_ms.is_some());
+    assert!(!status.is_aligned());
+    assert!(status.estimated_convergence_ms > 0);
+
+    let (session2, storage2, admin_key2) = start_replica(endpoint, false).await;
+    let start = Instant::now();
+    loop {
+        if let Some(status) = alignment_status(&session2, &admin_key2).await {
+            if status.is_aligned() {
+                assert_eq!(status.estimated_convergence_ms, 0);
+                assert_eq!(status.diverging_keys, 0);
+                assert!(status.replicas[&session1.zid().to_string()].aligned);
+                break;
+            }
+        }
+        assert!(start.elapsed() < TIMEOUT, "The replicas did not align");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    let data: Vec<_> = session2
+        .get("alignment/test/a")
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .collect();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "1");
+
+    drop(storage2);
+    drop(storage1);
+}
+
+#[test]
+fn alignment_status_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_alignment_status().await });
+}
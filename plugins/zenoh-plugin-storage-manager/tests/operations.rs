@@ -143,6 +143,25 @@ async fn test_updates_in_order() {
     assert_eq!(data[0].payload().try_to_string().unwrap(), "2");
     assert_eq!(data[0].key_expr().as_str(), "operation/test/b");
 
+    put_data(
+        &session,
+        "operation/test/c",
+        r#"{"value":4}"#,
+        Timestamp::from_str("7054123824268606560/BC779A06D7E049BD88C3FF3DB0C17FCC").unwrap(),
+    )
+    .await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    // expects exactly one sample aggregating the matching values
+    let data = get_data(&session, "operation/test/*?_agg=count").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "2");
+    assert_eq!(data[0].key_expr().as_str(), "operation/test/*");
+    let data = get_data(&session, "operation/test/*?_agg=sum;_field=value").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "4");
+
     drop(storage);
 }
 
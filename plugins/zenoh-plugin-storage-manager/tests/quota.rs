@@ -0,0 +1,111 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the quota of a storage: the oldest entries are evicted once its `max_entries` is exceeded.
+
+use std::{thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{internal::zasync_executor_init, sample::Sample, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn get_data(session: &Session, key_expr: &str) -> Vec<Sample> {
+    session
+        .get(key_expr)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .collect()
+}
+
+async fn test_quota_eviction() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        quota_test: {
+                            key_expr: "quota/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            quota: {
+                                max_entries: 2,
+                                eviction: "oldest_first"
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    for (key, value) in [("a", "1"), ("b", "2"), ("c", "3")] {
+        session
+            .put(format!("quota/test/{key}"), value)
+            .await
+            .unwrap();
+        sleep(Duration::from_millis(10));
+    }
+
+    // The oldest entry was evicted
+    let mut keys: Vec<String> = get_data(&session, "quota/test/**")
+        .await
+        .iter()
+        .map(|sample| sample.key_expr().to_string())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, vec!["quota/test/b", "quota/test/c"]);
+
+    // An update of an existing entry does not evict any other
+    session.put("quota/test/b", "4").await.unwrap();
+    sleep(Duration::from_millis(10));
+    assert_eq!(get_data(&session, "quota/test/**").await.len(), 2);
+
+    drop(storage);
+}
+
+#[test]
+fn quota_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_quota_eviction().await });
+}
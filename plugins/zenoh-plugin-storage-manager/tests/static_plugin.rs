@@ -0,0 +1,81 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the storage manager linked in the application opening the session, without loading a
+// dynamic library.
+
+use std::time::Duration;
+
+use zenoh::Config;
+use zenoh_plugin_storage_manager::StoragesPlugin;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn static_storage_manager() {
+    let mut config = Config::default();
+    config.insert_json5("mode", r#""router""#).unwrap();
+    config.insert_json5("adminspace/enabled", "true").unwrap();
+    config
+        .insert_json5("plugins_loading", r#"{ enabled: false }"#)
+        .unwrap();
+    config
+        .insert_json5(
+            "plugins/storage_manager",
+            r#"{
+                    storages: {
+                        demo: {
+                            key_expr: "static/**",
+                            volume: { id: "memory" }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    let session = zenoh::open(config)
+        .with_static_plugin::<StoragesPlugin>()
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    session.put("static/a", "1").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let replies: Vec<_> = session
+        .get("static/**")
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].payload().try_to_string().unwrap(), "1");
+
+    // The status of the plugin is reported in the admin space as for a dynamic plugin
+    let status = session
+        .get(format!(
+            "@/{}/router/plugins/storage_manager",
+            session.zid()
+        ))
+        .await
+        .unwrap()
+        .recv_async()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&status.payload().to_bytes()).unwrap();
+    assert_eq!(status["path"], "__static_lib__");
+    assert_eq!(status["state"], "Started");
+
+    session.close().await.unwrap();
+}
@@ -0,0 +1,117 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+// Test the time-range queries of a storage keeping the history of its keys.
+
+use std::{thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{internal::zasync_executor_init, query::Reply, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn get_replies(session: &Session, selector: &str) -> Vec<Reply> {
+    session.get(selector).await.unwrap().into_iter().collect()
+}
+
+async fn test_time_range_queries() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        time_series_test: {
+                            key_expr: "time_series/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            time_series: {
+                                max_samples: 10
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    for value in ["1", "2", "3"] {
+        session.put("time_series/test/a", value).await.unwrap();
+        sleep(Duration::from_millis(10));
+    }
+
+    // The history of the key is served for a time range
+    let values: Vec<String> = get_replies(&session, "time_series/test/a?_time=[now(-1h)..]")
+        .await
+        .into_iter()
+        .map(|reply| {
+            reply
+                .into_result()
+                .unwrap()
+                .payload()
+                .try_to_string()
+                .unwrap()
+                .into_owned()
+        })
+        .collect();
+    assert_eq!(values, vec!["1", "2", "3"]);
+
+    // An invalid time range is answered with an error
+    let replies = get_replies(&session, "time_series/test/a?_time=yesterday").await;
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_err());
+
+    // So is an invalid aggregation
+    let replies = get_replies(&session, "time_series/test/a?_agg=median").await;
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_err());
+
+    // And an invalid downsampling
+    let replies = get_replies(&session, "time_series/test/a?_time=[now(-1h)..];_bucket=-1").await;
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_err());
+
+    drop(storage);
+}
+
+#[test]
+fn time_series_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_time_range_queries().await });
+}
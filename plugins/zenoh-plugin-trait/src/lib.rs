@@ -28,6 +28,8 @@
 //! The actual work of the plugin is performed by the instance, which is created by the [`start`](Plugin::start) function.
 //!
 //! Plugins are loaded, started and stopped by [`PluginsManager`]. Stopping plugin is just dropping it's instance.
+//! A plugin can also be unloaded and reloaded at runtime with [`PluginsManager::reload_plugin`],
+//! which loads the current version of a dynamic plugin's library.
 //!
 //! Plugins can be static and dynamic.
 //!
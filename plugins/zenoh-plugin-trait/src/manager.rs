@@ -14,7 +14,7 @@ mod dynamic_plugin;
 mod static_plugin;
 
 use zenoh_keyexpr::keyexpr;
-use zenoh_result::ZResult;
+use zenoh_result::{zerror, ZResult};
 use zenoh_util::LibLoader;
 
 use self::{
@@ -28,6 +28,9 @@ pub trait DeclaredPlugin<StartArgs, Instance>: PluginStatus {
     fn load(&mut self) -> ZResult<Option<&mut dyn LoadedPlugin<StartArgs, Instance>>>;
     fn loaded(&self) -> Option<&dyn LoadedPlugin<StartArgs, Instance>>;
     fn loaded_mut(&mut self) -> Option<&mut dyn LoadedPlugin<StartArgs, Instance>>;
+    /// Stops the plugin if it is started and unloads it, so that the next [`load`](Self::load)
+    /// loads it again from its source. A static plugin is only stopped.
+    fn unload(&mut self);
 }
 pub trait LoadedPlugin<StartArgs, Instance>: PluginStatus {
     fn as_status(&self) -> &dyn PluginStatus;
@@ -97,6 +100,9 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance> DeclaredPlugin<StartA
     fn loaded_mut(&mut self) -> Option<&mut dyn LoadedPlugin<StartArgs, Instance>> {
         self.0.loaded_mut()
     }
+    fn unload(&mut self) {
+        self.0.unload()
+    }
 }
 
 /// A plugins manager that handles starting and stopping plugins.
@@ -296,6 +302,37 @@ impl<StartArgs: PluginStartArgs + 'static, Instance: PluginInstance + 'static>
         self.plugin_mut(id)?.loaded_mut()
     }
 
+    /// Unloads the plugin `id` and loads it again from its source before starting it, which
+    /// upgrades a dynamic plugin whose library has been replaced.
+    ///
+    /// If the library cannot be loaded, e.g. because it is not compatible with the host, the
+    /// plugin is left declared with the error in its report.
+    pub fn reload_plugin(
+        &mut self,
+        id: &str,
+        args: &StartArgs,
+    ) -> ZResult<&mut dyn StartedPlugin<StartArgs, Instance>> {
+        let plugin = self
+            .plugin_mut(id)
+            .ok_or_else(|| zerror!("Plugin `{}` is not declared", id))?;
+        let previous_version = plugin.long_version().map(String::from);
+        plugin.unload();
+        let loaded = plugin
+            .load()?
+            .ok_or_else(|| zerror!("Plugin `{}` not reloaded: plugin loading is disabled", id))?;
+        if let Some(previous_version) = previous_version {
+            if loaded.long_version() != Some(previous_version.as_str()) {
+                tracing::info!(
+                    "Plugin `{}` upgraded from {} to {}",
+                    id,
+                    previous_version,
+                    loaded.long_version().unwrap_or("<unknown>")
+                );
+            }
+        }
+        loaded.start(args)
+    }
+
     /// Returns started plugin record by id
     pub fn started_plugin(&self, id: &str) -> Option<&dyn StartedPlugin<StartArgs, Instance>> {
         self.loaded_plugin(id)?.started()
@@ -345,3 +382,81 @@ impl<StartArgs: PluginStartArgs + 'static, Instance: PluginInstance + 'static> P
         plugins
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{PluginControl, PluginInstance, PluginState, StructVersion};
+
+    static INSTANCES: AtomicUsize = AtomicUsize::new(0);
+
+    struct Args;
+    impl StructVersion for Args {
+        fn struct_version() -> u64 {
+            1
+        }
+        fn struct_features() -> &'static str {
+            ""
+        }
+    }
+    impl PluginStartArgs for Args {}
+
+    struct Instance;
+    impl StructVersion for Instance {
+        fn struct_version() -> u64 {
+            1
+        }
+        fn struct_features() -> &'static str {
+            ""
+        }
+    }
+    impl PluginControl for Instance {}
+    impl PluginInstance for Instance {}
+    impl Drop for Instance {
+        fn drop(&mut self) {
+            INSTANCES.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    struct TestPlugin;
+    impl Plugin for TestPlugin {
+        type StartArgs = Args;
+        type Instance = Instance;
+        const DEFAULT_NAME: &'static str = "test";
+        const PLUGIN_VERSION: &'static str = "1";
+        const PLUGIN_LONG_VERSION: &'static str = "1";
+        fn start(_name: &str, _args: &Args) -> ZResult<Instance> {
+            INSTANCES.fetch_add(1, Ordering::SeqCst);
+            Ok(Instance)
+        }
+    }
+
+    #[test]
+    fn test_reload_plugin() {
+        let mut manager = PluginsManager::<Args, Instance>::static_plugins_only();
+        manager.declare_static_plugin::<TestPlugin, _>("test", false);
+        manager
+            .plugin_mut("test")
+            .unwrap()
+            .load()
+            .unwrap()
+            .unwrap()
+            .start(&Args)
+            .unwrap();
+        assert_eq!(INSTANCES.load(Ordering::SeqCst), 1);
+
+        // The running instance is dropped before the new one is started
+        manager.reload_plugin("test", &Args).unwrap();
+        assert_eq!(INSTANCES.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            manager.plugin("test").unwrap().state(),
+            PluginState::Started
+        );
+
+        manager.plugin_mut("test").unwrap().unload();
+        assert_eq!(INSTANCES.load(Ordering::SeqCst), 0);
+        assert!(manager.reload_plugin("unknown", &Args).is_err());
+    }
+}
@@ -46,10 +46,74 @@ impl DynamicPluginSource {
     }
 }
 
+/// Returns the symbol `name` of the plugin library, failing if the library is not a plugin or
+/// was built with an incompatible version of this crate.
+unsafe fn get_symbol<'lib, T>(
+    lib: &'lib Library,
+    name: &str,
+) -> ZResult<libloading::Symbol<'lib, T>> {
+    lib.get::<T>(name.as_bytes()).map_err(|e| {
+        zerror!(
+            "`{}` not found ({}): the library is not a zenoh plugin, or it was built with an incompatible version of zenoh-plugin-trait",
+            name,
+            e
+        )
+        .into()
+    })
+}
+
+/// Calls the function `name` of the plugin library, turning its panics into errors.
+fn call_plugin<T>(name: &str, f: impl FnOnce() -> T) -> ZResult<T> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+        .map_err(|_| zerror!("`{}` panicked", name).into())
+}
+
+/// A temporary copy of a plugin library, removed when dropped.
+struct LibraryCopy(PathBuf);
+
+impl Drop for LibraryCopy {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.0) {
+            tracing::debug!("Failed to remove {}: {}", self.0.display(), e);
+        }
+    }
+}
+
+/// Loads a copy of the library at `path`.
+///
+/// Loading `path` again after an unload could return the library still in memory instead of the
+/// one currently on disk.
+unsafe fn load_copy(path: &Path, id: &str, generation: u64) -> ZResult<(Library, LibraryCopy)> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| zerror!("Invalid plugin path {}", path.display()))?;
+    let copy = std::env::temp_dir().join(format!(
+        "zenoh-{}-{}-{}-{}",
+        std::process::id(),
+        id,
+        generation,
+        file_name.to_string_lossy()
+    ));
+    std::fs::copy(path, &copy).map_err(|e| {
+        zerror!(
+            "Failed to copy {} to {}: {}",
+            path.display(),
+            copy.display(),
+            e
+        )
+    })?;
+    let copy = LibraryCopy(copy);
+    let lib =
+        Library::new(&copy.0).map_err(|e| zerror!("Failed to load {}: {}", path.display(), e))?;
+    Ok((lib, copy))
+}
+
 struct DynamicPluginStarter<StartArgs, Instance> {
-    _lib: Library,
+    lib: Library,
     path: PathBuf,
     vtable: PluginVTable<StartArgs, Instance>,
+    /// The copy `lib` was loaded from, if it was reloaded
+    copy: Option<LibraryCopy>,
 }
 
 impl<StartArgs: PluginStartArgs, Instance: PluginInstance>
@@ -58,8 +122,9 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance>
     fn get_vtable(lib: &Library, path: &Path) -> ZResult<PluginVTable<StartArgs, Instance>> {
         tracing::debug!("Loading plugin {}", path.to_str().unwrap(),);
         let get_plugin_loader_version =
-            unsafe { lib.get::<fn() -> PluginLoaderVersion>(b"get_plugin_loader_version")? };
-        let plugin_loader_version = get_plugin_loader_version();
+            unsafe { get_symbol::<fn() -> PluginLoaderVersion>(lib, "get_plugin_loader_version")? };
+        let plugin_loader_version =
+            call_plugin("get_plugin_loader_version", *get_plugin_loader_version)?;
         tracing::debug!("Plugin loader version: {}", &plugin_loader_version);
         if plugin_loader_version != PLUGIN_LOADER_VERSION {
             bail!(
@@ -68,8 +133,10 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance>
                 plugin_loader_version
             );
         }
-        let get_compatibility = unsafe { lib.get::<fn() -> Compatibility>(b"get_compatibility")? };
-        let mut plugin_compatibility_record = get_compatibility();
+        // The layout of the compatibility record is only known once the loader versions match
+        let get_compatibility =
+            unsafe { get_symbol::<fn() -> Compatibility>(lib, "get_compatibility")? };
+        let mut plugin_compatibility_record = call_plugin("get_compatibility", *get_compatibility)?;
         let mut host_compatibility_record =
             Compatibility::with_empty_plugin_version::<StartArgs, Instance>();
         tracing::debug!(
@@ -84,25 +151,49 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance>
             );
         }
         let load_plugin =
-            unsafe { lib.get::<fn() -> PluginVTable<StartArgs, Instance>>(b"load_plugin")? };
+            unsafe { get_symbol::<fn() -> PluginVTable<StartArgs, Instance>>(lib, "load_plugin")? };
 
-        Ok(load_plugin())
+        call_plugin("load_plugin", *load_plugin)
     }
     fn new(lib: Library, path: PathBuf) -> ZResult<Self> {
         let vtable = Self::get_vtable(&lib, &path)
             .map_err(|e| format!("Error loading {}: {}", path.to_str().unwrap(), e))?;
         Ok(Self {
-            _lib: lib,
+            lib,
             path,
             vtable,
+            copy: None,
         })
     }
     fn start(&self, name: &str, args: &StartArgs) -> ZResult<Instance> {
-        (self.vtable.start)(name, args)
+        call_plugin("start", || (self.vtable.start)(name, args))?
     }
     fn path(&self) -> &str {
         self.path.to_str().unwrap()
     }
+    /// Stops the plugin and unloads its library, which is left loaded if the plugin can't be
+    /// stopped as its tasks and threads could still run its code.
+    fn unload(self, name: &str) {
+        let Self {
+            lib, vtable, copy, ..
+        } = self;
+        match call_plugin("stop", vtable.stop).and_then(|stopped| stopped) {
+            Ok(()) => {
+                if let Err(e) = lib.close() {
+                    tracing::warn!("Failed to unload plugin `{}`: {}", name, e);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Plugin `{}` not stopped, its library is left loaded: {}",
+                    name,
+                    e
+                );
+                std::mem::forget(lib);
+            }
+        }
+        drop(copy);
+    }
 }
 
 pub struct DynamicPlugin<StartArgs, Instance> {
@@ -113,6 +204,8 @@ pub struct DynamicPlugin<StartArgs, Instance> {
     source: DynamicPluginSource,
     starter: Option<DynamicPluginStarter<StartArgs, Instance>>,
     instance: Option<Instance>,
+    /// The number of times the plugin has been unloaded
+    generation: u64,
 }
 
 impl<StartArgs, Instance> DynamicPlugin<StartArgs, Instance> {
@@ -125,6 +218,7 @@ impl<StartArgs, Instance> DynamicPlugin<StartArgs, Instance> {
             source,
             starter: None,
             instance: None,
+            generation: 0,
         }
     }
 }
@@ -188,7 +282,16 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance> DeclaredPlugin<StartA
                 );
                 return Ok(None);
             };
-            let starter = DynamicPluginStarter::new(lib, path).add_error(&mut self.report)?;
+            let (lib, copy) = if self.generation > 0 {
+                drop(lib);
+                let (lib, copy) = unsafe { load_copy(&path, &self.id, self.generation) }
+                    .add_error(&mut self.report)?;
+                (lib, Some(copy))
+            } else {
+                (lib, None)
+            };
+            let mut starter = DynamicPluginStarter::new(lib, path).add_error(&mut self.report)?;
+            starter.copy = copy;
             tracing::debug!("Plugin {} loaded from {}", self.name, starter.path());
             self.starter = Some(starter);
         } else {
@@ -210,6 +313,17 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance> DeclaredPlugin<StartA
             None
         }
     }
+    fn unload(&mut self) {
+        if self.instance.take().is_some() {
+            tracing::debug!("Plugin `{}` stopped", self.name);
+        }
+        self.report.clear();
+        if let Some(starter) = self.starter.take() {
+            starter.unload(&self.name);
+            self.generation += 1;
+            tracing::debug!("Plugin `{}` unloaded", self.name);
+        }
+    }
 }
 
 impl<StartArgs: PluginStartArgs, Instance: PluginInstance> LoadedPlugin<StartArgs, Instance>
@@ -271,3 +385,27 @@ impl<StartArgs: PluginStartArgs, Instance: PluginInstance> StartedPlugin<StartAr
         self.instance.as_mut().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_copy_cleanup() {
+        let path =
+            std::env::temp_dir().join(format!("zenoh-test-{}-invalid.so", std::process::id()));
+        std::fs::write(&path, b"not a library").unwrap();
+        assert!(unsafe { load_copy(&path, "invalid", 1) }.is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        // The copy of a library that failed to load is removed
+        let prefix = format!("zenoh-{}-invalid-1-", std::process::id());
+        assert!(!std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .any(|entry| entry
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with(&prefix)));
+    }
+}
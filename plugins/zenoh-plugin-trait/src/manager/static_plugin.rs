@@ -93,6 +93,11 @@ where
     fn loaded_mut(&mut self) -> Option<&mut dyn LoadedPlugin<StartArgs, Instance>> {
         Some(self)
     }
+    fn unload(&mut self) {
+        if self.instance.take().is_some() {
+            tracing::debug!("Plugin `{}` stopped", self.name());
+        }
+    }
 }
 
 impl<StartArgs, Instance: PluginInstance, P> LoadedPlugin<StartArgs, Instance>
@@ -15,7 +15,7 @@ use std::{borrow::Cow, ops::BitOrAssign};
 
 use serde::{Deserialize, Serialize};
 use zenoh_keyexpr::keyexpr;
-use zenoh_result::ZResult;
+use zenoh_result::{bail, ZResult};
 
 use crate::StructVersion;
 
@@ -190,6 +190,17 @@ pub trait Plugin: Sized + 'static {
     const PLUGIN_LONG_VERSION: &'static str;
     /// Starts your plugin. Use `Ok` to return your plugin's control structure
     fn start(name: &str, args: &Self::StartArgs) -> ZResult<Self::Instance>;
+    /// Stops the tasks and threads the plugin runs outside of its instances, e.g. on a runtime of
+    /// its own, and waits for them to finish.
+    ///
+    /// It is called when a dynamic plugin is unloaded, after its instance is dropped: the library
+    /// of the plugin is only unloaded if it succeeds, which it doesn't by default.
+    fn stop() -> ZResult<()> {
+        bail!(
+            "Plugin `{}` doesn't support being stopped",
+            Self::DEFAULT_NAME
+        )
+    }
 }
 
 #[macro_export]
@@ -19,16 +19,18 @@ pub type PluginLoaderVersion = u64;
 pub const PLUGIN_LOADER_VERSION: PluginLoaderVersion = 1;
 
 type StartFn<StartArgs, Instance> = fn(&str, &StartArgs) -> ZResult<Instance>;
+type StopFn = fn() -> ZResult<()>;
 
 #[repr(C)]
 pub struct PluginVTable<StartArgs, Instance> {
     pub plugin_version: &'static str,
     pub plugin_long_version: &'static str,
     pub start: StartFn<StartArgs, Instance>,
+    pub stop: StopFn,
 }
 impl<StartArgs, Instance> StructVersion for PluginVTable<StartArgs, Instance> {
     fn struct_version() -> u64 {
-        1
+        2
     }
     fn struct_features() -> &'static str {
         FEATURES
@@ -41,6 +43,7 @@ impl<StartArgs, Instance> PluginVTable<StartArgs, Instance> {
             plugin_version: ConcretePlugin::PLUGIN_VERSION,
             plugin_long_version: ConcretePlugin::PLUGIN_LONG_VERSION,
             start: ConcretePlugin::start,
+            stop: ConcretePlugin::stop,
         }
     }
 }
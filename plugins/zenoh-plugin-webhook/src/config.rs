@@ -0,0 +1,139 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_BATCH_SIZE: usize = 1;
+const DEFAULT_BATCH_TIMEOUT_MS: u64 = 1000;
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_RETRY_DELAY_MS: u64 = 500;
+const DEFAULT_TIMEOUT_MS: u64 = 5000;
+const DEFAULT_QUEUE_SIZE: usize = 64;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The webhooks, by name.
+    #[serde(default)]
+    pub hooks: HashMap<String, HookConfig>,
+    #[serde(default)]
+    __path__: Option<serde_json::Value>,
+    __required__: Option<bool>,
+    __config__: Option<String>,
+    __plugin__: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    /// The key expression of the samples to push, which may be a key expression format capturing
+    /// chunks of the keys, e.g. `sensors/${room:*}/${sensor:**}`. The wildcards of a format must
+    /// be within its captures.
+    pub key_expr: String,
+    /// The `http://` URL the samples are posted to, in which `${<id>}` is replaced by the chunks
+    /// captured by `<id>` in the key of the samples.
+    pub url: String,
+    /// The additional headers of the requests.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The maximum number of samples posted in a request.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// The maximum delay before the samples of an incomplete batch are posted.
+    #[serde(default = "default_batch_timeout_ms")]
+    pub batch_timeout_ms: u64,
+    /// The number of times a failed request is retried.
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    /// The delay before the first retry of a failed request, doubled for each following retry.
+    #[serde(default = "default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    /// The timeout of a request.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// The maximum number of batches waiting to be posted. The batches completed while it is
+    /// full are dropped.
+    #[serde(default = "default_queue_size")]
+    pub queue_size: usize,
+}
+
+impl HookConfig {
+    pub fn batch_timeout(&self) -> Duration {
+        Duration::from_millis(self.batch_timeout_ms)
+    }
+
+    pub fn retry_delay(&self) -> Duration {
+        Duration::from_millis(self.retry_delay_ms)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_batch_timeout_ms() -> u64 {
+    DEFAULT_BATCH_TIMEOUT_MS
+}
+
+fn default_retries() -> u32 {
+    DEFAULT_RETRIES
+}
+
+fn default_retry_delay_ms() -> u64 {
+    DEFAULT_RETRY_DELAY_MS
+}
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+fn default_queue_size() -> usize {
+    DEFAULT_QUEUE_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config() {
+        let config = serde_json::from_str::<Config>(
+            r#"{
+                "hooks": {
+                    "alerts": {
+                        "key_expr": "alerts/${room:*}",
+                        "url": "http://localhost:8080/${room}",
+                        "batch_size": 10
+                    }
+                },
+                "__required__": true
+            }"#,
+        )
+        .unwrap();
+        let hook = &config.hooks["alerts"];
+        assert_eq!(hook.batch_size, 10);
+        assert_eq!(hook.batch_timeout(), Duration::from_secs(1));
+        assert_eq!(hook.retries, DEFAULT_RETRIES);
+        assert_eq!(hook.queue_size, DEFAULT_QUEUE_SIZE);
+        assert!(hook.headers.is_empty());
+
+        assert!(serde_json::from_str::<Config>(r#"{"hooks": {"a": {"key_expr": "a"}}}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"unknown": 1}"#).is_err());
+    }
+}
@@ -0,0 +1,457 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! A webhook: the samples matching its key expression are posted to its URL, as a JSON array of
+//! `{"key": ..., "value": ..., "encoding": ..., "timestamp": ..., "kind": ...}` objects.
+//!
+//! The samples are grouped in batches by URL, a batch being posted when it is full or when its
+//! `batch_timeout_ms` after its first sample. The batches are queued and posted one at a time, in
+//! order: when the endpoints are too slow, the queue fills up and the new batches are dropped.
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use base64::{engine::general_purpose, Engine};
+use http_types::{Method, Request, Url};
+use tokio::{
+    net::TcpStream,
+    sync::mpsc::{self, error::TrySendError},
+    time::{sleep_until, timeout, Instant},
+};
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use zenoh::{
+    bytes::Encoding,
+    internal::{bail, zerror},
+    key_expr::{format::OwnedKeFormat, keyexpr, OwnedKeyExpr},
+    sample::Sample,
+    session::Session,
+    Result as ZResult,
+};
+
+use crate::{config::HookConfig, spawn_runtime};
+
+// A batch of samples, with the URL it is posted to
+type Batch = (String, Vec<serde_json::Value>);
+
+#[derive(Debug, PartialEq)]
+enum UrlPart {
+    Text(String),
+    Capture(String),
+}
+
+#[derive(Default)]
+struct HookStats {
+    samples: AtomicU64,
+    delivered: AtomicU64,
+    retried: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+pub(crate) struct Hook {
+    name: String,
+    key_expr: OwnedKeyExpr,
+    format: Option<OwnedKeFormat>,
+    url: Vec<UrlPart>,
+    config: HookConfig,
+    stats: HookStats,
+}
+
+impl Hook {
+    pub(crate) fn new(name: &str, config: HookConfig) -> ZResult<Self> {
+        if keyexpr::new(name).map_or(true, |k| k.is_wild() || name.contains('/')) {
+            bail!(
+                "Invalid webhook name '{}': expecting a key expression chunk",
+                name
+            );
+        }
+        if config.batch_size == 0 {
+            bail!("Webhook '{}': `batch_size` must be greater than 0", name);
+        }
+        if config.queue_size == 0 {
+            bail!("Webhook '{}': `queue_size` must be greater than 0", name);
+        }
+        let (key_expr, format, ids) = if config.key_expr.contains("${") {
+            let format = OwnedKeFormat::from_str(&config.key_expr)
+                .map_err(|e| zerror!("Webhook '{}': invalid `key_expr`: {}", name, e))?;
+            let ids: Vec<&str> = config
+                .key_expr
+                .split("${")
+                .skip(1)
+                .filter_map(|spec| spec.split_once(':').map(|(id, _)| id))
+                .collect();
+            (OwnedKeyExpr::try_from(&*format)?, Some(format), ids)
+        } else {
+            (
+                OwnedKeyExpr::autocanonize(config.key_expr.clone())?,
+                None,
+                vec![],
+            )
+        };
+
+        let url = parse_url_template(&config.url)?;
+        for part in &url {
+            if let UrlPart::Capture(id) = part {
+                if !ids.contains(&id.as_str()) {
+                    bail!(
+                        "Webhook '{}': `url` uses `${{{}}}`, which is not captured by `key_expr`",
+                        name,
+                        id
+                    );
+                }
+            }
+        }
+        let example: String = url
+            .iter()
+            .map(|part| match part {
+                UrlPart::Text(text) => text.as_str(),
+                UrlPart::Capture(_) => "capture",
+            })
+            .collect();
+        match Url::parse(&example) {
+            Ok(url) if url.scheme() == "http" && url.host_str().is_some() => {}
+            Ok(_) => bail!("Webhook '{}': only `http://` URLs are supported", name),
+            Err(e) => bail!("Webhook '{}': invalid `url`: {}", name, e),
+        }
+
+        Ok(Hook {
+            name: name.into(),
+            key_expr,
+            format,
+            url,
+            config,
+            stats: HookStats::default(),
+        })
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn status(&self) -> serde_json::Value {
+        serde_json::json!({
+            "key_expr": self.config.key_expr,
+            "url": self.config.url,
+            "samples": self.stats.samples.load(Ordering::Relaxed),
+            "delivered": self.stats.delivered.load(Ordering::Relaxed),
+            "retried": self.stats.retried.load(Ordering::Relaxed),
+            "failed": self.stats.failed.load(Ordering::Relaxed),
+            "dropped": self.stats.dropped.load(Ordering::Relaxed),
+        })
+    }
+
+    /// Returns the URL the sample with the given key is posted to.
+    fn url(&self, key: &keyexpr) -> ZResult<String> {
+        let parsed = match &self.format {
+            Some(format) => Some(format.parse(key)?),
+            None => None,
+        };
+        let mut url = String::new();
+        for part in &self.url {
+            match (part, &parsed) {
+                (UrlPart::Text(text), _) => url.push_str(text),
+                (UrlPart::Capture(id), Some(parsed)) => {
+                    percent_encode(&mut url, parsed.get(id)?);
+                }
+                (UrlPart::Capture(id), None) => bail!("No capture for `${{{}}}`", id),
+            }
+        }
+        Ok(url)
+    }
+
+    pub(crate) async fn run(self: Arc<Self>, session: Arc<Session>) {
+        let subscriber = match session.declare_subscriber(&self.key_expr).await {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                tracing::error!("Webhook '{}' failed to subscribe: {}", self.name, e);
+                return;
+            }
+        };
+        tracing::debug!(
+            "Webhook '{}' subscribed to {}",
+            self.name,
+            subscriber.key_expr()
+        );
+        // The batches to post, delivered by a single task
+        let (queue, pending) = mpsc::channel(self.config.queue_size);
+        spawn_runtime(self.clone().deliver_all(pending));
+        // The pending batches by URL, with the instant they are due
+        let mut batches: HashMap<String, (Instant, Vec<serde_json::Value>)> = HashMap::new();
+        loop {
+            let deadline = batches.values().map(|(deadline, _)| *deadline).min();
+            tokio::select! {
+                sample = subscriber.recv_async() => {
+                    let Ok(sample) = sample else {
+                        break;
+                    };
+                    let url = match self.url(sample.key_expr()) {
+                        Ok(url) => url,
+                        Err(e) => {
+                            tracing::debug!(
+                                "Webhook '{}' ignoring < {} >: {}",
+                                self.name,
+                                sample.key_expr(),
+                                e
+                            );
+                            continue;
+                        }
+                    };
+                    self.stats.samples.fetch_add(1, Ordering::Relaxed);
+                    let (_, batch) = batches
+                        .entry(url.clone())
+                        .or_insert_with(|| (Instant::now() + self.config.batch_timeout(), vec![]));
+                    batch.push(sample_to_json(&sample));
+                    if batch.len() >= self.config.batch_size {
+                        if let Some((_, batch)) = batches.remove(&url) {
+                            self.post(&queue, url, batch);
+                        }
+                    }
+                }
+                _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    let now = Instant::now();
+                    let due: Vec<String> = batches
+                        .iter()
+                        .filter(|(_, (deadline, _))| *deadline <= now)
+                        .map(|(url, _)| url.clone())
+                        .collect();
+                    for url in due {
+                        if let Some((_, batch)) = batches.remove(&url) {
+                            self.post(&queue, url, batch);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn post(&self, queue: &mpsc::Sender<Batch>, url: String, batch: Vec<serde_json::Value>) {
+        if batch.is_empty() {
+            return;
+        }
+        if let Err(TrySendError::Full((url, batch))) = queue.try_send((url, batch)) {
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                "Webhook '{}' dropped {} samples: too many pending requests, not posting to {}",
+                self.name,
+                batch.len(),
+                url
+            );
+        }
+    }
+
+    /// Posts the queued batches, until the queue is closed.
+    async fn deliver_all(self: Arc<Self>, mut pending: mpsc::Receiver<Batch>) {
+        while let Some((url, batch)) = pending.recv().await {
+            self.deliver(url, batch).await;
+        }
+    }
+
+    async fn deliver(&self, url: String, batch: Vec<serde_json::Value>) {
+        let body = serde_json::to_vec(&batch).unwrap_or_default();
+        let mut delay = self.config.retry_delay();
+        for attempt in 0..=self.config.retries {
+            let error = match timeout(self.config.timeout(), self.send(&url, body.clone())).await {
+                Ok(Ok(())) => {
+                    self.stats.delivered.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => "timeout".to_string(),
+            };
+            tracing::debug!(
+                "Webhook '{}' failed to post to {} (attempt {}): {}",
+                self.name,
+                url,
+                attempt + 1,
+                error
+            );
+            if attempt < self.config.retries {
+                self.stats.retried.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+        self.stats.failed.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(
+            "Webhook '{}' dropped {} samples: failed to post to {}",
+            self.name,
+            batch.len(),
+            url
+        );
+    }
+
+    async fn send(&self, url: &str, body: Vec<u8>) -> ZResult<()> {
+        let url = Url::parse(url)?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| zerror!("No host in URL {}", url))?;
+        let port = url.port_or_known_default().unwrap_or(80);
+        let stream = TcpStream::connect((host, port)).await?;
+
+        let mut request = Request::new(Method::Post, url.clone());
+        for (name, value) in &self.config.headers {
+            request.insert_header(name.as_str(), value.as_str());
+        }
+        request.set_content_type(http_types::mime::JSON);
+        request.set_body(body);
+        let response = async_h1::connect(stream.compat(), request)
+            .await
+            .map_err(|e| zerror!("{}", e))?;
+        if !response.status().is_success() {
+            bail!("HTTP status {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+fn parse_url_template(template: &str) -> ZResult<Vec<UrlPart>> {
+    let mut parts = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            bail!("Invalid `url` '{}': unterminated `${{`", template);
+        };
+        if start > 0 {
+            parts.push(UrlPart::Text(rest[..start].into()));
+        }
+        parts.push(UrlPart::Capture(rest[start + 2..start + end].into()));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(UrlPart::Text(rest.into()));
+    }
+    Ok(parts)
+}
+
+/// Appends `value` to `url`, percent-encoding its characters other than the unreserved ones and
+/// `/`.
+fn percent_encode(url: &mut String, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                url.push(byte as char)
+            }
+            byte => {
+                let _ = write!(url, "%{byte:02X}");
+            }
+        }
+    }
+}
+
+fn sample_to_json(sample: &Sample) -> serde_json::Value {
+    let bytes = sample.payload().to_bytes();
+    let encoding = sample.encoding();
+    let value: serde_json::Value = match std::str::from_utf8(&bytes) {
+        Ok(text) if encoding == &Encoding::APPLICATION_JSON || encoding == &Encoding::TEXT_JSON => {
+            serde_json::from_str(text).unwrap_or_else(|_| text.into())
+        }
+        Ok(text) => text.into(),
+        Err(_) => general_purpose::STANDARD.encode(&bytes).into(),
+    };
+    serde_json::json!({
+        "key": sample.key_expr().as_str(),
+        "value": value,
+        "encoding": encoding.to_string(),
+        "timestamp": sample.timestamp().map(|ts| ts.to_string()),
+        "kind": sample.kind().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_hook(key_expr: &str, url: &str) -> ZResult<Hook> {
+        let config = serde_json::from_value(serde_json::json!({
+            "key_expr": key_expr,
+            "url": url,
+        }))
+        .unwrap();
+        Hook::new("test", config)
+    }
+
+    #[test]
+    fn test_url_template() {
+        let hook = new_hook(
+            "sensors/${room:*}/${sensor:**}",
+            "http://localhost:8080/rooms/${room}/sensors/${sensor}?source=zenoh",
+        )
+        .unwrap();
+        assert_eq!(hook.key_expr.as_str(), "sensors/*/**");
+        let key = keyexpr::new("sensors/kitchen/temp/1").unwrap();
+        assert_eq!(
+            hook.url(key).unwrap(),
+            "http://localhost:8080/rooms/kitchen/sensors/temp/1?source=zenoh"
+        );
+        let key = keyexpr::new("sensors/living room/temp").unwrap();
+        assert_eq!(
+            hook.url(key).unwrap(),
+            "http://localhost:8080/rooms/living%20room/sensors/temp?source=zenoh"
+        );
+
+        let hook = new_hook("alerts/**", "http://localhost:8080/alerts").unwrap();
+        assert_eq!(
+            hook.url(keyexpr::new("alerts/a/b").unwrap()).unwrap(),
+            "http://localhost:8080/alerts"
+        );
+    }
+
+    #[test]
+    fn test_invalid_hook() {
+        assert!(new_hook("alerts/${room:*}", "http://localhost/${sensor}").is_err());
+        assert!(new_hook("alerts/**", "http://localhost/${room}").is_err());
+        assert!(new_hook("alerts/**", "https://localhost/alerts").is_err());
+        assert!(new_hook("alerts/**", "http://localhost/${room").is_err());
+        assert!(new_hook("alerts/**", "not a url").is_err());
+        let config: HookConfig = serde_json::from_value(serde_json::json!({
+            "key_expr": "alerts/**",
+            "url": "http://localhost/alerts",
+        }))
+        .unwrap();
+        assert!(Hook::new("a/b", config.clone()).is_err());
+        assert!(Hook::new("a", config.clone()).is_ok());
+        let config = HookConfig {
+            queue_size: 0,
+            ..config
+        };
+        assert!(Hook::new("a", config).is_err());
+    }
+
+    #[test]
+    fn test_queue_full() {
+        let config = serde_json::from_value(serde_json::json!({
+            "key_expr": "alerts/**",
+            "url": "http://localhost/alerts",
+            "queue_size": 1,
+        }))
+        .unwrap();
+        let hook = Hook::new("test", config).unwrap();
+        let (queue, mut pending) = mpsc::channel(hook.config.queue_size);
+        for value in ["1", "2"] {
+            hook.post(&queue, "http://localhost/alerts".into(), vec![value.into()]);
+        }
+        // The batch posted while the queue is full is dropped
+        assert_eq!(hook.status()["dropped"], 1);
+        let (_, batch) = pending.try_recv().unwrap();
+        assert_eq!(batch, vec![serde_json::Value::from("1")]);
+        assert!(pending.try_recv().is_err());
+    }
+}
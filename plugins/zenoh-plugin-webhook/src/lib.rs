@@ -0,0 +1,178 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This crate is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use tokio::task::{JoinHandle, JoinSet};
+use zenoh::{
+    internal::{
+        plugins::{RunningPluginTrait, ZenohPlugin},
+        runtime::Runtime,
+        zerror,
+    },
+    key_expr::{keyexpr, KeyExpr},
+    Result as ZResult,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin, PluginControl};
+
+mod config;
+pub use config::{Config, HookConfig};
+mod hook;
+use hook::Hook;
+
+const WORKER_THREAD_NUM: usize = 2;
+const MAX_BLOCK_THREAD_NUM: usize = 50;
+lazy_static::lazy_static! {
+    // The global runtime is used in the dynamic plugins, which we can't get the current runtime
+    // It is created on first use and shut down when the plugin is stopped
+    static ref TOKIO_RUNTIME: Mutex<Option<tokio::runtime::Runtime>> = Mutex::new(None);
+}
+
+/// Returns a handle on the global runtime, creating it if needed.
+fn global_runtime() -> tokio::runtime::Handle {
+    TOKIO_RUNTIME
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(WORKER_THREAD_NUM)
+                .max_blocking_threads(MAX_BLOCK_THREAD_NUM)
+                .enable_all()
+                .build()
+                .expect("Unable to create runtime")
+        })
+        .handle()
+        .clone()
+}
+
+fn spawn_runtime<F>(task: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    // Check whether able to get the current runtime
+    match tokio::runtime::Handle::try_current() {
+        Ok(rt) => {
+            // Able to get the current runtime (standalone binary), spawn on the current runtime
+            rt.spawn(task)
+        }
+        Err(_) => {
+            // Unable to get the current runtime (dynamic plugins), spawn on the global runtime
+            global_runtime().spawn(task)
+        }
+    }
+}
+
+#[cfg(feature = "dynamic_plugin")]
+zenoh_plugin_trait::declare_plugin!(WebhookPlugin);
+
+pub struct WebhookPlugin {}
+
+impl ZenohPlugin for WebhookPlugin {}
+
+impl Plugin for WebhookPlugin {
+    type StartArgs = Runtime;
+    type Instance = zenoh::internal::plugins::RunningPlugin;
+    const DEFAULT_NAME: &'static str = "webhook";
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(
+        name: &str,
+        runtime: &Self::StartArgs,
+    ) -> ZResult<zenoh::internal::plugins::RunningPlugin> {
+        // Try to initiate login.
+        // Required in case of dynamic lib, otherwise no logs.
+        // But cannot be done twice in case of static link.
+        zenoh::init_log_from_env_or("error");
+        tracing::debug!("Webhook plugin {}", Self::PLUGIN_LONG_VERSION);
+
+        let runtime_conf = runtime.config().lock();
+        let plugin_conf = runtime_conf
+            .plugin(name)
+            .ok_or_else(|| zerror!("Plugin `{}`: missing config", name))?;
+        let conf: Config = serde_json::from_value(plugin_conf.clone())
+            .map_err(|e| zerror!("Plugin `{}` configuration error: {}", name, e))?;
+        drop(runtime_conf);
+
+        let hooks = conf
+            .hooks
+            .into_iter()
+            .map(|(name, config)| Hook::new(&name, config).map(Arc::new))
+            .collect::<ZResult<Vec<_>>>()?;
+        let task = spawn_runtime(run(runtime.clone(), hooks.clone()));
+        Ok(Box::new(RunningPlugin { hooks, task }))
+    }
+
+    fn stop() -> ZResult<()> {
+        // The runtime is dropped out of the lock, as it waits for its tasks and threads
+        let runtime = TOKIO_RUNTIME.lock().unwrap().take();
+        drop(runtime);
+        Ok(())
+    }
+}
+
+struct RunningPlugin {
+    hooks: Vec<Arc<Hook>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for RunningPlugin {
+    fn drop(&mut self) {
+        // The subscribers of the hooks are undeclared when their tasks are aborted
+        self.task.abort();
+    }
+}
+
+impl PluginControl for RunningPlugin {}
+
+impl RunningPluginTrait for RunningPlugin {
+    fn adminspace_getter<'a>(
+        &'a self,
+        key_expr: &'a KeyExpr<'a>,
+        plugin_status_key: &str,
+    ) -> ZResult<Vec<zenoh::internal::plugins::Response>> {
+        let mut responses = Vec::new();
+        for hook in &self.hooks {
+            let key = format!("{plugin_status_key}/hooks/{}", hook.name());
+            if keyexpr::new(key.as_str())?.intersects(key_expr) {
+                responses.push(zenoh::internal::plugins::Response::new(key, hook.status()));
+            }
+        }
+        Ok(responses)
+    }
+}
+
+async fn run(runtime: Runtime, hooks: Vec<Arc<Hook>>) {
+    let session = match zenoh::session::init(runtime).await {
+        Ok(session) => Arc::new(session),
+        Err(e) => {
+            tracing::error!("Webhook plugin failed to open a session: {}", e);
+            return;
+        }
+    };
+    let mut tasks = JoinSet::new();
+    for hook in hooks {
+        tasks.spawn(hook.run(session.clone()));
+    }
+    while tasks.join_next().await.is_some() {}
+}
@@ -0,0 +1,126 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the delivery of samples to an HTTP endpoint, batched by templated URL.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread::sleep,
+    time::Duration,
+};
+
+use tokio::runtime::Runtime;
+use zenoh::Config;
+use zenoh_plugin_trait::Plugin;
+
+// Serves the requests on 127.0.0.1:47465, sending their path and body on the returned channel.
+fn serve() -> mpsc::Receiver<(String, serde_json::Value)> {
+    let listener = TcpListener::bind("127.0.0.1:47465").unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            let path = request_line.split(' ').nth(1).unwrap().to_string();
+            let _ = tx.send((path, serde_json::from_slice(&body).unwrap()));
+        }
+    });
+    rx
+}
+
+async fn test_webhook() {
+    let requests = serve();
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/webhook",
+            r#"{
+                    hooks: {
+                        rooms: {
+                            key_expr: "test/rooms/${room:*}/${sensor:**}",
+                            url: "http://127.0.0.1:47465/rooms/${room}",
+                            batch_size: 2,
+                            batch_timeout_ms: 200
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let plugin = zenoh_plugin_webhook::WebhookPlugin::start("webhook", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    session
+        .put("test/rooms/kitchen/temp", r#"{"celsius": 20}"#)
+        .encoding(zenoh::bytes::Encoding::APPLICATION_JSON)
+        .await
+        .unwrap();
+    session.put("test/rooms/kitchen/temp", "21").await.unwrap();
+    session.put("test/rooms/hall/temp", "19").await.unwrap();
+
+    let mut received: Vec<_> = (0..2)
+        .map(|_| requests.recv_timeout(Duration::from_secs(5)).unwrap())
+        .collect();
+    received.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // The incomplete batch is posted after its timeout
+    assert_eq!(received[0].0, "/rooms/hall");
+    assert_eq!(received[0].1.as_array().unwrap().len(), 1);
+    assert_eq!(received[0].1[0]["value"], "19");
+
+    // The full batch is posted immediately
+    assert_eq!(received[1].0, "/rooms/kitchen");
+    let batch = received[1].1.as_array().unwrap();
+    assert_eq!(batch.len(), 2);
+    assert_eq!(batch[0]["key"], "test/rooms/kitchen/temp");
+    assert_eq!(batch[0]["value"]["celsius"], 20);
+    assert_eq!(batch[0]["kind"], "PUT");
+    assert_eq!(batch[1]["value"], "21");
+
+    drop(plugin);
+}
+
+#[test]
+fn webhook_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_webhook().await });
+}
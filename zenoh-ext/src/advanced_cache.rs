@@ -31,6 +31,8 @@ use zenoh::{
     Resolvable, Result as ZResult, Session, Wait, KE_ADV_PREFIX, KE_AT, KE_STARSTAR,
 };
 
+use crate::persistent_cache::{DiskRing, PersistenceConfig};
+
 pub(crate) static KE_UHLC: &keyexpr = ke!("uhlc");
 #[zenoh_macros::unstable]
 kedefine!(
@@ -88,6 +90,7 @@ impl QoSBuilderTrait for RepliesConfig {
 pub struct CacheConfig {
     max_samples: usize,
     replies_config: RepliesConfig,
+    persistence: Option<PersistenceConfig>,
 }
 
 #[zenoh_macros::unstable]
@@ -96,6 +99,7 @@ impl Default for CacheConfig {
         Self {
             max_samples: 1,
             replies_config: RepliesConfig::default(),
+            persistence: None,
         }
     }
 }
@@ -115,6 +119,14 @@ impl CacheConfig {
         self.replies_config = qos;
         self
     }
+
+    /// Also store the samples on disk, so that the history survives the restarts of the
+    /// publisher.
+    #[zenoh_macros::unstable]
+    pub fn persistence(mut self, persistence: PersistenceConfig) -> Self {
+        self.persistence = Some(persistence);
+        self
+    }
 }
 
 /// The builder of an [`AdvancedCache`], allowing to configure it.
@@ -210,6 +222,7 @@ fn decode_range(range: &str) -> (Bound<u32>, Bound<u32>) {
 #[zenoh_macros::unstable]
 pub struct AdvancedCache {
     cache: Arc<RwLock<VecDeque<Sample>>>,
+    persistence: Option<DiskRing>,
     max_samples: usize,
     _queryable: Queryable<()>,
     _token: Option<LivelinessToken>,
@@ -232,6 +245,10 @@ impl AdvancedCache {
             conf.history,
         );
         let cache = Arc::new(RwLock::new(VecDeque::<Sample>::new()));
+        let persistence = match &conf.history.persistence {
+            Some(persistence) => Some(DiskRing::open(persistence)?),
+            None => None,
+        };
 
         // declare the queryable that will answer to queries on cache
         let queryable = conf
@@ -240,6 +257,7 @@ impl AdvancedCache {
             .allowed_origin(conf.queryable_origin)
             .callback({
                 let cache = cache.clone();
+                let persisted = persistence.as_ref().map(DiskRing::samples);
                 move |query| {
                     let range = query
                         .parameters()
@@ -250,7 +268,22 @@ impl AdvancedCache {
                         .parameters()
                         .get("_max")
                         .and_then(|s| s.parse::<u32>().ok());
+                    // History queries are answered from the disk if persisted, while recovery
+                    // queries only concern the samples published since the publisher started
+                    let persisted = match &persisted {
+                        Some(persisted) if query.parameters().get("_sn").is_none() => {
+                            match persisted.samples() {
+                                Ok(samples) => Some(VecDeque::from(samples)),
+                                Err(e) => {
+                                    tracing::warn!("Unable to read persisted cache: {}", e);
+                                    None
+                                }
+                            }
+                        }
+                        _ => None,
+                    };
                     if let Ok(queue) = cache.read() {
+                        let queue = persisted.as_ref().unwrap_or(&queue);
                         if let Some(max) = max {
                             let mut samples = VecDeque::new();
                             for sample in queue.iter() {
@@ -343,6 +376,7 @@ impl AdvancedCache {
 
         Ok(AdvancedCache {
             cache,
+            persistence,
             max_samples: conf.history.max_samples,
             _queryable: queryable,
             _token: token,
@@ -351,6 +385,11 @@ impl AdvancedCache {
 
     #[zenoh_macros::unstable]
     pub(crate) fn cache_sample(&self, sample: Sample) {
+        if let Some(persistence) = &self.persistence {
+            if let Err(e) = persistence.push(&sample) {
+                tracing::warn!("Unable to persist AdvancedPublisher cache sample: {}", e);
+            }
+        }
         if let Ok(mut queue) = self.cache.write() {
             if queue.len() >= self.max_samples {
                 queue.pop_front();
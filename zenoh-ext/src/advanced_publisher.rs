@@ -13,13 +13,19 @@
 //
 use std::{
     future::{IntoFuture, Ready},
-    sync::atomic::{AtomicU32, Ordering},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
+use async_trait::async_trait;
 use zenoh::{
     bytes::{Encoding, OptionZBytes, ZBytes},
     internal::{
         bail,
+        runtime::ZRuntime,
         traits::{
             EncodingBuilderTrait, QoSBuilderTrait, SampleBuilderTrait, TimestampBuilderTrait,
         },
@@ -36,8 +42,12 @@ use zenoh::{
     Resolvable, Resolve, Result as ZResult, Session, Wait, KE_ADV_PREFIX, KE_AT, KE_EMPTY,
 };
 use zenoh_macros::ke;
+use zenoh_util::{Timed, TimedEvent, TimedHandle, Timer};
 
-use crate::advanced_cache::{AdvancedCache, AdvancedCacheBuilder, CacheConfig, KE_UHLC};
+use crate::{
+    advanced_cache::{AdvancedCache, AdvancedCacheBuilder, CacheConfig, KE_UHLC},
+    z_serialize,
+};
 
 pub(crate) static KE_PUB: &keyexpr = ke!("pub");
 
@@ -49,6 +59,27 @@ pub(crate) enum Sequencing {
     SequenceNumber,
 }
 
+#[derive(Debug, Default, Clone)]
+/// Configure the sample miss detection.
+#[zenoh_macros::unstable]
+pub struct MissDetectionConfig {
+    heartbeat: Option<Duration>,
+}
+
+#[zenoh_macros::unstable]
+impl MissDetectionConfig {
+    /// Periodically announce the sequence number of the last published Sample.
+    ///
+    /// This allows [`AdvancedSubscribers`](crate::AdvancedSubscriber) that enable
+    /// [`heartbeat`](crate::RecoveryConfig::heartbeat) to detect the loss of the last Sample(s)
+    /// of a burst without waiting for the next publication.
+    #[zenoh_macros::unstable]
+    pub fn heartbeat(mut self, period: Duration) -> Self {
+        self.heartbeat = Some(period);
+        self
+    }
+}
+
 /// The builder of PublicationCache, allowing to configure it.
 #[must_use = "Resolvables do nothing unless you resolve them using the `res` method from either `SyncResolve` or `AsyncResolve`"]
 #[zenoh_macros::unstable]
@@ -63,6 +94,7 @@ pub struct AdvancedPublisherBuilder<'a, 'b, 'c> {
     is_express: bool,
     meta_key_expr: Option<ZResult<KeyExpr<'c>>>,
     sequencing: Sequencing,
+    miss_config: Option<MissDetectionConfig>,
     liveliness: bool,
     cache: bool,
     history: CacheConfig,
@@ -83,6 +115,7 @@ impl<'a, 'b, 'c> AdvancedPublisherBuilder<'a, 'b, 'c> {
             is_express: builder.is_express,
             meta_key_expr: None,
             sequencing: Sequencing::None,
+            miss_config: None,
             liveliness: false,
             cache: false,
             history: CacheConfig::default(),
@@ -118,8 +151,19 @@ impl<'a, 'b, 'c> AdvancedPublisherBuilder<'a, 'b, 'c> {
     ///
     /// Retransmission can only be achieved if [`cache`](crate::AdvancedPublisherBuilder::cache) is enabled.
     #[zenoh_macros::unstable]
-    pub fn sample_miss_detection(mut self) -> Self {
+    pub fn sample_miss_detection(self) -> Self {
+        self.sample_miss_detection_with(MissDetectionConfig::default())
+    }
+
+    /// Allow matching [`AdvancedSubscribers`](crate::AdvancedSubscriber) to detect lost samples
+    /// and optionally ask for retransimission, configuring the detection, e.g. with a
+    /// [`heartbeat`](crate::MissDetectionConfig::heartbeat).
+    ///
+    /// Retransmission can only be achieved if [`cache`](crate::AdvancedPublisherBuilder::cache) is enabled.
+    #[zenoh_macros::unstable]
+    pub fn sample_miss_detection_with(mut self, config: MissDetectionConfig) -> Self {
         self.sequencing = Sequencing::SequenceNumber;
+        self.miss_config = Some(config);
         self
     }
 
@@ -230,9 +274,10 @@ impl IntoFuture for AdvancedPublisherBuilder<'_, '_, '_> {
 #[zenoh_macros::unstable]
 pub struct AdvancedPublisher<'a> {
     publisher: Publisher<'a>,
-    seqnum: Option<AtomicU32>,
+    seqnum: Option<Arc<AtomicU32>>,
     cache: Option<AdvancedCache>,
     _token: Option<LivelinessToken>,
+    _heartbeat: Option<Heartbeat>,
 }
 
 #[zenoh_macros::unstable]
@@ -270,7 +315,7 @@ impl<'a> AdvancedPublisher<'a> {
         };
 
         let seqnum = match conf.sequencing {
-            Sequencing::SequenceNumber => Some(AtomicU32::new(0)),
+            Sequencing::SequenceNumber => Some(Arc::new(AtomicU32::new(0))),
             Sequencing::Timestamp => {
                 if conf.session.hlc().is_none() {
                     bail!(
@@ -299,18 +344,28 @@ impl<'a> AdvancedPublisher<'a> {
             Some(
                 conf.session
                     .liveliness()
-                    .declare_token(prefix / &key_expr)
+                    .declare_token(prefix.clone() / &key_expr)
                     .wait()?,
             )
         } else {
             None
         };
 
+        let heartbeat = match (&seqnum, conf.miss_config.as_ref().and_then(|c| c.heartbeat)) {
+            (Some(seqnum), Some(period)) => Some(Heartbeat::new(
+                conf.session.declare_publisher(prefix / &key_expr).wait()?,
+                seqnum.clone(),
+                period,
+            )),
+            _ => None,
+        };
+
         Ok(AdvancedPublisher {
             publisher,
             seqnum,
             cache,
             _token: token,
+            _heartbeat: heartbeat,
         })
     }
 
@@ -488,6 +543,64 @@ impl<'a> AdvancedPublisher<'a> {
     }
 }
 
+/// Periodically publishes the sequence number of the last Sample published by an
+/// [`AdvancedPublisher`], until dropped.
+#[zenoh_macros::unstable]
+struct Heartbeat {
+    timer: Timer,
+    handle: Option<TimedHandle>,
+}
+
+#[zenoh_macros::unstable]
+impl Heartbeat {
+    fn new(publisher: Publisher<'static>, seqnum: Arc<AtomicU32>, period: Duration) -> Self {
+        let _rt = ZRuntime::Application.enter();
+        let timer = Timer::new(false);
+        let event = TimedEvent::periodic(period, HeartbeatEvent { publisher, seqnum });
+        let handle = event.get_handle();
+        timer.add(event);
+        Heartbeat {
+            timer,
+            handle: Some(handle),
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Drop for Heartbeat {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.defuse();
+        }
+        self.timer.stop();
+    }
+}
+
+#[zenoh_macros::unstable]
+struct HeartbeatEvent {
+    publisher: Publisher<'static>,
+    seqnum: Arc<AtomicU32>,
+}
+
+#[zenoh_macros::unstable]
+#[async_trait]
+impl Timed for HeartbeatEvent {
+    async fn run(&mut self) {
+        let next = self.seqnum.load(Ordering::Relaxed);
+        if next == 0 {
+            return;
+        }
+        tracing::trace!(
+            "Heartbeat of {}: last sequence number {}",
+            self.publisher.key_expr(),
+            next - 1
+        );
+        if let Err(e) = self.publisher.put(z_serialize(&(next - 1))).await {
+            tracing::warn!("Failed to send heartbeat: {}", e);
+        }
+    }
+}
+
 #[zenoh_macros::unstable]
 pub type AdvancedPublisherPutBuilder<'a> = AdvancedPublicationBuilder<'a, PublicationBuilderPut>;
 #[zenoh_macros::unstable]
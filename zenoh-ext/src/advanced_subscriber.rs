@@ -46,7 +46,10 @@ use {
     zenoh::Result as ZResult,
 };
 
-use crate::advanced_cache::{ke_liveliness, KE_UHLC};
+use crate::{
+    advanced_cache::{ke_liveliness, KE_UHLC},
+    z_deserialize,
+};
 
 #[derive(Debug, Default, Clone)]
 /// Configure query for historical data.
@@ -90,12 +93,16 @@ impl HistoryConfig {
 #[zenoh_macros::unstable]
 pub struct RecoveryConfig {
     periodic_queries: Option<Duration>,
+    repair_window: Option<u32>,
+    heartbeat: bool,
 }
 
 impl std::fmt::Debug for RecoveryConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = f.debug_struct("RetransmissionConf");
         s.field("periodic_queries", &self.periodic_queries);
+        s.field("repair_window", &self.repair_window);
+        s.field("heartbeat", &self.heartbeat);
         s.finish()
     }
 }
@@ -116,6 +123,60 @@ impl RecoveryConfig {
         self.periodic_queries = period;
         self
     }
+
+    /// Only recover the missed Samples among the `window` Samples preceding the last received one.
+    ///
+    /// The older missed Samples are not queried and are reported as missed
+    /// (see [`sample_miss_listener`](crate::AdvancedSubscriber::sample_miss_listener)).
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn repair_window(mut self, window: u32) -> Self {
+        self.repair_window = Some(window);
+        self
+    }
+
+    /// Subscribe to the heartbeats of the [`AdvancedPublishers`](crate::AdvancedPublisher)
+    /// and query for the Samples they announce but that were not received.
+    ///
+    /// This allows to retrieve the last Sample(s) of a burst as soon as the next heartbeat
+    /// is received, without waiting for the next publication nor issuing periodic queries.
+    /// Heartbeats are only sent by [`AdvancedPublishers`](crate::AdvancedPublisher) that enable
+    /// [`heartbeat`](crate::MissDetectionConfig::heartbeat).
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn heartbeat(mut self) -> Self {
+        self.heartbeat = true;
+        self
+    }
+}
+
+/// Statistics on the recovery of missed samples of an [`AdvancedSubscriber`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecoveryStats {
+    queries: u64,
+    recovered: u64,
+    lost: u64,
+}
+
+#[zenoh_macros::unstable]
+impl RecoveryStats {
+    /// The number of queries issued to recover missed samples.
+    pub fn queries(&self) -> u64 {
+        self.queries
+    }
+
+    /// The number of missed samples which were recovered, i.e. the samples received in reply to
+    /// the recovery queries which filled a gap before a received sample or a sample announced by
+    /// a heartbeat.
+    pub fn recovered(&self) -> u64 {
+        self.recovered
+    }
+
+    /// The number of missed samples which could not be recovered.
+    pub fn lost(&self) -> u64 {
+        self.lost
+    }
 }
 
 /// The builder of an [`AdvancedSubscriber`], allowing to configure it.
@@ -360,6 +421,9 @@ impl Wait for AdvancedSubscriberBuilder<'_, '_, '_, Callback<Sample>, true> {
         if let Some(mut liveliness_sub) = sub.liveliness_subscriber.take() {
             liveliness_sub.set_background(true);
         }
+        if let Some(mut heartbeat_sub) = sub.heartbeat_subscriber.take() {
+            heartbeat_sub.set_background(true);
+        }
         Ok(())
     }
 }
@@ -390,6 +454,8 @@ struct State {
     session: Session,
     key_expr: KeyExpr<'static>,
     retransmission: bool,
+    repair_window: Option<u32>,
+    stats: RecoveryStats,
     period: Option<Period>,
     query_target: QueryTarget,
     query_timeout: Duration,
@@ -430,6 +496,8 @@ macro_rules! spawn_periodoic_queries {
 #[zenoh_macros::unstable]
 struct SourceState<T> {
     last_delivered: Option<T>,
+    // The last sample announced by a heartbeat
+    announced: Option<T>,
     pending_queries: u64,
     pending_samples: BTreeMap<T, Sample>,
 }
@@ -441,6 +509,7 @@ pub struct AdvancedSubscriber<Receiver> {
     subscriber: Subscriber<()>,
     receiver: Receiver,
     liveliness_subscriber: Option<Subscriber<()>>,
+    heartbeat_subscriber: Option<Subscriber<()>>,
 }
 
 #[zenoh_macros::unstable]
@@ -468,6 +537,7 @@ fn handle_sample(states: &mut State, sample: Sample) -> bool {
         let new = matches!(&entry, Entry::Vacant(_));
         let state = entry.or_insert(SourceState::<u32> {
             last_delivered: None,
+            announced: None,
             pending_queries: 0,
             pending_samples: BTreeMap::new(),
         });
@@ -483,6 +553,7 @@ fn handle_sample(states: &mut State, sample: Sample) -> bool {
                         source_sn - state.last_delivered.unwrap() - 1,
                         source_id,
                     );
+                    states.stats.lost += (source_sn - state.last_delivered.unwrap() - 1) as u64;
                     for miss_callback in states.miss_handlers.values() {
                         miss_callback.call(Miss {
                             source: *source_id,
@@ -508,6 +579,7 @@ fn handle_sample(states: &mut State, sample: Sample) -> bool {
         let entry = states.timestamped_states.entry(*timestamp.get_id());
         let state = entry.or_insert(SourceState::<Timestamp> {
             last_delivered: None,
+            announced: None,
             pending_queries: 0,
             pending_samples: BTreeMap::new(),
         });
@@ -526,6 +598,45 @@ fn handle_sample(states: &mut State, sample: Sample) -> bool {
     }
 }
 
+/// Handles a sample received in reply to a recovery query, counting it as recovered if it fills
+/// a gap, i.e. if it was missed before a received sample or a sample announced by a heartbeat.
+#[zenoh_macros::unstable]
+fn handle_recovered_sample(states: &mut State, sample: Sample) {
+    if let (Some(source_id), Some(source_sn)) = (
+        sample.source_info().source_id(),
+        sample.source_info().source_sn(),
+    ) {
+        if states.sequenced_states.get(source_id).is_some_and(|state| {
+            let known = state
+                .pending_samples
+                .keys()
+                .next_back()
+                .copied()
+                .max(state.announced);
+            state.last_delivered.is_some_and(|last| source_sn > last)
+                && known.is_some_and(|known| source_sn <= known)
+                && !state.pending_samples.contains_key(&source_sn)
+        }) {
+            states.stats.recovered += 1;
+        }
+    }
+    handle_sample(states, sample);
+}
+
+/// Returns the first sequence number to query to recover the missed samples of `state`.
+#[zenoh_macros::unstable]
+fn recovery_start(state: &SourceState<u32>, repair_window: Option<u32>) -> Option<u32> {
+    let start = state.last_delivered.map(|s| s + 1);
+    match (
+        start,
+        repair_window,
+        state.pending_samples.keys().next_back(),
+    ) {
+        (Some(start), Some(window), Some(newest)) => Some(start.max(newest.saturating_sub(window))),
+        _ => start,
+    }
+}
+
 #[zenoh_macros::unstable]
 fn seq_num_range(start: Option<u32>, end: Option<u32>) -> String {
     match (start, end) {
@@ -551,6 +662,7 @@ impl Timed for PeriodicQuery {
         let states = &mut *lock;
         if let Some(state) = states.sequenced_states.get_mut(&self.source_id) {
             state.pending_queries += 1;
+            states.stats.queries += 1;
             let query_expr = KE_ADV_PREFIX
                 / KE_STAR
                 / &self.source_id.zid().into_keyexpr()
@@ -558,7 +670,7 @@ impl Timed for PeriodicQuery {
                 / KE_STARSTAR
                 / KE_AT
                 / &states.key_expr;
-            let seq_num_range = seq_num_range(state.last_delivered.map(|s| s + 1), None);
+            let seq_num_range = seq_num_range(recovery_start(state, states.repair_window), None);
 
             let session = states.session.clone();
             let key_expr = states.key_expr.clone().into_owned();
@@ -576,7 +688,7 @@ impl Timed for PeriodicQuery {
                         if let Ok(s) = r.into_result() {
                             if key_expr.intersects(s.key_expr()) {
                                 let states = &mut *zlock!(handler.statesref);
-                                handle_sample(states, s);
+                                handle_recovered_sample(states, s);
                             }
                         }
                     }
@@ -603,6 +715,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
             None => None,
         };
         let retransmission = conf.retransmission;
+        let heartbeat = retransmission.as_ref().is_some_and(|r| r.heartbeat);
         let query_target = conf.query_target;
         let query_timeout = conf.query_timeout;
         let session = conf.session.clone();
@@ -621,6 +734,8 @@ impl<Handler> AdvancedSubscriber<Handler> {
             }),
             key_expr: key_expr.clone().into_owned(),
             retransmission: retransmission.is_some(),
+            repair_window: retransmission.as_ref().and_then(|r| r.repair_window),
+            stats: RecoveryStats::default(),
             query_target: conf.query_target,
             query_timeout: conf.query_timeout,
             callback: callback.clone(),
@@ -650,6 +765,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
                             && !state.pending_samples.is_empty()
                         {
                             state.pending_queries += 1;
+                            states.stats.queries += 1;
                             let query_expr = KE_ADV_PREFIX
                                 / KE_STAR
                                 / &source_id.zid().into_keyexpr()
@@ -658,7 +774,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
                                 / KE_AT
                                 / &key_expr;
                             let seq_num_range =
-                                seq_num_range(state.last_delivered.map(|s| s + 1), None);
+                                seq_num_range(recovery_start(state, states.repair_window), None);
                             drop(lock);
                             let handler = SequencedRepliesHandler {
                                 source_id,
@@ -672,7 +788,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
                                         if let Ok(s) = r.into_result() {
                                             if key_expr.intersects(s.key_expr()) {
                                                 let states = &mut *zlock!(handler.statesref);
-                                                handle_sample(states, s);
+                                                handle_recovered_sample(states, s);
                                             }
                                         }
                                     }
@@ -751,6 +867,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
                                         let entry = states.timestamped_states.entry(ID::from(zid));
                                         let state = entry.or_insert(SourceState::<Timestamp> {
                                             last_delivered: None,
+                                            announced: None,
                                             pending_queries: 0,
                                             pending_samples: BTreeMap::new(),
                                         });
@@ -803,6 +920,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
                                         let new = matches!(&entry, Entry::Vacant(_));
                                         let state = entry.or_insert(SourceState::<u32> {
                                             last_delivered: None,
+                                            announced: None,
                                             pending_queries: 0,
                                             pending_samples: BTreeMap::new(),
                                         });
@@ -921,6 +1039,94 @@ impl<Handler> AdvancedSubscriber<Handler> {
             None
         };
 
+        let heartbeat_subscriber = if heartbeat {
+            let heartbeat_callback = {
+                let session = conf.session.clone();
+                let statesref = statesref.clone();
+                let key_expr = key_expr.clone().into_owned();
+                move |s: Sample| {
+                    let Ok(parsed) = ke_liveliness::parse(s.key_expr().as_keyexpr()) else {
+                        tracing::warn!(
+                            "Received malformed heartbeat key expression: {}",
+                            s.key_expr()
+                        );
+                        return;
+                    };
+                    let (Ok(zid), Ok(eid)) = (
+                        ZenohId::from_str(parsed.zid().as_str()),
+                        EntityId::from_str(parsed.eid().as_str()),
+                    ) else {
+                        return;
+                    };
+                    let Ok(heartbeat_sn) = z_deserialize::<u32>(s.payload()) else {
+                        tracing::warn!("Received malformed heartbeat from {}", s.key_expr());
+                        return;
+                    };
+                    let source_id = EntityGlobalId::new(zid, eid);
+                    let mut lock = zlock!(statesref);
+                    let states = &mut *lock;
+                    if states.global_pending_queries != 0 {
+                        return;
+                    }
+                    // Only query for the sources already known, the others are recovered by history
+                    let Some(state) = states.sequenced_states.get_mut(&source_id) else {
+                        return;
+                    };
+                    state.announced = state.announced.max(Some(heartbeat_sn));
+                    if state.pending_queries != 0
+                        || state
+                            .last_delivered
+                            .map_or(true, |last| last >= heartbeat_sn)
+                    {
+                        return;
+                    }
+                    state.pending_queries += 1;
+                    states.stats.queries += 1;
+                    let query_expr = KE_ADV_PREFIX
+                        / KE_STAR
+                        / &source_id.zid().into_keyexpr()
+                        / &KeyExpr::try_from(source_id.eid().to_string()).unwrap()
+                        / KE_STARSTAR
+                        / KE_AT
+                        / &key_expr;
+                    let seq_num_range =
+                        seq_num_range(recovery_start(state, states.repair_window), None);
+                    drop(lock);
+                    let handler = SequencedRepliesHandler {
+                        source_id,
+                        statesref: statesref.clone(),
+                    };
+                    let _ = session
+                        .get(Selector::from((query_expr, seq_num_range)))
+                        .callback({
+                            let key_expr = key_expr.clone().into_owned();
+                            move |r: Reply| {
+                                if let Ok(s) = r.into_result() {
+                                    if key_expr.intersects(s.key_expr()) {
+                                        let states = &mut *zlock!(handler.statesref);
+                                        handle_recovered_sample(states, s);
+                                    }
+                                }
+                            }
+                        })
+                        .consolidation(ConsolidationMode::None)
+                        .accept_replies(ReplyKeyExpr::Any)
+                        .target(query_target)
+                        .timeout(query_timeout)
+                        .wait();
+                }
+            };
+
+            Some(
+                conf.session
+                    .declare_subscriber(KE_ADV_PREFIX / KE_PUB / KE_STARSTAR / KE_AT / &key_expr)
+                    .callback(heartbeat_callback)
+                    .wait()?,
+            )
+        } else {
+            None
+        };
+
         if conf.liveliness {
             let prefix = KE_ADV_PREFIX
                 / KE_SUB
@@ -944,6 +1150,7 @@ impl<Handler> AdvancedSubscriber<Handler> {
             subscriber,
             receiver,
             liveliness_subscriber,
+            heartbeat_subscriber,
         };
 
         Ok(reliable_subscriber)
@@ -977,6 +1184,12 @@ impl<Handler> AdvancedSubscriber<Handler> {
         &mut self.receiver
     }
 
+    /// Returns the statistics on the recovery of missed samples.
+    #[zenoh_macros::unstable]
+    pub fn recovery_stats(&self) -> RecoveryStats {
+        zlock!(self.statesref).stats
+    }
+
     /// Declares a listener to detect missed samples.
     ///
     /// Missed samples can only be detected from [`AdvancedPublisher`](crate::AdvancedPublisher) that
@@ -1015,6 +1228,7 @@ fn flush_sequenced_source(
     callback: &Callback<Sample>,
     source_id: &EntityGlobalId,
     miss_handlers: &HashMap<usize, Callback<Miss>>,
+    stats: &mut RecoveryStats,
 ) {
     if state.pending_queries == 0 && !state.pending_samples.is_empty() {
         let mut pending_samples = BTreeMap::new();
@@ -1035,6 +1249,7 @@ fn flush_sequenced_source(
                         seq_num - last - 1,
                         source_id,
                     );
+                    stats.lost += (seq_num - last - 1) as u64;
                     for miss_callback in miss_handlers.values() {
                         miss_callback.call(Miss {
                             source: *source_id,
@@ -1085,7 +1300,13 @@ impl Drop for InitialRepliesHandler {
 
         if states.global_pending_queries == 0 {
             for (source_id, state) in states.sequenced_states.iter_mut() {
-                flush_sequenced_source(state, &states.callback, source_id, &states.miss_handlers);
+                flush_sequenced_source(
+                    state,
+                    &states.callback,
+                    source_id,
+                    &states.miss_handlers,
+                    &mut states.stats,
+                );
                 spawn_periodoic_queries!(states, *source_id, self.statesref.clone());
             }
             for state in states.timestamped_states.values_mut() {
@@ -1114,6 +1335,7 @@ impl Drop for SequencedRepliesHandler {
                     &states.callback,
                     &self.source_id,
                     &states.miss_handlers,
+                    &mut states.stats,
                 )
             }
         }
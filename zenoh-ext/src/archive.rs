@@ -0,0 +1,191 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{borrow::Cow, fmt, marker::PhantomData, ops::Deref};
+
+use rkyv::{
+    ser::serializers::AllocSerializer, validation::validators::DefaultValidator, AlignedVec,
+    Archive, CheckBytes, Deserialize, Infallible, Serialize,
+};
+use zenoh::{
+    bytes::ZBytes,
+    internal::zerror,
+    pubsub::{Publisher, PublisherPutBuilder},
+    sample::Sample,
+    Result as ZResult,
+};
+
+use crate::ZDeserializeError;
+
+// The size of the scratch space of the serializer before it allocates
+const SCRATCH_SIZE: usize = 256;
+
+/// Archive an object with [rkyv](https://rkyv.org), so that it can be accessed in place by the
+/// receiver with [`z_access`].
+///
+/// The archived types must derive [`CheckBytes`] with `#[archive(check_bytes)]` to be validated
+/// by the receiver.
+///
+/// # Examples
+/// ```rust
+/// use zenoh_ext::{z_access, z_archive};
+///
+/// #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+/// #[archive(check_bytes)]
+/// struct Position {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let payload = z_archive(&Position { x: 1.0, y: 2.0 }).unwrap();
+/// let position = z_access::<Position>(&payload).unwrap();
+/// assert_eq!(position.x, 1.0);
+/// ```
+#[zenoh_macros::unstable]
+pub fn z_archive<T>(t: &T) -> ZResult<ZBytes>
+where
+    T: Serialize<AllocSerializer<SCRATCH_SIZE>>,
+{
+    let bytes = rkyv::to_bytes::<T, SCRATCH_SIZE>(t).map_err(|e| zerror!("{}", e))?;
+    Ok(bytes.into_vec().into())
+}
+
+/// Validate an object archived with [`z_archive`] and access it in place.
+///
+/// No copy is made if the payload is contiguous and suitably aligned, which is the case of the
+/// payloads received in a single buffer, e.g. shared memory ones. Otherwise, the payload is first
+/// copied into an aligned buffer.
+#[zenoh_macros::unstable]
+pub fn z_access<T>(zbytes: &ZBytes) -> Result<ZArchived<'_, T>, ZDeserializeError>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>>,
+{
+    let bytes = match zbytes.to_bytes() {
+        Cow::Borrowed(bytes) if (bytes.as_ptr() as usize) % AlignedVec::ALIGNMENT == 0 => {
+            Storage::Borrowed(bytes)
+        }
+        bytes => {
+            let mut aligned = AlignedVec::with_capacity(bytes.len());
+            aligned.extend_from_slice(&bytes);
+            Storage::Aligned(aligned)
+        }
+    };
+    rkyv::check_archived_root::<T>(&bytes).map_err(|e| {
+        tracing::debug!("invalid archive: {}", e);
+        ZDeserializeError
+    })?;
+    Ok(ZArchived {
+        bytes,
+        _t: PhantomData,
+    })
+}
+
+/// Validate an object archived with [`z_archive`] and deserialize it.
+#[zenoh_macros::unstable]
+pub fn z_unarchive<T>(zbytes: &ZBytes) -> Result<T, ZDeserializeError>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<T, Infallible>,
+{
+    let archived = z_access::<T>(zbytes)?;
+    Deserialize::<T, _>::deserialize(&*archived, &mut Infallible).map_err(|_| ZDeserializeError)
+}
+
+enum Storage<'a> {
+    Borrowed(&'a [u8]),
+    Aligned(AlignedVec),
+}
+
+impl Deref for Storage<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Storage::Borrowed(bytes) => bytes,
+            Storage::Aligned(bytes) => bytes,
+        }
+    }
+}
+
+/// An object archived with [`z_archive`], validated and accessed in place by [`z_access`].
+///
+/// It dereferences to the archived representation of `T`.
+#[zenoh_macros::unstable]
+pub struct ZArchived<'a, T: Archive> {
+    // Aligned and validated at construction
+    bytes: Storage<'a>,
+    _t: PhantomData<T>,
+}
+
+#[zenoh_macros::unstable]
+impl<T: Archive> Deref for ZArchived<'_, T> {
+    type Target = T::Archived;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the bytes were aligned and validated as a `T::Archived` by `z_access`.
+        unsafe { rkyv::archived_root::<T>(&self.bytes) }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<T: Archive> fmt::Debug for ZArchived<'_, T>
+where
+    T::Archived: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// Publishing of the objects archived with [`z_archive`] by a [`Publisher`].
+#[zenoh_macros::unstable]
+pub trait PublisherArchiveExt {
+    /// Archive the object and publish it.
+    #[zenoh_macros::unstable]
+    fn put_archived<T>(&self, t: &T) -> ZResult<PublisherPutBuilder<'_>>
+    where
+        T: Serialize<AllocSerializer<SCRATCH_SIZE>>;
+}
+
+#[zenoh_macros::unstable]
+impl PublisherArchiveExt for Publisher<'_> {
+    fn put_archived<T>(&self, t: &T) -> ZResult<PublisherPutBuilder<'_>>
+    where
+        T: Serialize<AllocSerializer<SCRATCH_SIZE>>,
+    {
+        Ok(self.put(z_archive(t)?))
+    }
+}
+
+/// In place access to the objects archived with [`z_archive`] in the payload of a [`Sample`].
+#[zenoh_macros::unstable]
+pub trait SampleArchiveExt {
+    /// Validate the archived payload and access it in place, see [`z_access`].
+    #[zenoh_macros::unstable]
+    fn access<T>(&self) -> Result<ZArchived<'_, T>, ZDeserializeError>
+    where
+        T: Archive,
+        T::Archived: for<'a> CheckBytes<DefaultValidator<'a>>;
+}
+
+#[zenoh_macros::unstable]
+impl SampleArchiveExt for Sample {
+    fn access<T>(&self) -> Result<ZArchived<'_, T>, ZDeserializeError>
+    where
+        T: Archive,
+        T::Archived: for<'a> CheckBytes<DefaultValidator<'a>>,
+    {
+        z_access(self.payload())
+    }
+}
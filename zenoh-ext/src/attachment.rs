@@ -0,0 +1,272 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::str;
+
+use zenoh::bytes::ZBytes;
+
+use crate::{
+    z_deserialize, z_serialize, Deserialize, Serialize, ZDeserializeError, ZDeserializer,
+    ZSerializer,
+};
+
+/// A map of key-value pairs, carried as the attachment of a put, a delete, a query or a reply.
+///
+/// The keys and values are byte strings, typically UTF-8 strings, and the entries keep the order
+/// in which they were inserted. The attachment is converted to and from [`ZBytes`] with the
+/// [Zenoh serialization format][1] of a sequence of `(key, value)` pairs of byte strings, i.e.:
+///
+/// ```text
+/// +------------------+------------+-----+--------------+--------------+-----+
+/// | number of pairs  | key length | key | value length | value        | ... |
+/// | (varint)         | (varint)   |     | (varint)     |              |     |
+/// +------------------+------------+-----+--------------+--------------+-----+
+/// ```
+///
+/// This layout is stable: an attachment with UTF-8 keys and values can be deserialized as a
+/// `HashMap<String, String>` with [`z_deserialize`], and conversely.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::Attachment;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let attachment = Attachment::new()
+///     .with("trace-id", "4bf92f35")
+///     .with("retries", "2");
+/// session
+///     .put("key/expression", "value")
+///     .attachment(&attachment)
+///     .await
+///     .unwrap();
+///
+/// // On the receiving side, from `sample.attachment()`
+/// let bytes = zenoh::bytes::ZBytes::from(&attachment);
+/// let received = Attachment::try_from(&bytes).unwrap();
+/// assert_eq!(received.get_str("retries"), Some("2"));
+/// # }
+/// ```
+///
+/// [1]: https://github.com/eclipse-zenoh/roadmap/blob/main/rfcs/ALL/Serialization.md
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attachment {
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+#[zenoh_macros::unstable]
+impl Attachment {
+    /// Creates an empty attachment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the attachment with the given entry inserted, see [`Attachment::insert`].
+    pub fn with<K: Into<Vec<u8>>, V: Into<Vec<u8>>>(mut self, key: K, value: V) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Inserts an entry, returning the previous value of the key if any.
+    ///
+    /// An existing key keeps its position, a new key is inserted last.
+    pub fn insert<K: Into<Vec<u8>>, V: Into<Vec<u8>>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Option<Vec<u8>> {
+        let key = key.into();
+        let value = value.into();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Returns the value of the key.
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&[u8]> {
+        let key = key.as_ref();
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    /// Returns the value of the key, if it is a UTF-8 string.
+    pub fn get_str<K: AsRef<[u8]>>(&self, key: K) -> Option<&str> {
+        self.get(key).and_then(|v| str::from_utf8(v).ok())
+    }
+
+    /// Returns `true` if the attachment contains the key.
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes the key, returning its value if any.
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<Vec<u8>> {
+        let key = key.as_ref();
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the attachment has no entry.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.entries
+            .iter()
+            .map(|(k, v)| (k.as_slice(), v.as_slice()))
+    }
+
+    /// Returns an iterator over the entries whose key and value are UTF-8 strings, in insertion
+    /// order.
+    pub fn iter_str(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.iter()
+            .filter_map(|(k, v)| Some((str::from_utf8(k).ok()?, str::from_utf8(v).ok()?)))
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<K: Into<Vec<u8>>, V: Into<Vec<u8>>> Extend<(K, V)> for Attachment {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<K: Into<Vec<u8>>, V: Into<Vec<u8>>> FromIterator<(K, V)> for Attachment {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut attachment = Attachment::new();
+        attachment.extend(iter);
+        attachment
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoIterator for Attachment {
+    type Item = (Vec<u8>, Vec<u8>);
+    type IntoIter = std::vec::IntoIter<(Vec<u8>, Vec<u8>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Serialize for Attachment {
+    fn serialize(&self, serializer: &mut ZSerializer) {
+        serializer.serialize_iter(&self.entries);
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Deserialize for Attachment {
+    fn deserialize(deserializer: &mut ZDeserializer) -> Result<Self, ZDeserializeError> {
+        deserializer
+            .deserialize_iter::<(Vec<u8>, Vec<u8>)>()?
+            .collect()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl From<&Attachment> for ZBytes {
+    fn from(attachment: &Attachment) -> Self {
+        z_serialize(attachment)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl From<Attachment> for ZBytes {
+    fn from(attachment: Attachment) -> Self {
+        z_serialize(&attachment)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl TryFrom<&ZBytes> for Attachment {
+    type Error = ZDeserializeError;
+
+    fn try_from(bytes: &ZBytes) -> Result<Self, Self::Error> {
+        z_deserialize(bytes)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl TryFrom<ZBytes> for Attachment {
+    type Error = ZDeserializeError;
+
+    fn try_from(bytes: ZBytes) -> Result<Self, Self::Error> {
+        z_deserialize(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn attachment_layout() {
+        let attachment = Attachment::new()
+            .with("a", "1")
+            .with(b"b".to_vec(), [0xffu8]);
+        let bytes = ZBytes::from(&attachment);
+        assert_eq!(
+            bytes.to_bytes().as_ref(),
+            [2, 1, b'a', 1, b'1', 1, b'b', 1, 0xff]
+        );
+        assert_eq!(Attachment::try_from(&bytes).unwrap(), attachment);
+
+        let map: HashMap<String, String> = [("k".into(), "v".into())].into();
+        let attachment = Attachment::try_from(z_serialize(&map)).unwrap();
+        assert_eq!(attachment.get_str("k"), Some("v"));
+        assert_eq!(
+            z_deserialize::<HashMap<String, String>>(&attachment.into()).unwrap(),
+            map
+        );
+
+        assert!(Attachment::try_from(ZBytes::from("not an attachment")).is_err());
+    }
+
+    #[test]
+    fn attachment_entries() {
+        let mut attachment: Attachment = [("a", "1"), ("b", "2")].into_iter().collect();
+        assert_eq!(attachment.insert("a", "3"), Some(b"1".to_vec()));
+        attachment.insert("c", [0xffu8]);
+        assert_eq!(attachment.len(), 3);
+        assert_eq!(attachment.get("a"), Some(b"3".as_slice()));
+        assert_eq!(attachment.get_str("c"), None);
+        assert_eq!(
+            attachment.iter_str().collect::<Vec<_>>(),
+            [("a", "3"), ("b", "2")]
+        );
+        assert_eq!(attachment.remove("b"), Some(b"2".to_vec()));
+        assert!(!attachment.contains_key("b"));
+    }
+}
@@ -0,0 +1,240 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! To collect the replies of a get into a collection of deserialized values
+//!
+//! The payload of each reply is deserialized with [`z_deserialize`] as soon as it is received,
+//! and inserted in a [`ReplyCollection`] under its key expression. The replies which could not
+//! be collected are reported with the collection, instead of failing the whole get.
+use std::{
+    collections::HashMap,
+    fmt,
+    future::{Future, IntoFuture},
+    hash::BuildHasher,
+    pin::Pin,
+};
+
+use zenoh::{
+    handlers::DefaultHandler,
+    key_expr::OwnedKeyExpr,
+    query::{Reply, ReplyError},
+    session::SessionGetBuilder,
+    Resolvable, Result as ZResult, Wait,
+};
+
+use crate::{z_deserialize, Deserialize, ZDeserializeError};
+
+/// A collection built from the replies of a get, see [`SessionGetBuilderExt::collect_into`].
+#[zenoh_macros::unstable]
+pub trait ReplyCollection: Default {
+    /// The type the payloads of the replies are deserialized into.
+    type Value: Deserialize;
+
+    /// Inserts the value deserialized from a reply on `key_expr`.
+    fn insert(&mut self, key_expr: OwnedKeyExpr, value: Self::Value);
+}
+
+#[zenoh_macros::unstable]
+impl<T: Deserialize, S: BuildHasher + Default> ReplyCollection for HashMap<OwnedKeyExpr, T, S> {
+    type Value = T;
+
+    fn insert(&mut self, key_expr: OwnedKeyExpr, value: T) {
+        HashMap::insert(self, key_expr, value);
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<T: Deserialize> ReplyCollection for Vec<(OwnedKeyExpr, T)> {
+    type Value = T;
+
+    fn insert(&mut self, key_expr: OwnedKeyExpr, value: T) {
+        self.push((key_expr, value));
+    }
+}
+
+/// A reply which could not be inserted in a [`ReplyCollection`].
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub enum EntryError {
+    /// A queryable replied with an error.
+    Reply(ReplyError),
+    /// The payload of the reply on the key expression could not be deserialized.
+    Deserialize(OwnedKeyExpr, ZDeserializeError),
+}
+
+impl fmt::Display for EntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryError::Reply(e) => write!(f, "error reply: {:?}", e.payload()),
+            EntryError::Deserialize(key_expr, e) => write!(f, "{key_expr}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EntryError {}
+
+/// The collection built from the replies of a get, with the replies which could not be inserted
+/// in it.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct Collected<C> {
+    collection: C,
+    errors: Vec<EntryError>,
+}
+
+#[zenoh_macros::unstable]
+impl<C> Collected<C> {
+    /// Returns the collection.
+    pub fn collection(&self) -> &C {
+        &self.collection
+    }
+
+    /// Returns the errors of the replies which could not be inserted in the collection, in the
+    /// order they were received.
+    pub fn errors(&self) -> &[EntryError] {
+        &self.errors
+    }
+
+    /// Returns `true` if every reply was inserted in the collection.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the collection, ignoring the errors.
+    pub fn into_inner(self) -> C {
+        self.collection
+    }
+
+    /// Returns the collection and the errors.
+    pub fn into_parts(self) -> (C, Vec<EntryError>) {
+        (self.collection, self.errors)
+    }
+
+    /// Returns the collection, or the first error if a reply could not be inserted in it.
+    pub fn into_result(self) -> Result<C, EntryError> {
+        match self.errors.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(self.collection),
+        }
+    }
+}
+
+impl<C: ReplyCollection> Collected<C> {
+    fn new() -> Self {
+        Collected {
+            collection: C::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, reply: Reply) {
+        match reply.into_result() {
+            Ok(sample) => match z_deserialize::<C::Value>(sample.payload()) {
+                Ok(value) => self
+                    .collection
+                    .insert(sample.key_expr().clone().into(), value),
+                Err(e) => self
+                    .errors
+                    .push(EntryError::Deserialize(sample.key_expr().clone().into(), e)),
+            },
+            Err(e) => self.errors.push(EntryError::Reply(e)),
+        }
+    }
+}
+
+/// Some extensions to the [`SessionGetBuilder`].
+#[zenoh_macros::unstable]
+pub trait SessionGetBuilderExt<'a, 'b> {
+    /// Collect the replies into a collection, deserializing their payload as they are received.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use std::collections::HashMap;
+    ///
+    /// use zenoh::key_expr::OwnedKeyExpr;
+    /// use zenoh_ext::SessionGetBuilderExt;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let settings = session
+    ///     .get("settings/**")
+    ///     .collect_into::<HashMap<OwnedKeyExpr, u32>>()
+    ///     .await
+    ///     .unwrap();
+    /// for error in settings.errors() {
+    ///     eprintln!("Invalid setting: {error}");
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    fn collect_into<C: ReplyCollection>(self) -> CollectingGetBuilder<'a, 'b, C>;
+}
+
+#[zenoh_macros::unstable]
+impl<'a, 'b> SessionGetBuilderExt<'a, 'b> for SessionGetBuilder<'a, 'b, DefaultHandler> {
+    #[zenoh_macros::unstable]
+    fn collect_into<C: ReplyCollection>(self) -> CollectingGetBuilder<'a, 'b, C> {
+        CollectingGetBuilder {
+            get: self,
+            collection: Collected::new(),
+        }
+    }
+}
+
+/// A builder resolving a get into a [`Collected`] collection, see
+/// [`SessionGetBuilderExt::collect_into`].
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct CollectingGetBuilder<'a, 'b, C> {
+    get: SessionGetBuilder<'a, 'b, DefaultHandler>,
+    collection: Collected<C>,
+}
+
+#[zenoh_macros::unstable]
+impl<C> Resolvable for CollectingGetBuilder<'_, '_, C> {
+    type To = ZResult<Collected<C>>;
+}
+
+#[zenoh_macros::unstable]
+impl<C: ReplyCollection> Wait for CollectingGetBuilder<'_, '_, C> {
+    #[zenoh_macros::unstable]
+    fn wait(self) -> <Self as Resolvable>::To {
+        let mut collection = self.collection;
+        let replies = self.get.wait()?;
+        while let Ok(reply) = replies.recv() {
+            collection.push(reply);
+        }
+        Ok(collection)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a, C: ReplyCollection + Send + 'a> IntoFuture for CollectingGetBuilder<'a, '_, C> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+    #[zenoh_macros::unstable]
+    fn into_future(self) -> Self::IntoFuture {
+        let mut collection = self.collection;
+        let replies = self.get.wait();
+        Box::pin(async move {
+            let replies = replies?;
+            while let Ok(reply) = replies.recv_async().await {
+                collection.push(reply);
+            }
+            Ok(collection)
+        })
+    }
+}
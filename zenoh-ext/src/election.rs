@@ -0,0 +1,434 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! To elect a leader among the candidates of an election
+//!
+//! Every candidate declares a liveliness token, so that the other candidates know which
+//! candidates are alive. The leader renews its leadership by publishing heartbeats, and keeps
+//! it as long as it is alive and its lease does not expire: a candidate joining the election
+//! never takes over an established leader. When the leader leaves, fails or stops renewing its
+//! lease, the alive candidate with the smallest identifier becomes the new leader. Should two
+//! leaders be elected, for instance after a network partition heals, the one with the smallest
+//! identifier keeps the leadership. A leader which fails to publish its heartbeats gives up its
+//! leadership before its lease expires for the other candidates, so that it does not keep acting
+//! as leader once another one is elected.
+use std::{
+    collections::HashSet,
+    convert::TryInto,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use flume::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::ZBytesReader,
+    internal::{bail, TaskController},
+    key_expr::{keyexpr, OwnedKeyExpr},
+    liveliness::LivelinessToken,
+    pubsub::Publisher,
+    qos::CongestionControl,
+    sample::SampleKind,
+    Error as ZError, Result as ZResult, Session,
+};
+
+const ELECTION_PREFIX: &str = "zenoh/ext/net/election";
+// The candidates' liveliness tokens are declared under a dedicated chunk, so that no candidate
+// identifier can clash with the heartbeats' key expression
+const CANDIDATES_POSTFIX: &str = "candidates";
+const HEARTBEAT_POSTFIX: &str = "heartbeat";
+const DEFAULT_LEASE: Duration = Duration::from_secs(10);
+// The number of heartbeats published by the leader during its lease
+const HEARTBEATS_PER_LEASE: u32 = 4;
+
+/// Events exposed to the user to be informed of leadership changes.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElectionEvent {
+    /// The candidate was elected leader.
+    Elected(OwnedKeyExpr),
+    /// The leader lost its leadership, either because it left, failed or did not renew its
+    /// lease in time, or because another leader was elected concurrently.
+    Lost(OwnedKeyExpr),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Heartbeat {
+    cid: OwnedKeyExpr,
+    lease: Duration,
+}
+
+/// A candidate to an [`Election`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    cid: OwnedKeyExpr,
+    lease: Duration,
+}
+
+impl Candidate {
+    pub fn new<T>(cid: T) -> ZResult<Candidate>
+    where
+        T: TryInto<OwnedKeyExpr> + Send,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        let cid: OwnedKeyExpr = cid.try_into().map_err(|e| e.into())?;
+        if cid.is_wild() {
+            bail!("Candidate ID is not allowed to contain wildcards: {}", cid);
+        }
+        Ok(Candidate {
+            cid,
+            lease: DEFAULT_LEASE,
+        })
+    }
+
+    pub fn id(&self) -> &keyexpr {
+        &self.cid
+    }
+
+    /// Sets the lease of the leadership of this candidate: once elected, the other candidates
+    /// consider it lost if they do not receive any heartbeat from it during this duration.
+    /// A candidate joining the election also waits for this duration before taking part in
+    /// the election, to learn about the current leader.
+    pub fn lease(mut self, d: Duration) -> Self {
+        self.lease = d;
+        self
+    }
+}
+
+struct Leader {
+    cid: OwnedKeyExpr,
+    expires: Option<Instant>,
+}
+
+struct ElectionInner {
+    // The other alive candidates
+    candidates: HashSet<OwnedKeyExpr>,
+    leader: Option<Leader>,
+    events_tx: Vec<Sender<ElectionEvent>>,
+    // The local candidate waits for a lease before taking part in the election, when it joins it
+    // and when it gave up its leadership
+    eligible_at: Instant,
+}
+
+struct ElectionState {
+    eid: String,
+    local_candidate: Candidate,
+    publisher: Publisher<'static>,
+    inner: Mutex<ElectionInner>,
+}
+
+impl ElectionState {
+    fn lock(&self) -> MutexGuard<'_, ElectionInner> {
+        self.inner
+            .lock()
+            .expect("acquiring the election state Mutex should not fail")
+    }
+
+    fn is_local(&self, cid: &keyexpr) -> bool {
+        *cid == *self.local_candidate.cid
+    }
+
+    fn heartbeat_period(&self) -> Duration {
+        self.local_candidate.lease / HEARTBEATS_PER_LEASE
+    }
+
+    /// The expiration of the leadership of the local candidate, renewed by each heartbeat
+    /// published. It expires a heartbeat period before the lease seen by the other candidates.
+    fn local_expiration(&self) -> Option<Instant> {
+        Some(Instant::now() + self.local_candidate.lease - self.heartbeat_period())
+    }
+
+    /// Elects the local candidate if there is no leader and it is the first alive candidate,
+    /// returns `true` if the local candidate is the leader.
+    fn update(&self) -> bool {
+        let now = Instant::now();
+        let mut inner = self.lock();
+        if inner
+            .leader
+            .as_ref()
+            .and_then(|l| l.expires)
+            .is_some_and(|expires| expires < now)
+        {
+            if inner.leader.as_ref().is_some_and(|l| self.is_local(&l.cid)) {
+                tracing::warn!(
+                    "Candidate {} failed to renew its leadership of election {}",
+                    &self.local_candidate.cid,
+                    &self.eid
+                );
+                inner.eligible_at = now + self.local_candidate.lease;
+            } else {
+                tracing::debug!("Lease of the leader of election {} expired", &self.eid);
+            }
+            set_leader(&mut inner, None);
+        }
+        if inner.leader.is_none() && now >= inner.eligible_at {
+            let local = self.local_candidate.cid.as_str();
+            if inner.candidates.iter().all(|cid| cid.as_str() > local) {
+                let leader = Leader {
+                    cid: self.local_candidate.cid.clone(),
+                    expires: self.local_expiration(),
+                };
+                set_leader(&mut inner, Some(leader));
+            }
+        }
+        inner.leader.as_ref().is_some_and(|l| self.is_local(&l.cid))
+    }
+
+    async fn heartbeat(&self) {
+        let hb = Heartbeat {
+            cid: self.local_candidate.cid.clone(),
+            lease: self.local_candidate.lease,
+        };
+        tracing::trace!("Sending Heartbeat for: {}", &hb.cid);
+        let buf = bincode::serialize(&hb).unwrap();
+        if let Err(e) = self.publisher.put(buf).await {
+            tracing::warn!("Failed sending heartbeat due to: {}", e);
+            return;
+        }
+        let mut inner = self.lock();
+        if let Some(leader) = inner.leader.as_mut().filter(|l| self.is_local(&l.cid)) {
+            leader.expires = self.local_expiration();
+        }
+    }
+
+    /// Gives up the leadership if the local candidate is the leader.
+    fn resign(&self) {
+        let mut inner = self.lock();
+        if inner.leader.as_ref().is_some_and(|l| self.is_local(&l.cid)) {
+            set_leader(&mut inner, None);
+        }
+    }
+}
+
+/// Sets the leader to `leader`, notifying the user if it changed.
+fn set_leader(inner: &mut ElectionInner, leader: Option<Leader>) {
+    let old = inner.leader.as_ref().map(|l| l.cid.clone());
+    let new = leader.as_ref().map(|l| l.cid.clone());
+    inner.leader = leader;
+    if old == new {
+        return;
+    }
+    let mut events = vec![];
+    if let Some(cid) = old {
+        events.push(ElectionEvent::Lost(cid));
+    }
+    if let Some(cid) = new {
+        tracing::debug!("New leader: {}", cid);
+        events.push(ElectionEvent::Elected(cid));
+    }
+    inner
+        .events_tx
+        .retain(|tx| events.iter().all(|evt| tx.send(evt.clone()).is_ok()));
+}
+
+async fn heartbeat_task(state: Arc<ElectionState>) {
+    let period = state.heartbeat_period();
+    loop {
+        if state.update() {
+            state.heartbeat().await;
+        }
+        tokio::time::sleep(period).await;
+    }
+}
+
+async fn heartbeat_handler(z: Arc<Session>, state: Arc<ElectionState>) {
+    let sub = z
+        .declare_subscriber(state.publisher.key_expr())
+        .await
+        .unwrap();
+    while let Ok(s) = sub.recv_async().await {
+        let hb = match bincode::deserialize_from::<ZBytesReader, Heartbeat>(s.payload().reader()) {
+            Ok(hb) => hb,
+            Err(e) => {
+                tracing::warn!("Failed decoding heartbeat due to: {:?}", e);
+                continue;
+            }
+        };
+        if state.is_local(&hb.cid) {
+            continue;
+        }
+        tracing::trace!("Heartbeat from {}", &hb.cid);
+        let mut inner = state.lock();
+        // Ignore the late heartbeats of the candidates which left
+        if !inner.candidates.contains(&hb.cid) {
+            continue;
+        }
+        let expires = Some(Instant::now() + hb.lease);
+        match &mut inner.leader {
+            Some(leader) if leader.cid == hb.cid => leader.expires = expires,
+            // Concurrent leaders: the one with the smallest identifier wins
+            Some(leader) if leader.cid.as_str() < hb.cid.as_str() => {}
+            _ => set_leader(
+                &mut inner,
+                Some(Leader {
+                    cid: hb.cid,
+                    expires,
+                }),
+            ),
+        }
+    }
+}
+
+async fn liveliness_handler(z: Arc<Session>, state: Arc<ElectionState>) {
+    let prefix = format!("{}/{}/{}/", ELECTION_PREFIX, &state.eid, CANDIDATES_POSTFIX);
+    let sub = z
+        .liveliness()
+        .declare_subscriber(format!("{prefix}**"))
+        .history(true)
+        .await
+        .unwrap();
+    while let Ok(s) = sub.recv_async().await {
+        let Some(cid) = s
+            .key_expr()
+            .as_str()
+            .strip_prefix(&prefix)
+            .and_then(|cid| OwnedKeyExpr::try_from(cid).ok())
+        else {
+            continue;
+        };
+        if state.is_local(&cid) {
+            continue;
+        }
+        match s.kind() {
+            SampleKind::Put => {
+                tracing::debug!("Candidate {} joined election {}", &cid, &state.eid);
+                state.lock().candidates.insert(cid);
+            }
+            SampleKind::Delete => {
+                tracing::debug!("Candidate {} left election {}", &cid, &state.eid);
+                {
+                    let mut inner = state.lock();
+                    inner.candidates.remove(&cid);
+                    if inner.leader.as_ref().is_some_and(|l| l.cid == cid) {
+                        set_leader(&mut inner, None);
+                    }
+                }
+                if state.update() {
+                    state.heartbeat().await;
+                }
+            }
+        }
+    }
+}
+
+/// The participation of a [`Candidate`] to a leader election.
+///
+/// The candidate leaves the election when this is dropped.
+#[zenoh_macros::unstable]
+pub struct Election {
+    state: Arc<ElectionState>,
+    task_controller: TaskController,
+    token: Option<LivelinessToken>,
+}
+
+impl Drop for Election {
+    fn drop(&mut self) {
+        // cancel background tasks
+        self.task_controller.terminate_all(Duration::from_secs(10));
+        self.state.resign();
+    }
+}
+
+impl Election {
+    /// Joins the election `election` as the candidate `with`.
+    pub async fn join<T>(z: Arc<Session>, election: T, with: Candidate) -> ZResult<Election>
+    where
+        T: TryInto<OwnedKeyExpr>,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        let election: OwnedKeyExpr = election.try_into().map_err(|e| e.into())?;
+        if election.is_wild() {
+            bail!(
+                "Election ID is not allowed to contain wildcards: {}",
+                election
+            );
+        }
+
+        // A dropped heartbeat would let the lease of the leader expire for the other candidates
+        let publisher = z
+            .declare_publisher(format!("{ELECTION_PREFIX}/{election}/{HEARTBEAT_POSTFIX}"))
+            .congestion_control(CongestionControl::Block)
+            .await?;
+        let token = z
+            .liveliness()
+            .declare_token(format!(
+                "{ELECTION_PREFIX}/{election}/{CANDIDATES_POSTFIX}/{}",
+                with.cid
+            ))
+            .await?;
+        let state = Arc::new(ElectionState {
+            eid: String::from(election),
+            publisher,
+            inner: Mutex::new(ElectionInner {
+                candidates: HashSet::new(),
+                leader: None,
+                events_tx: vec![],
+                eligible_at: Instant::now() + with.lease,
+            }),
+            local_candidate: with,
+        });
+
+        let task_controller = TaskController::default();
+        task_controller.spawn_abortable(heartbeat_handler(z.clone(), state.clone()));
+        task_controller.spawn_abortable(liveliness_handler(z.clone(), state.clone()));
+        task_controller.spawn_abortable(heartbeat_task(state.clone()));
+        Ok(Election {
+            state,
+            task_controller,
+            token: Some(token),
+        })
+    }
+
+    /// Leaves the election, giving up the leadership if the local candidate is the leader.
+    pub async fn resign(mut self) -> ZResult<()> {
+        self.task_controller.terminate_all(Duration::from_secs(10));
+        self.state.resign();
+        if let Some(token) = self.token.take() {
+            token.undeclare().await?;
+        }
+        Ok(())
+    }
+
+    /// Returns a receiver of the leadership changes.
+    pub fn subscribe(&self) -> Receiver<ElectionEvent> {
+        let (tx, rx) = flume::unbounded();
+        self.state.lock().events_tx.push(tx);
+        rx
+    }
+
+    /// Returns the election identifier.
+    pub fn election_id(&self) -> &str {
+        &self.state.eid
+    }
+
+    /// Returns this candidate identifier.
+    pub fn local_candidate_id(&self) -> &str {
+        &self.state.local_candidate.cid
+    }
+
+    /// Returns the identifier of the current leader, if any.
+    pub fn leader(&self) -> Option<OwnedKeyExpr> {
+        self.state.lock().leader.as_ref().map(|l| l.cid.clone())
+    }
+
+    /// Returns `true` if the local candidate is the current leader.
+    pub fn is_leader(&self) -> bool {
+        self.state
+            .lock()
+            .leader
+            .as_ref()
+            .is_some_and(|l| self.state.is_local(&l.cid))
+    }
+}
@@ -27,10 +27,13 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 use zenoh::{
     bytes::ZBytesReader,
+    handlers::FifoChannelHandler,
     internal::{bail, Condition, TaskController},
     key_expr::{keyexpr, KeyExpr, OwnedKeyExpr},
-    pubsub::Publisher,
+    liveliness::LivelinessToken,
+    pubsub::{Publisher, Subscriber},
     qos::Priority,
+    sample::{Sample, SampleKind},
     Error as ZError, Result as ZResult, Session,
 };
 
@@ -39,6 +42,7 @@ const EVENT_POSTFIX: &str = "evt";
 const VIEW_REFRESH_LEASE_RATIO: f32 = 0.75f32;
 const DEFAULT_LEASE: Duration = Duration::from_secs(18);
 const DEFAULT_PRIORITY: Priority = Priority::DataHigh;
+const WATCHDOG_PERIOD: Duration = Duration::from_secs(1);
 
 #[zenoh_macros::unstable]
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -140,6 +144,10 @@ impl Member {
         self
     }
 
+    /// Sets the lease of this member: the other members consider it failed if they do not
+    /// receive any keep alive from it during this duration. The departure of a member whose
+    /// session is closed or disconnected is detected through its liveliness token, without
+    /// waiting for its lease to expire.
     pub fn lease(mut self, d: Duration) -> Self {
         self.lease = d;
         self
@@ -167,6 +175,7 @@ struct GroupState {
     members: Mutex<HashMap<OwnedKeyExpr, (Member, Instant)>>,
     group_publisher: Publisher<'static>,
     user_events_tx: Mutex<Option<Sender<GroupEvent>>>,
+    leader: Mutex<OwnedKeyExpr>,
     cond: Condition,
 }
 
@@ -174,6 +183,7 @@ struct GroupState {
 pub struct Group {
     state: Arc<GroupState>,
     task_controller: TaskController,
+    _token: LivelinessToken,
 }
 
 impl Drop for Group {
@@ -183,6 +193,76 @@ impl Drop for Group {
     }
 }
 
+/// Sends `evt` to the user, followed by a [`GroupEvent::NewLeader`] if the leader changed.
+async fn notify(state: &GroupState, evt: GroupEvent) {
+    let leader = {
+        let ms = state.members.lock().await;
+        ms.keys()
+            .chain(std::iter::once(&state.local_member.mid))
+            .max_by(|a, b| a.as_str().cmp(b.as_str()))
+            .cloned()
+            .unwrap()
+    };
+    let u_evt = &*state.user_events_tx.lock().await;
+    if let Some(tx) = u_evt {
+        let _ = tx.send(evt);
+    }
+    let mut current = state.leader.lock().await;
+    if *current != leader {
+        tracing::debug!("New leader: {}", leader);
+        *current = leader.clone();
+        if let Some(tx) = u_evt {
+            let _ = tx.send(GroupEvent::NewLeader(NewLeaderEvent { mid: leader }));
+        }
+    }
+}
+
+/// Queries the information of the member `mid`.
+async fn query_member(z: &Session, state: &GroupState, mid: &keyexpr) -> Option<Member> {
+    let qres = format!("{}/{}/{}", GROUP_PREFIX, &state.gid, mid);
+    let qc = zenoh::query::ConsolidationMode::None;
+    tracing::trace!("Issuing Query for {}", &qres);
+    let receiver = match z.get(&qres).consolidation(qc).await {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            tracing::warn!("Unable to query member {}: {}", mid, e);
+            return None;
+        }
+    };
+    let mut member = None;
+    while let Ok(reply) = receiver.recv_async().await {
+        match reply.result() {
+            Ok(sample) => {
+                match bincode::deserialize_from::<ZBytesReader, Member>(sample.payload().reader()) {
+                    Ok(m) => {
+                        tracing::debug!("Received member information: {:?}", &m);
+                        member = Some(m);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Unable to deserialize the Member info received: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Error received: {:?}", e);
+            }
+        }
+    }
+    member
+}
+
+/// Adds `member` to the group view, returns `false` if it was already part of it.
+async fn add_member(state: &GroupState, member: Member) -> bool {
+    let alive_till = Instant::now().add(member.lease);
+    let mut ms = state.members.lock().await;
+    let added = ms
+        .insert(member.mid.clone(), (member, alive_till))
+        .is_none();
+    tracing::debug!("Other members list: {:?}", ms.keys());
+    state.cond.notify_all();
+    added
+}
+
 async fn keep_alive_task(state: Arc<GroupState>) {
     let mid = state.local_member.mid.clone();
     let evt = GroupNetEvent::KeepAlive(KeepAliveEvent { mid });
@@ -216,13 +296,11 @@ async fn watchdog_task(s: Arc<GroupState>, period: Duration) {
         if !expired_members.is_empty() {
             tracing::debug!("Other members list: {:?}", ms.keys());
             drop(ms);
-            let u_evt = &*s.user_events_tx.lock().await;
             for e in expired_members {
-                if let Some(tx) = u_evt {
-                    tx.send(GroupEvent::LeaseExpired(LeaseExpiredEvent { mid: e }))
-                        .unwrap()
-                }
+                notify(&s, GroupEvent::LeaseExpired(LeaseExpiredEvent { mid: e })).await;
             }
+        } else {
+            drop(ms);
         }
     }
 }
@@ -254,26 +332,18 @@ async fn net_event_handler(z: Arc<Session>, state: Arc<GroupState>) {
             Ok(evt) => match evt {
                 GroupNetEvent::Join(je) => {
                     tracing::debug!("Member join: {:?}", &je.member);
-                    let alive_till = Instant::now().add(je.member.lease);
-                    let mut ms = state.members.lock().await;
-                    ms.insert(je.member.mid.clone(), (je.member.clone(), alive_till));
-                    tracing::debug!("Other members list: {:?}", ms.keys());
-                    state.cond.notify_all();
-                    drop(ms);
-                    let u_evt = &*state.user_events_tx.lock().await;
-                    if let Some(tx) = u_evt {
-                        tx.send(GroupEvent::Join(je)).unwrap()
+                    if add_member(&state, je.member.clone()).await {
+                        notify(&state, GroupEvent::Join(je)).await;
                     }
                 }
                 GroupNetEvent::Leave(le) => {
                     tracing::debug!("Member leave: {:?}", &le.mid);
                     let mut ms = state.members.lock().await;
-                    ms.remove(&le.mid);
+                    let removed = ms.remove(&le.mid).is_some();
                     tracing::debug!("Other members list: {:?}", ms.keys());
                     drop(ms);
-                    let u_evt = &*state.user_events_tx.lock().await;
-                    if let Some(tx) = u_evt {
-                        tx.send(GroupEvent::Leave(le)).unwrap()
+                    if removed {
+                        notify(&state, GroupEvent::Leave(le)).await;
                     }
                 }
                 GroupNetEvent::KeepAlive(kae) => {
@@ -296,55 +366,19 @@ async fn net_event_handler(z: Arc<Session>, state: Arc<GroupState>) {
                                 mm.insert(m.mid.clone(), (m, alive_till));
                             }
                             None => {
+                                drop(mm);
                                 tracing::debug!(
                                     "Received Keep Alive from unknown member: {}",
                                     &kae.mid
                                 );
-                                let qres = format!("{}/{}/{}", GROUP_PREFIX, &state.gid, kae.mid);
                                 // @TODO: we could also send this member info
-                                let qc = zenoh::query::ConsolidationMode::None;
-                                tracing::trace!("Issuing Query for {}", &qres);
-                                let receiver = z.get(&qres).consolidation(qc).await.unwrap();
-
-                                while let Ok(reply) = receiver.recv_async().await {
-                                    match reply.result() {
-                                        Ok(sample) => {
-                                            match bincode::deserialize_from::<ZBytesReader, Member>(
-                                                sample.payload().reader(),
-                                            ) {
-                                                Ok(m) => {
-                                                    let mut expiry = Instant::now();
-                                                    expiry = expiry.add(m.lease);
-                                                    tracing::debug!(
-                                                        "Received member information: {:?}",
-                                                        &m
-                                                    );
-                                                    mm.insert(kae.mid.clone(), (m.clone(), expiry));
-                                                    tracing::debug!(
-                                                        "Other members list: {:?}",
-                                                        mm.keys()
-                                                    );
-                                                    // Advertise a JoinEvent
-                                                    let u_evt = &*state.user_events_tx.lock().await;
-                                                    if let Some(tx) = u_evt {
-                                                        let je = JoinEvent { member: m };
-                                                        tx.send_async(GroupEvent::Join(je))
-                                                            .await
-                                                            .unwrap()
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    tracing::warn!(
-                                                        "Unable to deserialize the Member info received: {}", e);
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            tracing::warn!("Error received: {:?}", e);
-                                        }
+                                if let Some(m) = query_member(&z, &state, &kae.mid).await {
+                                    // Advertise a JoinEvent
+                                    if add_member(&state, m.clone()).await {
+                                        notify(&state, GroupEvent::Join(JoinEvent { member: m }))
+                                            .await;
                                     }
                                 }
-                                state.cond.notify_all();
                             }
                         }
                     } else {
@@ -359,7 +393,52 @@ async fn net_event_handler(z: Arc<Session>, state: Arc<GroupState>) {
     }
 }
 
+async fn liveliness_handler(
+    z: Arc<Session>,
+    state: Arc<GroupState>,
+    sub: Subscriber<FifoChannelHandler<Sample>>,
+) {
+    let prefix = format!("{}/{}/", GROUP_PREFIX, &state.gid);
+    while let Ok(s) = sub.recv_async().await {
+        let Some(mid) = s
+            .key_expr()
+            .as_str()
+            .strip_prefix(&prefix)
+            .and_then(|mid| OwnedKeyExpr::try_from(mid).ok())
+        else {
+            continue;
+        };
+        if mid == state.local_member.mid {
+            continue;
+        }
+        match s.kind() {
+            SampleKind::Put => {
+                if state.members.lock().await.contains_key(&mid) {
+                    continue;
+                }
+                tracing::debug!("Liveliness token of unknown member: {}", &mid);
+                if let Some(m) = query_member(&z, &state, &mid).await {
+                    if add_member(&state, m.clone()).await {
+                        notify(&state, GroupEvent::Join(JoinEvent { member: m })).await;
+                    }
+                }
+            }
+            SampleKind::Delete => {
+                let removed = state.members.lock().await.remove(&mid).is_some();
+                if removed {
+                    tracing::debug!("Liveliness token of member {} lost", &mid);
+                    notify(&state, GroupEvent::LeaseExpired(LeaseExpiredEvent { mid })).await;
+                }
+            }
+        }
+    }
+}
+
 impl Group {
+    /// Joins the group `group` as the member `with`.
+    ///
+    /// The member declares a liveliness token, so that the other members detect its departure
+    /// as soon as its session is closed or disconnected.
     pub async fn join<T>(z: Arc<Session>, group: T, with: Member) -> ZResult<Group>
     where
         T: TryInto<OwnedKeyExpr>,
@@ -374,16 +453,25 @@ impl Group {
         let publisher = z
             .declare_publisher(event_expr)
             .priority(with.priority)
-            .await
-            .unwrap();
+            .await?;
         let state = Arc::new(GroupState {
-            gid: String::from(group),
+            gid: String::from(group.clone()),
             local_member: with.clone(),
             members: Mutex::new(Default::default()),
             group_publisher: publisher,
             user_events_tx: Mutex::new(Default::default()),
+            leader: Mutex::new(with.mid.clone()),
             cond: Condition::new(),
         });
+        let token = z
+            .liveliness()
+            .declare_token(format!("{GROUP_PREFIX}/{group}/{}", with.mid))
+            .await?;
+        let liveliness_sub = z
+            .liveliness()
+            .declare_subscriber(format!("{GROUP_PREFIX}/{group}/**"))
+            .history(true)
+            .await?;
         let is_auto_liveliness = matches!(with.liveliness, MemberLiveliness::Auto);
 
         // announce the member:
@@ -399,13 +487,37 @@ impl Group {
         }
         task_controller.spawn_abortable(net_event_handler(z.clone(), state.clone()));
         task_controller.spawn_abortable(query_handler(z.clone(), state.clone()));
-        task_controller.spawn_abortable(watchdog_task(state.clone(), Duration::from_secs(1)));
+        task_controller.spawn_abortable(liveliness_handler(
+            z.clone(),
+            state.clone(),
+            liveliness_sub,
+        ));
+        // Check the leases at least twice per lease of the local member
+        let watchdog_period = WATCHDOG_PERIOD.min(state.local_member.lease / 2);
+        task_controller.spawn_abortable(watchdog_task(state.clone(), watchdog_period));
         Ok(Group {
             state,
             task_controller,
+            _token: token,
         })
     }
 
+    /// Leaves the group, announcing the departure of the local member to the other members.
+    pub async fn leave(self) -> ZResult<()> {
+        tracing::debug!(
+            "Sending Leave Message for local member: {:?}",
+            &self.state.local_member
+        );
+        let leave_evt = GroupNetEvent::Leave(LeaveEvent {
+            mid: self.state.local_member.mid.clone(),
+        });
+        let buf = bincode::serialize(&leave_evt)?;
+        self.state.group_publisher.put(buf).await?;
+        // Dropping the group stops its tasks and undeclares its liveliness token
+        drop(self);
+        Ok(())
+    }
+
     /// Returns a receivers that will allow to receive notifications for group events.
     /// Notice that there can be a single subscription at the time, each call to subscribe
     /// will cancel the previous subscription.
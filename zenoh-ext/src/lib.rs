@@ -17,20 +17,42 @@ mod advanced_cache;
 mod advanced_publisher;
 #[cfg(feature = "unstable")]
 mod advanced_subscriber;
+#[cfg(all(feature = "unstable", feature = "rkyv"))]
+mod archive;
+#[cfg(feature = "unstable")]
+mod attachment;
+#[cfg(feature = "unstable")]
+mod collect;
+#[cfg(feature = "unstable")]
+pub mod election;
 #[cfg(feature = "unstable")]
 pub mod group;
 #[cfg(feature = "unstable")]
+mod ordered_channel;
+#[cfg(feature = "unstable")]
+mod persistent_cache;
+#[cfg(feature = "unstable")]
 mod publication_cache;
 #[cfg(feature = "unstable")]
 mod publisher_ext;
 #[cfg(feature = "unstable")]
 mod querying_subscriber;
+#[cfg(feature = "unstable")]
+pub mod queue;
+#[cfg(feature = "unstable")]
+pub mod rpc;
 mod serialization;
 #[cfg(feature = "unstable")]
 mod session_ext;
 #[cfg(feature = "unstable")]
+pub mod shared;
+#[cfg(feature = "unstable")]
 mod subscriber_ext;
 
+#[cfg(all(feature = "unstable", feature = "rkyv"))]
+pub use crate::archive::{
+    z_access, z_archive, z_unarchive, PublisherArchiveExt, SampleArchiveExt, ZArchived,
+};
 #[cfg(feature = "internal")]
 pub use crate::serialization::VarInt;
 pub use crate::serialization::{
@@ -41,11 +63,16 @@ pub use crate::serialization::{
 #[allow(deprecated)]
 pub use crate::{
     advanced_cache::{CacheConfig, RepliesConfig},
-    advanced_publisher::{AdvancedPublisher, AdvancedPublisherBuilder},
+    advanced_publisher::{AdvancedPublisher, AdvancedPublisherBuilder, MissDetectionConfig},
     advanced_subscriber::{
         AdvancedSubscriber, AdvancedSubscriberBuilder, HistoryConfig, Miss, RecoveryConfig,
-        SampleMissHandlerUndeclaration, SampleMissListener, SampleMissListenerBuilder,
+        RecoveryStats, SampleMissHandlerUndeclaration, SampleMissListener,
+        SampleMissListenerBuilder,
     },
+    attachment::Attachment,
+    collect::{Collected, CollectingGetBuilder, EntryError, ReplyCollection, SessionGetBuilderExt},
+    ordered_channel::OrderedChannel,
+    persistent_cache::PersistenceConfig,
     publication_cache::{PublicationCache, PublicationCacheBuilder},
     publisher_ext::AdvancedPublisherBuilderExt,
     querying_subscriber::{
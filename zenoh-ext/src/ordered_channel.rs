@@ -0,0 +1,182 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use zenoh::{
+    handlers::{Callback, FifoChannel, FifoChannelHandler, IntoHandler},
+    internal::zlock,
+    sample::Sample,
+    session::EntityGlobalId,
+};
+
+const DEFAULT_WINDOW: u32 = 16;
+
+/// A channel delivering the samples of every source in the order of their sequence numbers.
+///
+/// The samples received out of order, e.g. because they were published with different
+/// priorities or over different links, are held back until the missing samples are received.
+/// A missing sample is given up once `window` more recent samples of its source are held back,
+/// and the held back samples are then delivered. The samples older than the last delivered
+/// sample of their source are dropped.
+///
+/// The samples are ordered according to their [`SourceInfo`](zenoh::sample::SourceInfo), which
+/// is set by an [`AdvancedPublisher`](crate::AdvancedPublisher) with sample miss detection
+/// enabled. The samples without source sequence number are delivered as soon as received.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::OrderedChannel;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let subscriber = session
+///     .declare_subscriber("key/expr")
+///     .with(OrderedChannel::new(100).window(32))
+///     .await
+///     .unwrap();
+/// while let Ok(sample) = subscriber.recv_async().await {
+///     println!("Received: {:?}", sample);
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct OrderedChannel {
+    capacity: usize,
+    window: u32,
+}
+
+#[zenoh_macros::unstable]
+impl OrderedChannel {
+    /// Initialize the [`OrderedChannel`] with the capacity size.
+    #[zenoh_macros::unstable]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            window: DEFAULT_WINDOW,
+        }
+    }
+
+    /// Change the maximum number of samples held back per source while waiting for a
+    /// missing sample.
+    #[zenoh_macros::unstable]
+    pub fn window(mut self, window: u32) -> Self {
+        self.window = window.max(1);
+        self
+    }
+}
+
+struct SourceState {
+    // The sequence number of the next sample to deliver
+    next: u32,
+    // The held back samples, by distance of their sequence number from `next`
+    pending: BTreeMap<u32, Sample>,
+}
+
+impl SourceState {
+    /// Moves the next sample to deliver `n` sequence numbers forward, the held back samples
+    /// before it having been delivered.
+    fn advance(&mut self, n: u32) {
+        if n == 0 {
+            return;
+        }
+        self.next = self.next.wrapping_add(n);
+        self.pending = std::mem::take(&mut self.pending)
+            .into_iter()
+            .map(|(distance, sample)| (distance - n, sample))
+            .collect();
+    }
+}
+
+struct Reorderer {
+    window: u32,
+    sources: HashMap<EntityGlobalId, SourceState>,
+}
+
+impl Reorderer {
+    /// Returns the samples to deliver, in order, after the reception of `sample`.
+    ///
+    /// The sequence numbers wrap around: a sample is late if its sequence number is less than
+    /// half of the sequence number space behind the next one to deliver.
+    fn push(&mut self, sample: Sample) -> Vec<Sample> {
+        let (Some(source_id), Some(sn)) = (
+            sample.source_info().source_id().copied(),
+            sample.source_info().source_sn(),
+        ) else {
+            return vec![sample];
+        };
+        let state = self.sources.entry(source_id).or_insert(SourceState {
+            next: sn,
+            pending: BTreeMap::new(),
+        });
+        let distance = sn.wrapping_sub(state.next);
+        if (distance as i32) < 0 {
+            tracing::debug!("Dropping late sample {} from {:?}", sn, source_id);
+            return vec![];
+        }
+        state.pending.insert(distance, sample);
+        let mut ready = vec![];
+        // Give up on the missing samples which are out of the window
+        if let (Some(&first), Some(&last)) = (
+            state.pending.keys().next(),
+            state.pending.keys().next_back(),
+        ) {
+            if last >= self.window {
+                let skip = first.max(last - self.window + 1);
+                tracing::debug!(
+                    "Missing samples {}..{} from {:?}, delivering the held back ones",
+                    state.next,
+                    state.next.wrapping_add(skip),
+                    source_id
+                );
+                let kept = state.pending.split_off(&skip);
+                ready.extend(std::mem::replace(&mut state.pending, kept).into_values());
+                state.advance(skip);
+            }
+        }
+        let mut delivered = 0;
+        while let Some(sample) = state.pending.remove(&delivered) {
+            ready.push(sample);
+            delivered += 1;
+        }
+        state.advance(delivered);
+        ready
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoHandler<Sample> for OrderedChannel {
+    type Handler = FifoChannelHandler<Sample>;
+
+    fn into_handler(self) -> (Callback<Sample>, Self::Handler) {
+        let (callback, handler) = FifoChannel::new(self.capacity).into_handler();
+        let reorderer = Mutex::new(Reorderer {
+            window: self.window,
+            sources: HashMap::new(),
+        });
+        (
+            Callback::new(Arc::new(move |sample| {
+                // Deliver in the lock, so that the samples of concurrent callbacks stay ordered
+                let mut reorderer = zlock!(reorderer);
+                for s in reorderer.push(sample) {
+                    callback.call(s);
+                }
+            })),
+            handler,
+        )
+    }
+}
@@ -0,0 +1,489 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::Encoding,
+    internal::bail,
+    key_expr::{KeyExpr, OwnedKeyExpr},
+    sample::{Sample, SampleBuilder, SampleKind, SourceInfo},
+    session::{EntityGlobalId, ZenohId},
+    time::{Timestamp, NTP64},
+    Result as ZResult,
+};
+
+const DEFAULT_MAX_SIZE: u64 = 16 * 1024 * 1024;
+const SEGMENTS: [&str; 2] = ["segment-0", "segment-1"];
+/// The number of samples waiting to be written before the publications are slowed down.
+const WRITE_QUEUE_SIZE: usize = 1024;
+
+#[derive(Debug, Clone)]
+/// Configure the persistence on disk of an [`AdvancedPublisher`](crate::AdvancedPublisher) cache,
+/// so that its history survives the restarts of the publisher.
+///
+/// The samples are stored in a ring of bounded size in the given directory, which must not be
+/// shared with other caches. The history queries are answered from the disk, while the recovery
+/// of missed samples is only served for the samples published since the publisher started.
+#[zenoh_macros::unstable]
+pub struct PersistenceConfig {
+    path: PathBuf,
+    max_size: u64,
+    retention: Option<Duration>,
+}
+
+#[zenoh_macros::unstable]
+impl PersistenceConfig {
+    /// Persist the cache in the directory at `path`.
+    #[zenoh_macros::unstable]
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            max_size: DEFAULT_MAX_SIZE,
+            retention: None,
+        }
+    }
+
+    /// Specify the maximum size in bytes of the samples stored on disk.
+    ///
+    /// The oldest samples are dropped once this size is reached.
+    #[zenoh_macros::unstable]
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Specify how long the samples stored on disk are kept.
+    #[zenoh_macros::unstable]
+    pub fn retention(mut self, retention: Duration) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedSample {
+    // The position of the sample in the ring, increasing across restarts
+    seq: u64,
+    // The time of the sample, see [`RingClock`]
+    time: u64,
+    key_expr: String,
+    delete: bool,
+    payload: Vec<u8>,
+    encoding: String,
+    timestamp: Option<String>,
+    source_id: Option<(String, u32)>,
+    source_sn: Option<u32>,
+    attachment: Option<Vec<u8>>,
+}
+
+impl PersistedSample {
+    fn new(sample: &Sample, time: NTP64) -> Self {
+        let source_info = sample.source_info();
+        PersistedSample {
+            // Assigned by the writer
+            seq: 0,
+            time: time.as_u64(),
+            key_expr: sample.key_expr().to_string(),
+            delete: sample.kind() == SampleKind::Delete,
+            payload: sample.payload().to_bytes().into_owned(),
+            encoding: sample.encoding().to_string(),
+            timestamp: sample.timestamp().map(|ts| ts.to_string()),
+            source_id: source_info
+                .source_id()
+                .map(|id| (id.zid().to_string(), id.eid())),
+            source_sn: source_info.source_sn(),
+            attachment: sample.attachment().map(|a| a.to_bytes().into_owned()),
+        }
+    }
+
+    fn into_sample(self) -> ZResult<Sample> {
+        let key_expr: KeyExpr<'static> = OwnedKeyExpr::try_from(self.key_expr)?.into();
+        let sample: Sample = if self.delete {
+            SampleBuilder::delete(key_expr).into()
+        } else {
+            SampleBuilder::put(key_expr, self.payload)
+                .encoding(Encoding::from(self.encoding))
+                .into()
+        };
+        let timestamp = match self.timestamp {
+            Some(ts) => match ts.parse::<Timestamp>() {
+                Ok(ts) => Some(ts),
+                Err(e) => bail!("Invalid timestamp {}: {:?}", ts, e),
+            },
+            None => None,
+        };
+        let source_id = match self.source_id {
+            Some((zid, eid)) => Some(EntityGlobalId::new(zid.parse::<ZenohId>()?, eid)),
+            None => None,
+        };
+        Ok(SampleBuilder::from(sample)
+            .timestamp(timestamp)
+            .source_info(SourceInfo::new(source_id, self.source_sn))
+            .attachment(self.attachment)
+            .into())
+    }
+}
+
+/// The clock dating the samples without timestamp and expiring the samples. Like an HLC, it
+/// never goes backward: it follows the timestamps of the samples and of the persisted records,
+/// so that a system clock set back across restarts does not revive expired samples.
+#[derive(Default)]
+struct RingClock {
+    last: AtomicU64,
+}
+
+impl RingClock {
+    fn now(&self) -> NTP64 {
+        let physical = NTP64::from(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+        )
+        .as_u64();
+        let previous = self
+            .last
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| {
+                Some(physical.max(last + 1))
+            })
+            .unwrap_or_else(|last| last);
+        NTP64(physical.max(previous + 1))
+    }
+
+    fn observe(&self, time: NTP64) {
+        self.last.fetch_max(time.as_u64(), Ordering::AcqRel);
+    }
+}
+
+/// The location of a record in its segment.
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    seq: u64,
+    time: u64,
+    offset: u64,
+    len: u64,
+}
+
+/// The index of the records of a segment, in the order they were written.
+#[derive(Default)]
+struct Segment {
+    entries: Vec<IndexEntry>,
+    size: u64,
+}
+
+impl Segment {
+    fn last_seq(&self) -> Option<u64> {
+        self.entries.last().map(|e| e.seq)
+    }
+}
+
+/// The index of the two segments of the ring, updated by the writer once the records are on disk,
+/// and the samples waiting to be written.
+struct RingIndex {
+    segments: [Segment; 2],
+    active: usize,
+    pending: VecDeque<(NTP64, Sample)>,
+    closed: bool,
+}
+
+/// Reads the records of the segment at `path`, returns their index, their latest time and the
+/// length of the valid part of the segment.
+fn read_segment(path: &Path) -> ZResult<(Segment, u64)> {
+    let mut buf = vec![];
+    match File::open(path) {
+        Ok(mut file) => file.read_to_end(&mut buf)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Segment::default(), 0)),
+        Err(e) => return Err(e.into()),
+    };
+    let mut segment = Segment::default();
+    let mut latest = 0;
+    let mut offset = 0;
+    // A truncated or corrupted record ends the segment, e.g. after a crash while writing
+    while let Some(len) = buf
+        .get(offset..offset + 4)
+        .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize)
+    {
+        let Some(record) = buf
+            .get(offset + 4..offset + 4 + len)
+            .and_then(|record| bincode::deserialize::<PersistedSample>(record).ok())
+        else {
+            tracing::warn!("Ignoring corrupted end of cache segment {}", path.display());
+            break;
+        };
+        segment.entries.push(IndexEntry {
+            seq: record.seq,
+            time: record.time,
+            offset: offset as u64,
+            len: 4 + len as u64,
+        });
+        latest = latest.max(record.time);
+        offset += 4 + len;
+    }
+    segment.size = offset as u64;
+    Ok((segment, latest))
+}
+
+/// The samples of a ring on disk, shared by the writer and the history queries.
+pub(crate) struct DiskSamples {
+    dir: PathBuf,
+    index: Mutex<RingIndex>,
+    // Notified when a sample is queued, written, or when the ring is closed
+    changed: Condvar,
+    clock: RingClock,
+    retention: Option<Duration>,
+}
+
+impl DiskSamples {
+    /// Returns the samples stored on disk which are not expired, from the oldest to the newest.
+    ///
+    /// Only the records selected by the index are read, the segments whose records are all
+    /// expired being skipped. The samples not yet written are returned from memory.
+    pub(crate) fn samples(&self) -> ZResult<Vec<Sample>> {
+        let oldest = self.retention.map_or(0, |r| {
+            self.clock
+                .now()
+                .as_u64()
+                .saturating_sub(NTP64::from(r).as_u64())
+        });
+        // The writer doesn't truncate a segment while it is being read
+        let index = zlock(&self.index)?;
+        let mut samples = vec![];
+        for id in [1 - index.active, index.active] {
+            let entries: Vec<&IndexEntry> = index.segments[id]
+                .entries
+                .iter()
+                .filter(|e| e.time >= oldest)
+                .collect();
+            let Some(&&first) = entries.first() else {
+                continue;
+            };
+            let mut file = File::open(self.dir.join(SEGMENTS[id]))?;
+            file.seek(SeekFrom::Start(first.offset))?;
+            let mut buf = vec![];
+            file.read_to_end(&mut buf)?;
+            for entry in entries {
+                let start = (entry.offset - first.offset) as usize + 4;
+                let end = (entry.offset - first.offset + entry.len) as usize;
+                let Some(record) = buf.get(start..end) else {
+                    bail!("Cache segment {} is shorter than its index", SEGMENTS[id]);
+                };
+                samples.push(bincode::deserialize::<PersistedSample>(record)?.into_sample()?);
+            }
+        }
+        samples.extend(
+            index
+                .pending
+                .iter()
+                .filter(|(time, _)| time.as_u64() >= oldest)
+                .map(|(_, sample)| sample.clone()),
+        );
+        Ok(samples)
+    }
+}
+
+fn zlock<T>(mutex: &Mutex<T>) -> ZResult<std::sync::MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| zenoh::internal::zerror!("Poisoned persisted cache index").into())
+}
+
+/// Appends the queued samples to the active segment, on its own thread.
+struct Writer {
+    samples: Arc<DiskSamples>,
+    file: File,
+    next_seq: u64,
+    max_segment_size: u64,
+}
+
+impl Writer {
+    fn run(mut self) {
+        loop {
+            let (time, sample) = {
+                let Ok(mut index) = zlock(&self.samples.index) else {
+                    return;
+                };
+                while index.pending.is_empty() && !index.closed {
+                    index = match self.samples.changed.wait(index) {
+                        Ok(index) => index,
+                        Err(_) => return,
+                    };
+                }
+                match index.pending.front() {
+                    Some(pending) => pending.clone(),
+                    None => return,
+                }
+            };
+            let entry = self.append(PersistedSample::new(&sample, time));
+            let Ok(mut index) = zlock(&self.samples.index) else {
+                return;
+            };
+            match entry {
+                Ok(entry) => {
+                    let active = index.active;
+                    let segment = &mut index.segments[active];
+                    segment.entries.push(entry);
+                    segment.size += entry.len;
+                }
+                Err(e) => tracing::warn!("Unable to persist AdvancedPublisher cache sample: {}", e),
+            }
+            index.pending.pop_front();
+            self.samples.changed.notify_all();
+        }
+    }
+
+    fn append(&mut self, mut record: PersistedSample) -> ZResult<IndexEntry> {
+        record.seq = self.next_seq;
+        self.next_seq += 1;
+        let bytes = bincode::serialize(&record)?;
+        let mut buf = Vec::with_capacity(4 + bytes.len());
+        buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&bytes);
+        let len = buf.len() as u64;
+        let offset = {
+            let mut index = zlock(&self.samples.index)?;
+            let size = index.segments[index.active].size;
+            if size > 0 && size + len > self.max_segment_size {
+                let next = 1 - index.active;
+                self.file = File::create(self.samples.dir.join(SEGMENTS[next]))?;
+                index.segments[next] = Segment::default();
+                index.active = next;
+                0
+            } else {
+                size
+            }
+        };
+        self.file.write_all(&buf)?;
+        Ok(IndexEntry {
+            seq: record.seq,
+            time: record.time,
+            offset,
+            len,
+        })
+    }
+}
+
+/// A ring of samples on disk, made of two segments: the samples are appended to the active
+/// segment, and the other segment is dropped when the active one is full.
+///
+/// The samples are written by a background thread, which is joined when the ring is dropped so
+/// that a ring opened afterwards on the same directory finds all of them.
+pub(crate) struct DiskRing {
+    samples: Arc<DiskSamples>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl DiskRing {
+    pub(crate) fn open(conf: &PersistenceConfig) -> ZResult<Self> {
+        std::fs::create_dir_all(&conf.path)?;
+        let (segment0, latest0) = read_segment(&conf.path.join(SEGMENTS[0]))?;
+        let (segment1, latest1) = read_segment(&conf.path.join(SEGMENTS[1]))?;
+        // The active segment holds the latest record
+        let active = if segment1.last_seq() > segment0.last_seq() {
+            1
+        } else {
+            0
+        };
+        let next_seq = segment0
+            .last_seq()
+            .max(segment1.last_seq())
+            .map_or(0, |seq| seq + 1);
+        let index = RingIndex {
+            segments: [segment0, segment1],
+            active,
+            pending: VecDeque::new(),
+            closed: false,
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(conf.path.join(SEGMENTS[active]))?;
+        // Drop the corrupted end of the segment, if any
+        file.set_len(index.segments[active].size)?;
+        let clock = RingClock::default();
+        clock.observe(NTP64(latest0.max(latest1)));
+        let samples = Arc::new(DiskSamples {
+            dir: conf.path.clone(),
+            index: Mutex::new(index),
+            changed: Condvar::new(),
+            clock,
+            retention: conf.retention,
+        });
+        let writer = Writer {
+            samples: samples.clone(),
+            file,
+            next_seq,
+            max_segment_size: conf.max_size / 2,
+        };
+        let writer = std::thread::Builder::new()
+            .name("zenoh-ext-cache-writer".into())
+            .spawn(move || writer.run())?;
+        Ok(DiskRing {
+            samples,
+            writer: Some(writer),
+        })
+    }
+
+    /// The samples of the ring, to answer the history queries.
+    pub(crate) fn samples(&self) -> Arc<DiskSamples> {
+        self.samples.clone()
+    }
+
+    /// Queues `sample` to be written on disk, waiting if too many samples are already queued.
+    pub(crate) fn push(&self, sample: &Sample) -> ZResult<()> {
+        let time = match sample.timestamp() {
+            Some(timestamp) => {
+                self.samples.clock.observe(*timestamp.get_time());
+                *timestamp.get_time()
+            }
+            None => self.samples.clock.now(),
+        };
+        let mut index = zlock(&self.samples.index)?;
+        while index.pending.len() >= WRITE_QUEUE_SIZE {
+            index = self
+                .samples
+                .changed
+                .wait(index)
+                .map_err(|_| zenoh::internal::zerror!("Poisoned persisted cache index"))?;
+        }
+        index.pending.push_back((time, sample.clone()));
+        self.samples.changed.notify_all();
+        Ok(())
+    }
+}
+
+impl Drop for DiskRing {
+    fn drop(&mut self) {
+        // The writer stops once the queued samples are written
+        if let Ok(mut index) = zlock(&self.samples.index) {
+            index.closed = true;
+            self.samples.changed.notify_all();
+        }
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                tracing::error!("The persisted cache writer panicked");
+            }
+        }
+    }
+}
@@ -12,14 +12,16 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 use std::{
+    cmp::Ordering,
     collections::{btree_map, BTreeMap, VecDeque},
     convert::TryInto,
-    future::{IntoFuture, Ready},
+    future::{Future, IntoFuture, Ready},
     mem::swap,
     sync::{Arc, Mutex},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use tokio::sync::watch;
 use zenoh::{
     handlers::{locked, Callback, DefaultHandler, IntoHandler},
     internal::{zerror, zlock},
@@ -83,6 +85,7 @@ pub struct QueryingSubscriberBuilder<'a, 'b, KeySpace, Handler, const BACKGROUND
     pub(crate) query_consolidation: QueryConsolidation,
     pub(crate) query_accept_replies: ReplyKeyExpr,
     pub(crate) query_timeout: Duration,
+    pub(crate) merge: Option<SampleOrder>,
     pub(crate) handler: Handler,
 }
 
@@ -145,6 +148,7 @@ impl<'a, 'b, KeySpace> QueryingSubscriberBuilder<'a, 'b, KeySpace, DefaultHandle
             query_consolidation,
             query_accept_replies,
             query_timeout,
+            merge,
             handler: _,
         } = self;
         QueryingSubscriberBuilder {
@@ -157,6 +161,7 @@ impl<'a, 'b, KeySpace> QueryingSubscriberBuilder<'a, 'b, KeySpace, DefaultHandle
             query_consolidation,
             query_accept_replies,
             query_timeout,
+            merge,
             handler,
         }
     }
@@ -182,6 +187,7 @@ impl<'a, 'b, KeySpace> QueryingSubscriberBuilder<'a, 'b, KeySpace, Callback<Samp
             query_consolidation: self.query_consolidation,
             query_accept_replies: self.query_accept_replies,
             query_timeout: self.query_timeout,
+            merge: self.merge,
             handler: self.handler,
         }
     }
@@ -264,6 +270,18 @@ impl<'a, 'b, KeySpace, Handler, const BACKGROUND: bool>
         self
     }
 
+    /// Change the order in which the fetched samples and the publications received during
+    /// the fetch are merged, see [`FetchingSubscriberBuilder::merge_by`].
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn merge_by<F>(mut self, order: F) -> Self
+    where
+        F: Fn(&Sample, &Sample) -> Ordering + Send + Sync + 'static,
+    {
+        self.merge = Some(Arc::new(order));
+        self
+    }
+
     #[zenoh_macros::unstable]
     #[allow(clippy::type_complexity)]
     #[deprecated = "Use `AdvancedPublisher` and `AdvancedSubscriber` instead."]
@@ -319,6 +337,7 @@ impl<'a, 'b, KeySpace, Handler, const BACKGROUND: bool>
                     .timeout(query_timeout)
                     .wait(),
             },
+            merge: self.merge,
             handler: self.handler,
             phantom: std::marker::PhantomData,
         })
@@ -399,29 +418,40 @@ where
     }
 }
 
+/// A user-provided order of the samples merged by a [`FetchingSubscriber`].
+pub(crate) type SampleOrder = Arc<dyn Fn(&Sample, &Sample) -> Ordering + Send + Sync>;
+
 // Collects samples in their Timestamp order, if any,
 // and ignores repeating samples with duplicate timestamps.
 // Samples without Timestamps are kept in a separate Vector,
 // and are considered as older than any sample with Timestamp.
+// With a user-provided order, the samples are collected in the `ordered` Vector
+// and sorted when drained.
 struct MergeQueue {
     untimestamped: VecDeque<Sample>,
     timstamped: BTreeMap<Timestamp, Sample>,
+    order: Option<SampleOrder>,
+    ordered: Vec<Sample>,
 }
 
 impl MergeQueue {
-    fn new() -> Self {
+    fn new(order: Option<SampleOrder>) -> Self {
         MergeQueue {
             untimestamped: VecDeque::new(),
             timstamped: BTreeMap::new(),
+            order,
+            ordered: Vec::new(),
         }
     }
 
     fn len(&self) -> usize {
-        self.untimestamped.len() + self.timstamped.len()
+        self.untimestamped.len() + self.timstamped.len() + self.ordered.len()
     }
 
     fn push(&mut self, sample: Sample) {
-        if let Some(ts) = sample.timestamp() {
+        if self.order.is_some() {
+            self.ordered.push(sample);
+        } else if let Some(ts) = sample.timestamp() {
             self.timstamped.entry(*ts).or_insert(sample);
         } else {
             self.untimestamped.push_back(sample);
@@ -433,6 +463,13 @@ impl MergeQueue {
         let mut queue = BTreeMap::new();
         swap(&mut self.untimestamped, &mut vec);
         swap(&mut self.timstamped, &mut queue);
+        if let Some(order) = &self.order {
+            // The sort is stable: the first received of equal samples is kept
+            self.ordered.sort_by(|a, b| order(a, b));
+            self.ordered
+                .dedup_by(|sample, previous| order(previous, sample) == Ordering::Equal);
+            vec.extend(self.ordered.drain(..));
+        }
         MergeQueueValues {
             untimestamped: vec,
             timstamped: queue.into_values(),
@@ -457,6 +494,7 @@ impl Iterator for MergeQueueValues {
 struct InnerState {
     pending_fetches: u64,
     merge_queue: MergeQueue,
+    synchronized: watch::Sender<bool>,
 }
 
 /// The builder of [`FetchingSubscriber`], allowing to configure it.
@@ -480,6 +518,7 @@ pub struct FetchingSubscriberBuilder<
     pub(crate) key_space: KeySpace,
     pub(crate) origin: Locality,
     pub(crate) fetch: Fetch,
+    pub(crate) merge: Option<SampleOrder>,
     pub(crate) handler: Handler,
     pub(crate) phantom: std::marker::PhantomData<TryIntoSample>,
 }
@@ -497,6 +536,23 @@ impl<
 where
     TryIntoSample: ExtractSample,
 {
+    /// Change the order in which the fetched samples and the publications received during
+    /// the fetch are merged.
+    ///
+    /// By default, the samples are delivered in the order of their timestamps, and the samples
+    /// with the same timestamp as a previous one are dropped. With `order`, the samples are
+    /// delivered sorted by `order`, and a sample equal to a previous one according to `order`
+    /// is dropped, the first received sample winning the conflict.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn merge_by<F>(mut self, order: F) -> Self
+    where
+        F: Fn(&Sample, &Sample) -> Ordering + Send + Sync + 'static,
+    {
+        self.merge = Some(Arc::new(order));
+        self
+    }
+
     #[zenoh_macros::unstable]
     fn with_static_keys(
         self,
@@ -507,6 +563,7 @@ where
             key_space: self.key_space,
             origin: self.origin,
             fetch: self.fetch,
+            merge: self.merge,
             handler: self.handler,
             phantom: std::marker::PhantomData,
         }
@@ -578,6 +635,7 @@ where
             key_space,
             origin,
             fetch,
+            merge,
             handler: _,
             phantom,
         } = self;
@@ -587,6 +645,7 @@ where
             key_space,
             origin,
             fetch,
+            merge,
             handler,
             phantom,
         }
@@ -621,6 +680,7 @@ where
             key_space: self.key_space,
             origin: self.origin,
             fetch: self.fetch,
+            merge: self.merge,
             handler: self.handler,
             phantom: self.phantom,
         }
@@ -846,7 +906,8 @@ impl<Handler> FetchingSubscriber<Handler> {
 
         let state = Arc::new(Mutex::new(InnerState {
             pending_fetches: 0,
-            merge_queue: MergeQueue::new(),
+            merge_queue: MergeQueue::new(conf.merge),
+            synchronized: watch::Sender::new(false),
         }));
         let (callback, receiver) = conf.handler.into_handler();
 
@@ -932,6 +993,20 @@ impl<Handler> FetchingSubscriber<Handler> {
         self.subscriber.key_expr()
     }
 
+    /// Wait until the fetches in progress, if any, are done and their samples merged with the
+    /// received publications.
+    ///
+    /// The returned future can be used to wait for the end of the initial synchronization of
+    /// the subscriber.
+    #[zenoh_macros::unstable]
+    pub fn synchronized(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut synchronized = zlock!(self.state).synchronized.subscribe();
+        async move {
+            // The sender lives as long as the subscriber
+            let _ = synchronized.wait_for(|s| *s).await;
+        }
+    }
+
     /// Perform an additional `fetch`.
     ///
     /// The provided `fetch` function should fetch some samples and return them through the callback function
@@ -1011,6 +1086,7 @@ impl Drop for RepliesHandler {
             for s in state.merge_queue.drain() {
                 self.callback.call(s);
             }
+            state.synchronized.send_replace(true);
         }
     }
 }
@@ -1104,7 +1180,11 @@ where
 }
 
 fn register_handler(state: Arc<Mutex<InnerState>>, callback: Callback<Sample>) -> RepliesHandler {
-    zlock!(state).pending_fetches += 1;
+    {
+        let mut state = zlock!(state);
+        state.pending_fetches += 1;
+        state.synchronized.send_replace(false);
+    }
     // pending fetches will be decremented in RepliesHandler drop()
     RepliesHandler { state, callback }
 }
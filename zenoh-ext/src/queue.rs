@@ -0,0 +1,402 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! To dispatch tasks to competing consumers with at-least-once semantics
+//!
+//! A [`Producer`] keeps the tasks pushed to a queue until they are acknowledged. A [`Consumer`]
+//! claims tasks by querying the producers of the queue: every claimed task is leased to the
+//! consumer, and is delivered again to a consumer if it is not acknowledged before its lease
+//! expires. A task may thus be delivered several times, e.g. when a consumer fails while
+//! processing it, and consumers should process tasks idempotently.
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryInto,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::ZBytes,
+    handlers::FifoChannelHandler,
+    internal::{bail, TaskController},
+    key_expr::OwnedKeyExpr,
+    pubsub::{Publisher, Subscriber},
+    query::{ConsolidationMode, Query, QueryTarget, Queryable},
+    sample::Sample,
+    Error as ZError, Result as ZResult, Session,
+};
+
+const QUEUE_PREFIX: &str = "zenoh/ext/net/queue";
+const CLAIM_POSTFIX: &str = "claim";
+const ACK_POSTFIX: &str = "ack";
+const AVAILABLE_POSTFIX: &str = "available";
+// How often an idle consumer claims tasks, in case it missed a notification of new tasks
+const POLL_PERIOD: Duration = Duration::from_secs(1);
+
+static PRODUCER_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize)]
+struct Claim {
+    lease: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Delivery {
+    producer: String,
+    seq: u64,
+    deliveries: u32,
+    payload: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Ack {
+    producer: String,
+    seq: u64,
+}
+
+fn queue_ke<T>(queue: T) -> ZResult<OwnedKeyExpr>
+where
+    T: TryInto<OwnedKeyExpr>,
+    <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+{
+    let queue: OwnedKeyExpr = queue.try_into().map_err(|e| e.into())?;
+    if queue.is_wild() {
+        bail!("Queue ID is not allowed to contain wildcards: {}", queue);
+    }
+    Ok(queue)
+}
+
+struct PendingTask {
+    payload: Vec<u8>,
+    leased_until: Option<Instant>,
+    deliveries: u32,
+}
+
+struct ProducerInner {
+    tasks: BTreeMap<u64, PendingTask>,
+    next_seq: u64,
+}
+
+struct ProducerState {
+    id: String,
+    inner: Mutex<ProducerInner>,
+}
+
+impl ProducerState {
+    fn lock(&self) -> MutexGuard<'_, ProducerInner> {
+        self.inner
+            .lock()
+            .expect("acquiring the producer state Mutex should not fail")
+    }
+
+    /// Leases the oldest available task for `lease`, if any.
+    fn lease(&self, lease: Duration) -> Option<Delivery> {
+        let now = Instant::now();
+        let mut inner = self.lock();
+        let (seq, task) = inner
+            .tasks
+            .iter_mut()
+            .find(|(_, t)| t.leased_until.map_or(true, |until| until <= now))?;
+        task.leased_until = Some(now + lease);
+        task.deliveries += 1;
+        Some(Delivery {
+            producer: self.id.clone(),
+            seq: *seq,
+            deliveries: task.deliveries,
+            payload: task.payload.clone(),
+        })
+    }
+}
+
+async fn claim_handler(queryable: Queryable<FifoChannelHandler<Query>>, state: Arc<ProducerState>) {
+    while let Ok(query) = queryable.recv_async().await {
+        let claim = match query
+            .payload()
+            .map(|p| bincode::deserialize::<Claim>(&p.to_bytes()))
+        {
+            Some(Ok(claim)) => claim,
+            _ => {
+                tracing::warn!("Received invalid task claim for {}", &state.id);
+                continue;
+            }
+        };
+        let Some(delivery) = state.lease(claim.lease) else {
+            continue;
+        };
+        tracing::trace!(
+            "Delivering task {} of {} (delivery {})",
+            delivery.seq,
+            &state.id,
+            delivery.deliveries
+        );
+        let buf = bincode::serialize(&delivery).unwrap();
+        if let Err(e) = query.reply(query.key_expr().clone(), buf).await {
+            tracing::warn!(
+                "Failed to deliver task {} of {}: {}",
+                delivery.seq,
+                &state.id,
+                e
+            );
+        }
+    }
+}
+
+async fn ack_handler(sub: Subscriber<FifoChannelHandler<Sample>>, state: Arc<ProducerState>) {
+    while let Ok(s) = sub.recv_async().await {
+        match bincode::deserialize::<Ack>(&s.payload().to_bytes()) {
+            Ok(ack) if ack.producer == state.id => {
+                tracing::trace!("Task {} of {} acknowledged", ack.seq, &state.id);
+                state.lock().tasks.remove(&ack.seq);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed decoding task ack due to: {:?}", e),
+        }
+    }
+}
+
+/// A producer pushing tasks to a queue.
+///
+/// The tasks which are not acknowledged yet are lost when this is dropped.
+#[zenoh_macros::unstable]
+pub struct Producer {
+    state: Arc<ProducerState>,
+    notifier: Publisher<'static>,
+    task_controller: TaskController,
+}
+
+impl Drop for Producer {
+    fn drop(&mut self) {
+        // cancel background tasks
+        self.task_controller.terminate_all(Duration::from_secs(10));
+    }
+}
+
+impl Producer {
+    /// Declares a producer of the queue `queue`.
+    pub async fn declare<T>(z: Arc<Session>, queue: T) -> ZResult<Producer>
+    where
+        T: TryInto<OwnedKeyExpr>,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        let queue = queue_ke(queue)?;
+        let notifier = z
+            .declare_publisher(format!("{QUEUE_PREFIX}/{queue}/{AVAILABLE_POSTFIX}"))
+            .await?;
+        let queryable = z
+            .declare_queryable(format!("{QUEUE_PREFIX}/{queue}/{CLAIM_POSTFIX}"))
+            .await?;
+        let acks = z
+            .declare_subscriber(format!("{QUEUE_PREFIX}/{queue}/{ACK_POSTFIX}"))
+            .await?;
+        let state = Arc::new(ProducerState {
+            id: format!(
+                "{}/{}",
+                z.zid(),
+                PRODUCER_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+            inner: Mutex::new(ProducerInner {
+                tasks: BTreeMap::new(),
+                next_seq: 0,
+            }),
+        });
+
+        let task_controller = TaskController::default();
+        task_controller.spawn_abortable(claim_handler(queryable, state.clone()));
+        task_controller.spawn_abortable(ack_handler(acks, state.clone()));
+        Ok(Producer {
+            state,
+            notifier,
+            task_controller,
+        })
+    }
+
+    /// Pushes a task to the queue, returns its sequence number.
+    pub async fn push<P: Into<ZBytes>>(&self, payload: P) -> ZResult<u64> {
+        let payload = payload.into().to_bytes().into_owned();
+        let seq = {
+            let mut inner = self.state.lock();
+            let seq = inner.next_seq;
+            inner.next_seq += 1;
+            inner.tasks.insert(
+                seq,
+                PendingTask {
+                    payload,
+                    leased_until: None,
+                    deliveries: 0,
+                },
+            );
+            seq
+        };
+        // Wake up the idle consumers
+        self.notifier.put(ZBytes::default()).await?;
+        Ok(seq)
+    }
+
+    /// Returns the number of tasks which are not acknowledged yet.
+    pub fn pending(&self) -> usize {
+        self.state.lock().tasks.len()
+    }
+}
+
+/// A task claimed by a [`Consumer`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone)]
+pub struct Task {
+    producer: String,
+    seq: u64,
+    deliveries: u32,
+    payload: ZBytes,
+    leased_until: Instant,
+}
+
+impl Task {
+    /// Returns the sequence number of the task, unique among the tasks of its producer.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns the payload of the task.
+    pub fn payload(&self) -> &ZBytes {
+        &self.payload
+    }
+
+    /// Returns how many times the task was delivered, including this delivery.
+    pub fn deliveries(&self) -> u32 {
+        self.deliveries
+    }
+
+    /// Returns the instant the lease of the task expires, after which it may be delivered
+    /// to another consumer.
+    pub fn leased_until(&self) -> Instant {
+        self.leased_until
+    }
+}
+
+/// A consumer claiming tasks from a queue, competing with the other consumers of the queue.
+#[zenoh_macros::unstable]
+pub struct Consumer {
+    session: Arc<Session>,
+    claim_ke: String,
+    lease: Duration,
+    acks: Publisher<'static>,
+    available: Subscriber<FifoChannelHandler<Sample>>,
+    // The tasks claimed from several producers at once, to deliver before claiming again
+    backlog: Mutex<VecDeque<Task>>,
+}
+
+impl Consumer {
+    /// Declares a consumer of the queue `queue`, leasing the claimed tasks for `lease`.
+    pub async fn declare<T>(z: Arc<Session>, queue: T, lease: Duration) -> ZResult<Consumer>
+    where
+        T: TryInto<OwnedKeyExpr>,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        let queue = queue_ke(queue)?;
+        let acks = z
+            .declare_publisher(format!("{QUEUE_PREFIX}/{queue}/{ACK_POSTFIX}"))
+            .await?;
+        let available = z
+            .declare_subscriber(format!("{QUEUE_PREFIX}/{queue}/{AVAILABLE_POSTFIX}"))
+            .await?;
+        Ok(Consumer {
+            session: z,
+            claim_ke: format!("{QUEUE_PREFIX}/{queue}/{CLAIM_POSTFIX}"),
+            lease,
+            acks,
+            available,
+            backlog: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    fn lock_backlog(&self) -> MutexGuard<'_, VecDeque<Task>> {
+        self.backlog
+            .lock()
+            .expect("acquiring the consumer backlog Mutex should not fail")
+    }
+
+    /// Pops the first task of the backlog whose lease did not expire yet.
+    fn pop_backlog(&self) -> Option<Task> {
+        let now = Instant::now();
+        let mut backlog = self.lock_backlog();
+        while let Some(task) = backlog.pop_front() {
+            if task.leased_until > now {
+                return Some(task);
+            }
+            // Its producer will deliver it again
+            tracing::debug!("Dropping expired task {} of {}", task.seq, task.producer);
+        }
+        None
+    }
+
+    /// Claims a task from every producer having an available task.
+    async fn claim(&self) -> ZResult<()> {
+        let claim = bincode::serialize(&Claim { lease: self.lease })?;
+        let leased_until = Instant::now() + self.lease;
+        let replies = self
+            .session
+            .get(&self.claim_ke)
+            .payload(claim)
+            .target(QueryTarget::All)
+            .consolidation(ConsolidationMode::None)
+            .await?;
+        while let Ok(reply) = replies.recv_async().await {
+            let Ok(sample) = reply.result() else {
+                continue;
+            };
+            match bincode::deserialize::<Delivery>(&sample.payload().to_bytes()) {
+                Ok(d) => self.lock_backlog().push_back(Task {
+                    producer: d.producer,
+                    seq: d.seq,
+                    deliveries: d.deliveries,
+                    payload: d.payload.into(),
+                    leased_until,
+                }),
+                Err(e) => tracing::warn!("Failed decoding task due to: {:?}", e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for a task to be available and claims it.
+    pub async fn recv(&self) -> ZResult<Task> {
+        loop {
+            if let Some(task) = self.pop_backlog() {
+                return Ok(task);
+            }
+            // Drain the notifications received so far, they are answered by this claim
+            while self.available.try_recv()?.is_some() {}
+            self.claim().await?;
+            if let Some(task) = self.pop_backlog() {
+                return Ok(task);
+            }
+            tokio::select! {
+                _ = self.available.recv_async() => {}
+                _ = tokio::time::sleep(POLL_PERIOD) => {}
+            }
+        }
+    }
+
+    /// Acknowledges the processing of `task`, so that it is not delivered again.
+    pub async fn ack(&self, task: &Task) -> ZResult<()> {
+        let ack = Ack {
+            producer: task.producer.clone(),
+            seq: task.seq,
+        };
+        self.acks.put(bincode::serialize(&ack)?).await
+    }
+}
@@ -0,0 +1,331 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! To call remote procedures with typed requests and responses
+//!
+//! A [`Service`] declares a queryable on its key expression and answers every query by calling
+//! its handler on the decoded request. A [`Client`] sends its requests as the payload of a query
+//! and decodes the first reply it receives, either as the response or as an [`RpcError`].
+use std::{fmt, future::Future, marker::PhantomData, sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use zenoh::{
+    bytes::ZBytes,
+    internal::{bail, TaskController},
+    key_expr::OwnedKeyExpr,
+    query::{ConsolidationMode, Query, QueryTarget, ReplyError},
+    Error as ZError, Result as ZResult, Session,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// The error codes of an [`RpcError`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// The service could not decode the request.
+    InvalidRequest,
+    /// The client could not decode the response.
+    InvalidResponse,
+    /// No service answered the request.
+    Unavailable,
+    /// The service did not answer before the timeout.
+    Timeout,
+    /// The call was cancelled by the client.
+    Cancelled,
+    /// The service failed to handle the request.
+    Internal,
+    /// An error specific to the application.
+    Application(u32),
+}
+
+impl ErrorCode {
+    /// Returns `true` if a call failing with this code may succeed when retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ErrorCode::Unavailable | ErrorCode::Timeout)
+    }
+}
+
+/// The error of a remote procedure call, either returned by the handler of the [`Service`] or
+/// raised by the [`Client`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcError {
+    code: ErrorCode,
+    message: String,
+}
+
+impl RpcError {
+    pub fn new<T: Into<String>>(code: ErrorCode, message: T) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn from_reply_error(err: &ReplyError) -> Self {
+        let payload = err.payload().to_bytes();
+        if let Ok(err) = bincode::deserialize::<RpcError>(&payload) {
+            return err;
+        }
+        // The errors raised by zenoh itself, e.g. on query timeout, are plain strings
+        let message = String::from_utf8_lossy(&payload);
+        if message == "Timeout" {
+            RpcError::new(ErrorCode::Timeout, "the service did not answer in time")
+        } else {
+            RpcError::new(ErrorCode::Internal, message)
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+async fn handle_query<Req, Resp, F, Fut>(query: Query, handler: Arc<F>)
+where
+    Req: DeserializeOwned,
+    Resp: Serialize,
+    F: Fn(Req) -> Fut,
+    Fut: Future<Output = Result<Resp, RpcError>>,
+{
+    let payload = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+    let result = match bincode::deserialize::<Req>(&payload) {
+        Ok(req) => handler(req).await,
+        Err(e) => Err(RpcError::new(
+            ErrorCode::InvalidRequest,
+            format!("failed to decode request: {e}"),
+        )),
+    };
+    let sent = match result.map(|resp| bincode::serialize(&resp)) {
+        Ok(Ok(resp)) => query.reply(query.key_expr().clone(), resp).await,
+        Ok(Err(e)) => {
+            let err = RpcError::new(
+                ErrorCode::Internal,
+                format!("failed to encode response: {e}"),
+            );
+            query.reply_err(bincode::serialize(&err).unwrap()).await
+        }
+        Err(err) => query.reply_err(bincode::serialize(&err).unwrap()).await,
+    };
+    if let Err(e) = sent {
+        tracing::warn!("Failed to reply to RPC on {}: {}", query.key_expr(), e);
+    }
+}
+
+/// A service answering the remote procedure calls made on its key expression.
+///
+/// The service is undeclared when this is dropped.
+#[zenoh_macros::unstable]
+pub struct Service {
+    key_expr: OwnedKeyExpr,
+    task_controller: TaskController,
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        // cancel background tasks
+        self.task_controller.terminate_all(Duration::from_secs(10));
+    }
+}
+
+impl Service {
+    /// Declares a service on `key_expr`, calling `handler` on every request.
+    ///
+    /// The requests are handled concurrently.
+    pub async fn declare<T, Req, Resp, F, Fut>(
+        z: &Session,
+        key_expr: T,
+        handler: F,
+    ) -> ZResult<Service>
+    where
+        T: TryInto<OwnedKeyExpr>,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+        Req: DeserializeOwned + Send + 'static,
+        Resp: Serialize + Send + 'static,
+        F: Fn(Req) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Resp, RpcError>> + Send + 'static,
+    {
+        let key_expr: OwnedKeyExpr = key_expr.try_into().map_err(|e| e.into())?;
+        if key_expr.is_wild() {
+            bail!(
+                "Service key expression is not allowed to contain wildcards: {}",
+                key_expr
+            );
+        }
+        let queryable = z.declare_queryable(key_expr.clone()).await?;
+        let handler = Arc::new(handler);
+        let task_controller = TaskController::default();
+        task_controller.spawn_abortable(
+            futures::stream::unfold(queryable, |queryable| async move {
+                let query = queryable.recv_async().await.ok()?;
+                Some((query, queryable))
+            })
+            .for_each_concurrent(None, move |query| handle_query(query, handler.clone())),
+        );
+        Ok(Service {
+            key_expr,
+            task_controller,
+        })
+    }
+
+    /// Returns the key expression of this service.
+    pub fn key_expr(&self) -> &OwnedKeyExpr {
+        &self.key_expr
+    }
+}
+
+/// A client making typed remote procedure calls to a [`Service`].
+#[zenoh_macros::unstable]
+pub struct Client<Req, Resp> {
+    session: Session,
+    key_expr: OwnedKeyExpr,
+    timeout: Duration,
+    retries: u32,
+    retry_delay: Duration,
+    _types: PhantomData<fn(Req) -> Resp>,
+}
+
+impl<Req, Resp> Client<Req, Resp>
+where
+    Req: Serialize,
+    Resp: DeserializeOwned,
+{
+    /// Creates a client of the service declared on `key_expr`.
+    pub fn new<T>(z: &Session, key_expr: T) -> ZResult<Self>
+    where
+        T: TryInto<OwnedKeyExpr>,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        let key_expr: OwnedKeyExpr = key_expr.try_into().map_err(|e| e.into())?;
+        Ok(Client {
+            session: z.clone(),
+            key_expr,
+            timeout: DEFAULT_TIMEOUT,
+            retries: 0,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            _types: PhantomData,
+        })
+    }
+
+    /// Sets the time a single attempt of a call waits for the response.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a call is retried when no service answered it, or not in time.
+    ///
+    /// The errors returned by the service are never retried.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay between two attempts of a call.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Returns the key expression of the called service.
+    pub fn key_expr(&self) -> &OwnedKeyExpr {
+        &self.key_expr
+    }
+
+    /// Calls the service with `request`.
+    ///
+    /// The call is cancelled when the returned future is dropped.
+    pub async fn call(&self, request: &Req) -> Result<Resp, RpcError> {
+        let payload = bincode::serialize(request).map_err(|e| {
+            RpcError::new(
+                ErrorCode::InvalidRequest,
+                format!("failed to encode request: {e}"),
+            )
+        })?;
+        let payload = ZBytes::from(payload);
+        let mut attempt = 0;
+        loop {
+            match self.attempt(payload.clone()).await {
+                Err(err) if err.code.is_retryable() && attempt < self.retries => {
+                    attempt += 1;
+                    tracing::debug!(
+                        "Retrying RPC on {} ({}/{}) after: {}",
+                        self.key_expr,
+                        attempt,
+                        self.retries,
+                        err
+                    );
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Calls the service with `request`, failing with [`ErrorCode::Cancelled`] as soon as
+    /// `token` is cancelled.
+    pub async fn call_with_cancellation(
+        &self,
+        request: &Req,
+        token: &CancellationToken,
+    ) -> Result<Resp, RpcError> {
+        tokio::select! {
+            _ = token.cancelled() => Err(RpcError::new(ErrorCode::Cancelled, "the call was cancelled")),
+            result = self.call(request) => result,
+        }
+    }
+
+    async fn attempt(&self, payload: ZBytes) -> Result<Resp, RpcError> {
+        let replies = self
+            .session
+            .get(&self.key_expr)
+            .payload(payload)
+            .target(QueryTarget::BestMatching)
+            .consolidation(ConsolidationMode::None)
+            .timeout(self.timeout)
+            .await
+            .map_err(|e| RpcError::new(ErrorCode::Internal, e.to_string()))?;
+        let Ok(reply) = replies.recv_async().await else {
+            return Err(RpcError::new(
+                ErrorCode::Unavailable,
+                format!("no service answered on {}", self.key_expr),
+            ));
+        };
+        match reply.result() {
+            Ok(sample) => bincode::deserialize(&sample.payload().to_bytes()).map_err(|e| {
+                RpcError::new(
+                    ErrorCode::InvalidResponse,
+                    format!("failed to decode response: {e}"),
+                )
+            }),
+            Err(err) => Err(RpcError::from_reply_error(err)),
+        }
+    }
+}
@@ -0,0 +1,407 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! To share a state among several sessions without a storage
+//!
+//! Every replica of a [`SharedMap`] publishes its updates timestamped with the HLC of its session,
+//! and applies the updates of the other replicas when they are newer than its own entries: the
+//! last writer wins. A joining replica queries the state of the other replicas, so that all the
+//! replicas eventually converge to the same state. The removed entries are kept as tombstones so
+//! that a late update does not resurrect them.
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    hash::Hash,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use flume::{Receiver, Sender};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zenoh::{
+    handlers::FifoChannelHandler,
+    internal::{bail, TaskController},
+    key_expr::OwnedKeyExpr,
+    pubsub::{Publisher, Subscriber},
+    query::{Query, Queryable},
+    sample::{Locality, Sample},
+    time::{Timestamp, NTP64},
+    Error as ZError, Result as ZResult, Session,
+};
+
+const SHARED_PREFIX: &str = "zenoh/ext/net/shared";
+const UPDATE_POSTFIX: &str = "update";
+const STATE_POSTFIX: &str = "state";
+
+#[derive(Serialize, Deserialize)]
+struct Update<K, V> {
+    key: K,
+    value: Option<V>,
+    timestamp: String,
+}
+
+/// A change of an entry of a [`SharedMap`], either made locally or by a remote replica.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<K, V> {
+    key: K,
+    value: Option<V>,
+    timestamp: Timestamp,
+}
+
+impl<K, V> Change<K, V> {
+    /// Returns the key of the changed entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the new value of the entry, or `None` if it was removed.
+    pub fn value(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    /// Returns the timestamp of the change.
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+}
+
+struct Entry<V> {
+    value: Option<V>,
+    timestamp: Timestamp,
+}
+
+struct SharedInner<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    events_tx: Vec<Sender<Change<K, V>>>,
+}
+
+struct SharedState<K, V> {
+    session: Arc<Session>,
+    name: String,
+    publisher: Publisher<'static>,
+    inner: Mutex<SharedInner<K, V>>,
+}
+
+impl<K, V> SharedState<K, V>
+where
+    K: Serialize + DeserializeOwned + Eq + Hash + Clone + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    fn lock(&self) -> MutexGuard<'_, SharedInner<K, V>> {
+        self.inner
+            .lock()
+            .expect("acquiring the shared state Mutex should not fail")
+    }
+
+    /// Applies `update` if it is newer than the current entry, returns `true` if it was applied.
+    fn merge(&self, update: Update<K, V>) -> bool {
+        let timestamp = match update.timestamp.parse::<Timestamp>() {
+            Ok(ts) => ts,
+            Err(e) => {
+                tracing::warn!("Invalid timestamp {}: {:?}", update.timestamp, e);
+                return false;
+            }
+        };
+        let mut inner = self.lock();
+        if inner
+            .entries
+            .get(&update.key)
+            .is_some_and(|e| e.timestamp >= timestamp)
+        {
+            return false;
+        }
+        set_entry(&mut inner, update.key, update.value, timestamp);
+        true
+    }
+
+    /// Sets the entry `key` locally and publishes the change to the other replicas.
+    async fn write(&self, key: K, value: Option<V>) -> ZResult<()> {
+        let update = {
+            let mut inner = self.lock();
+            let mut timestamp = self.session.new_timestamp();
+            // The new value must win over the current one, even if the clock of its writer
+            // is ahead of the local clock
+            if let Some(entry) = inner.entries.get(&key) {
+                if entry.timestamp >= timestamp {
+                    let time = NTP64(entry.timestamp.get_time().as_u64() + 1);
+                    timestamp = Timestamp::new(time, *timestamp.get_id());
+                }
+            }
+            set_entry(&mut inner, key.clone(), value.clone(), timestamp);
+            Update {
+                key,
+                value,
+                timestamp: timestamp.to_string(),
+            }
+        };
+        self.publisher.put(bincode::serialize(&update)?).await
+    }
+
+    fn updates(&self) -> Vec<Update<K, V>> {
+        self.lock()
+            .entries
+            .iter()
+            .map(|(key, entry)| Update {
+                key: key.clone(),
+                value: entry.value.clone(),
+                timestamp: entry.timestamp.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Sets the entry `key`, notifying the user of the change.
+fn set_entry<K: Eq + Hash + Clone, V: Clone>(
+    inner: &mut SharedInner<K, V>,
+    key: K,
+    value: Option<V>,
+    timestamp: Timestamp,
+) {
+    let change = Change {
+        key: key.clone(),
+        value: value.clone(),
+        timestamp,
+    };
+    inner.entries.insert(key, Entry { value, timestamp });
+    inner.events_tx.retain(|tx| tx.send(change.clone()).is_ok());
+}
+
+async fn update_handler<K, V>(
+    sub: Subscriber<FifoChannelHandler<Sample>>,
+    state: Arc<SharedState<K, V>>,
+) where
+    K: Serialize + DeserializeOwned + Eq + Hash + Clone + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    while let Ok(s) = sub.recv_async().await {
+        match bincode::deserialize::<Update<K, V>>(&s.payload().to_bytes()) {
+            Ok(update) => {
+                state.merge(update);
+            }
+            Err(e) => tracing::warn!("Failed decoding update of {} due to: {:?}", &state.name, e),
+        }
+    }
+}
+
+async fn state_handler<K, V>(
+    queryable: Queryable<FifoChannelHandler<Query>>,
+    state: Arc<SharedState<K, V>>,
+) where
+    K: Serialize + DeserializeOwned + Eq + Hash + Clone + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    while let Ok(query) = queryable.recv_async().await {
+        tracing::trace!("Sending state of {}", &state.name);
+        let buf = bincode::serialize(&state.updates()).unwrap();
+        if let Err(e) = query.reply(query.key_expr().clone(), buf).await {
+            tracing::warn!("Failed to send state of {}: {}", &state.name, e);
+        }
+    }
+}
+
+/// A map replicated among all the sessions which joined it under the same name.
+///
+/// The replica leaves the map when this is dropped.
+#[zenoh_macros::unstable]
+pub struct SharedMap<K, V> {
+    state: Arc<SharedState<K, V>>,
+    task_controller: TaskController,
+}
+
+impl<K, V> Drop for SharedMap<K, V> {
+    fn drop(&mut self) {
+        // cancel background tasks
+        self.task_controller.terminate_all(Duration::from_secs(10));
+    }
+}
+
+impl<K, V> SharedMap<K, V>
+where
+    K: Serialize + DeserializeOwned + Eq + Hash + Clone + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Joins the shared map `name`, retrieving its current state from the other replicas.
+    pub async fn join<T>(z: Arc<Session>, name: T) -> ZResult<SharedMap<K, V>>
+    where
+        T: TryInto<OwnedKeyExpr>,
+        <T as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        let name: OwnedKeyExpr = name.try_into().map_err(|e| e.into())?;
+        if name.is_wild() {
+            bail!(
+                "Shared map name is not allowed to contain wildcards: {}",
+                name
+            );
+        }
+
+        let publisher = z
+            .declare_publisher(format!("{SHARED_PREFIX}/{name}/{UPDATE_POSTFIX}"))
+            .allowed_destination(Locality::Remote)
+            .await?;
+        let sub = z
+            .declare_subscriber(publisher.key_expr())
+            .allowed_origin(Locality::Remote)
+            .await?;
+        let state_ke = format!("{SHARED_PREFIX}/{name}/{STATE_POSTFIX}");
+        let queryable = z
+            .declare_queryable(&state_ke)
+            .allowed_origin(Locality::Remote)
+            .await?;
+        let state = Arc::new(SharedState {
+            session: z.clone(),
+            name: String::from(name),
+            publisher,
+            inner: Mutex::new(SharedInner {
+                entries: HashMap::new(),
+                events_tx: vec![],
+            }),
+        });
+
+        let task_controller = TaskController::default();
+        task_controller.spawn_abortable(update_handler(sub, state.clone()));
+        task_controller.spawn_abortable(state_handler(queryable, state.clone()));
+
+        // The updates received in the meantime are merged with the retrieved state
+        let replies = z
+            .get(state_ke)
+            .allowed_destination(Locality::Remote)
+            .await?;
+        while let Ok(reply) = replies.recv_async().await {
+            let Ok(sample) = reply.result() else {
+                continue;
+            };
+            match bincode::deserialize::<Vec<Update<K, V>>>(&sample.payload().to_bytes()) {
+                Ok(updates) => {
+                    for update in updates {
+                        state.merge(update);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed decoding state of {} due to: {:?}", &state.name, e)
+                }
+            }
+        }
+
+        Ok(SharedMap {
+            state,
+            task_controller,
+        })
+    }
+
+    /// Returns the name of the shared map.
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    /// Returns the value of the entry `key`, if any.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.state
+            .lock()
+            .entries
+            .get(key)
+            .and_then(|e| e.value.clone())
+    }
+
+    /// Sets the entry `key` to `value`.
+    pub async fn insert(&self, key: K, value: V) -> ZResult<()> {
+        self.state.write(key, Some(value)).await
+    }
+
+    /// Removes the entry `key`.
+    pub async fn remove(&self, key: K) -> ZResult<()> {
+        self.state.write(key, None).await
+    }
+
+    /// Returns the entries of the shared map.
+    pub fn entries(&self) -> Vec<(K, V)> {
+        self.state
+            .lock()
+            .entries
+            .iter()
+            .filter_map(|(key, e)| e.value.clone().map(|value| (key.clone(), value)))
+            .collect()
+    }
+
+    /// Returns the number of entries of the shared map.
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .entries
+            .values()
+            .filter(|e| e.value.is_some())
+            .count()
+    }
+
+    /// Returns `true` if the shared map has no entry.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a receiver of the changes of the shared map, both local and remote.
+    pub fn subscribe(&self) -> Receiver<Change<K, V>> {
+        let (tx, rx) = flume::unbounded();
+        self.state.lock().events_tx.push(tx);
+        rx
+    }
+}
+
+/// A value replicated among all the sessions which joined it under the same name.
+///
+/// The replica leaves the value when this is dropped.
+#[zenoh_macros::unstable]
+pub struct SharedValue<T> {
+    map: SharedMap<(), T>,
+}
+
+impl<T> SharedValue<T>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Joins the shared value `name`, retrieving its current value from the other replicas.
+    pub async fn join<N>(z: Arc<Session>, name: N) -> ZResult<SharedValue<T>>
+    where
+        N: TryInto<OwnedKeyExpr>,
+        <N as TryInto<OwnedKeyExpr>>::Error: Into<ZError>,
+    {
+        Ok(SharedValue {
+            map: SharedMap::join(z, name).await?,
+        })
+    }
+
+    /// Returns the name of the shared value.
+    pub fn name(&self) -> &str {
+        self.map.name()
+    }
+
+    /// Returns the current value, if any.
+    pub fn get(&self) -> Option<T> {
+        self.map.get(&())
+    }
+
+    /// Sets the value.
+    pub async fn set(&self, value: T) -> ZResult<()> {
+        self.map.insert((), value).await
+    }
+
+    /// Clears the value.
+    pub async fn clear(&self) -> ZResult<()> {
+        self.map.remove(()).await
+    }
+
+    /// Returns a receiver of the changes of the value, both local and remote.
+    pub fn subscribe(&self) -> Receiver<Change<(), T>> {
+        self.map.subscribe()
+    }
+}
@@ -213,6 +213,7 @@ impl<'a, 'b, Handler> SubscriberBuilderExt<'a, 'b, Handler> for SubscriberBuilde
             key_space: crate::UserSpace,
             origin: self.origin,
             fetch,
+            merge: None,
             handler: self.handler,
             phantom: std::marker::PhantomData,
         }
@@ -261,6 +262,7 @@ impl<'a, 'b, Handler> SubscriberBuilderExt<'a, 'b, Handler> for SubscriberBuilde
             query_consolidation: QueryConsolidation::from(zenoh::query::ConsolidationMode::None),
             query_accept_replies: ReplyKeyExpr::default(),
             query_timeout: Duration::from_secs(10),
+            merge: None,
             handler: self.handler,
         }
     }
@@ -361,6 +363,7 @@ impl<'a, 'b, Handler> SubscriberBuilderExt<'a, 'b, Handler>
             key_space: crate::LivelinessSpace,
             origin: Locality::default(),
             fetch,
+            merge: None,
             handler: self.handler,
             phantom: std::marker::PhantomData,
         }
@@ -408,6 +411,7 @@ impl<'a, 'b, Handler> SubscriberBuilderExt<'a, 'b, Handler>
             query_consolidation: QueryConsolidation::DEFAULT,
             query_accept_replies: ReplyKeyExpr::MatchingQuery,
             query_timeout: Duration::from_secs(10),
+            merge: None,
             handler: self.handler,
         }
     }
@@ -0,0 +1,79 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "rkyv"))]
+use std::time::Duration;
+
+use zenoh::{bytes::ZBytes, internal::ztimeout};
+use zenoh_ext::{
+    z_access, z_archive, z_serialize, z_unarchive, PublisherArchiveExt, SampleArchiveExt,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, PartialEq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[archive(check_bytes)]
+struct Reading {
+    sensor: String,
+    values: Vec<f32>,
+    sequence: u64,
+}
+
+fn reading() -> Reading {
+    Reading {
+        sensor: "temperature".into(),
+        values: vec![21.5, 21.7, 22.0],
+        sequence: 42,
+    }
+}
+
+#[test]
+fn test_archive_roundtrip() {
+    let payload = z_archive(&reading()).unwrap();
+
+    let archived = z_access::<Reading>(&payload).unwrap();
+    assert_eq!(archived.sensor, "temperature");
+    assert_eq!(archived.values.len(), 3);
+    assert_eq!(archived.values[1], 21.7);
+    assert_eq!(archived.sequence, 42);
+
+    assert_eq!(z_unarchive::<Reading>(&payload).unwrap(), reading());
+}
+
+#[test]
+fn test_archive_invalid() {
+    assert!(z_access::<Reading>(&ZBytes::from(vec![0xff; 3])).is_err());
+    assert!(z_unarchive::<Reading>(&z_serialize(&(1u32, "sensor"))).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_archive_pubsub() {
+    const KEYEXPR: &str = "test/archive";
+    zenoh_util::init_log_from_env_or("error");
+
+    let mut c = zenoh::Config::default();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let session = ztimeout!(zenoh::open(c)).unwrap();
+    let sub = ztimeout!(session.declare_subscriber(KEYEXPR)).unwrap();
+    let publisher = ztimeout!(session.declare_publisher(KEYEXPR)).unwrap();
+
+    ztimeout!(publisher.put_archived(&reading()).unwrap()).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    let archived = sample.access::<Reading>().unwrap();
+    assert_eq!(archived.sensor, "temperature");
+    assert_eq!(archived.sequence, 42);
+
+    ztimeout!(publisher.undeclare()).unwrap();
+    ztimeout!(sub.undeclare()).unwrap();
+    ztimeout!(session.close()).unwrap();
+}
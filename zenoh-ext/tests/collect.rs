@@ -0,0 +1,92 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::collections::HashMap;
+
+use zenoh::{key_expr::OwnedKeyExpr, query::Query, Session, Wait};
+use zenoh_ext::{z_serialize, EntryError, SessionGetBuilderExt};
+
+fn reply(query: Query) {
+    let replies = [
+        ("test/collect/a", z_serialize(&1u32)),
+        ("test/collect/b", z_serialize(&2u32)),
+        ("test/collect/c", z_serialize(&"x".to_string())),
+    ];
+    for (key_expr, payload) in replies {
+        if query.key_expr().intersects(key_expr.try_into().unwrap()) {
+            query.reply(key_expr, payload).wait().unwrap();
+        }
+    }
+    if query.key_expr().is_wild() {
+        query.reply_err("unavailable").wait().unwrap();
+    }
+}
+
+fn open() -> Session {
+    let session = zenoh::open(zenoh::Config::default()).wait().unwrap();
+    session
+        .declare_queryable("test/collect/**")
+        .callback(reply)
+        .background()
+        .wait()
+        .unwrap();
+    session
+}
+
+#[test]
+fn collect_into_map() {
+    zenoh::init_log_from_env_or("error");
+    let session = open();
+
+    let collected = session
+        .get("test/collect/**")
+        .collect_into::<HashMap<OwnedKeyExpr, u32>>()
+        .wait()
+        .unwrap();
+    assert!(!collected.is_complete());
+    let key_expr = |key| OwnedKeyExpr::try_from(format!("test/collect/{key}")).unwrap();
+    assert_eq!(collected.collection().len(), 2);
+    assert_eq!(collected.collection()[&key_expr("a")], 1);
+    assert_eq!(collected.collection()[&key_expr("b")], 2);
+    let (_, errors) = collected.into_parts();
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, EntryError::Deserialize(k, _) if *k == key_expr("c"))));
+    assert!(errors.iter().any(|e| matches!(e, EntryError::Reply(_))));
+    session.close().wait().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn collect_into_async() {
+    zenoh::init_log_from_env_or("error");
+    let session = open();
+
+    let values = session
+        .get("test/collect/a")
+        .collect_into::<HashMap<_, u32>>()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert_eq!(values.into_values().collect::<Vec<_>>(), [1]);
+
+    let entries = session
+        .get("test/collect/**")
+        .collect_into::<Vec<(_, String)>>()
+        .await
+        .unwrap();
+    assert!(entries.into_result().is_err());
+    session.close().await.unwrap();
+}
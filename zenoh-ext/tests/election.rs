@@ -0,0 +1,132 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::{sync::Arc, time::Duration};
+
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    key_expr::OwnedKeyExpr,
+    Session,
+};
+use zenoh_ext::election::{Candidate, Election, ElectionEvent};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const ELECTION: &str = "test/election";
+const LEASE: Duration = Duration::from_millis(500);
+
+async fn open_peer(listen: Option<&str>, connect: Option<&str>) -> Arc<Session> {
+    let mut c = zenoh::Config::default();
+    if let Some(endpoint) = listen {
+        c.listen
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    if let Some(endpoint) = connect {
+        c.connect
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    Arc::new(ztimeout!(zenoh::open(c)).unwrap())
+}
+
+fn cid(id: &str) -> OwnedKeyExpr {
+    OwnedKeyExpr::try_from(id).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_leader_election() {
+    const PEER1_ENDPOINT: &str = "tcp/localhost:47457";
+    zenoh_util::init_log_from_env_or("error");
+
+    // A single candidate elects itself once its lease elapsed
+    let peer1 = open_peer(Some(PEER1_ENDPOINT), None).await;
+    let election1 = ztimeout!(Election::join(
+        peer1.clone(),
+        ELECTION,
+        Candidate::new("candidate/b").unwrap().lease(LEASE)
+    ))
+    .unwrap();
+    let events1 = election1.subscribe();
+    assert_eq!(
+        ztimeout!(events1.recv_async()).unwrap(),
+        ElectionEvent::Elected(cid("candidate/b"))
+    );
+    assert!(election1.is_leader());
+
+    // A joining candidate does not take over the established leader
+    let peer2 = open_peer(None, Some(PEER1_ENDPOINT)).await;
+    let election2 = ztimeout!(Election::join(
+        peer2.clone(),
+        ELECTION,
+        Candidate::new("candidate/a").unwrap().lease(LEASE)
+    ))
+    .unwrap();
+    let events2 = election2.subscribe();
+    assert_eq!(
+        ztimeout!(events2.recv_async()).unwrap(),
+        ElectionEvent::Elected(cid("candidate/b"))
+    );
+    tokio::time::sleep(LEASE * 3).await;
+    assert!(election1.is_leader());
+    assert!(!election2.is_leader());
+    assert!(events1.is_empty());
+    assert!(events2.is_empty());
+
+    // The leadership is handed over when the leader resigns
+    ztimeout!(election1.resign()).unwrap();
+    assert_eq!(
+        ztimeout!(events1.recv_async()).unwrap(),
+        ElectionEvent::Lost(cid("candidate/b"))
+    );
+    assert_eq!(
+        ztimeout!(events2.recv_async()).unwrap(),
+        ElectionEvent::Lost(cid("candidate/b"))
+    );
+    assert_eq!(
+        ztimeout!(events2.recv_async()).unwrap(),
+        ElectionEvent::Elected(cid("candidate/a"))
+    );
+    assert_eq!(election2.leader(), Some(cid("candidate/a")));
+
+    // A failed leader loses its leadership
+    let election1 = ztimeout!(Election::join(
+        peer1.clone(),
+        ELECTION,
+        Candidate::new("candidate/b").unwrap().lease(LEASE)
+    ))
+    .unwrap();
+    let events1 = election1.subscribe();
+    assert_eq!(
+        ztimeout!(events1.recv_async()).unwrap(),
+        ElectionEvent::Elected(cid("candidate/a"))
+    );
+    ztimeout!(peer2.close()).unwrap();
+    assert_eq!(
+        ztimeout!(events1.recv_async()).unwrap(),
+        ElectionEvent::Lost(cid("candidate/a"))
+    );
+    assert_eq!(
+        ztimeout!(events1.recv_async()).unwrap(),
+        ElectionEvent::Elected(cid("candidate/b"))
+    );
+
+    drop(election2);
+    drop(election1);
+    ztimeout!(peer1.close()).unwrap();
+}
@@ -0,0 +1,60 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{internal::ztimeout, sample::Sample, Wait};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+fn value(sample: &Sample) -> u32 {
+    sample.payload().try_to_string().unwrap().parse().unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[allow(deprecated)]
+async fn test_fetching_subscriber_merge_by() {
+    use zenoh_ext::SubscriberBuilderExt;
+
+    const KEYEXPR: &str = "test/fetching/merge_by";
+    zenoh_util::init_log_from_env_or("error");
+
+    let mut c = zenoh::Config::default();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let session = ztimeout!(zenoh::open(c)).unwrap();
+
+    // A queryable replying out of order, with a duplicate
+    let _queryable = ztimeout!(session.declare_queryable(KEYEXPR).callback(|query| {
+        for v in ["3", "1", "2", "2"] {
+            query.reply(KEYEXPR, v).wait().unwrap();
+        }
+    }))
+    .unwrap();
+
+    let sub = ztimeout!(session
+        .declare_subscriber(KEYEXPR)
+        .querying()
+        .merge_by(|a, b| value(a).cmp(&value(b))))
+    .unwrap();
+    ztimeout!(sub.synchronized());
+    let values: Vec<u32> = sub.drain().map(|s| value(&s)).collect();
+    assert_eq!(values, vec![1, 2, 3]);
+
+    // Once synchronized, the publications are delivered as received
+    ztimeout!(session.put(KEYEXPR, "0")).unwrap();
+    assert_eq!(value(&ztimeout!(sub.recv_async()).unwrap()), 0);
+
+    ztimeout!(sub.undeclare()).unwrap();
+    ztimeout!(session.close()).unwrap();
+}
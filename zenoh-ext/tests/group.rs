@@ -0,0 +1,122 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::{sync::Arc, time::Duration};
+
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session,
+};
+use zenoh_ext::group::{Group, GroupEvent, Member};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const GROUP: &str = "test/group";
+
+async fn open_peer(listen: Option<&str>, connect: Option<&str>) -> Arc<Session> {
+    let mut c = zenoh::Config::default();
+    if let Some(endpoint) = listen {
+        c.listen
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    if let Some(endpoint) = connect {
+        c.connect
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    Arc::new(ztimeout!(zenoh::open(c)).unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_group_membership() {
+    const PEER1_ENDPOINT: &str = "tcp/localhost:47456";
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer1 = open_peer(Some(PEER1_ENDPOINT), None).await;
+    let group1 = ztimeout!(Group::join(
+        peer1.clone(),
+        GROUP,
+        Member::new("member/a").unwrap()
+    ))
+    .unwrap();
+    let events = group1.subscribe().await;
+
+    // A joining member is part of the view and becomes the leader
+    let peer2 = open_peer(None, Some(PEER1_ENDPOINT)).await;
+    let group2 = ztimeout!(Group::join(
+        peer2.clone(),
+        GROUP,
+        Member::new("member/b").unwrap()
+    ))
+    .unwrap();
+    assert!(group1.wait_for_view_size(2, TIMEOUT).await);
+    assert!(group2.wait_for_view_size(2, TIMEOUT).await);
+    assert_eq!(group1.leader().await.id().as_str(), "member/b");
+    let event = ztimeout!(events.recv_async()).unwrap();
+    assert!(
+        matches!(&event, GroupEvent::Join(e) if e.member.id() == "member/b"),
+        "{event:?}"
+    );
+    let event = ztimeout!(events.recv_async()).unwrap();
+    assert!(
+        matches!(&event, GroupEvent::NewLeader(e) if e.mid.as_str() == "member/b"),
+        "{event:?}"
+    );
+
+    // A leaving member is removed from the view
+    ztimeout!(group2.leave()).unwrap();
+    let event = ztimeout!(events.recv_async()).unwrap();
+    assert!(
+        matches!(&event, GroupEvent::Leave(e) if e.mid.as_str() == "member/b"),
+        "{event:?}"
+    );
+    let event = ztimeout!(events.recv_async()).unwrap();
+    assert!(
+        matches!(&event, GroupEvent::NewLeader(e) if e.mid.as_str() == "member/a"),
+        "{event:?}"
+    );
+    assert_eq!(group1.size().await, 1);
+
+    // A failed member is removed from the view before its lease expires
+    let group2 = ztimeout!(Group::join(
+        peer2.clone(),
+        GROUP,
+        Member::new("member/b").unwrap()
+    ))
+    .unwrap();
+    assert!(group1.wait_for_view_size(2, TIMEOUT).await);
+    let event = ztimeout!(events.recv_async()).unwrap();
+    assert!(matches!(&event, GroupEvent::Join(_)), "{event:?}");
+    let event = ztimeout!(events.recv_async()).unwrap();
+    assert!(matches!(&event, GroupEvent::NewLeader(_)), "{event:?}");
+    ztimeout!(peer2.close()).unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv_async())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        matches!(&event, GroupEvent::LeaseExpired(e) if e.mid.as_str() == "member/b"),
+        "{event:?}"
+    );
+    assert_eq!(group1.size().await, 1);
+
+    drop(group2);
+    drop(group1);
+    ztimeout!(peer1.close()).unwrap();
+}
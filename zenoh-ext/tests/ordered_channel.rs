@@ -0,0 +1,84 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{internal::ztimeout, sample::SourceInfo, session::EntityGlobalId};
+use zenoh_ext::OrderedChannel;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_ordered_channel() {
+    const KEYEXPR: &str = "test/ordered_channel";
+    zenoh_util::init_log_from_env_or("error");
+
+    let mut c = zenoh::Config::default();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let session = ztimeout!(zenoh::open(c)).unwrap();
+    let sub = ztimeout!(session
+        .declare_subscriber(KEYEXPR)
+        .with(OrderedChannel::new(100).window(3)))
+    .unwrap();
+
+    let source_a = EntityGlobalId::new(session.zid(), 1);
+    let source_b = EntityGlobalId::new(session.zid(), 2);
+    let put = |source, sn: u32| {
+        session
+            .put(KEYEXPR, format!("{sn}"))
+            .source_info(SourceInfo::new(Some(source), Some(sn)))
+    };
+    let received = || -> Vec<String> {
+        sub.drain()
+            .map(|s| s.payload().try_to_string().unwrap().into_owned())
+            .collect()
+    };
+
+    // The samples of a source are re-ordered, independently of the other sources
+    ztimeout!(put(source_a, 0)).unwrap();
+    ztimeout!(put(source_a, 2)).unwrap();
+    ztimeout!(put(source_b, 10)).unwrap();
+    ztimeout!(put(source_a, 3)).unwrap();
+    assert_eq!(received(), vec!["0", "10"]);
+    ztimeout!(put(source_a, 1)).unwrap();
+    assert_eq!(received(), vec!["1", "2", "3"]);
+
+    // A late sample is dropped
+    ztimeout!(put(source_a, 2)).unwrap();
+    assert!(received().is_empty());
+
+    // A missing sample is given up once the window is full
+    ztimeout!(put(source_a, 5)).unwrap();
+    ztimeout!(put(source_a, 6)).unwrap();
+    assert!(received().is_empty());
+    ztimeout!(put(source_a, 7)).unwrap();
+    assert_eq!(received(), vec!["5", "6", "7"]);
+
+    // The sequence numbers wrap around
+    let source_c = EntityGlobalId::new(session.zid(), 3);
+    ztimeout!(put(source_c, u32::MAX - 1)).unwrap();
+    ztimeout!(put(source_c, 0)).unwrap();
+    assert_eq!(received(), vec![(u32::MAX - 1).to_string()]);
+    ztimeout!(put(source_c, u32::MAX)).unwrap();
+    assert_eq!(received(), vec![u32::MAX.to_string(), "0".to_string()]);
+    ztimeout!(put(source_c, u32::MAX)).unwrap();
+    assert!(received().is_empty());
+
+    // The samples without sequence number are delivered as received
+    ztimeout!(session.put(KEYEXPR, "none")).unwrap();
+    assert_eq!(received(), vec!["none"]);
+
+    ztimeout!(sub.undeclare()).unwrap();
+    ztimeout!(session.close()).unwrap();
+}
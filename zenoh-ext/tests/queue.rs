@@ -0,0 +1,91 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::{sync::Arc, time::Duration};
+
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session,
+};
+use zenoh_ext::queue::{Consumer, Producer};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const QUEUE: &str = "test/queue";
+const LEASE: Duration = Duration::from_secs(1);
+
+async fn open_peer(listen: Option<&str>, connect: Option<&str>) -> Arc<Session> {
+    let mut c = zenoh::Config::default();
+    if let Some(endpoint) = listen {
+        c.listen
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    if let Some(endpoint) = connect {
+        c.connect
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    Arc::new(ztimeout!(zenoh::open(c)).unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_work_queue() {
+    const PEER1_ENDPOINT: &str = "tcp/localhost:47462";
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer1 = open_peer(Some(PEER1_ENDPOINT), None).await;
+    let peer2 = open_peer(None, Some(PEER1_ENDPOINT)).await;
+    let producer = ztimeout!(Producer::declare(peer1.clone(), QUEUE)).unwrap();
+    let consumer1 = ztimeout!(Consumer::declare(peer2.clone(), QUEUE, LEASE)).unwrap();
+    let consumer2 = ztimeout!(Consumer::declare(peer2.clone(), QUEUE, LEASE)).unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // A waiting consumer receives the pushed tasks
+    let recv = tokio::spawn(async move {
+        let task = consumer1.recv().await.unwrap();
+        (consumer1, task)
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    for i in 0..3u32 {
+        ztimeout!(producer.push(i.to_le_bytes().to_vec())).unwrap();
+    }
+    let (consumer1, task0) = ztimeout!(recv).unwrap();
+    assert_eq!(task0.payload().to_bytes().as_ref(), 0u32.to_le_bytes());
+    assert_eq!(task0.deliveries(), 1);
+
+    // The competing consumers receive distinct tasks
+    let task1 = ztimeout!(consumer2.recv()).unwrap();
+    assert_eq!(task1.seq(), 1);
+    ztimeout!(consumer2.ack(&task1)).unwrap();
+    let task2 = ztimeout!(consumer2.recv()).unwrap();
+    assert_eq!(task2.seq(), 2);
+    ztimeout!(consumer2.ack(&task2)).unwrap();
+
+    // A task which is not acknowledged is delivered again once its lease expired
+    let task = ztimeout!(consumer2.recv()).unwrap();
+    assert_eq!(task.seq(), task0.seq());
+    assert_eq!(task.deliveries(), 2);
+    ztimeout!(consumer2.ack(&task)).unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(producer.pending(), 0);
+
+    drop((consumer1, consumer2, producer));
+    ztimeout!(peer2.close()).unwrap();
+    ztimeout!(peer1.close()).unwrap();
+}
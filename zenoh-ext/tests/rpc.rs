@@ -0,0 +1,117 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session,
+};
+use zenoh_ext::rpc::{Client, ErrorCode, RpcError, Service};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SERVICE: &str = "test/rpc/divide";
+
+async fn open_peer(listen: Option<&str>, connect: Option<&str>) -> Session {
+    let mut c = zenoh::Config::default();
+    if let Some(endpoint) = listen {
+        c.listen
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    if let Some(endpoint) = connect {
+        c.connect
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    ztimeout!(zenoh::open(c)).unwrap()
+}
+
+async fn divide((a, b): (i64, i64)) -> Result<i64, RpcError> {
+    if b == 0 {
+        return Err(RpcError::new(ErrorCode::Application(1), "division by zero"));
+    }
+    if a < 0 {
+        // Never answers in time
+        tokio::time::sleep(TIMEOUT).await;
+    }
+    Ok(a / b)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_rpc() {
+    const PEER1_ENDPOINT: &str = "tcp/localhost:47460";
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer1 = open_peer(Some(PEER1_ENDPOINT), None).await;
+    let peer2 = open_peer(None, Some(PEER1_ENDPOINT)).await;
+    let client = Client::<(i64, i64), i64>::new(&peer2, SERVICE)
+        .unwrap()
+        .timeout(Duration::from_secs(1))
+        .retries(2)
+        .retry_delay(Duration::from_millis(500));
+
+    // No service is declared yet
+    let err = ztimeout!(client.call(&(6, 3))).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unavailable);
+
+    // The call is retried until the service is declared
+    let call = tokio::spawn({
+        let client = Client::<(i64, i64), i64>::new(&peer2, SERVICE)
+            .unwrap()
+            .retries(20)
+            .retry_delay(Duration::from_millis(100));
+        async move { client.call(&(6, 3)).await }
+    });
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let service = ztimeout!(Service::declare(&peer1, SERVICE, divide)).unwrap();
+    assert_eq!(ztimeout!(call).unwrap(), Ok(2));
+    assert_eq!(ztimeout!(client.call(&(7, 2))), Ok(3));
+
+    // The errors of the service are not retried
+    let err = ztimeout!(client.call(&(1, 0))).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Application(1));
+    assert_eq!(err.message(), "division by zero");
+
+    // A malformed request is rejected by the service
+    let bad_client = Client::<String, i64>::new(&peer2, SERVICE).unwrap();
+    let err = ztimeout!(bad_client.call(&"6/3".to_string())).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidRequest);
+
+    // A call which is not answered in time times out
+    let err = ztimeout!(client.call(&(-1, 1))).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Timeout);
+
+    // A call can be cancelled
+    let token = CancellationToken::new();
+    let slow_client = Client::<(i64, i64), i64>::new(&peer2, SERVICE).unwrap();
+    let call = tokio::spawn({
+        let token = token.clone();
+        async move { slow_client.call_with_cancellation(&(-1, 1), &token).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    token.cancel();
+    let err = ztimeout!(call).unwrap().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Cancelled);
+
+    drop(service);
+    ztimeout!(peer2.close()).unwrap();
+    ztimeout!(peer1.close()).unwrap();
+}
@@ -0,0 +1,97 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::{sync::Arc, time::Duration};
+
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session,
+};
+use zenoh_ext::shared::{SharedMap, SharedValue};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_millis(500);
+
+async fn open_peer(listen: Option<&str>, connect: Option<&str>) -> Arc<Session> {
+    let mut c = zenoh::Config::default();
+    if let Some(endpoint) = listen {
+        c.listen
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    if let Some(endpoint) = connect {
+        c.connect
+            .endpoints
+            .set(vec![endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+    }
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    Arc::new(ztimeout!(zenoh::open(c)).unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_shared_state() {
+    const PEER1_ENDPOINT: &str = "tcp/localhost:47461";
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer1 = open_peer(Some(PEER1_ENDPOINT), None).await;
+    let peer2 = open_peer(None, Some(PEER1_ENDPOINT)).await;
+    tokio::time::sleep(SLEEP).await;
+
+    // The updates of a replica are applied by the other ones
+    let map1 = ztimeout!(SharedMap::<String, u32>::join(peer1.clone(), "test/map")).unwrap();
+    let map2 = ztimeout!(SharedMap::<String, u32>::join(peer2.clone(), "test/map")).unwrap();
+    let changes2 = map2.subscribe();
+    tokio::time::sleep(SLEEP).await;
+    ztimeout!(map1.insert("a".into(), 1)).unwrap();
+    ztimeout!(map1.insert("b".into(), 2)).unwrap();
+    let change = ztimeout!(changes2.recv_async()).unwrap();
+    assert_eq!((change.key().as_str(), change.value()), ("a", Some(&1)));
+    let change = ztimeout!(changes2.recv_async()).unwrap();
+    assert_eq!((change.key().as_str(), change.value()), ("b", Some(&2)));
+    assert_eq!(map2.get(&"a".into()), Some(1));
+    assert_eq!(map2.len(), 2);
+
+    // The last writer wins
+    ztimeout!(map2.insert("a".into(), 3)).unwrap();
+    ztimeout!(map1.remove("b".into())).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(map1.get(&"a".into()), Some(3));
+    assert_eq!(map2.get(&"b".into()), None);
+    assert_eq!(map1.entries(), vec![("a".to_string(), 3)]);
+
+    // A joining replica retrieves the current state
+    let map3 = ztimeout!(SharedMap::<String, u32>::join(peer2.clone(), "test/map")).unwrap();
+    assert_eq!(map3.entries(), vec![("a".to_string(), 3)]);
+
+    // A shared value behaves as a map with a single entry
+    let value1 = ztimeout!(SharedValue::<String>::join(peer1.clone(), "test/value")).unwrap();
+    let value2 = ztimeout!(SharedValue::<String>::join(peer2.clone(), "test/value")).unwrap();
+    let changes2 = value2.subscribe();
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(value2.get(), None);
+    ztimeout!(value1.set("on".into())).unwrap();
+    let change = ztimeout!(changes2.recv_async()).unwrap();
+    assert_eq!(change.value().map(String::as_str), Some("on"));
+    ztimeout!(value2.clear()).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(value1.get(), None);
+
+    drop((map1, map2, map3, value1, value2));
+    ztimeout!(peer2.close()).unwrap();
+    ztimeout!(peer1.close()).unwrap();
+}
@@ -0,0 +1,186 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Simulation of the access control decisions, to test ACL policies before rolling them out.
+use std::future::{IntoFuture, Ready};
+
+use serde_json::json;
+use zenoh_config::{
+    AclConfig, AclMessage, CertCommonName, InterceptorFlow, Interface, Permission, Username,
+};
+use zenoh_core::{Resolvable, Wait};
+use zenoh_protocol::core::key_expr::keyexpr;
+use zenoh_result::ZResult;
+
+use crate::{
+    api::{key_expr::KeyExpr, session::Session},
+    net::routing::interceptor::authorization::{PolicyEnforcer, SubjectQuery},
+};
+
+/// The decision of the access control on a message, returned by
+/// [`Session::check_acl`](crate::Session::check_acl).
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AclDecision {
+    /// Whether the message is allowed or denied.
+    pub permission: Permission,
+    /// The id of the rule of `access_control/rules` which decided the permission, or `None` if
+    /// the default permission applies.
+    pub rule: Option<String>,
+}
+
+#[zenoh_macros::unstable]
+impl AclDecision {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        json!({
+            "permission": self.permission,
+            "rule": self.rule,
+        })
+    }
+}
+
+/// Decides whether the `acl_config` allows the `message` of the `subject` on `key_expr`.
+pub(crate) fn check(
+    acl_config: &AclConfig,
+    subject: &SubjectQuery,
+    flow: InterceptorFlow,
+    message: AclMessage,
+    key_expr: &keyexpr,
+) -> ZResult<AclDecision> {
+    let mut enforcer = PolicyEnforcer::new();
+    enforcer.init(acl_config)?;
+    let (permission, rule) = enforcer.explain(subject, flow, message, key_expr);
+    Ok(AclDecision { permission, rule })
+}
+
+/// A builder for checking the access control decision on a message, see
+/// [`Session::check_acl`](crate::Session::check_acl).
+///
+/// The message is checked against the `access_control` configuration of the session, as received
+/// from a remote node authenticated with the given subject properties. A property which is not
+/// set only matches the subjects which don't restrict it.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::config::{AclMessage, Permission};
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let decision = session
+///     .check_acl(AclMessage::Put, "key/expression")
+///     .username("alice")
+///     .await
+///     .unwrap();
+/// assert_eq!(decision.permission, Permission::Allow);
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+#[derive(Debug)]
+pub struct AclCheckBuilder<'a, 'b> {
+    pub(crate) session: &'a Session,
+    pub(crate) message: AclMessage,
+    pub(crate) key_expr: ZResult<KeyExpr<'b>>,
+    pub(crate) flow: InterceptorFlow,
+    pub(crate) subject: SubjectQuery,
+}
+
+#[zenoh_macros::unstable]
+impl<'a, 'b> AclCheckBuilder<'a, 'b> {
+    pub(crate) fn new(
+        session: &'a Session,
+        message: AclMessage,
+        key_expr: ZResult<KeyExpr<'b>>,
+    ) -> Self {
+        Self {
+            session,
+            message,
+            key_expr,
+            flow: InterceptorFlow::Ingress,
+            subject: SubjectQuery {
+                interface: None,
+                cert_common_name: None,
+                username: None,
+            },
+        }
+    }
+
+    /// The direction of the message ([`InterceptorFlow::Ingress`] by default).
+    #[inline]
+    pub fn flow(mut self, flow: InterceptorFlow) -> Self {
+        self.flow = flow;
+        self
+    }
+
+    /// The network interface the message goes through.
+    #[inline]
+    pub fn interface<S: Into<String>>(mut self, interface: S) -> Self {
+        self.subject.interface = Some(Interface(interface.into()));
+        self
+    }
+
+    /// The common name of the TLS certificate of the remote node.
+    #[inline]
+    pub fn cert_common_name<S: Into<String>>(mut self, cert_common_name: S) -> Self {
+        self.subject.cert_common_name = Some(CertCommonName(cert_common_name.into()));
+        self
+    }
+
+    /// The username the remote node authenticated with.
+    #[inline]
+    pub fn username<S: Into<String>>(mut self, username: S) -> Self {
+        self.subject.username = Some(Username(username.into()));
+        self
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for AclCheckBuilder<'_, '_> {
+    type To = ZResult<AclDecision>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for AclCheckBuilder<'_, '_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        let key_expr = self.key_expr?;
+        let acl_config = self
+            .session
+            .0
+            .runtime
+            .config()
+            .lock()
+            .0
+            .access_control()
+            .clone();
+        check(
+            &acl_config,
+            &self.subject,
+            self.flow,
+            self.message,
+            &key_expr,
+        )
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for AclCheckBuilder<'_, '_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
@@ -15,9 +15,11 @@ use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
     sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use zenoh_core::{Result as ZResult, Wait};
+use serde_json::json;
+use zenoh_core::{zread, Result as ZResult, Wait};
 use zenoh_keyexpr::keyexpr;
 use zenoh_macros::ke;
 #[cfg(feature = "unstable")]
@@ -33,7 +35,7 @@ use crate::{
         encoding::Encoding,
         key_expr::KeyExpr,
         queryable::Query,
-        sample::{DataInfo, Locality, SampleKind},
+        sample::{DataInfo, Locality, QoS, SampleKind},
         session::WeakSession,
         subscriber::SubscriberKind,
     },
@@ -68,12 +70,14 @@ static KE_STARSTAR: &keyexpr = ke!("**");
 static KE_SESSION: &keyexpr = ke!("session");
 static KE_TRANSPORT_UNICAST: &keyexpr = ke!("transport/unicast");
 static KE_LINK: &keyexpr = ke!("link");
+static KE_ENTITIES: &keyexpr = ke!("entities");
 
 pub(crate) fn init(session: WeakSession) {
     if let Ok(own_zid) = keyexpr::new(&session.zid().to_string()) {
         let _admin_qabl = session.declare_queryable_inner(
             &KeyExpr::from(KE_AT / own_zid / KE_SESSION / KE_STARSTAR),
             true,
+            0,
             Locality::SessionLocal,
             Callback::new(Arc::new({
                 let session = session.clone();
@@ -86,6 +90,7 @@ pub(crate) fn init(session: WeakSession) {
         let _admin_adv_qabl = session.declare_queryable_inner(
             &KeyExpr::from(&adv_prefix / own_zid / KE_SESSION / KE_STARSTAR),
             true,
+            0,
             Locality::SessionLocal,
             Callback::new(Arc::new({
                 let session = session.clone();
@@ -136,7 +141,92 @@ pub(crate) fn on_admin_query(session: &WeakSession, prefix: &keyexpr, query: Que
         }
     }
 
+    fn qos_json(qos: &QoS) -> serde_json::Value {
+        json!({
+            "priority": format!("{:?}", qos.priority()),
+            "congestion_control": format!("{:?}", qos.congestion_control()),
+            "express": qos.express(),
+        })
+    }
+
+    fn reply_entities(session: &WeakSession, prefix: &keyexpr, own_zid: &keyexpr, query: &Query) {
+        let declared_at = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64
+        };
+        let mut entities = vec![];
+        {
+            let state = zread!(session.state);
+            for publisher in state.publishers.values() {
+                entities.push((
+                    "publisher",
+                    publisher.id,
+                    json!({
+                        "key_expr": publisher.key_expr.as_str(),
+                        "qos": qos_json(&publisher.qos),
+                        "declared_at": declared_at(publisher.declared_at),
+                    }),
+                ));
+            }
+            for subscriber in state.subscribers.values() {
+                entities.push((
+                    "subscriber",
+                    subscriber.id,
+                    json!({
+                        "key_expr": subscriber.key_expr.as_str(),
+                        "declared_at": declared_at(subscriber.declared_at),
+                    }),
+                ));
+            }
+            for queryable in state.queryables.values() {
+                entities.push((
+                    "queryable",
+                    queryable.id,
+                    json!({
+                        "key_expr": queryable.key_expr.to_string(),
+                        "complete": queryable.complete,
+                        "declared_at": declared_at(queryable.declared_at),
+                    }),
+                ));
+            }
+            #[cfg(feature = "unstable")]
+            for querier in state.queriers.values() {
+                entities.push((
+                    "querier",
+                    querier.id,
+                    json!({
+                        "key_expr": querier.key_expr.as_str(),
+                        "qos": qos_json(&querier.qos),
+                        "declared_at": declared_at(querier.declared_at),
+                    }),
+                ));
+            }
+        }
+        for (kind, id, mut value) in entities {
+            let id = id.to_string();
+            let (Ok(kind), Ok(id)) = (keyexpr::new(kind), keyexpr::new(&id)) else {
+                continue;
+            };
+            let key_expr = prefix / own_zid / KE_SESSION / KE_ENTITIES / kind / id;
+            if query.key_expr().intersects(&key_expr) {
+                value["owner"] = own_zid.as_str().into();
+                let reply_expr = KE_AT / own_zid / KE_SESSION / KE_ENTITIES / kind / id;
+                match serde_json::to_vec(&value) {
+                    Ok(bytes) => {
+                        let _ = query
+                            .reply(reply_expr, bytes)
+                            .encoding(Encoding::APPLICATION_JSON)
+                            .wait();
+                    }
+                    Err(e) => tracing::debug!("Admin query error: {}", e),
+                }
+            }
+        }
+    }
+
     if let Ok(own_zid) = keyexpr::new(&session.zid().to_string()) {
+        reply_entities(session, prefix, own_zid, &query);
         for transport in zenoh_runtime::ZRuntime::Net
             .block_in_place(session.runtime.manager().get_transports_unicast())
         {
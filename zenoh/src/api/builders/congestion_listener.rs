@@ -0,0 +1,230 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    future::{IntoFuture, Ready},
+    sync::Arc,
+};
+
+use zenoh_core::{Resolvable, Wait};
+use zenoh_result::ZResult;
+
+use crate::api::{
+    congestion::{CongestionEvent, CongestionListener, CongestionListenerInner, CongestionMonitor},
+    handlers::{Callback, DefaultHandler, IntoHandler},
+};
+
+/// A builder for initializing a [`CongestionListener`].
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct CongestionListenerBuilder<'a, Handler, const BACKGROUND: bool = false> {
+    pub(crate) monitor: &'a Arc<CongestionMonitor>,
+    pub handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> CongestionListenerBuilder<'a, DefaultHandler> {
+    /// Receive the CongestionEvents for this listener with a callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let congestion_listener = publisher
+    ///     .congestion_events()
+    ///     .callback(|event| {
+    ///         if event.is_congested() {
+    ///             println!("Publisher is congested.");
+    ///         } else {
+    ///             println!("Publisher is no longer congested.");
+    ///         }
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    #[zenoh_macros::unstable]
+    pub fn callback<F>(
+        self,
+        callback: F,
+    ) -> CongestionListenerBuilder<'a, Callback<CongestionEvent>>
+    where
+        F: Fn(CongestionEvent) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the CongestionEvents for this listener with a mutable callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let mut n = 0;
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let congestion_listener = publisher
+    ///     .congestion_events()
+    ///     .callback_mut(move |_event| { n += 1; })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    #[zenoh_macros::unstable]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> CongestionListenerBuilder<'a, Callback<CongestionEvent>>
+    where
+        F: FnMut(CongestionEvent) + Send + Sync + 'static,
+    {
+        self.callback(crate::api::handlers::locked(callback))
+    }
+
+    /// Receive the CongestionEvents for this listener with a [`Handler`](IntoHandler).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let congestion_listener = publisher
+    ///     .congestion_events()
+    ///     .with(flume::bounded(32))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(event) = congestion_listener.recv_async().await {
+    ///     println!("Publisher is {:?}", event.status());
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    #[zenoh_macros::unstable]
+    pub fn with<Handler>(self, handler: Handler) -> CongestionListenerBuilder<'a, Handler>
+    where
+        Handler: IntoHandler<CongestionEvent>,
+    {
+        CongestionListenerBuilder {
+            monitor: self.monitor,
+            handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a> CongestionListenerBuilder<'a, Callback<CongestionEvent>> {
+    /// Register the listener callback to be run in background until the publisher is undeclared.
+    ///
+    /// Background builder doesn't return a `CongestionListener` object anymore.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// // no need to assign and keep a variable with a background listener
+    /// publisher
+    ///     .congestion_events()
+    ///     .callback(|event| println!("Publisher is {:?}", event.status()))
+    ///     .background()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn background(self) -> CongestionListenerBuilder<'a, Callback<CongestionEvent>, true> {
+        CongestionListenerBuilder {
+            monitor: self.monitor,
+            handler: self.handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for CongestionListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<CongestionEvent> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<CongestionListener<Handler::Handler>>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for CongestionListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<CongestionEvent> + Send,
+    Handler::Handler: Send,
+{
+    #[zenoh_macros::unstable]
+    fn wait(self) -> <Self as Resolvable>::To {
+        let (callback, handler) = self.handler.into_handler();
+        let id = self.monitor.declare(callback);
+        Ok(CongestionListener {
+            inner: CongestionListenerInner {
+                monitor: self.monitor.clone(),
+                id,
+                undeclare_on_drop: true,
+            },
+            handler,
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for CongestionListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<CongestionEvent> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    #[zenoh_macros::unstable]
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for CongestionListenerBuilder<'_, Callback<CongestionEvent>, true> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for CongestionListenerBuilder<'_, Callback<CongestionEvent>, true> {
+    #[zenoh_macros::unstable]
+    fn wait(self) -> <Self as Resolvable>::To {
+        self.monitor.declare(self.handler);
+        Ok(())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for CongestionListenerBuilder<'_, Callback<CongestionEvent>, true> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    #[zenoh_macros::unstable]
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
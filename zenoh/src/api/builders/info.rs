@@ -17,7 +17,11 @@ use std::future::{IntoFuture, Ready};
 use zenoh_config::wrappers::ZenohId;
 use zenoh_core::{Resolvable, Wait};
 use zenoh_protocol::core::WhatAmI;
+#[cfg(feature = "unstable")]
+use zenoh_result::{zerror, ZResult};
 
+#[cfg(feature = "unstable")]
+use crate::api::info::Transport;
 use crate::net::runtime::Runtime;
 
 /// A builder returned by [`SessionInfo::zid()`](crate::session::SessionInfo::zid) that allows
@@ -169,3 +173,120 @@ impl IntoFuture for PeersZenohIdBuilder<'_> {
         std::future::ready(self.wait())
     }
 }
+
+/// A builder returned by [`SessionInfo::transports()`](crate::session::SessionInfo::transports)
+/// that allows to access the unicast transport sessions established with the remote zenoh nodes.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let transports = session.info().transports().await;
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TransportsBuilder<'a> {
+    runtime: &'a Runtime,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> TransportsBuilder<'a> {
+    pub(crate) fn new(runtime: &'a Runtime) -> Self {
+        Self { runtime }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for TransportsBuilder<'_> {
+    type To = Box<dyn Iterator<Item = Transport> + Send + Sync>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for TransportsBuilder<'_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        Box::new(
+            zenoh_runtime::ZRuntime::Application
+                .block_in_place(self.runtime.manager().get_transports_unicast())
+                .into_iter()
+                .filter_map(|s| {
+                    Some(Transport {
+                        zid: s.get_zid().ok()?.into(),
+                        whatami: s.get_whatami().ok()?,
+                        links: s.get_links().ok()?.into_iter().map(Into::into).collect(),
+                    })
+                }),
+        )
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for TransportsBuilder<'_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A builder returned by
+/// [`SessionInfo::close_transport()`](crate::session::SessionInfo::close_transport) that closes
+/// the unicast transport session established with a remote zenoh node.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let zid = "1".parse().unwrap();
+/// session.info().close_transport(zid).await.unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct CloseTransportBuilder<'a> {
+    runtime: &'a Runtime,
+    zid: ZenohId,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> CloseTransportBuilder<'a> {
+    pub(crate) fn new(runtime: &'a Runtime, zid: ZenohId) -> Self {
+        Self { runtime, zid }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for CloseTransportBuilder<'_> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for CloseTransportBuilder<'_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        zenoh_runtime::ZRuntime::Application.block_in_place(async {
+            let transport = self
+                .runtime
+                .manager()
+                .get_transport_unicast(&self.zid.into())
+                .await
+                .ok_or_else(|| zerror!("No transport established with {}", self.zid))?;
+            tracing::info!("Closing the transport with {}", self.zid);
+            transport.close().await
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for CloseTransportBuilder<'_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
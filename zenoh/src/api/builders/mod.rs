@@ -13,6 +13,8 @@
 //
 
 pub(crate) mod close;
+#[cfg(feature = "unstable")]
+pub(crate) mod congestion_listener;
 pub(crate) mod info;
 pub(crate) mod matching_listener;
 pub(crate) mod publisher;
@@ -12,17 +12,19 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 use std::future::{IntoFuture, Ready};
+#[cfg(feature = "unstable")]
+use std::time::Duration;
 
 use itertools::Itertools;
 use zenoh_config::qos::PublisherQoSConfig;
 use zenoh_core::{Resolvable, Result as ZResult, Wait};
-use zenoh_keyexpr::keyexpr_tree::{IKeyExprTree, IKeyExprTreeNode};
-use zenoh_protocol::core::CongestionControl;
+use zenoh_keyexpr::keyexpr_tree::{IKeyExprTree, IKeyExprTreeNode, KeBoxTree};
 #[cfg(feature = "unstable")]
 use zenoh_protocol::core::Reliability;
+use zenoh_protocol::{core::CongestionControl, network::push};
 
 #[cfg(feature = "unstable")]
-use crate::api::sample::SourceInfo;
+use crate::api::{sample::SourceInfo, signature::SigningKey, trace::TraceContext};
 use crate::{
     api::{
         builders::sample::{
@@ -32,7 +34,8 @@ use crate::{
         encoding::Encoding,
         key_expr::KeyExpr,
         publisher::{Priority, Publisher},
-        sample::{Locality, SampleKind},
+        sample::{Locality, QoS, SampleKind},
+        session::SessionState,
     },
     Session,
 };
@@ -87,6 +90,8 @@ pub struct PublicationBuilder<P, T> {
     pub(crate) timestamp: Option<uhlc::Timestamp>,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
     pub(crate) attachment: Option<ZBytes>,
 }
 
@@ -148,6 +153,34 @@ impl<T> PublicationBuilder<PublisherBuilder<'_, '_>, T> {
             ..self
         }
     }
+
+    /// Limits the number of links the data may traverse, e.g. `1` to only deliver it to the
+    /// nodes directly connected to this one, or `0` to only deliver it within this process.
+    ///
+    /// Unlike [`Locality`], which only distinguishes the local and the remote subscribers, the
+    /// hop limit is enforced by every router forwarding the data.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn hop_limit(self, hop_limit: u8) -> Self {
+        Self {
+            publisher: self.publisher.hop_limit(hop_limit),
+            ..self
+        }
+    }
+
+    /// Drops the data instead of transmitting it if it could not be transmitted within
+    /// `deadline`, e.g. a control command which is harmful once stale.
+    ///
+    /// The deadline is carried with the data and enforced by every node forwarding it, which
+    /// assumes that their clocks are synchronized.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn deadline(self, deadline: Duration) -> Self {
+        Self {
+            publisher: self.publisher.deadline(deadline),
+            ..self
+        }
+    }
 }
 
 #[zenoh_macros::internal_trait]
@@ -201,6 +234,19 @@ impl<P, T> TimestampBuilderTrait for PublicationBuilder<P, T> {
     }
 }
 
+impl<P, T> PublicationBuilder<P, T> {
+    /// Sets the trace context propagated along with the publication.
+    ///
+    /// When not set, the trace context of the current [`tracing::Span`] is used, if any.
+    #[zenoh_macros::unstable]
+    pub fn trace_context<TC: Into<Option<TraceContext>>>(self, trace_context: TC) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
+}
+
 impl<P, T> Resolvable for PublicationBuilder<P, T> {
     type To = ZResult<()>;
 }
@@ -220,9 +266,17 @@ impl Wait for PublicationBuilder<PublisherBuilder<'_, '_>, PublicationBuilderPut
             self.publisher.destination,
             #[cfg(feature = "unstable")]
             self.publisher.reliability,
+            #[cfg(feature = "unstable")]
+            self.publisher.hop_limit,
+            #[cfg(feature = "unstable")]
+            self.publisher.deadline,
             self.timestamp,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
+            #[cfg(feature = "unstable")]
+            self.publisher.signing_key.as_ref(),
             self.attachment,
         )
     }
@@ -243,9 +297,17 @@ impl Wait for PublicationBuilder<PublisherBuilder<'_, '_>, PublicationBuilderDel
             self.publisher.destination,
             #[cfg(feature = "unstable")]
             self.publisher.reliability,
+            #[cfg(feature = "unstable")]
+            self.publisher.hop_limit,
+            #[cfg(feature = "unstable")]
+            self.publisher.deadline,
             self.timestamp,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
+            #[cfg(feature = "unstable")]
+            self.publisher.signing_key.as_ref(),
             self.attachment,
         )
     }
@@ -324,6 +386,24 @@ pub struct PublisherBuilder<'a, 'b> {
     pub destination: Locality,
     #[cfg(not(feature = "internal"))]
     pub(crate) destination: Locality,
+    #[cfg(feature = "internal")]
+    #[cfg(feature = "unstable")]
+    pub signing_key: Option<SigningKey>,
+    #[cfg(not(feature = "internal"))]
+    #[cfg(feature = "unstable")]
+    pub(crate) signing_key: Option<SigningKey>,
+    #[cfg(feature = "internal")]
+    #[cfg(feature = "unstable")]
+    pub hop_limit: Option<u8>,
+    #[cfg(not(feature = "internal"))]
+    #[cfg(feature = "unstable")]
+    pub(crate) hop_limit: Option<u8>,
+    #[cfg(feature = "internal")]
+    #[cfg(feature = "unstable")]
+    pub deadline: Option<Duration>,
+    #[cfg(not(feature = "internal"))]
+    #[cfg(feature = "unstable")]
+    pub(crate) deadline: Option<Duration>,
 }
 
 impl Clone for PublisherBuilder<'_, '_> {
@@ -341,6 +421,12 @@ impl Clone for PublisherBuilder<'_, '_> {
             #[cfg(feature = "unstable")]
             reliability: self.reliability,
             destination: self.destination,
+            #[cfg(feature = "unstable")]
+            signing_key: self.signing_key.clone(),
+            #[cfg(feature = "unstable")]
+            hop_limit: self.hop_limit,
+            #[cfg(feature = "unstable")]
+            deadline: self.deadline,
         }
     }
 }
@@ -373,21 +459,33 @@ impl QoSBuilderTrait for PublisherBuilder<'_, '_> {
 }
 
 impl PublisherBuilder<'_, '_> {
+    /// Looks up if any configured QoS defaults apply on the builder's key expression.
+    /// Returns a new builder with the defaulted QoS parameters, which may still be changed
+    /// through the builder.
+    pub(crate) fn apply_qos_defaults(self) -> Self {
+        let defaults = self.lookup_qos_config(|state| &state.publisher_qos_defaults_tree);
+        self.with_qos_config(defaults)
+    }
+
     /// Looks up if any configured QoS overwrites apply on the builder's key expression.
     /// Returns a new builder with the overwritten QoS parameters.
     pub(crate) fn apply_qos_overwrites(self) -> Self {
-        let mut qos_overwrites = PublisherQoSConfig::default();
+        let overwrites = self.lookup_qos_config(|state| &state.publisher_qos_tree);
+        self.with_qos_config(overwrites)
+    }
+
+    fn lookup_qos_config(
+        &self,
+        tree: impl FnOnce(&SessionState) -> &KeBoxTree<PublisherQoSConfig>,
+    ) -> PublisherQoSConfig {
+        let mut qos_config = PublisherQoSConfig::default();
         if let Ok(key_expr) = &self.key_expr {
-            // get overwritten builder
             let state = zread!(self.session.0.state);
-            let nodes_including = state
-                .publisher_qos_tree
-                .nodes_including(key_expr)
-                .collect_vec();
+            let nodes_including = tree(&state).nodes_including(key_expr).collect_vec();
             for node in &nodes_including {
-                // Take the first one yielded by the iterator that has overwrites
-                if let Some(overwrites) = node.weight() {
-                    qos_overwrites = overwrites.clone();
+                // Take the first one yielded by the iterator that has a config
+                if let Some(config) = node.weight() {
+                    qos_config = config.clone();
                     // log warning if multiple keyexprs include it
                     if nodes_including.len() > 1 {
                         tracing::warn!(
@@ -400,27 +498,32 @@ impl PublisherBuilder<'_, '_> {
                 }
             }
         }
+        qos_config
+    }
 
+    fn with_qos_config(self, qos_config: PublisherQoSConfig) -> Self {
         Self {
-            congestion_control: qos_overwrites
+            congestion_control: qos_config
                 .congestion_control
                 .map(|cc| cc.into())
                 .unwrap_or(self.congestion_control),
-            priority: qos_overwrites
+            priority: qos_config
                 .priority
                 .map(|p| p.into())
                 .unwrap_or(self.priority),
-            is_express: qos_overwrites.express.unwrap_or(self.is_express),
+            is_express: qos_config.express.unwrap_or(self.is_express),
             #[cfg(feature = "unstable")]
-            reliability: qos_overwrites
+            reliability: qos_config
                 .reliability
                 .map(|r| r.into())
                 .unwrap_or(self.reliability),
             #[cfg(feature = "unstable")]
-            destination: qos_overwrites
+            destination: qos_config
                 .allowed_destination
                 .map(|d| d.into())
                 .unwrap_or(self.destination),
+            #[cfg(feature = "unstable")]
+            hop_limit: qos_config.hop_limit.or(self.hop_limit),
             ..self
         }
     }
@@ -449,6 +552,49 @@ impl PublisherBuilder<'_, '_> {
             ..self
         }
     }
+
+    /// Limits the number of links the data may traverse, e.g. `1` to only deliver it to the
+    /// nodes directly connected to this one, or `0` to only deliver it within this process.
+    ///
+    /// Unlike [`Locality`], which only distinguishes the local and the remote subscribers, the
+    /// hop limit is enforced by every router forwarding the data.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn hop_limit(self, hop_limit: u8) -> Self {
+        Self {
+            hop_limit: Some(hop_limit),
+            ..self
+        }
+    }
+
+    /// Drops the data instead of transmitting it if it could not be transmitted within
+    /// `deadline` of being published, e.g. a control command which is harmful once stale.
+    ///
+    /// The deadline is carried with the data and enforced by every node forwarding it, which
+    /// assumes that their clocks are synchronized. The publications dropped by this node are
+    /// counted by [`Publisher::expired`].
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn deadline(self, deadline: Duration) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Signs the samples of the [`Publisher`] with the given [`SigningKey`], authenticating their
+    /// key expression, kind, timestamp and payload.
+    ///
+    /// The signed samples are always timestamped. Their signature can be verified by the
+    /// subscribers with a [`SignaturePolicy`](crate::sample::SignaturePolicy).
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn signing_key(self, signing_key: SigningKey) -> Self {
+        Self {
+            signing_key: Some(signing_key),
+            ..self
+        }
+    }
 }
 
 impl<'b> Resolvable for PublisherBuilder<'_, 'b> {
@@ -462,10 +608,15 @@ impl Wait for PublisherBuilder<'_, '_> {
         if !key_expr.is_fully_optimized(&self.session.0) {
             key_expr = self.session.declare_keyexpr(key_expr).wait()?;
         }
-        let id = self
-            .session
-            .0
-            .declare_publisher_inner(key_expr.clone(), self.destination)?;
+        let id = self.session.0.declare_publisher_inner(
+            key_expr.clone(),
+            self.destination,
+            QoS::from(push::ext::QoSType::new(
+                self.priority.into(),
+                self.congestion_control,
+                self.is_express,
+            )),
+        )?;
         Ok(Publisher {
             session: self.session.downgrade(),
             id,
@@ -479,6 +630,14 @@ impl Wait for PublisherBuilder<'_, '_> {
             reliability: self.reliability,
             #[cfg(feature = "unstable")]
             matching_listeners: Default::default(),
+            #[cfg(feature = "unstable")]
+            signing_key: self.signing_key,
+            #[cfg(feature = "unstable")]
+            hop_limit: self.hop_limit,
+            #[cfg(feature = "unstable")]
+            deadline: self.deadline,
+            #[cfg(feature = "unstable")]
+            congestion: Default::default(),
             undeclare_on_drop: true,
         })
     }
@@ -495,43 +654,63 @@ impl IntoFuture for PublisherBuilder<'_, '_> {
 
 impl Wait for PublicationBuilder<&Publisher<'_>, PublicationBuilderPut> {
     fn wait(self) -> <Self as Resolvable>::To {
-        self.publisher.session.resolve_put(
-            &self.publisher.key_expr,
-            self.kind.payload,
-            SampleKind::Put,
-            self.kind.encoding,
-            self.publisher.congestion_control,
-            self.publisher.priority,
-            self.publisher.is_express,
-            self.publisher.destination,
-            #[cfg(feature = "unstable")]
-            self.publisher.reliability,
-            self.timestamp,
-            #[cfg(feature = "unstable")]
-            self.source_info,
-            self.attachment,
-        )
+        self.publisher.publish(|| {
+            self.publisher.session.resolve_put(
+                &self.publisher.key_expr,
+                self.kind.payload,
+                SampleKind::Put,
+                self.kind.encoding,
+                self.publisher.congestion_control,
+                self.publisher.priority,
+                self.publisher.is_express,
+                self.publisher.destination,
+                #[cfg(feature = "unstable")]
+                self.publisher.reliability,
+                #[cfg(feature = "unstable")]
+                self.publisher.hop_limit,
+                #[cfg(feature = "unstable")]
+                self.publisher.deadline,
+                self.timestamp,
+                #[cfg(feature = "unstable")]
+                self.source_info,
+                #[cfg(feature = "unstable")]
+                self.trace_context,
+                #[cfg(feature = "unstable")]
+                self.publisher.signing_key.as_ref(),
+                self.attachment,
+            )
+        })
     }
 }
 
 impl Wait for PublicationBuilder<&Publisher<'_>, PublicationBuilderDelete> {
     fn wait(self) -> <Self as Resolvable>::To {
-        self.publisher.session.resolve_put(
-            &self.publisher.key_expr,
-            ZBytes::new(),
-            SampleKind::Delete,
-            Encoding::ZENOH_BYTES,
-            self.publisher.congestion_control,
-            self.publisher.priority,
-            self.publisher.is_express,
-            self.publisher.destination,
-            #[cfg(feature = "unstable")]
-            self.publisher.reliability,
-            self.timestamp,
-            #[cfg(feature = "unstable")]
-            self.source_info,
-            self.attachment,
-        )
+        self.publisher.publish(|| {
+            self.publisher.session.resolve_put(
+                &self.publisher.key_expr,
+                ZBytes::new(),
+                SampleKind::Delete,
+                Encoding::ZENOH_BYTES,
+                self.publisher.congestion_control,
+                self.publisher.priority,
+                self.publisher.is_express,
+                self.publisher.destination,
+                #[cfg(feature = "unstable")]
+                self.publisher.reliability,
+                #[cfg(feature = "unstable")]
+                self.publisher.hop_limit,
+                #[cfg(feature = "unstable")]
+                self.publisher.deadline,
+                self.timestamp,
+                #[cfg(feature = "unstable")]
+                self.source_info,
+                #[cfg(feature = "unstable")]
+                self.trace_context,
+                #[cfg(feature = "unstable")]
+                self.publisher.signing_key.as_ref(),
+                self.attachment,
+            )
+        })
     }
 }
 
@@ -28,7 +28,7 @@ use super::sample::QoSBuilderTrait;
 #[cfg(feature = "unstable")]
 use crate::api::query::ReplyKeyExpr;
 #[cfg(feature = "unstable")]
-use crate::api::sample::SourceInfo;
+use crate::api::{sample::SourceInfo, trace::TraceContext};
 #[cfg(feature = "unstable")]
 use crate::query::ZenohParameters;
 use crate::{
@@ -38,7 +38,7 @@ use crate::{
         encoding::Encoding,
         handlers::{locked, Callback, DefaultHandler, IntoHandler},
         querier::Querier,
-        sample::{Locality, QoSBuilder},
+        sample::{Locality, QoS, QoSBuilder},
     },
     bytes::OptionZBytes,
     key_expr::KeyExpr,
@@ -81,6 +81,7 @@ pub struct QuerierBuilder<'a, 'b> {
     pub(crate) qos: QoSBuilder,
     pub(crate) destination: Locality,
     pub(crate) timeout: Duration,
+    pub(crate) reply_timeout: Option<Duration>,
     #[cfg(feature = "unstable")]
     pub(crate) accept_replies: ReplyKeyExpr,
 }
@@ -136,6 +137,19 @@ impl QuerierBuilder<'_, '_> {
         Self { timeout, ..self }
     }
 
+    /// Finalize each query once no reply was received for `reply_timeout`, although its
+    /// [`timeout`](Self::timeout) is not elapsed.
+    ///
+    /// See [`SessionGetBuilder::reply_timeout`](crate::session::SessionGetBuilder::reply_timeout).
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn reply_timeout(self, reply_timeout: Duration) -> Self {
+        Self {
+            reply_timeout: Some(reply_timeout),
+            ..self
+        }
+    }
+
     /// By default, only replies whose key expressions intersect
     /// with the querier key expression will be received by calls to [`Querier::get`](crate::query::Querier::get) method.
     ///
@@ -160,20 +174,22 @@ impl Wait for QuerierBuilder<'_, '_> {
         if !key_expr.is_fully_optimized(&self.session.0) {
             key_expr = self.session.declare_keyexpr(key_expr).wait()?;
         }
+        let qos: QoS = self.qos.into();
         let id = self
             .session
             .0
-            .declare_querier_inner(key_expr.clone(), self.destination)?;
+            .declare_querier_inner(key_expr.clone(), self.destination, qos)?;
         Ok(Querier {
             session: self.session.downgrade(),
             id,
             key_expr,
-            qos: self.qos.into(),
+            qos,
             destination: self.destination,
             undeclare_on_drop: true,
             target: self.target,
             consolidation: self.consolidation,
             timeout: self.timeout,
+            reply_timeout: self.reply_timeout,
             #[cfg(feature = "unstable")]
             accept_replies: self.accept_replies,
             #[cfg(feature = "unstable")]
@@ -226,6 +242,8 @@ pub struct QuerierGetBuilder<'a, 'b, Handler> {
     pub(crate) attachment: Option<ZBytes>,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
 }
 
 #[zenoh_macros::internal_trait]
@@ -361,6 +379,8 @@ impl<'a, 'b> QuerierGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler: _,
         } = self;
         QuerierGetBuilder {
@@ -370,11 +390,25 @@ impl<'a, 'b> QuerierGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler,
         }
     }
 }
 impl<'b, Handler> QuerierGetBuilder<'_, 'b, Handler> {
+    /// Set the trace context propagated along with the query.
+    ///
+    /// When not set, the trace context of the current [`tracing::Span`] is used, if any.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn trace_context<TC: Into<Option<TraceContext>>>(self, trace_context: TC) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
+
     /// Set the query payload.
     #[inline]
     #[zenoh_macros::unstable]
@@ -433,10 +467,13 @@ where
                 self.querier.qos,
                 self.querier.destination,
                 self.querier.timeout,
+                self.querier.reply_timeout,
                 self.value,
                 self.attachment,
                 #[cfg(feature = "unstable")]
                 self.source_info,
+                #[cfg(feature = "unstable")]
+                self.trace_context,
                 callback,
             )
             .map(|_| receiver)
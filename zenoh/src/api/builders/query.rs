@@ -22,9 +22,9 @@ use zenoh_protocol::{core::CongestionControl, network::request::ext::QueryTarget
 use zenoh_result::ZResult;
 
 #[cfg(feature = "unstable")]
-use crate::api::query::ReplyKeyExpr;
+use crate::api::query::{ReplyKeyExpr, TaggedReply};
 #[cfg(feature = "unstable")]
-use crate::api::{sample::SourceInfo, selector::ZenohParameters};
+use crate::api::{sample::SourceInfo, selector::ZenohParameters, trace::TraceContext};
 use crate::{
     api::{
         builders::sample::{EncodingBuilderTrait, QoSBuilderTrait, SampleBuilderTrait},
@@ -70,11 +70,14 @@ pub struct SessionGetBuilder<'a, 'b, Handler> {
     pub(crate) qos: QoSBuilder,
     pub(crate) destination: Locality,
     pub(crate) timeout: Duration,
+    pub(crate) reply_timeout: Option<Duration>,
     pub(crate) handler: Handler,
     pub(crate) value: Option<(ZBytes, Encoding)>,
     pub(crate) attachment: Option<ZBytes>,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
 }
 
 #[zenoh_macros::internal_trait]
@@ -208,10 +211,13 @@ impl<'a, 'b> SessionGetBuilder<'a, 'b, DefaultHandler> {
             qos,
             destination,
             timeout,
+            reply_timeout,
             value,
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler: _,
         } = self;
         SessionGetBuilder {
@@ -222,10 +228,13 @@ impl<'a, 'b> SessionGetBuilder<'a, 'b, DefaultHandler> {
             qos,
             destination,
             timeout,
+            reply_timeout,
             value,
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler,
         }
     }
@@ -243,11 +252,26 @@ impl<Handler> SessionGetBuilder<'_, '_, Handler> {
     }
 
     /// Change the target of the query.
+    ///
+    /// With `QueryTarget::Specific` (unstable), the query is only delivered to the queryables of
+    /// the given zenoh node, and the replies are not consolidated unless a consolidation mode is set.
     #[inline]
     pub fn target(self, target: QueryTarget) -> Self {
         Self { target, ..self }
     }
 
+    /// Set the trace context propagated along with the query.
+    ///
+    /// When not set, the trace context of the current [`tracing::Span`] is used, if any.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn trace_context<TC: Into<Option<TraceContext>>>(self, trace_context: TC) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
+
     /// Change the consolidation mode of the query.
     #[inline]
     pub fn consolidation<QC: Into<QueryConsolidation>>(self, consolidation: QC) -> Self {
@@ -276,6 +300,37 @@ impl<Handler> SessionGetBuilder<'_, '_, Handler> {
         Self { timeout, ..self }
     }
 
+    /// Finalize the query once no reply was received for `reply_timeout`, since the query was sent
+    /// or since the last reply, although its [`timeout`](Self::timeout) is not elapsed.
+    ///
+    /// The [`timeout`](Self::timeout) is still sent along with the query, for the routing and the
+    /// queryables, while this one only applies to the reception of the replies. Unlike the
+    /// `timeout`, it doesn't produce a "Timeout" error reply.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let replies = session
+    ///     .get("key/expression")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .reply_timeout(Duration::from_millis(500))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn reply_timeout(self, reply_timeout: Duration) -> Self {
+        Self {
+            reply_timeout: Some(reply_timeout),
+            ..self
+        }
+    }
+
     ///
     ///
     /// By default, `get` guarantees that it will only receive replies whose key expressions intersect
@@ -332,10 +387,13 @@ where
                 self.qos.into(),
                 self.destination,
                 self.timeout,
+                self.reply_timeout,
                 self.value,
                 self.attachment,
                 #[cfg(feature = "unstable")]
                 self.source_info,
+                #[cfg(feature = "unstable")]
+                self.trace_context,
                 callback,
             )
             .map(|_| receiver)
@@ -354,3 +412,238 @@ where
         std::future::ready(self.wait())
     }
 }
+
+/// A builder for initializing a batch of queries, issued by [`get_many`](crate::Session::get_many).
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let replies = session
+///     .get_many(["key/expression/a", "key/expression/b"])
+///     .await
+///     .unwrap();
+/// while let Ok(reply) = replies.recv_async().await {
+///     println!("Received {:?} for selector {}", reply.reply().result(), reply.tag())
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+#[derive(Debug)]
+pub struct SessionGetManyBuilder<'a, 'b, Handler> {
+    pub(crate) session: &'a Session,
+    pub(crate) selectors: ZResult<Vec<Selector<'b>>>,
+    pub(crate) target: QueryTarget,
+    pub(crate) consolidation: QueryConsolidation,
+    pub(crate) qos: QoSBuilder,
+    pub(crate) destination: Locality,
+    pub(crate) timeout: Duration,
+    pub(crate) reply_timeout: Option<Duration>,
+    pub(crate) handler: Handler,
+    pub(crate) attachment: Option<ZBytes>,
+}
+
+#[zenoh_macros::unstable]
+#[zenoh_macros::internal_trait]
+impl<Handler> QoSBuilderTrait for SessionGetManyBuilder<'_, '_, Handler> {
+    fn congestion_control(self, congestion_control: CongestionControl) -> Self {
+        let qos = self.qos.congestion_control(congestion_control);
+        Self { qos, ..self }
+    }
+
+    fn priority(self, priority: Priority) -> Self {
+        let qos = self.qos.priority(priority);
+        Self { qos, ..self }
+    }
+
+    fn express(self, is_express: bool) -> Self {
+        let qos = self.qos.express(is_express);
+        Self { qos, ..self }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a, 'b> SessionGetManyBuilder<'a, 'b, DefaultHandler> {
+    /// Receive the replies for these queries with a callback.
+    #[inline]
+    pub fn callback<F>(self, callback: F) -> SessionGetManyBuilder<'a, 'b, Callback<TaggedReply>>
+    where
+        F: Fn(TaggedReply) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the replies for these queries with a mutable callback.
+    ///
+    /// Using this guarantees that your callback will never be called concurrently.
+    /// If your callback is also accepted by the [`callback`](crate::session::SessionGetManyBuilder::callback) method, we suggest you use it instead of `callback_mut`.
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> SessionGetManyBuilder<'a, 'b, Callback<TaggedReply>>
+    where
+        F: FnMut(TaggedReply) + Send + Sync + 'static,
+    {
+        self.callback(locked(callback))
+    }
+
+    /// Receive the replies for these queries with a [`Handler`](crate::handlers::IntoHandler).
+    ///
+    /// The handler is dropped once all the queries received their final reply.
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> SessionGetManyBuilder<'a, 'b, Handler>
+    where
+        Handler: IntoHandler<TaggedReply>,
+    {
+        let SessionGetManyBuilder {
+            session,
+            selectors,
+            target,
+            consolidation,
+            qos,
+            destination,
+            timeout,
+            reply_timeout,
+            attachment,
+            handler: _,
+        } = self;
+        SessionGetManyBuilder {
+            session,
+            selectors,
+            target,
+            consolidation,
+            qos,
+            destination,
+            timeout,
+            reply_timeout,
+            attachment,
+            handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> SessionGetManyBuilder<'_, '_, Handler> {
+    /// Change the target of the queries.
+    #[inline]
+    pub fn target(self, target: QueryTarget) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Change the consolidation mode of the queries, applied to the replies of each query.
+    #[inline]
+    pub fn consolidation<QC: Into<QueryConsolidation>>(self, consolidation: QC) -> Self {
+        Self {
+            consolidation: consolidation.into(),
+            ..self
+        }
+    }
+
+    /// Restrict the matching queryables that will receive the queries
+    /// to the ones that have the given [`Locality`](Locality).
+    #[inline]
+    pub fn allowed_destination(self, destination: Locality) -> Self {
+        Self {
+            destination,
+            ..self
+        }
+    }
+
+    /// Set the timeout of the queries.
+    #[inline]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Finalize each query once no reply was received for `reply_timeout`, although its
+    /// [`timeout`](Self::timeout) is not elapsed.
+    ///
+    /// See [`SessionGetBuilder::reply_timeout`].
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn reply_timeout(self, reply_timeout: Duration) -> Self {
+        Self {
+            reply_timeout: Some(reply_timeout),
+            ..self
+        }
+    }
+
+    /// Set the attachment sent along with each query.
+    #[inline]
+    pub fn attachment<T: Into<OptionZBytes>>(self, attachment: T) -> Self {
+        let attachment: OptionZBytes = attachment.into();
+        Self {
+            attachment: attachment.into(),
+            ..self
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for SessionGetManyBuilder<'_, '_, Handler>
+where
+    Handler: IntoHandler<TaggedReply> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<Handler::Handler>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for SessionGetManyBuilder<'_, '_, Handler>
+where
+    Handler: IntoHandler<TaggedReply> + Send,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let (callback, receiver) = self.handler.into_handler();
+        // All the selectors are validated before any query is issued
+        let selectors = self.selectors?;
+        let qos = self.qos.into();
+        // The requests are sent back to back, so that they are batched by the transports
+        for (
+            tag,
+            Selector {
+                key_expr,
+                parameters,
+            },
+        ) in selectors.into_iter().enumerate()
+        {
+            let callback = callback.clone();
+            self.session.0.query(
+                &key_expr,
+                &parameters,
+                self.target,
+                self.consolidation,
+                qos,
+                self.destination,
+                self.timeout,
+                self.reply_timeout,
+                None,
+                self.attachment.clone(),
+                SourceInfo::empty(),
+                TraceContext::current(),
+                Callback::new(Arc::new(move |reply| {
+                    callback.call(TaggedReply { tag, reply })
+                })),
+            )?;
+        }
+        Ok(receiver)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for SessionGetManyBuilder<'_, '_, Handler>
+where
+    Handler: IntoHandler<TaggedReply> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
@@ -47,6 +47,7 @@ pub struct QueryableBuilder<'a, 'b, Handler, const BACKGROUND: bool = false> {
     pub(crate) session: &'a Session,
     pub(crate) key_expr: ZResult<KeyExpr<'b>>,
     pub(crate) complete: bool,
+    pub(crate) load: u8,
     pub(crate) origin: Locality,
     pub(crate) handler: Handler,
 }
@@ -129,6 +130,7 @@ impl<'a, 'b> QueryableBuilder<'a, 'b, DefaultHandler> {
             session,
             key_expr,
             complete,
+            load,
             origin,
             handler: _,
         } = self;
@@ -136,6 +138,7 @@ impl<'a, 'b> QueryableBuilder<'a, 'b, DefaultHandler> {
             session,
             key_expr,
             complete,
+            load,
             origin,
             handler,
         }
@@ -167,6 +170,7 @@ impl<'a, 'b> QueryableBuilder<'a, 'b, Callback<Query>> {
             session: self.session,
             key_expr: self.key_expr,
             complete: self.complete,
+            load: self.load,
             origin: self.origin,
             handler: self.handler,
         }
@@ -181,6 +185,18 @@ impl<Handler, const BACKGROUND: bool> QueryableBuilder<'_, '_, Handler, BACKGROU
         self
     }
 
+    /// Change the initial load reported by the queryable, from 0 (unloaded) to 255.
+    ///
+    /// The routers configured with the `least_loaded` queries load balancing route the queries
+    /// to the nearest complete queryable with the lowest load,
+    /// see [`Queryable::set_load`](crate::query::Queryable::set_load).
+    #[inline]
+    #[zenoh_macros::unstable]
+    pub fn load(mut self, load: u8) -> Self {
+        self.load = load;
+        self
+    }
+
     ///
     ///
     /// Restrict the matching queries that will be receive by this [`Queryable`]
@@ -208,15 +224,22 @@ where
 {
     fn wait(self) -> <Self as Resolvable>::To {
         let session = self.session;
+        let key_expr = self.key_expr?;
         let (callback, receiver) = self.handler.into_handler();
         session
             .0
-            .declare_queryable_inner(&self.key_expr?, self.complete, self.origin, callback)
+            .declare_queryable_inner(&key_expr, self.complete, self.load, self.origin, callback)
             .map(|qable_state| Queryable {
                 inner: QueryableInner {
                     session: self.session.downgrade(),
                     id: qable_state.id,
                     undeclare_on_drop: true,
+                    #[cfg(feature = "unstable")]
+                    key_expr: key_expr.into_owned(),
+                    #[cfg(feature = "unstable")]
+                    origin: self.origin,
+                    #[cfg(feature = "unstable")]
+                    matching_listeners: Default::default(),
                 },
                 handler: receiver,
             })
@@ -245,6 +268,7 @@ impl Wait for QueryableBuilder<'_, '_, Callback<Query>, true> {
         self.session.0.declare_queryable_inner(
             &self.key_expr?,
             self.complete,
+            self.load,
             self.origin,
             self.handler,
         )?;
@@ -73,7 +73,10 @@ impl<'a, 'b> ReplyBuilder<'a, 'b, ReplyBuilderPut> {
         Self {
             query,
             key_expr: key_expr.try_into().map_err(Into::into),
-            qos: response::ext::QoSType::RESPONSE.into(),
+            qos: query
+                .inner
+                .response_qos(response::ext::QoSType::RESPONSE)
+                .into(),
             kind: ReplyBuilderPut {
                 payload: payload.into(),
                 encoding: Encoding::default(),
@@ -95,7 +98,10 @@ impl<'a, 'b> ReplyBuilder<'a, 'b, ReplyBuilderDelete> {
         Self {
             query,
             key_expr: key_expr.try_into().map_err(Into::into),
-            qos: response::ext::QoSType::RESPONSE.into(),
+            qos: query
+                .inner
+                .response_qos(response::ext::QoSType::RESPONSE)
+                .into(),
             kind: ReplyBuilderDelete,
             timestamp: None,
             #[cfg(feature = "unstable")]
@@ -267,7 +273,11 @@ impl Wait for ReplyErrBuilder<'_> {
                 ext_unknown: vec![],
                 payload: self.payload.into(),
             }),
-            ext_qos: response::ext::QoSType::RESPONSE,
+            ext_qos: self
+                .query
+                .inner
+                .response_qos(response::ext::QoSType::RESPONSE)
+                .into(),
             ext_tstamp: None,
             ext_respid: Some(response::ext::ResponderIdType {
                 zid: self.query.inner.zid,
@@ -31,7 +31,7 @@ use crate::pubsub::{
     PublicationBuilder, PublicationBuilderDelete, PublicationBuilderPut, Publisher,
 };
 #[cfg(feature = "unstable")]
-use crate::sample::SourceInfo;
+use crate::sample::{SignatureStatus, SourceInfo, TraceContext};
 pub trait QoSBuilderTrait {
     /// Change the `congestion_control` to apply when routing the data.
     fn congestion_control(self, congestion_control: CongestionControl) -> Self;
@@ -95,6 +95,14 @@ impl SampleBuilder<SampleBuilderPut> {
                 reliability: Reliability::DEFAULT,
                 #[cfg(feature = "unstable")]
                 source_info: SourceInfo::empty(),
+                #[cfg(feature = "unstable")]
+                trace_context: None,
+                #[cfg(feature = "unstable")]
+                signature: None,
+                #[cfg(feature = "unstable")]
+                signature_status: SignatureStatus::Unverified,
+                #[cfg(feature = "sample_metadata")]
+                reception: None,
                 attachment: None,
             },
             _t: PhantomData::<SampleBuilderPut>,
@@ -127,6 +135,14 @@ impl SampleBuilder<SampleBuilderDelete> {
                 reliability: Reliability::DEFAULT,
                 #[cfg(feature = "unstable")]
                 source_info: SourceInfo::empty(),
+                #[cfg(feature = "unstable")]
+                trace_context: None,
+                #[cfg(feature = "unstable")]
+                signature: None,
+                #[cfg(feature = "unstable")]
+                signature_status: SignatureStatus::Unverified,
+                #[cfg(feature = "sample_metadata")]
+                reception: None,
                 attachment: None,
             },
             _t: PhantomData::<SampleBuilderDelete>,
@@ -167,6 +183,18 @@ impl<T> SampleBuilder<T> {
             _t: PhantomData::<T>,
         }
     }
+
+    /// Sets the trace context propagated along with the [`Sample`]
+    #[zenoh_macros::unstable]
+    pub fn trace_context<U: Into<Option<TraceContext>>>(self, trace_context: U) -> Self {
+        Self {
+            sample: Sample {
+                trace_context: trace_context.into(),
+                ..self.sample
+            },
+            _t: PhantomData::<T>,
+        }
+    }
 }
 
 #[zenoh_macros::internal_trait]
@@ -307,6 +335,14 @@ impl From<&PublicationBuilder<&Publisher<'_>, PublicationBuilderPut>> for Sample
             reliability: builder.publisher.reliability,
             #[cfg(feature = "unstable")]
             source_info: builder.source_info.clone(),
+            #[cfg(feature = "unstable")]
+            trace_context: builder.trace_context,
+            #[cfg(feature = "unstable")]
+            signature: None,
+            #[cfg(feature = "unstable")]
+            signature_status: SignatureStatus::Unverified,
+            #[cfg(feature = "sample_metadata")]
+            reception: None,
             attachment: builder.attachment.clone(),
         }
     }
@@ -330,6 +366,14 @@ impl From<&PublicationBuilder<&Publisher<'_>, PublicationBuilderDelete>> for Sam
             reliability: builder.publisher.reliability,
             #[cfg(feature = "unstable")]
             source_info: builder.source_info.clone(),
+            #[cfg(feature = "unstable")]
+            trace_context: builder.trace_context,
+            #[cfg(feature = "unstable")]
+            signature: None,
+            #[cfg(feature = "unstable")]
+            signature_status: SignatureStatus::Unverified,
+            #[cfg(feature = "sample_metadata")]
+            reception: None,
             attachment: builder.attachment.clone(),
         }
     }
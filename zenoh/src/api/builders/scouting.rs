@@ -15,6 +15,7 @@
 use std::{
     future::{IntoFuture, Ready},
     sync::Arc,
+    time::Duration,
 };
 
 use zenoh_config::wrappers::Hello;
@@ -24,7 +25,7 @@ use zenoh_result::ZResult;
 
 use crate::api::{
     handlers::{locked, Callback, DefaultHandler, IntoHandler},
-    scouting::{Scout, _scout},
+    scouting::{_scout, HelloFilter, Scout},
 };
 
 /// A builder for initializing a [`Scout`].
@@ -48,6 +49,9 @@ use crate::api::{
 pub struct ScoutBuilder<Handler> {
     pub(crate) what: WhatAmIMatcher,
     pub(crate) config: ZResult<crate::config::Config>,
+    pub(crate) protocols: Vec<String>,
+    pub(crate) subnets: Vec<String>,
+    pub(crate) probe: Option<Duration>,
     pub(crate) handler: Handler,
 }
 
@@ -125,16 +129,107 @@ impl ScoutBuilder<DefaultHandler> {
         let ScoutBuilder {
             what,
             config,
+            protocols,
+            subnets,
+            probe,
             handler: _,
         } = self;
         ScoutBuilder {
             what,
             config,
+            protocols,
+            subnets,
+            probe,
             handler,
         }
     }
 }
 
+impl<Handler> ScoutBuilder<Handler> {
+    /// Only keep the locators using one of the given `protocols` (e.g. `tcp`, `quic`).
+    ///
+    /// The [`Hello`] messages without any remaining locator are not received.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::config::WhatAmI;
+    ///
+    /// let receiver = zenoh::scout(WhatAmI::Router, zenoh::Config::default())
+    ///     .protocols(["tcp", "quic"])
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn protocols<I, S>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.protocols = protocols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only keep the locators whose IP address belongs to one of the given `subnets`
+    /// (e.g. `192.168.1.0/24`).
+    ///
+    /// The [`Hello`] messages without any remaining locator are not received.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::config::WhatAmI;
+    ///
+    /// let receiver = zenoh::scout(WhatAmI::Router, zenoh::Config::default())
+    ///     .subnets(["192.168.1.0/24"])
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn subnets<I, S>(mut self, subnets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.subnets = subnets.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Probe the reachability of the locators before receiving the [`Hello`] messages, by
+    /// opening a link to them (e.g. a TCP connection or a QUIC handshake) within `timeout`.
+    ///
+    /// Only the reachable locators are kept, sorted by increasing latency, and the estimated
+    /// latency of the first one is given by [`Hello::latency`]. The [`Hello`] messages without
+    /// any reachable locator are not received.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// use zenoh::config::WhatAmI;
+    ///
+    /// let receiver = zenoh::scout(WhatAmI::Router, zenoh::Config::default())
+    ///     .probe(Duration::from_secs(1))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(hello) = receiver.recv_async().await {
+    ///     println!("{} ({:?})", hello, hello.latency());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn probe(mut self, timeout: Duration) -> Self {
+        self.probe = Some(timeout);
+        self
+    }
+}
+
 impl<Handler> Resolvable for ScoutBuilder<Handler>
 where
     Handler: IntoHandler<Hello> + Send,
@@ -149,8 +244,10 @@ where
     Handler::Handler: Send,
 {
     fn wait(self) -> <Self as Resolvable>::To {
+        let config = self.config?;
+        let filter = HelloFilter::new(self.protocols, &self.subnets, self.probe, &config)?;
         let (callback, receiver) = self.handler.into_handler();
-        _scout(self.what, self.config?, callback).map(|scout| Scout { scout, receiver })
+        _scout(self.what, config, filter, callback).map(|scout| Scout { scout, receiver })
     }
 }
 
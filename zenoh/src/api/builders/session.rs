@@ -13,9 +13,11 @@
 //
 
 use std::future::{IntoFuture, Ready};
-#[cfg(feature = "shared-memory")]
+#[cfg(any(feature = "shared-memory", feature = "unstable"))]
 use std::sync::Arc;
 
+#[cfg(feature = "unstable")]
+use uhlc::NTP64;
 use zenoh_core::{Resolvable, Wait};
 #[cfg(feature = "internal")]
 use zenoh_keyexpr::OwnedKeyExpr;
@@ -23,7 +25,11 @@ use zenoh_result::ZResult;
 #[cfg(feature = "shared-memory")]
 use zenoh_shm::api::client_storage::ShmClientStorage;
 
+#[cfg(feature = "unstable")]
+use crate::api::encryption::KeyProvider;
 use crate::api::session::Session;
+#[cfg(feature = "plugins")]
+use crate::api::{loader::StaticPlugin, plugins::ZenohPlugin};
 #[cfg(feature = "internal")]
 use crate::net::runtime::Runtime;
 
@@ -46,6 +52,12 @@ where
     config: TryIntoConfig,
     #[cfg(feature = "shared-memory")]
     shm_clients: Option<Arc<ShmClientStorage>>,
+    #[cfg(feature = "plugins")]
+    static_plugins: Vec<StaticPlugin>,
+    #[cfg(feature = "unstable")]
+    key_provider: Option<Arc<dyn KeyProvider>>,
+    #[cfg(feature = "unstable")]
+    clock: Option<fn() -> NTP64>,
 }
 
 impl<TryIntoConfig> OpenBuilder<TryIntoConfig>
@@ -58,8 +70,70 @@ where
             config,
             #[cfg(feature = "shared-memory")]
             shm_clients: None,
+            #[cfg(feature = "plugins")]
+            static_plugins: Vec::new(),
+            #[cfg(feature = "unstable")]
+            key_provider: None,
+            #[cfg(feature = "unstable")]
+            clock: None,
         }
     }
+
+    /// Encrypts end-to-end the payloads and attachments of the samples published by the session,
+    /// and decrypts those of the samples it receives, with the keys of the given [`KeyProvider`].
+    ///
+    /// The samples of the key expressions the provider has no key for are sent and received in
+    /// clear. The samples the session fails to decrypt are dropped.
+    #[zenoh_macros::unstable]
+    pub fn with_key_provider<P: KeyProvider + 'static>(mut self, key_provider: P) -> Self {
+        self.key_provider = Some(Arc::new(key_provider));
+        self
+    }
+
+    /// Use the given physical clock, e.g. synchronized with PTP or GPS, for the HLC timestamping
+    /// the samples instead of the system time, so that the timestamps reflect the synchronized
+    /// clock. The clock only needs to return the current time, the HLC keeps it monotonic.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::time::NTP64;
+    ///
+    /// fn ptp_clock() -> NTP64 {
+    ///     // Read the PTP hardware clock here
+    ///     let now = std::time::SystemTime::now();
+    ///     now.duration_since(std::time::UNIX_EPOCH).unwrap().into()
+    /// }
+    ///
+    /// let session = zenoh::open(zenoh::Config::default())
+    ///     .with_clock(ptp_clock)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn with_clock(mut self, clock: fn() -> NTP64) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+}
+
+#[cfg(feature = "plugins")]
+impl<TryIntoConfig> OpenBuilder<TryIntoConfig>
+where
+    TryIntoConfig: std::convert::TryInto<crate::config::Config> + Send + 'static,
+    <TryIntoConfig as std::convert::TryInto<crate::config::Config>>::Error: std::fmt::Debug,
+{
+    /// Registers the plugin `P` linked in the application binary, which is started with the
+    /// configuration `plugins/<P::DEFAULT_NAME>` without loading a dynamic library.
+    ///
+    /// This allows an application embedding a router to run plugins like the storage manager or
+    /// the REST API where loading dynamic libraries is not possible or desired.
+    pub fn with_static_plugin<P: ZenohPlugin + Send + Sync>(mut self) -> Self {
+        self.static_plugins.push(StaticPlugin::new::<P>());
+        self
+    }
 }
 
 #[cfg(feature = "shared-memory")]
@@ -96,6 +170,12 @@ where
             config,
             #[cfg(feature = "shared-memory")]
             self.shm_clients,
+            #[cfg(feature = "plugins")]
+            self.static_plugins,
+            #[cfg(feature = "unstable")]
+            self.key_provider,
+            #[cfg(feature = "unstable")]
+            self.clock,
         )
         .wait()
     }
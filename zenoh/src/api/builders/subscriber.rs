@@ -11,6 +11,8 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(feature = "unstable")]
+use std::time::Duration;
 use std::{
     future::{IntoFuture, Ready},
     sync::Arc,
@@ -19,6 +21,8 @@ use std::{
 use zenoh_core::{Resolvable, Wait};
 use zenoh_result::ZResult;
 
+#[cfg(feature = "unstable")]
+use crate::api::{key_tracker::KeyTracker, reorder::reordered, signature::SignaturePolicy};
 use crate::{
     api::{
         handlers::{locked, Callback, DefaultHandler, IntoHandler},
@@ -65,6 +69,27 @@ pub struct SubscriberBuilder<'a, 'b, Handler, const BACKGROUND: bool = false> {
     pub handler: Handler,
     #[cfg(not(feature = "internal"))]
     pub(crate) handler: Handler,
+
+    #[cfg(feature = "internal")]
+    #[cfg(feature = "unstable")]
+    pub signature_policy: Option<SignaturePolicy>,
+    #[cfg(not(feature = "internal"))]
+    #[cfg(feature = "unstable")]
+    pub(crate) signature_policy: Option<SignaturePolicy>,
+
+    #[cfg(feature = "internal")]
+    #[cfg(feature = "unstable")]
+    pub max_delay: Option<Duration>,
+    #[cfg(not(feature = "internal"))]
+    #[cfg(feature = "unstable")]
+    pub(crate) max_delay: Option<Duration>,
+
+    #[cfg(feature = "internal")]
+    #[cfg(feature = "unstable")]
+    pub key_tracking: Option<usize>,
+    #[cfg(not(feature = "internal"))]
+    #[cfg(feature = "unstable")]
+    pub(crate) key_tracking: Option<usize>,
 }
 
 impl<'a, 'b> SubscriberBuilder<'a, 'b, DefaultHandler> {
@@ -146,12 +171,24 @@ impl<'a, 'b> SubscriberBuilder<'a, 'b, DefaultHandler> {
             key_expr,
             origin,
             handler: _,
+            #[cfg(feature = "unstable")]
+            signature_policy,
+            #[cfg(feature = "unstable")]
+            max_delay,
+            #[cfg(feature = "unstable")]
+            key_tracking,
         } = self;
         SubscriberBuilder {
             session,
             key_expr,
             origin,
             handler,
+            #[cfg(feature = "unstable")]
+            signature_policy,
+            #[cfg(feature = "unstable")]
+            max_delay,
+            #[cfg(feature = "unstable")]
+            key_tracking,
         }
     }
 }
@@ -182,6 +219,12 @@ impl<'a, 'b> SubscriberBuilder<'a, 'b, Callback<Sample>> {
             key_expr: self.key_expr,
             origin: self.origin,
             handler: self.handler,
+            #[cfg(feature = "unstable")]
+            signature_policy: self.signature_policy,
+            #[cfg(feature = "unstable")]
+            max_delay: self.max_delay,
+            #[cfg(feature = "unstable")]
+            key_tracking: self.key_tracking,
         }
     }
 }
@@ -197,6 +240,60 @@ impl<Handler, const BACKGROUND: bool> SubscriberBuilder<'_, '_, Handler, BACKGRO
         self.origin = origin;
         self
     }
+
+    /// Verifies the signatures of the received samples with the given [`SignaturePolicy`].
+    ///
+    /// The result of the verification is given by [`Sample::signature_status`].
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn signature_policy(mut self, signature_policy: SignaturePolicy) -> Self {
+        self.signature_policy = Some(signature_policy);
+        self
+    }
+
+    /// Delivers the samples of each key from each source in the order they were published,
+    /// even when they were routed over different priorities or links.
+    ///
+    /// The order is given by the [`SourceInfo`](crate::sample::SourceInfo) of the samples,
+    /// the samples without source id or sequence number being delivered as received.
+    /// A sample received after a gap in the sequence numbers is held until the missing samples
+    /// are received, for at most `max_delay`, which bounds the latency added to the samples.
+    /// The missing samples are then skipped, and dropped if received afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let subscriber = session
+    ///     .declare_subscriber("key/expression")
+    ///     .ordered(Duration::from_millis(10))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn ordered(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Tracks the distinct keys of the received samples, e.g. to enumerate the keys which
+    /// actually exist under a wildcard key expression without querying a storage.
+    ///
+    /// At most `capacity` keys are tracked, the least recently received key being evicted to
+    /// make room for a new one. A key is no longer tracked once a delete is received for it.
+    /// The tracked keys are given by [`Subscriber::tracked_keys`]; they are not tracked for a
+    /// background subscriber.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn track_keys(mut self, capacity: usize) -> Self {
+        self.key_tracking = Some(capacity);
+        self
+    }
 }
 
 impl<Handler> Resolvable for SubscriberBuilder<'_, '_, Handler>
@@ -216,6 +313,22 @@ where
         let key_expr = self.key_expr?;
         let session = self.session;
         let (callback, receiver) = self.handler.into_handler();
+        #[cfg(feature = "unstable")]
+        let callback = reordered(callback, self.max_delay);
+        #[cfg(feature = "unstable")]
+        let callback = match self.signature_policy {
+            Some(signature_policy) => signature_policy.wrap(callback),
+            None => callback,
+        };
+        #[cfg(feature = "unstable")]
+        let key_tracker = self
+            .key_tracking
+            .map(|capacity| Arc::new(KeyTracker::new(capacity)));
+        #[cfg(feature = "unstable")]
+        let callback = match &key_tracker {
+            Some(key_tracker) => key_tracker.wrap(callback),
+            None => callback,
+        };
         session
             .0
             .declare_subscriber_inner(&key_expr, self.origin, callback)
@@ -225,6 +338,8 @@ where
                     id: sub_state.id,
                     key_expr: sub_state.key_expr.clone(),
                     kind: SubscriberKind::Subscriber,
+                    #[cfg(feature = "unstable")]
+                    key_tracker,
                     undeclare_on_drop: true,
                 },
                 handler: receiver,
@@ -251,9 +366,18 @@ impl Resolvable for SubscriberBuilder<'_, '_, Callback<Sample>, true> {
 
 impl Wait for SubscriberBuilder<'_, '_, Callback<Sample>, true> {
     fn wait(self) -> <Self as Resolvable>::To {
+        #[cfg(feature = "unstable")]
+        let callback = reordered(self.handler, self.max_delay);
+        #[cfg(feature = "unstable")]
+        let callback = match self.signature_policy {
+            Some(signature_policy) => signature_policy.wrap(callback),
+            None => callback,
+        };
+        #[cfg(not(feature = "unstable"))]
+        let callback = self.handler;
         self.session
             .0
-            .declare_subscriber_inner(&self.key_expr?, self.origin, self.handler)?;
+            .declare_subscriber_inner(&self.key_expr?, self.origin, callback)?;
         Ok(())
     }
 }
@@ -13,7 +13,14 @@
 //
 
 //! ZBytes primitives.
-use std::{borrow::Cow, fmt::Debug, mem, str::Utf8Error};
+use std::{
+    borrow::Cow,
+    fmt::Debug,
+    mem,
+    pin::Pin,
+    str::Utf8Error,
+    task::{Context, Poll},
+};
 
 use zenoh_buffers::{
     buffer::{Buffer, SplitBuffer},
@@ -132,6 +139,20 @@ impl ZBytes {
 
     /// Get a [`ZBytesReader`] implementing [`std::io::Read`] trait.
     ///
+    /// The reader also implements [`std::io::BufRead`], as well as [`tokio::io::AsyncRead`] and
+    /// [`tokio::io::AsyncBufRead`], so that the payload can be given to a parser without being
+    /// copied into a contiguous buffer first.
+    ///
+    /// ```rust
+    /// use std::io::BufRead;
+    ///
+    /// use zenoh::bytes::ZBytes;
+    ///
+    /// let payload = ZBytes::from("first\nsecond\n");
+    /// let lines: Vec<String> = payload.reader().lines().map(Result::unwrap).collect();
+    /// assert_eq!(lines, ["first", "second"]);
+    /// ```
+    ///
     /// See [`ZBytesWriter`] on how to chain the deserialization of different types from a single [`ZBytes`].
     pub fn reader(&self) -> ZBytesReader<'_> {
         ZBytesReader(self.0.reader())
@@ -253,12 +274,45 @@ impl std::io::Read for ZBytesReader<'_> {
     }
 }
 
+impl std::io::BufRead for ZBytesReader<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        std::io::BufRead::fill_buf(&mut self.0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        std::io::BufRead::consume(&mut self.0, amt)
+    }
+}
+
 impl std::io::Seek for ZBytesReader<'_> {
     fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
         std::io::Seek::seek(&mut self.0, pos)
     }
 }
 
+// The bytes are in memory, so reading them never blocks
+impl tokio::io::AsyncRead for ZBytesReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let n = std::io::Read::read(&mut self.get_mut().0, buf.initialize_unfilled())?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl tokio::io::AsyncBufRead for ZBytesReader<'_> {
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        Poll::Ready(std::io::BufRead::fill_buf(&mut self.get_mut().0))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        std::io::BufRead::consume(&mut self.get_mut().0, amt)
+    }
+}
+
 /// A writer that implements [`std::io::Write`] trait to serialize into a [`ZBytes`].
 #[derive(Debug)]
 pub struct ZBytesWriter {
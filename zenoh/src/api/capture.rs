@@ -0,0 +1,441 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Capture of the network messages exchanged by a node, and their replay into a session.
+//!
+//! A capture file starts with the `ZCAP` magic followed by the format version, then holds one
+//! block per message:
+//! - the length of the rest of the block (u32 LE);
+//! - the capture time in nanoseconds since the UNIX epoch (u64 LE);
+//! - the direction of the message (u8, 0 for ingress, 1 for egress);
+//! - the length (u8) and the bytes of the [`ZenohId`] of the remote node, and its [`WhatAmI`] (u8);
+//! - the length (u16 LE) and the bytes of the resolved key expression of the message, 0 if none;
+//! - the reliability of the message (u8);
+//! - the message, encoded as on the wire.
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    future::{Future, IntoFuture},
+    io::{BufReader, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use zenoh_buffers::{reader::HasReader, writer::HasWriter};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_config::{wrappers::ZenohId, WhatAmI};
+use zenoh_core::{zlock, zread, Resolvable, Wait};
+use zenoh_protocol::{
+    core::{Reliability, WireExpr, ZenohIdProto},
+    network::{Mapping, NetworkBody, NetworkMessage, Push},
+};
+use zenoh_result::{bail, zerror, ZResult};
+use zenoh_runtime::ZRuntime;
+
+use crate::{api::session::Session, net::primitives::Primitives};
+
+const MAGIC: &[u8; 4] = b"ZCAP";
+const VERSION: u8 = 1;
+
+/// The direction of a captured message.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureDirection {
+    /// The message was received from the remote node.
+    Ingress,
+    /// The message was sent to the remote node.
+    Egress,
+}
+
+impl fmt::Display for CaptureDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureDirection::Ingress => f.write_str("<-"),
+            CaptureDirection::Egress => f.write_str("->"),
+        }
+    }
+}
+
+/// A network message read from a capture file, with its dissection metadata.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone)]
+pub struct CapturedMessage {
+    timestamp: SystemTime,
+    direction: CaptureDirection,
+    zid: ZenohId,
+    whatami: WhatAmI,
+    key_expr: Option<String>,
+    msg: NetworkMessage,
+}
+
+#[zenoh_macros::unstable]
+impl CapturedMessage {
+    /// The time at which the message was captured.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Whether the message was received from or sent to the remote node.
+    pub fn direction(&self) -> CaptureDirection {
+        self.direction
+    }
+
+    /// The [`ZenohId`] of the remote node.
+    pub fn zid(&self) -> ZenohId {
+        self.zid
+    }
+
+    /// The kind of the remote node.
+    pub fn whatami(&self) -> WhatAmI {
+        self.whatami
+    }
+
+    /// The key expression of the message, resolved from the key expression declarations of the
+    /// remote node, if the message has one.
+    pub fn key_expr(&self) -> Option<&str> {
+        self.key_expr.as_deref()
+    }
+
+    /// The kind of the message: `push`, `request`, `response`, `response_final`, `interest`,
+    /// `declare` or `oam`.
+    pub fn kind(&self) -> &'static str {
+        match &self.msg.body {
+            NetworkBody::Push(_) => "push",
+            NetworkBody::Request(_) => "request",
+            NetworkBody::Response(_) => "response",
+            NetworkBody::ResponseFinal(_) => "response_final",
+            NetworkBody::Interest(_) => "interest",
+            NetworkBody::Declare(_) => "declare",
+            NetworkBody::OAM(_) => "oam",
+        }
+    }
+
+    /// The push to replay and its reliability, if the message is a push with a known key expression.
+    fn replayable(&self) -> Option<(Push, Reliability)> {
+        let NetworkBody::Push(push) = &self.msg.body else {
+            return None;
+        };
+        let mut push = push.clone();
+        match &self.key_expr {
+            Some(key_expr) => {
+                push.wire_expr = WireExpr {
+                    scope: 0,
+                    suffix: key_expr.clone().into(),
+                    mapping: Mapping::Sender,
+                }
+            }
+            None if push.wire_expr.scope == 0 => push.wire_expr.mapping = Mapping::Sender,
+            None => return None,
+        }
+        Some((push, self.msg.reliability))
+    }
+}
+
+impl fmt::Display for CapturedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        write!(
+            f,
+            "{}.{:09} {} {} ({}) {} {}",
+            timestamp.as_secs(),
+            timestamp.subsec_nanos(),
+            self.direction,
+            self.zid,
+            self.whatami,
+            self.kind(),
+            self.key_expr.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+/// A reader of the messages of a capture file.
+///
+/// # Examples
+/// ```no_run
+/// use zenoh::capture::CaptureReader;
+///
+/// for msg in CaptureReader::open("zenoh.zcap").unwrap() {
+///     println!("{}", msg.unwrap());
+/// }
+/// ```
+#[zenoh_macros::unstable]
+pub struct CaptureReader {
+    reader: BufReader<File>,
+}
+
+#[zenoh_macros::unstable]
+impl CaptureReader {
+    /// Open a capture file.
+    pub fn open<P: AsRef<Path>>(path: P) -> ZResult<CaptureReader> {
+        let path = path.as_ref();
+        let mut reader = BufReader::new(
+            File::open(path).map_err(|e| zerror!("Unable to open {}: {e}", path.display()))?,
+        );
+        let mut header = [0u8; 5];
+        reader
+            .read_exact(&mut header)
+            .map_err(|e| zerror!("Unable to read {}: {e}", path.display()))?;
+        if &header[..4] != MAGIC {
+            bail!("{} is not a zenoh capture file", path.display());
+        }
+        if header[4] != VERSION {
+            bail!(
+                "Unsupported version {} of capture file {}",
+                header[4],
+                path.display()
+            );
+        }
+        Ok(CaptureReader { reader })
+    }
+
+    fn read_block(&mut self) -> ZResult<Option<CapturedMessage>> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => bail!("Unable to read capture block: {e}"),
+        }
+        let mut block = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.reader
+            .read_exact(&mut block)
+            .map_err(|e| zerror!("Truncated capture block: {e}"))?;
+        decode(&block)
+            .map(Some)
+            .ok_or_else(|| zerror!("Malformed capture block").into())
+    }
+}
+
+impl Iterator for CaptureReader {
+    type Item = ZResult<CapturedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_block().transpose()
+    }
+}
+
+fn decode(block: &[u8]) -> Option<CapturedMessage> {
+    let mut block = block;
+    let mut take = |n: usize| -> Option<&[u8]> {
+        if block.len() < n {
+            return None;
+        }
+        let (head, tail) = block.split_at(n);
+        block = tail;
+        Some(head)
+    };
+    let nanos = u64::from_le_bytes(take(8)?.try_into().ok()?);
+    let direction = match take(1)?[0] {
+        0 => CaptureDirection::Ingress,
+        1 => CaptureDirection::Egress,
+        _ => return None,
+    };
+    let zid_len = take(1)?[0] as usize;
+    let zid = ZenohIdProto::try_from(take(zid_len)?).ok()?.into();
+    let whatami = WhatAmI::try_from(take(1)?[0]).ok()?;
+    let ke_len = u16::from_le_bytes(take(2)?.try_into().ok()?) as usize;
+    let key_expr = match ke_len {
+        0 => None,
+        n => Some(String::from_utf8(take(n)?.to_vec()).ok()?),
+    };
+    let reliability = match take(1)?[0] {
+        0 => Reliability::BestEffort,
+        _ => Reliability::Reliable,
+    };
+    let mut reader = block.reader();
+    let mut msg: NetworkMessage = Zenoh080::new().read(&mut reader).ok()?;
+    msg.reliability = reliability;
+    Some(CapturedMessage {
+        timestamp: UNIX_EPOCH + Duration::from_nanos(nanos),
+        direction,
+        zid,
+        whatami,
+        key_expr,
+        msg,
+    })
+}
+
+/// The writer of the capture file configured in `capture/file`, shared by the capture
+/// interceptors of all the transports of a runtime.
+pub(crate) struct CaptureWriter {
+    file: Mutex<File>,
+}
+
+impl CaptureWriter {
+    /// Open the capture file for appending, creating it if needed.
+    pub(crate) fn open(path: &str) -> ZResult<CaptureWriter> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| zerror!("Unable to open capture file {path}: {e}"))?;
+        if file.metadata()?.len() == 0 {
+            file.write_all(MAGIC)?;
+            file.write_all(&[VERSION])?;
+        }
+        Ok(CaptureWriter {
+            file: Mutex::new(file),
+        })
+    }
+
+    pub(crate) fn write(
+        &self,
+        direction: CaptureDirection,
+        zid: &ZenohIdProto,
+        whatami: WhatAmI,
+        key_expr: Option<&str>,
+        msg: &NetworkMessage,
+    ) {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        let key_expr = key_expr.unwrap_or_default().as_bytes();
+        let key_expr = &key_expr[..key_expr.len().min(u16::MAX as usize)];
+
+        let mut block = vec![0u8; 4];
+        block.extend_from_slice(&nanos.to_le_bytes());
+        block.push(direction as u8);
+        block.push(zid.size() as u8);
+        block.extend_from_slice(&zid.to_le_bytes()[..zid.size()]);
+        block.push(whatami as u8);
+        block.extend_from_slice(&(key_expr.len() as u16).to_le_bytes());
+        block.extend_from_slice(key_expr);
+        block.push(msg.reliability as u8);
+        let mut writer = block.writer();
+        if Zenoh080::new().write(&mut writer, msg).is_err() {
+            tracing::warn!("Unable to encode captured message {:?}", msg.body);
+            return;
+        }
+        let len = (block.len() - 4) as u32;
+        block[..4].copy_from_slice(&len.to_le_bytes());
+        if let Err(e) = zlock!(self.file).write_all(&block) {
+            tracing::warn!("Unable to write captured message: {e}");
+        }
+    }
+}
+
+/// A builder for replaying the pushes of a capture file, returned by [`Session::replay`].
+///
+/// The puts and deletes of the captured pushes are sent through the session on their resolved
+/// key expression, so that they reach the other sessions of the node and the remote nodes like
+/// if they were published by the session; they are not delivered to the subscribers of the
+/// replaying session itself. The other messages are not replayed.
+///
+/// Resolving the builder returns the number of replayed messages.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::capture::CaptureDirection;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let count = session
+///     .replay("zenoh.zcap")
+///     .direction(CaptureDirection::Ingress)
+///     .paced(true)
+///     .await
+///     .unwrap();
+/// println!("{count} messages replayed");
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct ReplayBuilder<'a> {
+    pub(crate) session: &'a Session,
+    pub(crate) path: PathBuf,
+    pub(crate) direction: CaptureDirection,
+    pub(crate) paced: bool,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> ReplayBuilder<'a> {
+    pub(crate) fn new(session: &'a Session, path: PathBuf) -> Self {
+        Self {
+            session,
+            path,
+            direction: CaptureDirection::Ingress,
+            paced: false,
+        }
+    }
+
+    /// Replay the messages captured in the given direction, [`CaptureDirection::Ingress`] by
+    /// default.
+    #[inline]
+    pub fn direction(mut self, direction: CaptureDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Whether to wait between the replayed messages as long as between their capture, or to
+    /// replay them as fast as possible (default).
+    #[inline]
+    pub fn paced(mut self, paced: bool) -> Self {
+        self.paced = paced;
+        self
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for ReplayBuilder<'_> {
+    type To = ZResult<usize>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for ReplayBuilder<'_> {
+    fn wait(self) -> Self::To {
+        ZRuntime::Application.block_in_place(self.into_future())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a> IntoFuture for ReplayBuilder<'a> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Pin<Box<dyn Future<Output = <Self as IntoFuture>::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let messages = CaptureReader::open(&self.path)?
+                .filter(|msg| {
+                    msg.as_ref()
+                        .map_or(true, |msg| msg.direction == self.direction)
+                })
+                .collect::<ZResult<Vec<_>>>()?;
+            let mut previous: Option<SystemTime> = None;
+            let mut count = 0;
+            for msg in messages {
+                let Some((push, reliability)) = msg.replayable() else {
+                    continue;
+                };
+                if self.paced {
+                    if let Some(delay) = previous.and_then(|p| msg.timestamp.duration_since(p).ok())
+                    {
+                        tokio::time::sleep(delay).await;
+                    }
+                    previous = Some(msg.timestamp);
+                }
+                zread!(self.session.0.state)
+                    .primitives()?
+                    .send_push(push, reliability);
+                count += 1;
+            }
+            Ok(count)
+        })
+    }
+}
@@ -0,0 +1,93 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Introspection of the hybrid logical clock (HLC) timestamping the samples.
+use std::time::Duration;
+
+use serde_json::json;
+use uhlc::Timestamp;
+
+use crate::net::{routing::dispatcher::timestamps::Drift, runtime::Runtime};
+
+/// A received timestamp exceeding the maximum delta accepted by the HLC.
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct TimestampDrift {
+    /// The received timestamp.
+    pub timestamp: Timestamp,
+    /// The time of the HLC when the timestamp was received.
+    pub local: Timestamp,
+    /// Whether the sample was dropped, as configured in `timestamping/drop_future_timestamp`,
+    /// or re-timestamped.
+    pub dropped: bool,
+}
+
+#[zenoh_macros::unstable]
+impl From<Drift> for TimestampDrift {
+    fn from(drift: Drift) -> Self {
+        TimestampDrift {
+            timestamp: drift.timestamp,
+            local: drift.local,
+            dropped: drift.dropped,
+        }
+    }
+}
+
+/// The state of the HLC of a session, returned by [`Session::clock`](crate::Session::clock).
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ClockInfo {
+    /// Whether the session timestamps the samples, as configured in `timestamping/enabled`.
+    pub enabled: bool,
+    /// The current time of the HLC, see [`Session::new_timestamp`](crate::Session::new_timestamp).
+    pub now: Timestamp,
+    /// The maximum delta accepted between the received timestamps and the HLC, if enabled.
+    pub max_delta: Option<Duration>,
+    /// The number of received timestamps which exceeded the maximum delta.
+    pub drifts: u64,
+    /// The last received timestamp which exceeded the maximum delta.
+    pub last_drift: Option<TimestampDrift>,
+}
+
+#[zenoh_macros::unstable]
+impl ClockInfo {
+    pub(crate) fn new(runtime: &Runtime, now: Timestamp) -> Self {
+        let hlc = runtime.hlc();
+        let router = runtime.router();
+        let tables = zread!(router.tables.tables);
+        ClockInfo {
+            enabled: hlc.is_some(),
+            now,
+            max_delta: hlc.map(|hlc| hlc.get_delta().to_duration()),
+            drifts: tables.timestamp_drifts.count(),
+            last_drift: tables.timestamp_drifts.last().map(Into::into),
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        json!({
+            "enabled": self.enabled,
+            "now": self.now.to_string(),
+            "max_delta_ms": self.max_delta.map(|d| d.as_millis() as u64),
+            "drifts": self.drifts,
+            "last_drift": self.last_drift.as_ref().map(|drift| json!({
+                "timestamp": drift.timestamp.to_string(),
+                "local": drift.local.to_string(),
+                "dropped": drift.dropped,
+            })),
+        })
+    }
+}
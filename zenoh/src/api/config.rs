@@ -38,10 +38,13 @@ impl Config {
     pub const DEFAULT_CONFIG_PATH_ENV: &'static str = "ZENOH_CONFIG";
 
     /// Load configuration from the file path specified in the [`Self::DEFAULT_CONFIG_PATH_ENV`]
-    /// environment variable.
+    /// environment variable, then apply the environment variable overlay
+    /// (see [`Config::apply_env_overlay`]).
     pub fn from_env() -> ZResult<Self> {
         let path = env::var(Self::DEFAULT_CONFIG_PATH_ENV)?;
-        Ok(Config(zenoh_config::Config::from_file(Path::new(&path))?))
+        let mut config = Config(zenoh_config::Config::from_file(Path::new(&path))?);
+        config.apply_env_overlay()?;
+        Ok(config)
     }
 
     /// Load configuration from the file at `path`.
@@ -49,6 +52,19 @@ impl Config {
         Ok(Config(zenoh_config::Config::from_file(path)?))
     }
 
+    /// Load configuration from the file at `path`, rejecting unknown fields and reporting the
+    /// path of every invalid field.
+    #[zenoh_macros::unstable]
+    pub fn from_file_strict<P: AsRef<Path>>(path: P) -> ZResult<Self> {
+        Ok(Config(zenoh_config::Config::from_file_strict(path)?))
+    }
+
+    /// Returns the JSON schema of the configuration.
+    #[zenoh_macros::unstable]
+    pub fn json_schema() -> serde_json::Value {
+        zenoh_config::Config::json_schema()
+    }
+
     /// Load configuration from the JSON5 string `input`.
     pub fn from_json5(input: &str) -> ZResult<Config> {
         match zenoh_config::Config::from_deserializer(&mut json5::Deserializer::from_str(input)?) {
@@ -60,6 +76,18 @@ impl Config {
         }
     }
 
+    /// Overrides configuration fields with the value of their `ZENOH_` environment variable.
+    ///
+    /// The variable name is the configuration path in uppercase, with `/` replaced by `_` and
+    /// prefixed by `ZENOH_`, e.g. `ZENOH_MODE` for `mode` or `ZENOH_CONNECT_ENDPOINTS` for
+    /// `connect/endpoints`. Values are parsed as JSON5; values which are not valid JSON5 are taken
+    /// as strings, or as comma-separated lists of strings.
+    ///
+    /// The overlay takes precedence over the values already present in the configuration.
+    pub fn apply_env_overlay(&mut self) -> ZResult<()> {
+        self.0.apply_env_overlay()
+    }
+
     /// Inserts configuration value `value` at `key`.
     pub fn insert_json5(&mut self, key: &str, value: &str) -> ZResult<()> {
         self.0
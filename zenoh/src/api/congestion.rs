@@ -0,0 +1,346 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    fmt,
+    future::{IntoFuture, Ready},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use zenoh_core::{Resolvable, Wait};
+use zenoh_result::ZResult;
+use zenoh_transport::common::congestion::{self, CongestionReport};
+
+use super::{handlers::Callback, session::UndeclarableSealed, Id};
+
+/// The congestion status of the publications of a [`Publisher`](crate::pubsub::Publisher).
+#[zenoh_macros::unstable]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CongestionStatus {
+    /// The publications are transmitted without waiting.
+    #[default]
+    Clear,
+    /// The publications wait for room in the transmission queues before being transmitted,
+    /// see [`CongestionControl::Block`](crate::qos::CongestionControl::Block).
+    Blocked,
+    /// The publications are dropped because the transmission queues are full,
+    /// see [`CongestionControl::Drop`](crate::qos::CongestionControl::Drop).
+    Dropping,
+}
+
+impl From<&CongestionReport> for CongestionStatus {
+    fn from(report: &CongestionReport) -> Self {
+        if report.dropped > 0 {
+            CongestionStatus::Dropping
+        } else if report.blocked > 0 {
+            CongestionStatus::Blocked
+        } else {
+            CongestionStatus::Clear
+        }
+    }
+}
+
+/// A change of the [`CongestionStatus`] of a publisher, with the occupancy of the transmission
+/// queue of the publication which caused it.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session.declare_publisher("key/expression").await.unwrap();
+/// let congestion_listener = publisher.congestion_events().await.unwrap();
+/// while let Ok(event) = congestion_listener.recv_async().await {
+///     println!(
+///         "{:?}: queue {}/{}, {} dropped",
+///         event.status(),
+///         event.occupied(),
+///         event.capacity(),
+///         event.dropped()
+///     );
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CongestionEvent {
+    pub(crate) status: CongestionStatus,
+    pub(crate) dropped: u64,
+    pub(crate) occupied: usize,
+    pub(crate) capacity: usize,
+}
+
+#[zenoh_macros::unstable]
+impl CongestionEvent {
+    /// The new congestion status of the publisher.
+    pub fn status(&self) -> CongestionStatus {
+        self.status
+    }
+
+    /// Returns `true` if the publications are blocked or dropped.
+    pub fn is_congested(&self) -> bool {
+        self.status != CongestionStatus::Clear
+    }
+
+    /// The number of messages of the publisher dropped because of congestion since it has
+    /// congestion listeners.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// The number of occupied batches of the most occupied transmission queue the publication
+    /// was pushed on, or 0 if it was not pushed on any.
+    pub fn occupied(&self) -> usize {
+        self.occupied
+    }
+
+    /// The number of batches of the transmission queue of [`CongestionEvent::occupied`], or 0
+    /// if the publication was not pushed on any.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[derive(Default)]
+struct CongestionState {
+    status: CongestionStatus,
+    dropped: u64,
+    next_id: Id,
+    listeners: Vec<(Id, Callback<CongestionEvent>)>,
+}
+
+/// The congestion listeners of a publisher, notified of the congestion met by its publications.
+#[derive(Default)]
+pub(crate) struct CongestionMonitor {
+    // The publications are only observed while there are listeners, or if they have a deadline
+    listening: AtomicBool,
+    expired: AtomicU64,
+    state: Mutex<CongestionState>,
+}
+
+impl CongestionMonitor {
+    pub(crate) fn declare(&self, callback: Callback<CongestionEvent>) -> Id {
+        let mut state = zlock!(self.state);
+        let id = state.next_id;
+        state.next_id += 1;
+        state.listeners.push((id, callback));
+        self.listening.store(true, Ordering::Relaxed);
+        id
+    }
+
+    pub(crate) fn undeclare(&self, id: Id) {
+        let mut state = zlock!(self.state);
+        state.listeners.retain(|(i, _)| *i != id);
+        self.listening
+            .store(!state.listeners.is_empty(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut state = zlock!(self.state);
+        state.listeners.clear();
+        self.listening.store(false, Ordering::Relaxed);
+    }
+
+    /// Runs the publication `f`, notifying the listeners if the congestion it met changes the
+    /// congestion status, and counting its messages dropped because their deadline expired if
+    /// it has one.
+    #[inline]
+    pub(crate) fn observe<R>(&self, has_deadline: bool, f: impl FnOnce() -> R) -> R {
+        let listening = self.listening.load(Ordering::Relaxed);
+        if !listening && !has_deadline {
+            return f();
+        }
+        let (res, report) = congestion::observe(f);
+        if report.expired != 0 {
+            self.expired
+                .fetch_add(report.expired as u64, Ordering::Relaxed);
+        }
+        if listening {
+            self.update(&report);
+        }
+        res
+    }
+
+    /// The number of messages dropped because their deadline expired.
+    pub(crate) fn expired(&self) -> u64 {
+        self.expired.load(Ordering::Relaxed)
+    }
+
+    fn update(&self, report: &CongestionReport) {
+        let status = CongestionStatus::from(report);
+        let mut state = zlock!(self.state);
+        state.dropped += report.dropped as u64;
+        if state.status == status {
+            return;
+        }
+        state.status = status;
+        let event = CongestionEvent {
+            status,
+            dropped: state.dropped,
+            occupied: report.queue.map_or(0, |q| q.occupied),
+            capacity: report.queue.map_or(0, |q| q.capacity),
+        };
+        let listeners: Vec<_> = state.listeners.iter().map(|(_, cb)| cb.clone()).collect();
+        // The callbacks may publish with the same publisher
+        drop(state);
+        for callback in listeners {
+            callback.call(event);
+        }
+    }
+}
+
+impl fmt::Debug for CongestionMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = zlock!(self.state);
+        f.debug_struct("CongestionMonitor")
+            .field("status", &state.status)
+            .field("dropped", &state.dropped)
+            .field("expired", &self.expired())
+            .field("listeners", &state.listeners.len())
+            .finish()
+    }
+}
+
+#[zenoh_macros::unstable]
+pub(crate) struct CongestionListenerInner {
+    pub(crate) monitor: Arc<CongestionMonitor>,
+    pub(crate) id: Id,
+    pub(crate) undeclare_on_drop: bool,
+}
+
+/// A listener that sends notifications when the [`CongestionStatus`] of a publisher changes.
+///
+/// Callback congestion listeners will run in background until the publisher is undeclared,
+/// or until they are undeclared.
+/// On the other hand, congestion listeners with a handler are automatically undeclared when
+/// dropped.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session.declare_publisher("key/expression").await.unwrap();
+/// let congestion_listener = publisher.congestion_events().await.unwrap();
+/// while let Ok(event) = congestion_listener.recv_async().await {
+///     if event.is_congested() {
+///         println!("Publisher is congested, slowing down.");
+///     } else {
+///         println!("Publisher is no longer congested.");
+///     }
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct CongestionListener<Handler> {
+    pub(crate) inner: CongestionListenerInner,
+    pub(crate) handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> CongestionListener<Handler> {
+    /// Undeclare the [`CongestionListener`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let congestion_listener = publisher.congestion_events().await.unwrap();
+    /// congestion_listener.undeclare().await.unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn undeclare(self) -> CongestionListenerUndeclaration<Handler>
+    where
+        Handler: Send,
+    {
+        self.undeclare_inner(())
+    }
+
+    fn undeclare_impl(&mut self) -> ZResult<()> {
+        // set the flag first to avoid double panic if this function panic
+        self.inner.undeclare_on_drop = false;
+        self.inner.monitor.undeclare(self.inner.id);
+        Ok(())
+    }
+
+    #[zenoh_macros::internal]
+    pub fn set_background(&mut self, background: bool) {
+        self.inner.undeclare_on_drop = !background;
+    }
+}
+
+impl<Handler> Drop for CongestionListener<Handler> {
+    fn drop(&mut self) {
+        if self.inner.undeclare_on_drop {
+            let _ = self.undeclare_impl();
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: Send> UndeclarableSealed<()> for CongestionListener<Handler> {
+    type Undeclaration = CongestionListenerUndeclaration<Handler>;
+
+    fn undeclare_inner(self, _: ()) -> Self::Undeclaration {
+        CongestionListenerUndeclaration(self)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::Deref for CongestionListener<Handler> {
+    type Target = Handler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::DerefMut for CongestionListener<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+pub struct CongestionListenerUndeclaration<Handler>(CongestionListener<Handler>);
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for CongestionListenerUndeclaration<Handler> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for CongestionListenerUndeclaration<Handler> {
+    fn wait(mut self) -> <Self as Resolvable>::To {
+        self.0.undeclare_impl()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for CongestionListenerUndeclaration<Handler> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
@@ -0,0 +1,174 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! End-to-end encryption of the payloads and attachments of the samples.
+use std::{fmt, sync::Arc};
+
+use zenoh_buffers::ZBuf;
+use zenoh_crypto::{hmac, XAeadCipher};
+use zenoh_keyexpr::{keyexpr, OwnedKeyExpr};
+use zenoh_result::ZResult;
+
+use crate::api::{bytes::ZBytes, sample::SampleKind};
+
+/// A key encrypting the samples of a key expression, with XChaCha20-Poly1305.
+///
+/// Creating a key sets up its cipher, shared by its clones: a [`KeyProvider`] should keep its
+/// keys rather than create them on each call.
+#[zenoh_macros::unstable]
+#[derive(Clone)]
+pub struct EncryptionKey(Arc<XAeadCipher>);
+
+#[zenoh_macros::unstable]
+impl From<[u8; XAeadCipher::KEY_SIZE]> for EncryptionKey {
+    fn from(key: [u8; XAeadCipher::KEY_SIZE]) -> Self {
+        Self(Arc::new(XAeadCipher::new(key)))
+    }
+}
+
+#[zenoh_macros::unstable]
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(***)")
+    }
+}
+
+/// A provider of the keys encrypting the samples published by a [`Session`](crate::Session)
+/// and decrypting the samples it receives, registered with
+/// [`OpenBuilder::with_key_provider`](crate::session::OpenBuilder::with_key_provider).
+///
+/// The payloads and attachments of the samples are encrypted end-to-end: the routers forward them
+/// without being able to read them. The key expressions and the other metadata are not encrypted,
+/// nor are the queries and their replies.
+/// A provider can be implemented on top of a key management service.
+#[zenoh_macros::unstable]
+pub trait KeyProvider: Send + Sync {
+    /// Returns the key of the given key expression, or `None` if its samples are not encrypted.
+    fn key(&self, key_expr: &keyexpr) -> Option<EncryptionKey>;
+}
+
+/// A [`KeyProvider`] deriving a key per key expression subtree from a secret.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::session::SubtreeKeyProvider;
+///
+/// let provider = SubtreeKeyProvider::new()
+///     .subtree("vehicle/telemetry/**", b"secret")
+///     .unwrap();
+/// let session = zenoh::open(zenoh::Config::default())
+///     .with_key_provider(provider)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Debug, Default)]
+pub struct SubtreeKeyProvider {
+    subtrees: Vec<(OwnedKeyExpr, EncryptionKey)>,
+}
+
+#[zenoh_macros::unstable]
+impl SubtreeKeyProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encrypts the samples of the key expressions included in `subtree` with a key derived from
+    /// `secret` and `subtree`. The first subtree including a key expression applies.
+    pub fn subtree<TryIntoKeyExpr>(
+        mut self,
+        subtree: TryIntoKeyExpr,
+        secret: &[u8],
+    ) -> ZResult<Self>
+    where
+        TryIntoKeyExpr: TryInto<OwnedKeyExpr>,
+        <TryIntoKeyExpr as TryInto<OwnedKeyExpr>>::Error: Into<zenoh_result::Error>,
+    {
+        let subtree = subtree.try_into().map_err(Into::into)?;
+        let key: [u8; XAeadCipher::KEY_SIZE] =
+            hmac::sign(secret, subtree.as_bytes())?
+                .try_into()
+                .map_err(|_| zenoh_result::zerror!("Invalid derived key length"))?;
+        self.subtrees.push((subtree, key.into()));
+        Ok(self)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl KeyProvider for SubtreeKeyProvider {
+    fn key(&self, key_expr: &keyexpr) -> Option<EncryptionKey> {
+        self.subtrees
+            .iter()
+            .find(|(subtree, _)| subtree.includes(key_expr))
+            .map(|(_, key)| key.clone())
+    }
+}
+
+fn encrypt(key: &EncryptionKey, key_expr: &keyexpr, bytes: &ZBytes) -> ZResult<ZBytes> {
+    let sealed = key.0.seal(&bytes.to_bytes(), key_expr.as_bytes())?;
+    Ok(sealed.into())
+}
+
+fn decrypt(key: &EncryptionKey, key_expr: &keyexpr, bytes: &ZBytes) -> ZResult<ZBytes> {
+    let opened = key.0.open(&bytes.to_bytes(), key_expr.as_bytes())?;
+    Ok(opened.into())
+}
+
+/// Encrypts the payload and attachment of a sample sent to remote nodes, authenticating its key
+/// expression, if the provider has a key for it.
+pub(crate) fn encrypt_sample(
+    key_provider: &dyn KeyProvider,
+    key_expr: &keyexpr,
+    kind: SampleKind,
+    payload: &ZBytes,
+    attachment: &Option<ZBytes>,
+) -> ZResult<(ZBytes, Option<ZBytes>)> {
+    let Some(key) = key_provider.key(key_expr) else {
+        return Ok((payload.clone(), attachment.clone()));
+    };
+    let payload = match kind {
+        SampleKind::Put => encrypt(&key, key_expr, payload)?,
+        SampleKind::Delete => payload.clone(),
+    };
+    let attachment = attachment
+        .as_ref()
+        .map(|attachment| encrypt(&key, key_expr, attachment))
+        .transpose()?;
+    Ok((payload, attachment))
+}
+
+/// Decrypts the payload and attachment of a sample received from a remote node, encrypted by
+/// [`encrypt_sample`] for the same key expression.
+pub(crate) fn decrypt_sample(
+    key_provider: &dyn KeyProvider,
+    key_expr: &keyexpr,
+    kind: SampleKind,
+    payload: ZBuf,
+    attachment: Option<ZBytes>,
+) -> ZResult<(ZBuf, Option<ZBytes>)> {
+    let Some(key) = key_provider.key(key_expr) else {
+        return Ok((payload, attachment));
+    };
+    let payload = match kind {
+        SampleKind::Put => decrypt(&key, key_expr, &payload.into())?.into(),
+        SampleKind::Delete => payload,
+    };
+    let attachment = attachment
+        .map(|attachment| decrypt(&key, key_expr, &attachment))
+        .transpose()?;
+    Ok((payload, attachment))
+}
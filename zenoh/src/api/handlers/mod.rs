@@ -15,10 +15,14 @@
 //! Callback handler trait.
 mod callback;
 mod fifo;
+#[cfg(feature = "unstable")]
+mod poll;
 mod ring;
 
 pub use callback::*;
 pub use fifo::*;
+#[cfg(feature = "unstable")]
+pub use poll::*;
 pub use ring::*;
 
 use crate::api::session::API_DATA_RECEPTION_CHANNEL_SIZE;
@@ -0,0 +1,187 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Callback handler trait.
+use std::{
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::api::{
+    handlers::{callback::Callback, IntoHandler},
+    session::API_DATA_RECEPTION_CHANNEL_SIZE,
+};
+
+/// A bounded channel polled without blocking, for consumers which can neither block nor run an
+/// async executor, e.g. real-time threads.
+///
+/// The channel is a single-producer single-consumer queue: [`PollChannelHandler::try_recv`] is
+/// wait-free and does not allocate, while the callbacks of zenoh, which may run concurrently,
+/// are serialized on the producer side. When the channel is full, the newest elements are
+/// dropped and counted in [`PollChannelHandler::dropped`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::handlers::PollChannel;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let subscriber = session
+///     .declare_subscriber("key/expression")
+///     .with(PollChannel::new(64))
+///     .await
+///     .unwrap();
+/// // In the real-time loop
+/// while let Some(sample) = subscriber.try_recv() {
+///     println!("Received: {}", sample.key_expr());
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct PollChannel {
+    capacity: usize,
+}
+
+#[zenoh_macros::unstable]
+impl PollChannel {
+    /// Initialize the channel with the capacity size, at least 1.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Default for PollChannel {
+    fn default() -> Self {
+        Self::new(*API_DATA_RECEPTION_CHANNEL_SIZE)
+    }
+}
+
+struct PollChannelInner<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// The number of elements pulled by the consumer, only written by the consumer.
+    head: AtomicUsize,
+    /// The number of elements pushed by the producer, only written by the producer.
+    tail: AtomicUsize,
+    dropped: AtomicUsize,
+    producer: Mutex<()>,
+}
+
+// The slots between `head` and `tail` are only accessed by the consumer, the others by the
+// producer, the ownership of a slot being transferred by the release of the index it is behind.
+unsafe impl<T: Send> Sync for PollChannelInner<T> {}
+
+impl<T> PollChannelInner<T> {
+    fn push(&self, t: T) {
+        let _guard = zlock!(self.producer);
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == self.slots.len() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        unsafe { (*self.slots[tail % self.slots.len()].get()).write(t) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Must only be called by a single consumer at a time.
+    unsafe fn pull(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let t = (*self.slots[head % self.slots.len()].get()).assume_init_read();
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(t)
+    }
+}
+
+impl<T> Drop for PollChannelInner<T> {
+    fn drop(&mut self) {
+        // Both the producer and the consumer are gone
+        while unsafe { self.pull() }.is_some() {}
+    }
+}
+
+/// The handler of a [`PollChannel`], the consumer of the channel.
+///
+/// The handler is [`Send`] but not [`Sync`], so that the channel is polled by one thread at a time.
+#[zenoh_macros::unstable]
+pub struct PollChannelHandler<T> {
+    channel: Arc<PollChannelInner<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+#[zenoh_macros::unstable]
+impl<T> PollChannelHandler<T> {
+    /// Try to receive from the channel, without blocking nor allocating.
+    ///
+    /// Return `None` if the channel is empty.
+    pub fn try_recv(&self) -> Option<T> {
+        // The handler is not `Sync` and not `Clone`, it is the only consumer
+        unsafe { self.channel.pull() }
+    }
+
+    /// The number of elements in the channel.
+    pub fn len(&self) -> usize {
+        let head = self.channel.head.load(Ordering::Relaxed);
+        self.channel.tail.load(Ordering::Acquire).wrapping_sub(head)
+    }
+
+    /// Whether the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The capacity of the channel.
+    pub fn capacity(&self) -> usize {
+        self.channel.slots.len()
+    }
+
+    /// The number of elements dropped because the channel was full.
+    pub fn dropped(&self) -> usize {
+        self.channel.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<T: Send + 'static> IntoHandler<T> for PollChannel {
+    type Handler = PollChannelHandler<T>;
+
+    fn into_handler(self) -> (Callback<T>, Self::Handler) {
+        let inner = Arc::new(PollChannelInner {
+            slots: (0..self.capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+            producer: Mutex::new(()),
+        });
+        let handler = PollChannelHandler {
+            channel: inner.clone(),
+            _not_sync: PhantomData,
+        };
+        (Callback::new(Arc::new(move |t| inner.push(t))), handler)
+    }
+}
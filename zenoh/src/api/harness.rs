@@ -0,0 +1,385 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! In-process networks of zenoh nodes with fault injection, for integration tests.
+use std::{
+    collections::HashMap,
+    future::{Future, IntoFuture},
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+};
+use tokio_util::sync::CancellationToken;
+use zenoh_config::WhatAmI;
+use zenoh_core::{zlock, Resolvable, Wait};
+use zenoh_result::{bail, ZResult};
+use zenoh_runtime::ZRuntime;
+
+use crate::api::{config::Config, session::Session};
+
+/// The size of the length prefix of the batches on streamed links.
+const LEN_SIZE: usize = 2;
+
+#[derive(Clone, Copy, Default)]
+struct Faults {
+    loss: f64,
+    delay: Duration,
+}
+
+/// The faults injected between two nodes, shared by the proxies connecting them.
+struct Link {
+    seed: u64,
+    partitioned: AtomicBool,
+    faults: Mutex<Faults>,
+    connections: Mutex<CancellationToken>,
+}
+
+impl Link {
+    fn new(seed: u64, token: &CancellationToken) -> Self {
+        Self {
+            seed,
+            partitioned: AtomicBool::new(false),
+            faults: Mutex::new(Faults::default()),
+            connections: Mutex::new(token.child_token()),
+        }
+    }
+
+    fn is_partitioned(&self) -> bool {
+        self.partitioned.load(Ordering::Acquire)
+    }
+
+    fn partition(&self, token: &CancellationToken) {
+        self.partitioned.store(true, Ordering::Release);
+        let connections = std::mem::replace(&mut *zlock!(self.connections), token.child_token());
+        connections.cancel();
+    }
+
+    fn faults(&self) -> Faults {
+        *zlock!(self.faults)
+    }
+}
+
+/// An in-process network of zenoh nodes, for integration tests.
+///
+/// Every node is a [`Session`] opened with the given name, and every connection between two
+/// nodes goes through a proxy owned by the network. The proxies forward the batches of the
+/// transport one by one, which allows to:
+/// - [`partition`](Network::partition) two nodes, closing their connections and refusing new
+///   ones until they are [`heal`](Network::heal)ed;
+/// - drop a fraction of the batches exchanged by two nodes with [`set_loss`](Network::set_loss),
+///   the dropped batches being drawn from a random generator seeded by the network seed;
+/// - delay the batches exchanged by two nodes with [`set_delay`](Network::set_delay).
+///
+/// The nodes listen on the loopback interface and have multicast and gossip scouting disabled,
+/// so they only discover each other through the connections of the network. The nodes run on
+/// the zenoh runtimes and therefore on the wall clock: delays and leases are real durations.
+///
+/// Dropping the network closes all the connections between its nodes.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::test::Network;
+///
+/// let network = Network::new(0);
+/// let router = network.router("router").await.unwrap();
+/// let client = network.client("client").connect("router").await.unwrap();
+/// network.set_loss("client", "router", 0.1);
+/// network.partition("client", "router");
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct Network {
+    seed: u64,
+    token: CancellationToken,
+    nodes: Mutex<HashMap<String, Option<SocketAddr>>>,
+    links: Mutex<HashMap<(String, String), Arc<Link>>>,
+}
+
+#[zenoh_macros::unstable]
+impl Network {
+    /// Create an empty network whose losses are drawn from random generators seeded by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            token: CancellationToken::new(),
+            nodes: Mutex::new(HashMap::new()),
+            links: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Open a node of the given mode named `name`.
+    pub fn node(&self, name: &str, mode: WhatAmI) -> NodeBuilder<'_> {
+        NodeBuilder {
+            network: self,
+            name: name.to_string(),
+            mode,
+            connect: vec![],
+            config: Config::default(),
+        }
+    }
+
+    /// Open a router named `name`.
+    pub fn router(&self, name: &str) -> NodeBuilder<'_> {
+        self.node(name, WhatAmI::Router)
+    }
+
+    /// Open a peer named `name`.
+    pub fn peer(&self, name: &str) -> NodeBuilder<'_> {
+        self.node(name, WhatAmI::Peer)
+    }
+
+    /// Open a client named `name`.
+    pub fn client(&self, name: &str) -> NodeBuilder<'_> {
+        self.node(name, WhatAmI::Client)
+    }
+
+    /// Close the connections between the nodes `a` and `b` and refuse the new ones.
+    pub fn partition(&self, a: &str, b: &str) {
+        self.link(a, b).partition(&self.token);
+    }
+
+    /// Accept again the connections between the nodes `a` and `b`.
+    ///
+    /// The nodes reconnect according to their `connect/retry` configuration.
+    pub fn heal(&self, a: &str, b: &str) {
+        self.link(a, b).partitioned.store(false, Ordering::Release);
+    }
+
+    /// Drop the given fraction, between 0 and 1, of the batches exchanged by the nodes `a` and `b`.
+    pub fn set_loss(&self, a: &str, b: &str, loss: f64) {
+        zlock!(self.link(a, b).faults).loss = loss.clamp(0.0, 1.0);
+    }
+
+    /// Delay each batch exchanged by the nodes `a` and `b`.
+    pub fn set_delay(&self, a: &str, b: &str, delay: Duration) {
+        zlock!(self.link(a, b).faults).delay = delay;
+    }
+
+    fn link(&self, a: &str, b: &str) -> Arc<Link> {
+        let key = if a <= b {
+            (a.to_string(), b.to_string())
+        } else {
+            (b.to_string(), a.to_string())
+        };
+        // FNV-1a, so that the seeds of the links do not depend on the process
+        let seed = format!("{}/{}", key.0, key.1)
+            .bytes()
+            .fold(0xcbf29ce484222325u64 ^ self.seed, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x100000001b3)
+            });
+        zlock!(self.links)
+            .entry(key)
+            .or_insert_with(|| Arc::new(Link::new(seed, &self.token)))
+            .clone()
+    }
+
+    /// Start a proxy forwarding the connections of `from` to the listener of `to`.
+    fn proxy(&self, from: &str, to: &str) -> ZResult<SocketAddr> {
+        let target = match zlock!(self.nodes).get(to) {
+            Some(Some(target)) => *target,
+            Some(None) => bail!("Unable to connect to the client '{to}'"),
+            None => {
+                bail!("Unknown node '{to}': the nodes must be opened before being connected to")
+            }
+        };
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let link = self.link(from, to);
+        let token = self.token.clone();
+        ZRuntime::Net.spawn(async move {
+            let listener = match TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    tracing::error!("Unable to start the test proxy {addr}: {e}");
+                    return;
+                }
+            };
+            loop {
+                let inbound = tokio::select! {
+                    _ = token.cancelled() => return,
+                    res = listener.accept() => match res {
+                        Ok((inbound, _)) => inbound,
+                        Err(_) => continue,
+                    },
+                };
+                if link.is_partitioned() {
+                    continue;
+                }
+                let connections = zlock!(link.connections).clone();
+                let link = link.clone();
+                ZRuntime::Net.spawn(async move {
+                    tokio::select! {
+                        _ = connections.cancelled() => {},
+                        _ = forward(inbound, target, &link) => {},
+                    }
+                });
+            }
+        });
+        Ok(addr)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Default for Network {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Drop for Network {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+async fn forward(inbound: TcpStream, target: SocketAddr, link: &Link) {
+    let Ok(outbound) = TcpStream::connect(target).await else {
+        return;
+    };
+    let _ = inbound.set_nodelay(true);
+    let _ = outbound.set_nodelay(true);
+    let (inbound_rx, inbound_tx) = inbound.into_split();
+    let (outbound_rx, outbound_tx) = outbound.into_split();
+    tokio::select! {
+        _ = pump(inbound_rx, outbound_tx, link, StdRng::seed_from_u64(link.seed)) => {},
+        _ = pump(outbound_rx, inbound_tx, link, StdRng::seed_from_u64(!link.seed)) => {},
+    }
+}
+
+async fn pump(
+    mut rx: OwnedReadHalf,
+    mut tx: OwnedWriteHalf,
+    link: &Link,
+    mut rng: StdRng,
+) -> std::io::Result<()> {
+    let mut batch = vec![0u8; LEN_SIZE + u16::MAX as usize];
+    loop {
+        rx.read_exact(&mut batch[..LEN_SIZE]).await?;
+        let len = LEN_SIZE + u16::from_le_bytes([batch[0], batch[1]]) as usize;
+        rx.read_exact(&mut batch[LEN_SIZE..len]).await?;
+
+        let faults = link.faults();
+        if rng.gen_bool(faults.loss) {
+            continue;
+        }
+        if !faults.delay.is_zero() {
+            tokio::time::sleep(faults.delay).await;
+        }
+        tx.write_all(&batch[..len]).await?;
+    }
+}
+
+/// A builder for opening a node of a [`Network`], returned by [`Network::node`].
+///
+/// The configuration of the node is completed with its mode, its listener on the loopback
+/// interface, its connections through the network and a connect retry period of 100 ms.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct NodeBuilder<'a> {
+    network: &'a Network,
+    name: String,
+    mode: WhatAmI,
+    connect: Vec<String>,
+    config: Config,
+}
+
+#[zenoh_macros::unstable]
+impl NodeBuilder<'_> {
+    /// Connect the node to the node named `name`, which must have been opened before.
+    #[inline]
+    pub fn connect(mut self, name: &str) -> Self {
+        self.connect.push(name.to_string());
+        self
+    }
+
+    /// The configuration of the node, [`Config::default`] by default.
+    #[inline]
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for NodeBuilder<'_> {
+    type To = ZResult<Session>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for NodeBuilder<'_> {
+    fn wait(self) -> Self::To {
+        ZRuntime::Application.block_in_place(self.into_future())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a> IntoFuture for NodeBuilder<'a> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Pin<Box<dyn Future<Output = <Self as IntoFuture>::Output> + Send + 'a>>;
+
+    fn into_future(mut self) -> Self::IntoFuture {
+        Box::pin(async move {
+            if zlock!(self.network.nodes).contains_key(&self.name) {
+                bail!("A node named '{}' already exists", self.name);
+            }
+            let listen = match self.mode {
+                WhatAmI::Client => None,
+                _ => Some(std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?),
+            };
+            let mut connect = Vec::with_capacity(self.connect.len());
+            for to in &self.connect {
+                connect.push(format!("tcp/{}", self.network.proxy(&self.name, to)?));
+            }
+
+            let config = &mut self.config;
+            config.insert_json5("mode", &format!("\"{}\"", self.mode))?;
+            config.insert_json5("scouting/multicast/enabled", "false")?;
+            config.insert_json5("scouting/gossip/enabled", "false")?;
+            config.insert_json5(
+                "listen/endpoints",
+                &serde_json::json!(listen
+                    .iter()
+                    .map(|a| format!("tcp/{a}"))
+                    .collect::<Vec<_>>())
+                .to_string(),
+            )?;
+            config.insert_json5("connect/endpoints", &serde_json::json!(connect).to_string())?;
+            config.insert_json5(
+                "connect/retry",
+                "{ period_init_ms: 100, period_max_ms: 100, period_increase_factor: 1 }",
+            )?;
+
+            let session = crate::open(self.config).await?;
+            zlock!(self.network.nodes).insert(self.name, listen);
+            Ok(session)
+        })
+    }
+}
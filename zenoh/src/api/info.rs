@@ -13,10 +13,60 @@
 //
 
 //! Tools to access information about the current zenoh [`Session`](crate::Session).
+#[cfg(feature = "unstable")]
+use zenoh_config::{wrappers::ZenohId, Locator, WhatAmI};
+
+#[cfg(feature = "unstable")]
+use crate::api::builders::info::{CloseTransportBuilder, TransportsBuilder};
 use crate::{
     api::builders::info::{PeersZenohIdBuilder, RoutersZenohIdBuilder, ZenohIdBuilder},
     net::runtime::Runtime,
 };
+
+/// A unicast transport session established with a remote zenoh node, returned by
+/// [`SessionInfo::transports()`].
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transport {
+    /// The [`ZenohId`] of the remote node.
+    pub zid: ZenohId,
+    /// The [`WhatAmI`] of the remote node.
+    pub whatami: WhatAmI,
+    /// The links of the transport.
+    pub links: Vec<Link>,
+}
+
+/// A link of a [`Transport`].
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The [`Locator`] of the local end of the link.
+    pub src: Locator,
+    /// The [`Locator`] of the remote end of the link.
+    pub dst: Locator,
+    /// The network interfaces of the local end of the link.
+    pub interfaces: Vec<String>,
+    /// The maximum size of the batches sent on the link.
+    pub mtu: u16,
+    /// Whether the link is stream-oriented, e.g. TCP.
+    pub is_streamed: bool,
+}
+
+#[zenoh_macros::unstable]
+impl From<zenoh_link::Link> for Link {
+    fn from(link: zenoh_link::Link) -> Self {
+        Link {
+            src: link.src,
+            dst: link.dst,
+            interfaces: link.interfaces,
+            mtu: link.mtu,
+            is_streamed: link.is_streamed,
+        }
+    }
+}
+
 /// Struct returned by [`Session::info()`](crate::Session::info) which allows
 /// to access information about the current zenoh [`Session`](crate::Session).
 ///
@@ -82,4 +132,43 @@ impl SessionInfo {
     pub fn peers_zid(&self) -> PeersZenohIdBuilder<'_> {
         PeersZenohIdBuilder::new(&self.runtime)
     }
+
+    /// Return the unicast transport sessions established with the remote zenoh nodes, along
+    /// with their links.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// for transport in session.info().transports().await {
+    ///     println!("{} ({}): {:?}", transport.zid, transport.whatami, transport.links);
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn transports(&self) -> TransportsBuilder<'_> {
+        TransportsBuilder::new(&self.runtime)
+    }
+
+    /// Forcibly close the unicast transport session established with the remote zenoh node
+    /// `zid`, e.g. to evict a misbehaving peer. The node may connect again afterwards.
+    ///
+    /// Resolves into an error if no transport is established with `zid`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let zid = "1".parse().unwrap();
+    /// session.info().close_transport(zid).await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn close_transport(&self, zid: ZenohId) -> CloseTransportBuilder<'_> {
+        CloseTransportBuilder::new(&self.runtime, zid)
+    }
 }
@@ -0,0 +1,113 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The distinct keys of the samples received by a subscriber.
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use zenoh_keyexpr::OwnedKeyExpr;
+
+use crate::api::{
+    handlers::Callback,
+    key_expr::KeyExpr,
+    sample::{Sample, SampleKind},
+};
+
+#[derive(Debug, Default)]
+struct KeyTrackerState {
+    /// The generation at which each key was last received.
+    keys: HashMap<OwnedKeyExpr, u64>,
+    /// The keys indexed by the generation at which they were last received.
+    lru: BTreeMap<u64, OwnedKeyExpr>,
+    next_generation: u64,
+}
+
+/// The most recently received keys of a subscriber, see
+/// [`SubscriberBuilder::track_keys`](crate::pubsub::SubscriberBuilder::track_keys).
+#[derive(Debug)]
+pub(crate) struct KeyTracker {
+    capacity: usize,
+    state: Mutex<KeyTrackerState>,
+}
+
+impl KeyTracker {
+    pub(crate) fn new(capacity: usize) -> Self {
+        KeyTracker {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    fn insert(&self, key_expr: &KeyExpr) {
+        let mut guard = zlock!(self.state);
+        let state = &mut *guard;
+        let generation = state.next_generation;
+        state.next_generation += 1;
+        match state.keys.get_mut(key_expr.as_keyexpr()) {
+            Some(g) => {
+                let key = state.lru.remove(g).unwrap();
+                *g = generation;
+                state.lru.insert(generation, key);
+            }
+            None => {
+                let key = key_expr.as_keyexpr().to_owned();
+                state.keys.insert(key.clone(), generation);
+                state.lru.insert(generation, key);
+                // The least recently received key is evicted
+                if state.keys.len() > self.capacity {
+                    if let Some((_, key)) = state.lru.pop_first() {
+                        state.keys.remove(&key);
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove(&self, key_expr: &KeyExpr) {
+        let mut guard = zlock!(self.state);
+        let state = &mut *guard;
+        if let Some(generation) = state.keys.remove(key_expr.as_keyexpr()) {
+            state.lru.remove(&generation);
+        }
+    }
+
+    /// The tracked keys, from the most to the least recently received.
+    pub(crate) fn keys(&self) -> Vec<KeyExpr<'static>> {
+        zlock!(self.state)
+            .lru
+            .values()
+            .rev()
+            .map(|key| key.clone().into())
+            .collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        zlock!(self.state).keys.len()
+    }
+
+    /// Wraps `callback` so that the keys of the samples are tracked, a deleted key being no
+    /// longer tracked.
+    pub(crate) fn wrap(self: &Arc<Self>, callback: Callback<Sample>) -> Callback<Sample> {
+        let tracker = self.clone();
+        Callback::new(Arc::new(move |sample: Sample| {
+            match sample.kind() {
+                SampleKind::Put => tracker.insert(sample.key_expr()),
+                SampleKind::Delete => tracker.remove(sample.key_expr()),
+            }
+            callback.call(sample);
+        }))
+    }
+}
@@ -13,22 +13,25 @@
 //
 
 use std::{
+    collections::HashMap,
     convert::TryInto,
     future::{IntoFuture, Ready},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
+use tokio_util::sync::{CancellationToken, DropGuard};
 use tracing::error;
 use zenoh_config::unwrap_or_default;
 use zenoh_core::{Resolvable, Resolve, Result as ZResult, Wait};
+use zenoh_runtime::ZRuntime;
 
 use crate::{
     api::{
         handlers::{locked, DefaultHandler, IntoHandler},
         key_expr::KeyExpr,
         query::Reply,
-        sample::{Locality, Sample},
+        sample::{Locality, Sample, SampleKind},
         session::{Session, UndeclarableSealed, WeakSession},
         subscriber::{Subscriber, SubscriberInner},
         Id,
@@ -166,6 +169,7 @@ impl<'a> Liveliness<'a> {
             key_expr: TryIntoKeyExpr::try_into(key_expr).map_err(Into::into),
             handler: DefaultHandler::default(),
             history: false,
+            debounce: None,
         }
     }
 
@@ -403,6 +407,7 @@ pub struct LivelinessSubscriberBuilder<'a, 'b, Handler, const BACKGROUND: bool =
     pub key_expr: ZResult<KeyExpr<'b>>,
     pub handler: Handler,
     pub history: bool,
+    pub debounce: Option<Duration>,
 }
 
 impl<'a, 'b> LivelinessSubscriberBuilder<'a, 'b, DefaultHandler> {
@@ -490,12 +495,14 @@ impl<'a, 'b> LivelinessSubscriberBuilder<'a, 'b, DefaultHandler> {
             key_expr,
             handler: _,
             history,
+            debounce,
         } = self;
         LivelinessSubscriberBuilder {
             session,
             key_expr,
             handler,
             history,
+            debounce,
         }
     }
 }
@@ -527,6 +534,7 @@ impl<'a, 'b> LivelinessSubscriberBuilder<'a, 'b, Callback<Sample>> {
             key_expr: self.key_expr,
             handler: self.handler,
             history: self.history,
+            debounce: self.debounce,
         }
     }
 }
@@ -537,6 +545,118 @@ impl<Handler, const BACKGROUND: bool> LivelinessSubscriberBuilder<'_, '_, Handle
         self.history = history;
         self
     }
+
+    /// Coalesce the rapid changes of the liveliness tokens.
+    ///
+    /// The first change of a token is delivered immediately. Its subsequent changes are held
+    /// until it did not change for the `debounce` duration, then only its last change is
+    /// delivered, if it differs from the previously delivered one. Rapid drop/re-declare cycles
+    /// of a token (process restarts, link flaps, ...) are thus delivered as a single DOWN/UP pair.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let subscriber = session
+    ///     .liveliness()
+    ///     .declare_subscriber("key/expression")
+    ///     .debounce(Duration::from_secs(1))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = Some(debounce);
+        self
+    }
+}
+
+/// The changes of a liveliness token held by a debounced subscriber.
+struct DebounceWindow {
+    deadline: tokio::time::Instant,
+    delivered: SampleKind,
+    pending: Option<Sample>,
+}
+
+/// The state of a debounced subscriber, shared with its debounce timers.
+///
+/// It is dropped with the subscriber callback, i.e. when the subscriber is undeclared or its
+/// session is closed, which cancels the pending debounce timers.
+struct Debouncer {
+    windows: Mutex<HashMap<String, DebounceWindow>>,
+    callback: Callback<Sample>,
+    token: CancellationToken,
+    _cancel: DropGuard,
+}
+
+/// Wraps `callback` so that the rapid changes of the liveliness tokens are coalesced,
+/// see [`LivelinessSubscriberBuilder::debounce`].
+fn debounced(callback: Callback<Sample>, debounce: Option<Duration>) -> Callback<Sample> {
+    let Some(debounce) = debounce else {
+        return callback;
+    };
+    let token = CancellationToken::new();
+    let debouncer = Arc::new(Debouncer {
+        windows: Mutex::default(),
+        callback,
+        token: token.clone(),
+        _cancel: token.drop_guard(),
+    });
+    Callback::new(Arc::new(move |sample: Sample| {
+        let key = sample.key_expr().as_str().to_string();
+        let deadline = tokio::time::Instant::now() + debounce;
+        // Samples are delivered while holding the lock so that they are delivered in order
+        let mut guard = zlock!(debouncer.windows);
+        if let Some(window) = guard.get_mut(&key) {
+            window.deadline = deadline;
+            window.pending = Some(sample);
+            return;
+        }
+        guard.insert(
+            key.clone(),
+            DebounceWindow {
+                deadline,
+                delivered: sample.kind(),
+                pending: None,
+            },
+        );
+        debouncer.callback.call(sample);
+        drop(guard);
+
+        // The timer only holds a weak reference so that it does not keep the debouncer alive
+        let (weak, token) = (Arc::downgrade(&debouncer), debouncer.token.clone());
+        ZRuntime::Net.spawn(async move {
+            let mut deadline = deadline;
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(deadline) => {}
+                    _ = token.cancelled() => return,
+                }
+                let Some(debouncer) = weak.upgrade() else {
+                    return;
+                };
+                let mut guard = zlock!(debouncer.windows);
+                let Some(window) = guard.get(&key) else {
+                    return;
+                };
+                if window.deadline > deadline {
+                    deadline = window.deadline;
+                    continue;
+                }
+                if let Some(window) = guard.remove(&key) {
+                    if let Some(sample) = window.pending.filter(|s| s.kind() != window.delivered) {
+                        debouncer.callback.call(sample);
+                    }
+                }
+                return;
+            }
+        });
+    }))
 }
 
 impl<Handler> Resolvable for LivelinessSubscriberBuilder<'_, '_, Handler>
@@ -564,7 +684,7 @@ where
                 &key_expr,
                 Locality::default(),
                 self.history,
-                callback,
+                debounced(callback, self.debounce),
             )
             .map(|sub_state| Subscriber {
                 inner: SubscriberInner {
@@ -572,6 +692,8 @@ where
                     id: sub_state.id,
                     key_expr: sub_state.key_expr.clone(),
                     kind: SubscriberKind::LivelinessSubscriber,
+                    #[cfg(feature = "unstable")]
+                    key_tracker: None,
                     undeclare_on_drop: true,
                 },
                 handler,
@@ -602,7 +724,7 @@ impl Wait for LivelinessSubscriberBuilder<'_, '_, Callback<Sample>, true> {
             &self.key_expr?,
             Locality::default(),
             self.history,
-            self.handler,
+            debounced(self.handler, self.debounce),
         )?;
         Ok(())
     }
@@ -15,10 +15,48 @@ use zenoh_config::{Config, PluginLoad};
 use zenoh_result::ZResult;
 
 use crate::{
-    api::plugins::{PluginsManager, PLUGIN_PREFIX},
+    api::plugins::{PluginsManager, ZenohPlugin, PLUGIN_PREFIX},
     net::runtime::Runtime,
 };
 
+/// A plugin linked in the application binary, started for the configuration of its
+/// [`DEFAULT_NAME`](zenoh_plugin_trait::Plugin::DEFAULT_NAME) instead of a dynamic library.
+#[derive(Clone, Copy)]
+pub(crate) struct StaticPlugin {
+    name: &'static str,
+    declare: fn(&mut PluginsManager, &str, bool),
+}
+
+impl StaticPlugin {
+    pub(crate) fn new<P: ZenohPlugin + Send + Sync>() -> Self {
+        fn declare<P: ZenohPlugin + Send + Sync>(
+            manager: &mut PluginsManager,
+            id: &str,
+            required: bool,
+        ) {
+            manager.declare_static_plugin::<P, _>(id, required)
+        }
+        Self {
+            name: P::DEFAULT_NAME,
+            declare: declare::<P>,
+        }
+    }
+}
+
+pub(crate) fn declare_static_plugins(
+    manager: &mut PluginsManager,
+    config: &Config,
+    static_plugins: &[StaticPlugin],
+) {
+    for plugin in static_plugins {
+        let required = config
+            .plugins()
+            .load_requests()
+            .any(|request| request.id == plugin.name && request.required);
+        (plugin.declare)(manager, plugin.name, required);
+    }
+}
+
 pub(crate) fn load_plugin(
     plugin_mgr: &mut PluginsManager,
     name: &str,
@@ -47,9 +85,9 @@ pub(crate) fn load_plugin(
     Ok(())
 }
 
-pub(crate) fn load_plugins(config: &Config) -> PluginsManager {
+pub(crate) fn load_plugins(config: &Config, static_plugins: &[StaticPlugin]) -> PluginsManager {
     let mut manager = PluginsManager::dynamic(config.libloader(), PLUGIN_PREFIX.to_string());
-    // Static plugins are to be added here, with `.add_static::<PluginType>()`
+    declare_static_plugins(&mut manager, config, static_plugins);
     for plugin_load in config.plugins().load_requests() {
         let PluginLoad {
             id,
@@ -57,6 +95,10 @@ pub(crate) fn load_plugins(config: &Config) -> PluginsManager {
             paths,
             required,
         } = plugin_load;
+        if manager.plugin(&id).is_some() {
+            tracing::debug!("Plugin \"{id}\" is statically linked");
+            continue;
+        }
         tracing::info!(
             "Loading {req} plugin \"{id}\"",
             req = if required { "required" } else { "" }
@@ -75,6 +117,11 @@ pub(crate) fn load_plugins(config: &Config) -> PluginsManager {
 pub(crate) fn start_plugins(runtime: &Runtime) {
     let mut manager = runtime.plugins_manager();
     for plugin in manager.loaded_plugins_iter_mut() {
+        // The static plugins are declared whether they are configured or not
+        if runtime.config().lock().plugin(plugin.id()).is_none() {
+            tracing::debug!("Plugin \"{}\" is not configured", plugin.id());
+            continue;
+        }
         let required = plugin.required();
         tracing::info!(
             "Starting {req} plugin \"{name}\"",
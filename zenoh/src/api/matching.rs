@@ -54,11 +54,12 @@ pub struct MatchingStatus {
 pub(crate) enum MatchingStatusType {
     Subscribers,
     Queryables(bool),
+    Queriers,
 }
 
 #[zenoh_macros::unstable]
 impl MatchingStatus {
-    /// Return true if there exist entities matching the target (i.e either Subscribers matching Publisher's key expression or Queryables matching Querier's key expression and target).
+    /// Return true if there exist entities matching the target (i.e either Subscribers matching Publisher's key expression, Queryables matching Querier's key expression and target or Queriers matching Queryable's key expression).
     ///
     /// # Examples
     /// ```
@@ -106,6 +107,10 @@ impl MatchingListenerState {
                     || (self.match_type == MatchingStatusType::Queryables(true)
                         && key_expr.includes(&self.key_expr))
             }
+            MatchingStatusType::Queriers => {
+                self.match_type == MatchingStatusType::Queriers
+                    && self.key_expr.intersects(key_expr)
+            }
         }
     }
 }
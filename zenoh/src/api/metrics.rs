@@ -0,0 +1,188 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Snapshots of the core metrics of a [`Session`](crate::Session).
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tokio_util::sync::CancellationToken;
+use zenoh_config::{wrappers::ZenohId, Locator};
+use zenoh_core::{zread, Resolvable, Wait};
+
+use crate::{
+    api::{publisher::Priority, session::SessionInner},
+    net::routing::dispatcher::resource::Resource,
+};
+
+/// The depth of a transmission queue of a link of the [`Session`](crate::Session).
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueDepth {
+    /// The [`ZenohId`] of the remote node of the link.
+    pub peer: ZenohId,
+    /// The destination [`Locator`] of the link.
+    pub link: Locator,
+    /// The priority of the queue.
+    pub priority: Priority,
+    /// The number of batches being filled or waiting to be transmitted.
+    pub occupied: usize,
+    /// The total number of batches of the queue.
+    pub capacity: usize,
+    /// The number of batches transmitted from the queue.
+    pub served: usize,
+    /// Whether the occupancy is above the alarm threshold of the queue, see the
+    /// `transport.link.tx.queue.occupancy_alarm` configuration.
+    pub alarm: bool,
+    /// The number of times the occupancy rose above the alarm threshold of the queue.
+    pub alarms: usize,
+}
+
+/// A snapshot of the core metrics of a [`Session`](crate::Session), returned by
+/// [`Session::metrics`](crate::Session::metrics).
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetrics {
+    /// The number of queries waiting for their final reply.
+    pub pending_queries: usize,
+    /// The number of replies dropped as duplicates by the queries with deduplication,
+    /// see [`QueryConsolidation::with_deduplication`](crate::query::QueryConsolidation::with_deduplication).
+    pub duplicate_replies: u64,
+    /// The number of key expressions declared by the session.
+    pub local_keyexprs: usize,
+    /// The number of key expressions declared to the session by remote nodes.
+    pub remote_keyexprs: usize,
+    /// The number of resources of the routing tables.
+    pub routing_resources: usize,
+    /// The depths of the transmission queues of the unicast links, the queue of the control
+    /// messages excepted.
+    pub queues: Vec<QueueDepth>,
+    /// How much the HLC of the session is ahead of the physical clock, if timestamping is
+    /// enabled.
+    pub hlc_drift: Option<Duration>,
+}
+
+pub(crate) async fn snapshot(session: &SessionInner) -> SessionMetrics {
+    let mut metrics = {
+        let state = zread!(session.state);
+        SessionMetrics {
+            pending_queries: state.queries.len() + state.liveliness_queries.len(),
+            duplicate_replies: state.duplicate_replies,
+            local_keyexprs: state.local_resources.len(),
+            remote_keyexprs: state.remote_resources.len(),
+            ..Default::default()
+        }
+    };
+    {
+        let router = session.runtime.router();
+        let tables = zread!(router.tables.tables);
+        metrics.routing_resources = count_resources(&tables.root_res) - 1;
+    }
+    for transport in session.runtime.manager().get_transports_unicast().await {
+        let (Ok(peer), Ok(queues)) = (transport.get_zid(), transport.get_queues()) else {
+            continue;
+        };
+        for (link, queues) in queues {
+            metrics
+                .queues
+                .extend(queues.into_iter().filter_map(|queue| {
+                    Some(QueueDepth {
+                        peer: peer.into(),
+                        link: link.dst.clone(),
+                        priority: Priority::try_from(queue.priority as u8).ok()?,
+                        occupied: queue.occupied,
+                        capacity: queue.capacity,
+                        served: queue.served,
+                        alarm: queue.alarm,
+                        alarms: queue.alarms,
+                    })
+                }));
+        }
+    }
+    metrics.hlc_drift = session.runtime.hlc().map(|hlc| {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        hlc.new_timestamp()
+            .get_time()
+            .to_duration()
+            .saturating_sub(now)
+    });
+    metrics
+}
+
+fn count_resources(res: &Arc<Resource>) -> usize {
+    1 + res.children.values().map(count_resources).sum::<usize>()
+}
+
+/// A builder returned by [`Session::metrics`](crate::Session::metrics) resolving into a
+/// [`SessionMetrics`] snapshot.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let metrics = session.metrics().await;
+/// println!("{} pending queries", metrics.pending_queries);
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct MetricsBuilder<'a> {
+    pub(crate) session: &'a SessionInner,
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for MetricsBuilder<'_> {
+    type To = SessionMetrics;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for MetricsBuilder<'_> {
+    fn wait(self) -> Self::To {
+        zenoh_runtime::ZRuntime::Application.block_in_place(self.into_future())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a> IntoFuture for MetricsBuilder<'a> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Pin<Box<dyn Future<Output = <Self as IntoFuture>::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(snapshot(self.session))
+    }
+}
+
+/// A periodic export of the [`SessionMetrics`] of a [`Session`](crate::Session) to a callback,
+/// returned by [`Session::metrics_exporter`](crate::Session::metrics_exporter).
+///
+/// The export stops when the [`MetricsExporter`] is dropped or the session is closed.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct MetricsExporter {
+    pub(crate) token: CancellationToken,
+}
+
+#[zenoh_macros::unstable]
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
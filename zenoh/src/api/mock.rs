@@ -0,0 +1,188 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! A session without network recording its publications and answering its queries, for unit tests.
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+use zenoh_core::{zlock, Resolve, ResolveFuture, Wait};
+use zenoh_result::ZResult;
+
+use crate::api::{
+    bytes::ZBytes, config::Config, key_expr::KeyExpr, queryable::Queryable, sample::Sample,
+    selector::Selector, session::Session, subscriber::Subscriber,
+};
+
+/// A [`Session`] without network, for unit testing the code using a session.
+///
+/// The mock session dereferences to a session that neither listens, connects nor scouts, so
+/// that the code under test can use it like any other session. It additionally:
+/// - records the samples put and deleted through the session, see [`published`](Self::published);
+/// - answers the queries of the session with canned replies, see [`reply`](Self::reply), and
+///   records the queries it answered, see [`queries`](Self::queries).
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::test::MockSession;
+///
+/// let session = MockSession::open().await.unwrap();
+/// session.reply("config/rate", "10").unwrap();
+///
+/// // The code under test
+/// let rate = session.get("config/rate").await.unwrap().recv_async().await.unwrap();
+/// session.put("status", rate.result().unwrap().payload().clone()).await.unwrap();
+///
+/// session.expect_published("status", "10");
+/// assert_eq!(session.queries()[0].key_expr().as_str(), "config/rate");
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct MockSession {
+    session: Session,
+    samples: Arc<Mutex<Vec<Sample>>>,
+    queries: Arc<Mutex<Vec<Selector<'static>>>>,
+    queryables: Mutex<Vec<Queryable<()>>>,
+    _recorder: Subscriber<()>,
+}
+
+#[zenoh_macros::unstable]
+impl MockSession {
+    /// Open a mock session.
+    pub fn open() -> impl Resolve<ZResult<MockSession>> {
+        ResolveFuture::new(async move {
+            let mut config = Config::default();
+            config.insert_json5("mode", r#""peer""#)?;
+            config.insert_json5("listen/endpoints", "[]")?;
+            config.insert_json5("scouting/multicast/enabled", "false")?;
+            config.insert_json5("scouting/gossip/enabled", "false")?;
+            let session = crate::open(config).await?;
+
+            let samples = Arc::new(Mutex::new(Vec::new()));
+            let recorder = session
+                .declare_subscriber("**")
+                .callback({
+                    let samples = samples.clone();
+                    move |sample| zlock!(samples).push(sample)
+                })
+                .await?;
+            Ok(MockSession {
+                session,
+                samples,
+                queries: Arc::new(Mutex::new(Vec::new())),
+                queryables: Mutex::new(Vec::new()),
+                _recorder: recorder,
+            })
+        })
+    }
+
+    /// The samples put and deleted through the session, in order.
+    pub fn published(&self) -> Vec<Sample> {
+        zlock!(self.samples).clone()
+    }
+
+    /// The selectors of the queries answered by the canned replies, in order.
+    pub fn queries(&self) -> Vec<Selector<'static>> {
+        zlock!(self.queries).clone()
+    }
+
+    /// Forget the recorded samples and queries.
+    pub fn clear(&self) {
+        zlock!(self.samples).clear();
+        zlock!(self.queries).clear();
+    }
+
+    /// Answer the queries intersecting `key_expr` with a reply of the given payload on `key_expr`.
+    pub fn reply<'a, TryIntoKeyExpr, IntoZBytes>(
+        &self,
+        key_expr: TryIntoKeyExpr,
+        payload: IntoZBytes,
+    ) -> ZResult<()>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'a>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'a>>>::Error: Into<zenoh_result::Error>,
+        IntoZBytes: Into<ZBytes>,
+    {
+        self.answer(key_expr.try_into().map_err(Into::into)?, Ok(payload.into()))
+    }
+
+    /// Answer the queries intersecting `key_expr` with an error of the given payload.
+    pub fn reply_err<'a, TryIntoKeyExpr, IntoZBytes>(
+        &self,
+        key_expr: TryIntoKeyExpr,
+        payload: IntoZBytes,
+    ) -> ZResult<()>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'a>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'a>>>::Error: Into<zenoh_result::Error>,
+        IntoZBytes: Into<ZBytes>,
+    {
+        self.answer(
+            key_expr.try_into().map_err(Into::into)?,
+            Err(payload.into()),
+        )
+    }
+
+    fn answer(&self, key_expr: KeyExpr<'_>, reply: Result<ZBytes, ZBytes>) -> ZResult<()> {
+        let key_expr = key_expr.into_owned();
+        let queries = self.queries.clone();
+        let queryable = self
+            .session
+            .declare_queryable(key_expr.clone())
+            .callback(move |query| {
+                zlock!(queries).push(query.selector().into_owned());
+                let res = match &reply {
+                    Ok(payload) => query.reply(key_expr.clone(), payload.clone()).wait(),
+                    Err(payload) => query.reply_err(payload.clone()).wait(),
+                };
+                if let Err(e) = res {
+                    tracing::error!("Unable to reply to {}: {e}", query.selector());
+                }
+            })
+            .wait()?;
+        zlock!(self.queryables).push(queryable);
+        Ok(())
+    }
+
+    /// Panic unless a sample with the given key expression and payload was put through the session.
+    #[track_caller]
+    pub fn expect_published<IntoZBytes: Into<ZBytes>>(&self, key_expr: &str, payload: IntoZBytes) {
+        let payload = payload.into().to_bytes().into_owned();
+        let samples = self.published();
+        if !samples
+            .iter()
+            .any(|s| s.key_expr().as_str() == key_expr && *s.payload().to_bytes() == *payload)
+        {
+            panic!(
+                "No sample published on '{key_expr}' with payload {payload:?}, published: {:?}",
+                samples
+                    .iter()
+                    .map(|s| (s.key_expr().as_str(), s.payload().to_bytes()))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Deref for MockSession {
+    type Target = Session;
+
+    fn deref(&self) -> &Self::Target {
+        &self.session
+    }
+}
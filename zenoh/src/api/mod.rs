@@ -14,28 +14,54 @@
 
 pub(crate) type Id = u32;
 
+#[cfg(feature = "unstable")]
+pub(crate) mod acl;
 pub(crate) mod admin;
 pub(crate) mod builders;
 pub(crate) mod bytes;
+#[cfg(feature = "unstable")]
+pub(crate) mod capture;
+#[cfg(feature = "unstable")]
+pub(crate) mod clock;
 pub(crate) mod config;
+#[cfg(feature = "unstable")]
+pub(crate) mod congestion;
 pub(crate) mod encoding;
+#[cfg(feature = "unstable")]
+pub(crate) mod encryption;
 pub(crate) mod handlers;
+#[cfg(feature = "test_harness")]
+pub(crate) mod harness;
 pub(crate) mod info;
 pub(crate) mod key_expr;
+#[cfg(feature = "unstable")]
+pub(crate) mod key_tracker;
 pub(crate) mod liveliness;
 #[cfg(feature = "plugins")]
 pub(crate) mod loader;
 #[cfg(feature = "unstable")]
 pub(crate) mod matching;
+#[cfg(feature = "unstable")]
+pub(crate) mod metrics;
+#[cfg(feature = "test_harness")]
+pub(crate) mod mock;
 #[cfg(feature = "plugins")]
 pub(crate) mod plugins;
+#[cfg(feature = "unstable")]
+pub(crate) mod probe;
 pub(crate) mod publisher;
 #[cfg(feature = "unstable")]
 pub(crate) mod querier;
 pub(crate) mod query;
 pub(crate) mod queryable;
+#[cfg(feature = "unstable")]
+pub(crate) mod reorder;
 pub(crate) mod sample;
 pub(crate) mod scouting;
 pub(crate) mod selector;
 pub(crate) mod session;
+#[cfg(feature = "unstable")]
+pub(crate) mod signature;
 pub(crate) mod subscriber;
+#[cfg(feature = "unstable")]
+pub(crate) mod trace;
@@ -0,0 +1,269 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Measurement of the latency and throughput to a remote zenoh node.
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+use zenoh_config::wrappers::ZenohId;
+use zenoh_core::{Resolvable, Wait};
+use zenoh_protocol::core::CongestionControl;
+use zenoh_result::{bail, ZResult};
+use zenoh_runtime::ZRuntime;
+
+use crate::api::{
+    bytes::ZBytes,
+    handlers::FifoChannelHandler,
+    publisher::Priority,
+    query::{ConsolidationMode, QueryTarget, Reply},
+    session::Session,
+};
+use crate::net::runtime::adminspace::{DISCARD_PARAM, PRIORITY_PARAM};
+
+const DEFAULT_COUNT: usize = 10;
+const DEFAULT_PAYLOAD_SIZE: usize = 8192;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The results of a [`Session::probe`] for a given [`Priority`].
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct PriorityProbe {
+    /// The priority of the echo requests and replies.
+    pub priority: Priority,
+    /// The number of echo requests sent, for both the latency and the throughput measurements.
+    pub sent: usize,
+    /// The number of echo replies received.
+    pub received: usize,
+    /// The minimum round-trip time.
+    pub rtt_min: Option<Duration>,
+    /// The average round-trip time.
+    pub rtt_avg: Option<Duration>,
+    /// The maximum round-trip time.
+    pub rtt_max: Option<Duration>,
+    /// The average one-way latency, computed from the timestamps of the echo replies and only
+    /// meaningful if the clocks of both nodes are synchronized.
+    pub one_way: Option<Duration>,
+    /// The throughput of the echo requests, in bytes per second.
+    pub throughput: f64,
+}
+
+#[zenoh_macros::unstable]
+impl PriorityProbe {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        let micros = |d: Option<Duration>| d.map(|d| d.as_micros() as u64);
+        json!({
+            "priority": self.priority as u8,
+            "sent": self.sent,
+            "received": self.received,
+            "rtt_min_us": micros(self.rtt_min),
+            "rtt_avg_us": micros(self.rtt_avg),
+            "rtt_max_us": micros(self.rtt_max),
+            "one_way_us": micros(self.one_way),
+            "throughput_bps": self.throughput,
+        })
+    }
+}
+
+/// A builder for probing a remote zenoh node, returned by [`Session::probe`].
+///
+/// The probe sends echo requests to the admin space of the node, which must be enabled, with
+/// each of the configured priorities:
+/// - `count` small requests are sent one after the other to measure the round-trip time and the
+///   one-way latency;
+/// - `count` requests of `payload_size` bytes are then sent at once to measure the throughput.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::qos::Priority;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let zid = "1".parse().unwrap();
+/// let report = session
+///     .probe(zid)
+///     .priorities([Priority::RealTime, Priority::Data])
+///     .await
+///     .unwrap();
+/// for probe in report {
+///     println!("{:?}: {:?}", probe.priority, probe.rtt_avg);
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+#[derive(Debug)]
+pub struct ProbeBuilder<'a> {
+    pub(crate) session: &'a Session,
+    pub(crate) zid: ZenohId,
+    pub(crate) priorities: Vec<Priority>,
+    pub(crate) count: usize,
+    pub(crate) payload_size: usize,
+    pub(crate) timeout: Duration,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> ProbeBuilder<'a> {
+    pub(crate) fn new(session: &'a Session, zid: ZenohId) -> Self {
+        Self {
+            session,
+            zid,
+            priorities: vec![Priority::DEFAULT],
+            count: DEFAULT_COUNT,
+            payload_size: DEFAULT_PAYLOAD_SIZE,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// The priorities to probe ([`Priority::DEFAULT`] by default).
+    #[inline]
+    pub fn priorities<I: IntoIterator<Item = Priority>>(mut self, priorities: I) -> Self {
+        self.priorities = priorities.into_iter().collect();
+        self
+    }
+
+    /// The number of echo requests of each measurement (10 by default).
+    #[inline]
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// The size of the payload of the echo requests measuring the throughput (8 KiB by default).
+    #[inline]
+    pub fn payload_size(mut self, payload_size: usize) -> Self {
+        self.payload_size = payload_size;
+        self
+    }
+
+    /// The timeout of each echo request (2 s by default).
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for ProbeBuilder<'_> {
+    type To = ZResult<Vec<PriorityProbe>>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for ProbeBuilder<'_> {
+    fn wait(self) -> Self::To {
+        ZRuntime::Application.block_in_place(self.into_future())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a> IntoFuture for ProbeBuilder<'a> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Pin<Box<dyn Future<Output = <Self as IntoFuture>::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            if self.count == 0 {
+                bail!("The number of echo requests of a probe must be positive");
+            }
+            let mut report = Vec::with_capacity(self.priorities.len());
+            for priority in &self.priorities {
+                report.push(self.probe(*priority).await?);
+            }
+            Ok(report)
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl ProbeBuilder<'_> {
+    async fn echo(
+        &self,
+        priority: Priority,
+        payload: ZBytes,
+        discard: bool,
+    ) -> ZResult<FifoChannelHandler<Reply>> {
+        let mut selector = format!("@/{}/*/echo?{PRIORITY_PARAM}={}", self.zid, priority as u8);
+        if discard {
+            selector.push_str(&format!(";{DISCARD_PARAM}"));
+        }
+        self.session
+            .get(selector)
+            .payload(payload)
+            .priority(priority)
+            .congestion_control(CongestionControl::Block)
+            .target(QueryTarget::All)
+            .consolidation(ConsolidationMode::None)
+            .timeout(self.timeout)
+            .await
+    }
+
+    async fn probe(&self, priority: Priority) -> ZResult<PriorityProbe> {
+        let mut received = 0;
+        let mut rtts = Vec::with_capacity(self.count);
+        let mut one_ways = Vec::with_capacity(self.count);
+        for _ in 0..self.count {
+            let sent = self.session.new_timestamp();
+            let start = Instant::now();
+            let replies = self.echo(priority, ZBytes::default(), false).await?;
+            let Ok(Ok(sample)) = replies.recv_async().await.map(|r| r.into_result()) else {
+                continue;
+            };
+            received += 1;
+            rtts.push(start.elapsed());
+            if let Some(timestamp) = sample.timestamp() {
+                one_ways.extend(
+                    timestamp
+                        .get_time()
+                        .to_duration()
+                        .checked_sub(sent.get_time().to_duration()),
+                );
+            }
+        }
+
+        let payload = ZBytes::from(vec![0u8; self.payload_size]);
+        let start = Instant::now();
+        let mut pending = Vec::with_capacity(self.count);
+        for _ in 0..self.count {
+            pending.push(self.echo(priority, payload.clone(), true).await?);
+        }
+        let mut acknowledged = 0;
+        for replies in pending {
+            if let Ok(Ok(_)) = replies.recv_async().await.map(|r| r.into_result()) {
+                acknowledged += 1;
+            }
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+
+        let average = |durations: &[Duration]| {
+            (!durations.is_empty())
+                .then(|| durations.iter().sum::<Duration>() / durations.len() as u32)
+        };
+        Ok(PriorityProbe {
+            priority,
+            sent: 2 * self.count,
+            received: received + acknowledged,
+            rtt_min: rtts.iter().min().copied(),
+            rtt_avg: average(&rtts),
+            rtt_max: rtts.iter().max().copied(),
+            one_way: average(&one_ways),
+            throughput: (acknowledged * self.payload_size) as f64 / elapsed,
+        })
+    }
+}
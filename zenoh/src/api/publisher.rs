@@ -18,6 +18,7 @@ use std::{
     future::{IntoFuture, Ready},
     pin::Pin,
     task::{Context, Poll},
+    time::SystemTime,
 };
 
 use futures::Sink;
@@ -29,12 +30,17 @@ use zenoh_result::{Error, ZResult};
 #[cfg(feature = "unstable")]
 use {
     crate::api::{
-        builders::matching_listener::MatchingListenerBuilder,
+        builders::{
+            congestion_listener::CongestionListenerBuilder,
+            matching_listener::MatchingListenerBuilder,
+        },
+        congestion::CongestionMonitor,
         handlers::DefaultHandler,
         matching::{MatchingStatus, MatchingStatusType},
         sample::SourceInfo,
+        signature::SigningKey,
     },
-    std::{collections::HashSet, sync::Arc, sync::Mutex},
+    std::{collections::HashSet, sync::Arc, sync::Mutex, time::Duration},
     zenoh_config::wrappers::EntityGlobalId,
     zenoh_protocol::core::EntityGlobalIdProto,
     zenoh_protocol::core::Reliability,
@@ -48,8 +54,8 @@ use crate::api::{
     bytes::ZBytes,
     encoding::Encoding,
     key_expr::KeyExpr,
-    sample::{Locality, Sample, SampleFields},
-    session::{UndeclarableSealed, WeakSession},
+    sample::{Locality, QoS, Sample, SampleFields},
+    session::{Session, UndeclarableSealed, WeakSession},
     Id,
 };
 
@@ -58,6 +64,8 @@ pub(crate) struct PublisherState {
     pub(crate) remote_id: Id,
     pub(crate) key_expr: KeyExpr<'static>,
     pub(crate) destination: Locality,
+    pub(crate) qos: QoS,
+    pub(crate) declared_at: SystemTime,
 }
 
 impl fmt::Debug for PublisherState {
@@ -112,6 +120,14 @@ pub struct Publisher<'a> {
     pub(crate) reliability: Reliability,
     #[cfg(feature = "unstable")]
     pub(crate) matching_listeners: Arc<Mutex<HashSet<Id>>>,
+    #[cfg(feature = "unstable")]
+    pub(crate) signing_key: Option<SigningKey>,
+    #[cfg(feature = "unstable")]
+    pub(crate) hop_limit: Option<u8>,
+    #[cfg(feature = "unstable")]
+    pub(crate) deadline: Option<Duration>,
+    #[cfg(feature = "unstable")]
+    pub(crate) congestion: Arc<CongestionMonitor>,
     pub(crate) undeclare_on_drop: bool,
 }
 
@@ -144,6 +160,56 @@ impl<'a> Publisher<'a> {
         &self.key_expr
     }
 
+    /// Change the key expression of the [`Publisher`], keeping its QoS and matching listeners.
+    ///
+    /// The publisher is declared on the new key expression before being undeclared on the
+    /// former one, so that it is always declared to the network. The matching listeners are
+    /// notified if the matching status changes. This is useful for applications whose key
+    /// expressions contain rotating identifiers, which would otherwise have to redeclare their
+    /// publishers and matching listeners.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let mut publisher = session.declare_publisher("key/expression/1").await.unwrap();
+    /// publisher.set_key_expr("key/expression/2").await.unwrap();
+    /// assert_eq!(publisher.key_expr().as_str(), "key/expression/2");
+    /// # }
+    /// ```
+    pub fn set_key_expr<TryIntoKeyExpr>(
+        &mut self,
+        key_expr: TryIntoKeyExpr,
+    ) -> PublisherKeyExprUpdate<'_, 'a>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'a>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'a>>>::Error: Into<zenoh_result::Error>,
+    {
+        PublisherKeyExprUpdate {
+            key_expr: key_expr.try_into().map_err(Into::into),
+            publisher: self,
+        }
+    }
+
+    fn set_key_expr_impl(&mut self, key_expr: ZResult<KeyExpr<'a>>) -> ZResult<()> {
+        let mut key_expr = key_expr?;
+        if !key_expr.is_fully_optimized(&self.session) {
+            key_expr = Session::ref_cast(&self.session)
+                .declare_keyexpr(key_expr)
+                .wait()?;
+        }
+        self.session
+            .rekey_publisher_inner(self.id, key_expr.clone())?;
+        #[cfg(feature = "unstable")]
+        for id in zlock!(self.matching_listeners).iter() {
+            self.session.rekey_matches_listener_inner(*id, &key_expr)?;
+        }
+        self.key_expr = key_expr;
+        Ok(())
+    }
+
     /// Get the [`Encoding`] used when publishing data.
     #[inline]
     pub fn encoding(&self) -> &Encoding {
@@ -169,6 +235,29 @@ impl<'a> Publisher<'a> {
         self.reliability
     }
 
+    /// Get the number of links the data may traverse, if limited.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn hop_limit(&self) -> Option<u8> {
+        self.hop_limit
+    }
+
+    /// Get the time within which the data must be transmitted before being dropped, if any.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    /// Get the number of messages of this publisher dropped by this node because their
+    /// [deadline](crate::pubsub::PublisherBuilder::deadline) expired before they could be
+    /// transmitted, a publication being counted once per transmission queue it was pushed on.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn expired(&self) -> u64 {
+        self.congestion.expired()
+    }
+
     /// Put data.
     ///
     /// # Examples
@@ -195,6 +284,8 @@ impl<'a> Publisher<'a> {
             timestamp: None,
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             attachment: None,
         }
     }
@@ -218,6 +309,8 @@ impl<'a> Publisher<'a> {
             timestamp: None,
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             attachment: None,
         }
     }
@@ -286,6 +379,56 @@ impl<'a> Publisher<'a> {
         }
     }
 
+    /// Return a [`CongestionListener`](crate::pubsub::CongestionListener) for this Publisher.
+    ///
+    /// The [`CongestionListener`](crate::pubsub::CongestionListener) will send a
+    /// [`CongestionEvent`](crate::pubsub::CongestionEvent) each time the publications of the
+    /// Publisher start or stop being blocked or dropped by the congestion control of the
+    /// transmission queues they are pushed on, with the occupancy of the queue. This allows the
+    /// application to adapt its publication rate instead of discovering the loss downstream.
+    ///
+    /// The publications are only observed while the Publisher has congestion listeners.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::{pubsub::CongestionStatus, qos::CongestionControl};
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session
+    ///     .declare_publisher("key/expression")
+    ///     .congestion_control(CongestionControl::Drop)
+    ///     .await
+    ///     .unwrap();
+    /// let congestion_listener = publisher.congestion_events().await.unwrap();
+    /// while let Ok(event) = congestion_listener.recv_async().await {
+    ///     if event.status() == CongestionStatus::Dropping {
+    ///         println!(
+    ///             "Publications are dropped, queue {}/{}",
+    ///             event.occupied(),
+    ///             event.capacity()
+    ///         );
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn congestion_events(&self) -> CongestionListenerBuilder<'_, DefaultHandler> {
+        CongestionListenerBuilder {
+            monitor: &self.congestion,
+            handler: DefaultHandler::default(),
+        }
+    }
+
+    // Runs the publication `f`, notifying the congestion listeners of the congestion it met
+    #[inline]
+    pub(crate) fn publish<R>(&self, f: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "unstable")]
+        let f = || self.congestion.observe(self.deadline.is_some(), f);
+        f()
+    }
+
     /// Undeclare the [`Publisher`], informing the network that it needn't optimize publications for its key expression anymore.
     ///
     /// # Examples
@@ -311,6 +454,7 @@ impl<'a> Publisher<'a> {
             for id in ids {
                 self.session.undeclare_matches_listener_inner(id)?
             }
+            self.congestion.clear();
         }
         self.session.undeclare_publisher_inner(self.id)
     }
@@ -363,6 +507,44 @@ impl IntoFuture for PublisherUndeclaration<'_> {
     }
 }
 
+/// A [`Resolvable`] returned when changing the key expression of a publisher, see
+/// [`Publisher::set_key_expr`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let mut publisher = session.declare_publisher("key/expression/1").await.unwrap();
+/// publisher.set_key_expr("key/expression/2").await.unwrap();
+/// # }
+/// ```
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct PublisherKeyExprUpdate<'b, 'a> {
+    publisher: &'b mut Publisher<'a>,
+    key_expr: ZResult<KeyExpr<'a>>,
+}
+
+impl Resolvable for PublisherKeyExprUpdate<'_, '_> {
+    type To = ZResult<()>;
+}
+
+impl Wait for PublisherKeyExprUpdate<'_, '_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        self.publisher.set_key_expr_impl(self.key_expr)
+    }
+}
+
+impl IntoFuture for PublisherKeyExprUpdate<'_, '_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
 impl Drop for Publisher<'_> {
     fn drop(&mut self) {
         if self.undeclare_on_drop {
@@ -388,24 +570,36 @@ impl Sink<Sample> for Publisher<'_> {
             kind,
             encoding,
             attachment,
+            #[cfg(feature = "unstable")]
+            trace_context,
             ..
         } = item.into();
-        self.session.resolve_put(
-            &self.key_expr,
-            payload,
-            kind,
-            encoding,
-            self.congestion_control,
-            self.priority,
-            self.is_express,
-            self.destination,
-            #[cfg(feature = "unstable")]
-            self.reliability,
-            None,
-            #[cfg(feature = "unstable")]
-            SourceInfo::empty(),
-            attachment,
-        )
+        self.publish(|| {
+            self.session.resolve_put(
+                &self.key_expr,
+                payload,
+                kind,
+                encoding,
+                self.congestion_control,
+                self.priority,
+                self.is_express,
+                self.destination,
+                #[cfg(feature = "unstable")]
+                self.reliability,
+                #[cfg(feature = "unstable")]
+                self.hop_limit,
+                #[cfg(feature = "unstable")]
+                self.deadline,
+                None,
+                #[cfg(feature = "unstable")]
+                SourceInfo::empty(),
+                #[cfg(feature = "unstable")]
+                trace_context,
+                #[cfg(feature = "unstable")]
+                self.signing_key.as_ref(),
+                attachment,
+            )
+        })
     }
 
     #[inline]
@@ -15,7 +15,7 @@
 use core::fmt;
 use std::{
     future::{IntoFuture, Ready},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use tracing::error;
@@ -52,6 +52,8 @@ pub(crate) struct QuerierState {
     pub(crate) remote_id: Id,
     pub(crate) key_expr: KeyExpr<'static>,
     pub(crate) destination: Locality,
+    pub(crate) qos: QoS,
+    pub(crate) declared_at: SystemTime,
 }
 
 /// A querier that allows to send queries to a queryable.
@@ -79,6 +81,7 @@ pub struct Querier<'a> {
     pub(crate) target: QueryTarget,
     pub(crate) consolidation: QueryConsolidation,
     pub(crate) timeout: Duration,
+    pub(crate) reply_timeout: Option<Duration>,
     #[cfg(feature = "unstable")]
     pub(crate) accept_replies: ReplyKeyExpr,
     pub(crate) undeclare_on_drop: bool,
@@ -165,6 +168,8 @@ impl<'a> Querier<'a> {
             querier: self,
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             value: None,
             attachment: None,
             parameters: Parameters::empty(),
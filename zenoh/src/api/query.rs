@@ -12,10 +12,8 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::{collections::HashMap, error::Error, fmt::Display};
+use std::{collections::HashMap, error::Error, fmt::Display, time::Instant};
 
-#[cfg(feature = "unstable")]
-use zenoh_config::ZenohId;
 use zenoh_keyexpr::OwnedKeyExpr;
 use zenoh_protocol::core::Parameters;
 #[cfg(feature = "unstable")]
@@ -24,6 +22,15 @@ use zenoh_protocol::core::ZenohIdProto;
 pub use zenoh_protocol::network::request::ext::QueryTarget;
 #[doc(inline)]
 pub use zenoh_protocol::zenoh::query::ConsolidationMode;
+#[cfg(feature = "unstable")]
+use {
+    crate::api::session::WeakSession,
+    std::time::Duration,
+    zenoh_config::ZenohId,
+    zenoh_core::{Resolve, ResolveClosure},
+    zenoh_protocol::network::RequestId,
+    zenoh_result::ZResult,
+};
 
 use crate::api::{
     bytes::ZBytes, encoding::Encoding, handlers::Callback, key_expr::KeyExpr, sample::Sample,
@@ -34,6 +41,7 @@ use crate::api::{
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct QueryConsolidation {
     pub(crate) mode: ConsolidationMode,
+    pub(crate) deduplication: bool,
 }
 
 impl QueryConsolidation {
@@ -41,16 +49,54 @@ impl QueryConsolidation {
     /// Automatic query consolidation strategy selection.
     pub const AUTO: Self = Self {
         mode: ConsolidationMode::Auto,
+        deduplication: false,
     };
 
     pub(crate) const fn from_mode(mode: ConsolidationMode) -> Self {
-        Self { mode }
+        Self {
+            mode,
+            deduplication: false,
+        }
     }
 
     /// Returns the requested [`ConsolidationMode`].
     pub fn mode(&self) -> ConsolidationMode {
         self.mode
     }
+
+    /// Enable or disable the deduplication of the replies by the querier.
+    ///
+    /// A reply for a key expression is dropped before being delivered if a reply with the same
+    /// timestamp, or with the same kind, encoding and payload, has already been received for
+    /// it, as it happens when querying several aligned replicas of a storage. The number of
+    /// dropped replies is reported by [`SessionMetrics`](crate::session::SessionMetrics).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::query::{ConsolidationMode, QueryConsolidation};
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let replies = session
+    ///     .get("key/expression")
+    ///     .consolidation(QueryConsolidation::from(ConsolidationMode::None).with_deduplication(true))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub const fn with_deduplication(mut self, deduplication: bool) -> Self {
+        self.deduplication = deduplication;
+        self
+    }
+
+    /// Returns `true` if the replies are deduplicated by the querier,
+    /// see [`QueryConsolidation::with_deduplication`].
+    #[zenoh_macros::unstable]
+    pub fn deduplication(&self) -> bool {
+        self.deduplication
+    }
 }
 
 impl From<ConsolidationMode> for QueryConsolidation {
@@ -152,23 +198,143 @@ impl From<Reply> for Result<Sample, ReplyError> {
     }
 }
 
+/// A [`Reply`] to one of the queries issued by a [`get_many`](crate::Session::get_many),
+/// tagged with the index of the queried selector.
+#[zenoh_macros::unstable]
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct TaggedReply {
+    pub(crate) tag: usize,
+    pub(crate) reply: Reply,
+}
+
+#[zenoh_macros::unstable]
+impl TaggedReply {
+    /// Gets the index, in the selectors passed to [`get_many`](crate::Session::get_many), of
+    /// the selector this reply answers.
+    pub fn tag(&self) -> usize {
+        self.tag
+    }
+
+    /// Gets the reply.
+    pub fn reply(&self) -> &Reply {
+        &self.reply
+    }
+
+    /// Converts this `TaggedReply` into its reply.
+    pub fn into_reply(self) -> Reply {
+        self.reply
+    }
+}
+
+#[zenoh_macros::unstable]
+impl From<TaggedReply> for (usize, Reply) {
+    fn from(value: TaggedReply) -> Self {
+        (value.tag, value.reply)
+    }
+}
+
 pub(crate) struct LivelinessQueryState {
     pub(crate) callback: Callback<Reply>,
 }
 
+/// A query issued by a [`Session`](crate::Session) which didn't receive its final reply yet,
+/// returned by [`Session::pending_queries`](crate::Session::pending_queries).
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use std::time::Duration;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// for query in session.pending_queries() {
+///     if query.elapsed() > Duration::from_secs(60) && query.replies() == 0 {
+///         println!("Finalize leaked query {}", query.selector());
+///         query.finalize().await.unwrap();
+///     }
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone)]
+pub struct PendingQuery {
+    pub(crate) session: WeakSession,
+    pub(crate) id: RequestId,
+    pub(crate) selector: Selector<'static>,
+    pub(crate) elapsed: Duration,
+    pub(crate) replies: usize,
+}
+
+#[zenoh_macros::unstable]
+impl PendingQuery {
+    /// The identifier of the query in the session.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The selector of the query.
+    pub fn selector(&self) -> &Selector<'static> {
+        &self.selector
+    }
+
+    /// The time elapsed since the query was issued, when it was listed.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The number of replies received for the query, when it was listed.
+    pub fn replies(&self) -> usize {
+        self.replies
+    }
+
+    /// Finalize the query as if it received its final reply: the consolidated replies are
+    /// delivered and its handler is dropped, the replies received afterwards being ignored.
+    pub fn finalize(&self) -> impl Resolve<ZResult<()>> + '_ {
+        ResolveClosure::new(move || self.session.finalize_query(self.id))
+    }
+}
+
 pub(crate) struct QueryState {
     pub(crate) nb_final: usize,
     pub(crate) key_expr: KeyExpr<'static>,
     pub(crate) parameters: Parameters<'static>,
     pub(crate) reception_mode: ConsolidationMode,
     pub(crate) replies: Option<HashMap<OwnedKeyExpr, Reply>>,
+    // The samples received for each key expression, if the replies are deduplicated
+    pub(crate) received: Option<HashMap<OwnedKeyExpr, Vec<Sample>>>,
     pub(crate) callback: Callback<Reply>,
+    // When the query was sent, reported by the pending queries of the session
+    #[cfg(feature = "unstable")]
+    pub(crate) started: Instant,
+    // When the last reply was received, or when the query was sent if none was received
+    pub(crate) last_reply: Instant,
+    pub(crate) nb_replies: usize,
 }
 
 impl QueryState {
     pub(crate) fn selector(&self) -> Selector {
         Selector::borrowed(&self.key_expr, &self.parameters)
     }
+
+    /// Returns `true` if the replies are deduplicated and a duplicate of `sample` has already
+    /// been received, records `sample` otherwise.
+    pub(crate) fn is_duplicate(&mut self, sample: &Sample) -> bool {
+        let Some(received) = self.received.as_mut() else {
+            return false;
+        };
+        let samples = received.entry(sample.key_expr.clone().into()).or_default();
+        let duplicate = samples.iter().any(|s| {
+            s.timestamp.is_some() && s.timestamp == sample.timestamp
+                || (s.kind == sample.kind
+                    && s.encoding == sample.encoding
+                    && s.payload == sample.payload)
+        });
+        if !duplicate {
+            samples.push(sample.clone());
+        }
+        duplicate
+    }
 }
 /// The kind of accepted query replies.
 #[zenoh_macros::unstable]
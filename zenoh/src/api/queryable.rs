@@ -16,6 +16,7 @@ use std::{
     future::{IntoFuture, Ready},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::SystemTime,
 };
 
 use tracing::error;
@@ -28,19 +29,30 @@ use zenoh_protocol::{
 use zenoh_result::ZResult;
 #[zenoh_macros::unstable]
 use {
-    crate::api::query::ReplyKeyExpr, zenoh_config::wrappers::EntityGlobalId,
+    crate::api::builders::matching_listener::MatchingListenerBuilder,
+    crate::api::handlers::DefaultHandler,
+    crate::api::matching::{MatchingStatus, MatchingStatusType},
+    crate::api::query::ReplyKeyExpr,
+    std::collections::HashSet,
+    std::sync::Mutex,
+    zenoh_config::wrappers::EntityGlobalId,
+    zenoh_core::ResolveClosure,
     zenoh_protocol::core::EntityGlobalIdProto,
 };
 
+#[zenoh_macros::unstable]
+use crate::api::publisher::Priority;
 #[zenoh_macros::unstable]
 use crate::api::selector::ZenohParameters;
+#[zenoh_macros::unstable]
+use crate::api::trace::TraceContext;
 use crate::{
     api::{
         builders::reply::{ReplyBuilder, ReplyBuilderDelete, ReplyBuilderPut, ReplyErrBuilder},
         bytes::ZBytes,
         encoding::Encoding,
         key_expr::KeyExpr,
-        sample::{Locality, Sample, SampleKind},
+        sample::{Locality, QoS, QoSBuilder, Sample, SampleKind},
         selector::Selector,
         session::{UndeclarableSealed, WeakSession},
         Id,
@@ -55,13 +67,30 @@ pub(crate) struct QueryInner {
     pub(crate) qid: RequestId,
     pub(crate) zid: ZenohIdProto,
     pub(crate) primitives: Arc<dyn Primitives>,
+    // The QoS of the request, whose priority and express flag the responses inherit
+    pub(crate) qos: QoS,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
+}
+
+impl QueryInner {
+    /// Returns `qos` with the priority and express flag of the query, so that the responses to an
+    /// urgent query are not queued behind less urgent traffic on their way back.
+    pub(crate) fn response_qos(&self, qos: response::ext::QoSType) -> QoS {
+        QoSBuilder::from(qos)
+            .priority(self.qos.priority())
+            .express(self.qos.express())
+            .into()
+    }
 }
 
 impl Drop for QueryInner {
     fn drop(&mut self) {
         self.primitives.send_response_final(ResponseFinal {
             rid: self.qid,
-            ext_qos: response::ext::QoSType::RESPONSE_FINAL,
+            ext_qos: self
+                .response_qos(response::ext::QoSType::RESPONSE_FINAL)
+                .into(),
             ext_tstamp: None,
         });
     }
@@ -113,6 +142,22 @@ impl Query {
         self.value.as_ref().map(|v| &v.1)
     }
 
+    /// The priority of this Query.
+    ///
+    /// Unless overridden, replies to this Query are sent with the same priority.
+    #[zenoh_macros::unstable]
+    pub fn priority(&self) -> Priority {
+        self.inner.qos.priority()
+    }
+
+    /// The express flag of this Query.
+    ///
+    /// Unless overridden, replies to this Query are sent with the same express flag.
+    #[zenoh_macros::unstable]
+    pub fn express(&self) -> bool {
+        self.inner.qos.express()
+    }
+
     /// This Query's attachment.
     pub fn attachment(&self) -> Option<&ZBytes> {
         self.attachment.as_ref()
@@ -123,6 +168,14 @@ impl Query {
         self.attachment.as_mut()
     }
 
+    /// The trace context propagated along with this Query.
+    ///
+    /// Unless overridden, replies to this Query carry the same trace context.
+    #[zenoh_macros::unstable]
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        self.inner.trace_context.as_ref()
+    }
+
     /// Sends a reply in the form of [`Sample`] to this Query.
     ///
     /// By default, queries only accept replies whose key expression intersects with the query's.
@@ -263,6 +316,8 @@ impl Query {
         let ext_sinfo = None;
         #[cfg(feature = "unstable")]
         let ext_sinfo = sample.source_info.into();
+        #[cfg(feature = "unstable")]
+        let trace_context = sample.trace_context.or(self.inner.trace_context);
         self.inner.primitives.send_response(Response {
             rid: self.inner.qid,
             wire_expr: WireExpr {
@@ -281,6 +336,11 @@ impl Query {
                         #[cfg(feature = "shared-memory")]
                         ext_shm: None,
                         ext_attachment: sample.attachment.map(|a| a.into()),
+                        #[cfg(feature = "unstable")]
+                        ext_trace: trace_context.map(Into::into),
+                        #[cfg(not(feature = "unstable"))]
+                        ext_trace: None,
+                        ext_signature: None,
                         ext_unknown: vec![],
                         payload: sample.payload.into(),
                     }),
@@ -288,6 +348,11 @@ impl Query {
                         timestamp: sample.timestamp,
                         ext_sinfo,
                         ext_attachment: sample.attachment.map(|a| a.into()),
+                        #[cfg(feature = "unstable")]
+                        ext_trace: trace_context.map(Into::into),
+                        #[cfg(not(feature = "unstable"))]
+                        ext_trace: None,
+                        ext_signature: None,
                         ext_unknown: vec![],
                     }),
                 },
@@ -308,6 +373,7 @@ pub(crate) struct QueryableState {
     pub(crate) complete: bool,
     pub(crate) origin: Locality,
     pub(crate) callback: Callback<Query>,
+    pub(crate) declared_at: SystemTime,
 }
 
 impl fmt::Debug for QueryableState {
@@ -325,6 +391,12 @@ pub(crate) struct QueryableInner {
     pub(crate) session: WeakSession,
     pub(crate) id: Id,
     pub(crate) undeclare_on_drop: bool,
+    #[cfg(feature = "unstable")]
+    pub(crate) key_expr: KeyExpr<'static>,
+    #[cfg(feature = "unstable")]
+    pub(crate) origin: Locality,
+    #[cfg(feature = "unstable")]
+    pub(crate) matching_listeners: Arc<Mutex<HashSet<Id>>>,
 }
 
 /// A [`Resolvable`] returned when undeclaring a queryable.
@@ -481,9 +553,110 @@ impl<Handler> Queryable<Handler> {
         UndeclarableSealed::undeclare_inner(self, ())
     }
 
+    /// Report the load of the [`Queryable`], from 0 (unloaded) to 255.
+    ///
+    /// The routers configured with the `least_loaded` queries load balancing route the queries
+    /// to the nearest complete queryable with the lowest load, so that replicas of a service
+    /// share the queries between them.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let queryable = session.declare_queryable("key/expression")
+    ///     .complete(true)
+    ///     .await
+    ///     .unwrap();
+    /// queryable.set_load(200).await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn set_load(&self, load: u8) -> impl Resolve<ZResult<()>> + '_ {
+        let session = &self.inner.session;
+        let id = self.inner.id;
+        ResolveClosure::new(move || session.set_queryable_load(id, load))
+    }
+
+    /// Return the [`MatchingStatus`] of the queryable.
+    ///
+    /// [`MatchingStatus::matching`] will return true if there exist Queriers
+    /// matching the Queryable's key expression and false otherwise.
+    ///
+    /// Only the queriers whose interests reach the local runtime are taken into account:
+    /// the queriers of this session or of other sessions of the same runtime,
+    /// and the queriers of the clients and peers that forward their interests to it.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let queryable = session.declare_queryable("key/expression").await.unwrap();
+    /// let matching_queriers: bool = queryable
+    ///     .matching_status()
+    ///     .await
+    ///     .unwrap()
+    ///     .matching();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn matching_status(&self) -> impl Resolve<ZResult<MatchingStatus>> + '_ {
+        // The status is computed before building the future as `Queryable` is not `Sync`
+        let status = self.inner.session.matching_status(
+            &self.inner.key_expr,
+            self.inner.origin,
+            MatchingStatusType::Queriers,
+        );
+        zenoh_core::ResolveFuture::new(async move { status })
+    }
+
+    /// Return a [`MatchingListener`](crate::api::matching::MatchingListener) for this Queryable.
+    ///
+    /// The [`MatchingListener`](crate::api::matching::MatchingListener) that will send a notification each time the [`MatchingStatus`](crate::api::matching::MatchingStatus) of
+    /// the Queryable changes.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let queryable = session.declare_queryable("key/expression").await.unwrap();
+    /// let matching_listener = queryable.matching_listener().await.unwrap();
+    /// while let Ok(matching_status) = matching_listener.recv_async().await {
+    ///     if matching_status.matching() {
+    ///         println!("Queryable has matching queriers.");
+    ///     } else {
+    ///         println!("Queryable has NO MORE matching queriers.");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn matching_listener(&self) -> MatchingListenerBuilder<'_, DefaultHandler> {
+        MatchingListenerBuilder {
+            session: &self.inner.session,
+            key_expr: &self.inner.key_expr,
+            destination: self.inner.origin,
+            matching_listeners: &self.inner.matching_listeners,
+            matching_status_type: MatchingStatusType::Queriers,
+            handler: DefaultHandler::default(),
+        }
+    }
+
     fn undeclare_impl(&mut self) -> ZResult<()> {
         // set the flag first to avoid double panic if this function panic
         self.inner.undeclare_on_drop = false;
+        #[cfg(feature = "unstable")]
+        {
+            let ids: Vec<Id> = zlock!(self.inner.matching_listeners).drain().collect();
+            for id in ids {
+                self.inner.session.undeclare_matches_listener_inner(id)?
+            }
+        }
         self.inner.session.close_queryable(self.inner.id)
     }
 
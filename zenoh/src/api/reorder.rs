@@ -0,0 +1,175 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Per-key, per-source FIFO delivery of the samples of a subscriber.
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+use zenoh_config::wrappers::EntityGlobalId;
+use zenoh_runtime::ZRuntime;
+
+use crate::api::{
+    handlers::Callback,
+    sample::{Sample, SourceSn},
+};
+
+/// The maximum number of samples held for a stream, a sample further ahead being considered
+/// as a restart of its source.
+const WINDOW: usize = 1024;
+
+/// The samples of a key from a source, ordered by their source sequence number.
+struct Stream {
+    /// The sequence number of the next sample to deliver.
+    next: SourceSn,
+    /// The samples following `next` with their deadline, indexed by their distance to it.
+    pending: VecDeque<Option<(Instant, Sample)>>,
+    /// Whether a task delivers the samples when their deadline expires.
+    timer: bool,
+}
+
+impl Stream {
+    fn new(next: SourceSn) -> Self {
+        Stream {
+            next,
+            pending: VecDeque::new(),
+            timer: false,
+        }
+    }
+
+    fn push(
+        &mut self,
+        sn: SourceSn,
+        deadline: Instant,
+        sample: Sample,
+        callback: &Callback<Sample>,
+    ) {
+        let mut offset = sn.wrapping_sub(self.next);
+        if (offset as i32) < 0 {
+            tracing::debug!(
+                "Dropping sample {} on {}: a later sample was delivered",
+                sn,
+                sample.key_expr()
+            );
+            return;
+        }
+        if offset as usize >= WINDOW {
+            self.release(None, callback);
+            self.next = sn;
+            offset = 0;
+        }
+        let offset = offset as usize;
+        if self.pending.len() <= offset {
+            self.pending.resize_with(offset + 1, || None);
+        }
+        if self.pending[offset].is_none() {
+            self.pending[offset] = Some((deadline, sample));
+        }
+        self.drain(callback);
+    }
+
+    /// Deliver the samples following `next` without gap.
+    fn drain(&mut self, callback: &Callback<Sample>) {
+        while matches!(self.pending.front(), Some(Some(_))) {
+            if let Some(Some((_, sample))) = self.pending.pop_front() {
+                callback.call(sample);
+            }
+            self.next = self.next.wrapping_add(1);
+        }
+    }
+
+    /// Deliver the samples whose deadline expired at `now`, or all of them if `None`,
+    /// skipping the missing samples before them.
+    fn release(&mut self, now: Option<Instant>, callback: &Callback<Sample>) {
+        let last = self.pending.iter().rposition(|s| match (s, now) {
+            (Some((deadline, _)), Some(now)) => *deadline <= now,
+            (Some(_), None) => true,
+            (None, _) => false,
+        });
+        if let Some(last) = last {
+            for (_, sample) in self.pending.drain(..=last).flatten() {
+                callback.call(sample);
+            }
+            self.next = self.next.wrapping_add(last as SourceSn + 1);
+        }
+        self.drain(callback);
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.pending
+            .iter()
+            .flatten()
+            .map(|(deadline, _)| *deadline)
+            .min()
+    }
+}
+
+/// Wraps `callback` so that the samples are delivered in the order of their source sequence
+/// number per key and per source, see
+/// [`SubscriberBuilder::ordered`](crate::pubsub::SubscriberBuilder::ordered).
+pub(crate) fn reordered(
+    callback: Callback<Sample>,
+    max_delay: Option<Duration>,
+) -> Callback<Sample> {
+    let Some(max_delay) = max_delay else {
+        return callback;
+    };
+    let streams: Arc<Mutex<HashMap<(String, EntityGlobalId), Stream>>> = Arc::default();
+    Callback::new(Arc::new(move |sample: Sample| {
+        let (Some(id), Some(sn)) = (
+            sample.source_info().source_id().copied(),
+            sample.source_info().source_sn(),
+        ) else {
+            callback.call(sample);
+            return;
+        };
+        let key = (sample.key_expr().as_str().to_string(), id);
+        let deadline = Instant::now() + max_delay;
+        // Samples are delivered while holding the lock so that they are delivered in order
+        let mut guard = zlock!(streams);
+        let stream = guard.entry(key.clone()).or_insert_with(|| Stream::new(sn));
+        stream.push(sn, deadline, sample, &callback);
+        if stream.pending.is_empty() || stream.timer {
+            return;
+        }
+        stream.timer = true;
+        drop(guard);
+
+        let (streams, callback) = (streams.clone(), callback.clone());
+        ZRuntime::Net.spawn(async move {
+            loop {
+                let deadline = {
+                    let mut guard = zlock!(streams);
+                    let Some(stream) = guard.get_mut(&key) else {
+                        return;
+                    };
+                    match stream.deadline() {
+                        Some(deadline) => deadline,
+                        None => {
+                            stream.timer = false;
+                            return;
+                        }
+                    }
+                };
+                tokio::time::sleep_until(deadline).await;
+                if let Some(stream) = zlock!(streams).get_mut(&key) {
+                    stream.release(Some(Instant::now()), &callback);
+                }
+            }
+        });
+    }))
+}
@@ -13,9 +13,13 @@
 //
 
 //! Sample primitives
+#[cfg(feature = "sample_metadata")]
+use std::time::SystemTime;
 use std::{convert::TryFrom, fmt};
 
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "sample_metadata")]
+use zenoh_config::Locator;
 use zenoh_config::{qos::PublisherLocalityConf, wrappers::EntityGlobalId};
 #[cfg(feature = "unstable")]
 use zenoh_protocol::core::Reliability;
@@ -28,6 +32,11 @@ use crate::api::{
     builders::sample::QoSBuilderTrait, bytes::ZBytes, encoding::Encoding, key_expr::KeyExpr,
     publisher::Priority,
 };
+#[cfg(feature = "unstable")]
+use crate::api::{
+    signature::{SampleSignature, SignatureStatus},
+    trace::TraceContext,
+};
 
 /// The sequence number of the [`Sample`] from the source.
 pub type SourceSn = u32;
@@ -78,6 +87,12 @@ pub(crate) struct DataInfo {
     pub source_id: Option<EntityGlobalId>,
     pub source_sn: Option<SourceSn>,
     pub qos: QoS,
+    #[cfg(feature = "unstable")]
+    pub trace_context: Option<TraceContext>,
+    #[cfg(feature = "unstable")]
+    pub signature: Option<SampleSignature>,
+    #[cfg(feature = "sample_metadata")]
+    pub reception: Option<ReceptionInfo>,
 }
 
 pub(crate) trait DataInfoIntoSample {
@@ -124,6 +139,14 @@ impl DataInfoIntoSample for DataInfo {
                 source_id: self.source_id,
                 source_sn: self.source_sn,
             },
+            #[cfg(feature = "unstable")]
+            trace_context: self.trace_context,
+            #[cfg(feature = "unstable")]
+            signature: self.signature,
+            #[cfg(feature = "unstable")]
+            signature_status: SignatureStatus::Unverified,
+            #[cfg(feature = "sample_metadata")]
+            reception: self.reception,
             attachment,
         }
     }
@@ -162,6 +185,14 @@ impl DataInfoIntoSample for Option<DataInfo> {
                 reliability,
                 #[cfg(feature = "unstable")]
                 source_info: SourceInfo::empty(),
+                #[cfg(feature = "unstable")]
+                trace_context: None,
+                #[cfg(feature = "unstable")]
+                signature: None,
+                #[cfg(feature = "unstable")]
+                signature_status: SignatureStatus::Unverified,
+                #[cfg(feature = "sample_metadata")]
+                reception: None,
                 attachment,
             }
         }
@@ -227,6 +258,70 @@ impl SourceInfo {
     }
 }
 
+/// Information on the reception of a zenoh [`Sample`] by the local session.
+///
+/// It is only available with the `sample_metadata` feature, for the samples received by the
+/// subscribers.
+#[zenoh_macros::unstable]
+#[cfg(feature = "sample_metadata")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceptionInfo {
+    pub(crate) time: SystemTime,
+    pub(crate) hops: u8,
+    pub(crate) link: Option<(Locator, Locator)>,
+}
+
+#[zenoh_macros::unstable]
+#[cfg(feature = "sample_metadata")]
+impl ReceptionInfo {
+    /// The reception info of a sample published by the local session.
+    pub(crate) fn local() -> Self {
+        ReceptionInfo {
+            time: SystemTime::now(),
+            hops: 0,
+            link: None,
+        }
+    }
+
+    /// The reception info of a sample which traversed `hops` links, the last of them being the
+    /// link the network message being handled by the current thread was received from.
+    pub(crate) fn received(hops: u8) -> Self {
+        match zenoh_transport::common::reception::ingress() {
+            Some(ingress) if hops > 0 => ReceptionInfo {
+                time: ingress.time,
+                hops,
+                link: Some((ingress.src, ingress.dst)),
+            },
+            _ => ReceptionInfo {
+                hops,
+                ..Self::local()
+            },
+        }
+    }
+
+    /// The time the [`Sample`] arrived at the local node.
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// The number of links the [`Sample`] traversed from its publisher, `0` for the samples
+    /// published by the local session.
+    pub fn hops(&self) -> u8 {
+        self.hops
+    }
+
+    /// The locator of the remote end of the link the [`Sample`] was received from.
+    pub fn ingress_src(&self) -> Option<&Locator> {
+        self.link.as_ref().map(|(src, _)| src)
+    }
+
+    /// The locator of the local end of the link the [`Sample`] was received from, or the
+    /// multicast group it was received on.
+    pub fn ingress_dst(&self) -> Option<&Locator> {
+        self.link.as_ref().map(|(_, dst)| dst)
+    }
+}
+
 #[zenoh_macros::unstable]
 impl From<SourceInfo> for Option<zenoh_protocol::zenoh::put::ext::SourceInfoType> {
     fn from(source_info: SourceInfo) -> Option<zenoh_protocol::zenoh::put::ext::SourceInfoType> {
@@ -313,6 +408,8 @@ pub struct SampleFields {
     pub reliability: Reliability,
     #[cfg(feature = "unstable")]
     pub source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub trace_context: Option<TraceContext>,
     pub attachment: Option<ZBytes>,
 }
 
@@ -331,6 +428,8 @@ impl From<Sample> for SampleFields {
             reliability: sample.reliability,
             #[cfg(feature = "unstable")]
             source_info: sample.source_info,
+            #[cfg(feature = "unstable")]
+            trace_context: sample.trace_context,
             attachment: sample.attachment,
         }
     }
@@ -350,6 +449,14 @@ pub struct Sample {
     pub(crate) reliability: Reliability,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
+    #[cfg(feature = "unstable")]
+    pub(crate) signature: Option<SampleSignature>,
+    #[cfg(feature = "unstable")]
+    pub(crate) signature_status: SignatureStatus,
+    #[cfg(feature = "sample_metadata")]
+    pub(crate) reception: Option<ReceptionInfo>,
     pub(crate) attachment: Option<ZBytes>,
 }
 
@@ -418,6 +525,29 @@ impl Sample {
         &self.source_info
     }
 
+    /// Gets the trace context propagated along with this Sample.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        self.trace_context.as_ref()
+    }
+
+    /// Gets the result of the verification of the signature of this Sample by the
+    /// [`SignaturePolicy`](crate::sample::SignaturePolicy) of the subscriber.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn signature_status(&self) -> SignatureStatus {
+        self.signature_status
+    }
+
+    /// Gets infos on the reception of this Sample by a subscriber of the local session.
+    #[zenoh_macros::unstable]
+    #[cfg(feature = "sample_metadata")]
+    #[inline]
+    pub fn reception_info(&self) -> Option<&ReceptionInfo> {
+        self.reception.as_ref()
+    }
+
     /// Gets the sample attachment: a map of key-value pairs, where each key and value are byte-slices.
     #[inline]
     pub fn attachment(&self) -> Option<&ZBytes> {
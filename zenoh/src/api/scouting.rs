@@ -11,13 +11,25 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{fmt, net::SocketAddr, ops::Deref, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use tokio::net::UdpSocket;
 use zenoh_config::wrappers::Hello;
-use zenoh_protocol::core::WhatAmIMatcher;
+use zenoh_link::{LinkConfigurator, LinkManagerBuilderUnicast, Locator};
+use zenoh_protocol::{
+    core::{parameters::Parameters, EndPoint, WhatAmIMatcher},
+    scouting::HelloProto,
+};
 use zenoh_result::ZResult;
 use zenoh_task::TerminatableTask;
+use zenoh_util::net::Subnet;
 
 use crate::{
     api::{
@@ -136,12 +148,143 @@ impl<Receiver> Scout<Receiver> {
     }
 }
 
+/// Returns the IP address of `locator`, if its address is an IP socket address.
+fn locator_ip(locator: &Locator) -> Option<IpAddr> {
+    let (ip, _port) = locator.address().as_str().rsplit_once(':')?;
+    let ip = ip.trim_start_matches('[').trim_end_matches(']');
+    // Strip the zone of IPv6 link local addresses
+    let ip = ip.split_once('%').map_or(ip, |(ip, _zone)| ip);
+    ip.parse().ok()
+}
+
+/// The filtering and reachability probing of the locators of the received [`Hello`] messages.
+pub(crate) struct HelloFilter {
+    protocols: Vec<String>,
+    subnets: Vec<Subnet>,
+    probe: Option<(Duration, HashMap<String, String>)>,
+}
+
+impl HelloFilter {
+    pub(crate) fn new(
+        protocols: Vec<String>,
+        subnets: &[String],
+        probe: Option<Duration>,
+        config: &Config,
+    ) -> ZResult<Self> {
+        let subnets = subnets
+            .iter()
+            .map(|s| s.parse())
+            .collect::<ZResult<Vec<Subnet>>>()?;
+        // Probes use the same link configuration (e.g. TLS certificates) as the sessions
+        let probe = probe.map(|timeout| {
+            (
+                timeout,
+                LinkConfigurator::default().configurations(&config.0).0,
+            )
+        });
+        Ok(HelloFilter {
+            protocols,
+            subnets,
+            probe,
+        })
+    }
+
+    fn matches(&self, locator: &Locator) -> bool {
+        (self.protocols.is_empty()
+            || self
+                .protocols
+                .iter()
+                .any(|p| p.as_str() == locator.protocol().as_str()))
+            && (self.subnets.is_empty()
+                || locator_ip(locator)
+                    .is_some_and(|ip| self.subnets.iter().any(|s| s.contains(&ip))))
+    }
+
+    /// Returns the estimated latency to `locator`, or `None` if it is unreachable.
+    async fn probe(
+        locator: &Locator,
+        timeout: Duration,
+        configs: &HashMap<String, String>,
+    ) -> Option<Duration> {
+        let mut endpoint = locator.to_endpoint();
+        if let Some(config) = configs.get(endpoint.protocol().as_str()) {
+            let mut config = Parameters::from(config.as_str());
+            config.extend_from_iter(endpoint.config().iter());
+            endpoint = EndPoint::new(
+                endpoint.protocol(),
+                endpoint.address(),
+                endpoint.metadata(),
+                config.as_str(),
+            )
+            .ok()?;
+        }
+        let (sender, _receiver) = flume::bounded(1);
+        let manager = LinkManagerBuilderUnicast::make(sender, endpoint.protocol().as_str()).ok()?;
+        let start = Instant::now();
+        match tokio::time::timeout(timeout, manager.new_link(endpoint)).await {
+            Ok(Ok(link)) => {
+                let latency = start.elapsed();
+                let _ = link.close().await;
+                Some(latency)
+            }
+            Ok(Err(e)) => {
+                tracing::debug!("Probe of {} failed: {}", locator, e);
+                None
+            }
+            Err(_) => {
+                tracing::debug!("Probe of {} timed out", locator);
+                None
+            }
+        }
+    }
+
+    /// Applies the filters and the probes to `hello`, returns `None` if none of its locators
+    /// remain.
+    async fn apply(&self, mut hello: HelloProto) -> Option<Hello> {
+        if !self.protocols.is_empty() || !self.subnets.is_empty() {
+            hello.locators.retain(|l| self.matches(l));
+            if hello.locators.is_empty() {
+                tracing::trace!("Ignoring Hello from {}: no matching locator", hello.zid);
+                return None;
+            }
+        }
+        let Some((timeout, configs)) = &self.probe else {
+            return Some(hello.into());
+        };
+        let latencies = futures::future::join_all(
+            hello
+                .locators
+                .iter()
+                .map(|l| Self::probe(l, *timeout, configs)),
+        )
+        .await;
+        let mut reachable: Vec<(Locator, Duration)> = hello
+            .locators
+            .drain(..)
+            .zip(latencies)
+            .filter_map(|(l, latency)| latency.map(|latency| (l, latency)))
+            .collect();
+        reachable.sort_by_key(|(_, latency)| *latency);
+        let latency = match reachable.first() {
+            Some((_, latency)) => *latency,
+            None => {
+                tracing::trace!("Ignoring Hello from {}: no reachable locator", hello.zid);
+                return None;
+            }
+        };
+        hello.locators = reachable.into_iter().map(|(l, _)| l).collect();
+        Some((hello, latency).into())
+    }
+}
+
 pub(crate) fn _scout(
     what: WhatAmIMatcher,
     config: Config,
+    filter: HelloFilter,
     callback: Callback<Hello>,
 ) -> ZResult<ScoutInner> {
     tracing::trace!("scout({}, {})", what, &config);
+    let filter = Arc::new(filter);
     let default_addr = SocketAddr::from(zenoh_config::defaults::scouting::multicast::address);
     let addr = config
         .0
@@ -174,8 +317,11 @@ pub(crate) fn _scout(
                 async move {
                     let scout = Runtime::scout(&sockets, what, &addr, move |hello| {
                         let callback = callback.clone();
+                        let filter = filter.clone();
                         async move {
-                            callback.call(hello.into());
+                            if let Some(hello) = filter.apply(hello).await {
+                                callback.call(hello);
+                            }
                             Loop::Continue
                         }
                     });
@@ -231,6 +377,78 @@ where
     ScoutBuilder {
         what: what.into(),
         config: config.try_into().map_err(|e| e.into()),
+        protocols: vec![],
+        subnets: vec![],
+        probe: None,
         handler: DefaultHandler::default(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
+
+    use super::*;
+
+    fn hello(locators: &[&str]) -> HelloProto {
+        HelloProto {
+            version: zenoh_protocol::VERSION,
+            whatami: WhatAmI::Router,
+            zid: ZenohIdProto::default(),
+            locators: locators.iter().map(|l| l.parse().unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn scout_locator_ip() {
+        let ip = |l: &str| locator_ip(&l.parse().unwrap());
+        assert_eq!(ip("tcp/192.168.1.1:7447"), "192.168.1.1".parse().ok());
+        assert_eq!(ip("udp/[fe80::1%eth0]:7447"), "fe80::1".parse().ok());
+        assert_eq!(ip("tcp/localhost:7447"), None);
+    }
+
+    #[tokio::test]
+    async fn scout_filter() {
+        let config = Config::default();
+        let filter =
+            HelloFilter::new(vec!["tcp".into()], &["10.0.0.0/8".into()], None, &config).unwrap();
+        let res = filter
+            .apply(hello(&[
+                "udp/10.0.0.1:7447",
+                "tcp/192.168.1.1:7447",
+                "tcp/10.0.0.1:7447",
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(res.locators(), ["tcp/10.0.0.1:7447".parse().unwrap()]);
+        assert!(filter.apply(hello(&["udp/10.0.0.1:7447"])).await.is_none());
+        assert!(
+            HelloFilter::new(vec![], &["10.0.0.0".into(), "nope".into()], None, &config).is_err()
+        );
+    }
+
+    #[cfg(all(feature = "unstable", feature = "transport_tcp"))]
+    #[tokio::test]
+    async fn scout_probe() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let reachable = format!("tcp/{}", listener.local_addr().unwrap());
+        let unreachable = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("tcp/{}", listener.local_addr().unwrap())
+        };
+        let filter = HelloFilter::new(
+            vec![],
+            &[],
+            Some(Duration::from_secs(1)),
+            &Config::default(),
+        )
+        .unwrap();
+        let res = filter
+            .apply(hello(&[&unreachable, &reachable]))
+            .await
+            .unwrap();
+        assert_eq!(res.locators(), [reachable.parse().unwrap()]);
+        assert!(res.latency().is_some());
+        assert!(filter.apply(hello(&[&unreachable])).await.is_none());
+    }
+}
@@ -20,19 +20,21 @@ use std::{
         atomic::{AtomicU16, Ordering},
         Arc, Mutex, RwLock,
     },
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
-#[zenoh_macros::internal]
-use ref_cast::ref_cast_custom;
-use ref_cast::RefCastCustom;
+use ref_cast::{ref_cast_custom, RefCastCustom};
 use tracing::{error, info, trace, warn};
 use uhlc::Timestamp;
 #[cfg(feature = "internal")]
 use uhlc::HLC;
+#[cfg(feature = "unstable")]
+use uhlc::NTP64;
 use zenoh_buffers::ZBuf;
 use zenoh_collections::SingleOrVec;
+#[cfg(feature = "unstable")]
+use zenoh_config::AclMessage;
 use zenoh_config::{qos::PublisherQoSConfig, unwrap_or_default, wrappers::ZenohId};
 use zenoh_core::{zconfigurable, zread, Resolve, ResolveClosure, ResolveFuture, Wait};
 use zenoh_keyexpr::keyexpr_tree::KeBoxTree;
@@ -68,15 +70,27 @@ use zenoh_shm::api::client_storage::ShmClientStorage;
 use zenoh_task::TaskController;
 
 use super::builders::close::{CloseBuilder, Closeable, Closee};
+#[cfg(feature = "plugins")]
+use crate::api::loader::StaticPlugin;
+#[cfg(feature = "sample_metadata")]
+use crate::api::sample::ReceptionInfo;
 #[cfg(feature = "unstable")]
 use crate::api::selector::ZenohParameters;
 #[cfg(feature = "unstable")]
 use crate::api::{
-    builders::querier::QuerierBuilder,
+    acl::AclCheckBuilder,
+    builders::{querier::QuerierBuilder, query::SessionGetManyBuilder},
+    capture::ReplayBuilder,
+    clock::ClockInfo,
+    encryption::{self, KeyProvider},
     matching::{MatchingListenerState, MatchingStatus, MatchingStatusType},
+    metrics::{snapshot, MetricsBuilder, MetricsExporter, SessionMetrics},
+    probe::ProbeBuilder,
     querier::QuerierState,
-    query::ReplyKeyExpr,
+    query::{PendingQuery, ReplyKeyExpr},
     sample::SourceInfo,
+    signature::{self, SignatureStatus, SigningKey},
+    trace::TraceContext,
 };
 use crate::{
     api::{
@@ -122,6 +136,7 @@ zconfigurable! {
     pub(crate) static ref API_QUERY_RECEPTION_CHANNEL_SIZE: usize = 256;
     pub(crate) static ref API_REPLY_EMISSION_CHANNEL_SIZE: usize = 256;
     pub(crate) static ref API_REPLY_RECEPTION_CHANNEL_SIZE: usize = 256;
+    pub(crate) static ref API_INTERNED_KEYEXPRS_SIZE: usize = 1024;
 }
 
 pub(crate) struct SessionState {
@@ -131,6 +146,8 @@ pub(crate) struct SessionState {
     pub(crate) liveliness_qid_counter: AtomicRequestId,
     pub(crate) local_resources: HashMap<ExprId, Resource>,
     pub(crate) remote_resources: HashMap<ExprId, Resource>,
+    // The key expressions received without declaration, along with their matching subscribers
+    pub(crate) interned_keyexprs: HashMap<OwnedKeyExpr, ResourceNode>,
     #[cfg(feature = "unstable")]
     pub(crate) remote_subscribers: HashMap<SubscriberId, KeyExpr<'static>>,
     pub(crate) publishers: HashMap<Id, PublisherState>,
@@ -146,10 +163,15 @@ pub(crate) struct SessionState {
     #[cfg(feature = "unstable")]
     pub(crate) matching_listeners: HashMap<Id, Arc<MatchingListenerState>>,
     pub(crate) queries: HashMap<RequestId, QueryState>,
+    #[cfg(feature = "unstable")]
+    pub(crate) duplicate_replies: u64,
     pub(crate) liveliness_queries: HashMap<InterestId, LivelinessQueryState>,
     pub(crate) aggregated_subscribers: Vec<OwnedKeyExpr>,
     pub(crate) aggregated_publishers: Vec<OwnedKeyExpr>,
     pub(crate) publisher_qos_tree: KeBoxTree<PublisherQoSConfig>,
+    pub(crate) publisher_qos_defaults_tree: KeBoxTree<PublisherQoSConfig>,
+    #[cfg(feature = "unstable")]
+    pub(crate) key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 impl SessionState {
@@ -157,6 +179,7 @@ impl SessionState {
         aggregated_subscribers: Vec<OwnedKeyExpr>,
         aggregated_publishers: Vec<OwnedKeyExpr>,
         publisher_qos_tree: KeBoxTree<PublisherQoSConfig>,
+        publisher_qos_defaults_tree: KeBoxTree<PublisherQoSConfig>,
     ) -> SessionState {
         SessionState {
             primitives: None,
@@ -165,6 +188,7 @@ impl SessionState {
             liveliness_qid_counter: AtomicRequestId::new(0),
             local_resources: HashMap::new(),
             remote_resources: HashMap::new(),
+            interned_keyexprs: HashMap::new(),
             #[cfg(feature = "unstable")]
             remote_subscribers: HashMap::new(),
             publishers: HashMap::new(),
@@ -180,10 +204,15 @@ impl SessionState {
             #[cfg(feature = "unstable")]
             matching_listeners: HashMap::new(),
             queries: HashMap::new(),
+            #[cfg(feature = "unstable")]
+            duplicate_replies: 0,
             liveliness_queries: HashMap::new(),
             aggregated_subscribers,
             aggregated_publishers,
             publisher_qos_tree,
+            publisher_qos_defaults_tree,
+            #[cfg(feature = "unstable")]
+            key_provider: None,
         }
     }
 }
@@ -290,6 +319,27 @@ impl SessionState {
         }
     }
 
+    /// Interns a key expression on which data is received, so that the next samples are matched
+    /// to their subscribers without comparing key expressions.
+    fn intern_keyexpr(&mut self, key_expr: OwnedKeyExpr) {
+        if self.interned_keyexprs.len() >= *API_INTERNED_KEYEXPRS_SIZE {
+            // The usage of the interned key expressions is not tracked, start over
+            self.interned_keyexprs.clear();
+        }
+        let mut res = ResourceNode::new(key_expr.clone());
+        for kind in [
+            SubscriberKind::Subscriber,
+            SubscriberKind::LivelinessSubscriber,
+        ] {
+            for sub in self.subscribers(kind).values() {
+                if key_expr.intersects(&sub.key_expr) {
+                    res.subscribers_mut(kind).push(sub.clone());
+                }
+            }
+        }
+        self.interned_keyexprs.insert(key_expr, res);
+    }
+
     pub(crate) fn subscribers(&self, kind: SubscriberKind) -> &HashMap<Id, Arc<SubscriberState>> {
         match kind {
             SubscriberKind::Subscriber => &self.subscribers,
@@ -307,18 +357,61 @@ impl SessionState {
         }
     }
 
+    fn register_publisher<'a>(
+        &mut self,
+        mut pub_state: PublisherState,
+        key_expr: &'a KeyExpr,
+    ) -> Option<KeyExpr<'a>> {
+        let declared_pub = (pub_state.destination != Locality::SessionLocal)
+            .then(|| {
+                match self
+                    .aggregated_publishers
+                    .iter()
+                    .find(|s| s.includes(key_expr))
+                {
+                    Some(join_pub) => {
+                        if let Some(joined_pub) = self.publishers.values().find(|p| {
+                            p.destination != Locality::SessionLocal
+                                && join_pub.includes(&p.key_expr)
+                        }) {
+                            pub_state.remote_id = joined_pub.remote_id;
+                            None
+                        } else {
+                            Some(join_pub.clone().into())
+                        }
+                    }
+                    None => {
+                        if let Some(twin_pub) = self.publishers.values().find(|p| {
+                            p.destination != Locality::SessionLocal && p.key_expr == *key_expr
+                        }) {
+                            pub_state.remote_id = twin_pub.remote_id;
+                            None
+                        } else {
+                            Some(key_expr.clone())
+                        }
+                    }
+                }
+            })
+            .flatten();
+        self.publishers.insert(pub_state.id, pub_state);
+        declared_pub
+    }
+
     #[cfg(feature = "unstable")]
     fn register_querier<'a>(
         &mut self,
         id: EntityId,
         key_expr: &'a KeyExpr,
         destination: Locality,
+        qos: QoS,
     ) -> Option<KeyExpr<'a>> {
         let mut querier_state = QuerierState {
             id,
             remote_id: id,
             key_expr: key_expr.clone().into_owned(),
             destination,
+            qos,
+            declared_at: SystemTime::now(),
         };
 
         let declared_querier =
@@ -351,6 +444,7 @@ impl SessionState {
             key_expr: key_expr.clone().into_owned(),
             origin,
             callback,
+            declared_at: SystemTime::now(),
         };
 
         let declared_sub = origin != Locality::SessionLocal;
@@ -416,6 +510,12 @@ impl SessionState {
                     .push(sub_state.clone());
             }
         }
+        for res in self.interned_keyexprs.values_mut() {
+            if key_expr.intersects(&res.key_expr) {
+                res.subscribers_mut(SubscriberKind::Subscriber)
+                    .push(sub_state.clone());
+            }
+        }
 
         (sub_state, declared_sub)
     }
@@ -561,7 +661,6 @@ impl Session {
         WeakSession::new(&self.0)
     }
 
-    #[cfg(feature = "internal")]
     #[ref_cast_custom]
     pub(crate) const fn ref_cast(from: &Arc<SessionInner>) -> &Self;
 }
@@ -676,11 +775,13 @@ impl Session {
             let router = runtime.router();
             let config = runtime.config().lock();
             let publisher_qos = config.0.qos().publication().clone();
+            let publisher_qos_defaults = config.0.qos().defaults().clone();
             drop(config);
             let state = RwLock::new(SessionState::new(
                 aggregated_subscribers,
                 aggregated_publishers,
                 publisher_qos.into(),
+                publisher_qos_defaults.into(),
             ));
             let session = Session(Arc::new(SessionInner {
                 weak_counter: Mutex::new(0),
@@ -693,6 +794,10 @@ impl Session {
 
             runtime.new_handler(Arc::new(admin::Handler::new(session.downgrade())));
 
+            router
+                .tables
+                .dead_letters
+                .add_session(Arc::new(session.downgrade()));
             let primitives = Some(router.new_primitives(Arc::new(session.downgrade())));
             zwrite!(session.0.state).primitives = primitives;
 
@@ -766,7 +871,7 @@ impl Session {
     /// assert!(session.is_closed());
     /// # }
     pub fn is_closed(&self) -> bool {
-        zread!(self.0.state).primitives.is_none()
+        zread!(self.0.state).primitives.is_none() || self.0.runtime.is_closed()
     }
 
     pub fn undeclare<'a, T>(&'a self, decl: T) -> impl Resolve<ZResult<()>> + 'a
@@ -833,6 +938,28 @@ impl Session {
             }
         }
     }
+
+    /// Get the state of the hybrid logical clock (HLC) of the session: its current time, the
+    /// maximum delta it accepts from the received timestamps, and the received timestamps which
+    /// exceeded it.
+    ///
+    /// The HLC can use an external physical clock, see
+    /// [`OpenBuilder::with_clock`](crate::session::OpenBuilder::with_clock).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let clock = session.clock();
+    /// println!("{} drifting timestamps received", clock.drifts);
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn clock(&self) -> ClockInfo {
+        ClockInfo::new(&self.0.runtime, self.new_timestamp())
+    }
 }
 
 impl Session {
@@ -888,6 +1015,12 @@ impl Session {
             key_expr: TryIntoKeyExpr::try_into(key_expr).map_err(Into::into),
             origin: Locality::default(),
             handler: DefaultHandler::default(),
+            #[cfg(feature = "unstable")]
+            signature_policy: None,
+            #[cfg(feature = "unstable")]
+            max_delay: None,
+            #[cfg(feature = "unstable")]
+            key_tracking: None,
         }
     }
 
@@ -929,6 +1062,7 @@ impl Session {
             session: self,
             key_expr: key_expr.try_into().map_err(Into::into),
             complete: false,
+            load: 0,
             origin: Locality::default(),
             handler: DefaultHandler::default(),
         }
@@ -970,7 +1104,14 @@ impl Session {
             #[cfg(feature = "unstable")]
             reliability: Reliability::DEFAULT,
             destination: Locality::default(),
+            #[cfg(feature = "unstable")]
+            signing_key: None,
+            #[cfg(feature = "unstable")]
+            hop_limit: None,
+            #[cfg(feature = "unstable")]
+            deadline: None,
         }
+        .apply_qos_defaults()
     }
 
     /// Create a [`Querier`](crate::query::Querier) for the given key expression.
@@ -1013,6 +1154,7 @@ impl Session {
             target: QueryTarget::default(),
             consolidation: QueryConsolidation::default(),
             timeout,
+            reply_timeout: None,
             #[cfg(feature = "unstable")]
             accept_replies: ReplyKeyExpr::default(),
         }
@@ -1036,6 +1178,173 @@ impl Session {
     pub fn liveliness(&self) -> Liveliness<'_> {
         Liveliness { session: self }
     }
+
+    /// Measure the round-trip time, the one-way latency and the throughput to the zenoh node
+    /// with the given [`ZenohId`], over the current links and for each requested priority.
+    ///
+    /// The admin space of the probed node must be enabled, as its echo service is used.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let zid = "1".parse().unwrap();
+    /// for probe in session.probe(zid).count(100).await.unwrap() {
+    ///     println!("{:?}: {:?}", probe.priority, probe.rtt_avg);
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn probe(&self, zid: ZenohId) -> ProbeBuilder<'_> {
+        ProbeBuilder::new(self, zid)
+    }
+
+    /// Check whether the `access_control` configuration of the session allows a message on a key
+    /// expression, and which rule decides it, to test ACL policies before rolling them out.
+    ///
+    /// The same check is available in the admin space of the nodes, see
+    /// [`AclCheckBuilder`](crate::config::AclCheckBuilder).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::config::{AclMessage, InterceptorFlow};
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let decision = session
+    ///     .check_acl(AclMessage::DeclareSubscriber, "key/expression")
+    ///     .flow(InterceptorFlow::Egress)
+    ///     .interface("lo")
+    ///     .await
+    ///     .unwrap();
+    /// println!("{:?} by rule {:?}", decision.permission, decision.rule);
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn check_acl<'b, TryIntoKeyExpr>(
+        &self,
+        message: AclMessage,
+        key_expr: TryIntoKeyExpr,
+    ) -> AclCheckBuilder<'_, 'b>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<zenoh_result::Error>,
+    {
+        AclCheckBuilder::new(self, message, key_expr.try_into().map_err(Into::into))
+    }
+
+    /// Replay into the session the publications of a capture file written by a node
+    /// configured with `capture/file`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let count = session.replay("zenoh.zcap").paced(true).await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn replay<P: AsRef<std::path::Path>>(&self, path: P) -> ReplayBuilder<'_> {
+        ReplayBuilder::new(self, path.as_ref().to_path_buf())
+    }
+
+    /// List the queries issued by the session which didn't receive their final reply yet,
+    /// e.g. to detect and [`finalize`](PendingQuery::finalize) the leaked ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// for query in session.pending_queries() {
+    ///     println!("{} ({:?}, {} replies)", query.selector(), query.elapsed(), query.replies());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn pending_queries(&self) -> Vec<PendingQuery> {
+        let state = zread!(self.0.state);
+        state
+            .queries
+            .iter()
+            .map(|(id, query)| PendingQuery {
+                session: self.downgrade(),
+                id: *id,
+                selector: query.selector().into_owned(),
+                elapsed: query.started.elapsed(),
+                replies: query.nb_replies,
+            })
+            .collect()
+    }
+
+    /// Take a snapshot of the core metrics of the session: pending queries, key expression
+    /// tables sizes, transmission queues depths and HLC drift.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let metrics = session.metrics().await;
+    /// println!("{} pending queries", metrics.pending_queries);
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn metrics(&self) -> MetricsBuilder<'_> {
+        MetricsBuilder { session: &self.0 }
+    }
+
+    /// Periodically call `callback` with a snapshot of the core metrics of the session, e.g. to
+    /// forward them to an application's telemetry, until the returned [`MetricsExporter`] is
+    /// dropped or the session is closed.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use std::time::Duration;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let _exporter = session
+    ///     .metrics_exporter(Duration::from_secs(10), |metrics| {
+    ///         println!("{} pending queries", metrics.pending_queries)
+    ///     })
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn metrics_exporter<F>(&self, period: Duration, callback: F) -> ZResult<MetricsExporter>
+    where
+        F: Fn(SessionMetrics) + Send + Sync + 'static,
+    {
+        if period.is_zero() {
+            bail!("The period of a metrics exporter must be positive");
+        }
+        let token = self.0.task_controller.get_cancellation_token();
+        self.0
+            .task_controller
+            .spawn_with_rt(zenoh_runtime::ZRuntime::Net, {
+                let session = self.downgrade();
+                let token = token.clone();
+                async move {
+                    let mut interval = tokio::time::interval(period);
+                    loop {
+                        tokio::select! {
+                            _ = interval.tick() => callback(snapshot(&session).await),
+                            _ = token.cancelled() => break,
+                        }
+                    }
+                }
+            });
+        Ok(MetricsExporter { token })
+    }
 }
 
 impl Session {
@@ -1133,6 +1442,8 @@ impl Session {
             attachment: None,
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            trace_context: None,
         }
     }
 
@@ -1167,6 +1478,8 @@ impl Session {
             attachment: None,
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            trace_context: None,
         }
     }
     /// Query data from the matching queryables in the system.
@@ -1212,11 +1525,71 @@ impl Session {
             qos: qos.into(),
             destination: Locality::default(),
             timeout,
+            reply_timeout: None,
             value: None,
             attachment: None,
             handler: DefaultHandler::default(),
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            trace_context: None,
+        }
+    }
+
+    /// Query data for several selectors at once.
+    ///
+    /// The queries are issued together, so that their requests are batched by the transports,
+    /// and their replies are delivered to a single handler. Each reply is tagged with the index
+    /// of the selector it answers, the handler being dropped once all the queries received
+    /// their final reply. If one of the selectors is invalid, no query is issued.
+    ///
+    /// # Arguments
+    ///
+    /// * `selectors` - The selections of resources to query
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let selectors = ["key/expression/a", "key/expression/b"];
+    /// let replies = session.get_many(selectors).await.unwrap();
+    /// while let Ok(reply) = replies.recv_async().await {
+    ///     println!(">> Received {:?} for {}", reply.reply().result(), selectors[reply.tag()]);
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn get_many<'a, 'b: 'a, I, TryIntoSelector>(
+        &'a self,
+        selectors: I,
+    ) -> SessionGetManyBuilder<'a, 'b, DefaultHandler>
+    where
+        I: IntoIterator<Item = TryIntoSelector>,
+        TryIntoSelector: TryInto<Selector<'b>>,
+        <TryIntoSelector as TryInto<Selector<'b>>>::Error: Into<zenoh_result::Error>,
+    {
+        let selectors = selectors
+            .into_iter()
+            .map(|s| s.try_into().map_err(Into::into))
+            .collect();
+        let timeout = {
+            let conf = &self.0.runtime.config().lock().0;
+            Duration::from_millis(unwrap_or_default!(conf.queries_default_timeout()))
+        };
+        let qos: QoS = request::ext::QoSType::REQUEST.into();
+        SessionGetManyBuilder {
+            session: self,
+            selectors,
+            target: QueryTarget::DEFAULT,
+            consolidation: QueryConsolidation::DEFAULT,
+            qos: qos.into(),
+            destination: Locality::default(),
+            timeout,
+            reply_timeout: None,
+            attachment: None,
+            handler: DefaultHandler::default(),
         }
     }
 }
@@ -1226,6 +1599,9 @@ impl Session {
     pub(super) fn new(
         config: Config,
         #[cfg(feature = "shared-memory")] shm_clients: Option<Arc<ShmClientStorage>>,
+        #[cfg(feature = "plugins")] static_plugins: Vec<StaticPlugin>,
+        #[cfg(feature = "unstable")] key_provider: Option<Arc<dyn KeyProvider>>,
+        #[cfg(feature = "unstable")] clock: Option<fn() -> NTP64>,
     ) -> impl Resolve<ZResult<Session>> {
         ResolveFuture::new(async move {
             tracing::debug!("Config: {:?}", &config);
@@ -1237,6 +1613,14 @@ impl Session {
             {
                 runtime = runtime.shm_clients(shm_clients);
             }
+            #[cfg(feature = "plugins")]
+            {
+                runtime = runtime.static_plugins(static_plugins);
+            }
+            #[cfg(feature = "unstable")]
+            if let Some(clock) = clock {
+                runtime = runtime.clock(clock);
+            }
             let mut runtime = runtime.build().await?;
 
             let session = Self::init(
@@ -1246,6 +1630,10 @@ impl Session {
                 true,
             )
             .await;
+            #[cfg(feature = "unstable")]
+            {
+                zwrite!(session.0.state).key_provider = key_provider;
+            }
             runtime.start().await?;
             Ok(session)
         })
@@ -1312,51 +1700,21 @@ impl SessionInner {
         &self,
         key_expr: KeyExpr,
         destination: Locality,
+        qos: QoS,
     ) -> ZResult<EntityId> {
         let mut state = zwrite!(self.state);
         tracing::trace!("declare_publisher({:?})", key_expr);
         let id = self.runtime.next_id();
 
-        let mut pub_state = PublisherState {
+        let pub_state = PublisherState {
             id,
             remote_id: id,
             key_expr: key_expr.clone().into_owned(),
             destination,
+            qos,
+            declared_at: SystemTime::now(),
         };
-
-        let declared_pub = (destination != Locality::SessionLocal)
-            .then(|| {
-                match state
-                    .aggregated_publishers
-                    .iter()
-                    .find(|s| s.includes(&key_expr))
-                {
-                    Some(join_pub) => {
-                        if let Some(joined_pub) = state.publishers.values().find(|p| {
-                            p.destination != Locality::SessionLocal
-                                && join_pub.includes(&p.key_expr)
-                        }) {
-                            pub_state.remote_id = joined_pub.remote_id;
-                            None
-                        } else {
-                            Some(join_pub.clone().into())
-                        }
-                    }
-                    None => {
-                        if let Some(twin_pub) = state.publishers.values().find(|p| {
-                            p.destination != Locality::SessionLocal && p.key_expr == key_expr
-                        }) {
-                            pub_state.remote_id = twin_pub.remote_id;
-                            None
-                        } else {
-                            Some(key_expr.clone())
-                        }
-                    }
-                }
-            })
-            .flatten();
-
-        state.publishers.insert(id, pub_state);
+        let declared_pub = state.register_publisher(pub_state, &key_expr);
 
         if let Some(res) = declared_pub {
             let primitives = state.primitives()?;
@@ -1374,6 +1732,62 @@ impl SessionInner {
         Ok(id)
     }
 
+    /// Moves the publisher `pid` to `key_expr`, keeping its id and QoS.
+    ///
+    /// The publisher is declared on the new key expression before being undeclared on the
+    /// former one, so that the network never sees a gap between both declarations.
+    pub(crate) fn rekey_publisher_inner(&self, pid: Id, key_expr: KeyExpr) -> ZResult<()> {
+        let mut state = zwrite!(self.state);
+        let primitives = state.primitives()?;
+        let Some(former) = state.publishers.remove(&pid) else {
+            bail!("Unable to find publisher");
+        };
+        tracing::trace!("rekey_publisher({:?}, {:?})", former, key_expr);
+
+        let pub_state = PublisherState {
+            id: former.id,
+            remote_id: self.runtime.next_id(),
+            key_expr: key_expr.clone().into_owned(),
+            destination: former.destination,
+            qos: former.qos,
+            declared_at: former.declared_at,
+        };
+        let remote_id = pub_state.remote_id;
+        let declared_pub = state.register_publisher(pub_state, &key_expr);
+        // Note: there might be several publishers sharing the former declaration
+        let undeclared_pub = former.destination != Locality::SessionLocal
+            && !state.publishers.values().any(|p| {
+                p.destination != Locality::SessionLocal && p.remote_id == former.remote_id
+            });
+        drop(state);
+
+        if let Some(res) = declared_pub {
+            primitives.send_interest(Interest {
+                id: remote_id,
+                mode: InterestMode::CurrentFuture,
+                options: InterestOptions::KEYEXPRS + InterestOptions::SUBSCRIBERS,
+                wire_expr: Some(res.to_wire(self).to_owned()),
+                ext_qos: network::ext::QoSType::DEFAULT,
+                ext_tstamp: None,
+                ext_nodeid: network::ext::NodeIdType::DEFAULT,
+            });
+        }
+        if undeclared_pub {
+            primitives.send_interest(Interest {
+                id: former.remote_id,
+                mode: InterestMode::Final,
+                // Note: InterestMode::Final options are undefined in the current protocol specification,
+                //       they are initialized here for internal use by local egress interceptors.
+                options: InterestOptions::SUBSCRIBERS,
+                wire_expr: None,
+                ext_qos: declare::ext::QoSType::DEFAULT,
+                ext_tstamp: None,
+                ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+            });
+        }
+        Ok(())
+    }
+
     pub(crate) fn undeclare_publisher_inner(&self, pid: Id) -> ZResult<()> {
         let mut state = zwrite!(self.state);
         let Ok(primitives) = state.primitives() else {
@@ -1409,14 +1823,15 @@ impl SessionInner {
 
     #[cfg(feature = "unstable")]
     pub(crate) fn declare_querier_inner(
-        &self,
+        self: &Arc<Self>,
         key_expr: KeyExpr,
         destination: Locality,
+        qos: QoS,
     ) -> ZResult<EntityId> {
         tracing::trace!("declare_querier({:?})", key_expr);
         let mut state = zwrite!(self.state);
         let id = self.runtime.next_id();
-        let declared_querier = state.register_querier(id, &key_expr, destination);
+        let declared_querier = state.register_querier(id, &key_expr, destination, qos);
         if let Some(res) = declared_querier {
             let primitives = state.primitives()?;
             drop(state);
@@ -1429,12 +1844,16 @@ impl SessionInner {
                 ext_tstamp: None,
                 ext_nodeid: network::ext::NodeIdType::DEFAULT,
             });
+        } else {
+            drop(state);
         }
+        let state = zread!(self.state);
+        self.update_matching_status(&state, &key_expr, MatchingStatusType::Queriers, true);
         Ok(id)
     }
 
     #[cfg(feature = "unstable")]
-    pub(crate) fn undeclare_querier_inner(&self, pid: Id) -> ZResult<()> {
+    pub(crate) fn undeclare_querier_inner(self: &Arc<Self>, pid: Id) -> ZResult<()> {
         let mut state = zwrite!(self.state);
         let Ok(primitives) = state.primitives() else {
             return Ok(());
@@ -1458,8 +1877,19 @@ impl SessionInner {
                         ext_tstamp: None,
                         ext_nodeid: declare::ext::NodeIdType::DEFAULT,
                     });
+                } else {
+                    drop(state);
                 }
+            } else {
+                drop(state);
             }
+            let state = zread!(self.state);
+            self.update_matching_status(
+                &state,
+                &querier_state.key_expr,
+                MatchingStatusType::Queriers,
+                false,
+            );
             Ok(())
         } else {
             Err(zerror!("Unable to find querier").into())
@@ -1557,6 +1987,10 @@ impl SessionInner {
                 res.subscribers_mut(kind)
                     .retain(|sub| sub.id != sub_state.id);
             }
+            for res in state.interned_keyexprs.values_mut() {
+                res.subscribers_mut(kind)
+                    .retain(|sub| sub.id != sub_state.id);
+            }
 
             match kind {
                 SubscriberKind::Subscriber => {
@@ -1636,6 +2070,7 @@ impl SessionInner {
         self: &Arc<Self>,
         key_expr: &KeyExpr,
         complete: bool,
+        load: u8,
         origin: Locality,
         callback: Callback<Query>,
     ) -> ZResult<Arc<QueryableState>> {
@@ -1649,6 +2084,7 @@ impl SessionInner {
             complete,
             origin,
             callback,
+            declared_at: SystemTime::now(),
         });
 
         state.queryables.insert(id, qable_state.clone());
@@ -1658,6 +2094,7 @@ impl SessionInner {
             let qabl_info = QueryableInfoType {
                 complete,
                 distance: 0,
+                load,
             };
             primitives.send_declare(Declare {
                 interest_id: None,
@@ -1688,6 +2125,37 @@ impl SessionInner {
         Ok(qable_state)
     }
 
+    #[zenoh_macros::unstable]
+    pub(crate) fn set_queryable_load(self: &Arc<Self>, qid: Id, load: u8) -> ZResult<()> {
+        let state = zread!(self.state);
+        let Some(qable_state) = state.queryables.get(&qid).cloned() else {
+            bail!("Unable to find queryable");
+        };
+        if qable_state.origin == Locality::SessionLocal {
+            return Ok(());
+        }
+        let primitives = state.primitives()?;
+        drop(state);
+        trace!("set_queryable_load({:?}, {})", qable_state, load);
+        // Declaring the queryable again with the same id updates its info in the routers
+        primitives.send_declare(Declare {
+            interest_id: None,
+            ext_qos: declare::ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+            body: DeclareBody::DeclareQueryable(DeclareQueryable {
+                id: qable_state.id,
+                wire_expr: qable_state.key_expr.clone(),
+                ext_info: QueryableInfoType {
+                    complete: qable_state.complete,
+                    distance: 0,
+                    load,
+                },
+            }),
+        });
+        Ok(())
+    }
+
     pub(crate) fn close_queryable(self: &Arc<Self>, qid: Id) -> ZResult<()> {
         let mut state = zwrite!(self.state);
         let Ok(primitives) = state.primitives() else {
@@ -1762,6 +2230,7 @@ impl SessionInner {
             key_expr: key_expr.clone().into_owned(),
             origin,
             callback: callback.clone(),
+            declared_at: SystemTime::now(),
         };
 
         let sub_state = Arc::new(sub_state);
@@ -1792,6 +2261,13 @@ impl SessionInner {
             }
         }
 
+        for res in state.interned_keyexprs.values_mut() {
+            if key_expr.intersects(&res.key_expr) {
+                res.subscribers_mut(SubscriberKind::LivelinessSubscriber)
+                    .push(sub_state.clone());
+            }
+        }
+
         let known_tokens = if history {
             state
                 .remote_tokens
@@ -1821,6 +2297,14 @@ impl SessionInner {
                             reliability: Reliability::Reliable,
                             #[cfg(feature = "unstable")]
                             source_info: SourceInfo::empty(),
+                            #[cfg(feature = "unstable")]
+                            trace_context: None,
+                            #[cfg(feature = "unstable")]
+                            signature: None,
+                            #[cfg(feature = "unstable")]
+                            signature_status: SignatureStatus::Unverified,
+                            #[cfg(feature = "sample_metadata")]
+                            reception: None,
                             attachment: None,
                         });
                     }
@@ -1924,6 +2408,10 @@ impl SessionInner {
                         .local_wireexpr_to_expr(&q.key_expr)
                         .map_or(false, |ke| ke.includes(key_expr))
             }),
+            MatchingStatusType::Queriers => state
+                .queriers
+                .values()
+                .any(|q| q.key_expr.intersects(key_expr)),
         };
         MatchingStatus { matching }
     }
@@ -1949,6 +2437,9 @@ impl SessionInner {
                     &tables, key_expr, complete,
                 )
             }
+            MatchingStatusType::Queriers => {
+                crate::net::routing::dispatcher::queries::get_matching_queriers(&tables, key_expr)
+            }
         };
 
         drop(tables);
@@ -2039,6 +2530,39 @@ impl SessionInner {
         }
     }
 
+    /// Moves the matching listener `sid` to `key_expr`, notifying it if its matching status
+    /// changes.
+    #[zenoh_macros::unstable]
+    pub(crate) fn rekey_matches_listener_inner(&self, sid: Id, key_expr: &KeyExpr) -> ZResult<()> {
+        let mut state = zwrite!(self.state);
+        let Some(former) = state.matching_listeners.get(&sid) else {
+            bail!("Unable to find MatchingListener");
+        };
+        tracing::trace!("rekey_matches_listener({:?}, {:?})", former, key_expr);
+        let listener_state = Arc::new(MatchingListenerState {
+            id: sid,
+            current: std::sync::Mutex::new(*zlock!(former.current)),
+            destination: former.destination,
+            key_expr: key_expr.clone().into_owned(),
+            match_type: former.match_type,
+            callback: former.callback.clone(),
+        });
+        state.matching_listeners.insert(sid, listener_state.clone());
+        drop(state);
+        let mut current = zlock!(listener_state.current);
+        if let Ok(status) = self.matching_status(
+            key_expr,
+            listener_state.destination,
+            listener_state.match_type,
+        ) {
+            if status.matching() != *current {
+                *current = status.matching();
+                listener_state.callback.call(status);
+            }
+        }
+        Ok(())
+    }
+
     #[zenoh_macros::unstable]
     pub(crate) fn undeclare_matches_listener_inner(&self, sid: Id) -> ZResult<()> {
         let mut state = zwrite!(self.state);
@@ -2065,6 +2589,7 @@ impl SessionInner {
         attachment: Option<ZBytes>,
     ) {
         let mut callbacks = SingleOrVec::default();
+        let mut intern = None;
         let state = zread!(self.state);
         if state.primitives.is_none() {
             return; // Session closing or closed
@@ -2094,23 +2619,63 @@ impl SessionInner {
             }
         } else {
             match state.wireexpr_to_keyexpr(key_expr, local) {
-                Ok(key_expr) => {
-                    for sub in state.subscribers(kind).values() {
-                        if (sub.origin == Locality::Any
-                            || (local == (sub.origin == Locality::SessionLocal)))
-                            && key_expr.intersects(&sub.key_expr)
-                        {
-                            callbacks.push((sub.callback.clone(), key_expr.clone().into_owned()));
+                Ok(key_expr) => match state.interned_keyexprs.get(key_expr.as_keyexpr()) {
+                    Some(res) => {
+                        for sub in res.subscribers(kind) {
+                            if sub.origin == Locality::Any
+                                || (local == (sub.origin == Locality::SessionLocal))
+                            {
+                                callbacks.push((sub.callback.clone(), res.key_expr.clone().into()));
+                            }
                         }
                     }
-                }
+                    None => {
+                        for sub in state.subscribers(kind).values() {
+                            if (sub.origin == Locality::Any
+                                || (local == (sub.origin == Locality::SessionLocal)))
+                                && key_expr.intersects(&sub.key_expr)
+                            {
+                                callbacks
+                                    .push((sub.callback.clone(), key_expr.clone().into_owned()));
+                            }
+                        }
+                        intern = Some(OwnedKeyExpr::from(key_expr));
+                    }
+                },
                 Err(err) => {
                     tracing::error!("Received Data for unknown key_expr: {}", err);
                     return;
                 }
             }
         };
+        #[cfg(feature = "unstable")]
+        let key_provider = state.key_provider.clone();
         drop(state);
+        if let Some(key_expr) = intern {
+            zwrite!(self.state).intern_keyexpr(key_expr);
+        }
+        #[cfg(feature = "unstable")]
+        let (payload, attachment) = match (key_provider, callbacks.get(0)) {
+            (Some(key_provider), Some((_, key_expr)))
+                if !local && kind == SubscriberKind::Subscriber =>
+            {
+                let sample_kind = info.as_ref().map_or(SampleKind::Put, |info| info.kind);
+                match encryption::decrypt_sample(
+                    key_provider.as_ref(),
+                    key_expr,
+                    sample_kind,
+                    payload,
+                    attachment,
+                ) {
+                    Ok(decrypted) => decrypted,
+                    Err(e) => {
+                        tracing::warn!("Dropping sample on {}: {}", key_expr, e);
+                        return;
+                    }
+                }
+            }
+            _ => (payload, attachment),
+        };
         let mut sample = info.clone().into_sample(
             // SAFETY: the keyexpr is valid
             unsafe { KeyExpr::from_str_unchecked("dummy") },
@@ -2119,6 +2684,10 @@ impl SessionInner {
             reliability,
             attachment,
         );
+        #[cfg(feature = "unstable")]
+        let _span = sample
+            .trace_context
+            .map(|tc| tc.span("sample", key_expr).entered());
         let zenoh_collections::single_or_vec::IntoIter { drain, last } = callbacks.into_iter();
         for (cb, key_expr) in drain {
             sample.key_expr = key_expr;
@@ -2142,15 +2711,69 @@ impl SessionInner {
         is_express: bool,
         destination: Locality,
         #[cfg(feature = "unstable")] reliability: Reliability,
+        #[cfg(feature = "unstable")] hop_limit: Option<u8>,
+        #[cfg(feature = "unstable")] deadline: Option<Duration>,
         timestamp: Option<uhlc::Timestamp>,
         #[cfg(feature = "unstable")] source_info: SourceInfo,
+        #[cfg(feature = "unstable")] trace_context: Option<TraceContext>,
+        #[cfg(feature = "unstable")] signing_key: Option<&SigningKey>,
         attachment: Option<ZBytes>,
     ) -> ZResult<()> {
         trace!("write({:?}, [...])", key_expr);
+        #[cfg(feature = "trace_spans")]
+        let _span = tracing::trace_span!(
+            "zenoh::put",
+            %key_expr,
+            keyexpr_hash = crate::net::routing::keyexpr_hash(key_expr.as_str()),
+            ?kind,
+            ?priority,
+        )
+        .entered();
         let primitives = zread!(self.state).primitives()?;
         let timestamp = timestamp.or_else(|| self.runtime.new_timestamp());
+        // The signed samples are timestamped, so that their timestamp is authenticated
+        #[cfg(feature = "unstable")]
+        let timestamp = match (timestamp, signing_key) {
+            (None, Some(_)) => Some(uhlc::Timestamp::new(
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap().into(),
+                self.runtime.zid().into(),
+            )),
+            (timestamp, _) => timestamp,
+        };
         let wire_expr = key_expr.to_wire(self);
+        #[cfg(feature = "unstable")]
+        let trace_context = trace_context.or_else(TraceContext::current);
+        #[cfg(feature = "unstable")]
+        let signature = signing_key.map(|key| {
+            signature::sign(
+                key,
+                key_expr,
+                kind,
+                timestamp.as_ref(),
+                &encoding,
+                &payload,
+                attachment.as_ref(),
+            )
+        });
         if destination != Locality::SessionLocal {
+            #[cfg(feature = "unstable")]
+            let (remote_payload, remote_attachment) = match &zread!(self.state).key_provider {
+                Some(key_provider) => encryption::encrypt_sample(
+                    key_provider.as_ref(),
+                    key_expr,
+                    kind,
+                    &payload,
+                    &attachment,
+                )?,
+                None => (payload.clone(), attachment.clone()),
+            };
+            #[cfg(not(feature = "unstable"))]
+            let (remote_payload, remote_attachment) = (payload.clone(), attachment.clone());
+            // The deadline is absolute, so that it can be enforced by the forwarding nodes
+            #[cfg(feature = "unstable")]
+            let deadline = deadline.map(|deadline| {
+                (SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + deadline).into()
+            });
             primitives.send_push(
                 Push {
                     wire_expr: wire_expr.to_owned(),
@@ -2161,6 +2784,15 @@ impl SessionInner {
                     ),
                     ext_tstamp: None,
                     ext_nodeid: push::ext::NodeIdType::DEFAULT,
+                    ext_hops: 0,
+                    #[cfg(feature = "unstable")]
+                    ext_hop_limit: hop_limit,
+                    #[cfg(not(feature = "unstable"))]
+                    ext_hop_limit: None,
+                    #[cfg(feature = "unstable")]
+                    ext_deadline: deadline,
+                    #[cfg(not(feature = "unstable"))]
+                    ext_deadline: None,
                     payload: match kind {
                         SampleKind::Put => PushBody::Put(Put {
                             timestamp,
@@ -2171,9 +2803,17 @@ impl SessionInner {
                             ext_sinfo: None,
                             #[cfg(feature = "shared-memory")]
                             ext_shm: None,
-                            ext_attachment: attachment.clone().map(|a| a.into()),
+                            ext_attachment: remote_attachment.map(|a| a.into()),
+                            #[cfg(feature = "unstable")]
+                            ext_trace: trace_context.map(Into::into),
+                            #[cfg(not(feature = "unstable"))]
+                            ext_trace: None,
+                            #[cfg(feature = "unstable")]
+                            ext_signature: signature.map(|s| Box::new(s.into())),
+                            #[cfg(not(feature = "unstable"))]
+                            ext_signature: None,
                             ext_unknown: vec![],
-                            payload: payload.clone().into(),
+                            payload: remote_payload.into(),
                         }),
                         SampleKind::Delete => PushBody::Del(Del {
                             timestamp,
@@ -2181,7 +2821,15 @@ impl SessionInner {
                             ext_sinfo: source_info.clone().into(),
                             #[cfg(not(feature = "unstable"))]
                             ext_sinfo: None,
-                            ext_attachment: attachment.clone().map(|a| a.into()),
+                            ext_attachment: remote_attachment.map(|a| a.into()),
+                            #[cfg(feature = "unstable")]
+                            ext_trace: trace_context.map(Into::into),
+                            #[cfg(not(feature = "unstable"))]
+                            ext_trace: None,
+                            #[cfg(feature = "unstable")]
+                            ext_signature: signature.map(|s| Box::new(s.into())),
+                            #[cfg(not(feature = "unstable"))]
+                            ext_signature: None,
                             ext_unknown: vec![],
                         }),
                     },
@@ -2210,6 +2858,12 @@ impl SessionInner {
                     congestion_control,
                     is_express,
                 )),
+                #[cfg(feature = "unstable")]
+                trace_context,
+                #[cfg(feature = "unstable")]
+                signature,
+                #[cfg(feature = "sample_metadata")]
+                reception: Some(ReceptionInfo::local()),
             };
 
             self.execute_subscriber_callbacks(
@@ -2236,9 +2890,11 @@ impl SessionInner {
         qos: QoS,
         destination: Locality,
         timeout: Duration,
+        reply_timeout: Option<Duration>,
         value: Option<(ZBytes, Encoding)>,
         attachment: Option<ZBytes>,
         #[cfg(feature = "unstable")] source: SourceInfo,
+        #[cfg(feature = "unstable")] trace_context: Option<TraceContext>,
         callback: Callback<Reply>,
     ) -> ZResult<()> {
         tracing::trace!(
@@ -2248,9 +2904,14 @@ impl SessionInner {
             consolidation
         );
         let mut state = zwrite!(self.state);
+        let deduplication = consolidation.deduplication;
         let consolidation = match consolidation.mode {
             #[cfg(feature = "unstable")]
             ConsolidationMode::Auto if parameters.time_range().is_some() => ConsolidationMode::None,
+            #[cfg(feature = "unstable")]
+            ConsolidationMode::Auto if matches!(target, QueryTarget::Specific(_)) => {
+                ConsolidationMode::None
+            }
             ConsolidationMode::Auto => ConsolidationMode::Latest,
             mode => mode,
         };
@@ -2265,25 +2926,57 @@ impl SessionInner {
             .spawn_with_rt(zenoh_runtime::ZRuntime::Net, {
                 let session = WeakSession::new(self);
                 async move {
-                    tokio::select! {
-                        _ = tokio::time::sleep(timeout) => {
-                            let mut state = zwrite!(session.state);
-                            if let Some(query) = state.queries.remove(&qid) {
-                                std::mem::drop(state);
-                                tracing::debug!("Timeout on query {}! Send error and close.", qid);
-                                if query.reception_mode == ConsolidationMode::Latest {
-                                    for (_, reply) in query.replies.unwrap().into_iter() {
-                                        query.callback.call(reply);
-                                    }
-                                }
-                                query.callback.call(Reply {
-                                    result: Err(ReplyError::new("Timeout", Encoding::ZENOH_STRING)),
-                                    #[cfg(feature = "unstable")]
-                                    replier_id: Some(session.zid().into()),
-                                });
+                    let deadline = tokio::time::Instant::now() + timeout;
+                    loop {
+                        // Wake up at the deadline, or earlier if no reply may be received anymore
+                        let wake_up = match reply_timeout {
+                            Some(reply_timeout) => match zread!(session.state).queries.get(&qid) {
+                                Some(query) => deadline.min(tokio::time::Instant::from_std(
+                                    query.last_reply + reply_timeout,
+                                )),
+                                None => break,
+                            },
+                            None => deadline,
+                        };
+                        tokio::select! {
+                            _ = tokio::time::sleep_until(wake_up) => {}
+                            _ = token.cancelled() => break,
+                        }
+                        let mut state = zwrite!(session.state);
+                        if wake_up < deadline {
+                            let inactive = state.queries.get(&qid).is_some_and(|query| {
+                                reply_timeout.is_some_and(|t| query.last_reply.elapsed() >= t)
+                            });
+                            if !inactive {
+                                continue;
                             }
                         }
-                        _ = token.cancelled() => {}
+                        let Some(query) = state.queries.remove(&qid) else {
+                            break;
+                        };
+                        std::mem::drop(state);
+                        if wake_up < deadline {
+                            tracing::debug!(
+                                "No reply on query {} for {:?}: close.",
+                                qid,
+                                reply_timeout
+                            );
+                        } else {
+                            tracing::debug!("Timeout on query {}! Send error and close.", qid);
+                        }
+                        if query.reception_mode == ConsolidationMode::Latest {
+                            for (_, reply) in query.replies.unwrap().into_iter() {
+                                query.callback.call(reply);
+                            }
+                        }
+                        if wake_up >= deadline {
+                            query.callback.call(Reply {
+                                result: Err(ReplyError::new("Timeout", Encoding::ZENOH_STRING)),
+                                #[cfg(feature = "unstable")]
+                                replier_id: Some(session.zid().into()),
+                            });
+                        }
+                        break;
                     }
                 }
             });
@@ -2298,13 +2991,20 @@ impl SessionInner {
                 parameters: parameters.clone().into_owned(),
                 reception_mode: consolidation,
                 replies: (consolidation != ConsolidationMode::None).then(HashMap::new),
+                received: deduplication.then(HashMap::new),
                 callback,
+                #[cfg(feature = "unstable")]
+                started: Instant::now(),
+                last_reply: Instant::now(),
+                nb_replies: 0,
             },
         );
 
         let primitives = state.primitives()?;
         drop(state);
 
+        #[cfg(feature = "unstable")]
+        let trace_context = trace_context.or_else(TraceContext::current);
         if destination != Locality::SessionLocal {
             let ext_attachment = attachment.clone().map(Into::into);
             primitives.send_request(Request {
@@ -2330,6 +3030,10 @@ impl SessionInner {
                         payload: v.0.clone().into(),
                     }),
                     ext_attachment,
+                    #[cfg(feature = "unstable")]
+                    ext_trace: trace_context.map(Into::into),
+                    #[cfg(not(feature = "unstable"))]
+                    ext_trace: None,
                     ext_unknown: vec![],
                 }),
             });
@@ -2349,11 +3053,30 @@ impl SessionInner {
                     payload: v.0.clone().into(),
                 }),
                 attachment,
+                qos,
+                #[cfg(feature = "unstable")]
+                trace_context,
             );
         }
         Ok(())
     }
 
+    #[zenoh_macros::unstable]
+    pub(crate) fn finalize_query(&self, qid: RequestId) -> ZResult<()> {
+        let mut state = zwrite!(self.state);
+        let Some(query) = state.queries.remove(&qid) else {
+            bail!("Unknown pending query {}", qid);
+        };
+        std::mem::drop(state);
+        if query.reception_mode == ConsolidationMode::Latest {
+            for (_, reply) in query.replies.unwrap().into_iter() {
+                query.callback.call(reply);
+            }
+        }
+        trace!("Finalize query {}", qid);
+        Ok(())
+    }
+
     pub(crate) fn liveliness_query(
         self: &Arc<Self>,
         key_expr: &KeyExpr<'_>,
@@ -2415,11 +3138,20 @@ impl SessionInner {
         key_expr: &WireExpr,
         parameters: &str,
         qid: RequestId,
-        _target: QueryTarget,
+        target: QueryTarget,
         _consolidation: ConsolidationMode,
         body: Option<QueryBodyType>,
         attachment: Option<ZBytes>,
+        qos: QoS,
+        #[cfg(feature = "unstable")] trace_context: Option<TraceContext>,
     ) {
+        let zid = self.zid();
+        // A query targeting another node is only answered with a final response
+        let targeted = match target {
+            #[cfg(feature = "unstable")]
+            QueryTarget::Specific(target_zid) => target_zid == zid.into(),
+            _ => true,
+        };
         let (primitives, key_expr, queryables) = {
             let state = zread!(self.state);
             if state.primitives.is_none() {
@@ -2435,6 +3167,8 @@ impl SessionInner {
                         .iter()
                         .filter(
                             |(_, queryable)|
+                                targeted
+                                &&
                                 (queryable.origin == Locality::Any
                                     || (local == (queryable.origin == Locality::SessionLocal)))
                                 &&
@@ -2462,8 +3196,6 @@ impl SessionInner {
             }
         };
 
-        let zid = self.zid();
-
         let query_inner = Arc::new(QueryInner {
             key_expr,
             parameters: parameters.to_owned().into(),
@@ -2474,6 +3206,9 @@ impl SessionInner {
             } else {
                 primitives
             },
+            qos,
+            #[cfg(feature = "unstable")]
+            trace_context,
         });
         let mut query = Query {
             inner: query_inner,
@@ -2481,6 +3216,8 @@ impl SessionInner {
             value: body.map(|b| (b.payload.into(), b.encoding.into())),
             attachment,
         };
+        #[cfg(feature = "unstable")]
+        let _span = trace_context.map(|tc| tc.span("query", &query.inner.key_expr).entered());
         for (eid, cb) in queryables {
             query.eid = eid;
             cb.call(query.clone());
@@ -2491,6 +3228,29 @@ impl SessionInner {
 impl Primitives for WeakSession {
     fn send_interest(&self, msg: zenoh_protocol::network::Interest) {
         trace!("recv Interest {} {:?}", msg.id, msg.wire_expr);
+        #[cfg(feature = "unstable")]
+        {
+            let state = zread!(self.state);
+            if state.primitives.is_none() {
+                return; // Session closing or closed
+            }
+            if msg.mode == InterestMode::Final {
+                // The key expression of an undeclared interest is unknown:
+                // check all the queryables matching listeners.
+                self.update_matching_status(
+                    &state,
+                    &KeyExpr::from(unsafe { keyexpr::from_str_unchecked("**") }),
+                    MatchingStatusType::Queriers,
+                    false,
+                );
+            } else if let Some(key_expr) = msg
+                .wire_expr
+                .as_ref()
+                .and_then(|expr| state.remote_key_to_expr(expr).ok())
+            {
+                self.update_matching_status(&state, &key_expr, MatchingStatusType::Queriers, true);
+            }
+        }
     }
     fn send_declare(&self, msg: zenoh_protocol::network::Declare) {
         match msg.body {
@@ -2653,6 +3413,14 @@ impl Primitives for WeakSession {
                                         reliability: Reliability::Reliable,
                                         #[cfg(feature = "unstable")]
                                         source_info: SourceInfo::empty(),
+                                        #[cfg(feature = "unstable")]
+                                        trace_context: None,
+                                        #[cfg(feature = "unstable")]
+                                        signature: None,
+                                        #[cfg(feature = "unstable")]
+                                        signature_status: SignatureStatus::Unverified,
+                                        #[cfg(feature = "sample_metadata")]
+                                        reception: None,
                                         attachment: None,
                                     }),
                                     #[cfg(feature = "unstable")]
@@ -2764,6 +3532,12 @@ impl Primitives for WeakSession {
                     qos: QoS::from(msg.ext_qos),
                     source_id: m.ext_sinfo.as_ref().map(|i| i.id.into()),
                     source_sn: m.ext_sinfo.as_ref().map(|i| i.sn),
+                    #[cfg(feature = "unstable")]
+                    trace_context: m.ext_trace.and_then(|t| t.try_into().ok()),
+                    #[cfg(feature = "unstable")]
+                    signature: m.ext_signature.map(|s| (*s).into()),
+                    #[cfg(feature = "sample_metadata")]
+                    reception: Some(ReceptionInfo::received(msg.ext_hops)),
                 };
                 self.execute_subscriber_callbacks(
                     false,
@@ -2784,6 +3558,12 @@ impl Primitives for WeakSession {
                     qos: QoS::from(msg.ext_qos),
                     source_id: m.ext_sinfo.as_ref().map(|i| i.id.into()),
                     source_sn: m.ext_sinfo.as_ref().map(|i| i.sn),
+                    #[cfg(feature = "unstable")]
+                    trace_context: m.ext_trace.and_then(|t| t.try_into().ok()),
+                    #[cfg(feature = "unstable")]
+                    signature: m.ext_signature.map(|s| (*s).into()),
+                    #[cfg(feature = "sample_metadata")]
+                    reception: Some(ReceptionInfo::received(msg.ext_hops)),
                 };
                 self.execute_subscriber_callbacks(
                     false,
@@ -2811,6 +3591,9 @@ impl Primitives for WeakSession {
                 m.consolidation,
                 m.ext_body,
                 m.ext_attachment.map(Into::into),
+                QoS::from(msg.ext_qos),
+                #[cfg(feature = "unstable")]
+                m.ext_trace.and_then(|t| t.try_into().ok()),
             ),
         }
     }
@@ -2825,6 +3608,8 @@ impl Primitives for WeakSession {
                 }
                 match state.queries.get_mut(&msg.rid) {
                     Some(query) => {
+                        query.nb_replies += 1;
+                        query.last_reply = Instant::now();
                         let callback = query.callback.clone();
                         std::mem::drop(state);
                         let new_reply = Reply {
@@ -2867,6 +3652,8 @@ impl Primitives for WeakSession {
                             );
                             return;
                         }
+                        query.nb_replies += 1;
+                        query.last_reply = Instant::now();
 
                         struct Ret {
                             payload: ZBuf,
@@ -2883,6 +3670,7 @@ impl Primitives for WeakSession {
                                 encoding,
                                 ext_sinfo,
                                 ext_attachment: _attachment,
+                                ext_trace: _ext_trace,
                                 payload,
                                 ..
                             }) => Ret {
@@ -2894,6 +3682,12 @@ impl Primitives for WeakSession {
                                     qos: QoS::from(msg.ext_qos),
                                     source_id: ext_sinfo.as_ref().map(|i| i.id.into()),
                                     source_sn: ext_sinfo.as_ref().map(|i| i.sn),
+                                    #[cfg(feature = "unstable")]
+                                    trace_context: _ext_trace.and_then(|t| t.try_into().ok()),
+                                    #[cfg(feature = "unstable")]
+                                    signature: None,
+                                    #[cfg(feature = "sample_metadata")]
+                                    reception: None,
                                 },
                                 attachment: _attachment.map(Into::into),
                             },
@@ -2901,6 +3695,7 @@ impl Primitives for WeakSession {
                                 timestamp,
                                 ext_sinfo,
                                 ext_attachment: _attachment,
+                                ext_trace: _ext_trace,
                                 ..
                             }) => Ret {
                                 payload: ZBuf::empty(),
@@ -2911,6 +3706,12 @@ impl Primitives for WeakSession {
                                     qos: QoS::from(msg.ext_qos),
                                     source_id: ext_sinfo.as_ref().map(|i| i.id.into()),
                                     source_sn: ext_sinfo.as_ref().map(|i| i.sn),
+                                    #[cfg(feature = "unstable")]
+                                    trace_context: _ext_trace.and_then(|t| t.try_into().ok()),
+                                    #[cfg(feature = "unstable")]
+                                    signature: None,
+                                    #[cfg(feature = "sample_metadata")]
+                                    reception: None,
                                 },
                                 attachment: _attachment.map(Into::into),
                             },
@@ -2922,6 +3723,14 @@ impl Primitives for WeakSession {
                             Reliability::Reliable,
                             attachment,
                         );
+                        if query.is_duplicate(&sample) {
+                            trace!("Drop duplicate Reply for query {}", msg.rid);
+                            #[cfg(feature = "unstable")]
+                            {
+                                state.duplicate_replies += 1;
+                            }
+                            return;
+                        }
                         let new_reply = Reply {
                             result: Ok(sample),
                             #[cfg(feature = "unstable")]
@@ -3108,7 +3917,7 @@ impl crate::net::primitives::EPrimitives for WeakSession {
 /// use zenoh::session::ZenohId;
 ///
 /// let mut config = zenoh::Config::default();
-/// config.set_id(ZenohId::from_str("221b72df20924c15b8794c6bdb471150").unwrap());
+/// config.set_id(Some(ZenohId::from_str("221b72df20924c15b8794c6bdb471150").unwrap()));
 /// config.connect.endpoints.set(
 ///     ["tcp/10.10.10.10:7447", "tcp/11.11.11.11:7447"].iter().map(|s|s.parse().unwrap()).collect());
 ///
@@ -3148,6 +3957,7 @@ impl Closee for Arc<SessionInner> {
         let _liveliness_subscribers = std::mem::take(&mut state.liveliness_subscribers);
         let _local_resources = std::mem::take(&mut state.local_resources);
         let _remote_resources = std::mem::take(&mut state.remote_resources);
+        let _interned_keyexprs = std::mem::take(&mut state.interned_keyexprs);
         drop(state);
         #[cfg(feature = "unstable")]
         {
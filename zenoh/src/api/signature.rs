@@ -0,0 +1,289 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Signing of the samples by their publishers and verification by the subscribers.
+use std::{fmt, sync::Arc};
+
+use uhlc::Timestamp;
+use zenoh_crypto::signature::{self, Signer};
+use zenoh_keyexpr::keyexpr;
+use zenoh_protocol::zenoh::ext::SignatureType;
+
+use crate::api::{
+    bytes::ZBytes,
+    encoding::Encoding,
+    handlers::Callback,
+    sample::{Sample, SampleKind},
+};
+
+/// An Ed25519 key signing the samples of a [`Publisher`](crate::pubsub::Publisher).
+#[zenoh_macros::unstable]
+#[derive(Clone)]
+pub struct SigningKey(Signer);
+
+#[zenoh_macros::unstable]
+impl SigningKey {
+    /// Generates a random key.
+    pub fn generate() -> Self {
+        Self(Signer::generate())
+    }
+
+    /// Returns the key of the given secret bytes.
+    pub fn from_bytes(secret: &[u8; Signer::SECRET_KEY_SIZE]) -> Self {
+        Self(Signer::from_bytes(secret))
+    }
+
+    /// Returns the secret bytes of the key.
+    pub fn to_bytes(&self) -> [u8; Signer::SECRET_KEY_SIZE] {
+        self.0.to_bytes()
+    }
+
+    /// Returns the public key verifying the signatures of the key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey(self.0.public_key())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SigningKey")
+            .field(&self.verifying_key())
+            .finish()
+    }
+}
+
+/// The Ed25519 public key of a publisher, verifying the signatures of its samples.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VerifyingKey([u8; Signer::PUBLIC_KEY_SIZE]);
+
+#[zenoh_macros::unstable]
+impl VerifyingKey {
+    /// Returns the bytes of the key.
+    pub fn to_bytes(&self) -> [u8; Signer::PUBLIC_KEY_SIZE] {
+        self.0
+    }
+}
+
+#[zenoh_macros::unstable]
+impl From<[u8; Signer::PUBLIC_KEY_SIZE]> for VerifyingKey {
+    fn from(key: [u8; Signer::PUBLIC_KEY_SIZE]) -> Self {
+        Self(key)
+    }
+}
+
+/// The signature carried by a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SampleSignature {
+    pub(crate) key: VerifyingKey,
+    pub(crate) signature: [u8; Signer::SIGNATURE_SIZE],
+}
+
+impl<const ID: u8> From<SignatureType<ID>> for SampleSignature {
+    fn from(ext: SignatureType<ID>) -> Self {
+        Self {
+            key: VerifyingKey(ext.public_key),
+            signature: ext.signature,
+        }
+    }
+}
+
+impl<const ID: u8> From<SampleSignature> for SignatureType<ID> {
+    fn from(signature: SampleSignature) -> Self {
+        Self {
+            public_key: signature.key.0,
+            signature: signature.signature,
+        }
+    }
+}
+
+/// The bytes of a sample authenticated by its signature: its key expression, kind, timestamp,
+/// encoding, payload and attachment. Each field is tagged or length-prefixed, so that no two
+/// samples share the same bytes.
+fn signed_bytes(
+    key_expr: &keyexpr,
+    kind: SampleKind,
+    timestamp: Option<&Timestamp>,
+    encoding: &Encoding,
+    payload: &ZBytes,
+    attachment: Option<&ZBytes>,
+) -> Vec<u8> {
+    fn extend_prefixed(bytes: &mut Vec<u8>, field: &[u8]) {
+        bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(field);
+    }
+
+    let payload = payload.to_bytes();
+    let mut bytes = Vec::with_capacity(key_expr.len() + payload.len() + 64);
+    extend_prefixed(&mut bytes, key_expr.as_bytes());
+    bytes.push(kind as u8);
+    match timestamp {
+        Some(timestamp) => {
+            bytes.push(1);
+            bytes.extend_from_slice(&timestamp.get_time().as_u64().to_le_bytes());
+            bytes.extend_from_slice(&timestamp.get_id().to_le_bytes());
+        }
+        None => bytes.push(0),
+    }
+    // The encoding of a delete is not carried on the wire
+    if kind == SampleKind::Put {
+        let encoding = zenoh_protocol::core::Encoding::from(encoding.clone());
+        bytes.extend_from_slice(&encoding.id.to_le_bytes());
+        match &encoding.schema {
+            Some(schema) => {
+                bytes.push(1);
+                extend_prefixed(&mut bytes, schema);
+            }
+            None => bytes.push(0),
+        }
+    }
+    extend_prefixed(&mut bytes, &payload);
+    match attachment {
+        Some(attachment) => {
+            bytes.push(1);
+            extend_prefixed(&mut bytes, &attachment.to_bytes());
+        }
+        None => bytes.push(0),
+    }
+    bytes
+}
+
+pub(crate) fn sign(
+    key: &SigningKey,
+    key_expr: &keyexpr,
+    kind: SampleKind,
+    timestamp: Option<&Timestamp>,
+    encoding: &Encoding,
+    payload: &ZBytes,
+    attachment: Option<&ZBytes>,
+) -> SampleSignature {
+    SampleSignature {
+        key: key.verifying_key(),
+        signature: key.0.sign(&signed_bytes(
+            key_expr, kind, timestamp, encoding, payload, attachment,
+        )),
+    }
+}
+
+/// The result of the verification of the signature of a [`Sample`], returned by
+/// [`Sample::signature_status`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// The subscriber has no [`SignaturePolicy`].
+    Unverified,
+    /// The sample is not signed.
+    Unsigned,
+    /// The signature does not match the sample.
+    Invalid,
+    /// The sample is signed by a key the [`SignaturePolicy`] does not trust.
+    Untrusted(VerifyingKey),
+    /// The sample is signed by the given key.
+    Valid(VerifyingKey),
+}
+
+/// The verification of the signatures of the samples received by a
+/// [`Subscriber`](crate::pubsub::Subscriber), set with
+/// [`SubscriberBuilder::signature_policy`](crate::pubsub::SubscriberBuilder::signature_policy).
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::sample::{SignaturePolicy, SigningKey};
+///
+/// let key = SigningKey::generate();
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let subscriber = session
+///     .declare_subscriber("key/expression")
+///     .signature_policy(SignaturePolicy::reject(key.verifying_key()))
+///     .await
+///     .unwrap();
+/// let publisher = session
+///     .declare_publisher("key/expression")
+///     .signing_key(key)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone)]
+pub struct SignaturePolicy {
+    reject: bool,
+    trusted: Vec<VerifyingKey>,
+}
+
+#[zenoh_macros::unstable]
+impl SignaturePolicy {
+    /// Delivers all the samples, flagged with their [`SignatureStatus`].
+    pub fn flag() -> Self {
+        Self {
+            reject: false,
+            trusted: vec![],
+        }
+    }
+
+    /// Delivers only the samples with a valid signature by a trusted key, dropping the others.
+    ///
+    /// The policy trusts the given key, more keys can be trusted with [`SignaturePolicy::trust`].
+    pub fn reject(trusted: VerifyingKey) -> Self {
+        Self {
+            reject: true,
+            trusted: vec![trusted],
+        }
+    }
+
+    /// Trusts the given key. A [`SignaturePolicy::flag`] policy without trusted key reports all
+    /// the valid signatures as [`SignatureStatus::Valid`].
+    pub fn trust(mut self, key: VerifyingKey) -> Self {
+        self.trusted.push(key);
+        self
+    }
+
+    fn status(&self, sample: &Sample) -> SignatureStatus {
+        let Some(signature) = &sample.signature else {
+            return SignatureStatus::Unsigned;
+        };
+        let bytes = signed_bytes(
+            &sample.key_expr,
+            sample.kind,
+            sample.timestamp.as_ref(),
+            &sample.encoding,
+            &sample.payload,
+            sample.attachment.as_ref(),
+        );
+        if signature::verify(&signature.key.0, &bytes, &signature.signature).is_err() {
+            SignatureStatus::Invalid
+        } else if self.trusted.is_empty() || self.trusted.contains(&signature.key) {
+            SignatureStatus::Valid(signature.key)
+        } else {
+            SignatureStatus::Untrusted(signature.key)
+        }
+    }
+
+    /// Wraps a subscriber callback into one verifying the samples it receives.
+    pub(crate) fn wrap(self, callback: Callback<Sample>) -> Callback<Sample> {
+        Callback::new(Arc::new(move |mut sample: Sample| {
+            sample.signature_status = self.status(&sample);
+            match sample.signature_status {
+                SignatureStatus::Valid(_) => callback.call(sample),
+                status if self.reject => {
+                    tracing::debug!("Dropping sample on {}: {:?}", sample.key_expr, status);
+                }
+                _ => callback.call(sample),
+            }
+        }))
+    }
+}
@@ -15,13 +15,17 @@ use std::{
     fmt,
     future::{IntoFuture, Ready},
     ops::{Deref, DerefMut},
+    time::SystemTime,
 };
 
 use tracing::error;
 use zenoh_core::{Resolvable, Wait};
 use zenoh_result::ZResult;
 #[cfg(feature = "unstable")]
-use {zenoh_config::wrappers::EntityGlobalId, zenoh_protocol::core::EntityGlobalIdProto};
+use {
+    crate::api::key_tracker::KeyTracker, std::sync::Arc, zenoh_config::wrappers::EntityGlobalId,
+    zenoh_protocol::core::EntityGlobalIdProto,
+};
 
 use crate::api::{
     handlers::Callback,
@@ -37,6 +41,7 @@ pub(crate) struct SubscriberState {
     pub(crate) key_expr: KeyExpr<'static>,
     pub(crate) origin: Locality,
     pub(crate) callback: Callback<Sample>,
+    pub(crate) declared_at: SystemTime,
 }
 
 impl fmt::Debug for SubscriberState {
@@ -54,6 +59,8 @@ pub(crate) struct SubscriberInner {
     pub(crate) id: Id,
     pub(crate) key_expr: KeyExpr<'static>,
     pub(crate) kind: SubscriberKind,
+    #[cfg(feature = "unstable")]
+    pub(crate) key_tracker: Option<Arc<KeyTracker>>,
     pub(crate) undeclare_on_drop: bool,
 }
 
@@ -173,6 +180,43 @@ impl<Handler> Subscriber<Handler> {
         &self.inner.key_expr
     }
 
+    /// Returns the distinct keys of the samples recently received by this subscriber, from the
+    /// most to the least recently received, or nothing if they are not tracked, see
+    /// [`SubscriberBuilder::track_keys`](crate::pubsub::SubscriberBuilder::track_keys).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let subscriber = session.declare_subscriber("sensor/**")
+    ///     .track_keys(1024)
+    ///     .await
+    ///     .unwrap();
+    /// for key_expr in subscriber.tracked_keys() {
+    ///     println!("{key_expr}");
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn tracked_keys(&self) -> Vec<KeyExpr<'static>> {
+        self.inner
+            .key_tracker
+            .as_ref()
+            .map_or_else(Vec::new, |tracker| tracker.keys())
+    }
+
+    /// Returns the number of distinct keys of the samples recently received by this
+    /// subscriber, see [`Subscriber::tracked_keys`].
+    #[zenoh_macros::unstable]
+    pub fn tracked_key_count(&self) -> usize {
+        self.inner
+            .key_tracker
+            .as_ref()
+            .map_or(0, |tracker| tracker.len())
+    }
+
     /// Returns a reference to this subscriber's handler.
     /// An handler is anything that implements [`crate::handlers::IntoHandler`].
     /// The default handler is [`crate::handlers::DefaultHandler`].
@@ -0,0 +1,296 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Distributed trace context propagation.
+use std::{fmt, str::FromStr};
+
+use rand::Rng;
+use zenoh_protocol::zenoh::ext::TraceContextType;
+use zenoh_result::{bail, ZResult};
+
+/// A [W3C trace context](https://www.w3.org/TR/trace-context/) propagated along with
+/// publications and queries.
+///
+/// When a [`TraceContext`] is attached to a put, a delete or a get, it is carried end-to-end
+/// through the zenoh infrastructure and made available to the receiving subscribers and
+/// queryables via [`Sample::trace_context`](crate::sample::Sample::trace_context) and
+/// [`Query::trace_context`](crate::query::Query::trace_context).
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::sample::TraceContext;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let trace_context: TraceContext = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+///     .parse()
+///     .unwrap();
+/// session
+///     .put("key/expression", "value")
+///     .trace_context(trace_context)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    flags: u8,
+}
+
+#[zenoh_macros::unstable]
+impl TraceContext {
+    /// The only `traceparent` version currently supported.
+    pub const VERSION: u8 = 0x00;
+    /// The `sampled` trace flag.
+    pub const FLAG_SAMPLED: u8 = 0x01;
+
+    /// Builds a new [`TraceContext`] from its parts.
+    ///
+    /// Fails if either the trace id or the span id is made of zeros only, as mandated by the
+    /// W3C specification.
+    pub fn new(trace_id: [u8; 16], span_id: [u8; 8], flags: u8) -> ZResult<Self> {
+        if trace_id == [0; 16] {
+            bail!("Invalid trace context: trace id must not be all zeros");
+        }
+        if span_id == [0; 8] {
+            bail!("Invalid trace context: span id must not be all zeros");
+        }
+        Ok(Self {
+            trace_id,
+            span_id,
+            flags,
+        })
+    }
+
+    /// Builds a new sampled [`TraceContext`] starting a new trace.
+    pub fn new_root() -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            trace_id: rng.gen_range(1..=u128::MAX).to_be_bytes(),
+            span_id: rng.gen_range(1..=u64::MAX).to_be_bytes(),
+            flags: Self::FLAG_SAMPLED,
+        }
+    }
+
+    /// Builds a new [`TraceContext`] belonging to the same trace, with a new span id.
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: rand::thread_rng().gen_range(1..=u64::MAX).to_be_bytes(),
+            ..*self
+        }
+    }
+
+    /// The 16-bytes identifier of the trace.
+    pub fn trace_id(&self) -> &[u8; 16] {
+        &self.trace_id
+    }
+
+    /// The 8-bytes identifier of the parent span.
+    pub fn span_id(&self) -> &[u8; 8] {
+        &self.span_id
+    }
+
+    /// The trace flags.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Whether the caller may have recorded trace data.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & Self::FLAG_SAMPLED != 0
+    }
+
+    /// Returns the `traceparent` header value representation of this [`TraceContext`].
+    pub fn to_traceparent(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns the [`TraceContext`] of the current [`tracing::Span`], if any.
+    ///
+    /// This always returns `None` unless zenoh is compiled with the `opentelemetry` feature
+    /// and the current span is recorded by an OpenTelemetry layer.
+    pub fn current() -> Option<Self> {
+        #[cfg(feature = "opentelemetry")]
+        {
+            Self::from_span(&tracing::Span::current())
+        }
+        #[cfg(not(feature = "opentelemetry"))]
+        {
+            None
+        }
+    }
+
+    /// Returns the [`TraceContext`] of the given [`tracing::Span`], if it is recorded by an
+    /// OpenTelemetry layer.
+    #[cfg(feature = "opentelemetry")]
+    pub fn from_span(span: &tracing::Span) -> Option<Self> {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let context = span.context();
+        let span_ref = context.span();
+        let span_context = span_ref.span_context();
+        if !span_context.is_valid() {
+            return None;
+        }
+        Some(Self {
+            trace_id: span_context.trace_id().to_bytes(),
+            span_id: span_context.span_id().to_bytes(),
+            flags: span_context.trace_flags().to_u8(),
+        })
+    }
+
+    /// Sets this [`TraceContext`] as the remote parent of the given [`tracing::Span`].
+    #[cfg(feature = "opentelemetry")]
+    pub fn set_as_parent_of(&self, span: &tracing::Span) {
+        use opentelemetry::trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let span_context = SpanContext::new(
+            TraceId::from_bytes(self.trace_id),
+            SpanId::from_bytes(self.span_id),
+            TraceFlags::new(self.flags),
+            true,
+            TraceState::default(),
+        );
+        span.set_parent(opentelemetry::Context::new().with_remote_span_context(span_context));
+    }
+
+    /// Creates the span under which the callbacks receiving a traced message are executed.
+    pub(crate) fn span(&self, name: &'static str, key_expr: &dyn fmt::Display) -> tracing::Span {
+        let span = tracing::debug_span!(
+            "zenoh",
+            op = name,
+            %key_expr,
+            traceparent = %self,
+        );
+        #[cfg(feature = "opentelemetry")]
+        self.set_as_parent_of(&span);
+        span
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for b in bytes {
+        write!(f, "{b:02x}")?;
+    }
+    Ok(())
+}
+
+fn parse_hex<const N: usize>(s: &str) -> ZResult<[u8; N]> {
+    if s.len() != 2 * N || !s.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')) {
+        bail!(
+            "Invalid trace context: expected {} lowercase hex digits",
+            2 * N
+        );
+    }
+    let mut bytes = [0u8; N];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)?;
+    }
+    Ok(bytes)
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}-", Self::VERSION)?;
+        write_hex(f, &self.trace_id)?;
+        write!(f, "-")?;
+        write_hex(f, &self.span_id)?;
+        write!(f, "-{:02x}", self.flags)
+    }
+}
+
+impl fmt::Debug for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TraceContext({self})")
+    }
+}
+
+impl FromStr for TraceContext {
+    type Err = zenoh_result::Error;
+
+    /// Parses a W3C `traceparent` header value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('-');
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            bail!("Invalid traceparent '{}': expected 4 fields", s);
+        };
+        let [version] = parse_hex::<1>(version)?;
+        if version == 0xff {
+            bail!("Invalid traceparent '{}': forbidden version", s);
+        }
+        // Future versions may append fields, version 00 must not.
+        if version == Self::VERSION && parts.next().is_some() {
+            bail!("Invalid traceparent '{}': unexpected trailing fields", s);
+        }
+        let [flags] = parse_hex::<1>(flags)?;
+        Self::new(parse_hex(trace_id)?, parse_hex(span_id)?, flags)
+    }
+}
+
+impl<const ID: u8> From<TraceContext> for TraceContextType<{ ID }> {
+    fn from(tc: TraceContext) -> Self {
+        TraceContextType {
+            trace_id: tc.trace_id,
+            span_id: tc.span_id,
+            flags: tc.flags,
+        }
+    }
+}
+
+impl<const ID: u8> TryFrom<TraceContextType<{ ID }>> for TraceContext {
+    type Error = zenoh_result::Error;
+
+    fn try_from(ext: TraceContextType<{ ID }>) -> Result<Self, Self::Error> {
+        Self::new(ext.trace_id, ext.span_id, ext.flags)
+    }
+}
+
+#[test]
+fn traceparent_roundtrip() {
+    let s = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+    let tc: TraceContext = s.parse().unwrap();
+    assert!(tc.is_sampled());
+    assert_eq!(
+        tc.span_id(),
+        &[0xb7, 0xad, 0x6b, 0x71, 0x69, 0x20, 0x33, 0x31]
+    );
+    assert_eq!(tc.to_traceparent(), s);
+
+    let child = tc.child();
+    assert_eq!(child.trace_id(), tc.trace_id());
+    assert_ne!(child.span_id(), tc.span_id());
+
+    assert!("00-00000000000000000000000000000000-b7ad6b7169203331-01"
+        .parse::<TraceContext>()
+        .is_err());
+    assert!("00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01"
+        .parse::<TraceContext>()
+        .is_err());
+    assert!("ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+        .parse::<TraceContext>()
+        .is_err());
+    assert!("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331"
+        .parse::<TraceContext>()
+        .is_err());
+}
@@ -119,6 +119,7 @@ pub const FEATURES: &str = zenoh_util::concat_enabled_features!(
         "transport_unixsock-stream",
         "transport_ws",
         "transport_vsock",
+        "transport_mem",
         "unstable",
         "default"
     ]
@@ -130,6 +131,9 @@ pub use zenoh_result::Error;
 /// A zenoh result.
 pub use zenoh_result::ZResult as Result;
 #[doc(inline)]
+#[cfg(feature = "opentelemetry")]
+pub use zenoh_util::init_log_with_opentelemetry;
+#[doc(inline)]
 pub use zenoh_util::{init_log_from_env_or, try_init_log_from_env};
 
 #[doc(inline)]
@@ -206,6 +210,18 @@ pub mod session {
 
     #[zenoh_macros::internal]
     pub use crate::api::builders::session::{init, InitBuilder};
+    #[zenoh_macros::unstable]
+    pub use crate::api::encryption::{EncryptionKey, KeyProvider, SubtreeKeyProvider};
+    #[zenoh_macros::unstable]
+    pub use crate::api::metrics::{MetricsBuilder, MetricsExporter, QueueDepth, SessionMetrics};
+    #[zenoh_macros::unstable]
+    pub use crate::api::probe::{PriorityProbe, ProbeBuilder};
+    #[zenoh_macros::unstable]
+    pub use crate::api::{
+        builders::info::{CloseTransportBuilder, TransportsBuilder},
+        builders::query::SessionGetManyBuilder,
+        info::{Link, Transport},
+    };
     pub use crate::api::{
         builders::{
             close::CloseBuilder,
@@ -227,7 +243,14 @@ pub mod sample {
     #[zenoh_macros::unstable]
     pub use crate::api::sample::Locality;
     #[zenoh_macros::unstable]
+    #[cfg(feature = "sample_metadata")]
+    pub use crate::api::sample::ReceptionInfo;
+    #[zenoh_macros::unstable]
     pub use crate::api::sample::{SourceInfo, SourceSn};
+    #[zenoh_macros::unstable]
+    pub use crate::api::signature::{SignaturePolicy, SignatureStatus, SigningKey, VerifyingKey};
+    #[zenoh_macros::unstable]
+    pub use crate::api::trace::TraceContext;
     pub use crate::api::{
         builders::sample::{
             SampleBuilder, SampleBuilderAny, SampleBuilderDelete, SampleBuilderPut,
@@ -263,6 +286,13 @@ pub mod bytes {
 /// declared by a [`Session::declare_subscriber`](crate::Session::declare_subscriber)
 ///
 pub mod pubsub {
+    #[zenoh_macros::unstable]
+    pub use crate::api::{
+        builders::congestion_listener::CongestionListenerBuilder,
+        congestion::{
+            CongestionEvent, CongestionListener, CongestionListenerUndeclaration, CongestionStatus,
+        },
+    };
     pub use crate::api::{
         builders::{
             publisher::{
@@ -271,7 +301,7 @@ pub mod pubsub {
             },
             subscriber::SubscriberBuilder,
         },
-        publisher::{Publisher, PublisherUndeclaration},
+        publisher::{Publisher, PublisherKeyExprUpdate, PublisherUndeclaration},
         subscriber::Subscriber,
     };
 }
@@ -294,7 +324,7 @@ pub mod query {
     pub use crate::api::{
         builders::querier::{QuerierBuilder, QuerierGetBuilder},
         querier::Querier,
-        query::ReplyKeyExpr,
+        query::{PendingQuery, ReplyKeyExpr, TaggedReply},
         selector::ZenohParameters,
     };
     pub use crate::api::{
@@ -352,6 +382,8 @@ pub mod handlers {
         Callback, CallbackDrop, DefaultHandler, FifoChannel, FifoChannelHandler, IntoHandler,
         RingChannel, RingChannelHandler,
     };
+    #[zenoh_macros::unstable]
+    pub use crate::api::handlers::{PollChannel, PollChannelHandler};
     pub mod fifo {
         pub use crate::api::handlers::{
             Drain, FifoChannel, FifoChannelHandler, IntoIter, Iter, RecvFut, RecvStream, TryIter,
@@ -444,6 +476,9 @@ pub mod liveliness {
 /// Timestamp support
 pub mod time {
     pub use zenoh_protocol::core::{Timestamp, TimestampId, NTP64};
+
+    #[zenoh_macros::unstable]
+    pub use crate::api::clock::{ClockInfo, TimestampDrift};
 }
 
 /// Configuration to pass to [`open`] and [`scout`] functions and associated constants.
@@ -460,11 +495,44 @@ pub mod time {
 pub mod config {
     pub use zenoh_config::{EndPoint, Locator, WhatAmI, WhatAmIMatcher, ZenohId};
 
+    #[zenoh_macros::unstable]
+    pub use zenoh_config::{AclMessage, InterceptorFlow, Permission};
+
+    #[zenoh_macros::unstable]
+    pub use crate::api::acl::{AclCheckBuilder, AclDecision};
     pub use crate::api::config::Config;
     #[zenoh_macros::unstable]
     pub use crate::api::config::Notifier;
 }
 
+/// Capture of the network messages exchanged by a node and their replay
+///
+/// The messages exchanged with the remote nodes are appended to the file configured in
+/// `capture/file`. A [`CaptureReader`](crate::capture::CaptureReader) reads them back with
+/// their dissection metadata, and [`Session::replay`](crate::Session::replay) replays the
+/// captured publications into a session.
+#[zenoh_macros::unstable]
+pub mod capture {
+    pub use crate::api::capture::{
+        CaptureDirection, CaptureReader, CapturedMessage, ReplayBuilder,
+    };
+}
+
+/// In-process networks of zenoh nodes for integration tests
+///
+/// A [`Network`](crate::test::Network) opens named routers, peers and clients and connects them
+/// through proxies that can partition the nodes, drop or delay the exchanged batches.
+/// A [`MockSession`](crate::test::MockSession) is a session without network recording its
+/// publications and answering its queries with canned replies.
+#[zenoh_macros::unstable]
+#[cfg(feature = "test_harness")]
+pub mod test {
+    pub use crate::api::{
+        harness::{Network, NodeBuilder},
+        mock::MockSession,
+    };
+}
+
 #[cfg(all(
     feature = "plugins",
     not(all(feature = "unstable", feature = "internal"))
@@ -115,6 +115,7 @@ where
             zid,
             whatami,
             locators,
+            labels: None,
             links,
         })
     }
@@ -135,6 +136,24 @@ where
             self.write(&mut *writer, ls)?;
         }
 
+        let labelled = x
+            .link_states
+            .iter()
+            .enumerate()
+            .filter_map(|(i, ls)| ls.labels.as_ref().map(|labels| (i, labels)))
+            .collect::<Vec<_>>();
+        if !labelled.is_empty() {
+            codec.write(&mut *writer, labelled.len())?;
+            for (i, labels) in labelled {
+                codec.write(&mut *writer, i)?;
+                codec.write(&mut *writer, labels.len())?;
+                for (k, v) in labels.iter() {
+                    codec.write(&mut *writer, k.as_str())?;
+                    codec.write(&mut *writer, v.as_str())?;
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -155,6 +174,68 @@ where
             link_states.push(ls);
         }
 
+        if reader.can_read() {
+            let len: usize = codec.read(&mut *reader)?;
+            for _ in 0..len {
+                let i: usize = codec.read(&mut *reader)?;
+                let n: usize = codec.read(&mut *reader)?;
+                let mut labels = Vec::with_capacity(n);
+                for _ in 0..n {
+                    let k: String = codec.read(&mut *reader)?;
+                    let v: String = codec.read(&mut *reader)?;
+                    labels.push((k, v));
+                }
+                link_states.get_mut(i).ok_or(DidntRead)?.labels = Some(labels);
+            }
+        }
+
         Ok(LinkStateList { link_states })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zenoh_buffers::{
+        reader::{HasReader, Reader},
+        writer::HasWriter,
+        ZBuf,
+    };
+
+    use super::*;
+
+    #[test]
+    fn codec_linkstate_list_labels() {
+        let link_state = |labels: Option<Vec<(String, String)>>| LinkState {
+            psid: 1,
+            sn: 2,
+            zid: Some(ZenohIdProto::default()),
+            whatami: Some(WhatAmI::Peer),
+            locators: None,
+            labels,
+            links: vec![3, 4],
+        };
+        let list = LinkStateList {
+            link_states: vec![
+                link_state(None),
+                link_state(Some(vec![("site".into(), "a".into())])),
+            ],
+        };
+
+        let codec = Zenoh080Routing::new();
+        let mut buf = ZBuf::empty();
+        codec.write(&mut buf.writer(), &list).unwrap();
+        let decoded: LinkStateList = codec.read(&mut buf.reader()).unwrap();
+        assert_eq!(decoded, list);
+
+        // The link states are decoded the same way by the nodes that ignore the labels
+        let mut reader = buf.reader();
+        let len: usize = Zenoh080::new().read(&mut reader).unwrap();
+        assert_eq!(len, 2);
+        for expected in list.link_states.iter() {
+            let decoded: LinkState = codec.read(&mut reader).unwrap();
+            assert_eq!(decoded.links, expected.links);
+            assert_eq!(decoded.labels, None);
+        }
+        assert!(reader.can_read());
+    }
+}
@@ -16,7 +16,7 @@ use std::{any::Any, sync::Arc};
 use zenoh_link::Link;
 use zenoh_protocol::network::{NetworkBody, NetworkMessage};
 use zenoh_result::ZResult;
-use zenoh_transport::{unicast::TransportUnicast, TransportPeerEventHandler};
+use zenoh_transport::{unicast::TransportUnicast, DropReason, TransportPeerEventHandler};
 
 use super::Primitives;
 use crate::net::routing::{
@@ -107,4 +107,15 @@ impl TransportPeerEventHandler for DeMux {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn dropped(&self, msg: NetworkMessage, reason: DropReason) {
+        // Dropped messages were on their way out through this face
+        let ctx = RoutingContext::new_out(msg, self.face.clone());
+        if let Some(key_expr) = ctx.full_expr() {
+            self.face
+                .tables
+                .dead_letters
+                .report(reason, key_expr, &ctx.msg);
+        }
+    }
 }
@@ -33,6 +33,8 @@ pub const LOC: u64 = 1 << 2; // 0x04
 // +---------------+
 // ~    [links]    ~
 // +---------------+
+//
+// The labels are not part of the link state but trail the link state list, see below.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LinkState {
     pub(crate) psid: u64,
@@ -40,6 +42,7 @@ pub(crate) struct LinkState {
     pub(crate) zid: Option<ZenohIdProto>,
     pub(crate) whatami: Option<WhatAmI>,
     pub(crate) locators: Option<Vec<Locator>>,
+    pub(crate) labels: Option<Vec<(String, String)>>,
     pub(crate) links: Vec<u64>,
 }
 
@@ -72,6 +75,15 @@ impl LinkState {
         } else {
             None
         };
+        let labels = if rng.gen_bool(0.5) {
+            let n = rng.gen_range(MIN..=MAX);
+            let labels = (0..n)
+                .map(|i| (format!("key{i}"), format!("value{}", rng.gen::<u8>())))
+                .collect::<Vec<(String, String)>>();
+            Some(labels)
+        } else {
+            None
+        };
         let n = rng.gen_range(MIN..=MAX);
         let links = (0..n).map(|_| rng.gen()).collect::<Vec<u64>>();
 
@@ -81,6 +93,7 @@ impl LinkState {
             zid,
             whatami,
             locators,
+            labels,
             links,
         }
     }
@@ -92,6 +105,10 @@ impl LinkState {
 // +-+-+-+---------+
 // ~ [link_states] ~
 // +---------------+
+// ~   [labels]    ~ (index of a link state, [(key, value)]) for the link states with labels,
+// +---------------+ omitted if none of them has labels
+//
+// The labels trail the link states so that the nodes that don't know them ignore them.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LinkStateList {
     pub(crate) link_states: Vec<LinkState>,
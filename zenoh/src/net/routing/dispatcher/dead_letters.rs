@@ -0,0 +1,80 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::{Arc, RwLock};
+
+use zenoh_config::Config;
+use zenoh_protocol::{
+    core::{WhatAmI, WireExpr, ZenohIdProto},
+    network::{NetworkBody, NetworkMessage, Push},
+};
+use zenoh_transport::DropReason;
+
+use crate::net::primitives::EPrimitives;
+
+/// The redirection of the samples dropped by the node to the subscribers of its sessions on
+/// `@/<zid>/<whatami>/dropped/<reason>/<key_expr>`.
+pub struct DeadLetters {
+    prefix: Option<String>,
+    sessions: RwLock<Vec<Arc<dyn EPrimitives + Send + Sync>>>,
+}
+
+impl DeadLetters {
+    pub(crate) fn new(zid: ZenohIdProto, whatami: WhatAmI, config: &Config) -> Self {
+        DeadLetters {
+            prefix: config
+                .dead_letters()
+                .enabled()
+                .then(|| format!("@/{zid}/{whatami}/dropped")),
+            sessions: RwLock::new(vec![]),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.prefix.is_some()
+    }
+
+    pub(crate) fn add_session(&self, session: Arc<dyn EPrimitives + Send + Sync>) {
+        if self.is_enabled() {
+            zwrite!(self.sessions).push(session);
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        zwrite!(self.sessions).clear();
+    }
+
+    /// Delivers the sample of the given dropped message, if any, to the registered sessions.
+    pub(crate) fn report(&self, reason: DropReason, key_expr: &str, msg: &NetworkMessage) {
+        let (Some(prefix), NetworkBody::Push(push)) = (&self.prefix, &msg.body) else {
+            return;
+        };
+        // Admin space messages are not redirected
+        if key_expr.starts_with('@') {
+            return;
+        }
+        tracing::trace!("Dead letter on {} ({})", key_expr, reason);
+        let sessions = zread!(self.sessions);
+        let wire_expr = WireExpr::from(format!("{prefix}/{reason}/{key_expr}"));
+        for session in sessions.iter() {
+            session.send_push(
+                Push {
+                    wire_expr: wire_expr.clone(),
+                    ..push.clone()
+                },
+                msg.reliability,
+            );
+        }
+    }
+}
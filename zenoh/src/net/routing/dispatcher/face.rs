@@ -16,13 +16,16 @@ use std::{
     collections::HashMap,
     fmt,
     sync::{Arc, Weak},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use tokio_util::sync::CancellationToken;
+#[cfg(feature = "unstable")]
+use zenoh_protocol::transport::init::ext::PatchType;
 use zenoh_protocol::{
-    core::{ExprId, Reliability, WhatAmI, ZenohIdProto},
+    core::{CongestionControl, ExprId, Priority, Reliability, WhatAmI, WireExpr, ZenohIdProto},
     network::{
+        ext::QoSType,
         interest::{InterestId, InterestMode, InterestOptions},
         Mapping, Push, Request, RequestId, Response, ResponseFinal,
     },
@@ -33,7 +36,10 @@ use zenoh_task::TaskController;
 use zenoh_transport::multicast::TransportMulticast;
 #[cfg(feature = "stats")]
 use zenoh_transport::stats::TransportStats;
+use zenoh_util::flight_recorder::FlightRecorder;
 
+#[cfg(feature = "unstable")]
+use super::interests::notify_local_faces;
 use super::{
     super::router::*,
     interests::{declare_final, declare_interest, undeclare_interest, CurrentInterest},
@@ -57,6 +63,16 @@ pub(crate) struct InterestState {
     pub(crate) finalized: bool,
 }
 
+/// The key expression, QoS and reception time of an entity declared on a face, shown in the
+/// admin space.
+pub(crate) struct EntityInfo {
+    pub(crate) expr: String,
+    pub(crate) priority: Priority,
+    pub(crate) congestion_control: CongestionControl,
+    pub(crate) express: bool,
+    pub(crate) declared_at: SystemTime,
+}
+
 pub struct FaceState {
     pub(crate) id: usize,
     pub(crate) zid: ZenohIdProto,
@@ -72,7 +88,11 @@ pub struct FaceState {
     pub(crate) remote_mappings: HashMap<ExprId, Arc<Resource>>,
     pub(crate) next_qid: RequestId,
     pub(crate) pending_queries: HashMap<RequestId, (Arc<Query>, CancellationToken)>,
+    // The entities declared on this face, by kind and id
+    pub(crate) entities: HashMap<(&'static str, u32), EntityInfo>,
     pub(crate) mcast_group: Option<TransportMulticast>,
+    #[cfg(feature = "unstable")]
+    pub(crate) patch: PatchType,
     pub(crate) in_interceptors: Option<Arc<InterceptorsChain>>,
     pub(crate) hat: Box<dyn Any + Send + Sync>,
     pub(crate) task_controller: TaskController,
@@ -87,6 +107,7 @@ impl FaceState {
         #[cfg(feature = "stats")] stats: Option<Arc<TransportStats>>,
         primitives: Arc<dyn crate::net::primitives::EPrimitives + Send + Sync>,
         mcast_group: Option<TransportMulticast>,
+        #[cfg(feature = "unstable")] patch: PatchType,
         in_interceptors: Option<Arc<InterceptorsChain>>,
         hat: Box<dyn Any + Send + Sync>,
     ) -> Arc<FaceState> {
@@ -104,7 +125,10 @@ impl FaceState {
             remote_mappings: HashMap::new(),
             next_qid: 0,
             pending_queries: HashMap::new(),
+            entities: HashMap::new(),
             mcast_group,
+            #[cfg(feature = "unstable")]
+            patch,
             in_interceptors,
             hat,
             task_controller: TaskController::default(),
@@ -213,12 +237,49 @@ impl Face {
             state: Arc::downgrade(&self.state),
         }
     }
+
+    fn register_entity<const ID: u8>(
+        &self,
+        kind: &'static str,
+        id: u32,
+        expr: Option<&WireExpr>,
+        qos: QoSType<ID>,
+    ) {
+        let expr = match expr {
+            Some(expr) => {
+                let tables = zread!(self.tables.tables);
+                match tables.get_mapping(&self.state, &expr.scope, expr.mapping) {
+                    Some(prefix) => format!("{}{}", prefix.expr(), expr.suffix),
+                    None => return,
+                }
+            }
+            None => "**".to_string(),
+        };
+        get_mut_unchecked(&mut self.state.clone()).entities.insert(
+            (kind, id),
+            EntityInfo {
+                expr,
+                priority: qos.get_priority(),
+                congestion_control: qos.get_congestion_control(),
+                express: qos.is_express(),
+                declared_at: SystemTime::now(),
+            },
+        );
+    }
 }
 
 impl Primitives for Face {
     fn send_interest(&self, msg: zenoh_protocol::network::Interest) {
         let ctrl_lock = zlock!(self.tables.ctrl_lock);
         if msg.mode != InterestMode::Final {
+            if msg.mode != InterestMode::Current {
+                if msg.options.subscribers() {
+                    self.register_entity("publisher", msg.id, msg.wire_expr.as_ref(), msg.ext_qos);
+                }
+                if msg.options.queryables() {
+                    self.register_entity("querier", msg.id, msg.wire_expr.as_ref(), msg.ext_qos);
+                }
+            }
             let mut declares = vec![];
             declare_interest(
                 ctrl_lock.as_ref(),
@@ -235,16 +296,24 @@ impl Primitives for Face {
                 p.send_declare(m);
             }
         } else {
+            let mut state = self.state.clone();
+            let entities = &mut get_mut_unchecked(&mut state).entities;
+            entities.remove(&("publisher", msg.id));
+            entities.remove(&("querier", msg.id));
             undeclare_interest(
                 ctrl_lock.as_ref(),
                 &self.tables,
                 &mut self.state.clone(),
                 msg.id,
             );
+            drop(ctrl_lock);
         }
+        #[cfg(feature = "unstable")]
+        notify_local_faces(&self.tables, &self.state, &msg);
     }
 
     fn send_declare(&self, msg: zenoh_protocol::network::Declare) {
+        record_declaration(&self.tables.flight_recorder, &self.state, &msg.body);
         let ctrl_lock = zlock!(self.tables.ctrl_lock);
         match msg.body {
             zenoh_protocol::network::DeclareBody::DeclareKeyExpr(m) => {
@@ -254,6 +323,7 @@ impl Primitives for Face {
                 unregister_expr(&self.tables, &mut self.state.clone(), m.id);
             }
             zenoh_protocol::network::DeclareBody::DeclareSubscriber(m) => {
+                self.register_entity("subscriber", m.id, Some(&m.wire_expr), msg.ext_qos);
                 let mut declares = vec![];
                 declare_subscription(
                     ctrl_lock.as_ref(),
@@ -271,6 +341,9 @@ impl Primitives for Face {
                 }
             }
             zenoh_protocol::network::DeclareBody::UndeclareSubscriber(m) => {
+                get_mut_unchecked(&mut self.state.clone())
+                    .entities
+                    .remove(&("subscriber", m.id));
                 let mut declares = vec![];
                 undeclare_subscription(
                     ctrl_lock.as_ref(),
@@ -287,6 +360,7 @@ impl Primitives for Face {
                 }
             }
             zenoh_protocol::network::DeclareBody::DeclareQueryable(m) => {
+                self.register_entity("queryable", m.id, Some(&m.wire_expr), msg.ext_qos);
                 let mut declares = vec![];
                 declare_queryable(
                     ctrl_lock.as_ref(),
@@ -304,6 +378,9 @@ impl Primitives for Face {
                 }
             }
             zenoh_protocol::network::DeclareBody::UndeclareQueryable(m) => {
+                get_mut_unchecked(&mut self.state.clone())
+                    .entities
+                    .remove(&("queryable", m.id));
                 let mut declares = vec![];
                 undeclare_queryable(
                     ctrl_lock.as_ref(),
@@ -451,6 +528,73 @@ impl Primitives for Face {
     }
 }
 
+fn record_declaration(
+    recorder: &FlightRecorder,
+    face: &FaceState,
+    body: &zenoh_protocol::network::DeclareBody,
+) {
+    use zenoh_protocol::network::DeclareBody;
+
+    // The face is recorded by its id and zid, as it doesn't have to outlive its events
+    let (id, zid) = (face.id, face.zid);
+    match body {
+        DeclareBody::DeclareSubscriber(m) => zenoh_util::record_event!(
+            recorder,
+            Declaration,
+            "Face{{{id}, {zid}}} declared subscriber {sub} on {wire_expr}",
+            id,
+            zid,
+            sub = m.id,
+            wire_expr = m.wire_expr
+        ),
+        DeclareBody::UndeclareSubscriber(m) => zenoh_util::record_event!(
+            recorder,
+            Declaration,
+            "Face{{{id}, {zid}}} undeclared subscriber {sub}",
+            id,
+            zid,
+            sub = m.id
+        ),
+        DeclareBody::DeclareQueryable(m) => zenoh_util::record_event!(
+            recorder,
+            Declaration,
+            "Face{{{id}, {zid}}} declared queryable {qabl} on {wire_expr}",
+            id,
+            zid,
+            qabl = m.id,
+            wire_expr = m.wire_expr
+        ),
+        DeclareBody::UndeclareQueryable(m) => zenoh_util::record_event!(
+            recorder,
+            Declaration,
+            "Face{{{id}, {zid}}} undeclared queryable {qabl}",
+            id,
+            zid,
+            qabl = m.id
+        ),
+        DeclareBody::DeclareToken(m) => zenoh_util::record_event!(
+            recorder,
+            Declaration,
+            "Face{{{id}, {zid}}} declared token {token} on {wire_expr}",
+            id,
+            zid,
+            token = m.id,
+            wire_expr = m.wire_expr
+        ),
+        DeclareBody::UndeclareToken(m) => zenoh_util::record_event!(
+            recorder,
+            Declaration,
+            "Face{{{id}, {zid}}} undeclared token {token}",
+            id,
+            zid,
+            token = m.id
+        ),
+        DeclareBody::DeclareKeyExpr(_)
+        | DeclareBody::UndeclareKeyExpr(_)
+        | DeclareBody::DeclareFinal(_) => {}
+    }
+}
+
 impl fmt::Display for Face {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.state.fmt(f)
@@ -21,6 +21,8 @@ use std::{
 use async_trait::async_trait;
 use tokio_util::sync::CancellationToken;
 use zenoh_keyexpr::keyexpr;
+#[cfg(feature = "unstable")]
+use zenoh_protocol::network::Interest;
 use zenoh_protocol::{
     core::WireExpr,
     network::{
@@ -275,3 +277,45 @@ pub(crate) fn undeclare_interest(
     let mut wtables = zwrite!(tables.tables);
     hat_code.undeclare_interest(&mut wtables, face, id);
 }
+
+/// Forwards the queryables interests received from `face` to the other local faces of this
+/// runtime, so that their queryables matching listeners can be updated.
+#[zenoh_macros::unstable]
+pub(crate) fn notify_local_faces(tables_ref: &TablesLock, face: &FaceState, msg: &Interest) {
+    // Note: InterestMode::Final options are undefined in the current protocol specification.
+    if msg.mode != InterestMode::Final && !msg.options.queryables() {
+        return;
+    }
+    let rtables = zread!(tables_ref.tables);
+    let wire_expr = match msg.wire_expr.as_ref() {
+        Some(expr) => match rtables.get_mapping(face, &expr.scope, expr.mapping) {
+            Some(prefix) => Some(WireExpr::from(
+                prefix.expr().to_string() + expr.suffix.as_ref(),
+            )),
+            None => return,
+        },
+        None => None,
+    };
+    let local_faces = rtables
+        .faces
+        .values()
+        .filter(|f| f.zid == rtables.zid && f.id != face.id)
+        .cloned()
+        .collect::<Vec<Arc<FaceState>>>();
+    drop(rtables);
+    for local_face in local_faces {
+        let full_expr = wire_expr
+            .as_ref()
+            .map(|expr| expr.suffix.to_string())
+            .unwrap_or_default();
+        local_face
+            .primitives
+            .send_interest(RoutingContext::with_expr(
+                Interest {
+                    wire_expr: wire_expr.clone(),
+                    ..msg.clone()
+                },
+                full_expr,
+            ));
+    }
+}
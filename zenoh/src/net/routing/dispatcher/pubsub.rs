@@ -290,7 +290,7 @@ pub(crate) fn disable_matches_data_routes(_tables: &mut Tables, res: &mut Arc<Re
 }
 
 macro_rules! treat_timestamp {
-    ($hlc:expr, $payload:expr, $drop:expr) => {
+    ($hlc:expr, $payload:expr, $drop:expr, $drifts:expr) => {
         // if an HLC was configured (via Config.add_timestamp),
         // check DataInfo and add a timestamp if there isn't
         if let Some(hlc) = $hlc {
@@ -300,6 +300,7 @@ macro_rules! treat_timestamp {
                     match hlc.update_with_timestamp(ts) {
                         Ok(()) => (),
                         Err(e) => {
+                            $drifts.report(hlc, ts, $drop);
                             if $drop {
                                 tracing::error!(
                                     "Error treating timestamp for received Data ({}). Drop it!",
@@ -388,6 +389,25 @@ macro_rules! inc_stats {
     };
 }
 
+/// Returns the hop limit of the data sent to `outface`, decremented if it traverses a link, or
+/// `None` if the data reached its hop limit and must not be sent to `outface`.
+#[inline]
+fn hop_limit_to(tables: &Tables, outface: &FaceState, hop_limit: Option<u8>) -> Option<Option<u8>> {
+    match hop_limit {
+        Some(hop_limit) if outface.zid != tables.zid => hop_limit.checked_sub(1).map(Some),
+        hop_limit => Some(hop_limit),
+    }
+}
+
+/// Whether the data is published on the drain key expression of this router. It is only routed
+/// within the router, which sends its drain announcements to its clients itself.
+#[inline]
+fn is_own_drain_key(tables: &Tables, expr: &mut RoutingExpr) -> bool {
+    tables.whatami == WhatAmI::Router
+        && (expr.prefix.expr().starts_with("@/") || expr.suffix.starts_with("@/"))
+        && expr.full_expr() == format!("@/{}/router/drain", tables.zid)
+}
+
 pub fn route_data(
     tables_ref: &Arc<TablesLock>,
     face: &FaceState,
@@ -407,6 +427,17 @@ pub fn route_data(
                 msg.wire_expr.suffix.as_ref()
             );
             let mut expr = RoutingExpr::new(&prefix, msg.wire_expr.suffix.as_ref());
+            #[cfg(feature = "trace_spans")]
+            let _span = {
+                let key_expr = expr.full_expr();
+                tracing::trace_span!(
+                    "zenoh::route",
+                    %face,
+                    key_expr,
+                    keyexpr_hash = crate::net::routing::keyexpr_hash(key_expr),
+                )
+                .entered()
+            };
 
             #[cfg(feature = "stats")]
             let admin = expr.full_expr().starts_with("@/");
@@ -423,14 +454,26 @@ pub fn route_data(
                 let route = get_data_route(&tables, face, &res, &mut expr, msg.ext_nodeid.node_id);
 
                 if !route.is_empty() {
-                    treat_timestamp!(&tables.hlc, msg.payload, tables.drop_future_timestamp);
+                    treat_timestamp!(
+                        &tables.hlc,
+                        msg.payload,
+                        tables.drop_future_timestamp,
+                        tables.timestamp_drifts
+                    );
+                    let local_only = is_own_drain_key(&tables, &mut expr);
 
                     if route.len() == 1 {
                         let (outface, key_expr, context) = route.values().next().unwrap();
-                        if tables
-                            .hat_code
-                            .egress_filter(&tables, face, outface, &mut expr)
+                        if (!local_only || outface.zid == tables.zid)
+                            && tables
+                                .hat_code
+                                .egress_filter(&tables, face, outface, &mut expr)
                         {
+                            let Some(ext_hop_limit) =
+                                hop_limit_to(&tables, outface, msg.ext_hop_limit)
+                            else {
+                                return;
+                            };
                             drop(tables);
                             #[cfg(feature = "stats")]
                             if !admin {
@@ -445,6 +488,9 @@ pub fn route_data(
                                     ext_qos: msg.ext_qos,
                                     ext_tstamp: msg.ext_tstamp,
                                     ext_nodeid: ext::NodeIdType { node_id: *context },
+                                    ext_hops: msg.ext_hops,
+                                    ext_hop_limit,
+                                    ext_deadline: msg.ext_deadline,
                                     payload: msg.payload,
                                 },
                                 reliability,
@@ -453,16 +499,22 @@ pub fn route_data(
                     } else {
                         let route = route
                             .values()
-                            .filter(|(outface, _key_expr, _context)| {
+                            .filter_map(|direction| {
+                                let (outface, _key_expr, _context) = direction;
+                                if local_only && outface.zid != tables.zid {
+                                    return None;
+                                }
+                                let ext_hop_limit =
+                                    hop_limit_to(&tables, outface, msg.ext_hop_limit)?;
                                 tables
                                     .hat_code
                                     .egress_filter(&tables, face, outface, &mut expr)
+                                    .then(|| (direction.clone(), ext_hop_limit))
                             })
-                            .cloned()
-                            .collect::<Vec<Direction>>();
+                            .collect::<Vec<(Direction, Option<u8>)>>();
 
                         drop(tables);
-                        for (outface, key_expr, context) in route {
+                        for ((outface, key_expr, context), ext_hop_limit) in route {
                             #[cfg(feature = "stats")]
                             if !admin {
                                 inc_stats!(outface, tx, user, msg.payload)
@@ -476,6 +528,9 @@ pub fn route_data(
                                     ext_qos: msg.ext_qos,
                                     ext_tstamp: None,
                                     ext_nodeid: ext::NodeIdType { node_id: context },
+                                    ext_hops: msg.ext_hops,
+                                    ext_hop_limit,
+                                    ext_deadline: msg.ext_deadline,
                                     payload: msg.payload.clone(),
                                 },
                                 reliability,
@@ -13,14 +13,14 @@
 //
 use std::{
     collections::HashMap,
-    sync::{Arc, Weak},
+    sync::{atomic::Ordering, Arc, Weak},
     time::Duration,
 };
 
 use async_trait::async_trait;
 use tokio_util::sync::CancellationToken;
 use zenoh_buffers::ZBuf;
-use zenoh_config::WhatAmI;
+use zenoh_config::{QueryLoadBalancing, WhatAmI};
 #[cfg(feature = "stats")]
 use zenoh_protocol::zenoh::reply::ReplyBody;
 use zenoh_protocol::{
@@ -40,7 +40,7 @@ use zenoh_util::Timed;
 
 use super::{
     face::FaceState,
-    resource::{QueryRoute, QueryRoutes, QueryTargetQablSet, Resource},
+    resource::{QueryRoute, QueryRoutes, QueryTargetQabl, QueryTargetQablSet, Resource},
     tables::{NodeId, RoutingExpr, Tables, TablesLock},
 };
 #[cfg(feature = "unstable")]
@@ -64,6 +64,15 @@ pub(crate) fn get_matching_queryables(
         .get_matching_queryables(tables, key_expr, complete)
 }
 
+#[zenoh_macros::unstable]
+#[inline]
+pub(crate) fn get_matching_queriers(
+    tables: &Tables,
+    key_expr: &KeyExpr<'_>,
+) -> HashMap<usize, Arc<FaceState>> {
+    tables.hat_code.get_matching_queriers(tables, key_expr)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn declare_queryable(
     hat_code: &(dyn HatTrait + Send + Sync),
@@ -314,6 +323,34 @@ fn insert_pending_query(outface: &mut Arc<FaceState>, query: Arc<Query>) -> Requ
 }
 
 #[inline]
+/// Selects the complete queryable a query targeting the best matching queryables is routed to,
+/// among the nearest ones according to the configured load balancing.
+fn select_best_matching_qabl<'a>(
+    tables: &Tables,
+    qabls: &'a QueryTargetQablSet,
+    src_face: &Arc<FaceState>,
+) -> Option<&'a QueryTargetQabl> {
+    // The qabls are sorted by distance
+    let mut candidates = qabls.iter().filter_map(|qabl| match qabl.info {
+        Some(info) if info.complete && qabl.direction.0.id != src_face.id => Some((qabl, info)),
+        _ => None,
+    });
+    let (first, first_info) = candidates.next()?;
+    let nearest = std::iter::once((first, first_info))
+        .chain(candidates.take_while(|(_, info)| info.distance == first_info.distance));
+    match tables.queries_load_balancing {
+        QueryLoadBalancing::None => Some(first),
+        QueryLoadBalancing::RoundRobin => {
+            let nearest: Vec<_> = nearest.collect();
+            let turn = tables.queries_next_qabl.fetch_add(1, Ordering::Relaxed);
+            Some(nearest[turn % nearest.len()].0)
+        }
+        QueryLoadBalancing::LeastLoaded => nearest
+            .min_by_key(|(qabl, info)| (info.load, qabl.direction.0.pending_queries.len()))
+            .map(|(qabl, _)| qabl),
+    }
+}
+
 fn compute_final_route(
     tables: &Tables,
     qabls: &Arc<QueryTargetQablSet>,
@@ -357,9 +394,7 @@ fn compute_final_route(
             route
         }
         QueryTarget::BestMatching => {
-            if let Some(qabl) = qabls.iter().find(|qabl| {
-                qabl.direction.0.id != src_face.id && qabl.info.is_some_and(|info| info.complete)
-            }) {
+            if let Some(qabl) = select_best_matching_qabl(tables, qabls, src_face) {
                 let mut route = HashMap::new();
 
                 let mut direction = qabl.direction.clone();
@@ -371,6 +406,38 @@ fn compute_final_route(
                 compute_final_route(tables, qabls, src_face, expr, &QueryTarget::All, query)
             }
         }
+        #[cfg(feature = "unstable")]
+        QueryTarget::Specific(zid) => {
+            // Faces that did not negotiate the TargetZid extension would ignore it and widen the
+            // query, so the query is never sent to them.
+            let qabls = qabls
+                .iter()
+                .filter(|qabl| qabl.direction.0.patch.has_query_target_zid());
+            let mut route = HashMap::new();
+            if let Some(qabl) = qabls
+                .clone()
+                .find(|qabl| qabl.direction.0.zid == *zid && qabl.direction.0.id != src_face.id)
+            {
+                let mut direction = qabl.direction.clone();
+                let qid = insert_pending_query(&mut direction.0, query);
+                route.insert(direction.0.id, (direction, qid));
+            } else {
+                // The targeted node is not a neighbour, the nodes in between narrow the route
+                for qabl in qabls {
+                    if tables
+                        .hat_code
+                        .egress_filter(tables, src_face, &qabl.direction.0, expr)
+                    {
+                        route.entry(qabl.direction.0.id).or_insert_with(|| {
+                            let mut direction = qabl.direction.clone();
+                            let qid = insert_pending_query(&mut direction.0, query.clone());
+                            (direction, qid)
+                        });
+                    }
+                }
+            }
+            route
+        }
     }
 }
 
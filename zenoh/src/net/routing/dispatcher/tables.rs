@@ -14,20 +14,21 @@
 use std::{
     any::Any,
     collections::HashMap,
-    sync::{Arc, Mutex, RwLock},
+    sync::{atomic::AtomicUsize, Arc, Mutex, RwLock},
     time::Duration,
 };
 
 use uhlc::HLC;
-use zenoh_config::{unwrap_or_default, Config};
+use zenoh_config::{unwrap_or_default, Config, QueryLoadBalancing};
 use zenoh_protocol::{
     core::{ExprId, WhatAmI, ZenohIdProto},
     network::Mapping,
 };
 use zenoh_result::ZResult;
 use zenoh_sync::get_mut_unchecked;
+use zenoh_util::flight_recorder::FlightRecorder;
 
-use super::face::FaceState;
+use super::{dead_letters::DeadLetters, face::FaceState, timestamps::TimestampDrifts};
 pub use super::{pubsub::*, queries::*, resource::*};
 use crate::net::{
     routing::{
@@ -70,7 +71,11 @@ pub struct Tables {
     #[allow(dead_code)]
     pub(crate) hlc: Option<Arc<HLC>>,
     pub(crate) drop_future_timestamp: bool,
+    pub(crate) timestamp_drifts: TimestampDrifts,
     pub(crate) queries_default_timeout: Duration,
+    pub(crate) queries_load_balancing: QueryLoadBalancing,
+    // The turn of the round robin load balancing of the queries
+    pub(crate) queries_next_qabl: AtomicUsize,
     pub(crate) root_res: Arc<Resource>,
     pub(crate) faces: HashMap<usize, Arc<FaceState>>,
     pub(crate) mcast_groups: Vec<Arc<FaceState>>,
@@ -86,6 +91,8 @@ impl Tables {
         whatami: WhatAmI,
         hlc: Option<Arc<HLC>>,
         config: &Config,
+        dead_letters: &Arc<DeadLetters>,
+        flight_recorder: &Arc<FlightRecorder>,
     ) -> ZResult<Self> {
         let drop_future_timestamp =
             unwrap_or_default!(config.timestamping().drop_future_timestamp());
@@ -93,6 +100,7 @@ impl Tables {
             unwrap_or_default!(config.routing().router().peers_failover_brokering());
         let queries_default_timeout =
             Duration::from_millis(unwrap_or_default!(config.queries_default_timeout()));
+        let queries_load_balancing = *config.routing().queries().load_balancing();
         let hat_code = hat::new_hat(whatami, config);
         Ok(Tables {
             zid,
@@ -101,12 +109,15 @@ impl Tables {
             face_counter: 0,
             hlc,
             drop_future_timestamp,
+            timestamp_drifts: TimestampDrifts::new(flight_recorder.clone()),
             queries_default_timeout,
+            queries_load_balancing,
+            queries_next_qabl: AtomicUsize::new(0),
             root_res: Resource::root(),
             faces: HashMap::new(),
             mcast_groups: vec![],
             mcast_faces: vec![],
-            interceptors: interceptor_factories(config)?,
+            interceptors: interceptor_factories(config, dead_letters)?,
             hat: hat_code.new_tables(router_peers_failover_brokering),
             hat_code: hat_code.into(),
         })
@@ -177,4 +188,6 @@ pub struct TablesLock {
     pub tables: RwLock<Tables>,
     pub(crate) ctrl_lock: Mutex<Box<dyn HatTrait + Send + Sync>>,
     pub queries_lock: RwLock<()>,
+    pub dead_letters: Arc<DeadLetters>,
+    pub flight_recorder: Arc<FlightRecorder>,
 }
@@ -0,0 +1,90 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::Arc;
+#[cfg(feature = "unstable")]
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+};
+
+use uhlc::{Timestamp, HLC};
+use zenoh_util::flight_recorder::FlightRecorder;
+
+/// A received timestamp exceeding the maximum delta of the HLC.
+#[cfg(feature = "unstable")]
+#[derive(Clone, Debug)]
+pub(crate) struct Drift {
+    /// The received timestamp.
+    pub(crate) timestamp: Timestamp,
+    /// The time of the HLC when the timestamp was received.
+    pub(crate) local: Timestamp,
+    /// Whether the sample was dropped, or re-timestamped.
+    pub(crate) dropped: bool,
+}
+
+/// The received timestamps exceeding the maximum delta of the HLC.
+pub struct TimestampDrifts {
+    #[cfg(feature = "unstable")]
+    count: AtomicU64,
+    #[cfg(feature = "unstable")]
+    last: Mutex<Option<Drift>>,
+    flight_recorder: Arc<FlightRecorder>,
+}
+
+impl TimestampDrifts {
+    pub(crate) fn new(flight_recorder: Arc<FlightRecorder>) -> Self {
+        Self {
+            #[cfg(feature = "unstable")]
+            count: AtomicU64::new(0),
+            #[cfg(feature = "unstable")]
+            last: Mutex::new(None),
+            flight_recorder,
+        }
+    }
+
+    pub(crate) fn report(&self, hlc: &HLC, timestamp: &Timestamp, dropped: bool) {
+        let local = hlc.new_timestamp();
+        zenoh_util::record_event!(
+            self.flight_recorder,
+            Error,
+            "Received timestamp {timestamp} exceeds the maximum delta of {delta:?} from {local}, \
+             sample {action}",
+            timestamp = *timestamp,
+            delta = hlc.get_delta().to_duration(),
+            local,
+            action = if dropped { "dropped" } else { "re-timestamped" }
+        );
+        #[cfg(feature = "unstable")]
+        {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            *zlock!(self.last) = Some(Drift {
+                timestamp: *timestamp,
+                local,
+                dropped,
+            });
+        }
+    }
+
+    /// The number of received timestamps exceeding the maximum delta.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// The last received timestamp exceeding the maximum delta.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn last(&self) -> Option<Drift> {
+        zlock!(self.last).clone()
+    }
+}
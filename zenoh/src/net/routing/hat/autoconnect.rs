@@ -0,0 +1,108 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{collections::HashMap, net::SocketAddr};
+
+use zenoh_config::{unwrap_or_default, Config, ModeDependent, WhatAmI, WhatAmIMatcher};
+use zenoh_link::Locator;
+use zenoh_result::ZResult;
+use zenoh_util::net::Subnet;
+
+/// Decides which of the nodes discovered through gossip are automatically connected.
+#[derive(Clone)]
+pub(crate) struct AutoconnectFilter {
+    whatami: WhatAmIMatcher,
+    labels: HashMap<String, String>,
+    subnets: Vec<Subnet>,
+}
+
+impl AutoconnectFilter {
+    pub(crate) fn new(config: &Config, whatami: WhatAmI) -> ZResult<Self> {
+        let filter = config.scouting().gossip().autoconnect_filter();
+        Ok(AutoconnectFilter {
+            whatami: if unwrap_or_default!(config.scouting().gossip().enabled()) {
+                *unwrap_or_default!(config.scouting().gossip().autoconnect().get(whatami))
+            } else {
+                WhatAmIMatcher::empty()
+            },
+            labels: filter.labels().clone().unwrap_or_default(),
+            subnets: filter
+                .subnets()
+                .iter()
+                .flatten()
+                .map(|s| s.parse())
+                .collect::<ZResult<_>>()?,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.whatami.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn matches_whatami(&self, whatami: WhatAmI) -> bool {
+        self.whatami.matches(whatami)
+    }
+
+    /// Returns the locators to use to connect the given discovered node,
+    /// or `None` if it should not be connected.
+    pub(crate) fn locators(
+        &self,
+        whatami: WhatAmI,
+        labels: Option<&[(String, String)]>,
+        locators: &[Locator],
+    ) -> Option<Vec<Locator>> {
+        if !self.matches_whatami(whatami) {
+            return None;
+        }
+        let labels = labels.unwrap_or_default();
+        if !self
+            .labels
+            .iter()
+            .all(|(k, v)| labels.iter().any(|(lk, lv)| lk == k && lv == v))
+        {
+            return None;
+        }
+        if self.subnets.is_empty() {
+            return Some(locators.to_vec());
+        }
+        let locators: Vec<Locator> = locators
+            .iter()
+            .filter(|locator| {
+                locator
+                    .address()
+                    .as_str()
+                    .parse::<SocketAddr>()
+                    .is_ok_and(|addr| self.subnets.iter().any(|s| s.contains(&addr.ip())))
+            })
+            .cloned()
+            .collect();
+        (!locators.is_empty()).then_some(locators)
+    }
+}
+
+/// Returns the labels advertised by the local node through gossip.
+pub(crate) fn local_labels(config: &Config) -> Option<Vec<(String, String)>> {
+    config
+        .scouting()
+        .gossip()
+        .labels()
+        .as_ref()
+        .filter(|labels| !labels.is_empty())
+        .map(|labels| {
+            let mut labels: Vec<(String, String)> = labels.clone().into_iter().collect();
+            labels.sort();
+            labels
+        })
+}
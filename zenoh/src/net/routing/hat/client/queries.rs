@@ -51,6 +51,7 @@ use crate::{
 fn merge_qabl_infos(mut this: QueryableInfoType, info: &QueryableInfoType) -> QueryableInfoType {
     this.complete = this.complete || info.complete;
     this.distance = std::cmp::min(this.distance, info.distance);
+    this.load = std::cmp::min(this.load, info.load);
     this
 }
 
@@ -418,6 +419,7 @@ impl HatQueriesTrait for HatCode {
                         info: Some(QueryableInfoType {
                             complete: complete && qabl_info.complete,
                             distance: 1,
+                            load: qabl_info.load,
                         }),
                     });
                 }
@@ -505,4 +507,15 @@ impl HatQueriesTrait for HatCode {
         }
         matching_queryables
     }
+
+    #[cfg(feature = "unstable")]
+    fn get_matching_queriers(
+        &self,
+        tables: &Tables,
+        key_expr: &KeyExpr<'_>,
+    ) -> HashMap<usize, Arc<FaceState>> {
+        crate::net::routing::hat::get_matching_queriers(tables, key_expr, |face| {
+            &face_hat!(face).remote_interests
+        })
+    }
 }
@@ -24,7 +24,7 @@ use std::{
 };
 
 use token::{token_remove_node, undeclare_simple_token};
-use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI, WhatAmIMatcher};
+use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI};
 use zenoh_protocol::{
     common::ZExtBody,
     core::ZenohIdProto,
@@ -50,6 +50,7 @@ use super::{
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
+    autoconnect::{local_labels, AutoconnectFilter},
     HatBaseTrait, HatTrait, SendDeclare,
 };
 use crate::net::{
@@ -189,11 +190,8 @@ impl HatBaseTrait for HatCode {
         if gossip_target.matches(WhatAmI::Client) {
             bail!("\"client\" is not allowed as gossip target")
         }
-        let autoconnect = if gossip {
-            *unwrap_or_default!(config.scouting().gossip().autoconnect().get(whatami))
-        } else {
-            WhatAmIMatcher::empty()
-        };
+        let autoconnect = AutoconnectFilter::new(config, whatami)?;
+        let labels = local_labels(config);
 
         let peer_full_linkstate =
             unwrap_or_default!(config.routing().peer().mode()) == *"linkstate";
@@ -211,6 +209,7 @@ impl HatBaseTrait for HatCode {
             gossip_multihop,
             gossip_target,
             autoconnect,
+            labels,
         ));
         Ok(())
     }
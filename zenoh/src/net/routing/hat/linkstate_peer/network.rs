@@ -35,7 +35,7 @@ use zenoh_transport::unicast::TransportUnicast;
 use crate::net::{
     codec::Zenoh080Routing,
     protocol::linkstate::{LinkState, LinkStateList},
-    routing::dispatcher::tables::NodeId,
+    routing::{dispatcher::tables::NodeId, hat::autoconnect::AutoconnectFilter},
     runtime::{Runtime, WeakRuntime},
 };
 
@@ -51,6 +51,7 @@ pub(super) struct Node {
     pub(super) zid: ZenohIdProto,
     pub(super) whatami: Option<WhatAmI>,
     pub(super) locators: Option<Vec<Locator>>,
+    pub(super) labels: Option<Vec<(String, String)>>,
     pub(super) sn: u64,
     pub(super) links: Vec<ZenohIdProto>,
 }
@@ -120,7 +121,7 @@ pub(super) struct Network {
     pub(super) gossip: bool,
     pub(super) gossip_multihop: bool,
     pub(super) gossip_target: WhatAmIMatcher,
-    pub(super) autoconnect: WhatAmIMatcher,
+    pub(super) autoconnect: AutoconnectFilter,
     pub(super) idx: NodeIndex,
     pub(super) links: VecMap<Link>,
     pub(super) trees: Vec<Tree>,
@@ -140,7 +141,8 @@ impl Network {
         gossip: bool,
         gossip_multihop: bool,
         gossip_target: WhatAmIMatcher,
-        autoconnect: WhatAmIMatcher,
+        autoconnect: AutoconnectFilter,
+        labels: Option<Vec<(String, String)>>,
     ) -> Self {
         let mut graph = petgraph::stable_graph::StableGraph::default();
         tracing::debug!("{} Add node (self) {}", name, zid);
@@ -148,6 +150,7 @@ impl Network {
             zid,
             whatami: Some(runtime.whatami()),
             locators: None,
+            labels,
             sn: 1,
             links: vec![],
         });
@@ -267,6 +270,11 @@ impl Network {
             } else {
                 None
             },
+            labels: if details.locators {
+                self.graph[idx].labels.clone()
+            } else {
+                None
+            },
             links,
         }
     }
@@ -394,6 +402,7 @@ impl Network {
                         zid,
                         link_state.whatami.unwrap_or(WhatAmI::Router),
                         link_state.locators,
+                        link_state.labels,
                         link_state.sn,
                         link_state.links,
                     ))
@@ -403,6 +412,7 @@ impl Network {
                             *zid,
                             link_state.whatami.unwrap_or(WhatAmI::Router),
                             link_state.locators,
+                            link_state.labels,
                             link_state.sn,
                             link_state.links,
                         )),
@@ -423,7 +433,7 @@ impl Network {
         let src_link = self.get_link_from_zid(&src).unwrap();
         let link_states = link_states
             .into_iter()
-            .map(|(zid, wai, locs, sn, links)| {
+            .map(|(zid, wai, locs, labels, sn, links)| {
                 let links: Vec<ZenohIdProto> = links
                     .iter()
                     .filter_map(|l| {
@@ -440,7 +450,7 @@ impl Network {
                         }
                     })
                     .collect();
-                (zid, wai, locs, sn, links)
+                (zid, wai, locs, labels, sn, links)
             })
             .collect::<Vec<_>>();
 
@@ -464,13 +474,14 @@ impl Network {
                 updated_nodes: vec![],
                 removed_nodes: vec![],
             };
-            for (zid, whatami, locators, sn, links) in link_states.into_iter() {
+            for (zid, whatami, locators, labels, sn, links) in link_states.into_iter() {
                 let idx = match self.get_idx(&zid) {
                     None => {
                         let idx = self.add_node(Node {
                             zid,
                             whatami: Some(whatami),
                             locators: locators.clone(),
+                            labels: labels.clone(),
                             sn,
                             links,
                         });
@@ -485,10 +496,13 @@ impl Network {
                                 node.sn = sn;
                                 node.links.clone_from(&links);
                                 changes.updated_nodes.push((idx, node.clone()));
-                                (node.locators != locators && locators.is_some()).then(|| {
-                                    node.locators.clone_from(&locators);
-                                    idx
-                                })
+                                (locators.is_some()
+                                    && (node.locators != locators || node.labels != labels))
+                                    .then(|| {
+                                        node.locators.clone_from(&locators);
+                                        node.labels.clone_from(&labels);
+                                        idx
+                                    })
                             })
                             .flatten()
                     }
@@ -510,9 +524,12 @@ impl Network {
                             );
                         }
 
-                        if !self.autoconnect.is_empty() && self.autoconnect.matches(whatami) {
+                        if !self.autoconnect.is_empty() {
                             // Connect discovered peers
-                            if let Some(locators) = locators {
+                            if let Some(locators) = locators.and_then(|locators| {
+                                self.autoconnect
+                                    .locators(whatami, labels.as_deref(), &locators)
+                            }) {
                                 let runtime = strong_runtime.clone();
                                 strong_runtime.spawn(async move {
                                     if runtime
@@ -541,7 +558,7 @@ impl Network {
         let mut link_states = link_states
             .into_iter()
             .filter_map(
-                |(zid, whatami, locators, sn, links)| match self.get_idx(&zid) {
+                |(zid, whatami, locators, labels, sn, links)| match self.get_idx(&zid) {
                     Some(idx) => {
                         let node = &mut self.graph[idx];
                         let oldsn = node.sn;
@@ -550,6 +567,7 @@ impl Network {
                             node.links.clone_from(&links);
                             if locators.is_some() {
                                 node.locators = locators;
+                                node.labels = labels;
                             }
                             if oldsn == 0 {
                                 Some((links, idx, true))
@@ -565,6 +583,7 @@ impl Network {
                             zid,
                             whatami: Some(whatami),
                             locators,
+                            labels,
                             sn,
                             links: links.clone(),
                         };
@@ -595,6 +614,7 @@ impl Network {
                         zid: *link,
                         whatami: None,
                         locators: None,
+                        labels: None,
                         sn: 0,
                         links: vec![],
                     };
@@ -633,27 +653,27 @@ impl Network {
             for (_, idx, _) in &link_states {
                 let node = &self.graph[*idx];
                 if let Some(whatami) = node.whatami {
-                    if self.autoconnect.matches(whatami) {
-                        if let Some(locators) = &node.locators {
-                            let runtime = strong_runtime.clone();
-                            let zid = node.zid;
-                            let locators = locators.clone();
-                            strong_runtime.spawn(async move {
-                                if runtime
-                                    .manager()
-                                    .get_transport_unicast(&zid)
-                                    .await
-                                    .is_none()
-                                {
-                                    // random backoff
-                                    let sleep_time = std::time::Duration::from_millis(
-                                        rand::thread_rng().gen_range(0..100),
-                                    );
-                                    tokio::time::sleep(sleep_time).await;
-                                    runtime.connect_peer(&zid, &locators).await;
-                                }
-                            });
-                        }
+                    if let Some(locators) = node.locators.as_ref().and_then(|locators| {
+                        self.autoconnect
+                            .locators(whatami, node.labels.as_deref(), locators)
+                    }) {
+                        let runtime = strong_runtime.clone();
+                        let zid = node.zid;
+                        strong_runtime.spawn(async move {
+                            if runtime
+                                .manager()
+                                .get_transport_unicast(&zid)
+                                .await
+                                .is_none()
+                            {
+                                // random backoff
+                                let sleep_time = std::time::Duration::from_millis(
+                                    rand::thread_rng().gen_range(0..100),
+                                );
+                                tokio::time::sleep(sleep_time).await;
+                                runtime.connect_peer(&zid, &locators).await;
+                            }
+                        });
                     }
                 }
             }
@@ -741,6 +761,7 @@ impl Network {
                             zid,
                             whatami: Some(whatami),
                             locators: None,
+                            labels: None,
                             sn: 0,
                             links: vec![],
                         }),
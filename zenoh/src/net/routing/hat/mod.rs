@@ -31,7 +31,10 @@ use zenoh_protocol::{
 use zenoh_result::ZResult;
 use zenoh_transport::unicast::TransportUnicast;
 #[cfg(feature = "unstable")]
-use {crate::key_expr::KeyExpr, std::collections::HashMap};
+use {
+    super::dispatcher::interests::RemoteInterest, crate::key_expr::KeyExpr,
+    std::collections::HashMap,
+};
 
 use super::{
     dispatcher::{
@@ -44,6 +47,7 @@ use super::{
 };
 use crate::net::runtime::Runtime;
 
+mod autoconnect;
 mod client;
 mod linkstate_peer;
 mod p2p_peer;
@@ -68,6 +72,13 @@ impl Sources {
             clients: vec![],
         }
     }
+
+    /// Iterates over the zids of the sources along with their [`WhatAmI`].
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (WhatAmI, &ZenohIdProto)> {
+        (self.routers.iter().map(|zid| (WhatAmI::Router, zid)))
+            .chain(self.peers.iter().map(|zid| (WhatAmI::Peer, zid)))
+            .chain(self.clients.iter().map(|zid| (WhatAmI::Client, zid)))
+    }
 }
 
 pub(crate) type SendDeclare<'a> = dyn FnMut(&Arc<dyn crate::net::primitives::EPrimitives + Send + Sync>, RoutingContext<Declare>)
@@ -244,6 +255,37 @@ pub(crate) trait HatQueriesTrait {
         key_expr: &KeyExpr<'_>,
         complete: bool,
     ) -> HashMap<usize, Arc<FaceState>>;
+
+    #[zenoh_macros::unstable]
+    fn get_matching_queriers(
+        &self,
+        tables: &Tables,
+        key_expr: &KeyExpr<'_>,
+    ) -> HashMap<usize, Arc<FaceState>>;
+}
+
+/// The faces that declared a queryables interest intersecting `key_expr`, `remote_interests`
+/// giving the interests a face declared in the hat.
+#[zenoh_macros::unstable]
+pub(crate) fn get_matching_queriers(
+    tables: &Tables,
+    key_expr: &KeyExpr<'_>,
+    remote_interests: impl Fn(&Arc<FaceState>) -> &HashMap<InterestId, RemoteInterest>,
+) -> HashMap<usize, Arc<FaceState>> {
+    tracing::trace!("get_matching_queriers({})", key_expr);
+    tables
+        .faces
+        .values()
+        .filter(|face| {
+            remote_interests(face).values().any(|interest| {
+                interest.options.queryables()
+                    && interest.res.as_ref().map_or(false, |res| {
+                        KeyExpr::keyexpr_intersect(res.expr(), key_expr)
+                    })
+            })
+        })
+        .map(|face| (face.id, face.clone()))
+        .collect()
 }
 
 pub(crate) fn new_hat(whatami: WhatAmI, config: &Config) -> Box<dyn HatTrait + Send + Sync> {
@@ -31,6 +31,7 @@ use zenoh_transport::unicast::TransportUnicast;
 use crate::net::{
     codec::Zenoh080Routing,
     protocol::linkstate::{LinkState, LinkStateList},
+    routing::hat::autoconnect::AutoconnectFilter,
     runtime::{Runtime, WeakRuntime},
 };
 
@@ -46,6 +47,7 @@ pub(super) struct Node {
     pub(super) zid: ZenohIdProto,
     pub(super) whatami: Option<WhatAmI>,
     pub(super) locators: Option<Vec<Locator>>,
+    pub(super) labels: Option<Vec<(String, String)>>,
     pub(super) sn: u64,
     pub(super) links: Vec<ZenohIdProto>,
 }
@@ -97,7 +99,7 @@ pub(super) struct Network {
     pub(super) gossip: bool,
     pub(super) gossip_multihop: bool,
     pub(super) gossip_target: WhatAmIMatcher,
-    pub(super) autoconnect: WhatAmIMatcher,
+    pub(super) autoconnect: AutoconnectFilter,
     pub(super) wait_declares: bool,
     pub(super) idx: NodeIndex,
     pub(super) links: VecMap<Link>,
@@ -115,7 +117,8 @@ impl Network {
         gossip: bool,
         gossip_multihop: bool,
         gossip_target: WhatAmIMatcher,
-        autoconnect: WhatAmIMatcher,
+        autoconnect: AutoconnectFilter,
+        labels: Option<Vec<(String, String)>>,
         wait_declares: bool,
     ) -> Self {
         let mut graph = petgraph::stable_graph::StableGraph::default();
@@ -124,6 +127,7 @@ impl Network {
             zid,
             whatami: Some(runtime.whatami()),
             locators: None,
+            labels,
             sn: 1,
             links: vec![],
         });
@@ -212,6 +216,11 @@ impl Network {
             } else {
                 None
             },
+            labels: if details.locators {
+                self.graph[idx].labels.clone()
+            } else {
+                None
+            },
             links,
         }
     }
@@ -325,6 +334,7 @@ impl Network {
                         zid,
                         link_state.whatami.unwrap_or(WhatAmI::Router),
                         link_state.locators,
+                        link_state.labels,
                         link_state.sn,
                         link_state.links,
                     ))
@@ -334,6 +344,7 @@ impl Network {
                             *zid,
                             link_state.whatami.unwrap_or(WhatAmI::Router),
                             link_state.locators,
+                            link_state.labels,
                             link_state.sn,
                             link_state.links,
                         )),
@@ -354,7 +365,7 @@ impl Network {
         let src_link = self.get_link_from_zid(&src).unwrap();
         let link_states = link_states
             .into_iter()
-            .map(|(zid, wai, locs, sn, links)| {
+            .map(|(zid, wai, locs, labels, sn, links)| {
                 let links: Vec<ZenohIdProto> = links
                     .iter()
                     .filter_map(|l| {
@@ -371,7 +382,7 @@ impl Network {
                         }
                     })
                     .collect();
-                (zid, wai, locs, sn, links)
+                (zid, wai, locs, labels, sn, links)
             })
             .collect::<Vec<_>>();
 
@@ -390,13 +401,14 @@ impl Network {
             );
         }
 
-        for (zid, whatami, locators, sn, links) in link_states.into_iter() {
+        for (zid, whatami, locators, labels, sn, links) in link_states.into_iter() {
             let idx = match self.get_idx(&zid) {
                 None => {
                     let idx = self.add_node(Node {
                         zid,
                         whatami: Some(whatami),
                         locators: locators.clone(),
+                        labels: labels.clone(),
                         sn,
                         links,
                     });
@@ -409,10 +421,13 @@ impl Network {
                         .then(|| {
                             node.sn = sn;
                             node.links.clone_from(&links);
-                            (node.locators != locators && locators.is_some()).then(|| {
-                                node.locators.clone_from(&locators);
-                                idx
-                            })
+                            (locators.is_some()
+                                && (node.locators != locators || node.labels != labels))
+                                .then(|| {
+                                    node.locators.clone_from(&locators);
+                                    node.labels.clone_from(&labels);
+                                    idx
+                                })
                         })
                         .flatten()
                 }
@@ -439,9 +454,12 @@ impl Network {
                         );
                     }
 
-                    if !self.autoconnect.is_empty() && self.autoconnect.matches(whatami) {
+                    if !self.autoconnect.is_empty() {
                         // Connect discovered peers
-                        if let Some(locators) = locators {
+                        if let Some(locators) = locators.and_then(|locators| {
+                            self.autoconnect
+                                .locators(whatami, labels.as_deref(), &locators)
+                        }) {
                             let runtime = strong_runtime.clone();
                             let wait_declares = self.wait_declares;
                             strong_runtime.spawn(async move {
@@ -496,6 +514,7 @@ impl Network {
                             zid,
                             whatami: Some(whatami),
                             locators: None,
+                            labels: None,
                             sn: 0,
                             links: vec![],
                         }),
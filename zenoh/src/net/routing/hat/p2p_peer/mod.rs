@@ -24,7 +24,7 @@ use std::{
 };
 
 use token::{token_new_face, undeclare_simple_token};
-use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI, WhatAmIMatcher};
+use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI};
 use zenoh_protocol::{
     common::ZExtBody,
     network::{
@@ -53,6 +53,7 @@ use super::{
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
+    autoconnect::{local_labels, AutoconnectFilter},
     HatBaseTrait, HatTrait, SendDeclare,
 };
 use crate::net::{
@@ -119,11 +120,8 @@ impl HatBaseTrait for HatCode {
         if gossip_target.matches(WhatAmI::Client) {
             bail!("\"client\" is not allowed as gossip target")
         }
-        let autoconnect = if gossip {
-            *unwrap_or_default!(config.scouting().gossip().autoconnect().get(whatami))
-        } else {
-            WhatAmIMatcher::empty()
-        };
+        let autoconnect = AutoconnectFilter::new(config, whatami)?;
+        let labels = local_labels(config);
         let wait_declares = unwrap_or_default!(config.open().return_conditions().declares());
         let router_peers_failover_brokering =
             unwrap_or_default!(config.routing().router().peers_failover_brokering());
@@ -139,6 +137,7 @@ impl HatBaseTrait for HatCode {
                 gossip_multihop,
                 gossip_target,
                 autoconnect,
+                labels,
                 wait_declares,
             ));
         }
@@ -25,7 +25,7 @@ use std::{
 };
 
 use token::{token_linkstate_change, token_remove_node, undeclare_simple_token};
-use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI, WhatAmIMatcher};
+use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI};
 use zenoh_protocol::{
     common::ZExtBody,
     core::ZenohIdProto,
@@ -51,6 +51,7 @@ use super::{
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
+    autoconnect::{local_labels, AutoconnectFilter},
     HatBaseTrait, HatTrait, SendDeclare,
 };
 use crate::net::{
@@ -316,11 +317,8 @@ impl HatBaseTrait for HatCode {
         if gossip_target.matches(WhatAmI::Client) {
             bail!("\"client\" is not allowed as gossip target")
         }
-        let autoconnect = if gossip {
-            *unwrap_or_default!(config.scouting().gossip().autoconnect().get(whatami))
-        } else {
-            WhatAmIMatcher::empty()
-        };
+        let autoconnect = AutoconnectFilter::new(config, whatami)?;
+        let labels = local_labels(config);
 
         let router_full_linkstate = true;
         let peer_full_linkstate =
@@ -339,7 +337,8 @@ impl HatBaseTrait for HatCode {
                 gossip,
                 gossip_multihop,
                 gossip_target,
-                autoconnect,
+                autoconnect.clone(),
+                labels.clone(),
             ));
         }
         if peer_full_linkstate | gossip {
@@ -353,6 +352,7 @@ impl HatBaseTrait for HatCode {
                 gossip_multihop,
                 gossip_target,
                 autoconnect,
+                labels,
             ));
         }
         if router_full_linkstate && peer_full_linkstate {
@@ -59,6 +59,7 @@ use crate::net::routing::{
 fn merge_qabl_infos(mut this: QueryableInfoType, info: &QueryableInfoType) -> QueryableInfoType {
     this.complete = this.complete || info.complete;
     this.distance = std::cmp::min(this.distance, info.distance);
+    this.load = std::cmp::min(this.load, info.load);
     this
 }
 
@@ -1106,6 +1107,7 @@ fn insert_target_for_qabls(
                                         info: Some(QueryableInfoType {
                                             complete: complete && qabl_info.complete,
                                             distance: net.distances[qabl_idx.index()] as u16,
+                                            load: qabl_info.load,
                                         }),
                                     });
                                 }
@@ -1501,6 +1503,7 @@ impl HatQueriesTrait for HatCode {
                                 info: Some(QueryableInfoType {
                                     complete: complete && qabl_info.complete,
                                     distance: 1,
+                                    load: qabl_info.load,
                                 }),
                             });
                         }
@@ -1588,6 +1591,17 @@ impl HatQueriesTrait for HatCode {
         }
         matching_queryables
     }
+
+    #[cfg(feature = "unstable")]
+    fn get_matching_queriers(
+        &self,
+        tables: &Tables,
+        key_expr: &KeyExpr<'_>,
+    ) -> HashMap<usize, Arc<FaceState>> {
+        crate::net::routing::hat::get_matching_queriers(tables, key_expr, |face| {
+            &face_hat!(face).remote_interests
+        })
+    }
 }
 
 #[cfg(feature = "unstable")]
@@ -36,6 +36,7 @@ use zenoh_result::ZResult;
 use zenoh_transport::{
     multicast::TransportMulticast,
     unicast::{authentication::AuthId, TransportUnicast},
+    DropReason,
 };
 
 use super::{
@@ -44,10 +45,14 @@ use super::{
 };
 use crate::{
     api::key_expr::KeyExpr,
-    net::routing::{interceptor::authorization::SubjectQuery, RoutingContext},
+    net::routing::{
+        dispatcher::dead_letters::DeadLetters, interceptor::authorization::SubjectQuery,
+        RoutingContext,
+    },
 };
 pub struct AclEnforcer {
     enforcer: Arc<PolicyEnforcer>,
+    dead_letters: Arc<DeadLetters>,
 }
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AuthSubject {
@@ -59,16 +64,19 @@ struct EgressAclEnforcer {
     policy_enforcer: Arc<PolicyEnforcer>,
     subject: Vec<AuthSubject>,
     zid: ZenohIdProto,
+    dead_letters: Arc<DeadLetters>,
 }
 
 struct IngressAclEnforcer {
     policy_enforcer: Arc<PolicyEnforcer>,
     subject: Vec<AuthSubject>,
     zid: ZenohIdProto,
+    dead_letters: Arc<DeadLetters>,
 }
 
 pub(crate) fn acl_interceptor_factories(
     acl_config: &AclConfig,
+    dead_letters: &Arc<DeadLetters>,
 ) -> ZResult<Vec<InterceptorFactory>> {
     let mut res: Vec<InterceptorFactory> = vec![];
 
@@ -79,6 +87,7 @@ pub(crate) fn acl_interceptor_factories(
                 tracing::debug!("Access control is enabled");
                 res.push(Box::new(AclEnforcer {
                     enforcer: Arc::new(policy_enforcer),
+                    dead_letters: dead_letters.clone(),
                 }))
             }
             Err(e) => bail!("Access control not enabled due to: {}", e),
@@ -185,11 +194,13 @@ impl InterceptorFactoryTrait for AclEnforcer {
             policy_enforcer: self.enforcer.clone(),
             zid,
             subject: auth_subjects.clone(),
+            dead_letters: self.dead_letters.clone(),
         });
         let egress_interceptor = Box::new(EgressAclEnforcer {
             policy_enforcer: self.enforcer.clone(),
             zid,
             subject: auth_subjects,
+            dead_letters: self.dead_letters.clone(),
         });
         (
             self.enforcer
@@ -258,6 +269,8 @@ impl InterceptorTrait for IngressAclEnforcer {
                 ..
             }) => {
                 if self.action(AclMessage::Put, "Put (ingress)", key_expr?) == Permission::Deny {
+                    self.dead_letters
+                        .report(DropReason::AccessDenied, key_expr?, &ctx.msg);
                     return None;
                 }
             }
@@ -268,6 +281,8 @@ impl InterceptorTrait for IngressAclEnforcer {
                 if self.action(AclMessage::Delete, "Delete (ingress)", key_expr?)
                     == Permission::Deny
                 {
+                    self.dead_letters
+                        .report(DropReason::AccessDenied, key_expr?, &ctx.msg);
                     return None;
                 }
             }
@@ -466,6 +481,8 @@ impl InterceptorTrait for EgressAclEnforcer {
                 ..
             }) => {
                 if self.action(AclMessage::Put, "Put (egress)", key_expr?) == Permission::Deny {
+                    self.dead_letters
+                        .report(DropReason::AccessDenied, key_expr?, &ctx.msg);
                     return None;
                 }
             }
@@ -475,6 +492,8 @@ impl InterceptorTrait for EgressAclEnforcer {
             }) => {
                 if self.action(AclMessage::Delete, "Delete (egress)", key_expr?) == Permission::Deny
                 {
+                    self.dead_letters
+                        .report(DropReason::AccessDenied, key_expr?, &ctx.msg);
                     return None;
                 }
             }
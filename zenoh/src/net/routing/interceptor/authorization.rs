@@ -25,6 +25,8 @@ use zenoh_config::{
     AclConfig, AclConfigPolicyEntry, AclConfigRule, AclConfigSubjects, AclMessage, CertCommonName,
     InterceptorFlow, Interface, Permission, PolicyRule, Username,
 };
+#[cfg(feature = "unstable")]
+use zenoh_keyexpr::keyexpr_tree::IKeyExprTreeNode;
 use zenoh_keyexpr::{
     keyexpr,
     keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, KeBoxTree},
@@ -157,7 +159,8 @@ impl SubjectMapBuilder {
     }
 }
 
-type KeTreeRule = KeBoxTree<bool>;
+// The weight of a key expression is the id of the first rule which set it
+type KeTreeRule = KeBoxTree<String>;
 
 #[derive(Default)]
 struct PermissionPolicy {
@@ -345,11 +348,14 @@ impl PolicyEnforcer {
                     let mut main_policy: PolicyMap = PolicyMap::default();
                     for rule in policy_information.policy_rules {
                         let subject_policy = main_policy.entry(rule.subject_id).or_default();
-                        subject_policy
+                        let tree = subject_policy
                             .flow_mut(rule.flow)
                             .action_mut(rule.message)
-                            .permission_mut(rule.permission)
-                            .insert(keyexpr::new(&rule.key_expr)?, true);
+                            .permission_mut(rule.permission);
+                        let key_expr = keyexpr::new(&rule.key_expr)?;
+                        if tree.weight_at(key_expr).is_none() {
+                            tree.insert(key_expr, rule.rule_id);
+                        }
 
                         if self.default_permission == Permission::Deny {
                             self.interface_enabled = InterfaceEnabled {
@@ -558,6 +564,7 @@ impl PolicyEnforcer {
                                         message: *message,
                                         permission: rule.permission,
                                         flow: *flow,
+                                        rule_id: rule.id.clone(),
                                     });
                                 }
                             }
@@ -617,4 +624,47 @@ impl PolicyEnforcer {
             None => Ok(self.default_permission),
         }
     }
+
+    /**
+     * Check a msg of the given subject against the ACL ruleset like the policy decision point,
+     * also returning the id of the rule which decided the permission, if any
+     */
+    #[cfg(feature = "unstable")]
+    pub(crate) fn explain(
+        &self,
+        subject: &SubjectQuery,
+        flow: InterceptorFlow,
+        message: AclMessage,
+        key_expr: &keyexpr,
+    ) -> (Permission, Option<String>) {
+        if !self.acl_enabled {
+            return (Permission::Allow, None);
+        }
+        let Some(single_policy) = self
+            .subject_store
+            .query(subject)
+            .and_then(|entry| self.policy_map.get(&entry.id))
+        else {
+            return (self.default_permission, None);
+        };
+        let policy = single_policy.flow(flow).action(message);
+        if let Some(rule_id) = policy
+            .deny
+            .nodes_including(key_expr)
+            .find_map(|n| n.weight())
+        {
+            return (Permission::Deny, Some(rule_id.clone()));
+        }
+        if self.default_permission == Permission::Allow {
+            return (Permission::Allow, None);
+        }
+        match policy
+            .allow
+            .nodes_including(key_expr)
+            .find_map(|n| n.weight())
+        {
+            Some(rule_id) => (Permission::Allow, Some(rule_id.clone())),
+            None => (Permission::Deny, None),
+        }
+    }
 }
@@ -0,0 +1,118 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::sync::Arc;
+
+use zenoh_config::{CaptureConf, WhatAmI};
+use zenoh_protocol::core::ZenohIdProto;
+use zenoh_result::ZResult;
+
+use crate::{
+    api::capture::{CaptureDirection, CaptureWriter},
+    net::routing::interceptor::*,
+};
+
+pub(crate) fn capture_interceptor_factories(
+    config: &CaptureConf,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+    if let Some(file) = config.file() {
+        res.push(Box::new(CaptureInterceptorFactory {
+            writer: Arc::new(CaptureWriter::open(file)?),
+        }));
+    }
+    Ok(res)
+}
+
+pub(crate) struct CaptureInterceptorFactory {
+    writer: Arc<CaptureWriter>,
+}
+
+impl CaptureInterceptorFactory {
+    fn interceptor(
+        &self,
+        direction: CaptureDirection,
+        zid: ZenohIdProto,
+        whatami: WhatAmI,
+    ) -> Option<Interceptor> {
+        Some(Box::new(CaptureInterceptor {
+            writer: self.writer.clone(),
+            direction,
+            zid,
+            whatami,
+        }))
+    }
+}
+
+impl InterceptorFactoryTrait for CaptureInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        let (Ok(zid), Ok(whatami)) = (transport.get_zid(), transport.get_whatami()) else {
+            return (None, None);
+        };
+        (
+            self.interceptor(CaptureDirection::Ingress, zid, whatami),
+            self.interceptor(CaptureDirection::Egress, zid, whatami),
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+/// Writes the messages of a unicast transport to the capture file, as they are received from
+/// the transport or before they are filtered by the egress interceptors that follow it.
+pub(crate) struct CaptureInterceptor {
+    writer: Arc<CaptureWriter>,
+    direction: CaptureDirection,
+    zid: ZenohIdProto,
+    whatami: WhatAmI,
+}
+
+impl InterceptorTrait for CaptureInterceptor {
+    fn compute_keyexpr_cache(&self, _key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        None
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        _cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        self.writer.write(
+            self.direction,
+            &self.zid,
+            self.whatami,
+            ctx.full_expr(),
+            &ctx.msg,
+        );
+        Some(ctx)
+    }
+}
@@ -0,0 +1,219 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use zenoh_config::{Config, WhatAmI};
+use zenoh_core::zlock;
+use zenoh_keyexpr::{keyexpr, OwnedKeyExpr};
+use zenoh_protocol::{
+    core::{Reliability, WireExpr, ZenohIdProto},
+    network::{
+        response::{self, ext::ResponderIdType},
+        Declare, DeclareBody, Mapping, NetworkBody, Push, Request, Response,
+    },
+    zenoh::{reply::ReplyBody, ConsolidationMode, PushBody, Reply, RequestBody, ResponseBody},
+};
+use zenoh_result::ZResult;
+
+use crate::net::routing::{dispatcher::face::Face, interceptor::*};
+
+pub(crate) fn last_value_cache_interceptor_factories(
+    config: &Config,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+    let conf = config.routing().router().last_value_cache();
+    if *config.mode() == Some(WhatAmI::Router) && !conf.key_exprs().is_empty() {
+        res.push(Box::new(LastValueCacheInterceptorFactory {
+            zid: config.id().unwrap_or_default().into(),
+            cache: Arc::new(LastValueCache {
+                key_exprs: conf.key_exprs().clone(),
+                max_keys: conf.max_keys().unwrap_or(usize::MAX),
+                samples: Mutex::new(HashMap::new()),
+            }),
+        }));
+    }
+    Ok(res)
+}
+
+struct LastValueCache {
+    key_exprs: Vec<OwnedKeyExpr>,
+    max_keys: usize,
+    samples: Mutex<HashMap<OwnedKeyExpr, (Push, Reliability)>>,
+}
+
+impl LastValueCache {
+    fn store(&self, key_expr: OwnedKeyExpr, mut push: Push, reliability: Reliability) {
+        let mut samples = zlock!(self.samples);
+        match push.payload {
+            PushBody::Put(_) => {
+                if samples.len() >= self.max_keys && !samples.contains_key(&key_expr) {
+                    tracing::debug!(
+                        "Last value cache is full, not caching sample for {}",
+                        key_expr
+                    );
+                    return;
+                }
+                push.wire_expr = WireExpr {
+                    scope: 0,
+                    suffix: key_expr.to_string().into(),
+                    mapping: Mapping::Sender,
+                };
+                samples.insert(key_expr, (push, reliability));
+            }
+            PushBody::Del(_) => {
+                samples.remove(&key_expr);
+            }
+        }
+    }
+
+    fn intersecting(&self, key_expr: &keyexpr) -> Vec<(Push, Reliability)> {
+        zlock!(self.samples)
+            .iter()
+            .filter(|(k, _)| k.intersects(key_expr))
+            .map(|(_, sample)| sample.clone())
+            .collect()
+    }
+}
+
+pub(crate) struct LastValueCacheInterceptorFactory {
+    zid: ZenohIdProto,
+    cache: Arc<LastValueCache>,
+}
+
+impl InterceptorFactoryTrait for LastValueCacheInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        _transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        (Some(self.interceptor()), None)
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        Some(self.interceptor())
+    }
+}
+
+impl LastValueCacheInterceptorFactory {
+    fn interceptor(&self) -> Interceptor {
+        Box::new(ComputeOnMiss::new(LastValueCacheInterceptor {
+            zid: self.zid,
+            cache: self.cache.clone(),
+        }))
+    }
+}
+
+pub(crate) struct LastValueCacheInterceptor {
+    zid: ZenohIdProto,
+    cache: Arc<LastValueCache>,
+}
+
+impl LastValueCacheInterceptor {
+    fn serves(face: &Face) -> bool {
+        face.state.whatami != WhatAmI::Router && face.state.mcast_group.is_none()
+    }
+}
+
+impl InterceptorTrait for LastValueCacheInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(KeyExprCoverage::new(
+            &self.cache.key_exprs,
+            key_expr,
+        )))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        // How the key expression relates to the cached key expressions
+        let Some(cached) = cache.and_then(|c| c.downcast_ref::<KeyExprCoverage>()) else {
+            return Some(ctx);
+        };
+        match &ctx.msg.body {
+            NetworkBody::Push(push) if cached.included => {
+                if let Some(key_expr) = ctx.full_key_expr() {
+                    self.cache
+                        .store(key_expr, push.clone(), ctx.msg.reliability);
+                }
+            }
+            // Cached samples are only served to the faces that hold the subscribers or queriers,
+            // routers will get them from the router they are connected to. They are sent through
+            // the primitives of the face, that apply its egress interceptors (access control,
+            // downsampling, regions, ...) as for routed samples. Multicast faces have no such
+            // primitives: they only fill the cache.
+            NetworkBody::Declare(Declare {
+                body: DeclareBody::DeclareSubscriber(_),
+                ..
+            }) if cached.intersects => {
+                if let (Some(face), Some(key_expr)) = (ctx.inface(), ctx.full_key_expr()) {
+                    if Self::serves(face) {
+                        for (push, reliability) in self.cache.intersecting(&key_expr) {
+                            face.state.primitives.send_push(push, reliability);
+                        }
+                    }
+                }
+            }
+            NetworkBody::Request(Request {
+                id,
+                payload: RequestBody::Query(_),
+                ..
+            }) if cached.intersects => {
+                if let (Some(face), Some(key_expr)) = (ctx.inface(), ctx.full_key_expr()) {
+                    if Self::serves(face) {
+                        for (push, _) in self.cache.intersecting(&key_expr) {
+                            let PushBody::Put(put) = push.payload else {
+                                continue;
+                            };
+                            face.state.primitives.send_response(Response {
+                                rid: *id,
+                                wire_expr: push.wire_expr,
+                                payload: ResponseBody::Reply(Reply {
+                                    consolidation: ConsolidationMode::DEFAULT,
+                                    ext_unknown: vec![],
+                                    payload: ReplyBody::Put(put),
+                                }),
+                                ext_qos: response::ext::QoSType::RESPONSE,
+                                ext_tstamp: None,
+                                ext_respid: Some(ResponderIdType {
+                                    zid: self.zid,
+                                    eid: 0,
+                                }),
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        Some(ctx)
+    }
+}
@@ -21,20 +21,35 @@
 mod access_control;
 use access_control::acl_interceptor_factories;
 
-mod authorization;
-use std::any::Any;
+pub(crate) mod authorization;
+use std::{any::Any, sync::Arc};
 
 use zenoh_config::Config;
-use zenoh_protocol::network::NetworkMessage;
+use zenoh_keyexpr::{keyexpr, OwnedKeyExpr};
+use zenoh_protocol::network::{Declare, DeclareBody, NetworkBody, NetworkMessage};
 use zenoh_result::ZResult;
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
 
-use super::RoutingContext;
+use super::{dispatcher::dead_letters::DeadLetters, RoutingContext};
 use crate::api::key_expr::KeyExpr;
 
 pub mod downsampling;
 use crate::net::routing::interceptor::downsampling::downsampling_interceptor_factories;
 
+pub(crate) mod regions;
+use crate::net::routing::interceptor::regions::regions_interceptor_factories;
+
+mod last_value_cache;
+use crate::net::routing::interceptor::last_value_cache::last_value_cache_interceptor_factories;
+
+mod static_topology;
+use crate::net::routing::interceptor::static_topology::static_topology_interceptor_factories;
+
+#[cfg(feature = "unstable")]
+mod capture;
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::capture::capture_interceptor_factories;
+
 pub(crate) trait InterceptorTrait {
     fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>>;
 
@@ -60,12 +75,28 @@ pub(crate) trait InterceptorFactoryTrait {
 
 pub(crate) type InterceptorFactory = Box<dyn InterceptorFactoryTrait + Send + Sync>;
 
-pub(crate) fn interceptor_factories(config: &Config) -> ZResult<Vec<InterceptorFactory>> {
+pub(crate) fn interceptor_factories(
+    config: &Config,
+    dead_letters: &Arc<DeadLetters>,
+) -> ZResult<Vec<InterceptorFactory>> {
     let mut res: Vec<InterceptorFactory> = vec![];
     // Uncomment to log the interceptors initialisation
     // res.push(Box::new(LoggerInterceptor {}));
+    // The capture comes first to record the messages before they are filtered
+    #[cfg(feature = "unstable")]
+    res.extend(capture_interceptor_factories(config.capture())?);
     res.extend(downsampling_interceptor_factories(config.downsampling())?);
-    res.extend(acl_interceptor_factories(config.access_control())?);
+    res.extend(acl_interceptor_factories(
+        config.access_control(),
+        dead_letters,
+    )?);
+    res.extend(regions_interceptor_factories(config.regions())?);
+    res.extend(static_topology_interceptor_factories(
+        config.routing().static_topology(),
+    )?);
+    // The last value cache comes last to only serve the declarations and queries admitted by the
+    // other interceptors
+    res.extend(last_value_cache_interceptor_factories(config)?);
     Ok(res)
 }
 
@@ -159,6 +190,44 @@ impl<T: InterceptorTrait> InterceptorTrait for ComputeOnMiss<T> {
     }
 }
 
+/// How a key expression relates to a set of key expressions, cached by the interceptors
+/// filtering or handling the messages on those key expressions.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct KeyExprCoverage {
+    /// The key expression is included in one of the key expressions.
+    pub(crate) included: bool,
+    /// The key expression intersects with one of the key expressions.
+    pub(crate) intersects: bool,
+}
+
+impl KeyExprCoverage {
+    pub(crate) fn new(key_exprs: &[OwnedKeyExpr], key_expr: &keyexpr) -> Self {
+        Self {
+            included: key_exprs.iter().any(|k| k.includes(key_expr)),
+            intersects: key_exprs.iter().any(|k| k.intersects(key_expr)),
+        }
+    }
+
+    /// Whether a message may pass a filter on the key expressions.
+    ///
+    /// Data and replies only pass when fully covered by the key expressions. Queries, interests
+    /// and declarations pass as soon as they may concern data on the key expressions.
+    pub(crate) fn passes(&self, body: &NetworkBody) -> bool {
+        match body {
+            NetworkBody::Push(_) | NetworkBody::Response(_) => self.included,
+            NetworkBody::Request(_) | NetworkBody::Interest(_) => self.intersects,
+            NetworkBody::Declare(Declare {
+                body:
+                    DeclareBody::DeclareSubscriber(_)
+                    | DeclareBody::DeclareQueryable(_)
+                    | DeclareBody::DeclareToken(_),
+                ..
+            }) => self.intersects,
+            _ => true,
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) struct IngressMsgLogger {}
 
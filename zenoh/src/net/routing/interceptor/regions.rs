@@ -0,0 +1,175 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::sync::Arc;
+
+use zenoh_config::{RegionNeighbourConf, RegionsConf};
+use zenoh_keyexpr::OwnedKeyExpr;
+use zenoh_protocol::core::ZenohIdProto;
+use zenoh_result::ZResult;
+
+use crate::net::routing::interceptor::*;
+
+pub(crate) fn regions_interceptor_factories(
+    config: &RegionsConf,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+    if let Some(name) = config.name() {
+        for n in config
+            .neighbours()
+            .iter()
+            .filter(|n| n.zids.is_none() && n.interfaces.is_none())
+        {
+            tracing::warn!(
+                "Neighbour region {} has neither zids nor interfaces: it will match no node",
+                n.region
+            );
+        }
+        if config.neighbours().iter().any(|n| &n.region == name) {
+            tracing::warn!(
+                "Region {} is listed among its own neighbours: those neighbours will be considered part of it",
+                name
+            );
+        }
+        res.push(Box::new(RegionsInterceptorFactory::new(config)));
+    }
+    Ok(res)
+}
+
+/// Returns the region of the remote node with the given zid reached through the given interfaces,
+/// or `None` if it belongs to the local region.
+///
+/// A neighbour entry matches the nodes satisfying all its criteria, an entry without any criteria
+/// matches no node.
+pub(crate) fn neighbour_region<'a>(
+    neighbours: &'a [RegionNeighbourConf],
+    local: &str,
+    zid: &ZenohIdProto,
+    interfaces: &[String],
+) -> Option<&'a str> {
+    neighbours
+        .iter()
+        .filter(|n| n.zids.is_some() || n.interfaces.is_some())
+        .find(|n| {
+            n.zids.as_ref().map_or(true, |zids| {
+                zids.iter().any(|z| ZenohIdProto::from(*z) == *zid)
+            }) && n
+                .interfaces
+                .as_ref()
+                .map_or(true, |itfs| interfaces.iter().any(|i| itfs.contains(i)))
+        })
+        .map(|n| n.region.as_str())
+        .filter(|region| *region != local)
+}
+
+pub(crate) struct RegionsInterceptorFactory {
+    name: String,
+    neighbours: Vec<RegionNeighbourConf>,
+    exports: Arc<Vec<OwnedKeyExpr>>,
+}
+
+impl RegionsInterceptorFactory {
+    pub(crate) fn new(conf: &RegionsConf) -> Self {
+        Self {
+            name: conf.name().clone().unwrap_or_default(),
+            neighbours: conf.neighbours().clone(),
+            exports: Arc::new(conf.exports().clone()),
+        }
+    }
+
+    fn interceptor(&self, region: &str) -> Interceptor {
+        Box::new(ComputeOnMiss::new(RegionsInterceptor {
+            region: region.to_string(),
+            exports: self.exports.clone(),
+        }))
+    }
+}
+
+impl InterceptorFactoryTrait for RegionsInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        let Ok(zid) = transport.get_zid() else {
+            return (None, None);
+        };
+        let interfaces = transport
+            .get_links()
+            .map(|links| links.into_iter().flat_map(|l| l.interfaces).collect())
+            .unwrap_or_else(|_| vec![]);
+        match neighbour_region(&self.neighbours, &self.name, &zid, &interfaces) {
+            Some(region) => {
+                tracing::debug!(
+                    "Transport {} crosses the boundary between regions {} and {}",
+                    zid,
+                    self.name,
+                    region
+                );
+                (
+                    Some(self.interceptor(region)),
+                    Some(self.interceptor(region)),
+                )
+            }
+            None => (None, None),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub(crate) struct RegionsInterceptor {
+    region: String,
+    exports: Arc<Vec<OwnedKeyExpr>>,
+}
+
+impl InterceptorTrait for RegionsInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(KeyExprCoverage::new(&self.exports, key_expr)))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        // Messages without key expression are not region specific
+        let Some(exported) = cache.and_then(|c| c.downcast_ref::<KeyExprCoverage>()) else {
+            return Some(ctx);
+        };
+        if !exported.passes(&ctx.msg.body) {
+            tracing::trace!(
+                "Message on {:?} not exported to region {}",
+                ctx.full_expr(),
+                self.region
+            );
+            return None;
+        }
+        Some(ctx)
+    }
+}
@@ -0,0 +1,123 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::sync::Arc;
+
+use zenoh_config::{StaticNeighbourConf, StaticTopologyConf};
+use zenoh_keyexpr::OwnedKeyExpr;
+use zenoh_protocol::core::ZenohIdProto;
+use zenoh_result::ZResult;
+
+use crate::net::routing::interceptor::*;
+
+pub(crate) fn static_topology_interceptor_factories(
+    config: &StaticTopologyConf,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+    if config.enabled().unwrap_or(false) {
+        res.push(Box::new(StaticTopologyInterceptorFactory {
+            neighbours: config.neighbours().clone(),
+        }));
+    }
+    Ok(res)
+}
+
+pub(crate) struct StaticTopologyInterceptorFactory {
+    neighbours: Vec<StaticNeighbourConf>,
+}
+
+impl StaticTopologyInterceptorFactory {
+    fn interceptor(
+        zid: ZenohIdProto,
+        key_exprs: &Option<Vec<OwnedKeyExpr>>,
+    ) -> Option<Interceptor> {
+        key_exprs.as_ref().map(|key_exprs| {
+            Box::new(ComputeOnMiss::new(StaticTopologyInterceptor {
+                zid,
+                key_exprs: Arc::new(key_exprs.clone()),
+            })) as Interceptor
+        })
+    }
+}
+
+impl InterceptorFactoryTrait for StaticTopologyInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        let Ok(zid) = transport.get_zid() else {
+            return (None, None);
+        };
+        match self
+            .neighbours
+            .iter()
+            .find(|n| ZenohIdProto::from(n.zid) == zid)
+        {
+            Some(neighbour) => (
+                Self::interceptor(zid, &neighbour.ingress),
+                Self::interceptor(zid, &neighbour.egress),
+            ),
+            // Such transports are refused by the runtime
+            None => (None, None),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub(crate) struct StaticTopologyInterceptor {
+    zid: ZenohIdProto,
+    key_exprs: Arc<Vec<OwnedKeyExpr>>,
+}
+
+impl InterceptorTrait for StaticTopologyInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(KeyExprCoverage::new(&self.key_exprs, key_expr)))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        // The key expressions allowed to flow to or from the neighbour
+        let Some(allowed) = cache.and_then(|c| c.downcast_ref::<KeyExprCoverage>()) else {
+            return Some(ctx);
+        };
+        if !allowed.passes(&ctx.msg.body) {
+            tracing::trace!(
+                "Message on {:?} not allowed by the static topology for neighbour {}",
+                ctx.full_expr(),
+                self.zid
+            );
+            return None;
+        }
+        Some(ctx)
+    }
+}
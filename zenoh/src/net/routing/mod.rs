@@ -175,3 +175,13 @@ impl RoutingContext<NetworkMessage> {
         OwnedKeyExpr::new(full_expr).ok()
     }
 }
+
+/// The FNV-1a hash of a key expression, recorded in the tracing spans of the messages so that the
+/// spans of a same publication can be correlated across the session, routing and transport layers
+/// and across processes.
+#[cfg(feature = "trace_spans")]
+pub(crate) fn keyexpr_hash(key_expr: &str) -> u64 {
+    key_expr.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
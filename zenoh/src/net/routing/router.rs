@@ -19,14 +19,18 @@ use std::{
 use uhlc::HLC;
 use zenoh_config::Config;
 use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
+#[cfg(feature = "unstable")]
+use zenoh_protocol::transport::init::ext::PatchType;
 // use zenoh_collections::Timer;
 use zenoh_result::ZResult;
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
+use zenoh_util::flight_recorder::FlightRecorder;
 
 pub(crate) use super::dispatcher::token::*;
 pub use super::dispatcher::{pubsub::*, queries::*, resource::*};
 use super::{
     dispatcher::{
+        dead_letters::DeadLetters,
         face::{Face, FaceState},
         tables::{Tables, TablesLock},
     },
@@ -51,12 +55,23 @@ impl Router {
         hlc: Option<Arc<HLC>>,
         config: &Config,
     ) -> ZResult<Self> {
+        let dead_letters = Arc::new(DeadLetters::new(zid, whatami, config));
+        let flight_recorder = Arc::new(FlightRecorder::new());
         Ok(Router {
             // whatami,
             tables: Arc::new(TablesLock {
-                tables: RwLock::new(Tables::new(zid, whatami, hlc, config)?),
+                tables: RwLock::new(Tables::new(
+                    zid,
+                    whatami,
+                    hlc,
+                    config,
+                    &dead_letters,
+                    &flight_recorder,
+                )?),
                 ctrl_lock: Mutex::new(hat::new_hat(whatami, config)),
                 queries_lock: RwLock::new(()),
+                dead_letters,
+                flight_recorder,
             }),
         })
     }
@@ -90,6 +105,8 @@ impl Router {
                     None,
                     primitives.clone(),
                     None,
+                    #[cfg(feature = "unstable")]
+                    PatchType::CURRENT,
                     None,
                     ctrl_lock.new_face(),
                 )
@@ -125,6 +142,8 @@ impl Router {
         let zid = transport.get_zid()?;
         #[cfg(feature = "stats")]
         let stats = transport.get_stats()?;
+        #[cfg(feature = "unstable")]
+        let patch = transport.get_patch()?;
         let (ingress, egress): (Vec<_>, Vec<_>) = tables
             .interceptors
             .iter()
@@ -149,6 +168,8 @@ impl Router {
                     Some(stats),
                     mux.clone(),
                     None,
+                    #[cfg(feature = "unstable")]
+                    patch,
                     Some(ingress.clone()),
                     ctrl_lock.new_face(),
                 )
@@ -201,6 +222,8 @@ impl Router {
             None,
             mux.clone(),
             Some(transport),
+            #[cfg(feature = "unstable")]
+            PatchType::NONE,
             None,
             ctrl_lock.new_face(),
         );
@@ -240,6 +263,8 @@ impl Router {
             Some(transport.get_stats().unwrap()),
             Arc::new(DummyPrimitives),
             Some(transport),
+            #[cfg(feature = "unstable")]
+            PatchType::NONE,
             Some(interceptor.clone()),
             ctrl_lock.new_face(),
         );
@@ -14,6 +14,7 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use serde_json::json;
@@ -26,7 +27,10 @@ use zenoh_plugin_trait::{PluginControl, PluginDiff, PluginStatus};
 #[cfg(feature = "plugins")]
 use zenoh_protocol::core::key_expr::keyexpr;
 use zenoh_protocol::{
-    core::{key_expr::OwnedKeyExpr, ExprId, Reliability, WireExpr, EMPTY_EXPR_ID},
+    core::{
+        key_expr::OwnedKeyExpr, CongestionControl, ExprId, Reliability, WireExpr, ZenohIdProto,
+        EMPTY_EXPR_ID,
+    },
     network::{
         declare::{queryable::ext::QueryableInfoType, QueryableId},
         ext, Declare, DeclareBody, DeclareQueryable, DeclareSubscriber, Interest, Push, Request,
@@ -37,22 +41,39 @@ use zenoh_protocol::{
 use zenoh_result::ZResult;
 use zenoh_transport::unicast::TransportUnicast;
 
-use super::{routing::dispatcher::face::Face, Runtime};
+use super::{drain::DrainConf, routing::dispatcher::face::Face, Runtime};
 #[cfg(feature = "plugins")]
 use crate::api::plugins::PluginsManager;
+#[cfg(feature = "unstable")]
+use crate::api::session::Session;
 use crate::{
     api::{
         bytes::ZBytes,
         key_expr::KeyExpr,
+        publisher::Priority,
         queryable::{Query, QueryInner},
     },
     bytes::Encoding,
-    net::primitives::Primitives,
+    net::{primitives::Primitives, routing::interceptor::regions::neighbour_region},
 };
 
+/// The selector parameter asking the echo service to reply with the given priority.
+pub(crate) const PRIORITY_PARAM: &str = "priority";
+/// The selector parameter asking the echo service to reply with an empty payload.
+pub(crate) const DISCARD_PARAM: &str = "discard";
+/// The maximum number of echo requests of each measurement of the probes run by the admin space.
+#[cfg(feature = "unstable")]
+const PROBE_MAX_COUNT: usize = 100;
+/// The maximum size of the echo requests of the probes run by the admin space.
+#[cfg(feature = "unstable")]
+const PROBE_MAX_PAYLOAD_SIZE: usize = 64 * 1024;
+
 pub struct AdminContext {
     runtime: Runtime,
     version: String,
+    /// The session running the probes, created by the first one.
+    #[cfg(feature = "unstable")]
+    probe_session: Arc<std::sync::OnceLock<Session>>,
 }
 
 type Handler = Arc<dyn Fn(&AdminContext, Query) + Send + Sync>;
@@ -160,6 +181,44 @@ impl AdminSpace {
                 .unwrap(),
             Arc::new(metrics),
         );
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/echo")
+                .try_into()
+                .unwrap(),
+            Arc::new(echo),
+        );
+        #[cfg(feature = "unstable")]
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/probe/*")
+                .try_into()
+                .unwrap(),
+            Arc::new(probe_data),
+        );
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/flight_recorder")
+                .try_into()
+                .unwrap(),
+            Arc::new(flight_recorder_data),
+        );
+        #[cfg(feature = "unstable")]
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/clock")
+                .try_into()
+                .unwrap(),
+            Arc::new(clock_data),
+        );
+        #[cfg(feature = "unstable")]
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/acl").try_into().unwrap(),
+            Arc::new(acl_data),
+        );
+        #[cfg(feature = "fault_injection")]
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/faults")
+                .try_into()
+                .unwrap(),
+            Arc::new(faults_data),
+        );
         if runtime.state.whatami == WhatAmI::Router {
             handlers.insert(
                 format!("@/{zid_str}/{whatami_str}/linkstate/routers")
@@ -202,6 +261,12 @@ impl AdminSpace {
                 .unwrap(),
             Arc::new(queriers_data),
         );
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/entities/**")
+                .try_into()
+                .unwrap(),
+            Arc::new(entities_data),
+        );
 
         #[cfg(feature = "plugins")]
         handlers.insert(
@@ -229,6 +294,8 @@ impl AdminSpace {
         let context = Arc::new(AdminContext {
             runtime: runtime.clone(),
             version,
+            #[cfg(feature = "unstable")]
+            probe_session: Arc::new(std::sync::OnceLock::new()),
         });
         let admin = Arc::new(AdminSpace {
             zid: runtime.zid(),
@@ -283,26 +350,37 @@ impl AdminSpace {
                             match diff {
                                 PluginDiff::Delete(id) => {
                                     active_plugins.remove(id.as_str());
-                                    if let Some(running) = plugins_mgr.started_plugin_mut(&id) {
-                                        running.stop()
+                                    // Unloading the plugin allows to load an upgraded library
+                                    // when it is added again
+                                    if let Some(plugin) = plugins_mgr.plugin_mut(&id) {
+                                        plugin.unload()
                                     }
                                 }
                                 PluginDiff::Start(plugin) => {
-                                    if let Err(e) = Self::start_plugin(
+                                    // A plugin failing to load at runtime, e.g. because of an
+                                    // incompatible library, must not bring the router down,
+                                    // even if it is required
+                                    match Self::start_plugin(
                                         &mut plugins_mgr,
                                         &plugin,
                                         &admin.context.runtime,
                                         plugin.required,
                                     ) {
-                                        if plugin.required {
-                                            panic!("Failed to load plugin `{}`: {}", plugin.id, e)
-                                        } else {
-                                            tracing::error!(
-                                                "Failed to load plugin `{}`: {}",
-                                                plugin.id,
-                                                e
-                                            )
+                                        Ok(()) => {
+                                            if let Some(started) =
+                                                plugins_mgr.started_plugin(&plugin.id)
+                                            {
+                                                active_plugins.insert(
+                                                    plugin.id.clone(),
+                                                    started.path().to_string(),
+                                                );
+                                            }
                                         }
+                                        Err(e) => tracing::error!(
+                                            "Failed to load plugin `{}`: {}",
+                                            plugin.id,
+                                            e
+                                        ),
                                     }
                                 }
                             }
@@ -339,6 +417,61 @@ impl AdminSpace {
                 wire_expr: [&root_key, "/config/**"].concat().into(),
             }),
         });
+
+        #[cfg(all(feature = "plugins", feature = "runtime_plugins"))]
+        primitives.send_declare(Declare {
+            interest_id: None,
+            ext_qos: ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType::DEFAULT,
+            body: DeclareBody::DeclareSubscriber(DeclareSubscriber {
+                id: runtime.next_id(),
+                wire_expr: [&root_key, "/plugins/*/reload"].concat().into(),
+            }),
+        });
+
+        #[cfg(feature = "fault_injection")]
+        primitives.send_declare(Declare {
+            interest_id: None,
+            ext_qos: ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType::DEFAULT,
+            body: DeclareBody::DeclareSubscriber(DeclareSubscriber {
+                id: runtime.next_id(),
+                wire_expr: [&root_key, "/faults"].concat().into(),
+            }),
+        });
+
+        if runtime.state.whatami == WhatAmI::Router {
+            primitives.send_declare(Declare {
+                interest_id: None,
+                ext_qos: ext::QoSType::DECLARE,
+                ext_tstamp: None,
+                ext_nodeid: ext::NodeIdType::DEFAULT,
+                body: DeclareBody::DeclareSubscriber(DeclareSubscriber {
+                    id: runtime.next_id(),
+                    wire_expr: [&root_key, "/drain"].concat().into(),
+                }),
+            });
+        }
+    }
+
+    /// Reloads the plugin `id` from its library, upgrading it if the library has been replaced.
+    #[cfg(all(feature = "plugins", feature = "runtime_plugins"))]
+    fn reload_plugin(&self, id: &str) {
+        let runtime = self.context.runtime.clone();
+        let id = id.to_string();
+        zenoh_runtime::ZRuntime::Net.spawn(async move {
+            let mut plugins_mgr = runtime.plugins_manager();
+            match plugins_mgr.reload_plugin(&id, &runtime) {
+                Ok(started) => tracing::info!(
+                    "Successfully reloaded plugin `{}` from {}",
+                    started.id(),
+                    started.path()
+                ),
+                Err(e) => tracing::error!("Failed to reload plugin `{}`: {}", id, e),
+            }
+        });
     }
 
     pub fn key_expr_to_string<'a>(&self, key_expr: &'a WireExpr) -> ZResult<KeyExpr<'a>> {
@@ -388,6 +521,42 @@ impl Primitives for AdminSpace {
             }
         }
 
+        #[cfg(all(feature = "plugins", feature = "runtime_plugins"))]
+        if let Some(id) = msg
+            .wire_expr
+            .as_str()
+            .strip_prefix(&format!(
+                "@/{}/{}/plugins/",
+                self.context.runtime.state.zid, self.context.runtime.state.whatami,
+            ))
+            .and_then(|command| command.strip_suffix("/reload"))
+        {
+            if let PushBody::Put(_) = msg.payload {
+                self.reload_plugin(id);
+            }
+            return;
+        }
+
+        #[cfg(feature = "fault_injection")]
+        if self.key_expr_to_string(&msg.wire_expr).is_ok_and(|k| {
+            k.as_str()
+                == format!(
+                    "@/{}/{}/faults",
+                    self.context.runtime.state.zid, self.context.runtime.state.whatami,
+                )
+        }) {
+            let faults = self.context.runtime.manager().faults();
+            match msg.payload {
+                PushBody::Put(put) => {
+                    if let Err(e) = set_faults(faults, &put.payload.contiguous()) {
+                        error!("Invalid faults on {} : {}", msg.wire_expr, e);
+                    }
+                }
+                PushBody::Del(_) => faults.clear(),
+            }
+            return;
+        }
+
         if let Some(key) = msg.wire_expr.as_str().strip_prefix(&format!(
             "@/{}/{}/config/",
             self.context.runtime.state.zid, self.context.runtime.state.whatami,
@@ -430,6 +599,25 @@ impl Primitives for AdminSpace {
                     }
                 }
             }
+        } else if self.context.runtime.state.whatami == WhatAmI::Router
+            && msg.wire_expr.as_str()
+                == format!(
+                    "@/{}/{}/drain",
+                    self.context.runtime.state.zid, self.context.runtime.state.whatami,
+                )
+        {
+            if let PushBody::Put(put) = msg.payload {
+                let payload = put.payload.contiguous();
+                let conf = match std::str::from_utf8(&payload) {
+                    Ok(json) if json.trim().is_empty() => Ok(DrainConf::default()),
+                    Ok(json) => json5::from_str::<DrainConf>(json).map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                match conf {
+                    Ok(conf) => self.context.runtime.drain(conf),
+                    Err(e) => error!("Invalid drain command on {} : {}", msg.wire_expr, e),
+                }
+            }
         }
     }
 
@@ -475,6 +663,9 @@ impl Primitives for AdminSpace {
                         qid: msg.id,
                         zid: zid.into(),
                         primitives,
+                        qos: msg.ext_qos.into(),
+                        #[cfg(feature = "unstable")]
+                        trace_context: query.ext_trace.and_then(|t| t.try_into().ok()),
                     }),
                     eid: self.queryable_id,
                     value: query
@@ -570,6 +761,9 @@ fn local_data(context: &AdminContext, query: Query) {
         .map(|locator| json!(locator.as_str()))
         .collect();
 
+    // regions info
+    let regions = context.runtime.config().lock().0.regions().clone();
+
     // transports info
     let transport_to_json = |transport: &TransportUnicast| {
         #[allow(unused_mut)]
@@ -581,6 +775,17 @@ fn local_data(context: &AdminContext, query: Query) {
                 |links| links.iter().map(|link| link.dst.to_string()).collect()
             ),
         });
+        if let (Some(local), Ok(zid)) = (regions.name(), transport.get_zid()) {
+            let interfaces: Vec<String> = transport
+                .get_links()
+                .map(|links| links.into_iter().flat_map(|l| l.interfaces).collect())
+                .unwrap_or_default();
+            let region =
+                neighbour_region(regions.neighbours(), local, &zid, &interfaces).unwrap_or(local);
+            json.as_object_mut()
+                .unwrap()
+                .insert("region".to_string(), json!(region));
+        }
         #[cfg(feature = "stats")]
         {
             let stats = query
@@ -594,6 +799,15 @@ fn local_data(context: &AdminContext, query: Query) {
                         .get_stats()
                         .map_or_else(|_| json!({}), |p| json!(p.report())),
                 );
+                let queues: serde_json::Map<String, serde_json::Value> = transport
+                    .get_queues()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(link, queues)| (link.dst.to_string(), json!(queues)))
+                    .collect();
+                json.as_object_mut()
+                    .unwrap()
+                    .insert("queues".to_string(), json!(queues));
             }
         }
         json
@@ -612,6 +826,7 @@ fn local_data(context: &AdminContext, query: Query) {
         "locators": locators,
         "sessions": transports,
         "plugins": plugins,
+        "regions": regions,
     });
 
     #[cfg(feature = "stats")]
@@ -680,6 +895,292 @@ zenoh_build{{version="{}"}} 1
     }
 }
 
+/// Replies to the echo requests of the probes of remote nodes with the payload of the request,
+/// or an empty payload when it is discarded, timestamped with the HLC of the runtime.
+fn echo(context: &AdminContext, query: Query) {
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/echo",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+    let parameters = query.parameters();
+    let priority = parameters
+        .get(PRIORITY_PARAM)
+        .and_then(|p| p.parse::<u8>().ok())
+        .and_then(|p| Priority::try_from(p).ok())
+        .unwrap_or_default();
+    let payload = match parameters.contains_key(DISCARD_PARAM) {
+        true => ZBytes::default(),
+        false => query.payload().cloned().unwrap_or_default(),
+    };
+    let timestamp = context.runtime.new_timestamp().unwrap_or_else(|| {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().into();
+        uhlc::Timestamp::new(now, context.runtime.zid().into())
+    });
+    if let Err(e) = query
+        .reply(reply_key, payload)
+        .priority(priority)
+        .congestion_control(CongestionControl::Block)
+        .timestamp(timestamp)
+        .wait()
+    {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
+/// Probes the node whose zid ends the key of the query, replying with the report of the probe.
+///
+/// As the probe loads the network, it requires `adminspace.permissions.write`.
+#[cfg(feature = "unstable")]
+fn probe_data(context: &AdminContext, query: Query) {
+    if !context
+        .runtime
+        .state
+        .config
+        .lock()
+        .0
+        .adminspace
+        .permissions()
+        .write
+    {
+        if let Err(e) = query
+            .reply_err("Probes require adminspace.permissions.write=true in configuration")
+            .wait()
+        {
+            tracing::error!("Error sending AdminSpace reply: {:?}", e);
+        }
+        return;
+    }
+    let Some(zid) = query
+        .key_expr()
+        .as_str()
+        .rsplit('/')
+        .next()
+        .and_then(|zid| zid.parse::<ZenohId>().ok())
+    else {
+        return;
+    };
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/probe/{}",
+        context.runtime.state.zid, context.runtime.state.whatami, zid
+    )
+    .try_into()
+    .unwrap();
+    let parameters = query.parameters();
+    let count = parameters
+        .get("count")
+        .and_then(|count| count.parse::<usize>().ok())
+        .map(|count| count.min(PROBE_MAX_COUNT));
+    let payload_size = parameters
+        .get("payload_size")
+        .and_then(|size| size.parse::<usize>().ok())
+        .map(|size| size.min(PROBE_MAX_PAYLOAD_SIZE));
+    let runtime = context.runtime.clone();
+    let probe_session = context.probe_session.clone();
+    // The probe lasts several round-trips: it can't be run in the routing path of the query
+    zenoh_runtime::ZRuntime::Net.spawn(async move {
+        let session =
+            probe_session.get_or_init(|| Session::init(runtime, vec![], vec![], false).wait());
+        let mut probe = session.probe(zid);
+        if let Some(count) = count {
+            probe = probe.count(count);
+        }
+        if let Some(payload_size) = payload_size {
+            probe = probe.payload_size(payload_size);
+        }
+        let result = match probe.await {
+            Ok(report) => {
+                let report = report.iter().map(|p| p.to_json()).collect::<Vec<_>>();
+                query
+                    .reply(reply_key, serde_json::to_vec(&report).unwrap())
+                    .encoding(Encoding::APPLICATION_JSON)
+                    .await
+            }
+            Err(e) => query.reply_err(e.to_string()).await,
+        };
+        if let Err(e) = result {
+            tracing::error!("Error sending AdminSpace reply: {:?}", e);
+        }
+    });
+}
+
+fn flight_recorder_data(context: &AdminContext, query: Query) {
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/flight_recorder",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+    let payload = match serde_json::to_vec(&context.runtime.flight_recorder().events()) {
+        Ok(bytes) => ZBytes::from(bytes),
+        Err(e) => {
+            tracing::error!("Error serializing AdminSpace reply: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = query
+        .reply(reply_key, payload)
+        .encoding(Encoding::APPLICATION_JSON)
+        .wait()
+    {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
+#[cfg(feature = "unstable")]
+fn clock_data(context: &AdminContext, query: Query) {
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/clock",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+    let now = context.runtime.new_timestamp().unwrap_or_else(|| {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().into();
+        uhlc::Timestamp::new(now, context.runtime.zid().into())
+    });
+    let clock = crate::api::clock::ClockInfo::new(&context.runtime, now);
+    let payload = match serde_json::to_vec(&clock.to_json()) {
+        Ok(bytes) => ZBytes::from(bytes),
+        Err(e) => {
+            tracing::error!("Error serializing AdminSpace reply: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = query
+        .reply(reply_key, payload)
+        .encoding(Encoding::APPLICATION_JSON)
+        .wait()
+    {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
+/// Checks the message of the `message` parameter (e.g. `put`) on the key expression of the
+/// `key_expr` parameter against the access control configuration, for the subject of the
+/// `interface`, `cert_common_name` and `username` parameters, in the direction of the `flow`
+/// parameter (`ingress` by default).
+#[cfg(feature = "unstable")]
+fn acl_decision(context: &AdminContext, query: &Query) -> ZResult<crate::api::acl::AclDecision> {
+    use zenoh_config::{CertCommonName, InterceptorFlow, Interface, Username};
+    use zenoh_protocol::core::key_expr::keyexpr;
+    use zenoh_result::bail;
+
+    use crate::net::routing::interceptor::authorization::SubjectQuery;
+
+    let parameters = query.parameters();
+    let message = match parameters.get("message") {
+        Some(message) => serde_json::from_value(json!(message))?,
+        None => bail!("Missing `message` parameter"),
+    };
+    let flow = match parameters.get("flow") {
+        Some(flow) => serde_json::from_value(json!(flow))?,
+        None => InterceptorFlow::Ingress,
+    };
+    let Some(key_expr) = parameters.get("key_expr") else {
+        bail!("Missing `key_expr` parameter");
+    };
+    let key_expr = keyexpr::new(key_expr)?;
+    let subject = SubjectQuery {
+        interface: parameters.get("interface").map(|i| Interface(i.into())),
+        cert_common_name: parameters
+            .get("cert_common_name")
+            .map(|ccn| CertCommonName(ccn.into())),
+        username: parameters.get("username").map(|u| Username(u.into())),
+    };
+    let acl_config = context.runtime.config().lock().0.access_control().clone();
+    crate::api::acl::check(&acl_config, &subject, flow, message, key_expr)
+}
+
+/// As the subject of the check is chosen by the querier, it requires
+/// `adminspace.permissions.write`.
+#[cfg(feature = "unstable")]
+fn acl_data(context: &AdminContext, query: Query) {
+    if !context
+        .runtime
+        .state
+        .config
+        .lock()
+        .0
+        .adminspace
+        .permissions()
+        .write
+    {
+        if let Err(e) = query
+            .reply_err("ACL checks require adminspace.permissions.write=true in configuration")
+            .wait()
+        {
+            tracing::error!("Error sending AdminSpace reply: {:?}", e);
+        }
+        return;
+    }
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/acl",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+    let result = match acl_decision(context, &query) {
+        Ok(decision) => query
+            .reply(reply_key, serde_json::to_vec(&decision.to_json()).unwrap())
+            .encoding(Encoding::APPLICATION_JSON)
+            .wait(),
+        Err(e) => query.reply_err(e.to_string()).wait(),
+    };
+    if let Err(e) = result {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
+/// Parses a `{ link: <locator>, delay_ms, drop, reorder, corrupt }` object, the faults being
+/// injected on all the links if `link` is absent.
+#[cfg(feature = "fault_injection")]
+fn set_faults(injector: &zenoh_transport::faults::FaultInjector, payload: &[u8]) -> ZResult<()> {
+    let mut value: serde_json::Value = json5::from_str(std::str::from_utf8(payload)?)?;
+    let link = match value.as_object_mut().and_then(|o| o.remove("link")) {
+        Some(link) => Some(serde_json::from_value(link)?),
+        None => None,
+    };
+    injector.set(link, serde_json::from_value(value)?)
+}
+
+#[cfg(feature = "fault_injection")]
+fn faults_data(context: &AdminContext, query: Query) {
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/faults",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+    let faults: Vec<serde_json::Value> = context
+        .runtime
+        .manager()
+        .faults()
+        .get()
+        .into_iter()
+        .map(|(link, faults)| {
+            let mut value = json!(faults);
+            value["link"] = json!(link.map(|l| l.to_string()));
+            value
+        })
+        .collect();
+    let payload = match serde_json::to_vec(&faults) {
+        Ok(bytes) => ZBytes::from(bytes),
+        Err(e) => {
+            tracing::error!("Error serializing AdminSpace reply: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = query
+        .reply(reply_key, payload)
+        .encoding(Encoding::APPLICATION_JSON)
+        .wait()
+    {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
 fn routers_linkstate_data(context: &AdminContext, query: Query) {
     let reply_key: OwnedKeyExpr = format!(
         "@/{}/{}/linkstate/routers",
@@ -814,6 +1315,74 @@ fn queriers_data(context: &AdminContext, query: Query) {
     }
 }
 
+fn entities_data(context: &AdminContext, query: Query) {
+    let tables = zread!(context.runtime.state.router.tables.tables);
+    let entities = [
+        ("subscriber", tables.hat_code.get_subscriptions(&tables)),
+        ("publisher", tables.hat_code.get_publications(&tables)),
+        ("queryable", tables.hat_code.get_queryables(&tables)),
+        ("querier", tables.hat_code.get_queriers(&tables)),
+    ];
+    // The QoS and the declaration time are only known for the entities declared on a face of
+    // this node, i.e. by its neighbours and its own session
+    let entity_info = |kind: &str, owner: &ZenohIdProto, expr: &str| {
+        tables
+            .faces
+            .values()
+            .filter(|face| face.zid == *owner)
+            .flat_map(|face| face.entities.iter())
+            .find(|((k, _), info)| *k == kind && info.expr == expr)
+            .map(|(_, info)| info)
+    };
+    let mut replies = vec![];
+    for (kind, declarations) in entities {
+        for (res, sources) in declarations {
+            for (whatami, owner) in sources.iter() {
+                let key = KeyExpr::try_from(format!(
+                    "@/{}/{}/entities/{}/{}/{}",
+                    context.runtime.state.zid,
+                    context.runtime.state.whatami,
+                    kind,
+                    owner,
+                    res.expr()
+                ))
+                .unwrap();
+                if query.key_expr().intersects(&key) {
+                    let mut value = json!({
+                        "kind": kind,
+                        "key_expr": res.expr(),
+                        "owner": owner.to_string(),
+                        "owner_whatami": whatami.to_str(),
+                    });
+                    if let Some(info) = entity_info(kind, owner, res.expr()) {
+                        value["qos"] = json!({
+                            "priority": format!("{:?}", info.priority),
+                            "congestion_control": format!("{:?}", info.congestion_control),
+                            "express": info.express,
+                        });
+                        value["declared_at"] = info
+                            .declared_at
+                            .duration_since(UNIX_EPOCH)
+                            .map_or(0, |t| t.as_millis() as u64)
+                            .into();
+                    }
+                    replies.push((key, value));
+                }
+            }
+        }
+    }
+    drop(tables);
+    for (key, value) in replies {
+        if let Err(e) = query
+            .reply(key, ZBytes::from(value.to_string()))
+            .encoding(Encoding::APPLICATION_JSON)
+            .wait()
+        {
+            tracing::error!("Error sending AdminSpace reply: {:?}", e);
+        }
+    }
+}
+
 #[cfg(feature = "plugins")]
 fn plugins_data(context: &AdminContext, query: Query) {
     let guard = context.runtime.plugins_manager();
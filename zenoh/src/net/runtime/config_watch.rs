@@ -0,0 +1,266 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Watch of a configuration file.
+//!
+//! The file is periodically checked for changes. Changed fields are re-applied live where
+//! supported and the other changes are reported as requiring a restart. Changes are computed
+//! between successive versions of the file, so fields overridden by the environment or the
+//! command line are left untouched as long as they do not change in the file. The files included
+//! by the configuration file are watched along with it.
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use zenoh_config::{unwrap_or_default, Config};
+use zenoh_result::{zerror, ZResult};
+
+use super::Runtime;
+use crate::net::routing::interceptor::interceptor_factories;
+
+/// The fields applied to the runtime as soon as they change.
+const LIVE_KEYS: [&str; 3] = ["connect/endpoints", "listen/endpoints", "logging/filter"];
+/// The fields configuring interceptors, applied to the sessions established after they change.
+const INTERCEPTOR_KEYS: [&str; 2] = ["downsampling", "access_control"];
+/// The plugins configurations, applied by the admin space as soon as they change.
+const PLUGINS_KEY: &str = "plugins";
+
+/// Returns the fields which differ between `old` and `new`, without their nested fields.
+fn changed_keys(old: &Config, new: &Config) -> Vec<String> {
+    let keys: Vec<String> = new.keys().collect();
+    keys.iter()
+        .filter(|key| old.get_json(key).ok() != new.get_json(key).ok())
+        .filter(|key| {
+            let prefix = format!("{key}/");
+            !keys.iter().any(|k| k.starts_with(&prefix))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Reads the contents of the `files` of a configuration, to detect their changes.
+async fn read_sources(files: &[PathBuf]) -> ZResult<Vec<String>> {
+    let mut contents = Vec::with_capacity(files.len());
+    for file in files {
+        contents.push(
+            tokio::fs::read_to_string(file)
+                .await
+                .map_err(|e| zerror!("{}: {}", file.display(), e))?,
+        );
+    }
+    Ok(contents)
+}
+
+/// Loads the configuration file, keeping the id of the `previous` version if the file does not
+/// set one, along with the files it was read from.
+async fn load(file: &str, previous: Option<&Config>) -> ZResult<(Config, Vec<PathBuf>)> {
+    let file = file.to_owned();
+    // Unless set in the file, the id is randomly generated on each load
+    let id = previous.and_then(|previous| *previous.id());
+    tokio::task::spawn_blocking(move || Config::from_file_with_sources(file, id))
+        .await
+        .map_err(|e| zerror!("{e}"))?
+}
+
+fn is_within(key: &str, section: &str) -> bool {
+    key == section
+        || key
+            .strip_prefix(section)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+impl Runtime {
+    /// Starts watching the configuration file set in `config_watch/file`, if any.
+    pub(crate) fn start_config_watch(&self) {
+        let (file, period) = {
+            let config = &self.state.config.lock().0;
+            (
+                config.config_watch().file().clone(),
+                unwrap_or_default!(config.config_watch().period()),
+            )
+        };
+        let Some(file) = file else {
+            return;
+        };
+        let runtime = self.clone();
+        self.spawn_abortable(async move {
+            let (mut current, mut files) = match load(&file, None).await {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    tracing::error!("Unable to watch config file {}: {}", file, e);
+                    return;
+                }
+            };
+            tracing::info!("Watching config file {}", file);
+            let mut contents = read_sources(&files).await.unwrap_or_default();
+            loop {
+                tokio::time::sleep(Duration::from_millis(period)).await;
+                match read_sources(&files).await {
+                    Ok(c) if c == contents => continue,
+                    Ok(c) => contents = c,
+                    Err(e) => {
+                        tracing::warn!("Unable to read config file {}: {}", file, e);
+                        continue;
+                    }
+                }
+                match load(&file, Some(&current)).await {
+                    Ok((new, new_files)) => {
+                        runtime.apply_config_changes(&file, &current, &new);
+                        current = new;
+                        // The included files may have changed
+                        if new_files != files {
+                            files = new_files;
+                            contents = read_sources(&files).await.unwrap_or_default();
+                        }
+                    }
+                    Err(e) => tracing::warn!("Ignoring invalid config file {}: {}", file, e),
+                }
+            }
+        });
+    }
+
+    fn apply_config_changes(&self, file: &str, old: &Config, new: &Config) {
+        let changed = changed_keys(old, new);
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut applied = vec![];
+        for key in LIVE_KEYS {
+            if changed.iter().any(|k| is_within(k, key)) {
+                match self.apply_config_key(key, new) {
+                    Ok(()) => applied.push(key.to_string()),
+                    Err(e) => tracing::warn!("Unable to apply {} from {}: {}", key, file, e),
+                }
+            }
+        }
+        if changed.iter().any(|k| is_within(k, PLUGINS_KEY)) {
+            match self.apply_plugins_config(old, new) {
+                Ok(()) => applied.push(PLUGINS_KEY.to_string()),
+                Err(e) => tracing::warn!("Unable to apply {} from {}: {}", PLUGINS_KEY, file, e),
+            }
+        }
+        let interceptors: Vec<&str> = INTERCEPTOR_KEYS
+            .into_iter()
+            .filter(|key| changed.iter().any(|k| is_within(k, key)))
+            .collect();
+        if !interceptors.is_empty() {
+            match interceptors
+                .iter()
+                .try_for_each(|key| self.apply_config_key(key, new))
+                .and_then(|()| self.update_interceptors())
+            {
+                Ok(()) => tracing::info!(
+                    "Config file {}: changes of {} apply to new sessions",
+                    file,
+                    interceptors.join(", ")
+                ),
+                Err(e) => tracing::warn!(
+                    "Unable to apply {} from {}: {}",
+                    interceptors.join(", "),
+                    file,
+                    e
+                ),
+            }
+        }
+        if !applied.is_empty() {
+            tracing::info!("Config file {}: applied {}", file, applied.join(", "));
+        }
+
+        let restart: Vec<&str> = changed
+            .iter()
+            .map(String::as_str)
+            .filter(|k| {
+                !LIVE_KEYS
+                    .iter()
+                    .chain(INTERCEPTOR_KEYS.iter())
+                    .chain([PLUGINS_KEY].iter())
+                    .any(|key| is_within(k, key))
+            })
+            .collect();
+        if !restart.is_empty() {
+            tracing::warn!(
+                "Config file {}: changes of {} require a restart",
+                file,
+                restart.join(", ")
+            );
+        }
+    }
+
+    /// Applies the log filter of the configuration, if set.
+    pub(crate) fn update_log_filter(&self) -> ZResult<()> {
+        let filter = self.state.config.lock().0.logging().filter().clone();
+        match filter {
+            Some(filter) => zenoh_util::set_log_filter(&filter),
+            None => Ok(()),
+        }
+    }
+
+    fn apply_config_key(&self, key: &str, new: &Config) -> ZResult<()> {
+        let value = new.get_json(key).map_err(|e| zerror!("{e}"))?;
+        self.state.config.insert_json5(key, &value)
+    }
+
+    fn apply_plugins_config(&self, old: &Config, new: &Config) -> ZResult<()> {
+        fn plugins(config: &Config) -> ZResult<serde_json::Map<String, serde_json::Value>> {
+            let json = config.get_json(PLUGINS_KEY).map_err(|e| zerror!("{e}"))?;
+            match serde_json::from_str(&json)? {
+                serde_json::Value::Object(plugins) => Ok(plugins),
+                _ => Ok(serde_json::Map::new()),
+            }
+        }
+        let (old, new) = (plugins(old)?, plugins(new)?);
+        let names: HashSet<&String> = old.keys().chain(new.keys()).collect();
+        for name in names {
+            match (old.get(name), new.get(name)) {
+                (old, Some(value)) if old != Some(value) => self
+                    .state
+                    .config
+                    .insert_json5(&format!("plugins/{name}"), &value.to_string())?,
+                (Some(_), None) => self.state.config.remove(format!("plugins/{name}"))?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn update_interceptors(&self) -> ZResult<()> {
+        let factories = interceptor_factories(
+            &self.state.config.lock().0,
+            &self.state.router.tables.dead_letters,
+        )?;
+        zwrite!(self.state.router.tables.tables).interceptors = factories;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_changed_keys() {
+        let old = Config::default();
+        let mut new = Config::default();
+        new.set_id(*old.id()).unwrap();
+        new.insert_json5("listen/endpoints", r#"["tcp/127.0.0.1:7447"]"#)
+            .unwrap();
+        new.insert_json5("scouting/delay", "42").unwrap();
+        let mut changed = changed_keys(&old, &new);
+        changed.sort();
+        assert_eq!(changed, ["listen/endpoints", "scouting/delay"]);
+        assert!(is_within("plugins", PLUGINS_KEY));
+        assert!(is_within("access_control/rules", "access_control"));
+        assert!(!is_within("listen/endpoints_extra", "listen/endpoints"));
+        assert!(!is_within("downsampling_extra", "downsampling"));
+    }
+}
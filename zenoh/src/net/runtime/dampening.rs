@@ -0,0 +1,158 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Dampening of flapping routers and peers.
+//!
+//! Each flap of a router or peer increases its penalty, which decays exponentially over time.
+//! A router or peer whose penalty exceeds the suppress threshold is suppressed until its penalty
+//! decays below the reuse threshold.
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use zenoh_config::{unwrap_or_default, Config};
+use zenoh_protocol::core::ZenohIdProto;
+
+struct Penalty {
+    value: f64,
+    updated: Instant,
+    suppressed: bool,
+}
+
+pub(crate) struct Dampening {
+    penalty: f64,
+    suppress_threshold: f64,
+    reuse_threshold: f64,
+    max_penalty: f64,
+    half_life: Duration,
+    penalties: Mutex<HashMap<ZenohIdProto, Penalty>>,
+}
+
+impl Dampening {
+    pub(crate) fn new(config: &Config) -> Option<Self> {
+        if !unwrap_or_default!(config.routing().dampening().enabled()) {
+            return None;
+        }
+        let reuse_threshold =
+            unwrap_or_default!(config.routing().dampening().reuse_threshold()) as f64;
+        let half_life =
+            Duration::from_millis(unwrap_or_default!(config.routing().dampening().half_life()))
+                .max(Duration::from_millis(1));
+        let max_suppress_time = Duration::from_millis(unwrap_or_default!(config
+            .routing()
+            .dampening()
+            .max_suppress_time()));
+        Some(Self {
+            penalty: unwrap_or_default!(config.routing().dampening().penalty()) as f64,
+            suppress_threshold: unwrap_or_default!(config
+                .routing()
+                .dampening()
+                .suppress_threshold()) as f64,
+            reuse_threshold,
+            // A penalty decays below the reuse threshold within the maximum suppress time
+            max_penalty: reuse_threshold
+                * 2f64.powf(max_suppress_time.as_secs_f64() / half_life.as_secs_f64()),
+            half_life,
+            penalties: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn decay(&self, penalty: &mut Penalty, now: Instant) {
+        let elapsed = now.saturating_duration_since(penalty.updated);
+        penalty.value *= 0.5f64.powf(elapsed.as_secs_f64() / self.half_life.as_secs_f64());
+        penalty.updated = now;
+        if penalty.suppressed && penalty.value < self.reuse_threshold {
+            penalty.suppressed = false;
+        }
+    }
+
+    /// Records a flap of the given router or peer.
+    pub(crate) fn flap(&self, zid: &ZenohIdProto) {
+        self.flap_at(zid, Instant::now())
+    }
+
+    fn flap_at(&self, zid: &ZenohIdProto, now: Instant) {
+        let mut penalties = zlock!(self.penalties);
+        let penalty = penalties.entry(*zid).or_insert(Penalty {
+            value: 0.0,
+            updated: now,
+            suppressed: false,
+        });
+        self.decay(penalty, now);
+        penalty.value = (penalty.value + self.penalty).min(self.max_penalty);
+        if !penalty.suppressed && penalty.value > self.suppress_threshold {
+            tracing::warn!(
+                "{} is flapping: suppressed for at most {:?}",
+                zid,
+                self.half_life
+                    .mul_f64((penalty.value / self.reuse_threshold).log2())
+            );
+            penalty.suppressed = true;
+        }
+    }
+
+    /// Returns `true` if the given router or peer is currently suppressed.
+    pub(crate) fn is_suppressed(&self, zid: &ZenohIdProto) -> bool {
+        self.is_suppressed_at(zid, Instant::now())
+    }
+
+    fn is_suppressed_at(&self, zid: &ZenohIdProto, now: Instant) -> bool {
+        let mut penalties = zlock!(self.penalties);
+        let Some(penalty) = penalties.get_mut(zid) else {
+            return false;
+        };
+        self.decay(penalty, now);
+        if penalty.value < 1.0 {
+            penalties.remove(zid);
+            return false;
+        }
+        penalty.suppressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dampening() {
+        let mut config = Config::default();
+        config
+            .insert_json5(
+                "routing/dampening",
+                r#"{ enabled: true, half_life: 1000, max_suppress_time: 4000 }"#,
+            )
+            .unwrap();
+        let dampening = Dampening::new(&config).unwrap();
+        let zid = ZenohIdProto::default();
+        let now = Instant::now();
+
+        dampening.flap_at(&zid, now);
+        dampening.flap_at(&zid, now);
+        assert!(!dampening.is_suppressed_at(&zid, now));
+        dampening.flap_at(&zid, now);
+        assert!(dampening.is_suppressed_at(&zid, now));
+        // 3000 decays to 750 after 2 half-lives
+        assert!(dampening.is_suppressed_at(&zid, now + Duration::from_millis(1900)));
+        assert!(!dampening.is_suppressed_at(&zid, now + Duration::from_millis(2100)));
+
+        // Suppression never exceeds the maximum suppress time
+        for _ in 0..100 {
+            dampening.flap_at(&zid, now);
+        }
+        assert!(!dampening.is_suppressed_at(&zid, now + Duration::from_millis(4100)));
+    }
+}
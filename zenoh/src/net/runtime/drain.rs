@@ -0,0 +1,238 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Graceful drain of a router.
+//!
+//! A draining router stops accepting new sessions, announces alternative routers to its clients
+//! on `@/<zid>/router/drain`, waits for them to migrate and finally shuts down.
+//!
+//! A client only migrates on the announcements published by the router itself on the session with
+//! it, and if its `adminspace.permissions.write` configuration allows it. The router doesn't route
+//! the publications of other nodes on its `@/<zid>/router/drain`.
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use serde_json::json;
+use zenoh_link::Locator;
+use zenoh_protocol::{
+    core::{Reliability, WhatAmI, WireExpr, ZenohIdProto},
+    network::{push, NetworkBody, NetworkMessage, Push},
+    zenoh::{PushBody, Put},
+};
+
+use super::Runtime;
+use crate::api::{
+    builders::close::{Closeable, Closee},
+    encoding::Encoding,
+};
+
+const DRAIN_POLL_PERIOD: Duration = Duration::from_millis(100);
+
+/// The parameters of a drain command.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct DrainConf {
+    /// The locators of the routers the clients should migrate to.
+    #[serde(default)]
+    pub(crate) alternatives: Vec<Locator>,
+    /// The maximum time in milliseconds to wait for the clients to migrate before shutting down.
+    #[serde(default = "DrainConf::default_timeout")]
+    pub(crate) timeout: u64,
+}
+
+impl DrainConf {
+    fn default_timeout() -> u64 {
+        30_000
+    }
+}
+
+impl Default for DrainConf {
+    fn default() -> Self {
+        Self {
+            alternatives: vec![],
+            timeout: Self::default_timeout(),
+        }
+    }
+}
+
+impl Runtime {
+    /// Returns `true` if this runtime has been put into drain mode.
+    pub fn is_draining(&self) -> bool {
+        self.state.draining.load(Ordering::Relaxed)
+    }
+
+    /// Puts this runtime into drain mode, then shuts it down once its clients have migrated.
+    pub(crate) fn drain(&self, conf: DrainConf) {
+        if self.state.draining.swap(true, Ordering::Relaxed) {
+            tracing::warn!("Runtime {} is already draining", self.zid());
+            return;
+        }
+        tracing::info!(
+            "Draining runtime {}: alternatives {:?}, timeout {}ms",
+            self.zid(),
+            conf.alternatives,
+            conf.timeout
+        );
+        // The drain task closes the runtime so it must not be tracked by its task controller
+        let runtime = self.clone();
+        zenoh_runtime::ZRuntime::Net.spawn(async move {
+            runtime.drain_impl(conf).await;
+        });
+    }
+
+    async fn drain_impl(&self, conf: DrainConf) {
+        let manager = self.manager();
+        for listener in manager.get_listeners().await {
+            if let Err(e) = manager.del_listener(&listener).await {
+                tracing::warn!("Unable to close listener {}: {}", listener, e);
+            }
+        }
+        self.state.locators.write().unwrap().clear();
+
+        self.announce_drain(&conf.alternatives);
+
+        let deadline = Instant::now() + Duration::from_millis(conf.timeout);
+        loop {
+            let clients = manager
+                .get_transports_unicast()
+                .await
+                .iter()
+                .filter(|t| t.get_whatami().is_ok_and(|w| w == WhatAmI::Client))
+                .count();
+            if clients == 0 {
+                tracing::info!("All clients of runtime {} migrated", self.zid());
+                break;
+            }
+            if Instant::now() >= deadline {
+                tracing::warn!(
+                    "{} clients of runtime {} did not migrate before the drain timeout",
+                    clients,
+                    self.zid()
+                );
+                break;
+            }
+            tokio::time::sleep(DRAIN_POLL_PERIOD).await;
+        }
+
+        tracing::info!("Runtime {} drained: shutting down", self.zid());
+        self.get_closee().close_inner().await;
+    }
+
+    fn announce_drain(&self, alternatives: &[Locator]) {
+        let key_expr = format!("@/{}/{}/drain", self.zid(), self.whatami());
+        let payload = json!({
+            "alternatives": alternatives.iter().map(Locator::as_str).collect::<Vec<_>>(),
+        })
+        .to_string();
+        let faces = zread!(self.state.router.tables.tables)
+            .faces
+            .values()
+            .filter(|face| face.whatami == WhatAmI::Client)
+            .cloned()
+            .collect::<Vec<_>>();
+        for face in faces {
+            face.primitives.send_push(
+                Push {
+                    wire_expr: WireExpr::from(key_expr.clone()),
+                    ext_qos: push::ext::QoSType::DEFAULT,
+                    ext_tstamp: None,
+                    ext_nodeid: push::ext::NodeIdType::DEFAULT,
+                    ext_hops: 0,
+                    ext_hop_limit: None,
+                    ext_deadline: None,
+                    payload: PushBody::Put(Put {
+                        timestamp: self.new_timestamp(),
+                        encoding: Encoding::APPLICATION_JSON.into(),
+                        ext_sinfo: None,
+                        ext_attachment: None,
+                        ext_trace: None,
+                        ext_signature: None,
+                        #[cfg(feature = "shared-memory")]
+                        ext_shm: None,
+                        ext_unknown: vec![],
+                        payload: payload.clone().into_bytes().into(),
+                    }),
+                },
+                Reliability::Reliable,
+            );
+        }
+    }
+
+    /// Migrates if `msg`, received on the session with `router`, is the drain announcement of
+    /// `router`.
+    pub(super) fn on_drain_announcement(&self, router: &ZenohIdProto, msg: &NetworkMessage) {
+        #[derive(Deserialize)]
+        struct Announcement {
+            alternatives: Vec<Locator>,
+        }
+
+        let NetworkBody::Push(push) = &msg.body else {
+            return;
+        };
+        let PushBody::Put(put) = &push.payload else {
+            return;
+        };
+        if push.wire_expr.scope != 0
+            || !push.wire_expr.suffix.ends_with("/router/drain")
+            || push.wire_expr.suffix[..] != format!("@/{router}/router/drain")
+        {
+            return;
+        }
+        if !self.config().lock().0.adminspace.permissions().write {
+            tracing::debug!(
+                "Ignoring drain announcement of {}: adminspace.permissions.write=false in configuration",
+                router
+            );
+            return;
+        }
+        match serde_json::from_slice::<Announcement>(&put.payload.to_zslice()) {
+            Ok(announcement) => self.migrate(*router, announcement.alternatives),
+            Err(e) => tracing::debug!("Invalid drain announcement from {}: {}", router, e),
+        }
+    }
+
+    /// Migrates this client from the draining router `from` to one of the `alternatives`.
+    fn migrate(&self, from: ZenohIdProto, alternatives: Vec<Locator>) {
+        let runtime = self.clone();
+        self.spawn(async move {
+            let manager = runtime.manager();
+            if manager.get_transport_unicast(&from).await.is_none() {
+                return;
+            }
+            for locator in alternatives {
+                match manager.open_transport_unicast(locator.clone().into()).await {
+                    Ok(transport) => {
+                        tracing::info!(
+                            "Router {} is draining: migrated to {:?}",
+                            from,
+                            transport.get_zid()
+                        );
+                        if let Some(transport) = manager.get_transport_unicast(&from).await {
+                            let _ = transport.close().await;
+                        }
+                        return;
+                    }
+                    Err(e) => tracing::debug!("Unable to migrate to {}: {}", locator, e),
+                }
+            }
+            tracing::warn!(
+                "Router {} is draining: unable to reach any alternative",
+                from
+            );
+        });
+    }
+}
@@ -0,0 +1,276 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Persistence of the identity of a node across restarts: its Zenoh ID and the credentials of its
+//! links, stored in the file configured in `identity/file`, encrypted with `identity/secret`.
+use std::{fs, io::ErrorKind, path::Path};
+
+use base64::{engine::general_purpose::STANDARD as b64_std_engine, Engine};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use zenoh_config::{secret, wrappers::ZenohId, Config};
+use zenoh_crypto::{hmac, XAeadCipher};
+use zenoh_result::{bail, zerror, ZResult};
+
+// Authenticated along with the identity, so that no other sealed data is taken for one
+const AAD: &[u8] = b"zenoh/identity";
+// The random salt of the key derivation prefixes the sealed identity in the file
+const SALT_SIZE: usize = 16;
+#[cfg(not(test))]
+const KDF_ROUNDS: u32 = 100_000;
+#[cfg(test)]
+const KDF_ROUNDS: u32 = 1_000;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Identity {
+    zid: ZenohId,
+    #[serde(default)]
+    usrpwd: Option<(String, String)>,
+    #[serde(default)]
+    tls_connect_private_key: Option<String>,
+    #[serde(default)]
+    tls_connect_certificate: Option<String>,
+}
+
+impl Identity {
+    fn from_config(config: &Config) -> ZResult<Self> {
+        let usrpwd = config.transport().auth().usrpwd();
+        let usrpwd = match (usrpwd.user(), usrpwd.password()) {
+            (Some(user), Some(password)) => Some((
+                secret::resolve(user)?.into_owned(),
+                secret::resolve(password)?.into_owned(),
+            )),
+            _ => None,
+        };
+        let tls = config.transport().link().tls();
+        Ok(Identity {
+            zid: config.id().unwrap_or_default(),
+            usrpwd,
+            tls_connect_private_key: pem(
+                tls.connect_private_key().as_deref(),
+                tls.connect_private_key_base64()
+                    .as_ref()
+                    .map(|s| s.expose_secret().as_str()),
+            )?,
+            tls_connect_certificate: pem(
+                tls.connect_certificate().as_deref(),
+                tls.connect_certificate_base64()
+                    .as_ref()
+                    .map(|s| s.expose_secret().as_str()),
+            )?,
+        })
+    }
+
+    /// Keeps the Zenoh ID of the stored identity, the credentials of the configuration replacing
+    /// the stored ones.
+    fn update(self, config: Identity) -> Identity {
+        Identity {
+            zid: self.zid,
+            usrpwd: config.usrpwd.or(self.usrpwd),
+            tls_connect_private_key: config
+                .tls_connect_private_key
+                .or(self.tls_connect_private_key),
+            tls_connect_certificate: config
+                .tls_connect_certificate
+                .or(self.tls_connect_certificate),
+        }
+    }
+
+    /// Sets the Zenoh ID of the configuration, and the TLS credentials it doesn't set. The usrpwd
+    /// credentials are kept out of the configuration, which is not secret.
+    fn apply(&self, config: &mut Config) -> ZResult<()> {
+        config
+            .set_id(Some(self.zid))
+            .map_err(|_| zerror!("Invalid Zenoh ID"))?;
+        let tls = config.transport().link().tls();
+        let set_key = tls.connect_private_key().is_none()
+            && tls.connect_private_key_base64().is_none()
+            && self.tls_connect_private_key.is_some();
+        let set_certificate = tls.connect_certificate().is_none()
+            && tls.connect_certificate_base64().is_none()
+            && self.tls_connect_certificate.is_some();
+        if set_key {
+            config.insert_json5(
+                "transport/link/tls/connect_private_key_base64",
+                &serde_json::to_string(
+                    &b64_std_engine.encode(self.tls_connect_private_key.as_ref().unwrap()),
+                )?,
+            )?;
+        }
+        if set_certificate {
+            config.insert_json5(
+                "transport/link/tls/connect_certificate_base64",
+                &serde_json::to_string(
+                    &b64_std_engine.encode(self.tls_connect_certificate.as_ref().unwrap()),
+                )?,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the PEM content of a file or of its base64 encoding, both possibly secret references.
+fn pem(file: Option<&str>, base64: Option<&str>) -> ZResult<Option<String>> {
+    if let Some(file) = secret::resolve_option(file)? {
+        let pem = fs::read_to_string(file.as_ref())
+            .map_err(|e| zerror!("Unable to read '{}': {}", file, e))?;
+        return Ok(Some(pem));
+    }
+    match secret::resolve_option(base64)? {
+        Some(base64) => {
+            let bytes = b64_std_engine.decode(base64.as_ref())?;
+            Ok(Some(String::from_utf8(bytes)?))
+        }
+        None => Ok(None),
+    }
+}
+
+fn cipher(secret: &str, salt: &[u8]) -> XAeadCipher {
+    XAeadCipher::new(hmac::derive_key(secret.as_bytes(), salt, KDF_ROUNDS))
+}
+
+fn write(path: &Path, sealed: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(path)?, sealed)
+}
+
+/// Loads the identity persisted in `identity/file` into the configuration, creating or updating
+/// the file with the identity of the configuration. Returns the usrpwd credentials of the
+/// identity, which are not set in the configuration.
+pub(crate) fn load(config: &mut Config) -> ZResult<Option<(String, String)>> {
+    let Some(file) = config.identity().file().clone() else {
+        return Ok(None);
+    };
+    let Some(secret) = config.identity().secret().as_ref() else {
+        bail!(
+            "`identity/secret` must be set to persist the identity in '{}'",
+            file
+        );
+    };
+    let secret = secret::resolve(secret.expose_secret())?;
+    let path = Path::new(&file);
+    let current = Identity::from_config(config)?;
+    let stored = match fs::read(path) {
+        Ok(content) if content.len() < SALT_SIZE => {
+            bail!("Invalid identity file '{}'", file)
+        }
+        Ok(content) => {
+            let (salt, sealed) = content.split_at(SALT_SIZE);
+            let bytes = cipher(&secret, salt)
+                .open(sealed, AAD)
+                .map_err(|e| zerror!("Unable to decrypt identity file '{}': {}", file, e))?;
+            Some(serde_json::from_slice::<Identity>(&bytes)?)
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => bail!("Unable to read identity file '{}': {}", file, e),
+    };
+    let identity = match &stored {
+        Some(stored) => {
+            if let Some(zid) = config.id().filter(|zid| *zid != stored.zid) {
+                bail!(
+                    "The configured Zenoh ID {} differs from the Zenoh ID {} of the identity file '{}'",
+                    zid,
+                    stored.zid,
+                    file
+                );
+            }
+            stored.clone().update(current)
+        }
+        None => current,
+    };
+    if stored.as_ref() != Some(&identity) {
+        let salt: [u8; SALT_SIZE] = rand::random();
+        let mut content = salt.to_vec();
+        content.extend(cipher(&secret, &salt).seal(&serde_json::to_vec(&identity)?, AAD)?);
+        write(path, &content)
+            .map_err(|e| zerror!("Unable to write identity file '{}': {}", file, e))?;
+        tracing::info!("Saved identity {} to '{}'", identity.zid, file);
+    } else {
+        tracing::debug!("Loaded identity {} from '{}'", identity.zid, file);
+    }
+    identity.apply(config)?;
+    Ok(identity.usrpwd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_persistence() {
+        let path = std::env::temp_dir().join(format!("zenoh-identity-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let config = |json: &str| {
+            let mut config = Config::default();
+            config
+                .insert_json5(
+                    "identity",
+                    &format!(r#"{{ file: {:?}, secret: "s3cr3t" }}"#, path.display()),
+                )
+                .unwrap();
+            if !json.is_empty() {
+                config.insert_json5("transport/auth/usrpwd", json).unwrap();
+            }
+            config
+        };
+
+        let credentials = |user: &str, password: &str| Some((user.into(), password.into()));
+
+        // The file is created with the identity of the configuration
+        let mut first = config(r#"{ user: "alice", password: "pwd" }"#);
+        assert_eq!(*first.id(), None);
+        load(&mut first).unwrap();
+        let zid = first.id().unwrap();
+        let bytes = fs::read(&path).unwrap();
+        assert!(!bytes.windows(3).any(|w| w == b"pwd"));
+
+        // The identity is restored after a restart, the credentials included but kept out of
+        // the configuration
+        let mut second = config("");
+        assert_eq!(load(&mut second).unwrap(), credentials("alice", "pwd"));
+        assert_eq!(*second.id(), Some(zid));
+        assert_eq!(*second.transport.auth.usrpwd.user(), None);
+        assert_eq!(*second.transport.auth.usrpwd.password(), None);
+
+        // The credentials of the configuration replace the stored ones
+        let mut third = config(r#"{ user: "bob", password: "pwd2" }"#);
+        load(&mut third).unwrap();
+        let mut fourth = config("");
+        assert_eq!(load(&mut fourth).unwrap(), credentials("bob", "pwd2"));
+        assert_eq!(*fourth.id(), Some(zid));
+
+        // A configured Zenoh ID must match the stored one
+        let mut explicit = config("");
+        explicit.insert_json5("id", r#""a1b2""#).unwrap();
+        assert!(load(&mut explicit).is_err());
+        let mut matching = config("");
+        matching
+            .insert_json5("id", &format!("{:?}", zid.to_string()))
+            .unwrap();
+        load(&mut matching).unwrap();
+        assert_eq!(*matching.id(), Some(zid));
+
+        // The file can't be decrypted with another secret
+        let mut other = config("");
+        other.insert_json5("identity/secret", r#""other""#).unwrap();
+        assert!(load(&mut other).is_err());
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,444 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! DNS-SD over mDNS scouting.
+//!
+//! Zenoh instances are advertised as `<zid>._zenoh._udp.local` service instances. The TXT record
+//! of each instance holds its zid, what-am-I and locators, the SRV record points to the port of
+//! its first locator for the benefit of generic DNS-SD browsers.
+use std::{
+    future::Future,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    str::FromStr,
+    time::Duration,
+};
+
+use tokio::net::UdpSocket;
+use zenoh_config::{unwrap_or_default, ModeDependent};
+use zenoh_link::Locator;
+use zenoh_protocol::{
+    core::{WhatAmI, WhatAmIMatcher, ZenohIdProto},
+    scouting::HelloProto,
+};
+use zenoh_result::{bail, ZResult};
+
+use super::{orchestrator::Loop, Runtime};
+
+const MDNS_ADDR: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(224, 0, 0, 251), 5353));
+const MDNS_TTL: u32 = 255;
+const SERVICE: &str = "_zenoh._udp.local";
+const RECORD_TTL: u32 = 120;
+const RCV_BUF_SIZE: usize = 9_000;
+const QUERY_INITIAL_PERIOD: Duration = Duration::from_millis(1_000);
+const QUERY_MAX_PERIOD: Duration = Duration::from_millis(8_000);
+const QUERY_PERIOD_INCREASE_FACTOR: u32 = 2;
+
+const FLAG_RESPONSE: u16 = 0x8000;
+const FLAG_AUTHORITATIVE: u16 = 0x0400;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_SRV: u16 = 33;
+const TYPE_ANY: u16 = 255;
+const CLASS_IN: u16 = 1;
+const CLASS_CACHE_FLUSH: u16 = 0x8000;
+
+#[derive(Debug, PartialEq, Eq)]
+enum MdnsMessage {
+    /// A query for the zenoh service instances.
+    Query,
+    /// A response advertising the given zenoh instances.
+    Response(Vec<HelloProto>),
+}
+
+fn write_name(buf: &mut Vec<u8>, name: &str) {
+    for label in name.split('.').filter(|l| !l.is_empty()) {
+        buf.push(label.len() as u8);
+        buf.extend_from_slice(label.as_bytes());
+    }
+    buf.push(0);
+}
+
+fn write_header(buf: &mut Vec<u8>, flags: u16, questions: u16, answers: u16) {
+    buf.extend_from_slice(&0u16.to_be_bytes());
+    buf.extend_from_slice(&flags.to_be_bytes());
+    buf.extend_from_slice(&questions.to_be_bytes());
+    buf.extend_from_slice(&answers.to_be_bytes());
+    buf.extend_from_slice(&0u16.to_be_bytes());
+    buf.extend_from_slice(&0u16.to_be_bytes());
+}
+
+fn write_record(buf: &mut Vec<u8>, name: &str, rtype: u16, class: u16, rdata: &[u8]) {
+    write_name(buf, name);
+    buf.extend_from_slice(&rtype.to_be_bytes());
+    buf.extend_from_slice(&class.to_be_bytes());
+    buf.extend_from_slice(&RECORD_TTL.to_be_bytes());
+    buf.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+    buf.extend_from_slice(rdata);
+}
+
+fn encode_query() -> Vec<u8> {
+    let mut buf = vec![];
+    write_header(&mut buf, 0, 1, 0);
+    write_name(&mut buf, SERVICE);
+    buf.extend_from_slice(&TYPE_PTR.to_be_bytes());
+    buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+    buf
+}
+
+fn encode_response(hello: &HelloProto) -> Vec<u8> {
+    let instance = format!("{}.{}", hello.zid, SERVICE);
+
+    let mut ptr = vec![];
+    write_name(&mut ptr, &instance);
+
+    let port = hello
+        .locators
+        .iter()
+        .find_map(|l| l.address().as_str().parse::<SocketAddr>().ok())
+        .map_or(0, |addr| addr.port());
+    let mut srv = vec![0, 0, 0, 0];
+    srv.extend_from_slice(&port.to_be_bytes());
+    write_name(&mut srv, &format!("{}.local", hello.zid));
+
+    let mut txt = vec![];
+    let entries = [
+        format!("zid={}", hello.zid),
+        format!("whatami={}", hello.whatami),
+    ]
+    .into_iter()
+    .chain(hello.locators.iter().map(|l| format!("locator={l}")));
+    for entry in entries {
+        match u8::try_from(entry.len()) {
+            Ok(len) => {
+                txt.push(len);
+                txt.extend_from_slice(entry.as_bytes());
+            }
+            Err(_) => tracing::warn!("Entry too long to be advertised through mDNS: {}", entry),
+        }
+    }
+
+    let mut buf = vec![];
+    write_header(&mut buf, FLAG_RESPONSE | FLAG_AUTHORITATIVE, 0, 3);
+    write_record(&mut buf, SERVICE, TYPE_PTR, CLASS_IN, &ptr);
+    let class = CLASS_IN | CLASS_CACHE_FLUSH;
+    write_record(&mut buf, &instance, TYPE_SRV, class, &srv);
+    write_record(&mut buf, &instance, TYPE_TXT, class, &txt);
+    buf
+}
+
+struct MdnsReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> MdnsReader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.buf.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn name(&mut self) -> Option<String> {
+        const MAX_JUMPS: usize = 16;
+
+        let mut labels = vec![];
+        let mut pos = self.pos;
+        let mut jumps = 0;
+        loop {
+            let len = *self.buf.get(pos)? as usize;
+            if len & 0xC0 == 0xC0 {
+                // Compression pointer
+                let ptr = ((len & 0x3F) << 8) | *self.buf.get(pos + 1)? as usize;
+                if jumps == 0 {
+                    self.pos = pos + 2;
+                }
+                jumps += 1;
+                if jumps > MAX_JUMPS {
+                    return None;
+                }
+                pos = ptr;
+                continue;
+            }
+            pos += 1;
+            if len == 0 {
+                break;
+            }
+            let label = self.buf.get(pos..pos + len)?;
+            labels.push(std::str::from_utf8(label).ok()?);
+            pos += len;
+        }
+        if jumps == 0 {
+            self.pos = pos;
+        }
+        Some(labels.join("."))
+    }
+}
+
+fn decode_txt(rdata: &[u8]) -> Option<HelloProto> {
+    let mut zid = None;
+    let mut whatami = None;
+    let mut locators = vec![];
+    let mut reader = MdnsReader { buf: rdata, pos: 0 };
+    while reader.pos < rdata.len() {
+        let len = *reader.bytes(1)?.first()? as usize;
+        let entry = std::str::from_utf8(reader.bytes(len)?).ok()?;
+        match entry.split_once('=') {
+            Some(("zid", value)) => zid = ZenohIdProto::from_str(value).ok(),
+            Some(("whatami", value)) => whatami = WhatAmI::from_str(value).ok(),
+            Some(("locator", value)) => match Locator::from_str(value) {
+                Ok(locator) => locators.push(locator),
+                Err(e) => {
+                    tracing::debug!("Invalid locator {} advertised through mDNS: {}", value, e)
+                }
+            },
+            _ => {}
+        }
+    }
+    Some(HelloProto {
+        version: zenoh_protocol::VERSION,
+        whatami: whatami?,
+        zid: zid?,
+        locators,
+    })
+}
+
+fn decode(buf: &[u8]) -> Option<MdnsMessage> {
+    let mut reader = MdnsReader { buf, pos: 0 };
+    let _id = reader.u16()?;
+    let flags = reader.u16()?;
+    let questions = reader.u16()?;
+    let records = [reader.u16()?, reader.u16()?, reader.u16()?]
+        .into_iter()
+        .map(usize::from)
+        .sum::<usize>();
+
+    if flags & FLAG_RESPONSE == 0 {
+        let mut asked = false;
+        for _ in 0..questions {
+            let name = reader.name()?;
+            let qtype = reader.u16()?;
+            let _class = reader.u16()?;
+            asked |= name.eq_ignore_ascii_case(SERVICE) && (qtype == TYPE_PTR || qtype == TYPE_ANY);
+        }
+        return asked.then_some(MdnsMessage::Query);
+    }
+
+    for _ in 0..questions {
+        reader.name()?;
+        reader.u32()?;
+    }
+    let mut hellos = vec![];
+    for _ in 0..records {
+        let name = reader.name()?;
+        let rtype = reader.u16()?;
+        let _class = reader.u16()?;
+        let _ttl = reader.u32()?;
+        let len = reader.u16()? as usize;
+        let rdata = reader.bytes(len)?;
+        if rtype == TYPE_TXT && name.to_ascii_lowercase().ends_with(SERVICE) {
+            hellos.extend(decode_txt(rdata));
+        }
+    }
+    Some(MdnsMessage::Response(hellos))
+}
+
+impl Runtime {
+    async fn bind_mdns_port(ifaces: &str) -> ZResult<UdpSocket> {
+        let ifaces = Runtime::get_interfaces(ifaces);
+        if ifaces.is_empty() {
+            bail!("Unable to find mDNS interface!")
+        }
+        Runtime::bind_mcast_port(&MDNS_ADDR, &ifaces, MDNS_TTL).await
+    }
+
+    /// Starts advertising and/or discovering Zenoh instances through mDNS if configured to.
+    pub(super) async fn start_mdns(&self) -> ZResult<()> {
+        let (enabled, listen, autoconnect, ifaces) = {
+            let guard = &self.state.config.lock().0;
+            let whatami = self.whatami();
+            (
+                unwrap_or_default!(guard.scouting().mdns().enabled()),
+                *unwrap_or_default!(guard.scouting().mdns().listen().get(whatami)),
+                *unwrap_or_default!(guard.scouting().mdns().autoconnect().get(whatami)),
+                unwrap_or_default!(guard.scouting().mdns().interface()),
+            )
+        };
+        if !enabled || (!listen && autoconnect.is_empty()) {
+            return Ok(());
+        }
+        let socket = Runtime::bind_mdns_port(&ifaces).await?;
+        let this = self.clone();
+        self.spawn_abortable(async move {
+            let runtime = &this;
+            this.mdns(&socket, listen, autoconnect, move |hello| async move {
+                if !hello.locators.is_empty() {
+                    runtime.connect_peer(&hello.zid, &hello.locators).await;
+                } else {
+                    tracing::warn!("Discovered Zenoh instance with no locators: {:?}", hello);
+                }
+                Loop::Continue
+            })
+            .await
+        });
+        Ok(())
+    }
+
+    /// Connects to the first Zenoh instance matching `what` discovered through mDNS.
+    pub(super) async fn connect_first_mdns(
+        &self,
+        what: WhatAmIMatcher,
+        timeout: Duration,
+    ) -> ZResult<()> {
+        let ifaces = {
+            let guard = &self.state.config.lock().0;
+            unwrap_or_default!(guard.scouting().mdns().interface())
+        };
+        let socket = Runtime::bind_mdns_port(&ifaces).await?;
+        let scout = self.mdns(&socket, false, what, move |hello| async move {
+            tracing::info!("Found {:?}", hello);
+            if !hello.locators.is_empty() && self.connect_peer(&hello.zid, &hello.locators).await {
+                return Loop::Break;
+            }
+            Loop::Continue
+        });
+        match tokio::time::timeout(timeout, scout).await {
+            Ok(()) => Ok(()),
+            Err(_) => bail!("timeout"),
+        }
+    }
+
+    /// Answers mDNS queries if `listen` is true and queries Zenoh instances matching `matcher`,
+    /// calling `f` on each discovered instance until it returns [`Loop::Break`].
+    async fn mdns<Fut, F>(&self, socket: &UdpSocket, listen: bool, matcher: WhatAmIMatcher, f: F)
+    where
+        F: Fn(HelloProto) -> Fut + Send + Sync,
+        Fut: Future<Output = Loop> + Send,
+    {
+        let send = async {
+            if matcher.is_empty() {
+                return std::future::pending::<()>().await;
+            }
+            let query = encode_query();
+            let mut delay = QUERY_INITIAL_PERIOD;
+            loop {
+                tracing::trace!("Send mDNS query for {} to {}", SERVICE, MDNS_ADDR);
+                if let Err(err) = socket.send_to(&query, MDNS_ADDR).await {
+                    tracing::debug!("Unable to send mDNS query to {}: {}", MDNS_ADDR, err);
+                }
+                tokio::time::sleep(delay).await;
+                if delay * QUERY_PERIOD_INCREASE_FACTOR <= QUERY_MAX_PERIOD {
+                    delay *= QUERY_PERIOD_INCREASE_FACTOR;
+                }
+            }
+        };
+        let recv = async {
+            let zid = self.manager().zid();
+            let mut buf = vec![0; RCV_BUF_SIZE];
+            loop {
+                let (n, peer) = match socket.recv_from(&mut buf).await {
+                    Ok(res) => res,
+                    Err(e) => {
+                        tracing::debug!("Error receiving mDNS datagram: {}", e);
+                        continue;
+                    }
+                };
+                match decode(&buf[..n]) {
+                    Some(MdnsMessage::Query) if listen => {
+                        let hello = HelloProto {
+                            version: zenoh_protocol::VERSION,
+                            whatami: self.whatami(),
+                            zid,
+                            locators: self.get_locators(),
+                        };
+                        tracing::trace!("Send mDNS response {:?} to {}", hello, MDNS_ADDR);
+                        if let Err(err) = socket.send_to(&encode_response(&hello), MDNS_ADDR).await
+                        {
+                            tracing::error!("Unable to send mDNS response to {}: {}", peer, err);
+                        }
+                    }
+                    Some(MdnsMessage::Response(hellos)) => {
+                        for hello in hellos {
+                            if hello.zid == zid || !matcher.matches(hello.whatami) {
+                                continue;
+                            }
+                            tracing::trace!("Received mDNS response {:?} from {}", hello, peer);
+                            if let Loop::Break = f(hello).await {
+                                return;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        };
+        tokio::select! {
+            _ = send => {},
+            _ = recv => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mdns_codec() {
+        let query = encode_query();
+        assert_eq!(decode(&query), Some(MdnsMessage::Query));
+
+        let hello = HelloProto {
+            version: zenoh_protocol::VERSION,
+            whatami: WhatAmI::Router,
+            zid: ZenohIdProto::rand(),
+            locators: vec![
+                "tcp/192.168.1.1:7447".parse().unwrap(),
+                "udp/[::1]:7447".parse().unwrap(),
+            ],
+        };
+        let response = encode_response(&hello);
+        assert_eq!(decode(&response), Some(MdnsMessage::Response(vec![hello])));
+    }
+
+    #[test]
+    fn mdns_compressed_names() {
+        let mut buf = vec![];
+        write_header(&mut buf, 0, 2, 0);
+        let service = buf.len() as u16;
+        write_name(&mut buf, SERVICE);
+        buf.extend_from_slice(&TYPE_SRV.to_be_bytes());
+        buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+        // Second question name is a pointer to the first one
+        buf.extend_from_slice(&(0xC000 | service).to_be_bytes());
+        buf.extend_from_slice(&TYPE_PTR.to_be_bytes());
+        buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+        assert_eq!(decode(&buf), Some(MdnsMessage::Query));
+
+        // Pointer loops are rejected
+        let mut buf = vec![];
+        write_header(&mut buf, 0, 1, 0);
+        buf.extend_from_slice(&0xC00Cu16.to_be_bytes());
+        assert_eq!(decode(&buf), None);
+    }
+}
@@ -17,7 +17,12 @@
 //! This module is intended for Zenoh's internal use.
 //!
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
-mod adminspace;
+pub(crate) mod adminspace;
+mod config_watch;
+mod dampening;
+mod drain;
+mod identity;
+mod mdns;
 pub mod orchestrator;
 
 #[cfg(feature = "plugins")]
@@ -26,9 +31,10 @@ use std::{
     any::Any,
     collections::HashSet,
     sync::{
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
         Arc, Weak,
     },
+    time::Duration,
 };
 
 pub use adminspace::AdminSpace;
@@ -36,7 +42,7 @@ use async_trait::async_trait;
 use futures::{stream::StreamExt, Future};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use uhlc::{HLCBuilder, HLC};
+use uhlc::{HLCBuilder, HLC, NTP64};
 use zenoh_config::{unwrap_or_default, ModeDependent, ZenohId};
 use zenoh_link::{EndPoint, Link};
 use zenoh_plugin_trait::{PluginStartArgs, StructVersion};
@@ -52,16 +58,17 @@ use zenoh_shm::reader::ShmReader;
 use zenoh_sync::get_mut_unchecked;
 use zenoh_task::TaskController;
 use zenoh_transport::{
-    multicast::TransportMulticast, unicast::TransportUnicast, TransportEventHandler,
+    multicast::TransportMulticast, unicast::TransportUnicast, DropReason, TransportEventHandler,
     TransportManager, TransportMulticastEventHandler, TransportPeer, TransportPeerEventHandler,
 };
+use zenoh_util::flight_recorder::FlightRecorder;
 
-use self::orchestrator::StartConditions;
+use self::{dampening::Dampening, orchestrator::StartConditions};
 use super::{primitives::DeMux, routing, routing::router::Router};
 #[cfg(feature = "plugins")]
-use crate::api::loader::{load_plugins, start_plugins};
+use crate::api::loader::{declare_static_plugins, load_plugins, start_plugins, StaticPlugin};
 #[cfg(feature = "plugins")]
-use crate::api::plugins::PluginsManager;
+use crate::api::plugins::{PluginsManager, ZenohPlugin};
 #[cfg(feature = "internal")]
 use crate::session::CloseBuilder;
 use crate::{
@@ -87,6 +94,9 @@ pub(crate) struct RuntimeState {
     plugins_manager: Mutex<PluginsManager>,
     start_conditions: Arc<StartConditions>,
     pending_connections: tokio::sync::Mutex<HashSet<ZenohIdProto>>,
+    static_neighbours: Option<HashSet<ZenohIdProto>>,
+    draining: AtomicBool,
+    dampening: Option<Dampening>,
 }
 
 pub struct WeakRuntime {
@@ -103,8 +113,11 @@ pub struct RuntimeBuilder {
     config: zenoh_config::Config,
     #[cfg(feature = "plugins")]
     plugins_manager: Option<PluginsManager>,
+    #[cfg(feature = "plugins")]
+    static_plugins: Vec<StaticPlugin>,
     #[cfg(feature = "shared-memory")]
     shm_clients: Option<Arc<ShmClientStorage>>,
+    clock: Option<fn() -> NTP64>,
 }
 
 impl RuntimeBuilder {
@@ -113,8 +126,11 @@ impl RuntimeBuilder {
             config: config.0,
             #[cfg(feature = "plugins")]
             plugins_manager: None,
+            #[cfg(feature = "plugins")]
+            static_plugins: Vec::new(),
             #[cfg(feature = "shared-memory")]
             shm_clients: None,
+            clock: None,
         }
     }
 
@@ -124,28 +140,84 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Registers the plugin `P` linked in the application binary, which is started with the
+    /// configuration `plugins/<P::DEFAULT_NAME>` without loading a dynamic library.
+    #[cfg(all(feature = "plugins", feature = "internal"))]
+    pub fn static_plugin<P: ZenohPlugin + Send + Sync>(mut self) -> Self {
+        self.static_plugins.push(StaticPlugin::new::<P>());
+        self
+    }
+
+    #[cfg(feature = "plugins")]
+    pub(crate) fn static_plugins(mut self, static_plugins: Vec<StaticPlugin>) -> Self {
+        self.static_plugins.extend(static_plugins);
+        self
+    }
+
     #[cfg(feature = "shared-memory")]
     pub fn shm_clients(mut self, shm_clients: Option<Arc<ShmClientStorage>>) -> Self {
         self.shm_clients = shm_clients;
         self
     }
 
+    /// Use the given physical clock, e.g. synchronized with PTP or GPS, for the HLC of the
+    /// runtime instead of the system time.
+    #[cfg(feature = "unstable")]
+    pub fn clock(mut self, clock: fn() -> NTP64) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
     pub async fn build(self) -> ZResult<Runtime> {
         let RuntimeBuilder {
-            config,
+            mut config,
             #[cfg(feature = "plugins")]
             mut plugins_manager,
+            #[cfg(feature = "plugins")]
+            static_plugins,
             #[cfg(feature = "shared-memory")]
             shm_clients,
+            clock,
         } = self;
 
         tracing::debug!("Zenoh Rust API {}", GIT_VERSION);
-        let zid = (*config.id()).into();
+        #[cfg_attr(not(feature = "auth_usrpwd"), allow(unused_variables))]
+        let usrpwd = identity::load(&mut config)?;
+        // Resolved once, for the whole runtime to use the same Zenoh ID
+        let zid = config.id().unwrap_or_default();
+        config.set_id(Some(zid)).unwrap();
+        let zid = zid.into();
         tracing::info!("Using ZID: {}", zid);
 
         let whatami = unwrap_or_default!(config.mode());
-        let hlc = (*unwrap_or_default!(config.timestamping().enabled().get(whatami)))
-            .then(|| Arc::new(HLCBuilder::new().with_id(uhlc::ID::from(&zid)).build()));
+
+        let static_neighbours = unwrap_or_default!(config.routing().static_topology().enabled())
+            .then(|| {
+                tracing::info!(
+                    "Static topology enabled: multicast, mDNS and gossip scouting are disabled"
+                );
+                config.scouting.multicast.set_enabled(Some(false)).unwrap();
+                config.scouting.mdns.set_enabled(Some(false)).unwrap();
+                config.scouting.gossip.set_enabled(Some(false)).unwrap();
+                config
+                    .routing()
+                    .static_topology()
+                    .neighbours()
+                    .iter()
+                    .map(|n| n.zid.into())
+                    .collect()
+            });
+        let dampening = Dampening::new(&config);
+        let hlc = (*unwrap_or_default!(config.timestamping().enabled().get(whatami))).then(|| {
+            let mut builder = HLCBuilder::new().with_id(uhlc::ID::from(&zid));
+            if let Some(clock) = clock {
+                builder = builder.with_clock(clock);
+            }
+            if let Some(max_delta) = config.timestamping().max_delta_ms() {
+                builder = builder.with_max_delta(Duration::from_millis(*max_delta));
+            }
+            Arc::new(builder.build())
+        });
 
         let router = Arc::new(Router::new(zid, whatami, hlc.clone(), &config)?);
 
@@ -157,7 +229,18 @@ impl RuntimeBuilder {
             .from_config(&config)
             .await?
             .whatami(whatami)
-            .zid(zid);
+            .zid(zid)
+            .flight_recorder(router.tables.flight_recorder.clone());
+        #[cfg(feature = "auth_usrpwd")]
+        let transport_manager_builder = match usrpwd {
+            Some((user, password)) => {
+                transport_manager_builder.default_usrpwd(user.into_bytes(), password.into_bytes())
+            }
+            None => transport_manager_builder,
+        };
+        if *config.flight_recorder().panic_hook() {
+            router.tables.flight_recorder.install_panic_hook();
+        }
 
         #[cfg(feature = "shared-memory")]
         let transport_manager_builder =
@@ -167,9 +250,13 @@ impl RuntimeBuilder {
 
         // Plugins manager
         #[cfg(feature = "plugins")]
-        let plugins_manager = plugins_manager
-            .take()
-            .unwrap_or_else(|| load_plugins(&config));
+        let plugins_manager = match plugins_manager.take() {
+            Some(mut plugins_manager) => {
+                declare_static_plugins(&mut plugins_manager, &config, &static_plugins);
+                plugins_manager
+            }
+            None => load_plugins(&config, &static_plugins),
+        };
         // Admin space creation flag
         let start_admin_space = *config.adminspace.enabled();
 
@@ -190,6 +277,9 @@ impl RuntimeBuilder {
                 plugins_manager: Mutex::new(plugins_manager),
                 start_conditions: Arc::new(StartConditions::default()),
                 pending_connections: tokio::sync::Mutex::new(HashSet::new()),
+                static_neighbours,
+                draining: AtomicBool::new(false),
+                dampening,
             }),
         };
         *handler.runtime.write().unwrap() = Runtime::downgrade(&runtime);
@@ -221,6 +311,16 @@ impl RuntimeBuilder {
                                             tracing::error!("Error updating peers: {}", e);
                                         }
                                     }
+                                    if &*event == "listen/endpoints" {
+                                        if let Err(e) = runtime2.update_listeners().await {
+                                            tracing::error!("Error updating listeners: {}", e);
+                                        }
+                                    }
+                                    if &*event == "logging/filter" {
+                                        if let Err(e) = runtime2.update_log_filter() {
+                                            tracing::error!("Error updating log filter: {}", e);
+                                        }
+                                    }
                                 },
                                 None => { break; }
                             }
@@ -231,6 +331,11 @@ impl RuntimeBuilder {
             }
         });
 
+        if let Err(e) = runtime.update_log_filter() {
+            tracing::warn!("Unable to apply the log filter: {}", e);
+        }
+        runtime.start_config_watch();
+
         Ok(runtime)
     }
 }
@@ -328,6 +433,11 @@ impl Runtime {
         self.state.hlc.as_ref().map(Arc::as_ref)
     }
 
+    /// The flight recorder of the recent significant events of this runtime.
+    pub fn flight_recorder(&self) -> &Arc<FlightRecorder> {
+        &self.state.router.tables.flight_recorder
+    }
+
     pub fn zid(&self) -> ZenohId {
         self.state.zid
     }
@@ -371,6 +481,22 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
     ) -> ZResult<Arc<dyn TransportPeerEventHandler>> {
         match zread!(self.runtime).upgrade().as_ref() {
             Some(runtime) => {
+                if let Some(neighbours) = &runtime.state.static_neighbours {
+                    if !neighbours.contains(&peer.zid) {
+                        bail!("{} is not a neighbour of the static topology", peer.zid);
+                    }
+                }
+                if runtime.is_draining() {
+                    bail!("Refusing session with {}: draining", peer.zid);
+                }
+                if let Some(dampening) = &runtime.state.dampening {
+                    if dampening.is_suppressed(&peer.zid) {
+                        bail!(
+                            "Refusing session with {}: suppressed for flapping",
+                            peer.zid
+                        );
+                    }
+                }
                 let slave_handlers: Vec<Arc<dyn TransportPeerEventHandler>> =
                     zread!(runtime.state.transport_handlers)
                         .iter()
@@ -378,8 +504,16 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
                             handler.new_unicast(peer.clone(), transport.clone()).ok()
                         })
                         .collect();
+                zenoh_util::record_event!(
+                    runtime.flight_recorder(),
+                    Link,
+                    "Session opened with {zid} ({whatami})",
+                    zid = peer.zid,
+                    whatami = peer.whatami
+                );
                 Ok(Arc::new(RuntimeSession {
                     runtime: runtime.clone(),
+                    peer: peer.clone(),
                     endpoint: std::sync::RwLock::new(None),
                     main_handler: runtime
                         .state
@@ -399,6 +533,9 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
     ) -> ZResult<Arc<dyn TransportMulticastEventHandler>> {
         match zread!(self.runtime).upgrade().as_ref() {
             Some(runtime) => {
+                if runtime.state.static_neighbours.is_some() {
+                    bail!("Multicast transports are not allowed by the static topology");
+                }
                 let slave_handlers: Vec<Arc<dyn TransportMulticastEventHandler>> =
                     zread!(runtime.state.transport_handlers)
                         .iter()
@@ -421,6 +558,7 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
 
 pub(super) struct RuntimeSession {
     pub(super) runtime: Runtime,
+    pub(super) peer: TransportPeer,
     pub(super) endpoint: std::sync::RwLock<Option<EndPoint>>,
     pub(super) main_handler: Arc<DeMux>,
     pub(super) slave_handlers: Vec<Arc<dyn TransportPeerEventHandler>>,
@@ -428,10 +566,20 @@ pub(super) struct RuntimeSession {
 
 impl TransportPeerEventHandler for RuntimeSession {
     fn handle_message(&self, msg: NetworkMessage) -> ZResult<()> {
+        if self.peer.whatami == WhatAmI::Router && self.runtime.whatami() == WhatAmI::Client {
+            self.runtime.on_drain_announcement(&self.peer.zid, &msg);
+        }
         self.main_handler.handle_message(msg)
     }
 
     fn new_link(&self, link: Link) {
+        zenoh_util::record_event!(
+            self.runtime.flight_recorder(),
+            Link,
+            "Link {link} added to session with {zid}",
+            link,
+            zid = self.peer.zid
+        );
         self.main_handler.new_link(link.clone());
         for handler in &self.slave_handlers {
             handler.new_link(link.clone());
@@ -439,6 +587,13 @@ impl TransportPeerEventHandler for RuntimeSession {
     }
 
     fn del_link(&self, link: Link) {
+        zenoh_util::record_event!(
+            self.runtime.flight_recorder(),
+            Link,
+            "Link {link} removed from session with {zid}",
+            link,
+            zid = self.peer.zid
+        );
         self.main_handler.del_link(link.clone());
         for handler in &self.slave_handlers {
             handler.del_link(link.clone());
@@ -446,7 +601,18 @@ impl TransportPeerEventHandler for RuntimeSession {
     }
 
     fn closed(&self) {
+        zenoh_util::record_event!(
+            self.runtime.flight_recorder(),
+            Link,
+            "Session closed with {zid}",
+            zid = self.peer.zid
+        );
         self.main_handler.closed();
+        if let Some(dampening) = &self.runtime.state.dampening {
+            if self.peer.whatami != WhatAmI::Client && !self.runtime.is_closed() {
+                dampening.flap(&self.peer.zid);
+            }
+        }
         Runtime::closed_session(self);
         for handler in &self.slave_handlers {
             handler.closed();
@@ -456,6 +622,10 @@ impl TransportPeerEventHandler for RuntimeSession {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn dropped(&self, msg: NetworkMessage, reason: DropReason) {
+        self.main_handler.dropped(msg, reason);
+    }
 }
 
 pub(super) struct RuntimeMulticastGroup {
@@ -538,6 +708,7 @@ impl Closee for Arc<RuntimeState> {
         self.manager.close().await;
         // clean up to break cyclic reference of self.state to itself
         self.transport_handlers.write().unwrap().clear();
+        self.router.tables.dead_letters.clear();
         // TODO: the call below is needed to prevent intermittent leak
         // due to not freed resource Arc, that apparently happens because
         // the task responsible for resource clean up was aborted earlier than expected.
@@ -127,7 +127,7 @@ impl Runtime {
     }
 
     async fn start_client(&self) -> ZResult<()> {
-        let (peers, scouting, addr, ifaces, timeout, multicast_ttl) = {
+        let (peers, scouting, mdns, addr, ifaces, timeout, multicast_ttl) = {
             let guard = &self.state.config.lock().0;
             (
                 guard
@@ -137,6 +137,7 @@ impl Runtime {
                     .unwrap_or(&vec![])
                     .clone(),
                 unwrap_or_default!(guard.scouting().multicast().enabled()),
+                unwrap_or_default!(guard.scouting().mdns().enabled()),
                 unwrap_or_default!(guard.scouting().multicast().address()),
                 unwrap_or_default!(guard.scouting().multicast().interface()),
                 std::time::Duration::from_millis(unwrap_or_default!(guard.scouting().timeout())),
@@ -162,6 +163,10 @@ impl Runtime {
                                 .await
                         }
                     }
+                } else if mdns {
+                    tracing::info!("Scouting for router through mDNS ...");
+                    self.connect_first_mdns(WhatAmI::Router.into(), timeout)
+                        .await
                 } else {
                     bail!("No peer specified and multicast scouting deactivated!")
                 }
@@ -211,6 +216,8 @@ impl Runtime {
             self.start_scout(listen, autoconnect, addr, ifaces).await?;
         }
 
+        self.start_mdns().await?;
+
         if linkstate {
             tokio::time::sleep(delay).await;
         } else if wait_scouting
@@ -258,6 +265,8 @@ impl Runtime {
             self.start_scout(listen, autoconnect, addr, ifaces).await?;
         }
 
+        self.start_mdns().await?;
+
         tokio::time::sleep(delay).await;
         Ok(())
     }
@@ -463,6 +472,30 @@ impl Runtime {
         Ok(())
     }
 
+    pub(crate) async fn update_listeners(&self) -> ZResult<()> {
+        let listeners = {
+            self.state
+                .config
+                .lock()
+                .0
+                .listen()
+                .endpoints()
+                .get(self.state.whatami)
+                .unwrap_or(&vec![])
+                .clone()
+        };
+        let manager = self.manager();
+        let current = manager.get_listeners().await;
+        for listener in current.iter().filter(|l| !listeners.contains(l)) {
+            manager.del_listener(listener).await?;
+        }
+        let added: Vec<EndPoint> = listeners
+            .into_iter()
+            .filter(|l| !current.contains(l))
+            .collect();
+        self.bind_listeners_impl(&added).await
+    }
+
     fn get_listen_retry_config(&self, endpoint: &EndPoint) -> zenoh_config::ConnectionRetryConf {
         let guard = &self.state.config.lock().0;
         zenoh_config::get_retry_config(guard, Some(endpoint), true)
@@ -1191,6 +1224,10 @@ impl Runtime {
                 let cancellation_token = runtime.get_cancellation_token();
 
                 session.runtime.spawn(async move {
+                    // The client may have migrated to another router, e.g. from a draining one
+                    if !runtime.manager().get_transports_unicast().await.is_empty() {
+                        return;
+                    }
                     let retry_config = runtime.get_global_connect_retry_config();
                     let mut period = retry_config.period();
                     while runtime.start_client().await.is_err() {
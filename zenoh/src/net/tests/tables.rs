@@ -730,6 +730,9 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -739,6 +742,8 @@ fn client_test() {
                 ext_unknown: vec![],
                 payload: ZBuf::empty(),
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
             }),
         },
         Reliability::Reliable,
@@ -767,6 +772,9 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -776,6 +784,8 @@ fn client_test() {
                 ext_unknown: vec![],
                 payload: ZBuf::empty(),
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
             }),
         },
         Reliability::Reliable,
@@ -804,6 +814,9 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -813,6 +826,8 @@ fn client_test() {
                 ext_unknown: vec![],
                 payload: ZBuf::empty(),
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
             }),
         },
         Reliability::Reliable,
@@ -841,6 +856,9 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -850,6 +868,8 @@ fn client_test() {
                 ext_unknown: vec![],
                 payload: ZBuf::empty(),
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
             }),
         },
         Reliability::Reliable,
@@ -878,6 +898,9 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_hops: 0,
+            ext_hop_limit: None,
+            ext_deadline: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -887,6 +910,8 @@ fn client_test() {
                 ext_unknown: vec![],
                 payload: ZBuf::empty(),
                 ext_attachment: None,
+                ext_trace: None,
+                ext_signature: None,
             }),
         },
         Reliability::Reliable,
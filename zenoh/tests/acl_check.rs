@@ -0,0 +1,205 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use zenoh::{
+    config::{AclDecision, AclMessage, InterceptorFlow, Permission},
+    Config, Session, Wait,
+};
+
+fn config(default_permission: &str) -> Config {
+    let mut config = Config::default();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    config
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    enabled: true,
+                    default_permission: "{default_permission}",
+                    rules: [
+                        {{
+                            id: "allow-sensors",
+                            permission: "allow",
+                            flows: ["ingress"],
+                            messages: ["put", "declare_subscriber"],
+                            key_exprs: ["test/acl/sensors/**"],
+                        }},
+                        {{
+                            id: "deny-secret",
+                            permission: "deny",
+                            messages: ["put", "query"],
+                            key_exprs: ["test/acl/**/secret"],
+                        }},
+                    ],
+                    subjects: [
+                        {{ id: "alice", usernames: ["alice"] }},
+                    ],
+                    policies: [
+                        {{ rules: ["allow-sensors", "deny-secret"], subjects: ["alice"] }},
+                    ],
+                }}"#
+            ),
+        )
+        .unwrap();
+    config
+}
+
+fn open(default_permission: &str) -> Session {
+    zenoh::open(config(default_permission)).wait().unwrap()
+}
+
+fn check(
+    session: &Session,
+    message: AclMessage,
+    key_expr: &str,
+    username: Option<&str>,
+) -> AclDecision {
+    let mut check = session.check_acl(message, key_expr.to_string());
+    if let Some(username) = username {
+        check = check.username(username);
+    }
+    check.wait().unwrap()
+}
+
+#[test]
+fn acl_check_session() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = open("deny");
+    let decision = check(
+        &session,
+        AclMessage::Put,
+        "test/acl/sensors/temp",
+        Some("alice"),
+    );
+    assert_eq!(decision.permission, Permission::Allow);
+    assert_eq!(decision.rule.as_deref(), Some("allow-sensors"));
+
+    // The deny rules prevail over the allow rules
+    let decision = check(
+        &session,
+        AclMessage::Put,
+        "test/acl/sensors/secret",
+        Some("alice"),
+    );
+    assert_eq!(decision.permission, Permission::Deny);
+    assert_eq!(decision.rule.as_deref(), Some("deny-secret"));
+
+    // No rule matches: the default permission applies
+    let decision = check(
+        &session,
+        AclMessage::Query,
+        "test/acl/sensors/temp",
+        Some("alice"),
+    );
+    assert_eq!(decision.permission, Permission::Deny);
+    assert_eq!(decision.rule, None);
+    let decision = check(
+        &session,
+        AclMessage::Put,
+        "test/acl/sensors/temp",
+        Some("bob"),
+    );
+    assert_eq!(decision.permission, Permission::Deny);
+    assert_eq!(decision.rule, None);
+    let decision = check(&session, AclMessage::Put, "test/acl/sensors/temp", None);
+    assert_eq!(decision.permission, Permission::Deny);
+    assert_eq!(decision.rule, None);
+
+    // The allow rule only applies to the ingress messages
+    let decision = session
+        .check_acl(AclMessage::Put, "test/acl/sensors/temp")
+        .username("alice")
+        .flow(InterceptorFlow::Egress)
+        .wait()
+        .unwrap();
+    assert_eq!(decision.permission, Permission::Deny);
+    assert_eq!(decision.rule, None);
+
+    assert!(session
+        .check_acl(AclMessage::Put, "test/acl/invalid/")
+        .wait()
+        .is_err());
+    session.close().wait().unwrap();
+
+    // With an allow default permission, only the deny rules are reported
+    let session = open("allow");
+    let decision = check(
+        &session,
+        AclMessage::Put,
+        "test/acl/sensors/temp",
+        Some("alice"),
+    );
+    assert_eq!(decision.permission, Permission::Allow);
+    assert_eq!(decision.rule, None);
+    let decision = check(
+        &session,
+        AclMessage::Query,
+        "test/acl/other/secret",
+        Some("alice"),
+    );
+    assert_eq!(decision.permission, Permission::Deny);
+    assert_eq!(decision.rule.as_deref(), Some("deny-secret"));
+    session.close().wait().unwrap();
+}
+
+#[test]
+fn acl_check_adminspace() {
+    zenoh_util::init_log_from_env_or("error");
+    let acl_query = |session: &Session, parameters: &str| {
+        session
+            .get(format!("@/{}/peer/acl?{parameters}", session.zid()))
+            .wait()
+            .unwrap()
+            .recv()
+            .unwrap()
+            .into_result()
+    };
+
+    // The subject being chosen by the querier, the check requires the write permission
+    let session = open("deny");
+    assert!(acl_query(
+        &session,
+        "message=declare_subscriber;key_expr=test/acl/sensors/**;username=alice"
+    )
+    .is_err());
+    session.close().wait().unwrap();
+
+    let mut config = config("deny");
+    config
+        .insert_json5("adminspace/permissions", r#"{ read: true, write: true }"#)
+        .unwrap();
+    let session = zenoh::open(config).wait().unwrap();
+    let query = |parameters: &str| acl_query(&session, parameters);
+
+    let reply =
+        query("message=declare_subscriber;key_expr=test/acl/sensors/**;username=alice").unwrap();
+    let decision: serde_json::Value = serde_json::from_slice(&reply.payload().to_bytes()).unwrap();
+    assert_eq!(
+        decision,
+        serde_json::json!({ "permission": "allow", "rule": "allow-sensors" })
+    );
+
+    let reply =
+        query("message=put;key_expr=test/acl/sensors/temp;username=alice;flow=egress").unwrap();
+    let decision: serde_json::Value = serde_json::from_slice(&reply.payload().to_bytes()).unwrap();
+    assert_eq!(
+        decision,
+        serde_json::json!({ "permission": "deny", "rule": null })
+    );
+
+    assert!(query("key_expr=test/acl/sensors/temp").is_err());
+    assert!(query("message=publish;key_expr=test/acl/sensors/temp").is_err());
+    session.close().wait().unwrap();
+}
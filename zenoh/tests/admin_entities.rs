@@ -0,0 +1,127 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, qos::Priority, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn entities(session: &Session, selector: String) -> Vec<(String, serde_json::Value)> {
+    session
+        .get(selector)
+        .wait()
+        .unwrap()
+        .iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .map(|sample| {
+            let value = serde_json::from_slice(&sample.payload().to_bytes()).unwrap();
+            (sample.key_expr().to_string(), value)
+        })
+        .collect()
+}
+
+#[test]
+fn admin_space_entities() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38151";
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5("adminspace", r#"{ enabled: true }"#)
+        .unwrap();
+    let router = zenoh::open(router_config).wait().unwrap();
+
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+
+    let _sub = client
+        .declare_subscriber("test/entities/sub")
+        .wait()
+        .unwrap();
+    let _pub = client
+        .declare_publisher("test/entities/pub")
+        .priority(Priority::DataHigh)
+        .wait()
+        .unwrap();
+    let _qabl = client
+        .declare_queryable("test/entities/qabl")
+        .complete(true)
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    // The session lists its own entities along with their QoS and declaration time
+    let client_zid = client.zid().to_string();
+    let session_entities = entities(&client, format!("@/{client_zid}/session/entities/**"));
+    let publisher = session_entities
+        .iter()
+        .find(|(_, v)| v["key_expr"] == "test/entities/pub")
+        .unwrap();
+    assert!(publisher.0.contains("/session/entities/publisher/"));
+    assert_eq!(publisher.1["owner"], client_zid);
+    assert_eq!(publisher.1["qos"]["priority"], "DataHigh");
+    assert!(publisher.1["declared_at"].as_u64().unwrap() > 0);
+    let queryable = session_entities
+        .iter()
+        .find(|(_, v)| v["key_expr"] == "test/entities/qabl")
+        .unwrap();
+    assert_eq!(queryable.1["complete"], true);
+    assert!(session_entities
+        .iter()
+        .any(|(k, v)| k.contains("/subscriber/") && v["key_expr"] == "test/entities/sub"));
+
+    // The router lists the entities declared by the nodes it knows about, along with the QoS
+    // and reception time of the declarations of its neighbours
+    let router_entities = entities(&router, "@/*/router/entities/**".to_string());
+    for (kind, key_expr) in [
+        ("subscriber", "test/entities/sub"),
+        ("publisher", "test/entities/pub"),
+        ("queryable", "test/entities/qabl"),
+    ] {
+        let (_, entity) = router_entities
+            .iter()
+            .find(|(_, v)| {
+                v["kind"] == kind
+                    && v["key_expr"] == key_expr
+                    && v["owner"] == client_zid
+                    && v["owner_whatami"] == "client"
+            })
+            .unwrap_or_else(|| panic!("missing {kind} {key_expr} in {router_entities:?}"));
+        assert!(entity["qos"]["priority"].is_string(), "{entity}");
+        assert!(entity["qos"]["express"].is_boolean(), "{entity}");
+        assert!(entity["declared_at"].as_u64().unwrap() > 0, "{entity}");
+    }
+}
@@ -0,0 +1,60 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use zenoh::bytes::ZBytes;
+
+// A payload made of several slices, the lines spanning them
+fn payload() -> ZBytes {
+    let mut writer = ZBytes::writer();
+    writer.append(ZBytes::from("first\nsec"));
+    writer.append(ZBytes::from(""));
+    writer.append(ZBytes::from("ond\nthird"));
+    writer.finish()
+}
+
+#[test]
+fn bytes_reader_buf_read() {
+    use std::io::{BufRead, Read};
+
+    let payload = payload();
+    assert!(payload.slices().count() > 1);
+
+    let lines: Vec<String> = payload.reader().lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["first", "second", "third"]);
+
+    let mut reader = payload.reader();
+    let mut first = [0u8; 2];
+    reader.read_exact(&mut first).unwrap();
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "rst\nsecond\nthird");
+    assert!(reader.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn bytes_reader_async_read() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let payload = payload();
+
+    let mut lines = payload.reader().lines();
+    let mut read = vec![];
+    while let Some(line) = lines.next_line().await.unwrap() {
+        read.push(line);
+    }
+    assert_eq!(read, ["first", "second", "third"]);
+
+    let mut bytes = vec![];
+    payload.reader().read_to_end(&mut bytes).await.unwrap();
+    assert_eq!(bytes, payload.to_bytes().as_ref());
+}
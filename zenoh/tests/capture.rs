@@ -0,0 +1,115 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{
+    capture::{CaptureDirection, CaptureReader},
+    config::WhatAmI,
+    Config, Session, Wait,
+};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open(mode: WhatAmI, locator: &str, capture: Option<&str>) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    let endpoints = match mode {
+        WhatAmI::Router => &mut config.listen.endpoints,
+        _ => &mut config.connect.endpoints,
+    };
+    endpoints.set(vec![locator.parse().unwrap()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .capture
+        .set_file(capture.map(str::to_string))
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn capture_replay() {
+    zenoh_util::init_log_from_env_or("error");
+    let path = std::env::temp_dir().join(format!("zenoh-capture-{}.zcap", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    // Capture the publications routed by a router
+    let locator = "tcp/127.0.0.1:38271";
+    let router = open(WhatAmI::Router, locator, path.to_str());
+    let publisher = open(WhatAmI::Client, locator, None);
+    let subscriber = open(WhatAmI::Client, locator, None);
+    let sub = subscriber
+        .declare_subscriber("test/capture/**")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+    publisher.put("test/capture/a", "one").wait().unwrap();
+    publisher.put("test/capture/a", "two").wait().unwrap();
+    for payload in ["one", "two"] {
+        let sample = sub.recv_timeout(SLEEP).unwrap().unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), payload);
+    }
+    publisher.close().wait().unwrap();
+    subscriber.close().wait().unwrap();
+    router.close().wait().unwrap();
+
+    let messages = CaptureReader::open(&path)
+        .unwrap()
+        .collect::<zenoh::Result<Vec<_>>>()
+        .unwrap();
+    let pushes = |direction| {
+        messages
+            .iter()
+            .filter(|m| {
+                m.direction() == direction
+                    && m.kind() == "push"
+                    && m.key_expr() == Some("test/capture/a")
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(pushes(CaptureDirection::Ingress).len(), 2);
+    assert_eq!(pushes(CaptureDirection::Egress).len(), 2);
+    let push = pushes(CaptureDirection::Ingress)[0];
+    assert_eq!(push.zid(), publisher.zid());
+    assert_eq!(push.whatami(), WhatAmI::Client);
+    assert!(messages
+        .iter()
+        .any(|m| m.kind() == "declare" && m.zid() == subscriber.zid()));
+
+    // Replay the received publications into another router
+    let locator = "tcp/127.0.0.1:38272";
+    let router = open(WhatAmI::Router, locator, None);
+    let subscriber = open(WhatAmI::Client, locator, None);
+    let sub = subscriber
+        .declare_subscriber("test/capture/**")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+    assert_eq!(router.replay(&path).paced(true).wait().unwrap(), 2);
+    for payload in ["one", "two"] {
+        let sample = sub.recv_timeout(SLEEP).unwrap().unwrap();
+        assert_eq!(sample.key_expr().as_str(), "test/capture/a");
+        assert_eq!(sample.payload().try_to_string().unwrap(), payload);
+    }
+    let count = router
+        .replay(&path)
+        .direction(CaptureDirection::Egress)
+        .wait()
+        .unwrap();
+    assert_eq!(count, 2);
+
+    subscriber.close().wait().unwrap();
+    router.close().wait().unwrap();
+    let _ = std::fs::remove_file(&path);
+}
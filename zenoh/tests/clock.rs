@@ -0,0 +1,90 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use zenoh::{config::WhatAmI, time::NTP64, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const LOCATOR: &str = "tcp/127.0.0.1:38281";
+const HOUR: Duration = Duration::from_secs(3600);
+
+/// A clock one hour ahead of the system clock.
+fn future_clock() -> NTP64 {
+    (SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + HOUR).into()
+}
+
+fn open(mode: WhatAmI, clock: Option<fn() -> NTP64>) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    let endpoints = match mode {
+        WhatAmI::Router => &mut config.listen.endpoints,
+        _ => &mut config.connect.endpoints,
+    };
+    endpoints.set(vec![LOCATOR.parse().unwrap()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: true, max_delta_ms: 100 }"#)
+        .unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    let builder = zenoh::open(config);
+    match clock {
+        Some(clock) => builder.with_clock(clock).wait().unwrap(),
+        None => builder.wait().unwrap(),
+    }
+}
+
+#[test]
+fn clock_drift() {
+    zenoh_util::init_log_from_env_or("error");
+    let router = open(WhatAmI::Router, None);
+    let client = open(WhatAmI::Client, Some(future_clock));
+
+    // The client timestamps with its external clock
+    let clock = client.clock();
+    assert!(clock.enabled);
+    assert_eq!(clock.max_delta, Some(Duration::from_millis(100)));
+    let ahead =
+        clock.now.get_time().to_duration() - SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    assert!(ahead > HOUR - SLEEP && ahead <= HOUR);
+
+    // The router re-timestamps the samples from the future and reports them
+    let subscriber = router.declare_subscriber("test/clock").wait().unwrap();
+    std::thread::sleep(SLEEP);
+    assert_eq!(router.clock().drifts, 0);
+    client.put("test/clock", "future").wait().unwrap();
+    let sample = subscriber.recv_timeout(SLEEP).unwrap().unwrap();
+    let received = sample.timestamp().unwrap().get_time().to_duration();
+    assert!(received < SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + SLEEP);
+
+    let clock = router.clock();
+    assert_eq!(clock.drifts, 1);
+    let drift = clock.last_drift.unwrap();
+    assert!(!drift.dropped);
+    assert!(drift.timestamp.get_time() > drift.local.get_time());
+
+    let reply = router
+        .get(format!("@/{}/router/clock", router.zid()))
+        .wait()
+        .unwrap()
+        .recv()
+        .unwrap();
+    let clock: serde_json::Value =
+        serde_json::from_slice(&reply.result().unwrap().payload().to_bytes()).unwrap();
+    assert_eq!(clock["drifts"], 1);
+    assert_eq!(clock["max_delta_ms"], 100);
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -0,0 +1,132 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn router_config(file: &str, port: u16) -> String {
+    format!(
+        r#"{{
+            mode: "router",
+            listen: {{ endpoints: ["tcp/127.0.0.1:{port}"] }},
+            scouting: {{ multicast: {{ enabled: false }} }},
+            config_watch: {{ file: "{file}", period: 100 }},
+        }}"#
+    )
+}
+
+fn client_config(port: u16) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+#[test]
+fn config_watch_listen_endpoints() {
+    zenoh_util::init_log_from_env_or("error");
+    let path =
+        std::env::temp_dir().join(format!("zenoh-config-watch-{}.json5", std::process::id()));
+    let file = path.to_str().unwrap().to_string();
+    std::fs::write(&path, router_config(&file, 38181)).unwrap();
+
+    let router = zenoh::open(Config::from_file(&path).unwrap())
+        .wait()
+        .unwrap();
+    let client = zenoh::open(client_config(38181)).wait().unwrap();
+    client.close().wait().unwrap();
+
+    // The router listens on the new endpoint once the file changed
+    std::fs::write(&path, router_config(&file, 38182)).unwrap();
+    std::thread::sleep(SLEEP);
+    let client = zenoh::open(client_config(38182)).wait().unwrap();
+    assert!(client
+        .info()
+        .routers_zid()
+        .wait()
+        .any(|zid| zid == router.zid()));
+    client.close().wait().unwrap();
+
+    // The router no longer listens on the former endpoint
+    assert!(zenoh::open(client_config(38181)).wait().is_err());
+
+    router.close().wait().unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn config_watch_included_file() {
+    zenoh_util::init_log_from_env_or("error");
+    let dir = std::env::temp_dir().join(format!("zenoh-config-watch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("router.json5");
+    let included = dir.join("listen.json5");
+    let file = path.to_str().unwrap().to_string();
+    let write_included = |port: u16, filter: &str| {
+        std::fs::write(
+            &included,
+            format!(
+                r#"{{
+                    listen: {{ endpoints: ["tcp/127.0.0.1:{port}"] }},
+                    logging: {{ filter: "{filter}" }},
+                }}"#
+            ),
+        )
+        .unwrap()
+    };
+    write_included(38183, "error");
+    std::fs::write(
+        &path,
+        format!(
+            r#"{{
+                include: "listen.json5",
+                mode: "router",
+                scouting: {{ multicast: {{ enabled: false }} }},
+                config_watch: {{ file: "{file}", period: 100 }},
+            }}"#
+        ),
+    )
+    .unwrap();
+
+    let router = zenoh::open(Config::from_file(&path).unwrap())
+        .wait()
+        .unwrap();
+
+    // Changes of the included file are applied like those of the watched file
+    write_included(38184, "error,config_watch_test=trace");
+    std::thread::sleep(SLEEP);
+    let client = zenoh::open(client_config(38184)).wait().unwrap();
+    assert!(client
+        .info()
+        .routers_zid()
+        .wait()
+        .any(|zid| zid == router.zid()));
+    client.close().wait().unwrap();
+    assert!(tracing::enabled!(
+        target: "config_watch_test",
+        tracing::Level::TRACE
+    ));
+
+    router.close().wait().unwrap();
+    zenoh_util::set_log_filter("error").unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,141 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::{Duration, Instant};
+
+use zenoh::{
+    config::WhatAmI, pubsub::CongestionStatus, qos::CongestionControl, Config, Session, Wait,
+};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn open_router(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "transport/link/tx/queue/size",
+            r#"{
+                control: 1, real_time: 1, interactive_high: 1, interactive_low: 1,
+                data_high: 1, data: 1, data_low: 1, background: 1,
+            }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "transport/link/tx/queue/congestion_control/drop/wait_before_drop",
+            "1",
+        )
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn open_client(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn congestion_events_dropping() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38301";
+    let router = open_router(locator);
+    let client = open_client(locator);
+    // A slow subscriber congests the link from the router
+    let _subscriber = client
+        .declare_subscriber("test/congestion")
+        .callback(|_| std::thread::sleep(Duration::from_millis(100)))
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    let publisher = router
+        .declare_publisher("test/congestion")
+        .congestion_control(CongestionControl::Drop)
+        .wait()
+        .unwrap();
+    let events = publisher.congestion_events().wait().unwrap();
+
+    let payload = vec![0u8; 1024 * 1024];
+    for _ in 0..50 {
+        publisher.put(payload.clone()).wait().unwrap();
+    }
+    let event = events.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(event.status(), CongestionStatus::Dropping);
+    assert!(event.is_congested());
+    assert!(event.dropped() > 0);
+    assert_eq!(event.capacity(), 1);
+    assert_eq!(event.occupied(), 1);
+    // The status only changes once the queue is no longer congested
+    while let Ok(Some(event)) = events.try_recv() {
+        assert_ne!(event.status(), CongestionStatus::Dropping);
+    }
+
+    // Once the subscriber caught up, the publications are transmitted again
+    let start = Instant::now();
+    let event = loop {
+        publisher.put("small").wait().unwrap();
+        if let Some(event) = events.recv_timeout(Duration::from_millis(200)).unwrap() {
+            if !event.is_congested() {
+                break event;
+            }
+        }
+        assert!(
+            start.elapsed() < TIMEOUT,
+            "The publisher is still congested"
+        );
+    };
+    assert_eq!(event.status(), CongestionStatus::Clear);
+
+    events.undeclare().wait().unwrap();
+    publisher.undeclare().wait().unwrap();
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn congestion_events_local() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+    let _subscriber = session
+        .declare_subscriber("test/congestion/local")
+        .wait()
+        .unwrap();
+    let publisher = session
+        .declare_publisher("test/congestion/local")
+        .congestion_control(CongestionControl::Drop)
+        .wait()
+        .unwrap();
+    let events = publisher.congestion_events().wait().unwrap();
+    for _ in 0..10 {
+        publisher.put("value").wait().unwrap();
+    }
+    // The samples delivered to the local subscribers never congest
+    assert!(events.try_recv().unwrap().is_none());
+    session.close().wait().unwrap();
+}
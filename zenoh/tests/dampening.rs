@@ -0,0 +1,76 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_millis(500);
+
+fn open_flapping_peer(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config.set_id(Some("b".parse().unwrap())).unwrap();
+    config.listen.endpoints.set(vec![]).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn dampening() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38171";
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "routing/dampening",
+            r#"{ enabled: true, suppress_threshold: 1200, half_life: 60000 }"#,
+        )
+        .unwrap();
+    let peer = zenoh::open(config).wait().unwrap();
+
+    // The first flap does not suppress the peer
+    for _ in 0..2 {
+        let flapping = open_flapping_peer(locator);
+        std::thread::sleep(SLEEP);
+        assert!(peer
+            .info()
+            .peers_zid()
+            .wait()
+            .any(|zid| zid == flapping.zid()));
+        flapping.close().wait().unwrap();
+        std::thread::sleep(SLEEP);
+    }
+
+    // The second one does
+    let flapping = open_flapping_peer(locator);
+    std::thread::sleep(SLEEP);
+    assert!(!peer
+        .info()
+        .peers_zid()
+        .wait()
+        .any(|zid| zid == flapping.zid()));
+}
@@ -0,0 +1,146 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open_router(locator: &str, config: &[(&str, &str)]) -> Session {
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5("dead_letters", r#"{ enabled: true }"#)
+        .unwrap();
+    for (key, value) in config {
+        router_config.insert_json5(key, value).unwrap();
+    }
+    zenoh::open(router_config).wait().unwrap()
+}
+
+fn open_client(locator: &str) -> Session {
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    zenoh::open(client_config).wait().unwrap()
+}
+
+#[test]
+fn dead_letters_access_denied() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38211";
+    let router = open_router(
+        locator,
+        &[(
+            "access_control",
+            r#"{
+                enabled: true,
+                default_permission: "deny",
+                rules: [],
+                subjects: [],
+                policies: [],
+            }"#,
+        )],
+    );
+    let dead_letters = router
+        .declare_subscriber(format!("@/{}/router/dropped/**", router.zid()))
+        .wait()
+        .unwrap();
+    let client = open_client(locator);
+    std::thread::sleep(SLEEP);
+
+    client.put("test/dead_letters", "denied").wait().unwrap();
+    let sample = dead_letters.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(
+        sample.key_expr().as_str(),
+        format!(
+            "@/{}/router/dropped/access_denied/test/dead_letters",
+            router.zid()
+        )
+    );
+    assert_eq!(sample.payload().try_to_string().unwrap(), "denied");
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn dead_letters_congestion() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38212";
+    let router = open_router(
+        locator,
+        &[
+            (
+                "transport/link/tx/queue/size",
+                r#"{
+                    control: 1, real_time: 1, interactive_high: 1, interactive_low: 1,
+                    data_high: 1, data: 1, data_low: 1, background: 1,
+                }"#,
+            ),
+            (
+                "transport/link/tx/queue/congestion_control/drop/wait_before_drop",
+                "1",
+            ),
+        ],
+    );
+    let dead_letters = router
+        .declare_subscriber(format!(
+            "@/{}/router/dropped/congestion/test/dead_letters",
+            router.zid()
+        ))
+        .wait()
+        .unwrap();
+    let client = open_client(locator);
+    // A slow subscriber congests the link from the router
+    let _subscriber = client
+        .declare_subscriber("test/dead_letters")
+        .callback(|_| std::thread::sleep(Duration::from_millis(100)))
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    let payload = vec![0u8; 1024 * 1024];
+    for _ in 0..50 {
+        router
+            .put("test/dead_letters", payload.clone())
+            .wait()
+            .unwrap();
+    }
+    let sample = dead_letters.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().len(), payload.len());
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -0,0 +1,89 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use zenoh::{
+    query::{ConsolidationMode, QueryConsolidation, Queryable},
+    time::Timestamp,
+    Config, Session, Wait,
+};
+
+fn declare_replica(
+    session: &Session,
+    key_expr: &'static str,
+    payload: &'static str,
+    timestamp: Option<Timestamp>,
+) -> Queryable<()> {
+    session
+        .declare_queryable(key_expr)
+        .callback(move |query| {
+            query
+                .reply(key_expr, payload)
+                .timestamp(timestamp)
+                .wait()
+                .unwrap();
+        })
+        .wait()
+        .unwrap()
+}
+
+fn get(session: &Session, key_expr: &str, deduplication: bool) -> Vec<String> {
+    let consolidation =
+        QueryConsolidation::from(ConsolidationMode::None).with_deduplication(deduplication);
+    let mut replies: Vec<String> = session
+        .get(key_expr)
+        .consolidation(consolidation)
+        .wait()
+        .unwrap()
+        .iter()
+        .map(|reply| {
+            reply
+                .result()
+                .unwrap()
+                .payload()
+                .try_to_string()
+                .unwrap()
+                .into_owned()
+        })
+        .collect();
+    replies.sort();
+    replies
+}
+
+#[test]
+fn reply_deduplication() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+
+    // Byte-identical replies
+    let key_expr = "test/deduplication/payload";
+    let _qabl1 = declare_replica(&session, key_expr, "value", None);
+    let _qabl2 = declare_replica(&session, key_expr, "value", None);
+    let _qabl3 = declare_replica(&session, key_expr, "other", None);
+    assert_eq!(get(&session, key_expr, false), ["other", "value", "value"]);
+    assert_eq!(session.metrics().wait().duplicate_replies, 0);
+    assert_eq!(get(&session, key_expr, true), ["other", "value"]);
+    assert_eq!(session.metrics().wait().duplicate_replies, 1);
+
+    // Same-timestamp replies
+    let key_expr = "test/deduplication/timestamp";
+    let timestamp = session.new_timestamp();
+    let _qabl4 = declare_replica(&session, key_expr, "value1", Some(timestamp));
+    let _qabl5 = declare_replica(&session, key_expr, "value2", Some(timestamp));
+    let _qabl6 = declare_replica(&session, key_expr, "value3", Some(session.new_timestamp()));
+    assert_eq!(get(&session, key_expr, false).len(), 3);
+    assert_eq!(get(&session, key_expr, true).len(), 2);
+    assert_eq!(session.metrics().wait().duplicate_replies, 2);
+
+    session.close().wait().unwrap();
+}
@@ -0,0 +1,123 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open_router(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "adminspace",
+            r#"{ enabled: true, permissions: { read: true, write: true } }"#,
+        )
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn client_config(locator: &str) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    // Allows the client to migrate on the drain announcements
+    config
+        .insert_json5("adminspace/permissions/write", "true")
+        .unwrap();
+    config
+}
+
+#[test]
+fn router_drain() {
+    zenoh_util::init_log_from_env_or("error");
+    let (locator01, locator02) = ("tcp/127.0.0.1:38161", "tcp/127.0.0.1:38162");
+    let router01 = open_router(locator01);
+    let router02 = open_router(locator02);
+    let client = zenoh::open(client_config(locator01)).wait().unwrap();
+    std::thread::sleep(SLEEP);
+    assert_eq!(
+        client.info().routers_zid().wait().collect::<Vec<_>>(),
+        vec![router01.zid()]
+    );
+
+    router01
+        .put(
+            format!("@/{}/router/drain", router01.zid()),
+            format!(r#"{{ alternatives: ["{locator02}"], timeout: 5000 }}"#),
+        )
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    // The draining router refuses new sessions
+    assert!(zenoh::open(client_config(locator01)).wait().is_err());
+
+    // Its clients migrate to the alternative, after which it shuts down
+    std::thread::sleep(SLEEP);
+    assert_eq!(
+        client.info().routers_zid().wait().collect::<Vec<_>>(),
+        vec![router02.zid()]
+    );
+    assert!(router01.is_closed());
+    assert!(!router02.is_closed());
+}
+
+#[test]
+fn router_drain_forged_announcement() {
+    zenoh_util::init_log_from_env_or("error");
+    let (locator01, locator02) = ("tcp/127.0.0.1:38163", "tcp/127.0.0.1:38164");
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator01.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let router01 = zenoh::open(config).wait().unwrap();
+    let router02 = open_router(locator02);
+    let client = zenoh::open(client_config(locator01)).wait().unwrap();
+    let key_expr = format!("@/{}/router/drain", router01.zid());
+    let subscriber = client.declare_subscriber(&key_expr).wait().unwrap();
+    let stranger = zenoh::open(client_config(locator01)).wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    // The announcements published by other nodes on the drain key expression of the router are
+    // not routed to its clients
+    stranger
+        .put(&key_expr, format!(r#"{{ alternatives: ["{locator02}"] }}"#))
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+    assert!(subscriber.try_recv().unwrap().is_none());
+    assert_eq!(
+        client.info().routers_zid().wait().collect::<Vec<_>>(),
+        vec![router01.zid()]
+    );
+    assert!(!router02.is_closed());
+}
@@ -0,0 +1,142 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{
+    config::WhatAmI,
+    sample::SampleKind,
+    session::{OpenBuilder, SubtreeKeyProvider},
+    Config, Session, Wait,
+};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn client(locator: &str) -> OpenBuilder<Config> {
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    zenoh::open(client_config)
+}
+
+fn provider(secret: &[u8]) -> SubtreeKeyProvider {
+    SubtreeKeyProvider::new()
+        .subtree("test/encryption/secret/**", secret)
+        .unwrap()
+}
+
+#[test]
+fn encryption_end_to_end() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38221";
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    let router: Session = zenoh::open(router_config).wait().unwrap();
+    let publisher = client(locator)
+        .with_key_provider(provider(b"secret"))
+        .wait()
+        .unwrap();
+    let subscriber = client(locator)
+        .with_key_provider(provider(b"secret"))
+        .wait()
+        .unwrap();
+    let intruder = client(locator)
+        .with_key_provider(provider(b"other"))
+        .wait()
+        .unwrap();
+
+    let router_sub = router
+        .declare_subscriber("test/encryption/**")
+        .wait()
+        .unwrap();
+    let sub = subscriber
+        .declare_subscriber("test/encryption/**")
+        .wait()
+        .unwrap();
+    let intruder_sub = intruder
+        .declare_subscriber("test/encryption/**")
+        .wait()
+        .unwrap();
+    let local_sub = publisher
+        .declare_subscriber("test/encryption/**")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    // The samples of the subtree are encrypted end-to-end
+    publisher
+        .put("test/encryption/secret/a", "plaintext")
+        .attachment("metadata")
+        .wait()
+        .unwrap();
+    let sample = sub.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "plaintext");
+    assert_eq!(
+        sample.attachment().unwrap().try_to_string().unwrap(),
+        "metadata"
+    );
+    let sample = local_sub.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "plaintext");
+    let sample = router_sub.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/encryption/secret/a");
+    assert_ne!(sample.payload().to_bytes().as_ref(), b"plaintext");
+    assert_ne!(
+        sample.attachment().unwrap().to_bytes().as_ref(),
+        b"metadata"
+    );
+    // A session with another key drops them
+    assert!(intruder_sub.recv_timeout(SLEEP).unwrap().is_none());
+
+    // The deletions are delivered
+    publisher.delete("test/encryption/secret/a").wait().unwrap();
+    let sample = sub.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.kind(), SampleKind::Delete);
+    let _ = local_sub.recv_timeout(SLEEP).unwrap().unwrap();
+    let _ = router_sub.recv_timeout(SLEEP).unwrap().unwrap();
+    let _ = intruder_sub.recv_timeout(SLEEP).unwrap().unwrap();
+
+    // The samples out of the subtree are sent in clear
+    publisher
+        .put("test/encryption/public", "plaintext")
+        .wait()
+        .unwrap();
+    for sub in [&sub, &router_sub, &intruder_sub] {
+        let sample = sub.recv_timeout(SLEEP).unwrap().unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), "plaintext");
+    }
+
+    publisher.close().wait().unwrap();
+    subscriber.close().wait().unwrap();
+    intruder.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
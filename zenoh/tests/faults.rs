@@ -0,0 +1,100 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "fault_injection", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const LOCATOR: &str = "tcp/127.0.0.1:38261";
+
+fn open(mode: WhatAmI) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    let endpoints = match mode {
+        WhatAmI::Router => &mut config.listen.endpoints,
+        _ => &mut config.connect.endpoints,
+    };
+    endpoints.set(vec![LOCATOR.parse().unwrap()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "adminspace",
+            r#"{ enabled: true, permissions: { read: true, write: true } }"#,
+        )
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn fault_injection() {
+    zenoh_util::init_log_from_env_or("error");
+    let router = open(WhatAmI::Router);
+    let client = open(WhatAmI::Client);
+    let router_faults = format!("@/{}/router/faults", router.zid());
+    let client_faults = format!("@/{}/client/faults", client.zid());
+    let router_sub = router
+        .declare_subscriber("test/faults/client")
+        .wait()
+        .unwrap();
+    let client_sub = client
+        .declare_subscriber("test/faults/router")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    // All the batches sent by the router are dropped
+    router.put(&router_faults, "{ drop: 1.0 }").wait().unwrap();
+    std::thread::sleep(SLEEP);
+    router.put("test/faults/router", "dropped").wait().unwrap();
+    assert!(client_sub.recv_timeout(SLEEP).unwrap().is_none());
+    let reply = router.get(&router_faults).wait().unwrap().recv().unwrap();
+    let faults: serde_json::Value =
+        serde_json::from_slice(&reply.result().unwrap().payload().to_bytes()).unwrap();
+    assert_eq!(faults[0]["drop"], 1.0);
+    assert_eq!(faults[0]["link"], serde_json::Value::Null);
+    router.delete(&router_faults).wait().unwrap();
+    std::thread::sleep(SLEEP);
+    router.put("test/faults/router", "received").wait().unwrap();
+    let sample = client_sub.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().to_bytes(), b"received".as_slice());
+
+    // The batches sent by the client to the router are delayed
+    client
+        .put(
+            &client_faults,
+            format!(r#"{{ link: "{LOCATOR}", delay_ms: 1000 }}"#),
+        )
+        .wait()
+        .unwrap();
+    client.put("test/faults/client", "delayed").wait().unwrap();
+    assert!(router_sub
+        .recv_timeout(Duration::from_millis(500))
+        .unwrap()
+        .is_none());
+    let sample = router_sub.recv_timeout(3 * SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().to_bytes(), b"delayed".as_slice());
+
+    // Invalid faults are ignored
+    client.delete(&client_faults).wait().unwrap();
+    client.put(&client_faults, "{ drop: 2.0 }").wait().unwrap();
+    let reply = client.get(&client_faults).wait().unwrap().recv().unwrap();
+    assert_eq!(
+        reply.result().unwrap().payload().to_bytes(),
+        b"[]".as_slice()
+    );
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -0,0 +1,103 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+#[test]
+fn flight_recorder_admin_space() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38181";
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5("adminspace", r#"{ enabled: true }"#)
+        .unwrap();
+    let router = zenoh::open(router_config).wait().unwrap();
+
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+    let client_zid = client.zid().to_string();
+
+    let sub = client
+        .declare_subscriber("test/flight_recorder")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+    sub.undeclare().wait().unwrap();
+    client.close().wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    let replies = router
+        .get(format!("@/{}/router/flight_recorder", router.zid()))
+        .wait()
+        .unwrap()
+        .iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .collect::<Vec<_>>();
+    assert_eq!(replies.len(), 1);
+    let events: Vec<serde_json::Value> =
+        serde_json::from_slice(&replies[0].payload().to_bytes()).unwrap();
+    let message = |event: &serde_json::Value| event["message"].as_str().unwrap().to_string();
+    let position = |kind: &str, pattern: &str| {
+        events
+            .iter()
+            .position(|event| event["kind"] == kind && message(event).contains(pattern))
+            .unwrap_or_else(|| panic!("missing {kind} event `{pattern}` in {events:#?}"))
+    };
+    let opened = position("link", &format!("Session opened with {client_zid}"));
+    // Each runtime has its own recorder: the router records the declaration of the client with
+    // the wire expression it received, "Face{<id>, <zid>} declared subscriber <id> on <wire_expr>"
+    let declared = position(
+        "declaration",
+        &format!("{client_zid}}} declared subscriber"),
+    );
+    let subscriber = message(&events[declared])
+        .split(' ')
+        .nth(4)
+        .unwrap()
+        .to_string();
+    let undeclared = position(
+        "declaration",
+        &format!("{client_zid}}} undeclared subscriber {subscriber}"),
+    );
+    let closed = position("link", &format!("Session closed with {client_zid}"));
+    assert!(opened < closed && declared < undeclared && undeclared < closed);
+
+    router.close().wait().unwrap();
+}
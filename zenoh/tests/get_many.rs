@@ -0,0 +1,58 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{sample::Locality, Config, Wait};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[test]
+fn get_many() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+    let _queryable = session
+        .declare_queryable("test/get_many/*")
+        .callback(|query| {
+            let key_expr = query.key_expr().clone();
+            query.reply(&key_expr, key_expr.as_str()).wait().unwrap();
+        })
+        .wait()
+        .unwrap();
+
+    let selectors = ["test/get_many/a", "test/get_many/b", "test/get_many/c"];
+    let replies = session
+        .get_many(selectors)
+        .allowed_destination(Locality::SessionLocal)
+        .timeout(TIMEOUT)
+        .wait()
+        .unwrap();
+    let mut received = vec![];
+    // The handler is dropped once all the queries are finalized
+    while let Ok(reply) = replies.recv() {
+        let sample = reply.reply().result().unwrap();
+        assert_eq!(sample.key_expr().as_str(), selectors[reply.tag()]);
+        received.push(reply.tag());
+    }
+    received.sort_unstable();
+    assert_eq!(received, [0, 1, 2]);
+
+    // No query is issued if a selector is invalid
+    assert!(session
+        .get_many(["test/get_many/a", "test/**/**"])
+        .wait()
+        .is_err());
+
+    session.close().wait().unwrap();
+}
@@ -0,0 +1,79 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, session::ZenohId, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(2);
+
+fn open_peer(listen: &str, connect: &[&str], gossip: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![listen.parse().unwrap()])
+        .unwrap();
+    config
+        .connect
+        .endpoints
+        .set(connect.iter().map(|c| c.parse().unwrap()).collect())
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.insert_json5("scouting/gossip", gossip).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn peers(session: &Session) -> Vec<ZenohId> {
+    session.info().peers_zid().wait().collect()
+}
+
+#[test]
+fn gossip_autoconnect_filter() {
+    zenoh_util::init_log_from_env_or("error");
+    let hub = "tcp/127.0.0.1:38121";
+    let _hub = open_peer(hub, &[], "{}");
+    let labelled = open_peer(
+        "tcp/127.0.0.1:38122",
+        &[hub],
+        r#"{ labels: { zone: "a" }, autoconnect_filter: { labels: { zone: "none" } } }"#,
+    );
+    std::thread::sleep(SLEEP);
+
+    // Discovered peers advertising the filtered labels in the filtered subnets get connected
+    let matching = open_peer(
+        "tcp/127.0.0.1:38123",
+        &[hub],
+        r#"{ autoconnect_filter: { labels: { zone: "a" }, subnets: ["127.0.0.0/8"] } }"#,
+    );
+    // Discovered peers advertising other labels do not
+    let other_label = open_peer(
+        "tcp/127.0.0.1:38124",
+        &[hub],
+        r#"{ autoconnect_filter: { labels: { zone: "b" } } }"#,
+    );
+    // Discovered peers without locators in the filtered subnets do not
+    let other_subnet = open_peer(
+        "tcp/127.0.0.1:38125",
+        &[hub],
+        r#"{ autoconnect_filter: { subnets: ["10.0.0.0/8"] } }"#,
+    );
+    std::thread::sleep(SLEEP);
+
+    let labelled_zid = labelled.zid();
+    assert!(peers(&matching).contains(&labelled_zid));
+    assert!(!peers(&other_label).contains(&labelled_zid));
+    assert!(!peers(&other_subnet).contains(&labelled_zid));
+}
@@ -64,3 +64,53 @@ fn query_with_ringbuffer() {
     // Only receive the latest query
     assert_eq!(query.payload().unwrap().try_to_string().unwrap(), "query2");
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn pubsub_with_poll_channel() {
+    use zenoh::handlers::PollChannel;
+
+    let zenoh = zenoh::open(Config::default()).wait().unwrap();
+    let sub = zenoh
+        .declare_subscriber("test/poll")
+        .with(PollChannel::new(3))
+        .wait()
+        .unwrap();
+    assert!(sub.try_recv().is_none());
+    for i in 0..5 {
+        zenoh.put("test/poll", format!("put{i}")).wait().unwrap();
+    }
+    // The channel keeps the first three samples and drops the newer ones
+    assert_eq!(sub.len(), 3);
+    assert_eq!(sub.dropped(), 2);
+    for i in 0..3 {
+        assert_eq!(
+            sub.try_recv().unwrap().payload().try_to_string().unwrap(),
+            format!("put{i}")
+        );
+    }
+    assert!(sub.is_empty());
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn poll_channel_concurrent_producer() {
+    use zenoh::handlers::{IntoHandler, PollChannel};
+
+    const COUNT: usize = 100_000;
+    let (callback, handler) = IntoHandler::<usize>::into_handler(PollChannel::new(16));
+    let producer = thread::spawn(move || {
+        for i in 0..COUNT {
+            callback.call(i);
+        }
+    });
+    let mut received = Vec::new();
+    while !producer.is_finished() || !handler.is_empty() {
+        if let Some(i) = handler.try_recv() {
+            received.push(i);
+        }
+    }
+    producer.join().unwrap();
+    assert_eq!(received.len() + handler.dropped(), COUNT);
+    assert!(received.windows(2).all(|w| w[0] < w[1]));
+}
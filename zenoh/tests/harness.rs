@@ -0,0 +1,119 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "test_harness")]
+use std::time::Duration;
+
+use zenoh::{
+    handlers::FifoChannelHandler,
+    pubsub::Subscriber,
+    sample::Sample,
+    test::{MockSession, Network},
+    Session, Wait,
+};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Put until the subscriber receives a sample, or fail after `TIMEOUT`.
+fn put_until_received(publisher: &Session, subscriber: &Subscriber<FifoChannelHandler<Sample>>) {
+    let start = std::time::Instant::now();
+    while start.elapsed() < TIMEOUT {
+        publisher.put("test/harness", "data").wait().unwrap();
+        if subscriber
+            .recv_timeout(Duration::from_millis(100))
+            .unwrap()
+            .is_some()
+        {
+            while subscriber.try_recv().unwrap().is_some() {}
+            return;
+        }
+    }
+    panic!("No sample received after {TIMEOUT:?}");
+}
+
+#[test]
+fn harness_faults() {
+    zenoh_util::init_log_from_env_or("error");
+    let network = Network::new(42);
+    let r1 = network.router("r1").wait().unwrap();
+    let r2 = network.router("r2").connect("r1").wait().unwrap();
+    let c1 = network.client("c1").connect("r1").wait().unwrap();
+    let c2 = network.client("c2").connect("r2").wait().unwrap();
+    assert!(network.client("c3").connect("c1").wait().is_err());
+    assert!(network.client("c3").connect("unknown").wait().is_err());
+
+    let subscriber = c2.declare_subscriber("test/harness").wait().unwrap();
+    put_until_received(&c1, &subscriber);
+
+    // Partitioned routers no longer exchange samples until they are healed
+    network.partition("r1", "r2");
+    std::thread::sleep(SLEEP);
+    c1.put("test/harness", "data").wait().unwrap();
+    assert!(subscriber.recv_timeout(SLEEP).unwrap().is_none());
+    network.heal("r1", "r2");
+    put_until_received(&c1, &subscriber);
+
+    // All the batches are lost
+    network.set_loss("r2", "r1", 1.0);
+    c1.put("test/harness", "data").wait().unwrap();
+    assert!(subscriber.recv_timeout(SLEEP).unwrap().is_none());
+    network.set_loss("r2", "r1", 0.0);
+    put_until_received(&c1, &subscriber);
+
+    // Delayed batches are received after the delay
+    network.set_delay("c1", "r1", SLEEP);
+    c1.put("test/harness", "data").wait().unwrap();
+    assert!(subscriber
+        .recv_timeout(Duration::from_millis(500))
+        .unwrap()
+        .is_none());
+    assert!(subscriber.recv_timeout(SLEEP).unwrap().is_some());
+    network.set_delay("c1", "r1", Duration::ZERO);
+
+    c1.close().wait().unwrap();
+    c2.close().wait().unwrap();
+    r2.close().wait().unwrap();
+    r1.close().wait().unwrap();
+}
+
+#[test]
+fn mock_session() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = MockSession::open().wait().unwrap();
+    session.reply("test/mock/config", "10").unwrap();
+    session.reply_err("test/mock/error", "denied").unwrap();
+
+    let replies = session.get("test/mock/*").wait().unwrap();
+    let mut results = vec![];
+    while let Ok(reply) = replies.recv() {
+        results.push(match reply.result() {
+            Ok(sample) => sample.payload().try_to_string().unwrap().into_owned(),
+            Err(err) => err.payload().try_to_string().unwrap().into_owned(),
+        });
+    }
+    results.sort();
+    assert_eq!(results, ["10", "denied"]);
+    assert_eq!(session.queries().len(), 2);
+    assert_eq!(session.queries()[0].key_expr().as_str(), "test/mock/*");
+
+    session.put("test/mock/status", "ok").wait().unwrap();
+    session.delete("test/mock/status").wait().unwrap();
+    session.expect_published("test/mock/status", "ok");
+    assert_eq!(session.published().len(), 2);
+
+    session.clear();
+    assert!(session.published().is_empty());
+    assert!(session.queries().is_empty());
+    session.close().wait().unwrap();
+}
@@ -0,0 +1,100 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, handlers::FifoChannelHandler, sample::Sample, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn open(mode: WhatAmI, locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    let endpoints = if mode == WhatAmI::Router {
+        &mut config.listen.endpoints
+    } else {
+        &mut config.connect.endpoints
+    };
+    endpoints.set(vec![locator.parse().unwrap()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn recv(subscriber: &zenoh::pubsub::Subscriber<FifoChannelHandler<Sample>>) -> Option<String> {
+    subscriber
+        .recv_timeout(SLEEP)
+        .unwrap()
+        .map(|s| s.payload().try_to_string().unwrap().into_owned())
+}
+
+#[test]
+fn hop_limit() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38312";
+    let router = open(WhatAmI::Router, locator);
+    let publisher = open(WhatAmI::Client, locator);
+    let client = open(WhatAmI::Client, locator);
+    let local_sub = publisher.declare_subscriber("test/hops").wait().unwrap();
+    let router_sub = router.declare_subscriber("test/hops").wait().unwrap();
+    let client_sub = client.declare_subscriber("test/hops").wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    // The data stays in the publishing session
+    publisher.put("test/hops", "0").hop_limit(0).wait().unwrap();
+    assert_eq!(
+        local_sub
+            .recv_timeout(TIMEOUT)
+            .unwrap()
+            .unwrap()
+            .payload()
+            .try_to_string()
+            .unwrap(),
+        "0"
+    );
+    assert_eq!(recv(&router_sub), None);
+    assert_eq!(recv(&client_sub), None);
+
+    // The data reaches the router, which does not forward it
+    let limited = publisher
+        .declare_publisher("test/hops")
+        .hop_limit(1)
+        .wait()
+        .unwrap();
+    assert_eq!(limited.hop_limit(), Some(1));
+    limited.put("1").wait().unwrap();
+    assert_eq!(recv(&local_sub).as_deref(), Some("1"));
+    assert_eq!(recv(&router_sub).as_deref(), Some("1"));
+    assert_eq!(recv(&client_sub), None);
+
+    // The data reaches the other client through the router
+    publisher.put("test/hops", "2").hop_limit(2).wait().unwrap();
+    assert_eq!(recv(&local_sub).as_deref(), Some("2"));
+    assert_eq!(recv(&router_sub).as_deref(), Some("2"));
+    assert_eq!(
+        client_sub
+            .recv_timeout(TIMEOUT)
+            .unwrap()
+            .unwrap()
+            .payload()
+            .try_to_string()
+            .unwrap(),
+        "2"
+    );
+
+    limited.undeclare().wait().unwrap();
+    client.close().wait().unwrap();
+    publisher.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -0,0 +1,147 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use jsonwebtoken::{EncodingKey, Header};
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const SECRET: &[u8] = b"zenoh-jwt-secret";
+const ISSUER: &str = "https://auth.zenoh.test";
+
+fn jwks_file() -> PathBuf {
+    let path = std::env::temp_dir().join("zenoh-test-auth-jwks.json");
+    // The `k` parameter is the base64url encoding of SECRET
+    std::fs::write(
+        &path,
+        r#"{"keys": [{"kty": "oct", "kid": "test", "alg": "HS256", "k": "emVub2gtand0LXNlY3JldA"}]}"#,
+    )
+    .unwrap();
+    path
+}
+
+fn token(secret: &[u8], claims: serde_json::Value) -> String {
+    let header = Header {
+        kid: Some("test".to_string()),
+        ..Default::default()
+    };
+    jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(secret)).unwrap()
+}
+
+fn claims(sub: &str, iss: &str, lifetime: i64) -> serde_json::Value {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    serde_json::json!({"sub": sub, "iss": iss, "exp": now + lifetime})
+}
+
+fn open_router(locator: &str) -> Session {
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5(
+            "transport/auth/jwt",
+            &format!(
+                r#"{{ jwks_file: "{}", issuer: "{ISSUER}" }}"#,
+                jwks_file().display()
+            ),
+        )
+        .unwrap();
+    router_config
+        .insert_json5(
+            "access_control",
+            r#"{
+                enabled: true,
+                default_permission: "deny",
+                rules: [
+                    {
+                        id: "r1",
+                        permission: "allow",
+                        flows: ["ingress", "egress"],
+                        messages: ["put", "declare_subscriber"],
+                        key_exprs: ["test/jwt/**"],
+                    },
+                ],
+                subjects: [{ id: "s1", usernames: ["alice"] }],
+                policies: [{ rules: ["r1"], subjects: ["s1"] }],
+            }"#,
+        )
+        .unwrap();
+    zenoh::open(router_config).wait().unwrap()
+}
+
+fn open_client(locator: &str, token: Option<String>) -> zenoh::Result<Session> {
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    client_config.transport.auth.jwt.set_token(token).unwrap();
+    zenoh::open(client_config).wait()
+}
+
+#[test]
+fn jwt_authentication() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38241";
+    let router = open_router(locator);
+
+    // Sessions without a valid token are refused
+    assert!(open_client(locator, None).is_err());
+    assert!(open_client(locator, Some(token(b"other", claims("alice", ISSUER, 60)))).is_err());
+    assert!(open_client(locator, Some(token(SECRET, claims("alice", ISSUER, -120)))).is_err());
+    assert!(open_client(
+        locator,
+        Some(token(SECRET, claims("alice", "https://other.test", 60)))
+    )
+    .is_err());
+
+    // The username claim of the token is matched by the access control
+    let alice = open_client(locator, Some(token(SECRET, claims("alice", ISSUER, 60)))).unwrap();
+    let mallory = open_client(locator, Some(token(SECRET, claims("mallory", ISSUER, 60)))).unwrap();
+    let subscriber = router.declare_subscriber("test/jwt/**").wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    mallory.put("test/jwt/mallory", "denied").wait().unwrap();
+    alice.put("test/jwt/alice", "allowed").wait().unwrap();
+    let sample = subscriber.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/jwt/alice");
+    assert!(subscriber.try_recv().unwrap().is_none());
+
+    alice.close().wait().unwrap();
+    mallory.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
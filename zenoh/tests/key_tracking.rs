@@ -0,0 +1,59 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use zenoh::{Config, Wait};
+
+#[test]
+fn subscriber_key_tracking() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+    let subscriber = session
+        .declare_subscriber("test/tracking/**")
+        .track_keys(2)
+        .wait()
+        .unwrap();
+    let untracked = session
+        .declare_subscriber("test/tracking/**")
+        .wait()
+        .unwrap();
+    let tracked = || -> Vec<String> {
+        subscriber
+            .tracked_keys()
+            .iter()
+            .map(|k| k.as_str().to_string())
+            .collect()
+    };
+
+    session.put("test/tracking/a", "a").wait().unwrap();
+    session.put("test/tracking/b", "b").wait().unwrap();
+    session.put("test/tracking/a", "a").wait().unwrap();
+    assert_eq!(tracked(), vec!["test/tracking/a", "test/tracking/b"]);
+
+    // The least recently received key is evicted
+    session.put("test/tracking/c", "c").wait().unwrap();
+    assert_eq!(tracked(), vec!["test/tracking/c", "test/tracking/a"]);
+    assert_eq!(subscriber.tracked_key_count(), 2);
+
+    // A deleted key is no longer tracked
+    session.delete("test/tracking/a").wait().unwrap();
+    assert_eq!(tracked(), vec!["test/tracking/c"]);
+    assert_eq!(subscriber.tracked_key_count(), 1);
+
+    assert!(untracked.tracked_keys().is_empty());
+    assert_eq!(untracked.tracked_key_count(), 0);
+    // The samples are still delivered
+    assert_eq!(subscriber.drain().count(), 5);
+
+    session.close().wait().unwrap();
+}
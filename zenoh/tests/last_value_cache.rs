@@ -0,0 +1,115 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+
+fn open_router(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "routing/router/last_value_cache",
+            r#"{ key_exprs: ["test/lvc/cached/**"] }"#,
+        )
+        .unwrap();
+    // The cached samples are subject to the egress access control of the late joiner
+    config
+        .insert_json5(
+            "access_control",
+            r#"{
+                enabled: true,
+                default_permission: "allow",
+                rules: [
+                    {
+                        id: "secret",
+                        permission: "deny",
+                        flows: ["egress"],
+                        messages: ["put", "reply"],
+                        key_exprs: ["test/lvc/cached/secret"],
+                    },
+                ],
+                subjects: [{ id: "late_joiner", zids: ["a1b2"] }],
+                policies: [{ rules: ["secret"], subjects: ["late_joiner"] }],
+            }"#,
+        )
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn open_client(locator: &str, id: Option<&str>) -> Session {
+    let mut config = Config::default();
+    if let Some(id) = id {
+        config.set_id(Some(id.parse().unwrap())).unwrap();
+    }
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn last_value_cache() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38111";
+    let _router = open_router(locator);
+    let publisher = open_client(locator, None);
+    std::thread::sleep(SLEEP);
+
+    for value in ["1", "2"] {
+        publisher.put("test/lvc/cached/a", value).wait().unwrap();
+        publisher.put("test/lvc/other/a", value).wait().unwrap();
+    }
+    publisher.put("test/lvc/cached/secret", "1").wait().unwrap();
+    publisher.put("test/lvc/cached/b", "1").wait().unwrap();
+    publisher.delete("test/lvc/cached/b").wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    // Late joining subscriber receives the last value of cached keys it is allowed to only
+    let late_joiner = open_client(locator, Some("a1b2"));
+    let subscriber = late_joiner
+        .declare_subscriber("test/lvc/**")
+        .wait()
+        .unwrap();
+    let sample = subscriber.recv_timeout(RECV_TIMEOUT).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/lvc/cached/a");
+    assert_eq!(sample.payload().try_to_string().unwrap(), "2");
+    assert!(subscriber.recv_timeout(RECV_TIMEOUT).unwrap().is_none());
+
+    // Queries are answered from the cache
+    let replies: Vec<_> = late_joiner
+        .get("test/lvc/**")
+        .wait()
+        .unwrap()
+        .iter()
+        .collect();
+    assert_eq!(replies.len(), 1);
+    let sample = replies[0].result().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/lvc/cached/a");
+    assert_eq!(sample.payload().try_to_string().unwrap(), "2");
+}
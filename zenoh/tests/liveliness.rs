@@ -4515,7 +4515,7 @@ async fn test_liveliness_issue_1470() {
 
     let router0 = {
         let mut c = zenoh::Config::default();
-        c.set_id(ZenohId::from_str("a0").unwrap()).unwrap();
+        c.set_id(Some(ZenohId::from_str("a0").unwrap())).unwrap();
         c.listen
             .endpoints
             .set(vec![ROUTER0_ENDPOINT.parse::<EndPoint>().unwrap()])
@@ -4533,7 +4533,7 @@ async fn test_liveliness_issue_1470() {
 
     let router1 = {
         let mut c = zenoh::Config::default();
-        c.set_id(ZenohId::from_str("a1").unwrap()).unwrap();
+        c.set_id(Some(ZenohId::from_str("a1").unwrap())).unwrap();
         c.listen
             .endpoints
             .set(vec![ROUTER1_ENDPOINT.parse::<EndPoint>().unwrap()])
@@ -4555,7 +4555,7 @@ async fn test_liveliness_issue_1470() {
 
     let peer = {
         let mut c = zenoh::Config::default();
-        c.set_id(ZenohId::from_str("b").unwrap()).unwrap();
+        c.set_id(Some(ZenohId::from_str("b").unwrap())).unwrap();
         c.listen
             .endpoints
             .set(vec![PEER_ENDPOINT.parse::<EndPoint>().unwrap()])
@@ -4577,7 +4577,7 @@ async fn test_liveliness_issue_1470() {
 
     let client0 = {
         let mut c = zenoh::Config::default();
-        c.set_id(ZenohId::from_str("c0").unwrap()).unwrap();
+        c.set_id(Some(ZenohId::from_str("c0").unwrap())).unwrap();
         c.connect
             .endpoints
             .set(vec![PEER_ENDPOINT.parse::<EndPoint>().unwrap()])
@@ -4623,7 +4623,7 @@ async fn test_liveliness_issue_1470() {
 
     let client1 = {
         let mut c = zenoh::Config::default();
-        c.set_id(ZenohId::from_str("c1").unwrap()).unwrap();
+        c.set_id(Some(ZenohId::from_str("c1").unwrap())).unwrap();
         c.connect
             .endpoints
             .set(vec![PEER_ENDPOINT.parse::<EndPoint>().unwrap()])
@@ -4746,3 +4746,118 @@ async fn test_liveliness_double_undeclare_clique() {
     peer1.close().await.unwrap();
     peer2.close().await.unwrap();
 }
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_liveliness_subscriber_debounce() {
+    use std::time::Duration;
+
+    use zenoh::sample::SampleKind;
+    use zenoh_config::WhatAmI;
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const SLEEP: Duration = Duration::from_secs(1);
+    const DEBOUNCE: Duration = Duration::from_millis(500);
+    const LIVELINESS_KEYEXPR: &str = "test/liveliness/subscriber/debounce";
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer = {
+        let mut c = zenoh::Config::default();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        let _ = c.set_mode(Some(WhatAmI::Peer));
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+
+    let sub = ztimeout!(peer
+        .liveliness()
+        .declare_subscriber(LIVELINESS_KEYEXPR)
+        .debounce(DEBOUNCE))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    let mut token = ztimeout!(peer.liveliness().declare_token(LIVELINESS_KEYEXPR)).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert!(sample.kind() == SampleKind::Put);
+
+    // A flapping token is delivered as a single DOWN/UP pair
+    for _ in 0..5 {
+        token.undeclare().await.unwrap();
+        token = ztimeout!(peer.liveliness().declare_token(LIVELINESS_KEYEXPR)).unwrap();
+    }
+    tokio::time::sleep(SLEEP).await;
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert!(sample.kind() == SampleKind::Delete);
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert!(sample.kind() == SampleKind::Put);
+    assert!(sub.try_recv().unwrap().is_none());
+
+    // A single change is delivered immediately
+    token.undeclare().await.unwrap();
+    let sample = tokio::time::timeout(DEBOUNCE / 2, sub.recv_async())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(sample.kind() == SampleKind::Delete);
+
+    sub.undeclare().await.unwrap();
+    peer.close().await.unwrap();
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_liveliness_subscriber_debounce_undeclare() {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use zenoh_config::WhatAmI;
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const SLEEP: Duration = Duration::from_secs(1);
+    const DEBOUNCE: Duration = Duration::from_millis(500);
+    const LIVELINESS_KEYEXPR: &str = "test/liveliness/subscriber/debounce/undeclare";
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer = {
+        let mut c = zenoh::Config::default();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        let _ = c.set_mode(Some(WhatAmI::Peer));
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+
+    let token = ztimeout!(peer.liveliness().declare_token(LIVELINESS_KEYEXPR)).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let sub = ztimeout!(peer
+        .liveliness()
+        .declare_subscriber(LIVELINESS_KEYEXPR)
+        .debounce(DEBOUNCE)
+        .callback({
+            let received = received.clone();
+            move |_| {
+                received.fetch_add(1, Ordering::SeqCst);
+            }
+        }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The DOWN is delivered immediately and the UP is held by the debounce timer
+    token.undeclare().await.unwrap();
+    let token = ztimeout!(peer.liveliness().declare_token(LIVELINESS_KEYEXPR)).unwrap();
+    tokio::time::sleep(DEBOUNCE / 5).await;
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+
+    // Undeclaring the subscriber cancels the pending debounce timer
+    sub.undeclare().await.unwrap();
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+
+    token.undeclare().await.unwrap();
+    peer.close().await.unwrap();
+}
@@ -0,0 +1,137 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use zenoh::{config::WhatAmI, query::Queryable, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open_router(locator: &str, load_balancing: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "routing/queries/load_balancing",
+            &format!("\"{load_balancing}\""),
+        )
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn open_client(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn declare_replica(session: &Session, key_expr: &str, count: &Arc<AtomicUsize>) -> Queryable<()> {
+    let count = count.clone();
+    session
+        .declare_queryable(key_expr)
+        .complete(true)
+        .callback(move |query| {
+            count.fetch_add(1, Ordering::SeqCst);
+            query
+                .reply(query.key_expr().clone(), "reply")
+                .wait()
+                .unwrap();
+        })
+        .wait()
+        .unwrap()
+}
+
+fn query(session: &Session, key_expr: &str, n: usize) {
+    for _ in 0..n {
+        let replies = session.get(key_expr).wait().unwrap();
+        assert_eq!(replies.iter().count(), 1);
+    }
+}
+
+#[test]
+fn queries_round_robin() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38303";
+    let key_expr = "test/load_balancing/round_robin";
+    let router = open_router(locator, "round_robin");
+    let replica1 = open_client(locator);
+    let replica2 = open_client(locator);
+    let querier = open_client(locator);
+
+    let count1 = Arc::new(AtomicUsize::new(0));
+    let count2 = Arc::new(AtomicUsize::new(0));
+    let _qabl1 = declare_replica(&replica1, key_expr, &count1);
+    let _qabl2 = declare_replica(&replica2, key_expr, &count2);
+    std::thread::sleep(SLEEP);
+
+    query(&querier, key_expr, 10);
+    assert_eq!(count1.load(Ordering::SeqCst), 5);
+    assert_eq!(count2.load(Ordering::SeqCst), 5);
+
+    querier.close().wait().unwrap();
+    replica2.close().wait().unwrap();
+    replica1.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn queries_least_loaded() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38304";
+    let key_expr = "test/load_balancing/least_loaded";
+    let router = open_router(locator, "least_loaded");
+    let replica1 = open_client(locator);
+    let replica2 = open_client(locator);
+    let querier = open_client(locator);
+
+    let count1 = Arc::new(AtomicUsize::new(0));
+    let count2 = Arc::new(AtomicUsize::new(0));
+    let qabl1 = declare_replica(&replica1, key_expr, &count1);
+    let qabl2 = declare_replica(&replica2, key_expr, &count2);
+
+    qabl1.set_load(100).wait().unwrap();
+    std::thread::sleep(SLEEP);
+    query(&querier, key_expr, 5);
+    assert_eq!(count1.load(Ordering::SeqCst), 0);
+    assert_eq!(count2.load(Ordering::SeqCst), 5);
+
+    qabl2.set_load(200).wait().unwrap();
+    std::thread::sleep(SLEEP);
+    query(&querier, key_expr, 5);
+    assert_eq!(count1.load(Ordering::SeqCst), 5);
+    assert_eq!(count2.load(Ordering::SeqCst), 5);
+
+    querier.close().wait().unwrap();
+    replica2.close().wait().unwrap();
+    replica1.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -224,6 +224,58 @@ async fn zenoh_publisher_matching_status_inner(publisher_locality: Locality, sam
     );
 }
 
+async fn zenoh_queryable_matching_status_inner(queryable_locality: Locality, same_session: bool) {
+    println!(
+        "Queryable origin: {:?}, same session: {same_session}",
+        queryable_locality
+    );
+    zenoh_util::init_log_from_env_or("error");
+    let key_expr = match queryable_locality {
+        Locality::SessionLocal => "zenoh_queryable_matching_status_local_test",
+        Locality::Remote => "zenoh_queryable_matching_status_remote_test",
+        Locality::Any => "zenoh_queryable_matching_status_any_test",
+    };
+
+    let (session1, session2) = match same_session {
+        false => create_session_pair("tcp/127.0.0.1:18003").await,
+        true => {
+            let s1 = ztimeout!(zenoh::open(zenoh::Config::default())).unwrap();
+            let s2 = s1.clone();
+            (s1, s2)
+        }
+    };
+    let locality_compatible = is_locality_compatible(queryable_locality, same_session);
+
+    let queryable = ztimeout!(session1
+        .declare_queryable(format!("{key_expr}/*"))
+        .allowed_origin(queryable_locality))
+    .unwrap();
+
+    let matching_listener = ztimeout!(queryable.matching_listener()).unwrap();
+
+    assert!(!ztimeout!(queryable.matching_status()).unwrap().matching());
+    assert_eq!(get_matching_listener_status(&matching_listener), None);
+
+    let querier = ztimeout!(session2.declare_querier(format!("{key_expr}/value"))).unwrap();
+
+    assert_eq!(
+        get_matching_listener_status(&matching_listener),
+        locality_compatible.then_some(true)
+    );
+    assert_eq!(
+        ztimeout!(queryable.matching_status()).unwrap().matching(),
+        locality_compatible
+    );
+
+    ztimeout!(querier.undeclare()).unwrap();
+
+    assert_eq!(
+        get_matching_listener_status(&matching_listener),
+        locality_compatible.then_some(false)
+    );
+    assert!(!ztimeout!(queryable.matching_status()).unwrap().matching());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn zenoh_querier_matching_status() -> ZResult<()> {
     zenoh_util::init_log_from_env_or("error");
@@ -247,3 +299,57 @@ async fn zenoh_publisher_matching_status() -> ZResult<()> {
     zenoh_publisher_matching_status_inner(Locality::SessionLocal, false).await;
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_queryable_matching_status() -> ZResult<()> {
+    zenoh_util::init_log_from_env_or("error");
+    zenoh_queryable_matching_status_inner(Locality::Any, true).await;
+    zenoh_queryable_matching_status_inner(Locality::Any, false).await;
+    zenoh_queryable_matching_status_inner(Locality::Remote, true).await;
+    zenoh_queryable_matching_status_inner(Locality::Remote, false).await;
+    zenoh_queryable_matching_status_inner(Locality::SessionLocal, true).await;
+    zenoh_queryable_matching_status_inner(Locality::SessionLocal, false).await;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_publisher_set_key_expr() -> ZResult<()> {
+    zenoh_util::init_log_from_env_or("error");
+    let (session1, session2) = create_session_pair("tcp/127.0.0.1:38302").await;
+
+    let sub1 = ztimeout!(session2.declare_subscriber("zenoh_publisher_set_key_expr/1")).unwrap();
+    let mut publisher =
+        ztimeout!(session1.declare_publisher("zenoh_publisher_set_key_expr/1")).unwrap();
+    let matching_listener = ztimeout!(publisher.matching_listener()).unwrap();
+    assert_eq!(get_matching_listener_status(&matching_listener), Some(true));
+
+    // The matching listener follows the publisher to its new key expression
+    ztimeout!(publisher.set_key_expr("zenoh_publisher_set_key_expr/2")).unwrap();
+    assert_eq!(
+        publisher.key_expr().as_str(),
+        "zenoh_publisher_set_key_expr/2"
+    );
+    assert_eq!(
+        get_matching_listener_status(&matching_listener),
+        Some(false)
+    );
+    assert!(!ztimeout!(publisher.matching_status()).unwrap().matching());
+
+    let sub2 = ztimeout!(session2.declare_subscriber("zenoh_publisher_set_key_expr/2")).unwrap();
+    assert_eq!(get_matching_listener_status(&matching_listener), Some(true));
+
+    ztimeout!(publisher.put("value")).unwrap();
+    let sample = sub2.recv_timeout(RECV_TIMEOUT).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "zenoh_publisher_set_key_expr/2");
+    assert!(sub1.recv_timeout(RECV_TIMEOUT).unwrap().is_none());
+
+    assert!(ztimeout!(publisher.set_key_expr("zenoh_publisher_set_key_expr/**/?")).is_err());
+    assert_eq!(
+        publisher.key_expr().as_str(),
+        "zenoh_publisher_set_key_expr/2"
+    );
+
+    ztimeout!(publisher.undeclare()).unwrap();
+    assert_eq!(get_matching_listener_status(&matching_listener), None);
+    Ok(())
+}
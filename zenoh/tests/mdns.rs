@@ -0,0 +1,53 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(3);
+
+fn open(mode: WhatAmI, listen: Option<&str>) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    if let Some(listen) = listen {
+        config
+            .listen
+            .endpoints
+            .set(vec![listen.parse().unwrap()])
+            .unwrap();
+    }
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.scouting.gossip.set_enabled(Some(false)).unwrap();
+    config.scouting.mdns.set_enabled(Some(true)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn mdns_scouting() {
+    zenoh_util::init_log_from_env_or("error");
+    let router = open(WhatAmI::Router, Some("tcp/127.0.0.1:38131"));
+    let peer01 = open(WhatAmI::Peer, Some("tcp/127.0.0.1:38132"));
+    let peer02 = open(WhatAmI::Peer, Some("tcp/127.0.0.1:38133"));
+    // Clients scout for a router until they find one
+    let client = open(WhatAmI::Client, None);
+    std::thread::sleep(SLEEP);
+
+    let peers: Vec<_> = peer02.info().peers_zid().wait().collect();
+    assert!(peers.contains(&peer01.zid()));
+    let routers: Vec<_> = peer02.info().routers_zid().wait().collect();
+    assert!(routers.contains(&router.zid()));
+    let routers: Vec<_> = client.info().routers_zid().wait().collect();
+    assert_eq!(routers, vec![router.zid()]);
+}
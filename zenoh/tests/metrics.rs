@@ -0,0 +1,119 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use zenoh::{config::WhatAmI, Config, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+#[test]
+fn session_metrics() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38201";
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5("timestamping", r#"{ enabled: true }"#)
+        .unwrap();
+    let router = zenoh::open(router_config).wait().unwrap();
+
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+
+    // A queryable holding the queries keeps them pending
+    let queryable = router
+        .declare_queryable("test/metrics")
+        .complete(true)
+        .wait()
+        .unwrap();
+    let _keyexpr = client.declare_keyexpr("test/metrics").wait().unwrap();
+    std::thread::sleep(SLEEP);
+    let _replies = client.get("test/metrics").wait().unwrap();
+    let _query = queryable.recv_timeout(SLEEP).unwrap().unwrap();
+
+    let metrics = client.metrics().wait();
+    assert_eq!(metrics.pending_queries, 1);
+    assert_eq!(metrics.local_keyexprs, 1);
+    assert!(metrics.routing_resources > 0);
+    assert!(!metrics.queues.is_empty());
+    assert!(metrics
+        .queues
+        .iter()
+        .all(|queue| queue.peer == router.zid() && queue.capacity > 0));
+    assert_eq!(metrics.hlc_drift, None);
+    assert!(router.metrics().wait().hlc_drift.is_some());
+
+    let exports = Arc::new(AtomicUsize::new(0));
+    let exporter = client
+        .metrics_exporter(Duration::from_millis(100), {
+            let exports = exports.clone();
+            move |metrics| {
+                assert_eq!(metrics.local_keyexprs, 1);
+                exports.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .unwrap();
+    std::thread::sleep(SLEEP);
+    drop(exporter);
+    std::thread::sleep(Duration::from_millis(200));
+    let count = exports.load(Ordering::Relaxed);
+    assert!(count >= 5, "{count} exports");
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(exports.load(Ordering::Relaxed), count);
+    assert!(client.metrics_exporter(Duration::ZERO, |_| ()).is_err());
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+// The metrics are resolved asynchronously, without blocking the worker of the runtime
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn session_metrics_async() {
+    zenoh_util::init_log_from_env_or("error");
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let session = zenoh::open(config).await.unwrap();
+    let _keyexpr = session.declare_keyexpr("test/metrics").await.unwrap();
+    assert_eq!(session.metrics().await.local_keyexprs, 1);
+    session.close().await.unwrap();
+}
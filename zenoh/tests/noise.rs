@@ -0,0 +1,89 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, qos::CongestionControl, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const TCP: &str = "tcp/127.0.0.1:38251";
+const UDP: &str = "udp/127.0.0.1:38252";
+
+fn config(mode: WhatAmI, noise: Option<&str>) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    if let Some(noise) = noise {
+        config
+            .insert_json5("transport/unicast/noise", noise)
+            .unwrap();
+    }
+    config
+}
+
+fn open_router() -> Session {
+    let mut config = config(WhatAmI::Router, Some(r#"{ enabled: true, psk: "secret" }"#));
+    config
+        .listen
+        .endpoints
+        .set(vec![TCP.parse().unwrap(), UDP.parse().unwrap()])
+        .unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn open_client(locator: &str, noise: Option<&str>) -> zenoh::Result<Session> {
+    let mut config = config(WhatAmI::Client, noise);
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    zenoh::open(config).wait()
+}
+
+#[test]
+fn noise_encryption() {
+    zenoh_util::init_log_from_env_or("error");
+    let router = open_router();
+
+    // Sessions without encryption or with another pre-shared key are refused
+    assert!(open_client(TCP, None).is_err());
+    assert!(open_client(TCP, Some(r#"{ enabled: true }"#)).is_err());
+    assert!(open_client(TCP, Some(r#"{ enabled: true, psk: "other" }"#)).is_err());
+
+    let noise = Some(r#"{ enabled: true, psk: "secret" }"#);
+    let tcp = open_client(TCP, noise).unwrap();
+    let udp = open_client(UDP, noise).unwrap();
+    let subscriber = router.declare_subscriber("test/noise/**").wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    // Payloads larger than a batch are fragmented before being encrypted
+    let large = vec![0x5a; 200_000];
+    tcp.put("test/noise/tcp", large.clone())
+        .congestion_control(CongestionControl::Block)
+        .wait()
+        .unwrap();
+    let sample = subscriber.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/noise/tcp");
+    assert_eq!(sample.payload().to_bytes(), large);
+
+    udp.put("test/noise/udp", "datagram").wait().unwrap();
+    let sample = subscriber.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/noise/udp");
+    assert_eq!(sample.payload().to_bytes(), b"datagram".as_slice());
+
+    tcp.close().wait().unwrap();
+    udp.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
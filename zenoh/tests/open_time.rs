@@ -190,6 +190,24 @@ async fn time_udp_only_with_lowlatency_open() {
 //     time_lowlatency_open(&endpoint, WhatAmI::Client).await;
 // }
 
+#[cfg(feature = "transport_mem")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore]
+async fn time_mem_only_open() {
+    zenoh::init_log_from_env_or("error");
+    let endpoint: EndPoint = "mem/time_mem_only_open".parse().unwrap();
+    time_universal_open(&endpoint, WhatAmI::Client).await;
+}
+
+#[cfg(feature = "transport_mem")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore]
+async fn time_mem_only_with_lowlatency_open() {
+    zenoh::init_log_from_env_or("error");
+    let endpoint: EndPoint = "mem/time_mem_only_with_lowlatency_open".parse().unwrap();
+    time_lowlatency_open(&endpoint, WhatAmI::Client).await;
+}
+
 #[cfg(feature = "transport_unixpipe")]
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 #[ignore]
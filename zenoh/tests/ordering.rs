@@ -0,0 +1,84 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::{Duration, Instant};
+
+use zenoh::{
+    handlers::FifoChannelHandler,
+    pubsub::Subscriber,
+    sample::{Sample, SourceInfo, SourceSn},
+    session::EntityGlobalId,
+    Config, Session, Wait,
+};
+
+const MAX_DELAY: Duration = Duration::from_millis(200);
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn put(session: &Session, key_expr: &str, id: EntityGlobalId, sn: SourceSn) {
+    session
+        .put(key_expr, sn.to_string())
+        .source_info(SourceInfo::new(Some(id), Some(sn)))
+        .wait()
+        .unwrap();
+}
+
+fn recv(subscriber: &Subscriber<FifoChannelHandler<Sample>>) -> SourceSn {
+    let sample = subscriber.recv_timeout(TIMEOUT).unwrap().unwrap();
+    sample.source_info().source_sn().unwrap()
+}
+
+#[test]
+fn ordered_subscriber() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+    let publisher = session.declare_publisher("test/ordering/a").wait().unwrap();
+    let id = publisher.id();
+    let subscriber = session
+        .declare_subscriber("test/ordering/**")
+        .ordered(MAX_DELAY)
+        .wait()
+        .unwrap();
+
+    // Reordered samples are delivered as soon as the missing ones are received
+    for sn in [0, 2, 1, 3] {
+        put(&session, "test/ordering/a", id, sn);
+    }
+    for sn in 0..4 {
+        assert_eq!(recv(&subscriber), sn);
+    }
+
+    // Each key is ordered on its own
+    put(&session, "test/ordering/b", id, 7);
+    put(&session, "test/ordering/a", id, 4);
+    assert_eq!(recv(&subscriber), 7);
+    assert_eq!(recv(&subscriber), 4);
+
+    // Samples without source info are delivered as received
+    session.put("test/ordering/a", "none").wait().unwrap();
+    let sample = subscriber.recv_timeout(TIMEOUT).unwrap().unwrap();
+    assert!(sample.source_info().source_sn().is_none());
+
+    // A gap is skipped after the maximum delay, the missing sample being dropped
+    let start = Instant::now();
+    put(&session, "test/ordering/a", id, 6);
+    assert!(subscriber.try_recv().unwrap().is_none());
+    assert_eq!(recv(&subscriber), 6);
+    assert!(start.elapsed() >= MAX_DELAY);
+    put(&session, "test/ordering/a", id, 5);
+    put(&session, "test/ordering/a", id, 7);
+    assert_eq!(recv(&subscriber), 7);
+    assert!(subscriber.try_recv().unwrap().is_none());
+
+    session.close().wait().unwrap();
+}
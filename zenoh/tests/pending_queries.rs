@@ -0,0 +1,90 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use zenoh::{
+    query::{ConsolidationMode, Query},
+    Config, Wait,
+};
+
+const SLEEP: Duration = Duration::from_millis(100);
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+#[test]
+fn pending_queries_finalize() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+    assert!(session.pending_queries().is_empty());
+
+    // A queryable which replies once and never finalizes the queries
+    let queries: Arc<Mutex<Vec<Query>>> = Arc::default();
+    let _queryable = session
+        .declare_queryable("test/pending/**")
+        .callback({
+            let queries = queries.clone();
+            move |query| {
+                query
+                    .reply(query.key_expr().clone(), "value")
+                    .wait()
+                    .unwrap();
+                queries.lock().unwrap().push(query);
+            }
+        })
+        .wait()
+        .unwrap();
+
+    let replies = session
+        .get("test/pending/a?arg=1")
+        .consolidation(ConsolidationMode::None)
+        .timeout(TIMEOUT)
+        .wait()
+        .unwrap();
+    let latest = session
+        .get("test/pending/b")
+        .consolidation(ConsolidationMode::Latest)
+        .timeout(TIMEOUT)
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    let mut pending = session.pending_queries();
+    pending.sort_by_key(|query| query.id());
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].selector().to_string(), "test/pending/a?arg=1");
+    assert_eq!(pending[0].replies(), 1);
+    assert!(pending[0].elapsed() >= SLEEP);
+    assert_eq!(pending[1].selector().to_string(), "test/pending/b");
+    assert_eq!(pending[1].replies(), 1);
+
+    // The reply is delivered right away without consolidation
+    assert!(replies.try_recv().unwrap().is_some());
+    assert!(latest.try_recv().unwrap().is_none());
+
+    pending[0].finalize().wait().unwrap();
+    assert!(replies.recv().is_err());
+    pending[1].finalize().wait().unwrap();
+    // The consolidated replies are delivered on finalization
+    assert!(latest.recv().unwrap().result().is_ok());
+    assert!(latest.recv().is_err());
+
+    assert!(session.pending_queries().is_empty());
+    assert!(pending[0].finalize().wait().is_err());
+
+    queries.lock().unwrap().clear();
+    session.close().wait().unwrap();
+}
@@ -0,0 +1,104 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, qos::Priority, Config, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+#[test]
+fn probe() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38191";
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5(
+            "adminspace",
+            r#"{ enabled: true, permissions: { read: true, write: true } }"#,
+        )
+        .unwrap();
+    let router = zenoh::open(router_config).wait().unwrap();
+
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    let report = client
+        .probe(router.zid())
+        .priorities([Priority::RealTime, Priority::Data])
+        .count(5)
+        .payload_size(1024)
+        .wait()
+        .unwrap();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].priority, Priority::RealTime);
+    for probe in &report {
+        assert_eq!(probe.sent, 10);
+        assert_eq!(probe.received, 10);
+        assert!(probe.rtt_min <= probe.rtt_avg && probe.rtt_avg <= probe.rtt_max);
+        assert!(probe.rtt_min.is_some());
+        assert!(probe.throughput > 0.0);
+    }
+
+    // A probe of a node without admin space receives no echo reply
+    let report = client
+        .probe(client.zid())
+        .count(1)
+        .timeout(Duration::from_millis(100))
+        .wait()
+        .unwrap();
+    assert_eq!(report[0].received, 0);
+    assert_eq!(report[0].rtt_avg, None);
+
+    // A probe can be triggered through the admin space
+    let router_zid = router.zid();
+    let replies = client
+        .get(format!("@/{router_zid}/router/probe/{router_zid}?count=2"))
+        .wait()
+        .unwrap()
+        .iter()
+        .filter_map(|reply| reply.into_result().ok())
+        .collect::<Vec<_>>();
+    assert_eq!(replies.len(), 1);
+    let report: serde_json::Value =
+        serde_json::from_slice(&replies[0].payload().to_bytes()).unwrap();
+    assert_eq!(report[0]["priority"], Priority::Data as u8);
+    assert_eq!(report[0]["received"], 4);
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -222,3 +222,118 @@ async fn qos_pubsub_overwrite_config() {
     assert!(sample.express());
     assert_eq!(sample.reliability(), Reliability::Reliable);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn qos_pubsub_defaults_config() {
+    let qos_config_defaults = zenoh::Config::from_json5(
+        r#"
+        {
+            qos: {
+                defaults: [
+                    {
+                        key_exprs: ["test/qos_defaults/defaulted/**"],
+                        config: {
+                            congestion_control: "block",
+                            priority: "data_high",
+                            express: true,
+                        },
+                    },
+                ]
+            }
+        }
+        "#,
+    )
+    .unwrap();
+    let session1 = ztimeout!(zenoh::open(qos_config_defaults)).unwrap();
+    let session2 = ztimeout!(zenoh::open(zenoh::Config::default())).unwrap();
+
+    let subscriber = ztimeout!(session2.declare_subscriber("test/qos_defaults/**")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // Session API - defaulted PUT
+    ztimeout!(session1.put("test/qos_defaults/defaulted/a", "qos")).unwrap();
+    let sample = ztimeout!(subscriber.recv_async()).unwrap();
+
+    assert_eq!(sample.congestion_control(), CongestionControl::Block);
+    assert_eq!(sample.priority(), Priority::DataHigh);
+    assert!(sample.express());
+
+    // Session API - defaulted PUT with QoS given through the API
+    ztimeout!(session1
+        .put("test/qos_defaults/defaulted/a", "qos")
+        .priority(Priority::DataLow)
+        .express(false))
+    .unwrap();
+    let sample = ztimeout!(subscriber.recv_async()).unwrap();
+
+    assert_eq!(sample.congestion_control(), CongestionControl::Block);
+    assert_eq!(sample.priority(), Priority::DataLow);
+    assert!(!sample.express());
+
+    // Publisher API - defaulted PUT
+    let publisher = ztimeout!(session1
+        .declare_publisher("test/qos_defaults/defaulted/b")
+        .congestion_control(CongestionControl::Drop))
+    .unwrap();
+    ztimeout!(publisher.put("qos")).unwrap();
+    let sample = ztimeout!(subscriber.recv_async()).unwrap();
+
+    assert_eq!(sample.congestion_control(), CongestionControl::Drop);
+    assert_eq!(sample.priority(), Priority::DataHigh);
+    assert!(sample.express());
+
+    // Session API - non-defaulted PUT
+    ztimeout!(session1.put("test/qos_defaults/other", "qos")).unwrap();
+    let sample = ztimeout!(subscriber.recv_async()).unwrap();
+
+    assert_eq!(sample.congestion_control(), CongestionControl::DEFAULT);
+    assert_eq!(sample.priority(), Priority::DEFAULT);
+    assert!(!sample.express());
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn qos_reply_inheritance() {
+    use zenoh::{sample::Locality, Wait};
+
+    let session = ztimeout!(zenoh::open(zenoh::Config::default())).unwrap();
+    let _queryable = ztimeout!(session
+        .declare_queryable("test/qos_reply/*")
+        .callback(|query| {
+            assert_eq!(query.priority(), Priority::RealTime);
+            assert!(query.express());
+            if query.key_expr().as_str() == "test/qos_reply/inherited" {
+                query.reply(query.key_expr(), "qos").wait().unwrap();
+            } else {
+                query
+                    .reply(query.key_expr(), "qos")
+                    .priority(Priority::DataLow)
+                    .express(false)
+                    .wait()
+                    .unwrap();
+            }
+        }))
+    .unwrap();
+
+    let replies = ztimeout!(session
+        .get("test/qos_reply/inherited")
+        .priority(Priority::RealTime)
+        .express(true)
+        .allowed_destination(Locality::SessionLocal))
+    .unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let sample = reply.result().unwrap();
+    assert_eq!(sample.priority(), Priority::RealTime);
+    assert!(sample.express());
+
+    let replies = ztimeout!(session
+        .get("test/qos_reply/overridden")
+        .priority(Priority::RealTime)
+        .express(true)
+        .allowed_destination(Locality::SessionLocal))
+    .unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let sample = reply.result().unwrap();
+    assert_eq!(sample.priority(), Priority::DataLow);
+    assert!(!sample.express());
+}
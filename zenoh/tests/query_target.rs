@@ -0,0 +1,139 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{
+    config::WhatAmI,
+    query::{ConsolidationMode, QueryTarget, Queryable},
+    Config, Session, Wait,
+};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open(mode: WhatAmI, listen: &[&str], connect: &[&str]) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(listen.iter().map(|l| l.parse().unwrap()).collect())
+        .unwrap();
+    config
+        .connect
+        .endpoints
+        .set(connect.iter().map(|c| c.parse().unwrap()).collect())
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+fn declare_replica(session: &Session, key_expr: &str, name: &'static str) -> Queryable<()> {
+    session
+        .declare_queryable(key_expr)
+        .complete(true)
+        .callback(move |query| {
+            query.reply(query.key_expr().clone(), name).wait().unwrap();
+        })
+        .wait()
+        .unwrap()
+}
+
+fn get(session: &Session, key_expr: &str, target: QueryTarget) -> Vec<String> {
+    session
+        .get(key_expr)
+        .target(target)
+        .consolidation(ConsolidationMode::None)
+        .wait()
+        .unwrap()
+        .iter()
+        .map(|reply| {
+            reply
+                .result()
+                .unwrap()
+                .payload()
+                .try_to_string()
+                .unwrap()
+                .into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn query_target_specific() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38305";
+    let key_expr = "test/query_target/specific";
+    let router = open(WhatAmI::Router, &[locator], &[]);
+    let replica1 = open(WhatAmI::Client, &[], &[locator]);
+    let replica2 = open(WhatAmI::Client, &[], &[locator]);
+    let querier = open(WhatAmI::Client, &[], &[locator]);
+
+    let _qabl1 = declare_replica(&replica1, key_expr, "replica1");
+    let _qabl2 = declare_replica(&replica2, key_expr, "replica2");
+    std::thread::sleep(SLEEP);
+
+    let mut replies = get(&querier, key_expr, QueryTarget::All);
+    replies.sort();
+    assert_eq!(replies, ["replica1", "replica2"]);
+    for _ in 0..5 {
+        let target = QueryTarget::Specific(replica2.zid().into());
+        assert_eq!(get(&querier, key_expr, target), ["replica2"]);
+        let target = QueryTarget::Specific(replica1.zid().into());
+        assert_eq!(get(&querier, key_expr, target), ["replica1"]);
+    }
+    let target = QueryTarget::Specific(router.zid().into());
+    assert!(get(&querier, key_expr, target).is_empty());
+
+    // The queries from a replica only reach its own queryables if it is the target
+    let target = QueryTarget::Specific(replica1.zid().into());
+    assert_eq!(get(&replica1, key_expr, target), ["replica1"]);
+    let target = QueryTarget::Specific(replica2.zid().into());
+    assert_eq!(get(&replica1, key_expr, target), ["replica2"]);
+
+    querier.close().wait().unwrap();
+    replica2.close().wait().unwrap();
+    replica1.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn query_target_specific_routed() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator1 = "tcp/127.0.0.1:38306";
+    let locator2 = "tcp/127.0.0.1:38307";
+    let key_expr = "test/query_target/routed";
+    let router1 = open(WhatAmI::Router, &[locator1], &[]);
+    let router2 = open(WhatAmI::Router, &[locator2], &[locator1]);
+    let replica1 = open(WhatAmI::Client, &[], &[locator2]);
+    let replica2 = open(WhatAmI::Client, &[], &[locator2]);
+    let querier = open(WhatAmI::Client, &[], &[locator1]);
+
+    let _qabl1 = declare_replica(&replica1, key_expr, "replica1");
+    let _qabl2 = declare_replica(&replica2, key_expr, "replica2");
+    std::thread::sleep(SLEEP);
+
+    for _ in 0..5 {
+        let target = QueryTarget::Specific(replica2.zid().into());
+        assert_eq!(get(&querier, key_expr, target), ["replica2"]);
+        let target = QueryTarget::Specific(replica1.zid().into());
+        assert_eq!(get(&querier, key_expr, target), ["replica1"]);
+    }
+
+    querier.close().wait().unwrap();
+    replica2.close().wait().unwrap();
+    replica1.close().wait().unwrap();
+    router2.close().wait().unwrap();
+    router1.close().wait().unwrap();
+}
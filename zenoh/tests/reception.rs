@@ -0,0 +1,86 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "sample_metadata")]
+use std::time::{Duration, SystemTime};
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn open(mode: WhatAmI, locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    let endpoints = if mode == WhatAmI::Router {
+        &mut config.listen.endpoints
+    } else {
+        &mut config.connect.endpoints
+    };
+    endpoints.set(vec![locator.parse().unwrap()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn reception_info() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38308";
+    let router = open(WhatAmI::Router, locator);
+    let publisher = open(WhatAmI::Client, locator);
+    let subscriber = open(WhatAmI::Client, locator);
+    let samples = subscriber
+        .declare_subscriber("test/reception/**")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    // Published by the local session
+    let start = SystemTime::now();
+    subscriber
+        .put("test/reception/local", "local")
+        .wait()
+        .unwrap();
+    let sample = samples.recv_timeout(TIMEOUT).unwrap().unwrap();
+    let info = sample.reception_info().unwrap();
+    assert_eq!(info.hops(), 0);
+    assert!(info.time() >= start);
+    assert!(info.ingress_src().is_none());
+
+    // Published by the router the subscriber is connected to
+    router
+        .put("test/reception/router", "router")
+        .wait()
+        .unwrap();
+    let sample = samples.recv_timeout(TIMEOUT).unwrap().unwrap();
+    let info = sample.reception_info().unwrap();
+    assert_eq!(info.hops(), 1);
+    assert!(info.time() >= start);
+    assert_eq!(info.ingress_src().unwrap().to_string(), locator);
+    assert_eq!(info.ingress_dst().unwrap().protocol().as_str(), "tcp");
+
+    // Published by another client, through the router
+    publisher
+        .put("test/reception/client", "client")
+        .wait()
+        .unwrap();
+    let sample = samples.recv_timeout(TIMEOUT).unwrap().unwrap();
+    let info = sample.reception_info().unwrap();
+    assert_eq!(info.hops(), 2);
+    assert_eq!(info.ingress_src().unwrap().to_string(), locator);
+
+    samples.undeclare().wait().unwrap();
+    subscriber.close().wait().unwrap();
+    publisher.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
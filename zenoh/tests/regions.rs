@@ -0,0 +1,142 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, query::ConsolidationMode, Config, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+const NEIGHBOURS: &str = r#"[{ region: "region-b", zids: ["b"] }]"#;
+
+fn build_configs(locator: &str, neighbours: &str) -> (Config, Config) {
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+    router_config
+        .insert_json5(
+            "regions",
+            &format!(
+                r#"{{
+                    name: "region-a",
+                    neighbours: {neighbours},
+                    exports: ["test/regions/exported/**"],
+                }}"#
+            ),
+        )
+        .unwrap();
+
+    let mut client_config = Config::default();
+    client_config.set_mode(Some(WhatAmI::Client)).unwrap();
+    client_config.set_id(Some("b".parse().unwrap())).unwrap();
+    client_config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    client_config
+        .scouting
+        .multicast
+        .set_enabled(Some(false))
+        .unwrap();
+
+    (router_config, client_config)
+}
+
+#[test]
+fn regions_pubsub() {
+    zenoh_util::init_log_from_env_or("error");
+    let (router_config, client_config) = build_configs("tcp/127.0.0.1:38101", NEIGHBOURS);
+    let router = zenoh::open(router_config).wait().unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+
+    let subscriber = client.declare_subscriber("test/regions/**").wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    router.put("test/regions/local", "local").wait().unwrap();
+    router
+        .put("test/regions/exported/data", "exported")
+        .wait()
+        .unwrap();
+
+    let sample = subscriber.recv_timeout(RECV_TIMEOUT).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/regions/exported/data");
+    assert!(subscriber.recv_timeout(RECV_TIMEOUT).unwrap().is_none());
+}
+
+#[test]
+fn regions_queries() {
+    zenoh_util::init_log_from_env_or("error");
+    let (router_config, client_config) = build_configs("tcp/127.0.0.1:38102", NEIGHBOURS);
+    let router = zenoh::open(router_config).wait().unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+
+    let queryable = |session: &zenoh::Session, name: &'static str| {
+        session
+            .declare_queryable("test/regions/**")
+            .callback(move |query| query.reply(query.key_expr().clone(), name).wait().unwrap())
+            .wait()
+            .unwrap()
+    };
+    let _router_queryable = queryable(&router, "router");
+    let _client_queryable = queryable(&client, "client");
+    std::thread::sleep(SLEEP);
+
+    let replies = |key_expr: &str| {
+        let mut replies: Vec<String> = router
+            .get(key_expr)
+            .consolidation(ConsolidationMode::None)
+            .timeout(SLEEP)
+            .wait()
+            .unwrap()
+            .iter()
+            .map(|reply| match reply.result() {
+                Ok(sample) => sample.payload().try_to_string().unwrap().into(),
+                Err(err) => format!("error: {}", err.payload().try_to_string().unwrap()),
+            })
+            .collect();
+        replies.sort();
+        replies
+    };
+    // Only the exported queries cross the boundary to the client of region-b, the other ones
+    // time out waiting for it
+    assert_eq!(replies("test/regions/local"), ["error: Timeout", "router"]);
+    assert_eq!(replies("test/regions/exported/data"), ["client", "router"]);
+}
+
+#[test]
+fn regions_neighbour_without_criteria() {
+    zenoh_util::init_log_from_env_or("error");
+    // A neighbour entry with neither zids nor interfaces matches no node
+    let (router_config, client_config) =
+        build_configs("tcp/127.0.0.1:38103", r#"[{ region: "region-b" }]"#);
+    let router = zenoh::open(router_config).wait().unwrap();
+    let client = zenoh::open(client_config).wait().unwrap();
+
+    let subscriber = client.declare_subscriber("test/regions/**").wait().unwrap();
+    std::thread::sleep(SLEEP);
+
+    router.put("test/regions/local", "local").wait().unwrap();
+    let sample = subscriber.recv_timeout(RECV_TIMEOUT).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/regions/local");
+}
@@ -0,0 +1,69 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use zenoh::{sample::Locality, Config, Wait};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const REPLY_TIMEOUT: Duration = Duration::from_millis(300);
+
+#[test]
+fn reply_timeout() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = zenoh::open(Config::default()).wait().unwrap();
+    // The queries are kept, so that they are never finalized by the queryable
+    let pending = Arc::new(Mutex::new(vec![]));
+    let _queryable = session
+        .declare_queryable("test/reply_timeout")
+        .callback({
+            let pending = pending.clone();
+            move |query| {
+                query.reply(query.key_expr(), "value").wait().unwrap();
+                pending.lock().unwrap().push(query);
+            }
+        })
+        .wait()
+        .unwrap();
+
+    let start = Instant::now();
+    let replies = session
+        .get("test/reply_timeout")
+        .allowed_destination(Locality::SessionLocal)
+        .timeout(TIMEOUT)
+        .reply_timeout(REPLY_TIMEOUT)
+        .wait()
+        .unwrap();
+    let replies: Vec<_> = replies.iter().collect();
+    assert!(start.elapsed() < TIMEOUT / 2);
+    // The query is finalized without a "Timeout" error reply
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_ok());
+
+    let replies = session
+        .get("test/reply_timeout")
+        .allowed_destination(Locality::SessionLocal)
+        .timeout(REPLY_TIMEOUT)
+        .wait()
+        .unwrap();
+    let replies: Vec<_> = replies.iter().collect();
+    assert_eq!(replies.len(), 2);
+    assert!(replies[1].result().is_err());
+
+    pending.lock().unwrap().clear();
+    session.close().wait().unwrap();
+}
@@ -442,3 +442,28 @@ async fn test_undeclare_subscribers_same_keyexpr() {
     ztimeout!(sub1.undeclare()).unwrap();
     ztimeout!(sub2.undeclare()).unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_subscribers_interned_keyexpr() {
+    let key_expr = "test/interned/keyexpr";
+    let session = ztimeout!(zenoh::open(zenoh::Config::default())).unwrap();
+    // The key expression is interned by the first sample, matching no subscriber
+    ztimeout!(session.put(key_expr, "0")).unwrap();
+
+    let sub1 = ztimeout!(session.declare_subscriber("test/interned/*")).unwrap();
+    let sub2 = ztimeout!(session.declare_subscriber(key_expr)).unwrap();
+    ztimeout!(session.put(key_expr, "1")).unwrap();
+    for sub in [&sub1, &sub2] {
+        let sample = ztimeout!(sub.recv_async()).unwrap();
+        assert_eq!(sample.key_expr().as_str(), key_expr);
+        assert_eq!(sample.payload().try_to_string().unwrap(), "1");
+    }
+
+    ztimeout!(sub1.undeclare()).unwrap();
+    ztimeout!(session.put(key_expr, "2")).unwrap();
+    let sample = ztimeout!(sub2.recv_async()).unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "2");
+    assert!(sub2.try_recv().unwrap().is_none());
+
+    ztimeout!(session.close()).unwrap();
+}
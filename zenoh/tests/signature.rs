@@ -0,0 +1,174 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{
+    bytes::Encoding,
+    config::WhatAmI,
+    sample::{SampleKind, SignaturePolicy, SignatureStatus, SigningKey},
+    session::SubtreeKeyProvider,
+    Config, Session, Wait,
+};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open(mode: WhatAmI, locator: &str) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    if mode == WhatAmI::Router {
+        config
+            .listen
+            .endpoints
+            .set(vec![locator.parse().unwrap()])
+            .unwrap();
+    } else {
+        config
+            .connect
+            .endpoints
+            .set(vec![locator.parse().unwrap()])
+            .unwrap();
+    }
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+#[test]
+fn signature_verification() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38231";
+    let router: Session = zenoh::open(open(WhatAmI::Router, locator)).wait().unwrap();
+    let publisher_session = zenoh::open(open(WhatAmI::Client, locator)).wait().unwrap();
+    let subscriber_session = zenoh::open(open(WhatAmI::Client, locator)).wait().unwrap();
+
+    let key = SigningKey::generate();
+    let other = SigningKey::generate();
+    let flagged = subscriber_session
+        .declare_subscriber("test/signature/**")
+        .signature_policy(SignaturePolicy::flag().trust(key.verifying_key()))
+        .wait()
+        .unwrap();
+    let rejecting = subscriber_session
+        .declare_subscriber("test/signature/**")
+        .signature_policy(SignaturePolicy::reject(key.verifying_key()))
+        .wait()
+        .unwrap();
+    let unverified = subscriber_session
+        .declare_subscriber("test/signature/**")
+        .wait()
+        .unwrap();
+    let signed = publisher_session
+        .declare_publisher("test/signature/signed")
+        .signing_key(key.clone())
+        .wait()
+        .unwrap();
+    let untrusted = publisher_session
+        .declare_publisher("test/signature/untrusted")
+        .signing_key(other.clone())
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    // A valid signature by a trusted key, covering the encoding and the attachment
+    signed
+        .put("payload")
+        .encoding(Encoding::TEXT_PLAIN)
+        .attachment("attachment")
+        .wait()
+        .unwrap();
+    let sample = flagged.recv_timeout(SLEEP).unwrap().unwrap();
+    assert!(sample.timestamp().is_some());
+    assert_eq!(
+        sample.signature_status(),
+        SignatureStatus::Valid(key.verifying_key())
+    );
+    let sample = rejecting.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "payload");
+    let sample = unverified.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.signature_status(), SignatureStatus::Unverified);
+
+    signed.delete().wait().unwrap();
+    let sample = rejecting.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.kind(), SampleKind::Delete);
+    let _ = flagged.recv_timeout(SLEEP).unwrap().unwrap();
+    let _ = unverified.recv_timeout(SLEEP).unwrap().unwrap();
+
+    // A valid signature by an untrusted key
+    untrusted.put("payload").wait().unwrap();
+    let sample = flagged.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(
+        sample.signature_status(),
+        SignatureStatus::Untrusted(other.verifying_key())
+    );
+    let _ = unverified.recv_timeout(SLEEP).unwrap().unwrap();
+
+    // An unsigned sample
+    publisher_session
+        .put("test/signature/unsigned", "payload")
+        .wait()
+        .unwrap();
+    let sample = flagged.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.signature_status(), SignatureStatus::Unsigned);
+    let _ = unverified.recv_timeout(SLEEP).unwrap().unwrap();
+    assert!(rejecting.recv_timeout(SLEEP).unwrap().is_none());
+
+    publisher_session.close().wait().unwrap();
+    subscriber_session.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn signature_with_encryption() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38232";
+    let provider = || {
+        SubtreeKeyProvider::new()
+            .subtree("test/signature/**", b"secret")
+            .unwrap()
+    };
+    let router: Session = zenoh::open(open(WhatAmI::Router, locator)).wait().unwrap();
+    let publisher_session = zenoh::open(open(WhatAmI::Client, locator))
+        .with_key_provider(provider())
+        .wait()
+        .unwrap();
+    let subscriber_session = zenoh::open(open(WhatAmI::Client, locator))
+        .with_key_provider(provider())
+        .wait()
+        .unwrap();
+
+    let key = SigningKey::generate();
+    let subscriber = subscriber_session
+        .declare_subscriber("test/signature/**")
+        .signature_policy(SignaturePolicy::reject(key.verifying_key()))
+        .wait()
+        .unwrap();
+    let publisher = publisher_session
+        .declare_publisher("test/signature/encrypted")
+        .signing_key(key)
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    publisher
+        .put("payload")
+        .attachment("attachment")
+        .wait()
+        .unwrap();
+    let sample = subscriber.recv_timeout(SLEEP).unwrap().unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "payload");
+
+    publisher_session.close().wait().unwrap();
+    subscriber_session.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
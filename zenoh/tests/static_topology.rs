@@ -0,0 +1,92 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+
+fn client_config(locator: &str, zid: &str) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config.set_id(Some(zid.parse().unwrap())).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+#[test]
+fn static_topology() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38141";
+    let mut router_config = Config::default();
+    router_config.set_mode(Some(WhatAmI::Router)).unwrap();
+    router_config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    router_config
+        .insert_json5(
+            "routing/static_topology",
+            r#"{
+                enabled: true,
+                neighbours: [{ zid: "b", ingress: ["test/static/in/**"], egress: ["test/static/out/**"] }],
+            }"#,
+        )
+        .unwrap();
+    let router = zenoh::open(router_config).wait().unwrap();
+    let neighbour = zenoh::open(client_config(locator, "b")).wait().unwrap();
+
+    // Nodes that are not part of the static topology are refused: their session opens but the
+    // router closes the transport right after the handshake, so no data flows to or from them
+    let stranger = zenoh::open(client_config(locator, "c")).wait().unwrap();
+    let stranger_sub = stranger
+        .declare_subscriber("test/static/**")
+        .wait()
+        .unwrap();
+
+    let router_sub = router.declare_subscriber("test/static/**").wait().unwrap();
+    let neighbour_sub = neighbour
+        .declare_subscriber("test/static/**")
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    stranger.put("test/static/in/b", "").wait().unwrap();
+    neighbour.put("test/static/other", "").wait().unwrap();
+    neighbour.put("test/static/in/a", "").wait().unwrap();
+    let sample = router_sub.recv_timeout(RECV_TIMEOUT).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/static/in/a");
+    assert!(router_sub.recv_timeout(RECV_TIMEOUT).unwrap().is_none());
+
+    // Ignore the samples published by the neighbour itself
+    while let Ok(Some(_)) = neighbour_sub.try_recv() {}
+    router.put("test/static/other", "").wait().unwrap();
+    router.put("test/static/out/a", "").wait().unwrap();
+    let sample = neighbour_sub.recv_timeout(RECV_TIMEOUT).unwrap().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/static/out/a");
+    assert!(neighbour_sub.recv_timeout(RECV_TIMEOUT).unwrap().is_none());
+    // Ignore the samples published by the stranger itself
+    while let Ok(Some(sample)) = stranger_sub.try_recv() {
+        assert_eq!(sample.key_expr().as_str(), "test/static/in/b");
+    }
+    assert!(stranger_sub.recv_timeout(RECV_TIMEOUT).unwrap().is_none());
+}
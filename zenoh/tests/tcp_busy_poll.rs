@@ -0,0 +1,92 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Wait};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn config(mode: WhatAmI, listen: Option<&str>, connect: Option<&str>) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    if let Some(endpoint) = listen {
+        config
+            .listen
+            .endpoints
+            .set(vec![endpoint.parse().unwrap()])
+            .unwrap();
+    }
+    if let Some(endpoint) = connect {
+        config
+            .connect
+            .endpoints
+            .set(vec![endpoint.parse().unwrap()])
+            .unwrap();
+    }
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+#[test]
+fn tcp_busy_poll() {
+    zenoh_util::init_log_from_env_or("error");
+    // Both the accepted and the established links are busy-polled
+    let router = zenoh::open(config(
+        WhatAmI::Router,
+        Some("tcp/127.0.0.1:38331#busy_poll=true"),
+        None,
+    ))
+    .wait()
+    .unwrap();
+    let client = zenoh::open(config(
+        WhatAmI::Client,
+        None,
+        Some("tcp/127.0.0.1:38331#busy_poll=true"),
+    ))
+    .wait()
+    .unwrap();
+
+    let subscriber = router.declare_subscriber("test/busy_poll").wait().unwrap();
+    let client_subscriber = client
+        .declare_subscriber("test/busy_poll/reply")
+        .wait()
+        .unwrap();
+    for i in 0..10 {
+        client.put("test/busy_poll", i.to_string()).wait().unwrap();
+        let sample = subscriber.recv_timeout(TIMEOUT).unwrap().unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), i.to_string());
+        router
+            .put("test/busy_poll/reply", i.to_string())
+            .wait()
+            .unwrap();
+        let sample = client_subscriber.recv_timeout(TIMEOUT).unwrap().unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), i.to_string());
+    }
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn tcp_busy_poll_invalid() {
+    zenoh_util::init_log_from_env_or("error");
+    assert!(zenoh::open(config(
+        WhatAmI::Router,
+        Some("tcp/127.0.0.1:38332#busy_poll=maybe"),
+        None,
+    ))
+    .wait()
+    .is_err());
+}
@@ -0,0 +1,74 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(unix, feature = "internal_config"))]
+use std::{os::unix::io::IntoRawFd, time::Duration};
+
+use zenoh::{config::WhatAmI, Config, Wait};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn router_config(endpoint: String) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![endpoint.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+#[test]
+fn tcp_listen_fd() {
+    zenoh_util::init_log_from_env_or("error");
+    // The socket is bound and listening before the router starts, as with socket activation
+    let socket = std::net::TcpListener::bind("127.0.0.1:38309").unwrap();
+    let fd = socket.into_raw_fd();
+    let router = zenoh::open(router_config(format!("tcp/127.0.0.1:38309#listen_fd={fd}")))
+        .wait()
+        .unwrap();
+    let subscriber = router.declare_subscriber("test/listen_fd").wait().unwrap();
+
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec!["tcp/127.0.0.1:38309".parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let client = zenoh::open(config).wait().unwrap();
+    client.put("test/listen_fd", "activated").wait().unwrap();
+    let sample = subscriber.recv_timeout(TIMEOUT).unwrap().unwrap();
+    assert_eq!(
+        sample.payload().try_to_string().unwrap().as_ref(),
+        "activated"
+    );
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
+
+#[test]
+fn tcp_listen_fd_invalid() {
+    zenoh_util::init_log_from_env_or("error");
+    let file = std::fs::File::open(std::env::current_exe().unwrap()).unwrap();
+    let fd = file.into_raw_fd();
+    assert!(
+        zenoh::open(router_config(format!("tcp/127.0.0.1:38310#listen_fd={fd}")))
+            .wait()
+            .is_err()
+    );
+}
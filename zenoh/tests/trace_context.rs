@@ -0,0 +1,73 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{config::Config, sample::TraceContext, Wait};
+
+const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+#[test]
+fn trace_context_pubsub() {
+    let zenoh = zenoh::open(Config::default()).wait().unwrap();
+    let trace_context: TraceContext = TRACEPARENT.parse().unwrap();
+    let subscriber = zenoh
+        .declare_subscriber("test/trace_context")
+        .wait()
+        .unwrap();
+
+    zenoh
+        .put("test/trace_context", "traced")
+        .trace_context(trace_context)
+        .wait()
+        .unwrap();
+    let sample = subscriber
+        .recv_timeout(Duration::from_secs(1))
+        .unwrap()
+        .unwrap();
+    assert_eq!(sample.trace_context(), Some(&trace_context));
+
+    zenoh.put("test/trace_context", "untraced").wait().unwrap();
+    let sample = subscriber
+        .recv_timeout(Duration::from_secs(1))
+        .unwrap()
+        .unwrap();
+    assert_eq!(sample.trace_context(), None);
+}
+
+#[test]
+fn trace_context_queries() {
+    let zenoh = zenoh::open(Config::default()).wait().unwrap();
+    let trace_context: TraceContext = TRACEPARENT.parse().unwrap();
+    let _queryable = zenoh
+        .declare_queryable("test/trace_context")
+        .callback(move |query| {
+            assert_eq!(query.trace_context(), Some(&trace_context));
+            query
+                .reply(query.key_expr().clone(), "reply")
+                .wait()
+                .unwrap();
+        })
+        .wait()
+        .unwrap();
+
+    let replies = zenoh
+        .get("test/trace_context")
+        .trace_context(trace_context)
+        .wait()
+        .unwrap();
+    let reply = replies.recv().unwrap();
+    let sample = reply.result().unwrap();
+    assert_eq!(sample.trace_context(), Some(&trace_context));
+}
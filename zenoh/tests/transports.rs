@@ -0,0 +1,67 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(feature = "unstable", feature = "internal_config"))]
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config, Session, Wait};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn open(mode: WhatAmI, locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    let endpoints = if mode == WhatAmI::Router {
+        &mut config.listen.endpoints
+    } else {
+        &mut config.connect.endpoints
+    };
+    endpoints.set(vec![locator.parse().unwrap()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    zenoh::open(config).wait().unwrap()
+}
+
+#[test]
+fn transports_list_and_close() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:38311";
+    let router = open(WhatAmI::Router, locator);
+    let client = open(WhatAmI::Client, locator);
+    std::thread::sleep(SLEEP);
+
+    let transports: Vec<_> = router.info().transports().wait().collect();
+    assert_eq!(transports.len(), 1);
+    let transport = &transports[0];
+    assert_eq!(transport.zid, client.zid());
+    assert_eq!(transport.whatami, WhatAmI::Client);
+    assert_eq!(transport.links.len(), 1);
+    assert_eq!(transport.links[0].src.to_string(), locator);
+    assert!(transport.links[0].is_streamed);
+
+    let transports: Vec<_> = client.info().transports().wait().collect();
+    assert_eq!(transports.len(), 1);
+    assert_eq!(transports[0].zid, router.zid());
+    assert_eq!(transports[0].links[0].dst.to_string(), locator);
+
+    // The client is evicted
+    router.info().close_transport(client.zid()).wait().unwrap();
+    assert!(router
+        .info()
+        .transports()
+        .wait()
+        .all(|t| t.zid != client.zid()));
+    assert!(router.info().close_transport(client.zid()).wait().is_err());
+
+    client.close().wait().unwrap();
+    router.close().wait().unwrap();
+}
@@ -11,14 +11,17 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+use std::time::Duration;
+
 use clap::Parser;
-use futures::future;
 use git_version::git_version;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-use zenoh::{config::WhatAmI, Config, Result};
+use zenoh::{config::WhatAmI, Config};
 use zenoh_config::{EndPoint, ModeDependentValue, PermissionsConf};
 use zenoh_util::LibSearchDirs;
 
+#[cfg(unix)]
+mod systemd;
+
 const GIT_VERSION: &str = git_version!(prefix = "v", cargo_prefix = "v");
 
 lazy_static::lazy_static!(
@@ -29,6 +32,8 @@ lazy_static::lazy_static!(
 #[command(version=GIT_VERSION, long_version=LONG_VERSION.as_str(), about="The zenoh router")]
 struct Args {
     /// The configuration file. Currently, this file must be a valid JSON5 or YAML file.
+    /// Any configuration field can be overridden with a `ZENOH_` environment variable named after its path,
+    /// e.g. `ZENOH_CONNECT_ENDPOINTS` for `connect/endpoints`. Command line options take precedence over both.
     #[arg(short, long, value_name = "PATH")]
     config: Option<String>,
     /// Locators on which this router will listen for incoming sessions. Repeat this option to open several listeners.
@@ -71,6 +76,20 @@ struct Args {
     /// - `--cfg='plugins/storage_manager/storages/demo:{key_expr:"demo/example/**",volume:"memory"}'`
     #[arg(long)]
     cfg: Vec<String>,
+    /// Watch the configuration file and re-apply its changes live where supported.
+    /// Changes which require a restart are reported in the logs.
+    #[arg(long, requires = "config")]
+    watch_config: bool,
+    /// Reject configuration files with unknown fields, and report the path of every invalid field.
+    #[arg(long, requires = "config")]
+    strict_config: bool,
+    /// Print the JSON schema of the configuration and exit.
+    #[arg(long)]
+    config_schema: bool,
+    /// Append the network messages exchanged with the remote nodes to the given capture file.
+    /// The capture can be read and replayed with the `zenoh::capture` API.
+    #[arg(long, value_name = "PATH")]
+    capture: Option<String>,
     /// Configure the read and/or write permissions on the admin space. Default is read only.
     #[arg(long, value_name = "[r|w|rw|none]")]
     adminspace_permissions: Option<String>,
@@ -82,15 +101,20 @@ fn main() {
         .build()
         .unwrap()
         .block_on(async {
-            init_logging().unwrap();
+            let args = Args::parse();
+            if args.config_schema {
+                println!("{:#}", Config::json_schema());
+                return;
+            }
+
+            zenoh::init_log_from_env_or("z=info");
 
             tracing::info!("zenohd {}", *LONG_VERSION);
 
-            let args = Args::parse();
             let config = config_from_args(&args);
             tracing::info!("Initial conf: {}", &config);
 
-            let _session = match zenoh::open(config).await {
+            let session = match zenoh::open(config).await {
                 Ok(runtime) => runtime,
                 Err(e) => {
                     println!("{e}. Exiting...");
@@ -98,7 +122,28 @@ fn main() {
                 }
             };
 
-            future::pending::<()>().await;
+            // All the listeners are opened and the plugins are started
+            #[cfg(unix)]
+            {
+                systemd::notify("READY=1");
+                if let Some(interval) = systemd::watchdog_interval() {
+                    let session = session.clone();
+                    tokio::spawn(async move {
+                        while !session.is_closed() {
+                            systemd::notify("WATCHDOG=1");
+                            tokio::time::sleep(interval).await;
+                        }
+                    });
+                }
+            }
+
+            // The session is closed once the router has been drained through the admin space
+            while !session.is_closed() {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            #[cfg(unix)]
+            systemd::notify("STOPPING=1");
+            tracing::info!("Session closed. Exiting...");
         });
 }
 
@@ -107,14 +152,27 @@ fn config_from_args(args: &Args) -> Config {
         .config
         .as_ref()
         .map_or_else(Config::default, |conf_file| {
-            Config::from_file(conf_file).unwrap()
+            let config = if args.strict_config {
+                Config::from_file_strict(conf_file)
+            } else {
+                Config::from_file(conf_file)
+            };
+            config.unwrap_or_else(|e| {
+                println!("{e}. Exiting...");
+                std::process::exit(-1);
+            })
         });
+    // The environment overrides the configuration file, the command line overrides both
+    if let Err(e) = config.apply_env_overlay() {
+        println!("{e}. Exiting...");
+        std::process::exit(-1);
+    }
 
     if config.mode().is_none() {
         config.set_mode(Some(WhatAmI::Router)).unwrap();
     }
     if let Some(id) = &args.id {
-        config.set_id(id.parse().unwrap()).unwrap();
+        config.set_id(Some(id.parse().unwrap())).unwrap();
     }
     // apply '--rest-http-port' to config only if explicitly set (overwriting config)
     if args.rest_http_port.is_some() {
@@ -128,6 +186,12 @@ fn config_from_args(args: &Args) -> Config {
                 .unwrap();
         }
     }
+    if args.watch_config {
+        config.config_watch.set_file(args.config.clone()).unwrap();
+    }
+    if args.capture.is_some() {
+        config.capture.set_file(args.capture.clone()).unwrap();
+    }
     config.adminspace.set_enabled(true).unwrap();
     config.plugins_loading.set_enabled(true).unwrap();
     if !args.plugin_search_dir.is_empty() {
@@ -189,6 +253,8 @@ fn config_from_args(args: &Args) -> Config {
             )
             .unwrap();
     }
+    #[cfg(unix)]
+    systemd::listen_on_activated_sockets(&mut config);
     if args.no_timestamp {
         config
             .timestamping
@@ -266,23 +332,6 @@ fn config_from_args(args: &Args) -> Config {
     config
 }
 
-fn init_logging() -> Result<()> {
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("z=info"));
-
-    let fmt_layer = tracing_subscriber::fmt::Layer::new()
-        .with_thread_ids(true)
-        .with_thread_names(true)
-        .with_level(true)
-        .with_target(true);
-
-    let tracing_sub = tracing_subscriber::registry()
-        .with(env_filter)
-        .with(fmt_layer);
-
-    tracing_sub.init();
-    Ok(())
-}
-
 #[test]
 #[cfg(feature = "default")]
 fn test_default_features() {
@@ -0,0 +1,156 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The integration with the systemd service manager: socket activation, and the notification of
+//! the readiness and the liveness of the router.
+//!
+//! See `sd_listen_fds(3)` and `sd_notify(3)` for the protocols.
+use std::{
+    mem::ManuallyDrop,
+    net::TcpListener,
+    os::unix::{
+        io::{FromRawFd, RawFd},
+        net::UnixDatagram,
+    },
+    time::Duration,
+};
+
+use zenoh::{config::WhatAmI, Config};
+use zenoh_config::{EndPoint, ModeDependent};
+
+// The first file descriptor passed by the service manager
+const LISTEN_FDS_START: RawFd = 3;
+
+// Returns the value of the environment variable `name`, unless the `pid` environment variable
+// tells it is meant for another process
+fn var_for_process(name: &str, pid: &str) -> Option<String> {
+    let value = std::env::var(name).ok()?;
+    match std::env::var(pid) {
+        Ok(pid) if pid.parse::<u32>().ok() != Some(std::process::id()) => None,
+        _ => Some(value),
+    }
+}
+
+/// Listens on the TCP sockets passed by the service manager, instead of the TCP endpoints of the
+/// configuration with the same ports.
+pub(crate) fn listen_on_activated_sockets(config: &mut Config) {
+    let activated = activated_endpoints();
+    if activated.is_empty() {
+        return;
+    }
+    let port = |e: &EndPoint| {
+        let address = e.address();
+        let port = address
+            .as_str()
+            .rsplit_once(':')
+            .map(|(_, p)| p.to_string());
+        port.filter(|_| e.protocol().as_str() == "tcp")
+    };
+    let ports: Vec<_> = activated.iter().filter_map(port).collect();
+    let mode = config.mode().unwrap_or(WhatAmI::Router);
+    let mut endpoints = config
+        .listen
+        .endpoints
+        .get(mode)
+        .cloned()
+        .unwrap_or_default();
+    endpoints.retain(|e| port(e).map_or(true, |p| !ports.contains(&p)));
+    tracing::info!(
+        "Listening on the sockets passed by systemd: {:?}",
+        activated
+    );
+    endpoints.extend(activated);
+    config.listen.endpoints.set(endpoints).unwrap();
+}
+
+// Returns the endpoints of the TCP sockets passed by the service manager, which the listeners
+// adopt instead of binding new ones
+fn activated_endpoints() -> Vec<EndPoint> {
+    let count = var_for_process("LISTEN_FDS", "LISTEN_PID").and_then(|n| n.parse::<RawFd>().ok());
+    // The sockets are not passed to the processes spawned by the router
+    std::env::remove_var("LISTEN_PID");
+    std::env::remove_var("LISTEN_FDS");
+    std::env::remove_var("LISTEN_FDNAMES");
+    let Some(count) = count else {
+        return vec![];
+    };
+    (LISTEN_FDS_START..LISTEN_FDS_START + count)
+        .filter_map(|fd| {
+            // SAFETY: the socket is only inspected, it is owned by the listener adopting it
+            let socket = ManuallyDrop::new(unsafe { TcpListener::from_raw_fd(fd) });
+            match socket.local_addr() {
+                Ok(addr) => match format!("tcp/{addr}#listen_fd={fd}").parse() {
+                    Ok(endpoint) => Some(endpoint),
+                    Err(e) => {
+                        tracing::warn!("Ignoring socket {} passed by systemd: {}", fd, e);
+                        None
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!("Ignoring non TCP socket {} passed by systemd: {}", fd, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Sends `state` (e.g. `READY=1`) to the service manager, if it expects notifications.
+pub(crate) fn notify(state: &str) {
+    let Ok(path) = std::env::var("NOTIFY_SOCKET") else {
+        return;
+    };
+    if let Err(e) = notify_to(&path, state) {
+        tracing::warn!("Unable to notify '{}' to systemd: {}", state, e);
+    }
+}
+
+fn notify_to(path: &str, state: &str) -> std::io::Result<usize> {
+    let socket = UnixDatagram::unbound()?;
+    match path.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+            socket.send_to_addr(state.as_bytes(), &SocketAddr::from_abstract_name(name)?)
+        }
+        _ => socket.send_to(state.as_bytes(), path),
+    }
+}
+
+/// Returns the interval at which the router must notify the service manager that it is alive,
+/// if the watchdog is enabled.
+pub(crate) fn watchdog_interval() -> Option<Duration> {
+    let usec = var_for_process("WATCHDOG_USEC", "WATCHDOG_PID")?
+        .parse::<u64>()
+        .ok()?;
+    // Notify twice per watchdog timeout, as recommended by sd_watchdog_enabled(3)
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn systemd_notify() {
+        let path = std::env::temp_dir().join(format!("zenohd-notify-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let receiver = UnixDatagram::bind(&path).unwrap();
+        notify_to(path.to_str().unwrap(), "READY=1").unwrap();
+        let mut buf = [0u8; 64];
+        let n = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+        let _ = std::fs::remove_file(&path);
+    }
+}